@@ -4,6 +4,30 @@ use crate::{
 
 const N: usize = 1 << 10;
 
+/// If a lookup walks more than this many points within one bucket, then the
+/// buckets are rebuilt to fit the hull's actual distribution of angles
+const MAX_CHAIN: usize = 32;
+
+/// Hulls for fewer points than this use a single bucket, i.e. a sorted
+/// linked list which is searched from its lowest point.  Below this size,
+/// a walk along the list is cheaper than allocating and maintaining the
+/// full set of buckets (see `bench_small_hull`), and it's never longer than
+/// [`MAX_CHAIN`], so the single bucket is never rebuilt.
+const SMALL: usize = MAX_CHAIN;
+
+/// Counters describing how well the hull's buckets fit its points
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChainStats {
+    /// Number of calls to [`Hull::get`]
+    pub lookups: usize,
+    /// Total number of points walked within buckets during lookups
+    pub steps: usize,
+    /// Longest walk within a single bucket
+    pub max_chain: usize,
+    /// Number of times that the buckets were rebuilt
+    pub rebuckets: usize,
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Node {
     /// Pseudo-angle of the point
@@ -33,7 +57,9 @@ struct Node {
 /// HullIndex (if present), for fast lookups without hash traversal.
 #[derive(Debug)]
 pub struct Hull {
-    buckets: [HullIndex; N],
+    /// Head of each bucket, which is either `N` buckets or (for small
+    /// hulls) one bucket holding every point
+    buckets: Vec<HullIndex>,
     data: HullVec<Node>,
 
     /// Random-access lookup of point->hull; this is only needed when doing
@@ -42,19 +68,41 @@ pub struct Hull {
 
     /// Spare slots in the [`Hull::data`] array, to keep it small
     empty: Vec<HullIndex>,
+
+    /// Lower bound of each bucket's angles, or empty if buckets are evenly
+    /// spaced.  This is only populated by [`Hull::rebucket`], so the common
+    /// case doesn't allocate.
+    bounds: Vec<f64>,
+
+    /// Number of points in the hull when it was last rebucketed, which
+    /// limits how often rebucketing happens when many points share an angle
+    /// (in which case no set of buckets can separate them)
+    rebucket_len: usize,
+
+    stats: ChainStats,
 }
 
 impl Hull {
     pub fn new(num_points: usize, constrained: bool) -> Hull {
+        let num_buckets = if num_points < SMALL { 1 } else { N };
+        Self::with_buckets(num_points, constrained, num_buckets)
+    }
+
+    fn with_buckets(num_points: usize, constrained: bool, num_buckets: usize)
+        -> Hull
+    {
         Hull {
             data: HullVec::new(),
-            buckets: [EMPTY_HULL; N],
+            buckets: vec![EMPTY_HULL; num_buckets],
             points: if constrained {
                 PointVec::of(vec![EMPTY_HULL; num_points])
             } else {
                 PointVec::new()
             },
             empty: Vec::new(),
+            bounds: Vec::new(),
+            rebucket_len: 0,
+            stats: ChainStats::default(),
         }
     }
 
@@ -82,7 +130,11 @@ impl Hull {
 
     /// For a given point, returns the HullIndex which will be split when this
     /// point is inserted.  Use `Hull::edge` to get the associated EdgeIndex.
-    pub fn get(&self, angle: f64) -> HullIndex {
+    ///
+    /// If the lookup has to walk a long chain within one bucket, then the
+    /// buckets are rebuilt afterwards (see [`Hull::rebucket`]).
+    pub fn get(&mut self, angle: f64) -> HullIndex {
+        self.stats.lookups += 1;
         let b = self.bucket(angle);
 
         // If the target bucket is empty, then we should search for the
@@ -95,7 +147,7 @@ impl Hull {
             // Find the next filled bucket, which must exist somewhere
             let mut t = b;
             while self.buckets[t] == EMPTY_HULL {
-                t = (t + 1) % N;
+                t = (t + 1) % self.buckets.len();
             }
             h = self.buckets[t];
         } else {
@@ -106,8 +158,10 @@ impl Hull {
             // that our new point, or we leave this bucket, or we're about
             // to wrap around in the same bucket.
             let start = h;
+            let mut chain = 0;
             while self.data[h].angle < angle && self.bucket_h(h) == b {
                 h = self.data[h].right;
+                chain += 1;
                 // If we've looped around, it means all points are in the same
                 // bucket *and* the new point is larger than all of them.  This
                 // means it will be inserted at the end of the bucket, and will
@@ -116,6 +170,16 @@ impl Hull {
                     break;
                 }
             }
+            self.stats.steps += chain;
+            self.stats.max_chain = self.stats.max_chain.max(chain);
+
+            // Only rebucket once the hull has doubled in size since the
+            // last time, so that points which can't be separated (because
+            // they share an angle) don't cause rebucketing on every lookup.
+            let len = self.data.len() - self.empty.len();
+            if chain > MAX_CHAIN && len >= 2 * self.rebucket_len {
+                self.rebucket();
+            }
         }
         assert!(h != EMPTY_HULL);
 
@@ -124,6 +188,49 @@ impl Hull {
         self.data[h].left
     }
 
+    /// Returns counters describing bucket chain lengths
+    pub fn chain_stats(&self) -> ChainStats {
+        self.stats
+    }
+
+    /// Rebuilds the buckets so that each one holds roughly the same number of
+    /// points, which keeps lookups fast when the points' angles are bunched
+    /// together (rather than spread evenly, as the default buckets assume).
+    ///
+    /// This only changes which point heads each bucket; the linked list is
+    /// untouched, so `HullIndex` values (e.g. hints for [`Hull::insert`])
+    /// remain valid.
+    pub fn rebucket(&mut self) {
+        // Walk the linked list from its lowest point, collecting the angles
+        // (which are in increasing order)
+        let start = self.start();
+        let mut order = vec![start];
+        let mut h = self.data[start].right;
+        while h != start {
+            order.push(h);
+            h = self.data[h].right;
+        }
+
+        // Pick bucket boundaries at evenly-spaced ranks, so that the
+        // mapping from angle to bucket is still monotonic
+        let n = order.len();
+        let num_buckets = self.buckets.len();
+        self.bounds = (0..num_buckets)
+            .map(|b| self.data[order[b * n / num_buckets]].angle)
+            .collect();
+        self.bounds[0] = f64::NEG_INFINITY;
+
+        self.buckets.fill(EMPTY_HULL);
+        for h in order {
+            let b = self.bucket_h(h);
+            if self.buckets[b] == EMPTY_HULL {
+                self.buckets[b] = h;
+            }
+        }
+        self.rebucket_len = n;
+        self.stats.rebuckets += 1;
+    }
+
     pub fn start(&self) -> HullIndex {
         self.buckets.iter()
             .filter(|b| **b != EMPTY_HULL)
@@ -139,43 +246,85 @@ impl Hull {
     /// # Panics
     /// Panics if the invariants are broken.
     pub fn check(&self) {
-        // Find the first non-empty bucket to use as our starting point for
-        // walking around the hull's linked list.
-        let point = self.buckets.iter()
-            .filter(|b| **b != EMPTY_HULL)
-            .copied()
-            .next();
-        assert!(point.is_some());
+        if let Err(e) = self.validate() {
+            panic!("Invalid hull: {}", e);
+        }
+    }
+
+    /// Checks invariants of the data structure, returning a description of
+    /// the first one which is broken:
+    /// - the live points form a single loop, stitched together in both
+    ///   directions, which doesn't include any spare slots
+    /// - pseudo-angles never decrease along the loop, starting from the
+    ///   head of the lowest non-empty bucket
+    /// - each bucket's head is its lowest point, and buckets without points
+    ///   are empty
+    ///
+    /// This walks the whole hull and every bucket, so it's meant for tests
+    /// and debug builds (e.g. `debug_assert!(hull.validate().is_ok())`),
+    /// particularly after erasing and reinserting points, since a broken
+    /// bucket silently sends later lookups to the wrong place.
+    pub fn validate(&self) -> Result<(), String> {
+        let start = match self.buckets.iter().find(|b| **b != EMPTY_HULL) {
+            Some(h) => *h,
+            None => return Err("hull has no points".to_owned()),
+        };
 
-        let start = point.unwrap();
-        assert!(self.buckets[self.bucket_h(start)] == start);
+        let mut spare = vec![false; self.data.len()];
+        for h in &self.empty {
+            if spare[h.0 as usize] {
+                return Err(format!("{:?} is spare more than once", h));
+            }
+            spare[h.0 as usize] = true;
+        }
 
-        let mut index = start;
-        // Walk around the hull, checking that position is strictly increasing,
-        // edges are correctly stitched together, and buckets are correct.
+        // Walk around the loop, checking stitching and angles, and find the
+        // head which each bucket should have
+        let mut heads = vec![EMPTY_HULL; self.buckets.len()];
+        let mut seen = vec![false; self.data.len()];
+        let mut h = start;
         loop {
-            // Assert that the list is correctly stitched together
-            let next = self.data[index].right;
-            assert!(index == self.data[next].left);
-
-            // If this is the first item in a new bucket, it should be at the
-            // head of the bucket's list.
-            let my_bucket = self.bucket_h(index);
-            let next_bucket = self.bucket_h(next);
-            if next_bucket != my_bucket {
-                assert!(self.buckets[next_bucket] == next);
+            if spare[h.0 as usize] {
+                return Err(format!("spare slot {:?} is in the hull", h));
+            } else if seen[h.0 as usize] {
+                return Err(format!("hull loops back to {:?}", h));
             }
+            seen[h.0 as usize] = true;
 
+            let next = self.data[h].right;
+            if next == EMPTY_HULL || self.data[next].left != h {
+                return Err(format!("{:?} is not stitched to {:?}", h, next));
+            }
+            let b = self.bucket_h(h);
+            if heads[b] == EMPTY_HULL {
+                heads[b] = h;
+            }
             if next == start {
                 break;
-            } else {
-                // Assert that position are increasing in the list
-                let my_position = self.data[index].angle;
-                let next_position = self.data[next].angle;
-                assert!(next_position >= my_position);
-                index = next;
+            } else if self.data[next].angle < self.data[h].angle {
+                return Err(format!("angle decreases from {:?} to {:?}",
+                                   h, next));
+            }
+            h = next;
+        }
+
+        let live = seen.iter().filter(|s| **s).count();
+        if live != self.len() {
+            return Err(format!("{} points are neither live nor spare",
+                               self.len() - live));
+        }
+        for (b, (head, expected)) in self.buckets.iter().zip(&heads).enumerate() {
+            if head != expected {
+                return Err(format!("bucket {} has head {:?} instead of {:?}",
+                                   b, head, expected));
             }
         }
+        Ok(())
+    }
+
+    /// Returns the number of points in the hull
+    pub fn len(&self) -> usize {
+        self.data.len() - self.empty.len()
     }
 
     pub fn left_hull(&self, h: HullIndex) -> HullIndex {
@@ -213,7 +362,8 @@ impl Hull {
     pub fn insert_bare(&mut self, angle: f64, point: PointIndex, e: EdgeIndex)
         -> HullIndex
     {
-        self.insert(self.get(angle), angle, point, e)
+        let left = self.get(angle);
+        self.insert(left, angle, point, e)
     }
 
     /// Insert a new Point-Edge pair into the hull, using a hint to save time
@@ -233,11 +383,13 @@ impl Hull {
             })
         };
 
-        // If the target bucket is empty, or the given point is below the first
-        // item in the target bucket, then it becomes the bucket's head
+        // If the target bucket is empty, or the given point is inserted before
+        // the first item in the target bucket, then it becomes the bucket's
+        // head.  A point which ties with the head is inserted before it, so
+        // it takes over as head too.
         let b = self.bucket(angle);
         if self.buckets[b] == EMPTY_HULL || (self.buckets[b] == right &&
-                                             angle < self.data[right].angle)
+                                             angle <= self.data[right].angle)
         {
             self.buckets[b] = h;
         }
@@ -311,6 +463,207 @@ impl Hull {
 
     /// Looks up what bucket a given pseudo-angle will fall into.
     pub fn bucket(&self, angle: f64) -> usize {
-        (angle * (self.buckets.len() as f64 - 1.0)).round() as usize
+        if self.bounds.is_empty() {
+            (angle * (self.buckets.len() as f64 - 1.0)).round() as usize
+        } else {
+            self.bounds.partition_point(|b| *b <= angle) - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    /// Builds a hull from a set of angles, using each point's index as its
+    /// edge index too
+    fn hull(angles: &[f64]) -> Hull {
+        let mut hull = Hull::new(1000, true);
+        hull.initialize(PointIndex::new(0), angles[0], EdgeIndex::new(0));
+        for (i, a) in angles.iter().enumerate().skip(1) {
+            hull.insert_bare(*a, PointIndex::new(i), EdgeIndex::new(i));
+            hull.validate().unwrap();
+        }
+        hull
+    }
+
+    /// Returns the angles of every point, walking around the hull
+    fn angles(hull: &Hull) -> Vec<f64> {
+        hull.values().map(|e| hull.data[hull.points[PointIndex::new(e.0 as usize)]].angle)
+            .collect()
+    }
+
+    #[test]
+    fn bucket_empties_and_refills() {
+        // Three points share one bucket, with a neighbour on either side
+        let b = 0.5;
+        let step = 0.1 / N as f64;
+        let mut hull = hull(&[0.25, b, b + step, b + 2.0 * step, 0.75]);
+        let bucket = hull.bucket(b);
+        assert_eq!(hull.buckets[bucket], hull.index_of(PointIndex::new(1)));
+
+        // Erasing the head hands the bucket on to the next point in it
+        hull.erase(hull.index_of(PointIndex::new(1)));
+        hull.validate().unwrap();
+        assert_eq!(hull.buckets[bucket], hull.index_of(PointIndex::new(2)));
+
+        // Erasing the rest leaves the bucket empty
+        hull.erase(hull.index_of(PointIndex::new(3)));
+        hull.validate().unwrap();
+        hull.erase(hull.index_of(PointIndex::new(2)));
+        hull.validate().unwrap();
+        assert_eq!(hull.buckets[bucket], EMPTY_HULL);
+        assert_eq!(hull.len(), 2);
+
+        // Refilling it, out of order, reuses the spare slots and puts the
+        // lowest point at the head again
+        for (i, a) in [(3, b + 2.0 * step), (1, b), (2, b + step)] {
+            hull.insert_bare(a, PointIndex::new(i), EdgeIndex::new(i));
+            hull.validate().unwrap();
+        }
+        assert_eq!(hull.data.len(), 5);
+        assert_eq!(hull.buckets[bucket], hull.index_of(PointIndex::new(1)));
+        assert_eq!(angles(&hull),
+                   [0.25, b, b + step, b + 2.0 * step, 0.75]);
+
+        // Emptying the lowest bucket moves the start of the hull
+        hull.erase(hull.index_of(PointIndex::new(0)));
+        hull.validate().unwrap();
+        assert_eq!(hull.start(), hull.index_of(PointIndex::new(1)));
+        hull.insert_bare(0.25, PointIndex::new(0), EdgeIndex::new(0));
+        hull.validate().unwrap();
+        assert_eq!(hull.start(), hull.index_of(PointIndex::new(0)));
+    }
+
+    #[test]
+    fn erase_reinsert_stress() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(12345);
+        for round in 0..20 {
+            // Alternate between angles which are spread out, bunched into a
+            // few buckets (so that buckets are emptied and refilled often),
+            // bunched into one (so that the hull is rebucketed), and drawn
+            // from a handful of values (so that many points share an angle)
+            let angle = |rng: &mut rand_chacha::ChaCha8Rng| match round % 4 {
+                0 => rng.gen_range(0.0..1.0),
+                1 => 0.5 + rng.gen_range(0..8) as f64 / N as f64
+                         + rng.gen_range(0.0..0.1) / N as f64,
+                2 => 0.25 + rng.gen_range(0.0..1e-6),
+                _ => rng.gen_range(0..16) as f64 / 64.0,
+            };
+            let first = angle(&mut rng);
+            let mut hull = hull(&[first]);
+            let mut live = vec![(first, 0)];
+            let mut spare: Vec<usize> = (1..200).collect();
+
+            for _ in 0..2000 {
+                if live.len() > 1 && (spare.is_empty() || rng.gen_bool(0.5)) {
+                    let (_, p) = live.swap_remove(rng.gen_range(0..live.len()));
+                    hull.erase(hull.index_of(PointIndex::new(p)));
+                    spare.push(p);
+                } else {
+                    let p = spare.swap_remove(rng.gen_range(0..spare.len()));
+                    let a = angle(&mut rng);
+                    hull.insert_bare(a, PointIndex::new(p), EdgeIndex::new(p));
+                    live.push((a, p));
+                }
+                hull.validate().unwrap();
+                assert_eq!(hull.len(), live.len());
+            }
+            let mut expected: Vec<f64> = live.iter().map(|(a, _)| *a).collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(angles(&hull), expected);
+        }
+    }
+
+    /// Fills a hull with random points, then erases and reinserts them,
+    /// returning the result of every lookup
+    fn churn(hull: &mut Hull, num_points: usize, seed: u64, check: bool)
+        -> Vec<HullIndex>
+    {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        let mut lookups = Vec::new();
+        let mut live = vec![0];
+        let mut spare: Vec<usize> = (1..num_points).collect();
+        hull.initialize(PointIndex::new(0), rng.gen_range(0.0..1.0),
+                        EdgeIndex::new(0));
+        for i in 0..4 * num_points {
+            if i >= num_points && live.len() > 1
+                && (spare.is_empty() || rng.gen_bool(0.5))
+            {
+                let p = live.swap_remove(rng.gen_range(0..live.len()));
+                hull.erase(hull.index_of(PointIndex::new(p)));
+                spare.push(p);
+            } else if !spare.is_empty() {
+                let p = spare.swap_remove(rng.gen_range(0..spare.len()));
+                let a = rng.gen_range(0.0..1.0);
+                let left = hull.get(a);
+                hull.insert(left, a, PointIndex::new(p), EdgeIndex::new(p));
+                live.push(p);
+                lookups.push(left);
+            }
+            if check {
+                hull.validate().unwrap();
+            }
+        }
+        lookups
+    }
+
+    #[test]
+    fn small_hull_matches_bucketed() {
+        assert_eq!(Hull::new(SMALL - 1, false).buckets.len(), 1);
+        assert_eq!(Hull::new(SMALL, false).buckets.len(), N);
+        for n in [3, 10, SMALL - 1, 200] {
+            let mut small = Hull::with_buckets(n, true, 1);
+            let mut bucketed = Hull::with_buckets(n, true, N);
+            assert_eq!(churn(&mut small, n, 12345, true),
+                       churn(&mut bucketed, n, 12345, true));
+            assert_eq!(angles(&small), angles(&bucketed));
+        }
+    }
+
+    /// Compares the single-bucket and bucketed hulls at various sizes, which
+    /// is how [`SMALL`] was picked.  Run it with
+    /// `cargo test --release -p cdt bench_small_hull -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_small_hull() {
+        for n in [4, 8, 16, 32, 64, 128, 256] {
+            let reps = 200_000 / n;
+            let time = |num_buckets| {
+                let now = std::time::Instant::now();
+                for seed in 0..reps {
+                    let mut hull = Hull::with_buckets(n, true, num_buckets);
+                    churn(&mut hull, n, seed as u64, false);
+                }
+                now.elapsed().as_secs_f64() * 1e9 / reps as f64
+            };
+            let small = time(1);
+            let bucketed = time(N);
+            println!("{:>4} points: {:>9.0} ns vs {:>9.0} ns bucketed",
+                     n, small, bucketed);
+        }
+    }
+
+    #[test]
+    fn validate_catches_corruption() {
+        let mut hull = hull(&[0.1, 0.5, 0.9]);
+        let h = hull.index_of(PointIndex::new(1));
+        let b = hull.bucket_h(h);
+
+        // A bucket which lost its head
+        hull.buckets[b] = EMPTY_HULL;
+        assert!(hull.validate().is_err());
+        hull.buckets[b] = h;
+        hull.validate().unwrap();
+
+        // A point which is out of order
+        hull.data[h].angle = 0.95;
+        assert!(hull.validate().is_err());
+        hull.data[h].angle = 0.5;
+
+        // A slot which is both live and spare
+        hull.empty.push(h);
+        assert!(hull.validate().is_err());
     }
 }
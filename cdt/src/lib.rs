@@ -32,6 +32,20 @@ for t in triangles {
 }
 ```
 
+## Polygon utilities
+The same exact predicates are used to locate points relative to polygons
+```rust
+use cdt::{Location, Orientation};
+let outer = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+let hole = [(0.2, 0.2), (0.2, 0.8), (0.8, 0.8), (0.8, 0.2)];
+assert_eq!(cdt::orientation(&outer), Orientation::CounterClockwise);
+assert_eq!(cdt::orientation(&hole), Orientation::Clockwise);
+assert_eq!(cdt::locate_with_holes(&outer, &[hole], (0.1, 0.5)),
+           Location::Inside);
+assert_eq!(cdt::locate_with_holes(&outer, &[hole], (0.5, 0.5)),
+           Location::Outside);
+```
+
 # Crate features
 By default, the library uses `u32` indexes for internal data structures,
 to improve performance.  If you are planning to triangulate more than 500M
@@ -44,8 +58,14 @@ pub(crate) mod predicates;
 pub(crate) mod half;
 pub(crate) mod hull;
 pub(crate) mod indexes;
+pub(crate) mod polygon;
 pub(crate) mod triangulate;
 pub use triangulate::Triangulation;
+pub use hull::ChainStats;
+pub use polygon::{
+    locate, locate_with_holes, orientation, signed_area, winding_number,
+    Location, Orientation,
+};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Common types for points and strongly-typed vectors
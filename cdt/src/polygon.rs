@@ -0,0 +1,209 @@
+//! 2D polygon utilities, built on the same exact predicates as the
+//! triangulation.
+//!
+//! Polygons are given as rings of points.  A ring may be closed (with its
+//! last point repeating the first, as in contours passed to
+//! [`triangulate_contours`](crate::triangulate_contours)) or open; the
+//! closing edge is implied either way.
+use crate::{Point, predicates::orient2d};
+
+/// The winding direction of a polygon
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Orientation {
+    /// Counter-clockwise, i.e. positive signed area
+    CounterClockwise,
+    /// Clockwise, i.e. negative signed area
+    Clockwise,
+    /// The polygon has no area (e.g. every point is on one line)
+    Degenerate,
+}
+
+/// Where a point lies relative to a polygon
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Location {
+    /// Strictly inside the polygon
+    Inside,
+    /// Strictly outside the polygon
+    Outside,
+    /// Exactly on one of the polygon's edges or vertices
+    Boundary,
+}
+
+/// Drops the closing point of a ring, if it's there
+fn ring(polygon: &[Point]) -> &[Point] {
+    match polygon {
+        [first, rest @ .., last] if !rest.is_empty() && first == last =>
+            &polygon[..polygon.len() - 1],
+        _ => polygon,
+    }
+}
+
+/// Iterates over the edges of a ring, including the closing edge
+fn edges(polygon: &[Point]) -> impl Iterator<Item=(Point, Point)> + '_ {
+    let r = ring(polygon);
+    r.iter().zip(r.iter().cycle().skip(1)).map(|(a, b)| (*a, *b))
+}
+
+/// Returns the signed area of a polygon, which is positive if it winds
+/// counter-clockwise.  This isn't exact, but it's measured relative to the
+/// polygon's first point, which avoids most cancellation for polygons which
+/// are far from the origin.
+pub fn signed_area(polygon: &[Point]) -> f64 {
+    let r = ring(polygon);
+    let o = match r.first() {
+        Some(o) => *o,
+        None => return 0.0,
+    };
+    let sum: f64 = edges(r)
+        .map(|(a, b)| (a.0 - o.0) * (b.1 - o.1) - (b.0 - o.0) * (a.1 - o.1))
+        .sum();
+    sum / 2.0
+}
+
+/// Returns the winding direction of a simple polygon.
+///
+/// This is decided exactly, from the turn at the polygon's lowest (then
+/// leftmost) point, which is convex in any simple polygon.  If that turn is
+/// degenerate (e.g. because of repeated points), this falls back to the
+/// sign of [`signed_area`].
+pub fn orientation(polygon: &[Point]) -> Orientation {
+    let r = ring(polygon);
+    let lowest = match (0..r.len()).min_by(|&i, &j|
+        (r[i].1, r[i].0).partial_cmp(&(r[j].1, r[j].0))
+            .unwrap_or(std::cmp::Ordering::Equal))
+    {
+        Some(i) => i,
+        None => return Orientation::Degenerate,
+    };
+    // Skip over neighbours which repeat the lowest point
+    let n = r.len();
+    let prev = (1..n).map(|k| r[(lowest + n - k) % n])
+        .find(|p| *p != r[lowest]);
+    let next = (1..n).map(|k| r[(lowest + k) % n])
+        .find(|p| *p != r[lowest]);
+    let turn = match (prev, next) {
+        (Some(a), Some(c)) => orient2d(a, r[lowest], c),
+        _ => return Orientation::Degenerate,
+    };
+    let turn = if turn == 0.0 { signed_area(r) } else { turn };
+    if turn > 0.0 {
+        Orientation::CounterClockwise
+    } else if turn < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Degenerate
+    }
+}
+
+/// Returns the winding number of a polygon around a point (positive for
+/// counter-clockwise windings), or `None` if the point is on the polygon's
+/// boundary.  Every side test uses exact predicates, so points which are
+/// very close to an edge are classified correctly.
+pub fn winding_number(polygon: &[Point], p: Point) -> Option<i32> {
+    let mut w = 0;
+    for (a, b) in edges(polygon) {
+        let o = orient2d(a, b, p);
+        if o == 0.0
+            && p.0 >= a.0.min(b.0) && p.0 <= a.0.max(b.0)
+            && p.1 >= a.1.min(b.1) && p.1 <= a.1.max(b.1)
+        {
+            return None;
+        }
+        if a.1 <= p.1 {
+            if b.1 > p.1 && o > 0.0 {
+                w += 1;
+            }
+        } else if b.1 <= p.1 && o < 0.0 {
+            w -= 1;
+        }
+    }
+    Some(w)
+}
+
+/// Locates a point relative to a polygon, using the non-zero winding rule
+pub fn locate(polygon: &[Point], p: Point) -> Location {
+    match winding_number(polygon, p) {
+        None => Location::Boundary,
+        Some(0) => Location::Outside,
+        Some(_) => Location::Inside,
+    }
+}
+
+/// Locates a point relative to a polygon with holes.  A point is inside if
+/// it's inside the outer ring and outside every hole, and it's on the
+/// boundary if it's on any ring.  The rings may wind in either direction.
+pub fn locate_with_holes<H>(outer: &[Point], holes: &[H], p: Point) -> Location
+    where H: AsRef<[Point]>
+{
+    match locate(outer, p) {
+        Location::Inside => (),
+        other => return other,
+    }
+    for h in holes {
+        match locate(h.as_ref(), p) {
+            Location::Outside => (),
+            Location::Inside => return Location::Outside,
+            Location::Boundary => return Location::Boundary,
+        }
+    }
+    Location::Inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [Point; 5] = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0),
+                                (0.0, 4.0), (0.0, 0.0)];
+
+    #[test]
+    fn area_and_orientation() {
+        assert_eq!(signed_area(&SQUARE), 16.0);
+        assert_eq!(signed_area(&SQUARE[..4]), 16.0);
+        assert_eq!(orientation(&SQUARE), Orientation::CounterClockwise);
+
+        let mut cw = SQUARE;
+        cw.reverse();
+        assert_eq!(signed_area(&cw), -16.0);
+        assert_eq!(orientation(&cw), Orientation::Clockwise);
+
+        // A concave polygon, far from the origin, with a repeated point
+        let o = 1e8;
+        let l = [(o, o), (o + 2.0, o), (o + 2.0, o), (o + 2.0, o + 1.0),
+                 (o + 1.0, o + 1.0), (o + 1.0, o + 2.0), (o, o + 2.0)];
+        assert_eq!(signed_area(&l), 3.0);
+        assert_eq!(orientation(&l), Orientation::CounterClockwise);
+
+        let line = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)];
+        assert_eq!(orientation(&line), Orientation::Degenerate);
+        assert_eq!(orientation(&[]), Orientation::Degenerate);
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        assert_eq!(locate(&SQUARE, (1.0, 1.0)), Location::Inside);
+        assert_eq!(locate(&SQUARE, (5.0, 1.0)), Location::Outside);
+        assert_eq!(locate(&SQUARE, (4.0, 1.0)), Location::Boundary);
+        assert_eq!(locate(&SQUARE, (0.0, 0.0)), Location::Boundary);
+        // On the line through an edge, but beyond it
+        assert_eq!(locate(&SQUARE, (5.0, 0.0)), Location::Outside);
+        assert_eq!(winding_number(&SQUARE, (2.0, 2.0)), Some(1));
+
+        // A point one ulp from the diagonal edge
+        let tri = [(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let p = (0.5, 0.5 + f64::EPSILON / 2.0);
+        assert_eq!(locate(&tri, p), Location::Inside);
+        assert_eq!(locate(&tri, (0.5, 0.5)), Location::Boundary);
+
+        let hole = [(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)];
+        let holes = [&hole[..]];
+        assert_eq!(locate_with_holes(&SQUARE, &holes, (0.5, 0.5)),
+                   Location::Inside);
+        assert_eq!(locate_with_holes(&SQUARE, &holes, (2.0, 2.0)),
+                   Location::Outside);
+        assert_eq!(locate_with_holes(&SQUARE, &holes, (1.0, 2.0)),
+                   Location::Boundary);
+        assert_eq!(locate_with_holes(&SQUARE, &holes, (6.0, 2.0)),
+                   Location::Outside);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     contour::{Contour, ContourData},
     Error, Point,
-    half::Half, hull::Hull,
+    half::Half, hull::{ChainStats, Hull},
     indexes::{PointIndex, PointVec, EdgeIndex, HullIndex, EMPTY_EDGE},
     predicates::{acute, orient2d, in_circle, centroid, distance2, pseudo_angle},
 };
@@ -403,6 +403,12 @@ impl Triangulation {
         acute(self.points[pa], self.points[pb], self.points[pc])
     }
 
+    /// Returns counters describing how the hull's buckets have performed,
+    /// which is useful when profiling unusual point distributions
+    pub fn hull_stats(&self) -> ChainStats {
+        self.hull.chain_stats()
+    }
+
     /// Checks whether the triangulation is done
     pub fn done(&self) -> bool {
         self.next == self.points.len() + 1
@@ -1287,6 +1293,24 @@ fn min3(buf: &[(usize, f64)], points: &[(f64, f64)]) -> [usize; 3] {
 mod tests {
     use super::*;
 
+    #[test]
+    fn bunched_angles() {
+        // Hundreds of points on a tiny arc all share one hull bucket, which
+        // would make each lookup walk the whole arc without rebucketing
+        let mut pts = vec![(-150.0, -150.0), (150.0, -150.0),
+                           (150.0, 150.0), (-150.0, 150.0)];
+        const N: usize = 2000;
+        for i in 0..N {
+            let a = 1e-3 * (i as f64) / (N as f64);
+            pts.push((100.0 * a.cos(), 100.0 * a.sin()));
+        }
+        let t = Triangulation::build(&pts).expect("Could not construct");
+        t.check();
+        let stats = t.hull_stats();
+        assert!(stats.rebuckets > 0);
+        assert!(stats.steps < 16 * stats.lookups, "{:?}", stats);
+    }
+
     #[test]
     fn simple_triangle() {
         let pts = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
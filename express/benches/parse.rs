@@ -0,0 +1,104 @@
+//! Measures parser throughput.
+//!
+//! By default, this parses a synthetic ~5 MB input (the grammar fixture,
+//! repeated under different schema names), which is about the size of the
+//! AP242 long form.  To measure a real schema instead, pass its path:
+//! ```text
+//! cargo bench -p express -- path/to/ap242.exp
+//! ```
+//!
+//! Each run's median times are saved under Cargo's target directory, and
+//! the next run on the same input reports the change against them, so
+//! throughput can be tracked across commits.
+//!
+//! AST nodes are still heap-allocated rather than living in a bump arena:
+//! they're about 5% of the allocations made while parsing, and moving them
+//! would mean threading an arena through every parser and every consumer
+//! of the syntax tree.
+use std::time::{Duration, Instant};
+
+use express::parse::{parse, strip_comments_and_lower};
+
+/// Number of timed runs
+const RUNS: usize = 10;
+
+/// Approximate size of the synthetic input, in bytes
+const SYNTHETIC_SIZE: usize = 5_000_000;
+
+/// Where the previous run's results are stored
+const BASELINE: &str = concat!(env!("CARGO_TARGET_TMPDIR"), "/parse-baseline");
+
+fn synthetic() -> Vec<u8> {
+    let fixture = include_str!("../tests/fixtures/grammar.exp");
+    let mut out = String::new();
+    let mut i = 0;
+    while out.len() < SYNTHETIC_SIZE {
+        out += &fixture.replace("SCHEMA grammar_test",
+                                &format!("SCHEMA grammar_test_{}", i));
+        i += 1;
+    }
+    out.into_bytes()
+}
+
+/// Runs `f` several times, returning the sorted times and the last result
+fn time<T, F: FnMut() -> T>(mut f: F) -> (Vec<Duration>, T) {
+    let mut out = None;
+    let mut times = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let r = f();
+        times.push(start.elapsed());
+        out = Some(r);
+    }
+    times.sort_unstable();
+    (times, out.unwrap())
+}
+
+/// Loads the median times of the previous run, if it used the same input
+fn baseline(bytes: usize) -> Option<(f64, f64)> {
+    let s = std::fs::read_to_string(BASELINE).ok()?;
+    let mut words = s.split_whitespace().map(str::parse::<f64>);
+    let (b, strip, parse) = (words.next()?.ok()?,
+                             words.next()?.ok()?,
+                             words.next()?.ok()?);
+    if b as usize == bytes { Some((strip, parse)) } else { None }
+}
+
+/// Prints the median time and throughput, and the change against the
+/// previous median (if there is one).  Returns the median, in seconds.
+fn report(name: &str, bytes: usize, times: &[Duration], prev: Option<f64>)
+    -> f64
+{
+    let median = times[times.len() / 2].as_secs_f64();
+    print!("{:<8} {:>10.2?} {:>8.1} MB/s  (min {:.2?}, max {:.2?})",
+           name, times[times.len() / 2], bytes as f64 / median / 1e6,
+           times[0], times[times.len() - 1]);
+    match prev {
+        Some(p) => println!("  {:+.1}%", (median / p - 1.0) * 100.0),
+        None => println!(),
+    }
+    median
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, which isn't a path
+    let data = match std::env::args().skip(1).find(|a| !a.starts_with('-')) {
+        Some(path) => std::fs::read(&path).expect("Could not read input"),
+        None => synthetic(),
+    };
+    println!("Parsing {} bytes, median of {} runs", data.len(), RUNS);
+    let prev = baseline(data.len());
+
+    let (t, s) = time(|| strip_comments_and_lower(&data));
+    let strip = report("strip", data.len(), &t, prev.map(|p| p.0));
+
+    let (t, n) = time(|| parse(&s).expect("Could not parse input").0.len());
+    let parse = report("parse", s.len(), &t, prev.map(|p| p.1));
+    println!("({} schemas)", n);
+
+    if let Err(e) = std::fs::write(BASELINE,
+                                   format!("{} {} {}\n", data.len(), strip, parse))
+    {
+        eprintln!("Could not save results to {}: {}", BASELINE, e);
+    }
+}
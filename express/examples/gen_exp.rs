@@ -3,7 +3,12 @@ use std::io::Read;
 use std::time::SystemTime;
 
 use clap::{Arg, App};
-use express::parse::{strip_comments_and_lower, parse};
+use express::{
+    error::render_error,
+    flatten::{flatten, roots},
+    parse::parse,
+    remark::{Docs, strip_comments_keep_remarks},
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = App::new("parse_exp")
@@ -16,6 +21,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .short("q")
             .long("quiet")
             .help("disable output"))
+        .arg(Arg::with_name("docs")
+            .short("d")
+            .long("docs")
+            .help("convert remarks into doc comments"))
+        .arg(Arg::with_name("schema")
+            .short("s")
+            .long("schema")
+            .takes_value(true)
+            .help("schema to generate, if the file has more than one"))
         .arg(Arg::with_name("output")
             .takes_value(true))
         .get_matches();
@@ -27,17 +41,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     f.read_to_end(&mut buffer).expect("read ok");
 
     let start = SystemTime::now();
-    let s = strip_comments_and_lower(&buffer);
+    let (s, remarks) = strip_comments_keep_remarks(&buffer);
     let mut parsed = match parse(&s) {
         Ok(o) => o,
-        Err(e) => panic!("Failed to parse:\n{:?}", e),
+        Err(e) => panic!("Failed to parse:\n{}", render_error(&s, &e)),
     };
     let end = SystemTime::now();
     let since_the_epoch = end.duration_since(start).expect("Time went backwards");
     eprintln!("parsed in {:?}", since_the_epoch);
 
     let start = SystemTime::now();
-    let gen = express::gen::gen(&mut parsed.1)?;
+    let docs = if matches.is_present("docs") {
+        Docs::new(&s, &parsed, &remarks)
+    } else {
+        Docs::default()
+    };
+    let mut schema = if parsed.0.len() > 1 {
+        let root = match matches.value_of("schema") {
+            Some(s) => s.to_owned(),
+            None => match roots(&parsed).as_slice() {
+                [r] => r.to_string(),
+                r => return Err(format!(
+                    "Multiple top-level schemas ({}); pick one with --schema",
+                    r.join(", ")).into()),
+            },
+        };
+        flatten(parsed, &root)?
+    } else {
+        parsed.0.pop().ok_or("No schema in file")?
+    };
+    let gen = express::gen::gen_schema_with_docs(&mut schema, &docs)?;
     let end = SystemTime::now();
     let since_the_epoch = end.duration_since(start).expect("Time went backwards");
     eprintln!("generated in {:?}", since_the_epoch);
@@ -3,7 +3,16 @@ use std::io::Read;
 use std::time::SystemTime;
 
 use clap::{Arg, App};
-use express::parse::{strip_comments_and_lower, parse};
+use express::{
+    dictionary::Dictionary,
+    diff::diff,
+    dot::to_dot,
+    error::render_error,
+    parse::{strip_comments_and_lower, strip_comments_preserve_case, parse,
+            parse_recovering},
+    resolve::resolve,
+    typecheck::check,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = App::new("parse_exp")
@@ -16,6 +25,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .short("q")
             .long("quiet")
             .help("disable output"))
+        .arg(Arg::with_name("preserve-case")
+            .long("preserve-case")
+            .help("keep the original case of identifiers"))
+        .arg(Arg::with_name("resolve")
+            .long("resolve")
+            .help("report references to undefined names"))
+        .arg(Arg::with_name("typecheck")
+            .long("typecheck")
+            .help("report type errors in expressions"))
+        .arg(Arg::with_name("dot")
+            .long("dot")
+            .takes_value(true)
+            .help("write the schema structure as a Graphviz file"))
+        .arg(Arg::with_name("dictionary")
+            .long("dictionary")
+            .takes_value(true)
+            .help("write a JSON data dictionary of the first schema"))
+        .arg(Arg::with_name("diff")
+            .long("diff")
+            .takes_value(true)
+            .help("compare the first schema with the first schema in another file"))
         .arg(Arg::with_name("output")
             .takes_value(true))
         .get_matches();
@@ -27,16 +57,62 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     f.read_to_end(&mut buffer).expect("read ok");
 
     let start = SystemTime::now();
-    let s = strip_comments_and_lower(&buffer);
-    let mut parsed = parse(&s);
+    let s = if matches.is_present("preserve-case") {
+        strip_comments_preserve_case(&buffer)
+    } else {
+        strip_comments_and_lower(&buffer)
+    };
+    // Report every error, rather than stopping at the first one
+    let (syntax, errors) = parse_recovering(&s);
+    let mut parsed = if errors.is_empty() { Ok(syntax) } else { Err(errors) };
 
     let end = SystemTime::now();
     let since_the_epoch = end.duration_since(start).expect("Time went backwards");
     eprintln!("time {:?}", since_the_epoch);
 
     match parsed {
-        Err(e) => eprintln!("Got err {:?}", e),
-        Ok((_, ref mut p)) => {
+        Err(errors) => for e in errors {
+            eprint!("{}", render_error(&s, &e));
+        },
+        Ok(ref mut p) => {
+            if matches.is_present("resolve") {
+                for u in resolve(p).1 {
+                    eprintln!("{}:{}: undefined {:?} '{}'", u.schema,
+                              u.line(&s).unwrap_or(0), u.kind, u.name);
+                }
+            }
+            if matches.is_present("typecheck") {
+                let table = resolve(p).0;
+                for e in check(p, &table) {
+                    let (line, col) = e.location(&s).unwrap_or((0, 0));
+                    eprintln!("{}:{}: {}", line, col, e);
+                }
+            }
+            if let (Some(d), Some(s)) = (matches.value_of("dictionary"),
+                                         p.0.first())
+            {
+                std::fs::write(d, Dictionary::new(s).to_json())?;
+            }
+            if let Some(d) = matches.value_of("diff") {
+                let other = std::fs::read(d)?;
+                let other = if matches.is_present("preserve-case") {
+                    strip_comments_preserve_case(&other)
+                } else {
+                    strip_comments_and_lower(&other)
+                };
+                match parse(&other) {
+                    Err(e) => eprint!("{}", render_error(&other, &e)),
+                    Ok(q) => match (p.0.first(), q.0.first()) {
+                        (Some(a), Some(b)) => for c in diff(a, b) {
+                            println!("{}", c);
+                        },
+                        _ => eprintln!("No schema to compare"),
+                    },
+                }
+            }
+            if let Some(d) = matches.value_of("dot") {
+                std::fs::write(d, to_dot(p))?;
+            }
             match matches.value_of("output") {
                 Some(o) => std::fs::write(o, format!("Parse tree:\n{:#?}", p))?,
                 _ => if !matches.is_present("quiet") {
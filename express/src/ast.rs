@@ -0,0 +1,54 @@
+//! The EXPRESS syntax tree.
+//!
+//! Each type matches a production of the grammar in ISO 10303-11 and is
+//! named after it (e.g. `entity_decl` becomes [`EntityDecl`]); the root is
+//! [`Syntax`].  Names are slices of the text that was parsed, so the tree
+//! borrows from it.
+//!
+//! The types are defined alongside their parsers in [`parse`](crate::parse)
+//! and re-exported here, so that code which only walks the tree doesn't
+//! depend on the parser's internals.
+pub use crate::parse::{
+    AbstractSupertypeDeclaration, ActualParameterList, AddLikeOp,
+    AggregateInitializer, AggregateSource, AggregateType, AggregationTypes,
+    AlgorithmHead, AliasStmt, ArrayType, AssignmentStmt, AttributeDecl,
+    AttributeId, AttributeQualifier, AttributeRef, BagType, BinaryType,
+    Bound1, Bound2, BoundSpec, BuiltInConstant, BuiltInFunction,
+    BuiltInOrFunctionRef, BuiltInOrProcedureRef, BuiltInProcedure,
+    CaseAction, CaseLabel, CaseStmt, CompoundStmt, ConcreteTypes,
+    ConstantBody, ConstantDecl, ConstantFactor, ConstantId, ConstantRef,
+    ConstructedTypes, Declaration, DeclarationOrRuleDecl, DeriveClause,
+    DerivedAttr, DomainRule, Element, EntityBody, EntityConstructor,
+    EntityDecl, EntityHead, EntityId, EntityOrTypeId, EntityRef,
+    EnumerationExtension, EnumerationId, EnumerationItems,
+    EnumerationItemsOrExtension, EnumerationRef, EnumerationReference,
+    EnumerationType, ExplicitAttr, Expression, ExpressionOrPrimary, Factor,
+    FormalParameter, FunctionCall, FunctionDecl, FunctionHead, FunctionId,
+    FunctionRef, GeneralAggregationTypes, GeneralArrayType, GeneralBagType,
+    GeneralListType, GeneralRef, GeneralSetType, GeneralizedTypes,
+    GenericEntityType, GenericType, GroupQualifier, IfStmt, Increment,
+    IncrementControl, Index, Index1, Index2, IndexQualifier,
+    InstantiableType, InterfaceSpecification, Interval, IntervalHigh,
+    IntervalItem, IntervalLow, IntervalOp, InverseAttr, InverseClause,
+    ListType, Literal, LocalDecl, LocalVariable, LogicalExpression,
+    LogicalLiteral, MultiplicationLikeOp, NamedTypeOrRename, NamedTypes,
+    NumericExpression, OneOf, Parameter, ParameterId, ParameterRef,
+    ParameterType, Population, PrecisionSpec, Primary, ProcedureCallStmt,
+    ProcedureDecl, ProcedureHead, ProcedureId, ProcedureRef,
+    QualifiableFactor, QualifiedAttribute, Qualifier, QueryExpression,
+    RealType, RedeclaredAttribute, ReferenceClause, ReferencedAttribute,
+    RelOp, RelOpExtended, RenameId, RepeatControl, RepeatStmt, Repetition,
+    ResourceOrRename, ResourceRef, ReturnStmt, RuleDecl, RuleHead, RuleId,
+    RuleLabelId, RuleLabelRef, RuleRef, SchemaBody, SchemaDecl, SchemaId,
+    SchemaRef, SchemaVersionId, SelectExtension, SelectList,
+    SelectListOrExtension, SelectType, Selector, SetOrBag, SetType,
+    SimpleExpression, SimpleFactor, SimpleId, SimpleTypes, Stmt,
+    StringLiteral, StringType, Subsuper, SubtypeConstraint,
+    SubtypeConstraintBody, SubtypeConstraintDecl, SubtypeConstraintHead,
+    SubtypeConstraintId, SubtypeConstraintRef, SubtypeDeclaration,
+    SupertypeConstraint, SupertypeExpression, SupertypeFactor,
+    SupertypeRule, SupertypeTerm, Syntax, Term, TotalOver, TypeDecl, TypeId,
+    TypeLabel, TypeLabelId, TypeLabelRef, TypeRef, UnaryOp, UnderlyingType,
+    UniqueClause, UniqueRule, UntilControl, UseClause, VariableId,
+    VariableRef, WhereClause, WhileControl, Width, WidthSpec,
+};
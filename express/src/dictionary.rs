@@ -0,0 +1,442 @@
+use std::fmt::Write;
+
+use crate::{
+    dot::{bound, named_types},
+    parse::*,
+};
+
+/// How an attribute's value is stored or computed
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AttributeKind {
+    Explicit,
+    Derived,
+    Inverse,
+}
+
+impl AttributeKind {
+    fn name(&self) -> &'static str {
+        match self {
+            AttributeKind::Explicit => "explicit",
+            AttributeKind::Derived => "derived",
+            AttributeKind::Inverse => "inverse",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttributeDef<'a> {
+    pub name: &'a str,
+    pub kind: AttributeKind,
+    /// The attribute's type in EXPRESS syntax, e.g. `LIST [2:?] OF point`
+    pub type_: String,
+    pub optional: bool,
+    /// For a redeclared attribute, the supertype and attribute which it
+    /// redeclares
+    pub redeclares: Option<(&'a str, &'a str)>,
+    /// For an inverse attribute, the attribute of `type_` which it inverts
+    pub inverts: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntityDef<'a> {
+    pub name: &'a str,
+    pub abstract_: bool,
+    /// Direct supertypes, in declaration order
+    pub supertypes: Vec<&'a str>,
+    /// Attributes declared by this entity (not inherited from supertypes),
+    /// with explicit attributes first, then derived, then inverse
+    pub attributes: Vec<AttributeDef<'a>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TypeKind<'a> {
+    /// A defined type, with its underlying type in EXPRESS syntax
+    Defined(String),
+    Enumeration {
+        values: Vec<&'a str>,
+        extensible: bool,
+        based_on: Option<&'a str>,
+    },
+    Select {
+        options: Vec<&'a str>,
+        extensible: bool,
+        based_on: Option<&'a str>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeDef<'a> {
+    pub name: &'a str,
+    pub kind: TypeKind<'a>,
+}
+
+/// A data dictionary for one schema, in the spirit of the SDAI dictionary
+/// schema: entities with their attributes and supertypes, and defined types
+/// with their enumeration values or select options.
+///
+/// This is a summary of the declarations, rather than a full model, so
+/// rules, functions, and `WHERE` clauses are left out.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dictionary<'a> {
+    pub schema: &'a str,
+    pub entities: Vec<EntityDef<'a>>,
+    pub types: Vec<TypeDef<'a>>,
+}
+
+impl<'a> Dictionary<'a> {
+    pub fn new(schema: &'a SchemaDecl<'a>) -> Self {
+        let mut out = Self {
+            schema: schema.id.0,
+            entities: Vec::new(),
+            types: Vec::new(),
+        };
+        for d in &schema.body.declarations {
+            match d {
+                DeclarationOrRuleDecl::Declaration(Declaration::Entity(e)) =>
+                    out.entities.push(entity(e)),
+                DeclarationOrRuleDecl::Declaration(Declaration::Type(t)) =>
+                    out.types.push(TypeDef {
+                        name: t.type_id.0,
+                        kind: type_kind(&t.underlying_type),
+                    }),
+                _ => (),
+            }
+        }
+        out
+    }
+
+    /// Renders the dictionary as JSON, with one line per entity and type
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        writeln!(&mut out, "{{\n  \"schema\": {},", string(self.schema)).unwrap();
+        out += "  \"entities\": [";
+        for (i, e) in self.entities.iter().enumerate() {
+            out += if i == 0 { "\n    " } else { ",\n    " };
+            write!(&mut out, "{{\"name\": {}, \"abstract\": {}, \
+                              \"supertypes\": {}, \"attributes\": [",
+                   string(e.name), e.abstract_, strings(&e.supertypes)).unwrap();
+            for (j, a) in e.attributes.iter().enumerate() {
+                if j > 0 {
+                    out += ", ";
+                }
+                write!(&mut out, "{{\"name\": {}, \"kind\": {}, \"type\": {}, \
+                                  \"optional\": {}",
+                       string(a.name), string(a.kind.name()), string(&a.type_),
+                       a.optional).unwrap();
+                if let Some((e, a)) = a.redeclares {
+                    write!(&mut out, ", \"redeclares\": {}",
+                           string(&format!("{}.{}", e, a))).unwrap();
+                }
+                if let Some(a) = a.inverts {
+                    write!(&mut out, ", \"inverts\": {}", string(a)).unwrap();
+                }
+                out += "}";
+            }
+            out += "]}";
+        }
+        out += "\n  ],\n  \"types\": [";
+        for (i, t) in self.types.iter().enumerate() {
+            out += if i == 0 { "\n    " } else { ",\n    " };
+            write!(&mut out, "{{\"name\": {}, ", string(t.name)).unwrap();
+            let (kind, items, extensible, based_on) = match &t.kind {
+                TypeKind::Defined(u) => {
+                    write!(&mut out, "\"kind\": \"defined\", \"type\": {}}}",
+                           string(u)).unwrap();
+                    continue;
+                },
+                TypeKind::Enumeration { values, extensible, based_on } =>
+                    (("enumeration", "values"), values, extensible, based_on),
+                TypeKind::Select { options, extensible, based_on } =>
+                    (("select", "options"), options, extensible, based_on),
+            };
+            write!(&mut out, "\"kind\": \"{}\", \"{}\": {}, \"extensible\": {}",
+                   kind.0, kind.1, strings(items), extensible).unwrap();
+            if let Some(b) = based_on {
+                write!(&mut out, ", \"based_on\": {}", string(b)).unwrap();
+            }
+            out += "}";
+        }
+        out += "\n  ]\n}\n";
+        out
+    }
+}
+
+/// Quotes a string for JSON
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            c if (c as u32) < 0x20 => write!(&mut out, "\\u{:04x}", c as u32)
+                .unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn strings(s: &[&str]) -> String {
+    let s: Vec<_> = s.iter().map(|s| string(s)).collect();
+    format!("[{}]", s.join(", "))
+}
+
+fn entity<'a>(e: &'a EntityDecl<'a>) -> EntityDef<'a> {
+    let EntityDecl(head, body) = e;
+    let mut attributes = Vec::new();
+    let redeclares = |d: &AttributeDecl<'a>| match d {
+        AttributeDecl::Redeclared(RedeclaredAttribute(q, _)) =>
+            Some(((q.0).0.0, (q.1).0.0)),
+        AttributeDecl::Id(_) => None,
+    };
+    for a in &body.explicit_attr {
+        for d in &a.attributes {
+            attributes.push(AttributeDef {
+                name: d.name(),
+                kind: AttributeKind::Explicit,
+                type_: parameter_type(&a.parameter_type),
+                optional: a.optional,
+                redeclares: redeclares(d),
+                inverts: None,
+            });
+        }
+    }
+    for a in body.derive.iter().flat_map(|d| d.0.iter()) {
+        attributes.push(AttributeDef {
+            name: a.0.name(),
+            kind: AttributeKind::Derived,
+            type_: parameter_type(&a.1),
+            optional: false,
+            redeclares: redeclares(&a.0),
+            inverts: None,
+        });
+    }
+    for a in body.inverse.iter().flat_map(|i| i.0.iter()) {
+        let type_ = match &a.bounds {
+            Some((kind, b)) => format!("{}{} OF {}",
+                match kind {
+                    SetOrBag::Set => "SET",
+                    SetOrBag::Bag => "BAG",
+                },
+                bounds(b.as_ref()), a.entity.0),
+            None => a.entity.0.to_owned(),
+        };
+        attributes.push(AttributeDef {
+            name: a.attribute_decl.name(),
+            kind: AttributeKind::Inverse,
+            type_,
+            optional: false,
+            redeclares: redeclares(&a.attribute_decl),
+            inverts: Some(a.attribute_ref.0),
+        });
+    }
+
+    EntityDef {
+        name: (head.0).0,
+        abstract_: matches!((head.1).0, Some(SupertypeConstraint::AbstractEntity)
+                             | Some(SupertypeConstraint::AbstractSupertype(_))),
+        supertypes: (head.1).1.iter()
+            .flat_map(|s| s.0.iter())
+            .map(|e| e.0)
+            .collect(),
+        attributes,
+    }
+}
+
+fn type_kind<'a>(u: &'a UnderlyingType<'a>) -> TypeKind<'a> {
+    match u {
+        UnderlyingType::Concrete(c) => TypeKind::Defined(concrete_types(c)),
+        UnderlyingType::Constructed(ConstructedTypes::Enumeration(e)) => {
+            let (values, based_on) = match &e.items_or_extension {
+                None => (None, None),
+                Some(EnumerationItemsOrExtension::Items(i)) => (Some(i), None),
+                Some(EnumerationItemsOrExtension::Extension(x)) =>
+                    (x.enumeration_items.as_ref(), Some(x.type_ref.0)),
+            };
+            TypeKind::Enumeration {
+                values: values.iter().flat_map(|v| v.0.iter())
+                    .map(|v| v.0)
+                    .collect(),
+                extensible: e.extensible,
+                based_on,
+            }
+        },
+        UnderlyingType::Constructed(ConstructedTypes::Select(s)) => {
+            let (options, based_on) = match &s.list_or_extension {
+                SelectListOrExtension::List(l) => (Some(l), None),
+                SelectListOrExtension::Extension(x) =>
+                    (x.select_list.as_ref(), Some(x.type_ref.0)),
+            };
+            TypeKind::Select {
+                options: options.iter().flat_map(|v| v.0.iter())
+                    .map(named_types)
+                    .collect(),
+                extensible: s.extensible,
+                based_on,
+            }
+        },
+    }
+}
+
+/// Renders aggregate bounds, e.g. ` [2:?]`, with a leading space
+fn bounds(b: Option<&BoundSpec>) -> String {
+    match b {
+        Some(b) => format!(" [{}:{}]", bound(&((b.0).0).0), bound(&((b.1).0).0)),
+        None => String::new(),
+    }
+}
+
+/// Renders the `OPTIONAL` and `UNIQUE` flags of an aggregate's elements
+fn flags(optional: bool, unique: bool) -> &'static str {
+    match (optional, unique) {
+        (false, false) => "",
+        (true, false) => "OPTIONAL ",
+        (false, true) => "UNIQUE ",
+        (true, true) => "OPTIONAL UNIQUE ",
+    }
+}
+
+fn simple_types(s: &SimpleTypes) -> &'static str {
+    match s {
+        SimpleTypes::Binary(_) => "BINARY",
+        SimpleTypes::Boolean => "BOOLEAN",
+        SimpleTypes::Integer => "INTEGER",
+        SimpleTypes::Logical => "LOGICAL",
+        SimpleTypes::Number => "NUMBER",
+        SimpleTypes::Real(_) => "REAL",
+        SimpleTypes::String(_) => "STRING",
+    }
+}
+
+fn label(t: &Option<TypeLabel>) -> String {
+    match t {
+        Some(t) => format!(":{}", t.name()),
+        None => String::new(),
+    }
+}
+
+/// Renders a parameter type in EXPRESS syntax, e.g. `LIST [2:?] OF point`
+pub fn parameter_type(p: &ParameterType) -> String {
+    match p {
+        ParameterType::Generalized(g) => match g {
+            GeneralizedTypes::Aggregate(a) => format!(
+                "AGGREGATE{} OF {}", label(&a.0), parameter_type(&a.1)),
+            GeneralizedTypes::GeneralAggregation(a) => match a {
+                GeneralAggregationTypes::Array(a) => format!(
+                    "ARRAY{} OF {}{}", bounds(a.bounds.as_ref()),
+                    flags(a.optional, a.unique),
+                    parameter_type(&a.parameter_type)),
+                GeneralAggregationTypes::Bag(b) => format!(
+                    "BAG{} OF {}", bounds(b.0.as_ref()), parameter_type(&b.1)),
+                GeneralAggregationTypes::List(l) => format!(
+                    "LIST{} OF {}{}", bounds(l.bounds.as_ref()),
+                    flags(false, l.unique),
+                    parameter_type(&l.parameter_type)),
+                GeneralAggregationTypes::Set(s) => format!(
+                    "SET{} OF {}", bounds(s.bounds.as_ref()),
+                    parameter_type(&s.parameter_type)),
+            },
+            GeneralizedTypes::GenericEntity(g) =>
+                format!("GENERIC_ENTITY{}", label(&g.0)),
+            GeneralizedTypes::Generic(g) => format!("GENERIC{}", label(&g.0)),
+        },
+        ParameterType::Named(n) => named_types(n).to_owned(),
+        ParameterType::Simple(s) => simple_types(s).to_owned(),
+    }
+}
+
+/// Renders a concrete type in EXPRESS syntax
+fn concrete_types(c: &ConcreteTypes) -> String {
+    match c {
+        ConcreteTypes::Aggregation(a) => match a {
+            AggregationTypes::Array(a) => format!(
+                "ARRAY{} OF {}{}", bounds(Some(&a.bounds)),
+                flags(a.optional, a.unique),
+                instantiable_type(&a.instantiable_type)),
+            AggregationTypes::Bag(b) => format!(
+                "BAG{} OF {}", bounds(b.0.as_ref()), instantiable_type(&b.1)),
+            AggregationTypes::List(l) => format!(
+                "LIST{} OF {}{}", bounds(l.bounds.as_ref()),
+                flags(false, l.unique),
+                instantiable_type(&l.instantiable_type)),
+            AggregationTypes::Set(s) => format!(
+                "SET{} OF {}", bounds(s.bounds.as_ref()),
+                instantiable_type(&s.instantiable_type)),
+        },
+        ConcreteTypes::Simple(s) => simple_types(s).to_owned(),
+        ConcreteTypes::TypeRef(t) => t.0.to_owned(),
+    }
+}
+
+fn instantiable_type(i: &InstantiableType) -> String {
+    match i {
+        InstantiableType::Concrete(c) => concrete_types(c),
+        InstantiableType::EntityRef(e) => e.0.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_dictionary() {
+        let s = strip_comments_and_lower(br#"SCHEMA geom;
+TYPE length_measure = REAL; END_TYPE;
+TYPE side = ENUMERATION OF (left, right); END_TYPE;
+TYPE shape_select = EXTENSIBLE SELECT (point, polyline); END_TYPE;
+ENTITY shape ABSTRACT SUPERTYPE;
+  name : OPTIONAL STRING;
+END_ENTITY;
+ENTITY point SUBTYPE OF (shape);
+  x, y : length_measure;
+INVERSE
+  users : SET [0:?] OF polyline FOR points;
+END_ENTITY;
+ENTITY polyline SUBTYPE OF (shape);
+  points : LIST [2:?] OF UNIQUE point;
+  s : side;
+DERIVE
+  first : point := points[1];
+END_ENTITY;
+END_SCHEMA;
+"#);
+        let syntax = parse(&s).unwrap();
+        let d = Dictionary::new(&syntax.0[0]);
+        assert_eq!(d.schema, "geom");
+        assert_eq!(d.entities.len(), 3);
+        assert!(d.entities[0].abstract_);
+        assert_eq!(d.entities[1].supertypes, ["shape"]);
+
+        let attrs = &d.entities[2].attributes;
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(attrs[0].type_, "LIST [2:?] OF UNIQUE point");
+        assert_eq!(attrs[2].kind, AttributeKind::Derived);
+        assert_eq!(d.entities[1].attributes[2].inverts, Some("points"));
+
+        assert_eq!(d.types[0].kind, TypeKind::Defined("REAL".to_owned()));
+        assert_eq!(d.types[1].kind, TypeKind::Enumeration {
+            values: vec!["left", "right"], extensible: false, based_on: None,
+        });
+
+        let json = d.to_json();
+        for line in &[
+            "\"schema\": \"geom\"",
+            "{\"name\": \"shape\", \"abstract\": true, \"supertypes\": [], \
+             \"attributes\": [{\"name\": \"name\", \"kind\": \"explicit\", \
+             \"type\": \"STRING\", \"optional\": true}]}",
+            "{\"name\": \"users\", \"kind\": \"inverse\", \
+             \"type\": \"SET [0:?] OF polyline\", \"optional\": false, \
+             \"inverts\": \"points\"}",
+            "{\"name\": \"side\", \"kind\": \"enumeration\", \
+             \"values\": [\"left\", \"right\"], \"extensible\": false}",
+            "{\"name\": \"shape_select\", \"kind\": \"select\", \
+             \"options\": [\"point\", \"polyline\"], \"extensible\": true}",
+        ] {
+            assert!(json.contains(line), "Missing {} in\n{}", line, json);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use crate::{parse::*, resolve::interface};
+
+/// What happened to an item between the old and new schema
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Op {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Part of a schema which is compared on its own
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Item<'a> {
+    /// The `USE` and `REFERENCE` clauses which name a particular schema
+    Interface(&'a str),
+    Constant(&'a str),
+    /// An entity's supertypes, subtype declaration, and `UNIQUE` and `WHERE`
+    /// rules.  Its attributes are compared separately.
+    Entity(&'a str),
+    /// An explicit, derived, or inverse attribute, as (entity, attribute)
+    Attribute(&'a str, &'a str),
+    Type(&'a str),
+    Function(&'a str),
+    Procedure(&'a str),
+    Rule(&'a str),
+    SubtypeConstraint(&'a str),
+}
+
+impl<'a> Item<'a> {
+    /// Returns the name of the top-level item, e.g. the entity which owns an
+    /// attribute
+    pub fn name(&self) -> &'a str {
+        match self {
+            Item::Interface(s) | Item::Constant(s) | Item::Entity(s) |
+            Item::Attribute(s, _) | Item::Type(s) | Item::Function(s) |
+            Item::Procedure(s) | Item::Rule(s) | Item::SubtypeConstraint(s) => s,
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for Item<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Item::Interface(s) => write!(f, "interface {}", s),
+            Item::Constant(s) => write!(f, "constant {}", s),
+            Item::Entity(s) => write!(f, "entity {}", s),
+            Item::Attribute(e, a) => write!(f, "attribute {}.{}", e, a),
+            Item::Type(s) => write!(f, "type {}", s),
+            Item::Function(s) => write!(f, "function {}", s),
+            Item::Procedure(s) => write!(f, "procedure {}", s),
+            Item::Rule(s) => write!(f, "rule {}", s),
+            Item::SubtypeConstraint(s) => write!(f, "subtype_constraint {}", s),
+        }
+    }
+}
+
+/// A single difference between two schemas
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Change<'a> {
+    pub op: Op,
+    pub item: Item<'a>,
+}
+
+impl<'a> std::fmt::Display for Change<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let op = match self.op {
+            Op::Added => '+',
+            Op::Removed => '-',
+            Op::Changed => '~',
+        };
+        write!(f, "{} {}", op, self.item)
+    }
+}
+
+/// Splits a schema into items, each with a fingerprint which is equal for
+/// items with the same definition.  The AST doesn't store source positions,
+/// so its `Debug` output works as a structural fingerprint.
+fn items<'a>(s: &'a SchemaDecl<'a>) -> BTreeMap<Item<'a>, String> {
+    let mut out = BTreeMap::new();
+    for i in &s.body.interfaces {
+        out.entry(Item::Interface(interface(i).0))
+            .or_insert_with(String::new)
+            .push_str(&format!("{:?}", i));
+    }
+    for c in s.body.constants.iter().flat_map(|c| c.0.iter()) {
+        out.insert(Item::Constant(c.constant_id.0),
+                   format!("{:?} {:?}", c.instantiable_type, c.expression));
+    }
+    for d in &s.body.declarations {
+        let d = match d {
+            DeclarationOrRuleDecl::Declaration(d) => d,
+            DeclarationOrRuleDecl::RuleDecl(r) => {
+                out.insert(Item::Rule(r.rule_head.rule_id.0),
+                           format!("{:?}", r));
+                continue;
+            },
+        };
+        match d {
+            Declaration::Entity(EntityDecl(head, body)) => {
+                let name = (head.0).0;
+                out.insert(Item::Entity(name), format!("{:?} {:?} {:?}",
+                           head.1, body.unique, body.where_));
+                for a in &body.explicit_attr {
+                    for d in &a.attributes {
+                        out.insert(Item::Attribute(name, d.name()),
+                                   format!("explicit {:?} {} {:?}", d,
+                                           a.optional, a.parameter_type));
+                    }
+                }
+                for a in body.derive.iter().flat_map(|d| d.0.iter()) {
+                    out.insert(Item::Attribute(name, a.0.name()),
+                               format!("derived {:?}", a));
+                }
+                for a in body.inverse.iter().flat_map(|i| i.0.iter()) {
+                    out.insert(Item::Attribute(name, a.attribute_decl.name()),
+                               format!("inverse {:?}", a));
+                }
+            },
+            Declaration::Function(f) =>
+                { out.insert(Item::Function(f.function_head.id.0),
+                             format!("{:?}", f)); },
+            Declaration::Procedure(p) =>
+                { out.insert(Item::Procedure(p.0.procedure_id.0),
+                             format!("{:?}", p)); },
+            Declaration::SubtypeConstraint(c) =>
+                { out.insert(Item::SubtypeConstraint((c.0).0.0),
+                             format!("{:?}", c)); },
+            Declaration::Type(t) =>
+                { out.insert(Item::Type(t.type_id.0), format!("{:?} {:?}",
+                             t.underlying_type, t.where_clause)); },
+        }
+    }
+    out
+}
+
+/// Compares two versions of a schema, e.g. from different editions of an
+/// application protocol.
+///
+/// Changes are sorted by the name of the top-level item, so an entity is
+/// followed by changes to its attributes.  The attributes of an entity which
+/// was added or removed aren't listed separately.  To compare schemas which
+/// interface with other schemas, [`flatten`](crate::flatten::flatten) them
+/// first.
+pub fn diff<'a>(old: &'a SchemaDecl<'a>, new: &'a SchemaDecl<'a>)
+    -> Vec<Change<'a>>
+{
+    let old = items(old);
+    let new = items(new);
+    let whole = |m: &BTreeMap<Item, String>, item: &Item| match item {
+        Item::Attribute(e, _) => m.contains_key(&Item::Entity(e)),
+        _ => true,
+    };
+
+    let mut out: Vec<Change> = old.iter()
+        .filter_map(|(item, a)| match new.get(item) {
+            Some(b) if a == b => None,
+            Some(_) => Some(Change { op: Op::Changed, item: *item }),
+            None if whole(&new, item) =>
+                Some(Change { op: Op::Removed, item: *item }),
+            None => None,
+        })
+        .chain(new.keys()
+            .filter(|item| !old.contains_key(item) && whole(&old, item))
+            .map(|item| Change { op: Op::Added, item: *item }))
+        .collect();
+    out.sort_by_key(|c| (c.item.name(), c.item));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_diff() {
+        let a = strip_comments_and_lower(br#"SCHEMA geom;
+TYPE label = STRING; END_TYPE;
+TYPE side = ENUMERATION OF (left, right); END_TYPE;
+ENTITY shape;
+  name : label;
+END_ENTITY;
+ENTITY point SUBTYPE OF (shape);
+  x, y : REAL;
+END_ENTITY;
+ENTITY circle SUBTYPE OF (shape);
+  r : REAL;
+END_ENTITY;
+FUNCTION area(c : circle) : REAL; RETURN (PI * c.r ** 2); END_FUNCTION;
+END_SCHEMA;
+"#);
+        let b = strip_comments_and_lower(br#"SCHEMA geom;
+TYPE label = STRING; END_TYPE;
+TYPE side = ENUMERATION OF (left, right, top); END_TYPE;
+ENTITY shape;
+  name : OPTIONAL label;
+END_ENTITY;
+ENTITY point SUBTYPE OF (shape);
+  x, y, z : REAL;
+WHERE
+  wr1: x > 0.0;
+END_ENTITY;
+ENTITY line SUBTYPE OF (shape);
+  a, b : point;
+END_ENTITY;
+FUNCTION area(c : circle) : REAL; RETURN (PI * c.r ** 2); END_FUNCTION;
+END_SCHEMA;
+"#);
+        let a = parse(&a).unwrap();
+        let b = parse(&b).unwrap();
+        let changes: Vec<String> = diff(&a.0[0], &b.0[0]).iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(changes, [
+            "- entity circle",
+            "+ entity line",
+            "~ entity point",
+            "+ attribute point.z",
+            "~ attribute shape.name",
+            "~ type side",
+        ]);
+
+        assert!(diff(&a.0[0], &a.0[0]).is_empty());
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{
+    parse::*,
+    resolve::{resolve, Decl, SymbolTable},
+};
+
+/// Renders the entities and defined types of every schema as a Graphviz
+/// graph, following EXPRESS-G conventions where DOT allows:
+///
+/// - Entities are solid boxes, and abstract entities are marked `(ABS)`
+/// - Defined, select, and enumeration types are dashed boxes
+/// - Names from other schemas (or which can't be resolved) are ellipses
+/// - Inheritance is a thick line from supertype to subtype
+/// - Attributes are lines from the entity to the attribute's type, labelled
+///   with the attribute name and any aggregation (e.g. `points L[2:?]`), and
+///   dashed if the attribute is `OPTIONAL`
+/// - Derived and inverse attributes are marked `(DER)` and `(INV)`
+///
+/// Every line ends in a circle at the subtype or referenced type.  Attributes
+/// of simple types (`REAL`, `STRING`, etc) are left out, since they would
+/// crowd the graph without showing any structure.
+pub fn to_dot<'a>(syntax: &'a Syntax<'a>) -> String {
+    let (table, _) = resolve(syntax);
+    let mut g = Graph::new(syntax, &table);
+    for s in &syntax.0 {
+        g.schema(s);
+    }
+    g.finish()
+}
+
+/// Returns the address of an entity or type declaration, which identifies it
+/// regardless of the name used to reach it
+fn address(d: &Decl) -> Option<usize> {
+    match d {
+        Decl::Entity(e) => Some(*e as *const EntityDecl as usize),
+        Decl::Type(t) => Some(*t as *const TypeDecl as usize),
+        _ => None,
+    }
+}
+
+struct Graph<'a, 'b> {
+    table: &'b SymbolTable<'a>,
+    /// Maps each top-level entity and type to the schema which declares it
+    home: HashMap<usize, &'a str>,
+    /// Nodes for names which aren't declared in the file
+    external: Vec<String>,
+    edges: String,
+    out: String,
+}
+
+impl<'a, 'b> Graph<'a, 'b> {
+    fn new(syntax: &'a Syntax<'a>, table: &'b SymbolTable<'a>) -> Self {
+        let mut home = HashMap::new();
+        for s in &syntax.0 {
+            for d in &s.body.declarations {
+                let d = match d {
+                    DeclarationOrRuleDecl::Declaration(Declaration::Entity(e)) =>
+                        Decl::Entity(e),
+                    DeclarationOrRuleDecl::Declaration(Declaration::Type(t)) =>
+                        Decl::Type(t),
+                    _ => continue,
+                };
+                home.insert(address(&d).unwrap(), s.id.0);
+            }
+        }
+        let out = "digraph express {\n    node [shape=box];\n".to_owned();
+        Self { table, home, external: Vec::new(), edges: String::new(), out }
+    }
+
+    fn finish(mut self) -> String {
+        self.external.sort();
+        self.external.dedup();
+        for e in &self.external {
+            writeln!(self.out, "    \"{}\" [shape=ellipse];", e).unwrap();
+        }
+        self.out.push_str(&self.edges);
+        self.out.push_str("}\n");
+        self.out
+    }
+
+    /// Returns the node ID for a name, as seen from the given schema
+    fn node(&mut self, schema: &str, name: &str) -> String {
+        let d = self.table.lookup(schema, name);
+        match d.as_ref().and_then(address).and_then(|a| self.home.get(&a)) {
+            Some(home) => format!("{}.{}", home, d.unwrap().name()),
+            None => {
+                let id = match d {
+                    Some(Decl::External { schema, name }) =>
+                        format!("{}.{}", schema, name),
+                    _ => format!("{}.{}", schema, name),
+                };
+                self.external.push(id.clone());
+                id
+            },
+        }
+    }
+
+    fn edge(&mut self, schema: &str, from: &str, to: &str, attrs: &str) {
+        let to = self.node(schema, to);
+        writeln!(self.edges, "    \"{}.{}\" -> \"{}\" [arrowhead=odot{}];",
+                 schema, from, to, attrs).unwrap();
+    }
+
+    fn attribute(&mut self, schema: &str, entity: &str, label: String,
+                 target: Option<&'a str>, optional: bool)
+    {
+        if let Some(t) = target {
+            let style = if optional { ", style=dashed" } else { "" };
+            self.edge(schema, entity, t,
+                      &format!(", label=\"{}\"{}", label, style));
+        }
+    }
+
+    fn schema(&mut self, s: &'a SchemaDecl<'a>) {
+        let name = s.id.0;
+        writeln!(self.out, "    subgraph \"cluster_{0}\" {{\n        \
+                            label=\"{0}\";", name).unwrap();
+        for d in &s.body.declarations {
+            match d {
+                DeclarationOrRuleDecl::Declaration(Declaration::Entity(e)) =>
+                    self.entity(name, e),
+                DeclarationOrRuleDecl::Declaration(Declaration::Type(t)) =>
+                    self.type_decl(name, t),
+                _ => (),
+            }
+        }
+        self.out.push_str("    }\n");
+    }
+
+    fn entity(&mut self, schema: &str, e: &'a EntityDecl<'a>) {
+        let name = (e.0).0.0;
+        let abs = match &((e.0).1).0 {
+            Some(SupertypeConstraint::AbstractEntity) |
+            Some(SupertypeConstraint::AbstractSupertype(_)) => "(ABS)",
+            _ => "",
+        };
+        writeln!(self.out, "        \"{}.{}\" [label=\"{}{}\"];",
+                 schema, name, abs, name).unwrap();
+
+        for sup in ((e.0).1).1.iter().flat_map(|s| s.0.iter()) {
+            // Inheritance lines point from the supertype to the subtype
+            let sup = self.node(schema, sup.0);
+            writeln!(self.edges,
+                     "    \"{}\" -> \"{}.{}\" [arrowhead=odot, penwidth=3];",
+                     sup, schema, name).unwrap();
+        }
+
+        let body = &e.1;
+        for a in &body.explicit_attr {
+            let (target, agg) = parameter_type(&a.parameter_type);
+            for d in &a.attributes {
+                self.attribute(schema, name, label("", d, &agg), target,
+                               a.optional);
+            }
+        }
+        for d in body.derive.iter().flat_map(|d| d.0.iter()) {
+            let (target, agg) = parameter_type(&d.1);
+            self.attribute(schema, name, label("(DER)", &d.0, &agg), target,
+                           false);
+        }
+        for i in body.inverse.iter().flat_map(|i| i.0.iter()) {
+            let agg = match &i.bounds {
+                None => String::new(),
+                Some((kind, b)) => aggregate(match kind {
+                    SetOrBag::Set => "S",
+                    SetOrBag::Bag => "B",
+                }, b.as_ref()),
+            };
+            self.attribute(schema, name,
+                           label("(INV)", &i.attribute_decl, &agg),
+                           Some(i.entity.0), false);
+        }
+    }
+
+    fn type_decl(&mut self, schema: &str, t: &'a TypeDecl<'a>) {
+        let name = t.type_id.0;
+        let kind = match &t.underlying_type {
+            UnderlyingType::Constructed(ConstructedTypes::Enumeration(_)) =>
+                "\\n(ENUMERATION)",
+            UnderlyingType::Constructed(ConstructedTypes::Select(_)) =>
+                "\\n(SELECT)",
+            UnderlyingType::Concrete(_) => "",
+        };
+        writeln!(self.out,
+                 "        \"{}.{}\" [label=\"{}{}\", style=dashed];",
+                 schema, name, name, kind).unwrap();
+
+        match &t.underlying_type {
+            UnderlyingType::Concrete(c) => {
+                let (target, agg) = concrete_types(c);
+                if let Some(target) = target {
+                    let label = if agg.is_empty() {
+                        String::new()
+                    } else {
+                        format!(", label=\"{}\"", agg)
+                    };
+                    self.edge(schema, name, target, &label);
+                }
+            },
+            UnderlyingType::Constructed(ConstructedTypes::Select(s)) => {
+                let (based_on, list) = match &s.list_or_extension {
+                    SelectListOrExtension::List(l) => (None, Some(l)),
+                    SelectListOrExtension::Extension(e) =>
+                        (Some(e.type_ref.0), e.select_list.as_ref()),
+                };
+                if let Some(b) = based_on {
+                    self.edge(schema, name, b,
+                              ", label=\"BASED_ON\", style=dashed");
+                }
+                for n in list.iter().flat_map(|l| l.0.iter()) {
+                    self.edge(schema, name, named_types(n), "");
+                }
+            },
+            UnderlyingType::Constructed(ConstructedTypes::Enumeration(_)) => (),
+        }
+    }
+}
+
+/// Builds an attribute label, e.g. `(INV)users S[0:?]`
+fn label(prefix: &str, d: &AttributeDecl, agg: &str) -> String {
+    let prefix = match d {
+        AttributeDecl::Redeclared(_) if prefix.is_empty() => "(RT)",
+        _ => prefix,
+    };
+    if agg.is_empty() {
+        format!("{}{}", prefix, d.name())
+    } else {
+        format!("{}{} {}", prefix, d.name(), agg)
+    }
+}
+
+/// Prints a bound if it's a plain number or `?`, which covers nearly every
+/// bound in real schemas
+pub(crate) fn bound(b: &SimpleExpression) -> String {
+    let Term(f, ops) = &*b.0;
+    if !b.1.is_empty() || !ops.is_empty() || !f.1.is_empty() {
+        return "..".to_owned();
+    }
+    match &f.0 {
+        SimpleFactor::Unary(sign, ExpressionOrPrimary::Primary(p)) => {
+            let sign = match sign {
+                Some(UnaryOp::Sub) => "-",
+                _ => "",
+            };
+            match p {
+                Primary::Literal(Literal::Real(r)) => format!("{}{}", sign, r),
+                Primary::Qualifiable(QualifiableFactor::ConstantFactor(
+                    ConstantFactor::BuiltIn(BuiltInConstant::Indeterminant)),
+                    _) => "?".to_owned(),
+                Primary::Qualifiable(QualifiableFactor::_Ambiguous(s), _) =>
+                    format!("{}{}", sign, s),
+                _ => "..".to_owned(),
+            }
+        },
+        _ => "..".to_owned(),
+    }
+}
+
+/// Builds an EXPRESS-G aggregation label, e.g. `L[2:?]`
+fn aggregate(kind: &str, b: Option<&BoundSpec>) -> String {
+    match b {
+        Some(b) => format!("{}[{}:{}]", kind, bound(&((b.0).0).0),
+                           bound(&((b.1).0).0)),
+        None => kind.to_owned(),
+    }
+}
+
+/// Joins the aggregation labels of nested aggregates
+fn nested(outer: String, (t, inner): (Option<&str>, String))
+    -> (Option<&str>, String)
+{
+    if inner.is_empty() {
+        (t, outer)
+    } else {
+        (t, format!("{} {}", outer, inner))
+    }
+}
+
+pub(crate) fn named_types<'a>(n: &NamedTypes<'a>) -> &'a str {
+    match n {
+        NamedTypes::Entity(e) => e.0,
+        NamedTypes::Type(t) => t.0,
+        NamedTypes::_Ambiguous(s) => s.0,
+    }
+}
+
+/// Finds the named type at the bottom of a (possibly aggregate) parameter
+/// type, along with its aggregation label.  Simple and generic types have no
+/// name, so they're returned as `None`.
+fn parameter_type<'a>(p: &'a ParameterType<'a>) -> (Option<&'a str>, String) {
+    match p {
+        ParameterType::Generalized(GeneralizedTypes::GeneralAggregation(a)) =>
+            match a {
+                GeneralAggregationTypes::Array(a) => nested(
+                    aggregate("A", a.bounds.as_ref()),
+                    parameter_type(&a.parameter_type)),
+                GeneralAggregationTypes::Bag(b) => nested(
+                    aggregate("B", b.0.as_ref()), parameter_type(&b.1)),
+                GeneralAggregationTypes::List(l) => nested(
+                    aggregate("L", l.bounds.as_ref()),
+                    parameter_type(&l.parameter_type)),
+                GeneralAggregationTypes::Set(s) => nested(
+                    aggregate("S", s.bounds.as_ref()),
+                    parameter_type(&s.parameter_type)),
+            },
+        ParameterType::Generalized(_) | ParameterType::Simple(_) =>
+            (None, String::new()),
+        ParameterType::Named(n) => (Some(named_types(n)), String::new()),
+    }
+}
+
+fn concrete_types<'a>(c: &'a ConcreteTypes<'a>) -> (Option<&'a str>, String) {
+    match c {
+        ConcreteTypes::Aggregation(a) => match a {
+            AggregationTypes::Array(a) => nested(
+                aggregate("A", Some(&a.bounds)),
+                instantiable_type(&a.instantiable_type)),
+            AggregationTypes::Bag(b) => nested(
+                aggregate("B", b.0.as_ref()), instantiable_type(&b.1)),
+            AggregationTypes::List(l) => nested(
+                aggregate("L", l.bounds.as_ref()),
+                instantiable_type(&l.instantiable_type)),
+            AggregationTypes::Set(s) => nested(
+                aggregate("S", s.bounds.as_ref()),
+                instantiable_type(&s.instantiable_type)),
+        },
+        ConcreteTypes::Simple(_) => (None, String::new()),
+        ConcreteTypes::TypeRef(t) => (Some(t.0), String::new()),
+    }
+}
+
+fn instantiable_type<'a>(i: &'a InstantiableType<'a>)
+    -> (Option<&'a str>, String)
+{
+    match i {
+        InstantiableType::Concrete(c) => concrete_types(c),
+        InstantiableType::EntityRef(e) => (Some(e.0), String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_to_dot() {
+        let s = strip_comments_and_lower(br#"SCHEMA geom;
+REFERENCE FROM support (label);
+TYPE length_measure = REAL; END_TYPE;
+TYPE side = ENUMERATION OF (left, right); END_TYPE;
+TYPE shape_select = SELECT (point, polyline); END_TYPE;
+ENTITY shape ABSTRACT SUPERTYPE;
+  name : OPTIONAL label;
+END_ENTITY;
+ENTITY point SUBTYPE OF (shape);
+  x, y : length_measure;
+INVERSE
+  users : SET [0:?] OF polyline FOR points;
+END_ENTITY;
+ENTITY polyline SUBTYPE OF (shape);
+  points : LIST [2:?] OF point;
+  s : side;
+DERIVE
+  first : point := points[1];
+END_ENTITY;
+END_SCHEMA;
+"#);
+        let syntax = parse(&s).unwrap();
+        let dot = to_dot(&syntax);
+        for line in &[
+            "\"geom.shape\" [label=\"(ABS)shape\"];",
+            "\"geom.side\" [label=\"side\\n(ENUMERATION)\", style=dashed];",
+            "\"support.label\" [shape=ellipse];",
+            "\"geom.shape\" -> \"geom.point\" [arrowhead=odot, penwidth=3];",
+            "\"geom.shape\" -> \"support.label\" \
+                [arrowhead=odot, label=\"name\", style=dashed];",
+            "\"geom.point\" -> \"geom.length_measure\" \
+                [arrowhead=odot, label=\"y\"];",
+            "\"geom.point\" -> \"geom.polyline\" \
+                [arrowhead=odot, label=\"(INV)users S[0:?]\"];",
+            "\"geom.polyline\" -> \"geom.point\" \
+                [arrowhead=odot, label=\"points L[2:?]\"];",
+            "\"geom.polyline\" -> \"geom.point\" \
+                [arrowhead=odot, label=\"(DER)first\"];",
+            "\"geom.shape_select\" -> \"geom.polyline\" [arrowhead=odot];",
+        ] {
+            assert!(dot.contains(line), "Missing {} in\n{}", line, dot);
+        }
+        // Simple-typed attributes aren't drawn
+        assert!(!dot.contains("REAL"));
+        assert!(dot.starts_with("digraph express {"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
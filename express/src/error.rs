@@ -0,0 +1,156 @@
+use nom::error::VerboseErrorKind;
+
+use crate::parse::ParseFailure;
+
+/// A parse failure, located within the (comment-stripped) input text
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    /// Byte offset into the input
+    pub offset: usize,
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Column (in characters), starting from 1
+    pub column: usize,
+    /// What the parser was looking for when it failed
+    pub expected: String,
+    /// Productions which were being parsed, innermost first
+    pub productions: Vec<&'static str>,
+}
+
+impl Error {
+    /// Builds an error at the position where `rest` begins.  `rest` must be
+    /// a suffix of `input`.
+    pub(crate) fn new(input: &str, rest: &str, expected: String,
+                      productions: Vec<&'static str>) -> Self
+    {
+        let offset = input.len().saturating_sub(rest.len());
+        let (line, column) = line_column(input, offset);
+        Self { offset, line, column, expected, productions }
+    }
+
+    /// Converts a nom error into a diagnostic.  `input` must be the full
+    /// string that was passed to the parser.
+    pub(crate) fn from_nom(input: &str, e: nom::Err<ParseFailure>) -> Self {
+        let e = match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => return Self::new(
+                input, "", "more input".to_owned(), vec![]),
+        };
+        // The first item is the innermost failure, which tells us what the
+        // parser expected; the contexts were attached by the enclosing
+        // productions.
+        let (rest, kind) = e.first;
+        let expected = match kind {
+            VerboseErrorKind::Char(c) => format!("'{}'", c),
+            VerboseErrorKind::Context(s) => s.to_string(),
+            VerboseErrorKind::Nom(k) => k.description().to_lowercase(),
+        };
+        let productions = e.contexts.iter()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(s) => Some(*s),
+                _ => None,
+            })
+            .collect();
+        Self::new(input, rest, expected, productions)
+    }
+}
+
+/// Converts a byte offset into `input` to a (line, column) pair, both
+/// starting from 1
+fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next()
+        .map(|s| s.chars().count())
+        .unwrap_or(0) + 1;
+    (line, column)
+}
+
+/// Finds the (line, column) at which `span` begins, if it's a slice of
+/// `input`.  The AST borrows names and expressions from the parser's input,
+/// so this locates them for diagnostics.
+pub fn locate(input: &str, span: &str) -> Option<(usize, usize)> {
+    offset_of(input, span).map(|offset| line_column(input, offset))
+}
+
+/// Finds the byte offset at which `span` begins, if it's a slice of `input`
+pub(crate) fn offset_of(input: &str, span: &str) -> Option<usize> {
+    (span.as_ptr() as usize)
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|o| *o + span.len() <= input.len())
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}: expected {}", self.line, self.column, self.expected)?;
+        if let Some(p) = self.productions.first() {
+            write!(f, " in {}", p)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Renders an error as a human-readable message, with a snippet of the
+/// offending line and a caret marking the failure point.  `s` must be the
+/// same text that was passed to the parser.
+pub fn render_error(s: &str, e: &Error) -> String {
+    let text = s.lines().nth(e.line - 1).unwrap_or("");
+    let gutter = e.line.to_string().len();
+    let mut out = format!("error: expected {}\n", e.expected);
+    out += &format!("{:w$}--> line {}, column {}\n", "", e.line, e.column,
+                    w = gutter);
+    out += &format!("{:w$} |\n", "", w = gutter);
+    out += &format!("{} | {}\n", e.line, text);
+    out += &format!("{:w$} | {:c$}^\n", "", "", w = gutter, c = e.column - 1);
+    if !e.productions.is_empty() {
+        out += &format!("{:w$} = while parsing {}\n", "",
+                        e.productions.join(" in "), w = gutter);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_error_location() {
+        let s = strip_comments_and_lower(b"SCHEMA foo;
+ENTITY bar;
+  x : REAL
+END_ENTITY;
+END_SCHEMA;");
+        let e = parse(&s).unwrap_err();
+        assert_eq!(e.line, 4);
+        assert_eq!(e.column, 1);
+        assert_eq!(e.expected, "';'");
+        assert_eq!(e.productions.first(), Some(&"entity_decl"));
+
+        let r = render_error(&s, &e);
+        assert!(r.contains("4 | end_entity;"));
+        assert!(r.contains("line 4, column 1"));
+    }
+
+    #[test]
+    fn test_comment_line_numbers() {
+        let s = strip_comments_and_lower(b"SCHEMA foo; (* a
+multi-line comment *)
+-- and a single-line comment
+ENTITY bar;
+  x : REAL;
+END_ENTITY
+END_SCHEMA;");
+        let e = parse(&s).unwrap_err();
+        assert_eq!(e.line, 7);
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let s = "schema foo; end_schema; garbage";
+        let e = parse(s).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 25));
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    parse::*,
+    resolve::{interface, references, Decl},
+};
+
+/// Reasons why a schema can't be converted to long form
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FlattenError {
+    /// The requested schema isn't in the syntax tree
+    UnknownSchema(String),
+    /// An item named in a `USE` or `REFERENCE` clause isn't declared in (or
+    /// interfaced into) its source schema
+    Missing { schema: String, name: String },
+    /// An item which is imported under a different name with `AS`, but is
+    /// also used under another name (e.g. its original one, by its own
+    /// schema).  A long form can only declare it under one name, and
+    /// references to the item aren't rewritten.
+    Renamed { schema: String, name: String, rename: String },
+    /// Two different declarations with the same name are both needed
+    Conflict { name: String, schemas: (String, String) },
+}
+
+impl std::fmt::Display for FlattenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FlattenError::UnknownSchema(s) => write!(f, "Unknown schema {}", s),
+            FlattenError::Missing { schema, name } =>
+                write!(f, "{} is not visible in schema {}", name, schema),
+            FlattenError::Renamed { schema, name, rename } =>
+                write!(f, "Cannot import {} AS {} from schema {}: it is also \
+                           used under another name", name, rename, schema),
+            FlattenError::Conflict { name, schemas } =>
+                write!(f, "{} is declared in both {} and {}",
+                       name, schemas.0, schemas.1),
+        }
+    }
+}
+
+impl std::error::Error for FlattenError {}
+
+/// A top-level declaration or constant, as (schema index, item index)
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum Item {
+    Declaration(usize, usize),
+    Constant(usize, usize),
+}
+
+impl Item {
+    fn schema(&self) -> usize {
+        match self {
+            Item::Declaration(s, _) | Item::Constant(s, _) => *s,
+        }
+    }
+}
+
+/// Works out which declarations from which schemas belong in a long form
+struct Flattener<'a> {
+    syntax: &'a Syntax<'a>,
+    schemas: HashMap<&'a str, usize>,
+    /// Declarations at the top level of each schema, by name
+    names: Vec<HashMap<&'a str, Item>>,
+}
+
+impl<'a> Flattener<'a> {
+    fn new(syntax: &'a Syntax<'a>) -> Self {
+        let mut out = Self {
+            syntax,
+            schemas: syntax.0.iter().enumerate()
+                .map(|(i, s)| (s.id.0, i))
+                .collect(),
+            names: Vec::new(),
+        };
+        for (i, s) in syntax.0.iter().enumerate() {
+            let constants = s.body.constants.iter()
+                .flat_map(|c| c.0.iter())
+                .enumerate()
+                .map(|(j, _)| Item::Constant(i, j));
+            let decls = (0..s.body.declarations.len())
+                .map(|j| Item::Declaration(i, j));
+            let names = constants.chain(decls)
+                .map(|item| (out.decl(item).name(), item))
+                .collect();
+            out.names.push(names);
+        }
+        out
+    }
+
+    fn decl(&self, item: Item) -> Decl<'a> {
+        let body = &self.syntax.0[item.schema()].body;
+        match item {
+            Item::Declaration(_, i) => match &body.declarations[i] {
+                DeclarationOrRuleDecl::Declaration(d) => Decl::from(d),
+                DeclarationOrRuleDecl::RuleDecl(r) => Decl::Rule(r),
+            },
+            Item::Constant(_, i) =>
+                Decl::Constant(&body.constants.as_ref().unwrap().0[i]),
+        }
+    }
+
+    /// Finds the declaration which `name` refers to at the top level of a
+    /// schema, following its interface specifications.  If `use_only` is
+    /// set, then only entities and types are visible (as through a bare
+    /// `USE FROM`).  Names from schemas which weren't parsed are ignored.
+    fn lookup(&self, schema: usize, name: &'a str, use_only: bool,
+              seen: &mut HashSet<(usize, &'a str, bool)>)
+        -> Result<Option<Item>, FlattenError>
+    {
+        if !seen.insert((schema, name, use_only)) {
+            return Ok(None);
+        }
+        if let Some(item) = self.names[schema].get(name) {
+            let visible = !use_only ||
+                matches!(self.decl(*item), Decl::Entity(_) | Decl::Type(_));
+            return Ok(if visible { Some(*item) } else { None });
+        }
+        for i in &self.syntax.0[schema].body.interfaces {
+            let (from_name, items, everything) = interface(i);
+            let from = match self.schemas.get(from_name) {
+                Some(f) => *f,
+                None => continue,
+            };
+            if items.is_empty() {
+                let found = self.lookup(from, name, use_only || !everything,
+                                        seen)?;
+                if found.is_some() {
+                    return Ok(found);
+                }
+            }
+            if let Some((orig, _)) = items.into_iter().find(|i| i.1 == name) {
+                return match self.lookup(from, orig, use_only, seen)? {
+                    Some(item) => Ok(Some(item)),
+                    None => Err(FlattenError::Missing {
+                        schema: from_name.to_owned(),
+                        name: orig.to_owned(),
+                    }),
+                };
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds every declaration which is visible at the top level of a schema,
+    /// along with the name which it's visible under
+    fn visible(&self, schema: usize, use_only: bool,
+               out: &mut Vec<(Item, &'a str)>,
+               seen: &mut HashSet<(usize, bool)>) -> Result<(), FlattenError>
+    {
+        if !seen.insert((schema, use_only)) {
+            return Ok(());
+        }
+        out.extend(self.names[schema].iter()
+            .filter(|(_, item)| !use_only ||
+                matches!(self.decl(**item), Decl::Entity(_) | Decl::Type(_)))
+            .map(|(name, item)| (*item, *name)));
+        for i in &self.syntax.0[schema].body.interfaces {
+            let (from, items, everything) = interface(i);
+            let from = match self.schemas.get(from) {
+                Some(f) => *f,
+                None => continue,
+            };
+            if items.is_empty() {
+                self.visible(from, use_only || !everything, out, seen)?;
+            }
+            for (_, rename) in items {
+                out.extend(self.lookup(schema, rename, use_only,
+                                       &mut HashSet::new())?
+                    .map(|item| (item, rename)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the schemas which are reachable through interfaces from a
+    /// schema (including itself), in order of index
+    fn involved(&self, schema: usize) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        let mut todo = vec![schema];
+        while let Some(s) = todo.pop() {
+            if seen.insert(s) {
+                todo.extend(self.syntax.0[s].body.interfaces.iter()
+                    .filter_map(|i| self.schemas.get(interface(i).0)));
+            }
+        }
+        let mut out: Vec<_> = seen.into_iter().collect();
+        out.sort_unstable();
+        out
+    }
+
+    /// Collects every declaration that a long form of `root` needs: the items
+    /// which are visible in it, everything they refer to (transitively), and
+    /// any subtype constraints and rules which apply to included entities.
+    /// Returns the items with the root's own declarations first, each with
+    /// the name which it's used under (which differs from its declared name
+    /// if it was imported with `AS`).
+    fn closure(&self, root: usize)
+        -> Result<Vec<(Item, String)>, FlattenError>
+    {
+        let mut todo = Vec::new();
+        self.visible(root, false, &mut todo, &mut HashSet::new())?;
+
+        let involved = self.involved(root);
+        let mut done: HashMap<Item, &str> = HashMap::new();
+        let mut names: HashMap<&str, Item> = HashMap::new();
+        while !todo.is_empty() {
+            while let Some((item, name)) = todo.pop() {
+                let d = self.decl(item);
+                if let Some(prev) = done.get(&item) {
+                    if *prev != name {
+                        let rename = if *prev == d.name() { name } else { prev };
+                        return Err(FlattenError::Renamed {
+                            schema: self.syntax.0[item.schema()].id.0.to_owned(),
+                            name: d.name().to_owned(),
+                            rename: rename.to_owned(),
+                        });
+                    }
+                    continue;
+                }
+                done.insert(item, name);
+                if let Some(prev) = names.insert(name, item) {
+                    return Err(FlattenError::Conflict {
+                        name: name.to_owned(),
+                        schemas: (self.syntax.0[prev.schema()].id.0.to_owned(),
+                                  self.syntax.0[item.schema()].id.0.to_owned()),
+                    });
+                }
+                for name in references(d) {
+                    todo.extend(self.lookup(item.schema(), name, false,
+                                            &mut HashSet::new())?
+                        .map(|item| (item, name)));
+                }
+            }
+
+            // Subtype constraints and rules aren't referred to by name, so
+            // pull in any which only apply to entities that are now included
+            for &s in &involved {
+                let decls = &self.syntax.0[s].body.declarations;
+                for (i, d) in decls.iter().enumerate() {
+                    let item = Item::Declaration(s, i);
+                    if done.contains_key(&item) {
+                        continue;
+                    }
+                    let entities = match d {
+                        DeclarationOrRuleDecl::Declaration(
+                            Declaration::SubtypeConstraint(c)) =>
+                                vec![(c.0).1.0],
+                        DeclarationOrRuleDecl::RuleDecl(r) =>
+                            r.rule_head.entities.iter().map(|e| e.0).collect(),
+                        _ => continue,
+                    };
+                    let mut applies = true;
+                    for e in entities {
+                        let found = self.lookup(s, e, false,
+                                                &mut HashSet::new())?;
+                        applies &= found.map(|f| done.contains_key(&f))
+                            .unwrap_or(false);
+                    }
+                    if applies {
+                        todo.push((item, self.decl(item).name()));
+                    }
+                }
+            }
+        }
+
+        let mut out: Vec<_> = done.into_iter()
+            .map(|(item, name)| (item, name.to_owned()))
+            .collect();
+        out.sort_unstable_by_key(|(i, _)| (i.schema() != root, *i));
+        Ok(out)
+    }
+}
+
+/// Converts a short-form schema into a single long-form schema, by copying in
+/// every declaration that it uses from the other schemas in `syntax`.
+///
+/// The result keeps the name and version of the original schema.  Its own
+/// declarations come first (in their original order), followed by those from
+/// other schemas.  Interfaces to schemas which aren't part of `syntax` are
+/// kept, since their items can't be copied in.
+///
+/// An item imported with `AS` is declared under its new name, which only
+/// works if nothing else refers to it by its original name.
+pub fn flatten<'a>(syntax: Syntax<'a>, schema: &str)
+    -> Result<SchemaDecl<'a>, FlattenError>
+{
+    let (root, items, involved) = {
+        let f = Flattener::new(&syntax);
+        let root = *f.schemas.get(schema)
+            .ok_or_else(|| FlattenError::UnknownSchema(schema.to_owned()))?;
+        (root, f.closure(root)?, f.involved(root))
+    };
+
+    let known: HashSet<&str> = syntax.0.iter().map(|s| s.id.0).collect();
+    // Local names given with `AS`, to declare renamed items under
+    let renames: HashSet<&'a str> = syntax.0.iter()
+        .flat_map(|s| s.body.interfaces.iter())
+        .flat_map(|i| interface(i).1)
+        .map(|(_, rename)| rename)
+        .collect();
+    let mut interfaces = Vec::new();
+    let mut constants: Vec<Vec<Option<ConstantBody>>> = Vec::new();
+    let mut declarations: Vec<Vec<Option<DeclarationOrRuleDecl>>> = Vec::new();
+    let mut id = None;
+    for (i, s) in syntax.0.into_iter().enumerate() {
+        if i == root {
+            id = Some((s.id, s.version));
+        }
+        if involved.contains(&i) {
+            interfaces.extend(s.body.interfaces.into_iter()
+                .filter(|i| !known.contains(interface(i).0)));
+        }
+        constants.push(s.body.constants.into_iter()
+            .flat_map(|c| c.0.into_iter())
+            .map(Some)
+            .collect());
+        declarations.push(s.body.declarations.into_iter().map(Some).collect());
+    }
+
+    let mut body = SchemaBody {
+        interfaces,
+        constants: None,
+        declarations: Vec::new(),
+    };
+    let mut consts = Vec::new();
+    for (item, name) in items {
+        let name = renames.get(name.as_str()).copied();
+        match item {
+            Item::Constant(s, i) => {
+                let mut c = constants[s][i].take().unwrap();
+                if let Some(name) = name {
+                    c.constant_id.0 = name;
+                }
+                consts.push(c)
+            },
+            Item::Declaration(s, i) => {
+                let mut d = declarations[s][i].take().unwrap();
+                if let Some(name) = name {
+                    rename(&mut d, name);
+                }
+                body.declarations.push(d)
+            },
+        }
+    }
+    if !consts.is_empty() {
+        body.constants = Some(ConstantDecl(consts));
+    }
+
+    let (id, version) = id.unwrap();
+    Ok(SchemaDecl { id, version, body })
+}
+
+/// Changes the name that a declaration is declared under
+fn rename<'a>(d: &mut DeclarationOrRuleDecl<'a>, name: &'a str) {
+    match d {
+        DeclarationOrRuleDecl::Declaration(d) => match d {
+            Declaration::Entity(e) => (e.0).0.0 = name,
+            Declaration::Function(f) => f.function_head.id.0 = name,
+            Declaration::Procedure(p) => p.0.procedure_id.0 = name,
+            Declaration::SubtypeConstraint(c) => (c.0).0.0 = name,
+            Declaration::Type(t) => t.type_id.0 = name,
+        },
+        DeclarationOrRuleDecl::RuleDecl(r) => r.rule_head.rule_id.0 = name,
+    }
+}
+
+/// Returns the schemas which no other schema in `syntax` interfaces with, in
+/// file order.  In a file which holds an application protocol along with the
+/// resource schemas that it uses, this is the protocol's schema, i.e. the
+/// one to pass to [`flatten`].
+pub fn roots<'a>(syntax: &Syntax<'a>) -> Vec<&'a str> {
+    let used: HashSet<&str> = syntax.0.iter()
+        .flat_map(|s| s.body.interfaces.iter()
+            .map(|i| interface(i).0)
+            .filter(move |i| *i != s.id.0))
+        .collect();
+    syntax.0.iter()
+        .map(|s| s.id.0)
+        .filter(|s| !used.contains(s))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::{parse, strip_comments_and_lower}, resolve::resolve};
+
+    const SCHEMAS: &[u8] = br#"SCHEMA app;
+USE FROM shapes (shape);
+REFERENCE FROM util (helper);
+ENTITY thing; s : shape; END_ENTITY;
+END_SCHEMA;
+SCHEMA shapes;
+REFERENCE FROM util;
+ENTITY shape; r : radius; WHERE wr1: helper(r) > min_r; END_ENTITY;
+TYPE radius = REAL; END_TYPE;
+ENTITY unused; END_ENTITY;
+SUBTYPE_CONSTRAINT sc FOR shape; ABSTRACT SUPERTYPE; ONEOF (shape);
+END_SUBTYPE_CONSTRAINT;
+RULE r1 FOR (shape); WHERE wr1: SIZEOF(shape) >= 0; END_RULE;
+END_SCHEMA;
+SCHEMA util;
+CONSTANT min_r : REAL := 0.5; unused_c : REAL := 1.0; END_CONSTANT;
+FUNCTION helper(x : REAL) : REAL; RETURN (x); END_FUNCTION;
+FUNCTION other(x : REAL) : REAL; RETURN (x); END_FUNCTION;
+END_SCHEMA;
+"#;
+
+    #[test]
+    fn test_flatten() {
+        let s = strip_comments_and_lower(SCHEMAS);
+        let flat = flatten(parse(&s).unwrap(), "app").unwrap();
+        assert_eq!(flat.id.0, "app");
+        assert!(flat.body.interfaces.is_empty());
+
+        let names: Vec<&str> = flat.body.declarations.iter()
+            .map(|d| match d {
+                DeclarationOrRuleDecl::Declaration(d) => Decl::from(d).name(),
+                DeclarationOrRuleDecl::RuleDecl(r) => r.rule_head.rule_id.0,
+            })
+            .collect();
+        assert_eq!(names, ["thing", "shape", "radius", "sc", "r1", "helper"]);
+        let consts: Vec<&str> = flat.body.constants.iter()
+            .flat_map(|c| c.0.iter().map(|c| c.constant_id.0))
+            .collect();
+        assert_eq!(consts, ["min_r"]);
+
+        // The long form must stand on its own
+        let syntax = Syntax(vec![flat]);
+        assert!(resolve(&syntax).1.is_empty());
+        assert_eq!(roots(&syntax), ["app"]);
+    }
+
+    #[test]
+    fn test_roots() {
+        let s = strip_comments_and_lower(SCHEMAS);
+        assert_eq!(roots(&parse(&s).unwrap()), ["app"]);
+
+        let s = strip_comments_and_lower(b"SCHEMA a; END_SCHEMA;
+SCHEMA b; REFERENCE FROM c; END_SCHEMA;
+SCHEMA c; END_SCHEMA;");
+        assert_eq!(roots(&parse(&s).unwrap()), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_flatten_errors() {
+        let s = strip_comments_and_lower(SCHEMAS);
+        assert_eq!(flatten(parse(&s).unwrap(), "nope").unwrap_err(),
+                   FlattenError::UnknownSchema("nope".to_owned()));
+
+        // A renamed item which is also used under its original name
+        let s = strip_comments_and_lower(br#"SCHEMA a;
+USE FROM b (x AS y, w);
+ENTITY z; v : y; END_ENTITY;
+END_SCHEMA;
+SCHEMA b; ENTITY x; END_ENTITY; ENTITY w; u : x; END_ENTITY; END_SCHEMA;
+"#);
+        assert_eq!(flatten(parse(&s).unwrap(), "a").unwrap_err(),
+                   FlattenError::Renamed {
+                       schema: "b".to_owned(),
+                       name: "x".to_owned(),
+                       rename: "y".to_owned(),
+                   });
+    }
+
+    #[test]
+    fn test_flatten_renamed() {
+        let s = strip_comments_and_lower(br#"SCHEMA a;
+USE FROM b (x AS y);
+REFERENCE FROM c (f AS g);
+ENTITY z; v : y; WHERE wr1: g(v); END_ENTITY;
+END_SCHEMA;
+SCHEMA b; ENTITY x; END_ENTITY; END_SCHEMA;
+SCHEMA c;
+FUNCTION f(x : GENERIC) : BOOLEAN; RETURN (TRUE); END_FUNCTION;
+END_SCHEMA;
+"#);
+        let flat = flatten(parse(&s).unwrap(), "a").unwrap();
+        let names: Vec<&str> = flat.body.declarations.iter()
+            .map(|d| match d {
+                DeclarationOrRuleDecl::Declaration(d) => Decl::from(d).name(),
+                DeclarationOrRuleDecl::RuleDecl(r) => r.rule_head.rule_id.0,
+            })
+            .collect();
+        assert_eq!(names, ["z", "y", "g"]);
+        assert!(resolve(&Syntax(vec![flat])).1.is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt::Write;
 use std::collections::{HashSet, HashMap};
-use crate::parse::*;
+use crate::{
+    flatten::{flatten, roots},
+    parse::*,
+    remark::Docs,
+};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Helper types to use when doing code-gen
@@ -79,10 +83,110 @@ impl <'a> TypeMap<'a> {
         let v = self.1.get(s).unwrap();
         let m = match v {
             Ref::Entity(e) => e.to_type(self),
-            Ref::Type(t) => t.to_type(self),
+            Ref::Type(t) => t.to_type(s, self),
         };
         self.0.insert(s, m);
     }
+    /// Looks up an `ENUMERATION` or `SELECT` type by name
+    fn constructed(&self, s: &str) -> Option<&'a ConstructedTypes<'a>> {
+        match self.1.get(s) {
+            Some(Ref::Type(UnderlyingType::Constructed(c))) => Some(c),
+            _ => None,
+        }
+    }
+    /// Returns the names of types which are declared `BASED_ON` the given
+    /// type, sorted so that the output doesn't depend on the HashMap
+    fn extensions(&self, s: &str) -> Vec<&'a str> {
+        let mut out: Vec<&'a str> = self.1.iter()
+            .filter(|(_, r)| match r {
+                Ref::Type(UnderlyingType::Constructed(c)) =>
+                    c.own_items().1 == Some(s),
+                _ => false,
+            })
+            .map(|(k, _)| *k)
+            .collect();
+        out.sort_unstable();
+        out
+    }
+    /// Collects the items (or `SELECT` options) of a type and the types it's
+    /// based on, with the base's items first
+    fn items_up(&self, s: &str, out: &mut Vec<&'a str>, seen: &mut Vec<&'a str>) {
+        if let Some(c) = self.constructed(s) {
+            let (items, base) = c.own_items();
+            if let Some(b) = base {
+                if !seen.contains(&b) {
+                    seen.push(b);
+                    self.items_up(b, out, seen);
+                }
+            }
+            out.extend(items);
+        }
+    }
+    /// Collects the items (or `SELECT` options) added by every extension of
+    /// a type, recursively
+    fn items_down(&self, s: &str, out: &mut Vec<&'a str>, seen: &mut Vec<&'a str>) {
+        for e in self.extensions(s) {
+            if !seen.contains(&e) {
+                seen.push(e);
+                out.extend(self.constructed(e).unwrap().own_items().0);
+                self.items_down(e, out, seen);
+            }
+        }
+    }
+    /// Builds an `ENUMERATION` or `SELECT` type.  Following ISO 10303-11
+    /// (2004), a type `BASED_ON` another has the base's items as well as its
+    /// own, and an extensible type also includes the items of every type
+    /// which extends it, since instances of the extensions may be used
+    /// wherever the base is expected.
+    fn constructed_type(&self, s: &'a str) -> Type<'a> {
+        let mut items = Vec::new();
+        self.items_up(s, &mut items, &mut vec![s]);
+        self.items_down(s, &mut items, &mut vec![s]);
+        let mut unique = HashSet::new();
+        items.retain(|i| unique.insert(*i));
+        match self.constructed(s).unwrap() {
+            ConstructedTypes::Enumeration(_) => Type::Enum(items),
+            ConstructedTypes::Select(_) => Type::Select(items),
+        }
+    }
+    /// Returns the name of the `ParamKind` which a STEP file uses to write a
+    /// value of the given Rust type.  `names` maps from camel-case type names
+    /// back to keys in the type map.
+    fn param_kind(&self, rtype: &str, names: &HashMap<String, &'a str>)
+        -> &'static str
+    {
+        if rtype.starts_with("Vec<") || rtype.starts_with("ArrayVec::<") ||
+           rtype.starts_with("Option<")
+        {
+            return "List";
+        }
+        match rtype {
+            "f64" => "Real",
+            "i64" => "Integer",
+            "&'a str" => "Str",
+            "usize" => "Binary",
+            "bool" | "Logical" => "Logical",
+            _ => {
+                let name = names[rtype.trim_end_matches("<'a>")];
+                match &self.0[name] {
+                    Type::Entity { .. } => "Ref",
+                    Type::Redeclared(r) =>
+                        self.param_kind(&self.to_rtype(r), names),
+                    Type::RedeclaredPrimitive(p) | Type::Primitive(p) =>
+                        self.param_kind(p, names),
+                    Type::Enum(_) => "Enum",
+                    // A SELECT of entities is parsed as an Id; otherwise, the
+                    // value could be written as any of its members
+                    Type::Select(_) => if self.is_entity(name) {
+                        "Ref"
+                    } else {
+                        "Any"
+                    },
+                    Type::Aggregation { .. } => "List",
+                }
+            },
+        }
+    }
     fn attributes(&mut self, s: &'a str) -> Vec<AttributeData<'a>> {
         if !self.0.contains_key(s) {
             self.build(s);
@@ -138,10 +242,32 @@ impl<'a> Type<'a> {
         }
         Ok(())
     }
-    fn write_type<W>(&self, name: &str, buf: &mut W, type_map: &TypeMap) -> std::fmt::Result
+    fn write_attributes<W>(&self, name: &str, buf: &mut W, type_map: &TypeMap,
+                           names: &HashMap<String, &str>) -> std::fmt::Result
+        where W: std::fmt::Write
+    {
+        if let Type::Entity{attrs, ..} = self {
+            writeln!(buf, r#"        "{}" => &["#, capitalize(name))?;
+            for a in attrs {
+                let kind = if a.derived {
+                    "Derived"
+                } else {
+                    type_map.param_kind(&a.type_, names)
+                };
+                writeln!(buf, r#"            ("{}", {}, {}),"#,
+                         a.name, kind, a.optional)?;
+            }
+            writeln!(buf, "        ],")?;
+        }
+        Ok(())
+    }
+
+    fn write_type<W>(&self, name: &str, buf: &mut W, type_map: &TypeMap,
+                     docs: &Docs) -> std::fmt::Result
         where W: std::fmt::Write
     {
         let camel_name = to_camel(name);
+        write_docs(buf, docs.get(name), "")?;
         match self {
             Type::Redeclared(c) => {
                 writeln!(buf,r#"
@@ -181,8 +307,11 @@ pub enum {}<'a> {{ // enum", camel_name)?;
                 for v in c {
                     writeln!(buf, "    {},", to_camel(v))?;
                 }
+                // UNKNOWN is a reserved word, so it can't clash with an item
                 writeln!(buf,
-                    r#"    _Unused(std::marker::PhantomData<&'a ()>),
+                    r#"    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }}
 impl<'a> Parse<'a> for {0}<'a> {{
     fn parse(s: &'a str) -> IResult<'a, Self> {{
@@ -194,7 +323,7 @@ impl<'a> Parse<'a> for {0}<'a> {{
                     writeln!(buf, r#"            "{}" => {},"#,
                         capitalize(enum_tag), to_camel(enum_tag))?;
                 }
-                writeln!(buf, r#"            _ => return nom_alt_err(s),
+                writeln!(buf, r#"            t => Unknown(t),
         }};
         Ok((s, e))
     }}
@@ -317,13 +446,15 @@ pub struct {}_<'a> {{ // entity", camel_name)?;
                     if a.derived {
                         continue;
                     }
+                    write_docs(buf, docs.get(&format!("{}.{}", name, a.name)),
+                               "    ")?;
                     if a.dupe {
                         write!(buf, "    pub {}__{}: ", a.from.unwrap(), a.name)?;
                     } else {
                         write!(buf, "    pub {}: ", a.name)?;
                     }
                     if a.optional {
-                        writeln!(buf, "Option<{}>,", a.type_)?;
+                        writeln!(buf, "Value<{}>,", a.type_)?;
                     } else {
                         writeln!(buf, "{},", a.type_)?;
                     }
@@ -364,7 +495,7 @@ impl<'a> ParseFromChunks<'a> for {0}_<'a> {{
                             write!(buf, "        let (s, {})", a.name)?;
                         }
                         if a.optional {
-                            write!(buf, " = param_from_chunks::<Option<{}>>",
+                            write!(buf, " = param_from_chunks::<Value<{}>>",
                                    a.type_)?;
                         } else {
                             write!(buf, " = param_from_chunks::<{}>", a.type_)?;
@@ -425,9 +556,47 @@ enum Ref<'a> {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Generates a Rust STEP parser from a parsed EXPRESS file.  See
+/// [`gen_schema`] for details.
+///
+/// If the file contains more than one schema, they're first flattened into
+/// the single schema which isn't used by any of the others (see
+/// [`roots`]), and `s` is left holding that long-form schema.
+///
+/// # Panics
+/// If there isn't exactly one such schema, or it can't be flattened.
 pub fn gen(s: &mut Syntax) -> Result<String, std::fmt::Error> {
-    assert!(s.0.len() == 1, "Multiple schemas are unsupported");
+    if s.0.len() > 1 {
+        let root = match roots(s).as_slice() {
+            [r] => *r,
+            r => panic!("Expected one top-level schema, found {:?}", r),
+        };
+        let syntax = Syntax(std::mem::take(&mut s.0));
+        match flatten(syntax, root) {
+            Ok(f) => s.0.push(f),
+            Err(e) => panic!("Could not flatten {}: {}", root, e),
+        }
+    }
+    gen_schema(&mut s.0[0])
+}
 
+/// Generates Rust code for a single schema.
+///
+/// Each `ENTITY` becomes a struct (with inherited attributes flattened in),
+/// `ENUMERATION` and `SELECT` types become enums, aggregates become `Vec` or
+/// `ArrayVec` (if their upper bound is known), and other `TYPE`s become
+/// newtype wrappers.  The output also includes the top-level `Entity` enum
+/// and its parser; it's meant to be dropped into the `step` crate, which
+/// provides the supporting parsers and traits.
+pub fn gen_schema(s: &mut SchemaDecl) -> Result<String, std::fmt::Error> {
+    gen_schema_with_docs(s, &Docs::default())
+}
+
+/// Generates Rust code for a single schema, as in [`gen_schema`], adding doc
+/// comments to types and struct fields from the schema's remarks.
+pub fn gen_schema_with_docs(s: &mut SchemaDecl, docs: &Docs)
+    -> Result<String, std::fmt::Error>
+{
     // First pass: collect entity names, then convert ambiguous IDs in SELECT
     // data types into Entity or Type refs
     let mut entity_names = HashSet::new();
@@ -456,11 +625,13 @@ pub fn gen(s: &mut Syntax) -> Result<String, std::fmt::Error> {
     keys.sort_unstable();
     let mut buf = String::new();
     writeln!(&mut buf, "// Autogenerated file, do not hand-edit!
+// Regenerate step/src/ap214.rs with step/gen_ap214.sh (see the README).
 use crate::{{
     id::{{Id, HasId}},
-    parse::{{IResult, Logical, Derived, Parse, ParseFromChunks, nom_alt_err,
+    parse::{{IResult, Logical, Derived, Value, Parse, ParseFromChunks, nom_alt_err,
             parse_enum_tag, param_from_chunks, parse_complex_mapping}},
     step_file::FromEntity,
+    check::ParamKind,
 }};
 use nom::{{
     branch::{{alt}},
@@ -473,7 +644,7 @@ use nom::{{
 use arrayvec::ArrayVec;")?;
 
     for k in &keys {
-        type_map.0[k].write_type(k, &mut buf, &type_map)?;
+        type_map.0[k].write_type(k, &mut buf, &type_map, docs)?;
     }
     writeln!(&mut buf, "#[derive(Debug)]
 pub enum Entity<'a> {{")?;
@@ -508,6 +679,25 @@ pub fn superclasses_of(s: &str) -> &[&str] {{
     writeln!(&mut buf, "        _ => &[],
     }}
 }}
+
+/// Returns the name, kind, and optionality of each attribute of an entity,
+/// in the order in which they're written in a STEP file, or `None` if the
+/// entity isn't part of this schema.
+pub fn attributes_of(s: &str)
+    -> Option<&'static [(&'static str, ParamKind, bool)]>
+{{
+    use ParamKind::*;
+    let out: &'static [(&'static str, ParamKind, bool)] = match s {{")?;
+    let names: HashMap<String, &str> = keys.iter()
+        .map(|k| (to_camel(k), *k))
+        .collect();
+    for k in &keys {
+        type_map.0[k].write_attributes(k, &mut buf, &type_map, &names)?;
+    }
+    writeln!(&mut buf, "        _ => return None,
+    }};
+    Some(out)
+}}
 impl<'a> Entity<'a> {{
     pub fn upstream(&self) -> Vec<usize> {{
         let mut out = Vec::new();
@@ -532,6 +722,19 @@ impl<'a> Entity<'a> {{
     Ok(buf)
 }
 
+fn write_docs<W>(buf: &mut W, lines: &[String], indent: &str) -> std::fmt::Result
+    where W: std::fmt::Write
+{
+    for line in lines {
+        if line.is_empty() {
+            writeln!(buf, "{}///", indent)?;
+        } else {
+            writeln!(buf, "{}/// {}", indent, line)?;
+        }
+    }
+    Ok(())
+}
+
 fn capitalize(s: &str) -> String {
     s.chars().map(|c| c.to_uppercase().next().unwrap()).collect()
 }
@@ -564,23 +767,6 @@ fn to_camel(s: &str) -> String {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-impl<'a> Syntax<'a> {
-    fn collect_entity_names(&self, entity_names: &mut HashSet<&'a str>) {
-        for v in &self.0 {
-            v.collect_entity_names(entity_names);
-        }
-    }
-    fn build_ref_map(&'a self, ref_map: &mut HashMap<&'a str, Ref<'a>>) {
-        for v in &self.0 {
-            v.build_ref_map(ref_map);
-        }
-    }
-    fn disambiguate(&mut self, entity_names: &HashSet<&str>) {
-        for v in &mut self.0 {
-            v.disambiguate(entity_names);
-        }
-    }
-}
 impl<'a> SchemaDecl<'a> {
     fn collect_entity_names(&self, entity_names: &mut HashSet<&'a str>) {
         self.body.collect_entity_names(entity_names);
@@ -655,10 +841,10 @@ impl<'a> TypeDecl<'a> {
     }
 }
 impl<'a> UnderlyingType<'a> {
-    fn to_type(&'a self, type_map: &mut TypeMap<'a>) -> Type {
+    fn to_type(&'a self, name: &'a str, type_map: &mut TypeMap<'a>) -> Type {
         match self {
             UnderlyingType::Concrete(c) => c.to_type(type_map),
-            UnderlyingType::Constructed(c) => c.to_type(),
+            UnderlyingType::Constructed(_) => type_map.constructed_type(name),
         }
     }
 }
@@ -681,7 +867,7 @@ impl<'a> SimpleExpression<'a> {
             return None;
         }
         let factor = &term.0;
-        if !factor.1.is_none() {
+        if !factor.1.is_empty() {
             return None;
         }
         let simple_factor = &factor.0;
@@ -737,48 +923,32 @@ impl<'a> AggregationTypes<'a> {
     }
 }
 impl<'a> ConstructedTypes<'a> {
-    fn to_type(&'a self) -> Type {
+    /// Returns the items (or `SELECT` options) which this type declares
+    /// itself, and the type that it's `BASED_ON` (if any)
+    fn own_items(&self) -> (Vec<&'a str>, Option<&'a str>) {
         match self {
-            ConstructedTypes::Enumeration(e) => e.to_type(),
-            ConstructedTypes::Select(s) => s.to_type(),
-        }
-    }
-}
-impl<'a> EnumerationType<'a> {
-    fn to_type(&self) -> Type {
-        assert!(!self.extensible, "Extensible enumerations are not supported");
-        match self.items_or_extension.as_ref().unwrap() {
-            EnumerationItemsOrExtension::Items(e) => e.to_type(),
-            _ => panic!("Extensions not supported")
-        }
-    }
-}
-impl<'a> EnumerationItems<'a> {
-    fn to_type(&self) -> Type {
-        let mut out = Vec::new();
-        for e in &self.0 {
-            out.push(e.0);
-        }
-        Type::Enum(out)
-    }
-}
-impl<'a> SelectType<'a> {
-    fn to_type(&'a self) -> Type {
-        assert!(!self.extensible, "Cannot handle extensible lists");
-        assert!(!self.generic_entity, "Cannot handle generic entity lists");
-        match &self.list_or_extension {
-            SelectListOrExtension::List(e) => e.to_type(),
-            _ => panic!("Extensions not supported"),
-        }
-    }
-}
-impl<'a> SelectList<'a> {
-    fn to_type(&'a self) -> Type {
-        let mut out = Vec::new();
-        for e in &self.0 {
-            out.push(e.name());
+            ConstructedTypes::Enumeration(e) => match &e.items_or_extension {
+                None => (vec![], None),
+                Some(EnumerationItemsOrExtension::Items(i)) =>
+                    (i.0.iter().map(|e| e.0).collect(), None),
+                Some(EnumerationItemsOrExtension::Extension(x)) =>
+                    (x.enumeration_items.iter()
+                        .flat_map(|i| i.0.iter())
+                        .map(|e| e.0)
+                        .collect(),
+                     Some(x.type_ref.0)),
+            },
+            ConstructedTypes::Select(s) => match &s.list_or_extension {
+                SelectListOrExtension::List(l) =>
+                    (l.0.iter().map(NamedTypes::name).collect(), None),
+                SelectListOrExtension::Extension(x) =>
+                    (x.select_list.iter()
+                        .flat_map(|l| l.0.iter())
+                        .map(NamedTypes::name)
+                        .collect(),
+                     Some(x.type_ref.0)),
+            },
         }
-        Type::Select(out)
     }
 }
 impl<'a> EntityDecl<'a> {
@@ -791,8 +961,8 @@ impl<'a> EntityDecl<'a> {
             for d in &derive.0 {
                 match &d.0 {
                     AttributeDecl::Redeclared(r) => {
-                        // There can't be a RENAMED clause here
-                        assert!(r.1.is_none());
+                        // A RENAMED clause doesn't matter here, since the
+                        // attribute is matched by its original name
                         derived.insert((r.0.0.0.0, r.0.1.0.0));
                     }
                     AttributeDecl::Id(_) => continue,
@@ -872,13 +1042,6 @@ impl<'a> EntityDecl<'a> {
     }
 }
 impl<'a> AttributeDecl<'a> {
-    fn name(&self) -> &str {
-        match self {
-            AttributeDecl::Id(i) => i.0,
-            AttributeDecl::Redeclared(_) =>
-                panic!("No support for renamed attributes"),
-        }
-    }
     fn is_redeclared(&self) -> bool {
         match self {
             AttributeDecl::Id(_) => false,
@@ -912,7 +1075,7 @@ impl<'a> ParameterType<'a> {
 impl<'a> GeneralAggregationTypes<'a> {
     fn upper_bound(&self) -> Option<usize> {
         let upper: Option<&Bound2> = match &self {
-            GeneralAggregationTypes::Array(a) => Some(&a.bounds.1),
+            GeneralAggregationTypes::Array(a) => a.bounds.as_ref().map(|b| &b.1),
             GeneralAggregationTypes::Bag(_) => None,
             GeneralAggregationTypes::List(a) => a.bounds.as_ref().map(|b| &b.1),
             GeneralAggregationTypes::Set(a) => a.bounds.as_ref().map(|b| &b.1),
@@ -964,13 +1127,12 @@ impl<'a> ConstructedTypes<'a> {
 }
 impl<'a> SelectType<'a> {
     fn disambiguate(&mut self, entity_names: &HashSet<&str>) {
-        match &mut self.list_or_extension {
-            SelectListOrExtension::List(items) => {
-                for t in &mut items.0 {
-                    t.disambiguate(entity_names);
-                }
-            }
-            _ => panic!("Nope nope nope"),
+        let items = match &mut self.list_or_extension {
+            SelectListOrExtension::List(items) => Some(items),
+            SelectListOrExtension::Extension(e) => e.select_list.as_mut(),
+        };
+        for t in items.into_iter().flat_map(|i| i.0.iter_mut()) {
+            t.disambiguate(entity_names);
         }
     }
 }
@@ -984,7 +1146,7 @@ impl<'a> NamedTypes<'a> {
             };
         }
     }
-    fn name(&self) -> &str {
+    fn name(&self) -> &'a str {
         match self {
             NamedTypes::Entity(e) => e.0,
             NamedTypes::Type(e) => e.0,
@@ -992,3 +1154,114 @@ impl<'a> NamedTypes<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_gen_schema() {
+        let s = strip_comments_and_lower(b"SCHEMA tiny;
+TYPE label = STRING; END_TYPE;
+TYPE color = ENUMERATION OF (red, green); END_TYPE;
+TYPE shape_select = SELECT (circle, label); END_TYPE;
+ENTITY circle;
+  name : label;
+  radius : REAL;
+  tags : LIST [0:?] OF label;
+END_ENTITY;
+END_SCHEMA;");
+        let mut syntax = parse(&s).unwrap();
+        let out = gen_schema(&mut syntax.0[0]).unwrap();
+        assert!(out.contains("pub struct Circle_<'a> { // entity"));
+        assert!(out.contains("    pub radius: f64,"));
+        assert!(out.contains("    pub tags: Vec<Label<'a>>,"));
+        assert!(out.contains("pub enum Color<'a> { // enum"));
+        assert!(out.contains("    Unknown(&'a str),\n}"));
+        assert!(out.contains("            t => Unknown(t),"));
+        assert!(out.contains("pub enum ShapeSelect<'a> { // select"));
+        assert!(out.contains("pub struct Label<'a>(pub &'a str"));
+        assert!(out.contains("    Circle(Circle_<'a>),"));
+        assert!(out.contains(r#"        "CIRCLE" => &[
+            ("name", Str, false),
+            ("radius", Real, false),
+            ("tags", List, false),
+        ],"#));
+    }
+
+    #[test]
+    fn test_gen_multiple_schemas() {
+        let s = strip_comments_and_lower(b"SCHEMA app;
+USE FROM shapes (circle);
+ENTITY drawing; items : LIST [0:?] OF circle; END_ENTITY;
+END_SCHEMA;
+SCHEMA shapes;
+ENTITY circle; radius : REAL; END_ENTITY;
+ENTITY square; side : REAL; END_ENTITY;
+END_SCHEMA;");
+        let mut syntax = parse(&s).unwrap();
+        let out = gen(&mut syntax).unwrap();
+        assert_eq!(syntax.0.len(), 1);
+        assert_eq!(syntax.0[0].id.0, "app");
+        assert!(out.contains("    Drawing(Drawing_<'a>),"));
+        assert!(out.contains("    Circle(Circle_<'a>),"));
+        assert!(!out.contains("Square"));
+    }
+
+    #[test]
+    fn test_gen_extensions() {
+        let s = strip_comments_and_lower(b"SCHEMA app;
+USE FROM base (shape AS part);
+TYPE finish = EXTENSIBLE ENUMERATION OF (matte, gloss); END_TYPE;
+TYPE metal_finish = ENUMERATION BASED_ON finish WITH (brushed); END_TYPE;
+TYPE item_select = EXTENSIBLE GENERIC_ENTITY SELECT (part); END_TYPE;
+TYPE item_select_2 = SELECT BASED_ON item_select WITH (hole); END_TYPE;
+ENTITY hole; d : REAL; END_ENTITY;
+ENTITY drawing; f : finish; i : item_select; END_ENTITY;
+ENTITY plate SUBTYPE OF (drawing);
+  SELF\\drawing.f RENAMED plate_finish : metal_finish;
+DERIVE
+  SELF\\drawing.i RENAMED first : part := ?;
+END_ENTITY;
+END_SCHEMA;
+SCHEMA base; ENTITY shape; END_ENTITY; END_SCHEMA;");
+        let mut syntax = parse(&s).unwrap();
+        let out = gen(&mut syntax).unwrap();
+        assert!(out.contains("pub struct Part_<'a> { // entity"));
+        // The extensible type includes items from its extension
+        assert!(out.contains("pub enum Finish<'a> { // enum
+    Matte,
+    Gloss,
+    Brushed,
+"));
+        assert!(out.contains("pub enum MetalFinish<'a> { // enum
+    Matte,
+    Gloss,
+    Brushed,
+"));
+        assert!(out.contains("pub struct ItemSelect_<'a>("));
+        assert!(out.contains("pub struct ItemSelect2_<'a>("));
+        assert!(out.contains(r#"        "PLATE" => &[
+            ("f", Enum, false),
+            ("i", Derived, false),
+        ],"#));
+    }
+
+    #[test]
+    fn test_gen_docs() {
+        let (s, remarks) = crate::remark::strip_comments_keep_remarks(
+            b"SCHEMA tiny;
+(* A round thing *)
+ENTITY circle;
+  radius : REAL; -- Always positive
+END_ENTITY;
+(*\"tiny.circle.radius\" In millimeters *)
+END_SCHEMA;");
+        let mut syntax = parse(&s).unwrap();
+        let docs = Docs::new(&s, &syntax, &remarks);
+        let out = gen_schema_with_docs(&mut syntax.0[0], &docs).unwrap();
+        assert!(out.contains("/// A round thing\n/// Always positive\n"));
+        assert!(out.contains("    /// In millimeters\n    pub radius: f64,"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Position-based lookups, as needed by editors and language servers.
+//!
+//! The syntax tree doesn't store positions, but every name in it is a slice
+//! of the parser's input, so declarations and references are located by
+//! where their names point.  Offsets are byte offsets into that input (e.g.
+//! the output of [`strip_comments_and_lower`]); use
+//! [`locate`](crate::error::locate) to turn a name into a line and column.
+//!
+//! [`strip_comments_and_lower`]: crate::parse::strip_comments_and_lower
+use std::ops::Range;
+
+use crate::{
+    error::offset_of,
+    parse::{skip_declaration, DeclarationOrRuleDecl, SchemaDecl},
+    resolve::{references, Decl},
+};
+
+/// A top-level declaration, along with where it appears in the input
+#[derive(Copy, Clone, Debug)]
+pub struct Located<'a> {
+    pub decl: Decl<'a>,
+    /// The whole declaration, from its keyword to the `;` which ends it.  For
+    /// a constant, this runs from its name to the end of its definition.
+    pub span: (usize, usize),
+    /// Offset of the declared name
+    pub name: usize,
+}
+
+impl Located<'_> {
+    /// Returns the whole declaration's span as a range of offsets
+    pub fn range(&self) -> Range<usize> {
+        self.span.0..self.span.1
+    }
+}
+
+/// Index of one schema's declarations by position
+pub struct Schema<'a> {
+    pub decl: &'a SchemaDecl<'a>,
+    /// The text that was passed to the parser
+    input: &'a str,
+    /// Top-level declarations, in the order in which they appear
+    decls: Vec<Located<'a>>,
+}
+
+impl<'a> Schema<'a> {
+    /// Indexes a schema, given the same text that was passed to the parser.
+    /// Declarations whose names don't point into `input` are left out.
+    pub fn new(input: &'a str, decl: &'a SchemaDecl<'a>) -> Self {
+        let mut decls = Vec::new();
+        let constants = decl.body.constants.iter().flat_map(|c| c.0.iter());
+        for c in constants {
+            let name = c.constant_id.0;
+            if let Some(start) = offset_of(input, name) {
+                let end = start + statement_end(&input[start..]);
+                decls.push(Located {
+                    decl: Decl::Constant(c), span: (start, end), name: start,
+                });
+            }
+        }
+        for d in &decl.body.declarations {
+            let d = match d {
+                DeclarationOrRuleDecl::Declaration(d) => Decl::from(d),
+                DeclarationOrRuleDecl::RuleDecl(r) => Decl::Rule(r),
+            };
+            let name = match offset_of(input, d.name()) {
+                Some(name) => name,
+                None => continue,
+            };
+            // The declaration's keyword is the word before its name
+            let before = input[..name].trim_end();
+            let start = before
+                .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            let rest = skip_declaration(&input[start..]);
+            let end = input[..input.len() - rest.len()].trim_end().len();
+            decls.push(Located { decl: d, span: (start, end), name });
+        }
+        Self { decl, input, decls }
+    }
+
+    /// Returns every top-level declaration, in the order in which they appear
+    pub fn declarations(&self) -> &[Located<'a>] {
+        &self.decls
+    }
+
+    /// Looks up a top-level declaration by name
+    pub fn declaration(&self, name: &str) -> Option<&Located<'a>> {
+        self.decls.iter().find(|d| d.decl.name() == name)
+    }
+
+    /// Finds the top-level declaration which contains the given offset.
+    /// Declarations nested within functions, procedures, and rules aren't
+    /// indexed, so this returns the enclosing top-level declaration.
+    pub fn declaration_at(&self, offset: usize) -> Option<&Located<'a>> {
+        self.decls.iter().find(|d| d.range().contains(&offset))
+    }
+
+    /// Finds every use of a top-level name within this schema's
+    /// declarations, returning the range of each one (in order).  The
+    /// declaration itself isn't included.
+    ///
+    /// References are found with the same rules as
+    /// [`resolve`](crate::resolve::resolve), so names which are shadowed by
+    /// local declarations (e.g. a function's local types) are skipped.  Bare
+    /// names in expressions can't always be told apart from attributes,
+    /// parameters, or local variables, so one of those with the same name
+    /// as a top-level declaration may be reported too.
+    pub fn find_references(&self, name: &str) -> Vec<Range<usize>> {
+        let mut out: Vec<Range<usize>> = self.decls.iter()
+            .flat_map(|d| references(d.decl))
+            .filter(|r| *r == name)
+            .filter_map(|r| offset_of(self.input, r))
+            .map(|o| o..o + name.len())
+            .collect();
+        out.sort_by_key(|r| r.start);
+        out.dedup();
+        out
+    }
+}
+
+/// Returns the length of the text up to and including the next `;` which
+/// isn't within a string literal, or the whole text if there's none
+fn statement_end(s: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, ';') => return i + 1,
+            _ => (),
+        }
+    }
+    s.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_index() {
+        let s = strip_comments_and_lower(br#"SCHEMA geometry;
+CONSTANT
+  origin : point := point(0.0, 0.0);
+  label : STRING := 'a;b';
+END_CONSTANT;
+TYPE distance = REAL;
+WHERE
+  wr1 : SELF >= 0.0;
+END_TYPE;
+ENTITY point;
+  x : distance;
+  y : distance;
+END_ENTITY;
+FUNCTION dist(a, b : point) : distance;
+  LOCAL
+    d : REAL := 0.0;
+  END_LOCAL;
+  RETURN (d);
+END_FUNCTION;
+END_SCHEMA;"#);
+        let syntax = parse(&s).unwrap();
+        let schema = Schema::new(&s, &syntax.0[0]);
+        let names: Vec<&str> = schema.declarations().iter()
+            .map(|d| d.decl.name())
+            .collect();
+        assert_eq!(names, ["origin", "label", "distance", "point", "dist"]);
+
+        let at = |text: &str| s.find(text).unwrap();
+        let d = schema.declaration_at(at("y : distance")).unwrap();
+        assert_eq!(d.decl.name(), "point");
+        assert_eq!(&s[d.range()], "entity point;\n  x : distance;\n  \
+                                   y : distance;\nend_entity;");
+        assert_eq!(&s[schema.declaration("label").unwrap().range()],
+                   "label : string := 'a;b';");
+        assert_eq!(schema.declaration_at(at("return")).unwrap().decl.name(),
+                   "dist");
+        assert!(schema.declaration_at(at("end_schema")).is_none());
+
+        let refs = schema.find_references("distance");
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].start, at("distance;\n  y"));
+        assert!(refs.iter().all(|r| &s[r.clone()] == "distance"));
+        assert_eq!(schema.find_references("point").len(), 3);
+    }
+}
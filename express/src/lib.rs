@@ -1,2 +1,103 @@
+//! Parses and analyzes EXPRESS (ISO 10303-11) schemas.
+//!
+//! The crate is organized as follows:
+//! - [`ast`] holds the syntax tree
+//! - [`parse`] turns text into that tree, with [`error`] describing failures
+//! - [`resolve`] and [`typecheck`] check references and expressions
+//! - [`gen`] writes Rust code for a schema, as used by the `step` crate
+//! - the remaining modules build tools on top of those (diagrams, diffs,
+//!   data dictionaries, editor support, and streaming)
+//!
+//! Most users only need [`parse_schema`]:
+//! ```
+//! let text = express::strip_comments_and_lower(b"SCHEMA s;
+//! ENTITY e; x : REAL; END_ENTITY;
+//! END_SCHEMA;");
+//! let syntax = express::parse_schema(&text)?;
+//! assert_eq!(syntax.0[0].id.0, "s");
+//! # Ok::<(), express::ParseError>(())
+//! ```
+pub mod ast;
+pub mod error;
 pub mod parse;
+pub mod stream;
 pub mod gen;
+pub mod remark;
+pub mod resolve;
+pub mod typecheck;
+pub mod dot;
+pub mod flatten;
+pub mod diff;
+pub mod dictionary;
+pub mod index;
+
+pub use crate::parse::strip_comments_and_lower;
+
+/// Error returned by [`parse_schema`].  More variants may be added in
+/// future versions, so matches must include a wildcard arm.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The text doesn't match the EXPRESS grammar
+    Syntax(error::Error),
+}
+
+impl ParseError {
+    /// Returns the line and column (both starting from 1) where the error
+    /// was found
+    pub fn position(&self) -> (usize, usize) {
+        match self {
+            ParseError::Syntax(e) => (e.line, e.column),
+        }
+    }
+
+    /// Renders the error as a human-readable message, with a snippet of the
+    /// offending line.  `s` must be the text passed to [`parse_schema`].
+    pub fn render(&self, s: &str) -> String {
+        match self {
+            ParseError::Syntax(e) => error::render_error(s, e),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::Syntax(e) => write!(f, "Syntax error at {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Syntax(e) => Some(e),
+        }
+    }
+}
+
+/// Parses one or more schemas.  `s` must already have its comments removed
+/// and be in lower-case, as done by [`strip_comments_and_lower`]; the syntax
+/// tree borrows from it.
+///
+/// This is the same as [`parse::parse`], but with an error type which can
+/// grow without breaking callers.
+pub fn parse_schema(s: &str) -> Result<ast::Syntax<'_>, ParseError> {
+    parse::parse(s).map_err(ParseError::Syntax)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_schema() {
+        let s = strip_comments_and_lower(b"SCHEMA s;\nENTITY e;\n  x : ;\n\
+                                           END_ENTITY;\nEND_SCHEMA;");
+        let e = parse_schema(&s).unwrap_err();
+        assert_eq!(e.position(), (3, 7));
+        assert!(e.to_string().starts_with("Syntax error at 3:7"));
+        assert!(e.render(&s).contains("3 |   x : ;"));
+        assert!(std::error::Error::source(&e).is_some());
+    }
+}
@@ -1,20 +1,60 @@
 use memchr::{memchr, memchr_iter};
+use crate::{error::Error, remark::Remark};
+// With the `serde` feature, every AST node can be serialized.  Nodes borrow
+// identifiers from the input text, so they must be deserialized from a
+// borrowed source (e.g. `serde_json::from_str`) without escaped strings.
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 use nom::{
     branch::{alt},
-    character::complete::{alpha1, multispace0},
-    combinator::{map, map_opt, recognize, opt, not, peek},
+    character::complete::{multispace0},
+    combinator::{cut, map, map_opt, recognize, opt, not, peek},
     error::*,
-    multi::{fold_many1, fold_many0, many0_count, separated_list0, separated_list1, many0, many1},
+    multi::{fold_many1, fold_many0, separated_list0, separated_list1, many0, many1},
     sequence::{delimited, pair, preceded, tuple, terminated},
 };
+use std::borrow::Cow;
 
-pub type IResult<'a, U> = nom::IResult<&'a str, U, nom::error::VerboseError<&'a str>>;
+pub type IResult<'a, U> = nom::IResult<&'a str, U, ParseFailure<'a>>;
+
+/// Parser error, equivalent to nom's [`VerboseError`] (the innermost failure,
+/// followed by the contexts of enclosing productions).
+///
+/// Most errors are discarded while backtracking through alternatives, so the
+/// innermost failure is stored inline; only errors which pick up a context
+/// need to allocate.  This avoids millions of allocations on large schemas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseFailure<'a> {
+    pub first: (&'a str, VerboseErrorKind),
+    pub contexts: Vec<(&'a str, VerboseErrorKind)>,
+}
+
+impl<'a> ParseError<&'a str> for ParseFailure<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self { first: (input, VerboseErrorKind::Nom(kind)), contexts: vec![] }
+    }
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        // Diagnostics only report contexts, so the trail of combinators
+        // which failed (e.g. every `alt`) isn't recorded
+        other
+    }
+    fn from_char(input: &'a str, c: char) -> Self {
+        Self { first: (input, VerboseErrorKind::Char(c)), contexts: vec![] }
+    }
+}
+
+impl<'a> ContextError<&'a str> for ParseFailure<'a> {
+    fn add_context(input: &'a str, ctx: &'static str, mut other: Self) -> Self {
+        other.contexts.push((input, VerboseErrorKind::Context(ctx)));
+        other
+    }
+}
 
 fn build_err<'a, U>(s: &'a str, msg: &'static str) -> IResult<'a, U> {
-    Err(nom::Err::Error(
-        VerboseError {
-            errors: vec![(s, VerboseErrorKind::Context(msg))]
-        }))
+    Err(nom::Err::Error(ParseFailure {
+        first: (s, VerboseErrorKind::Context(msg)),
+        contexts: vec![],
+    }))
 }
 
 /// Returns a parser which runs `p` then consumes all whitespace
@@ -24,22 +64,54 @@ fn ws<'a, U, F>(p: F) -> impl FnMut(&'a str) -> IResult<'a, U>
     terminated(p, multispace0)
 }
 
-/// Overloaded version of nom's `char` that eats trailing whitespace
+/// Skips the whitespace matched by `multispace0`, without going through nom
+fn skip_ws(s: &str) -> &str {
+    s.trim_start_matches(&[' ', '\t', '\r', '\n'][..])
+}
+
+/// Overloaded version of nom's `char` that eats trailing whitespace.  The
+/// punctuation, tag, and keyword parsers are the innermost loops of the
+/// parser, so they're written out by hand rather than built from nom's
+/// combinators.
 fn char<'a>(c: char) -> impl FnMut(&'a str) -> IResult<'a, char> {
-    ws(nom::character::complete::char(c))
+    move |s: &'a str| match s.strip_prefix(c) {
+        Some(r) => Ok((skip_ws(r), c)),
+        None => Err(nom::Err::Error(ParseFailure::from_char(s, c))),
+    }
 }
 
-/// Overloaded version of nom's `tag` that eats trailing whitespace
-fn tag<'a>(s: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str> {
-    ws(nom::bytes::complete::tag(s))
+/// Overloaded version of nom's `tag` that eats trailing whitespace.  On
+/// failure, the tag is recorded in the error so that diagnostics can report
+/// what was expected.
+fn tag<'a>(t: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str> {
+    move |s: &'a str| match s.strip_prefix(t) {
+        Some(r) => Ok((skip_ws(r), &s[..t.len()])),
+        None => build_err(s, t),
+    }
 }
 
 /// Matches a specific keyword, which ensuring that it's not followed by
 /// a letter.  This avoids cases like `generic_expression` being parsed as
-/// `generic`, `_expression`.
-fn kw<'a>(s: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str> {
-    ws(terminated(nom::bytes::complete::tag(s),
-                  not(alt((letter, digit, char('_'))))))
+/// `generic`, `_expression`.  Keywords are case-insensitive, so `k` must be
+/// given in lower-case and will also match `END_ENTITY`, `End_Entity`, etc.
+fn kw<'a>(k: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str> {
+    move |s: &'a str| match s.get(..k.len()) {
+        Some(h) if h.eq_ignore_ascii_case(k) &&
+                   !s[k.len()..].starts_with(is_id_char) =>
+            Ok((skip_ws(&s[k.len()..]), h)),
+        _ => build_err(s, k),
+    }
+}
+
+/// Returns a parser which runs `p` if the input begins with the keyword `k`.
+/// Once the keyword has matched, the parser is committed: errors within `p`
+/// are fatal and tagged with `name`, so they're reported where they actually
+/// occurred (rather than as a failure to match the enclosing list).
+fn committed<'a, U, F>(k: &'static str, name: &'static str, p: F)
+    -> impl FnMut(&'a str) -> IResult<'a, U>
+    where F: FnMut(&'a str) -> IResult<'a, U>
+{
+    preceded(peek(kw(k)), cut(context(name, p)))
 }
 
 /// Returns a parser which recognizes '(' p ')' with optional whitespace
@@ -67,11 +139,23 @@ fn list1<'a, U, F>(c: char, p: F) -> impl FnMut(&'a str) -> IResult<'a, Vec<U>>
 /// lets you define them without as much boilerplate, with or without a
 /// separate parser function.
 macro_rules! alias {
-    ($a:ident $(< $lt:lifetime >)?, $b:ident) => {
+    ($a:ident < $lt:lifetime >, $b:ident) => {
         #[derive(Debug)]
-        pub struct $a $(< $lt >)?(pub $b $(< $lt >)?);
-        impl $(< $lt >)? $a $(< $lt >)?  {
-            fn parse(s: &$( $lt )? str) -> IResult<Self> {
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+                   serde(bound(deserialize = "'de: 'a")))]
+        pub struct $a<$lt>(pub $b<$lt>);
+        impl<$lt> $a<$lt> {
+            fn parse(s: &$lt str) -> IResult<Self> {
+                map($b::parse, Self)(s)
+            }
+        }
+    };
+    ($a:ident, $b:ident) => {
+        #[derive(Debug)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $a(pub $b);
+        impl $a {
+            fn parse(s: &str) -> IResult<Self> {
                 map($b::parse, Self)(s)
             }
         }
@@ -99,49 +183,367 @@ macro_rules! id_type {
     };
     ($a:ident) => {
         #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+                   serde(bound(deserialize = "'de: 'a")))]
         pub struct $a<'a>(pub &'a str);
     }
 }
 
 
+/// Lower-cases an identifier or keyword for comparison, without allocating
+/// in the common case where it's already lower-case
+fn lower(s: &str) -> Cow<'_, str> {
+    if s.bytes().any(|c| c.is_ascii_uppercase()) {
+        Cow::Owned(s.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Checks whether a character may start an identifier.
+///
+/// The standard only allows ASCII letters, but schemas from non-English
+/// vendors sometimes use accented or non-Latin names, so any alphabetic
+/// character is accepted.
+fn is_id_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || (!c.is_ascii() && c.is_alphabetic())
+}
+
+/// Checks whether a character may continue an identifier (see
+/// [`is_id_start`])
+fn is_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || (!c.is_ascii() && c.is_alphanumeric())
+}
+
+/// Decodes an EXPRESS file as UTF-8.  Older schemas are often written in
+/// ISO 8859-1, so a file which isn't valid UTF-8 is decoded as that instead.
+fn decode(data: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(data) {
+        Ok(s) => Cow::Borrowed(s),
+        Err(_) => Cow::Owned(data.iter().map(|c| *c as char).collect()),
+    }
+}
+
 /// Remove comments from an EXPRESS file and converts to lower-case.  This
-/// should be run before any parsers.
+/// should be run before any parsers.  String literals are left as-is.
+///
+/// The file is decoded as UTF-8 (or ISO 8859-1, if it isn't valid UTF-8);
+/// non-ASCII letters in identifiers are also converted to lower-case.
+///
+/// Newlines within comments are preserved, so that line numbers in error
+/// messages match the original file.
 pub fn strip_comments_and_lower(data: &[u8]) -> String {
+    strip_comments(data, true, None)
+}
+
+/// Removes comments from an EXPRESS file, without changing its case.
+///
+/// The parser matches keywords without regard to case, so the output can be
+/// parsed directly; identifiers in the resulting AST keep the spelling used
+/// in the original file.  Later passes (e.g. [`resolve`](crate::resolve))
+/// compare names exactly, so this is best suited to schemas which spell each
+/// name consistently.
+pub fn strip_comments_preserve_case(data: &[u8]) -> String {
+    strip_comments(data, false, None)
+}
+
+/// Implementation of [`strip_comments_and_lower`], which optionally records
+/// the text of each comment (see [`strip_comments_keep_remarks`]).
+///
+/// [`strip_comments_keep_remarks`]: crate::remark::strip_comments_keep_remarks
+pub(crate) fn strip_comments(data: &[u8], to_lower: bool,
+                             mut remarks: Option<&mut Vec<Remark>>) -> String
+{
+    let text = decode(data);
+    let data = text.as_bytes();
     let mut out = String::with_capacity(data.len());
     let mut i = 0;
     while i < data.len() {
         match data[i] {
+            // String literals are copied verbatim, and may contain sequences
+            // which would otherwise look like comments.  A doubled quote
+            // within a simple string ends one literal and immediately starts
+            // the next, which has the same effect.
+            q @ (b'\'' | b'"') => {
+                let end = memchr(q, &data[i + 1..])
+                    .map(|j| i + j + 1)
+                    .unwrap_or(data.len() - 1);
+                out.push_str(&text[i..=end]);
+                i = end;
+            },
             // Block comments
             b'(' if i + 1 < data.len() && data[i + 1] == b'*' => {
+                let start = i;
                 for j in memchr_iter(b')', &data[i + 2..]) {
                     if data[i + j + 1] == b'*' {
                         i += j + 2;
                         break;
                     }
                 }
+                if let Some(r) = remarks.as_mut() {
+                    if i > start {
+                        r.push(Remark::new(out.len(), &data[start + 2..i - 1]));
+                    }
+                }
+                out.extend(memchr_iter(b'\n', &data[start..=i]).map(|_| '\n'));
             },
             // Single-line comments
             b'-' if i + 1 < data.len() && data[i + 1] == b'-' => {
                 let newline = memchr(b'\n', &data[i + 2..]);
+                if let Some(r) = remarks.as_mut() {
+                    let end = newline.map(|n| i + 2 + n).unwrap_or(data.len());
+                    r.push(Remark::new(out.len(), &data[i + 2..end]));
+                }
                 i += newline.unwrap_or(0) + 2;
+                if newline.is_some() {
+                    out.push('\n');
+                }
+            },
+            c if c.is_ascii() && to_lower => out.push(c.to_ascii_lowercase() as char),
+            c if c.is_ascii() => out.push(c as char),
+            _ => {
+                let c = text[i..].chars().next().unwrap();
+                if to_lower {
+                    out.extend(c.to_lowercase());
+                } else {
+                    out.push(c);
+                }
+                i += c.len_utf8() - 1;
             },
-            c => out.push(c.to_ascii_lowercase() as char)
         }
         i += 1;
     }
     out
 }
 
-/// Main entry function for the parser
-pub fn parse(s: &str) -> IResult<Syntax> {
-    syntax(s)
+/// Main entry function for the parser.  The entire input must be consumed;
+/// on failure, returns an [`Error`] which can be rendered with
+/// [`render_error`](crate::error::render_error).
+///
+/// ```
+/// use express::parse::{parse, strip_comments_and_lower};
+///
+/// let s = strip_comments_and_lower(b"SCHEMA Shapes; -- a comment
+/// ENTITY circle;
+///   radius : REAL;
+/// END_ENTITY;
+/// END_SCHEMA;");
+/// let syntax = parse(&s).unwrap();
+/// assert_eq!(syntax.0[0].id.0, "shapes");
+/// assert_eq!(syntax.0[0].body.declarations.len(), 1);
+/// ```
+pub fn parse(s: &str) -> Result<Syntax, Error> {
+    match syntax(s) {
+        Ok(("", out)) => Ok(out),
+        Ok((rest, _)) => Err(Error::new(s, rest, "schema".to_owned(), vec![])),
+        Err(e) => Err(Error::from_nom(s, e)),
+    }
+}
+
+/// Parses as much of the input as possible, collecting every error instead
+/// of stopping at the first one.  This is meant for editing large schemas,
+/// where one typo shouldn't hide the rest of the file.
+///
+/// When a declaration fails to parse, its error is recorded and parsing
+/// resumes after the `END_*;` keyword which closes it (e.g. `END_ENTITY;`
+/// for an `ENTITY`); the declaration is left out of the syntax tree.  A
+/// schema whose header can't be parsed is skipped entirely.  On valid input,
+/// this returns the same tree as [`parse`] and no errors.
+pub fn parse_recovering(s: &str) -> (Syntax<'_>, Vec<Error>) {
+    let mut errors = Vec::new();
+    let mut schemas = Vec::new();
+    let mut rest = s.trim_start();
+    if rest.is_empty() {
+        errors.push(Error::new(s, rest, "schema".to_owned(), vec![]));
+    }
+    while !rest.is_empty() {
+        let (r, schema) = schema_recovering(s, rest, &mut errors);
+        schemas.extend(schema);
+        rest = r;
+    }
+    (Syntax(schemas), errors)
+}
+
+/// Parses one schema for [`parse_recovering`], returning the remaining input
+fn schema_recovering<'a>(input: &str, s: &'a str, errors: &mut Vec<Error>)
+    -> (&'a str, Option<SchemaDecl<'a>>)
+{
+    let mut head = preceded(
+        peek(kw("schema")),
+        context("schema_decl", tuple((kw("schema"), schema_id,
+                                      opt(schema_version_id), char(';')))));
+    let (mut s, (_, id, version, _)) = match head(s) {
+        Ok(r) => r,
+        Err(e) => {
+            errors.push(Error::from_nom(input, e));
+            return (skip_declaration(s), None);
+        },
+    };
+    let mut body = SchemaBody {
+        interfaces: Vec::new(),
+        constants: None,
+        declarations: Vec::new(),
+    };
+    loop {
+        if s.is_empty() {
+            errors.push(Error::new(input, s, "end_schema".to_owned(),
+                                   vec!["schema_decl"]));
+            break;
+        }
+        if let Ok((r, _)) = kw("end_schema")(s) {
+            match char(';')(r) {
+                Ok((r, _)) => s = r,
+                Err(e) => {
+                    errors.push(Error::from_nom(input, e));
+                    s = r;
+                },
+            }
+            break;
+        }
+        // The parts of a schema body must come in order, so interfaces and
+        // constants are only accepted before the first declaration
+        let start = body.declarations.is_empty();
+        let r = if start && body.constants.is_none() &&
+                   (kw("use")(s).is_ok() || kw("reference")(s).is_ok())
+        {
+            interface_specification(s).map(|(r, i)| {
+                body.interfaces.push(i);
+                r
+            })
+        } else if start && body.constants.is_none() &&
+                  kw("constant")(s).is_ok()
+        {
+            cut(context("constant_decl", constant_decl))(s).map(|(r, c)| {
+                body.constants = Some(c);
+                r
+            })
+        } else {
+            alt((
+                map(declaration, DeclarationOrRuleDecl::Declaration),
+                map(committed("rule", "rule_decl", rule_decl),
+                    DeclarationOrRuleDecl::RuleDecl),
+            ))(s).map(|(r, d)| {
+                body.declarations.push(d);
+                r
+            })
+        };
+        s = match r {
+            Ok(r) => r,
+            Err(e) => {
+                errors.push(Error::from_nom(input, e));
+                skip_declaration(s)
+            },
+        };
+    }
+    (s, Some(SchemaDecl { id, version, body }))
+}
+
+/// One top-level item of a schema, as produced by the
+/// [`stream`](crate::stream) parser.  A complete schema is its `Schema`
+/// header, then its interfaces, constants, and declarations, then
+/// `EndSchema`.
+#[derive(Debug)]
+pub enum SchemaItem<'a> {
+    Schema(SchemaId<'a>, Option<SchemaVersionId>),
+    Interface(InterfaceSpecification<'a>),
+    Constants(ConstantDecl<'a>),
+    Declaration(DeclarationOrRuleDecl<'a>),
+    EndSchema,
+}
+
+/// Parses a single top-level item, which must make up the whole input
+pub(crate) fn schema_item(s: &str) -> Result<SchemaItem<'_>, Error> {
+    let input = s;
+    let s = skip_ws(s);
+    let r = if kw("schema")(s).is_ok() {
+        context("schema_decl", map(
+            tuple((kw("schema"), schema_id, opt(schema_version_id), char(';'))),
+            |(_, id, version, _)| SchemaItem::Schema(id, version)))(s)
+    } else if kw("end_schema")(s).is_ok() {
+        map(pair(kw("end_schema"), char(';')), |_| SchemaItem::EndSchema)(s)
+    } else if kw("use")(s).is_ok() || kw("reference")(s).is_ok() {
+        map(interface_specification, SchemaItem::Interface)(s)
+    } else if kw("constant")(s).is_ok() {
+        map(cut(context("constant_decl", constant_decl)),
+            SchemaItem::Constants)(s)
+    } else {
+        map(alt((
+            map(declaration, DeclarationOrRuleDecl::Declaration),
+            map(committed("rule", "rule_decl", rule_decl),
+                DeclarationOrRuleDecl::RuleDecl),
+        )), SchemaItem::Declaration)(s)
+    };
+    match r {
+        Ok(("", item)) => Ok(item),
+        Ok((rest, _)) => Err(Error::new(input, rest, "end of declaration"
+                                        .to_owned(), vec![])),
+        Err(e) => Err(Error::from_nom(input, e)),
+    }
+}
+
+/// Skips past a declaration which failed to parse, i.e. past the `END_*;`
+/// which matches its first keyword.  Nested declarations of the same kind
+/// (e.g. local functions) are counted, and string literals are skipped.
+///
+/// If the declaration doesn't start with a keyword that has an `END_*`, then
+/// this skips past the next `END_*;` (or `;`, for an interface).  Except when
+/// skipping a whole schema, this stops before `END_SCHEMA`, so that the
+/// enclosing schema can still be closed.
+pub(crate) fn skip_declaration(s: &str) -> &str {
+    let is_word = is_id_char;
+    let first = s.split(|c| !is_word(c)).next().unwrap_or("")
+        .to_ascii_lowercase();
+    let open = match first.as_str() {
+        "entity" | "type" | "function" | "procedure" | "rule" | "constant" |
+        "subtype_constraint" | "schema" => Some(first.as_str()),
+        _ => None,
+    };
+    let close = open.map(|o| format!("end_{}", o));
+    let semicolon = first == "use" || first == "reference";
+
+    // Returns the input after the `;` following byte offset `i`
+    let past_semicolon = |i: usize| {
+        let r = s[i..].trim_start();
+        r.strip_prefix(';').unwrap_or(r).trim_start()
+    };
+
+    let mut depth = 0;
+    let mut i = 0;
+    while i < s.len() {
+        let c = s[i..].chars().next().unwrap();
+        if c == '\'' {
+            // Escaped quotes ('') just end and restart the string
+            i += 1 + s[i + 1..].find('\'').unwrap_or(s.len() - i - 1) + 1;
+        } else if c == ';' && semicolon {
+            return past_semicolon(i);
+        } else if is_word(c) {
+            let len = s[i..].find(|c| !is_word(c)).unwrap_or(s.len() - i);
+            let word = s[i..i + len].to_ascii_lowercase();
+            if word == "end_schema" && open != Some("schema") {
+                return &s[i..];
+            } else if Some(word.as_str()) == open {
+                depth += 1;
+            } else if Some(&word) == close.as_ref() ||
+                      (open.is_none() && word.starts_with("end_"))
+            {
+                depth -= 1;
+                if depth <= 0 {
+                    return past_semicolon(i + len);
+                }
+            }
+            i += len;
+        } else {
+            i += c.len_utf8();
+        }
+    }
+    ""
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
 // 124
 fn digit(s: &str) -> IResult<char> {
-    nom::character::complete::one_of("0123456789")(s)
+    nom::character::complete::satisfy(|c| c.is_ascii_digit())(s)
 }
 
 // 125 digits
@@ -149,30 +551,35 @@ fn digit(s: &str) -> IResult<char> {
 
 // 126
 fn encoded_character(s: &str) -> IResult<char> {
-    map(recognize(tuple((octet, octet, octet, octet))),
-        |v| std::char::from_u32(u32::from_str_radix(v, 16).unwrap()).unwrap())
+    // Surrogates and values past U+10FFFF aren't characters, so they're
+    // rejected rather than decoded
+    map_opt(recognize(tuple((octet, octet, octet, octet))),
+        |v| std::char::from_u32(u32::from_str_radix(v, 16).unwrap()))
         (s)
 }
 
 // 127
 fn hex_digit(s: &str) -> IResult<char> {
-    alt((digit, nom::character::complete::one_of("abcdef")))(s)
+    alt((digit, nom::character::complete::one_of("abcdefABCDEF")))(s)
 }
 
-// 128
+// 128 (extended to non-ASCII letters, see `is_id_start`)
 fn letter(s: &str) -> IResult<char> {
-    nom::character::complete::one_of("abcdefghijklmnopqrstuvwxyz")(s)
+    nom::character::complete::satisfy(is_id_start)(s)
 }
 
 // 132
 fn not_paren_star_quote_special(s: &str) -> IResult<char> {
-    nom::character::complete::one_of("!\"#$%&+,-./:;<=>?@[\\]^_‘{|}~")(s)
+    nom::character::complete::one_of("!\"#$%&+,-./:;<=>?@[\\]^_`{|}~")(s)
 }
 
-// 134
+// 134 (extended to any printable character, so that strings may be written
+// in languages other than English)
 fn not_quote(s: &str) -> IResult<char> {
     alt((not_paren_star_quote_special, letter, digit,
-         nom::character::complete::one_of("()*")))(s)
+         nom::character::complete::one_of("()*"),
+         nom::character::complete::satisfy(|c| !c.is_ascii() && !c.is_control())
+    ))(s)
 }
 
 // 136
@@ -210,40 +617,58 @@ fn real_literal(s: &str) -> IResult<f64> {
     ws(real_literal_)(s)
 }
 
+/// Reserved words from ISO 10303-11, section 7.2, in sorted order: keywords,
+/// operators, built-in constants, functions, and procedures.  None of these
+/// may be used as an identifier.
+const RESERVED: &[&str] = &[
+    "abs", "abstract", "acos", "aggregate", "alias", "and", "andor", "array",
+    "as", "asin", "atan", "bag", "based_on", "begin", "binary", "blength",
+    "boolean", "by", "case", "const_e", "constant", "cos", "derive", "div",
+    "else", "end", "end_alias", "end_case", "end_constant", "end_entity",
+    "end_function", "end_if", "end_local", "end_procedure", "end_repeat",
+    "end_rule", "end_schema", "end_subtype_constraint", "end_type", "entity",
+    "enumeration", "escape", "exists", "exp", "extensible", "false", "fixed",
+    "for", "format", "from", "function", "generic", "generic_entity",
+    "hibound", "hiindex", "if", "in", "insert", "integer", "inverse", "length",
+    "like", "list", "lobound", "local", "log", "log10", "log2", "logical",
+    "loindex", "mod", "not", "number", "nvl", "odd", "of", "oneof", "optional",
+    "or", "otherwise", "pi", "procedure", "query", "real", "reference",
+    "remove", "renamed", "repeat", "return", "rolesof", "rule", "schema",
+    "select", "self", "set", "sin", "sizeof", "skip", "sqrt", "string",
+    "subtype", "subtype_constraint", "supertype", "tan", "then", "to",
+    "total_over", "true", "type", "typeof", "unique", "unknown", "until",
+    "use", "usedin", "value", "value_in", "value_unique", "var", "where",
+    "while", "with", "xor",
+];
+
+/// Checks whether an identifier is a reserved word, in any case
+fn is_reserved(s: &str) -> bool {
+    RESERVED.binary_search(&lower(s).as_ref()).is_ok()
+}
+
+/// Matches a run of characters which could be an identifier or keyword,
+/// without checking it against the reserved words
+fn word(s: &str) -> IResult<'_, &str> {
+    if !s.starts_with(is_id_start) {
+        return build_err(s, "letter");
+    }
+    let n = s.find(|c| !is_id_char(c)).unwrap_or(s.len());
+    Ok((&s[n..], &s[..n]))
+}
+
 // 143 simple_id = letter { letter | digit | ’_’ } .
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SimpleId<'a>(pub &'a str);
 impl<'a> SimpleId<'a> {
     fn parse(s: &'a str) -> IResult<Self> {
-        let r = ws(map(pair(
-                letter,
-                many0_count(alt((letter, digit, char('_'))))),
-            |(_c, i)| SimpleId(&s[..(i + 1)])))(s)?;
-        // Refuse to match language keywords
-        match r.1.0 {
-            "abs" | "abstract" | "acos" | "aggregate" | "alias" | "and" |
-            "andor" | "array" | "as" | "asin" | "atan" | "bag" | "based_on" |
-            "begin" | "binary" | "blength" | "boolean" | "by" | "case" |
-            "const_e" | "constant" | "cos" | "derive" | "div" | "else" |
-            "end" | "end_case" | "end_constant" | "end_entity" |
-            "end_function" | "end_if" | "end_local" | "end_procedure" |
-            "end_repeat" | "end_rule" | "end_schema" |
-            "end_subtype_constraint escape" | "end_type" | "entity" |
-            "enumeration" | "exists" | "exp" | "extensible" | "false" |
-            "fixed" | "for" | "format" | "from" | "function" | "generic" |
-            "generic_entity list" | "hibound" | "hiindex" | "if" | "in" |
-            "integer" | "inverse" | "length" | "like" | "lobound" | "local" |
-            "log" | "log10" | "log2" | "logical" | "loindex" | "mod" | "not" |
-            "number" | "nvl" | "odd" | "of" | "oneof" | "optional" | "or" |
-            "otherwise" | "pi" | "procedure reference schema" | "query" |
-            "real" | "renamed" | "repeat" | "return" | "rolesof" | "rule" |
-            "select" | "self" | "set" | "sin" | "sizeof" | "skip" | "sqrt" |
-            "string" | "subtype" | "subtype_constraint" | "supertype" | "tan" |
-            "then" | "to" | "total_over" | "true" | "type" | "unique" |
-            "unknown" | "until" | "use" | "usedin" | "value" | "value_in" |
-            "value_unique" | "var" | "where" | "while" | "with" | "xor"
-              => build_err(s, "keyword"),
-            _ => Ok(r)
+        let r = ws(map(word, SimpleId))(s)?;
+        // Refuse to match language keywords, in any case
+        if is_reserved(r.1.0) {
+            build_err(s, "identifier (found a reserved keyword)")
+        } else {
+            Ok(r)
         }
     }
 }
@@ -296,7 +721,9 @@ fn abstract_supertype(s: &str) -> IResult<()> {
 
 // 166 abstract_supertype_declaration = ABSTRACT SUPERTYPE [ subtype_constraint ] .
 #[derive(Debug)]
-pub struct AbstractSupertypeDeclaration<'a>(Option<SubtypeConstraint<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct AbstractSupertypeDeclaration<'a>(pub Option<SubtypeConstraint<'a>>);
 fn abstract_supertype_declaration(s: &str) -> IResult<AbstractSupertypeDeclaration> {
     map(tuple((
         kw("abstract"),
@@ -307,13 +734,16 @@ fn abstract_supertype_declaration(s: &str) -> IResult<AbstractSupertypeDeclarati
 
 // 167 actual_parameter_list = ’(’ parameter { ’,’ parameter } ’)’ .
 #[derive(Debug)]
-pub struct ActualParameterList<'a>(Vec<Parameter<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct ActualParameterList<'a>(pub Vec<Parameter<'a>>);
 fn actual_parameter_list(s: &str) -> IResult<ActualParameterList> {
     map(parens(list1(',', parameter)), ActualParameterList)(s)
 }
 
 // 168
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AddLikeOp { Add, Sub, Or, Xor }
 fn add_like_op(s: &str) -> IResult<AddLikeOp> {
     use AddLikeOp::*;
@@ -327,7 +757,9 @@ fn add_like_op(s: &str) -> IResult<AddLikeOp> {
 
 // 169
 #[derive(Debug)]
-pub struct AggregateInitializer<'a>(Vec<Element<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct AggregateInitializer<'a>(pub Vec<Element<'a>>);
 fn aggregate_initializer(s: &str) -> IResult<AggregateInitializer> {
     map(delimited(
             char('['),
@@ -341,7 +773,10 @@ alias!(AggregateSource<'a>, SimpleExpression, aggregate_source);
 
 // 171 aggregate_type = AGGREGATE [ ’:’ type_label ] OF parameter_type .
 #[derive(Debug)]
-pub struct AggregateType<'a>(Option<TypeLabel<'a>>, Box<ParameterType<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct AggregateType<'a>(pub Option<TypeLabel<'a>>,
+                             pub Box<ParameterType<'a>>);
 fn aggregate_type(s: &str) -> IResult<AggregateType> {
     map(tuple((
         kw("aggregate"),
@@ -353,6 +788,8 @@ fn aggregate_type(s: &str) -> IResult<AggregateType> {
 
 // 172
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum AggregationTypes<'a> {
     Array(ArrayType<'a>),
     Bag(BagType<'a>),
@@ -371,6 +808,8 @@ fn aggregation_types(s: &str) -> IResult<AggregationTypes> {
 
 // 173
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct AlgorithmHead<'a> {
     pub declaration: Vec<Declaration<'a>>,
     pub constant: Option<ConstantDecl<'a>>,
@@ -391,6 +830,8 @@ fn algorithm_head(s: &str) -> IResult<AlgorithmHead> {
 // 174 alias_stmt = ALIAS variable_id FOR general_ref { qualifier } ’;’ stmt { stmt }
 //                  END_ALIAS ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct AliasStmt<'a> {
     pub variable: VariableId<'a>,
     pub general: GeneralRef<'a>,
@@ -405,8 +846,10 @@ fn alias_stmt(s: &str) -> IResult<AliasStmt> {
         general_ref,
         many0(qualifier),
         char(';'),
-        many0(stmt),
-    )), |(_, v, _, g, q, _, s)| AliasStmt {
+        many1(stmt),
+        kw("end_alias"),
+        char(';'),
+    )), |(_, v, _, g, q, _, s, _, _)| AliasStmt {
         variable: v,
         general: g,
         qualifiers: q,
@@ -416,6 +859,8 @@ fn alias_stmt(s: &str) -> IResult<AliasStmt> {
 
 // 175
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ArrayType<'a> {
     pub bounds: BoundSpec<'a>,
     pub optional: bool,
@@ -441,6 +886,8 @@ fn array_type(s: &str) -> IResult<ArrayType> {
 
 // 176 assignment_stmt = general_ref { qualifier } ’:=’ expression ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct AssignmentStmt<'a> {
     pub general_ref: GeneralRef<'a>,
     pub qualifiers: Vec<Qualifier<'a>>,
@@ -462,6 +909,8 @@ fn assignment_stmt(s: &str) -> IResult<AssignmentStmt> {
 
 // 177 attribute_decl = attribute_id | redeclared_attribute .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum AttributeDecl<'a> {
     Id(AttributeId<'a>),
     Redeclared(RedeclaredAttribute<'a>),
@@ -473,12 +922,25 @@ fn attribute_decl(s: &str) -> IResult<AttributeDecl> {
         map(redeclared_attribute, Redeclared),
     ))(s)
 }
+impl<'a> AttributeDecl<'a> {
+    /// Returns the name by which the attribute is known in the declaring
+    /// entity, i.e. the new name for a `RENAMED` attribute.
+    pub fn name(&self) -> &'a str {
+        match self {
+            AttributeDecl::Id(a) => a.0,
+            AttributeDecl::Redeclared(RedeclaredAttribute(_, Some(a))) => a.0,
+            AttributeDecl::Redeclared(RedeclaredAttribute(q, None)) => (q.1).0.0,
+        }
+    }
+}
 
 // 178
 id_type!(AttributeId, attribute_id);
 
 // 179
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct AttributeQualifier<'a>(pub AttributeRef<'a>);
 fn attribute_qualifier(s: &str) -> IResult<AttributeQualifier> {
     map(preceded(char('.'), attribute_ref), AttributeQualifier)(s)
@@ -486,7 +948,9 @@ fn attribute_qualifier(s: &str) -> IResult<AttributeQualifier> {
 
 // 180
 #[derive(Debug)]
-pub struct BagType<'a>(Option<BoundSpec<'a>>, pub Box<InstantiableType<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct BagType<'a>(pub Option<BoundSpec<'a>>, pub Box<InstantiableType<'a>>);
 fn bag_type(s: &str) -> IResult<BagType> {
     map(tuple((
             kw("bag"),
@@ -499,6 +963,8 @@ fn bag_type(s: &str) -> IResult<BagType> {
 
 // 181 binary_type = BINARY [ width_spec ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct BinaryType<'a>(Option<WidthSpec<'a>>);
 fn binary_type(s: &str) -> IResult<BinaryType> {
     map(preceded(kw("binary"), opt(width_spec)), BinaryType)(s)
@@ -517,7 +983,9 @@ alias!(Bound2<'a>, NumericExpression, bound_2);
 
 // 185
 #[derive(Debug)]
-pub struct BoundSpec<'a>(Bound1<'a>, pub Bound2<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct BoundSpec<'a>(pub Bound1<'a>, pub Bound2<'a>);
 fn bound_spec(s: &str) -> IResult<BoundSpec> {
     map(tuple((
         char('['),
@@ -530,6 +998,7 @@ fn bound_spec(s: &str) -> IResult<BoundSpec> {
 
 // 186
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BuiltInConstant { ConstE, Pi, Self_, Indeterminant }
 fn built_in_constant(s: &str) -> IResult<BuiltInConstant> {
     use BuiltInConstant::*;
@@ -543,14 +1012,16 @@ fn built_in_constant(s: &str) -> IResult<BuiltInConstant> {
 
 // 187
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BuiltInFunction {
     Abs, Acos, Asin, Atan, Blength, Cos, Exists, Exp, Format, Hibound, HiIndex,
     Length, LoBound, LoIndex, Log, Log2, Log10, Nvl, Odd, RolesOf, Sin, SizeOf,
     Sqrt, Tan, Typeof, Usedin, Value, ValueIn, ValueUnique
 }
-fn to_built_in_function(s: &str) -> Option<BuiltInFunction> {
+/// Looks up a built-in function by name, in any case
+pub fn to_built_in_function(s: &str) -> Option<BuiltInFunction> {
     use BuiltInFunction::*;
-    Some(match s {
+    Some(match lower(s).as_ref() {
         "abs" => Abs,
         "acos" => Acos,
         "asin" => Asin,
@@ -586,12 +1057,14 @@ fn to_built_in_function(s: &str) -> Option<BuiltInFunction> {
     })
 }
 fn built_in_function(s: &str) -> IResult<BuiltInFunction> {
-    // Tokenize then match the keyword, instead of doing a huge alt(...)
-    ws(map_opt(alpha1, to_built_in_function))(s)
+    // Tokenize then match the keyword, instead of doing a huge alt(...).
+    // The whole word is taken so that e.g. `log10` isn't read as `log`.
+    ws(map_opt(word, to_built_in_function))(s)
 }
 
 // 188 built_in_procedure = INSERT | REMOVE .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BuiltInProcedure { Insert, Remove }
 fn built_in_procedure(s: &str) -> IResult<BuiltInProcedure> {
     use BuiltInProcedure::*;
@@ -603,7 +1076,9 @@ fn built_in_procedure(s: &str) -> IResult<BuiltInProcedure> {
 
 // 189 case_action = case_label { ’,’ case_label } ’:’ stmt .
 #[derive(Debug)]
-pub struct CaseAction<'a>(Vec<CaseLabel<'a>>, Stmt<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct CaseAction<'a>(pub Vec<CaseLabel<'a>>, pub Stmt<'a>);
 fn case_action(s: &str) -> IResult<CaseAction> {
     map(tuple((
         list1(',', case_label),
@@ -618,6 +1093,8 @@ alias!(CaseLabel<'a>, Expression, case_label);
 // 191 case_stmt = CASE selector OF { case_action } [ OTHERWISE ’:’ stmt ]
 //                  END_CASE ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct CaseStmt<'a> {
     pub selector: Selector<'a>,
     pub actions: Vec<CaseAction<'a>>,
@@ -644,7 +1121,9 @@ fn case_stmt(s: &str) -> IResult<CaseStmt> {
 
 // 192 compound_stmt = BEGIN stmt { stmt } END ’;’ .
 #[derive(Debug)]
-pub struct CompoundStmt<'a>(Vec<Stmt<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct CompoundStmt<'a>(pub Vec<Stmt<'a>>);
 fn compound_stmt(s: &str) -> IResult<CompoundStmt> {
     map(delimited(
             kw("begin"),
@@ -655,6 +1134,8 @@ fn compound_stmt(s: &str) -> IResult<CompoundStmt> {
 
 // 193
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ConcreteTypes<'a> {
     Aggregation(AggregationTypes<'a>),
     Simple(SimpleTypes<'a>),
@@ -671,6 +1152,8 @@ fn concrete_types(s: &str) -> IResult<ConcreteTypes> {
 
 // 194 constant_body = constant_id ’:’ instantiable_type ’:=’ expression ’;’
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ConstantBody<'a> {
     pub constant_id: ConstantId<'a>,
     pub instantiable_type: InstantiableType<'a>,
@@ -693,7 +1176,9 @@ fn constant_body(s: &str) -> IResult<ConstantBody> {
 
 // 195
 #[derive(Debug)]
-pub struct ConstantDecl<'a>(Vec<ConstantBody<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct ConstantDecl<'a>(pub Vec<ConstantBody<'a>>);
 fn constant_decl(s: &str) -> IResult<ConstantDecl> {
     map(tuple((
         kw("constant"),
@@ -705,6 +1190,8 @@ fn constant_decl(s: &str) -> IResult<ConstantDecl> {
 
 // 196 constant_factor = built_in_constant | constant_ref .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ConstantFactor<'a> {
     BuiltIn(BuiltInConstant),
     ConstantRef(ConstantRef<'a>),
@@ -722,6 +1209,8 @@ id_type!(ConstantId, constant_id);
 
 // 198
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ConstructedTypes<'a> {
     Enumeration(EnumerationType<'a>),
     Select(SelectType<'a>),
@@ -737,6 +1226,8 @@ fn constructed_types(s: &str) -> IResult<ConstructedTypes> {
 // 199 declaration = entity_decl | function_decl | procedure_decl |
 //                   subtype_constraint_decl | type_decl .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum Declaration<'a> {
     Entity(EntityDecl<'a>),
     Function(FunctionDecl<'a>),
@@ -747,19 +1238,23 @@ pub enum Declaration<'a> {
 fn declaration(s: &str) -> IResult<Declaration> {
     use Declaration::*;
     alt((
-        map(entity_decl, Entity),
-        map(function_decl, Function),
-        map(procedure_decl, Procedure),
-        map(subtype_constraint_decl, SubtypeConstraint),
-        map(type_decl, Type),
+        map(committed("entity", "entity_decl", entity_decl), Entity),
+        map(committed("function", "function_decl", function_decl), Function),
+        map(committed("procedure", "procedure_decl", procedure_decl),
+            Procedure),
+        map(committed("subtype_constraint", "subtype_constraint_decl",
+                      subtype_constraint_decl), SubtypeConstraint),
+        map(committed("type", "type_decl", type_decl), Type),
     ))(s)
 }
 
 // 200 derived_attr = attribute_decl ’:’ parameter_type ’:=’ expression ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct DerivedAttr<'a>(pub AttributeDecl<'a>,
-                           ParameterType<'a>,
-                           Expression<'a>);
+                           pub ParameterType<'a>,
+                           pub Expression<'a>);
 fn derived_attr(s: &str) -> IResult<DerivedAttr> {
     map(tuple((
         attribute_decl,
@@ -773,6 +1268,8 @@ fn derived_attr(s: &str) -> IResult<DerivedAttr> {
 
 // 201 derive_clause = DERIVE derived_attr { derived_attr } .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct DeriveClause<'a>(pub Vec<DerivedAttr<'a>>);
 fn derive_clause(s: &str) -> IResult<DeriveClause> {
     map(preceded(kw("derive"), many1(derived_attr)), DeriveClause)(s)
@@ -780,6 +1277,8 @@ fn derive_clause(s: &str) -> IResult<DeriveClause> {
 
 // 202 domain_rule = [ rule_label_id ’:’ ] expression .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct DomainRule<'a> {
     pub rule_label_id: Option<RuleLabelId<'a>>,
     pub expression: Expression<'a>,
@@ -792,7 +1291,9 @@ fn domain_rule(s: &str) -> IResult<DomainRule> {
 
 // 203
 #[derive(Debug)]
-pub struct Element<'a>(Expression<'a>, Option<Repetition<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct Element<'a>(pub Expression<'a>, pub Option<Repetition<'a>>);
 fn element(s: &str) -> IResult<Element> {
     map(pair(expression, opt(preceded(char(':'), repetition))),
         |(a, b)| Element(a, b))(s)
@@ -801,6 +1302,8 @@ fn element(s: &str) -> IResult<Element> {
 // 204 entity_body = { explicit_attr } [ derive_clause ] [ inverse_clause ]
 //                   [ unique_clause ] [ where_clause ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EntityBody<'a> {
     pub explicit_attr: Vec<ExplicitAttr<'a>>,
     pub derive: Option<DeriveClause<'a>>,
@@ -819,6 +1322,8 @@ fn entity_body(s: &str) -> IResult<EntityBody> {
 
 // 205 entity_constructor = entity_ref ’(’ [ expression { ’,’ expression } ] ’)’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EntityConstructor<'a> {
     pub entity_ref: EntityRef<'a>,
     pub args: Vec<Expression<'a>>,
@@ -828,6 +1333,8 @@ pub struct EntityConstructor<'a> {
 
 // 206 entity_decl = entity_head entity_body END_ENTITY ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EntityDecl<'a>(pub EntityHead<'a>, pub EntityBody<'a>);
 fn entity_decl(s: &str) -> IResult<EntityDecl> {
     let (s, a) = entity_head(s)?;
@@ -839,6 +1346,8 @@ fn entity_decl(s: &str) -> IResult<EntityDecl> {
 
 // 207 entity_head = ENTITY entity_id subsuper ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EntityHead<'a>(pub EntityId<'a>, pub Subsuper<'a>);
 fn entity_head(s: &str) -> IResult<EntityHead> {
     map(tuple((
@@ -854,6 +1363,8 @@ id_type!(EntityId, entity_id);
 
 // 209
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EnumerationExtension<'a> {
     pub type_ref: TypeRef<'a>,
     pub enumeration_items: Option<EnumerationItems<'a>>,
@@ -870,6 +1381,8 @@ id_type!(EnumerationId, enumeration_id);
 
 // 211 enumeration_items = ’(’ enumeration_id { ’,’ enumeration_id } ’)’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EnumerationItems<'a>(pub Vec<EnumerationId<'a>>);
 fn enumeration_items(s: &str) -> IResult<EnumerationItems> {
     map(parens(list1(',', enumeration_id)), EnumerationItems)(s)
@@ -877,7 +1390,10 @@ fn enumeration_items(s: &str) -> IResult<EnumerationItems> {
 
 // 212 enumeration_reference = [ type_ref ’.’ ] enumeration_ref .
 #[derive(Debug)]
-pub struct EnumerationReference<'a>(Option<TypeRef<'a>>, EnumerationRef<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct EnumerationReference<'a>(pub Option<TypeRef<'a>>,
+                                    pub EnumerationRef<'a>);
 fn enumeration_reference(s: &str) -> IResult<EnumerationReference> {
     map(tuple((
         opt(terminated(type_ref, char('.'))),
@@ -887,11 +1403,15 @@ fn enumeration_reference(s: &str) -> IResult<EnumerationReference> {
 
 // 213
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum EnumerationItemsOrExtension<'a> {
     Items(EnumerationItems<'a>),
     Extension(EnumerationExtension<'a>),
 }
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct EnumerationType<'a> {
     pub extensible: bool,
     pub items_or_extension: Option<EnumerationItemsOrExtension<'a>>
@@ -918,19 +1438,24 @@ fn escape_stmt(s: &str) -> IResult<()> {
 // 215 explicit_attr = attribute_decl { ’,’ attribute_decl } ’:’ [ OPTIONAL ]
 //                      parameter_type ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ExplicitAttr<'a> {
     pub attributes: Vec<AttributeDecl<'a>>,
     pub optional: bool,
     pub parameter_type: ParameterType<'a>,
 }
 fn explicit_attr(s: &str) -> IResult<ExplicitAttr> {
-    map(tuple((
-        list1(',', attribute_decl),
-        char(':'),
-        opt(kw("optional")),
-        parameter_type,
-        char(';'),
-    )), |(a, _, o, t, _)| ExplicitAttr {
+    // Nothing else in an entity body looks like `attr :`, so we can commit
+    // here and report errors in the attribute's type
+    map(pair(
+        terminated(list1(',', attribute_decl), char(':')),
+        cut(tuple((
+            opt(kw("optional")),
+            parameter_type,
+            char(';'),
+        )))
+    ), |(a, (o, t, _))| ExplicitAttr {
         attributes: a,
         optional: o.is_some(),
         parameter_type: t,
@@ -939,27 +1464,41 @@ fn explicit_attr(s: &str) -> IResult<ExplicitAttr> {
 
 // 216 expression = simple_expression [ rel_op_extended simple_expression ] .
 #[derive(Debug)]
-pub struct Expression<'a>(SimpleExpression<'a>, Option<(RelOpExtended, SimpleExpression<'a>)>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct Expression<'a>(pub SimpleExpression<'a>,
+                          pub Option<(RelOpExtended, SimpleExpression<'a>)>,
+                          /// Source text of the expression, for diagnostics
+                          #[cfg_attr(feature = "serde", serde(skip))]
+                          pub &'a str);
 impl<'a> Expression<'a> {
     fn parse(s: &'a str) -> IResult<Self> {
-        let (s, a) = simple_expression(s)?;
-        let (s, b) = opt(pair(rel_op_extended, simple_expression))(s)?;
-        Ok((s, Self(a, b)))
+        let (rest, a) = simple_expression(s)?;
+        let (rest, b) = opt(pair(rel_op_extended, simple_expression))(rest)?;
+        let span = s[..s.len() - rest.len()].trim_end();
+        Ok((rest, Self(a, b, span)))
     }
 }
 fn expression(s: &str) -> IResult<Expression> { Expression::parse(s) }
 
 // 217 factor = simple_factor [ ’**’ simple_factor ] .
+/// The grammar only allows a single `**`, but chains like `a ** b ** c` are
+/// accepted too.  Like every other operator, they're evaluated from left to
+/// right (ISO 10303-11, section 12.1), i.e. as `(a ** b) ** c`.
 #[derive(Debug)]
-pub struct Factor<'a>(pub SimpleFactor<'a>, pub Option<SimpleFactor<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct Factor<'a>(pub SimpleFactor<'a>, pub Vec<SimpleFactor<'a>>);
 fn factor(s: &str) -> IResult<Factor> {
-    map(pair(simple_factor, opt(preceded(tag("**"), simple_factor))),
+    map(pair(simple_factor, many0(preceded(tag("**"), simple_factor))),
         |(a, b)| Factor(a, b))(s)
 }
 
 // 218 formal_parameter = parameter_id { ’,’ parameter_id } ’:’ parameter_type .
 #[derive(Debug)]
-pub struct FormalParameter<'a>(Vec<ParameterId<'a>>, ParameterType<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct FormalParameter<'a>(pub Vec<ParameterId<'a>>, pub ParameterType<'a>);
 fn formal_parameter(s: &str) -> IResult<FormalParameter> {
     map(tuple((
         list1(',', parameter_id),
@@ -970,12 +1509,17 @@ fn formal_parameter(s: &str) -> IResult<FormalParameter> {
 
 // 219 function_call = ( built_in_function | function_ref ) [ actual_parameter_list ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum BuiltInOrFunctionRef<'a> {
     BuiltIn(BuiltInFunction),
     Ref(FunctionRef<'a>),
 }
 #[derive(Debug)]
-pub struct FunctionCall<'a>(BuiltInOrFunctionRef<'a>, ActualParameterList<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct FunctionCall<'a>(pub BuiltInOrFunctionRef<'a>,
+                            pub ActualParameterList<'a>);
 fn function_call(s: &str) -> IResult<FunctionCall> {
     map(pair(
             alt((map(built_in_function, BuiltInOrFunctionRef::BuiltIn),
@@ -985,6 +1529,8 @@ fn function_call(s: &str) -> IResult<FunctionCall> {
 }
 // 220 function_decl = function_head algorithm_head stmt { stmt } END_FUNCTION ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct FunctionDecl<'a> {
     pub function_head: FunctionHead<'a>,
     pub algorithm_head: AlgorithmHead<'a>,
@@ -1007,6 +1553,8 @@ fn function_decl(s: &str) -> IResult<FunctionDecl> {
 // 221 function_head = FUNCTION function_id [ ’(’ formal_parameter
 //                     { ’;’ formal_parameter } ’)’ ] ’:’ parameter_type ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct FunctionHead<'a> {
     pub id: FunctionId<'a>,
     pub params: Option<Vec<FormalParameter<'a>>>,
@@ -1033,6 +1581,8 @@ id_type!(FunctionId, function_id);
 // 223 generalized_types = aggregate_type | general_aggregation_types |
 //                         generic_entity_type | generic_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum GeneralizedTypes<'a> {
     Aggregate(AggregateType<'a>),
     GeneralAggregation(GeneralAggregationTypes<'a>),
@@ -1052,6 +1602,8 @@ fn generalized_types(s: &str) -> IResult<GeneralizedTypes> {
 // 224 general_aggregation_types = general_array_type | general_bag_type |
 //                                 general_list_type | general_set_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum GeneralAggregationTypes<'a> {
     Array(GeneralArrayType<'a>),
     Bag(GeneralBagType<'a>),
@@ -1071,8 +1623,10 @@ fn general_aggregation_types(s: &str) -> IResult<GeneralAggregationTypes> {
 // 225 general_array_type = ARRAY [ bound_spec ] OF [ OPTIONAL ] [ UNIQUE ]
 //                          parameter_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct GeneralArrayType<'a> {
-    pub bounds: BoundSpec<'a>,
+    pub bounds: Option<BoundSpec<'a>>,
     pub optional: bool,
     pub unique: bool,
     pub parameter_type: Box<ParameterType<'a>>,
@@ -1080,7 +1634,7 @@ pub struct GeneralArrayType<'a> {
 fn general_array_type(s: &str) -> IResult<GeneralArrayType> {
     map(tuple((
         kw("array"),
-        bound_spec,
+        opt(bound_spec),
         kw("of"),
         opt(kw("optional")),
         opt(kw("unique")),
@@ -1096,6 +1650,8 @@ fn general_array_type(s: &str) -> IResult<GeneralArrayType> {
 
 // 226 general_bag_type = BAG [ bound_spec ] OF parameter_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct GeneralBagType<'a>(pub Option<BoundSpec<'a>>,
                               pub Box<ParameterType<'a>>);
 fn general_bag_type(s: &str) -> IResult<GeneralBagType> {
@@ -1110,6 +1666,8 @@ fn general_bag_type(s: &str) -> IResult<GeneralBagType> {
 
 // 227 general_list_type = LIST [ bound_spec ] OF [ UNIQUE ] parameter_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct GeneralListType<'a> {
     pub bounds: Option<BoundSpec<'a>>,
     pub unique: bool,
@@ -1132,6 +1690,8 @@ fn general_list_type(s: &str) -> IResult<GeneralListType> {
 
 // 228 general_ref = parameter_ref | variable_ref .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum GeneralRef<'a> {
     Parameter(ParameterRef<'a>),
     Variable(VariableRef<'a>),
@@ -1143,6 +1703,8 @@ fn general_ref(s: &str) -> IResult<GeneralRef> {
 
 // 229 general_set_type = SET [ bound_spec ] OF parameter_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct GeneralSetType<'a> {
     pub bounds: Option<BoundSpec<'a>>,
     pub parameter_type: Box<ParameterType<'a>>,
@@ -1162,7 +1724,9 @@ fn general_set_type(s: &str) -> IResult<GeneralSetType> {
 
 // 230 generic_entity_type = GENERIC_ENTITY [ ’:’ type_label ] .
 #[derive(Debug)]
-pub struct GenericEntityType<'a>(Option<TypeLabel<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct GenericEntityType<'a>(pub Option<TypeLabel<'a>>);
 fn generic_entity_type(s: &str) -> IResult<GenericEntityType> {
     map(preceded(kw("generic_entity"),
                  opt(preceded(char(':'), type_label))),
@@ -1171,7 +1735,9 @@ fn generic_entity_type(s: &str) -> IResult<GenericEntityType> {
 
 // 231 generic_type = GENERIC [ ’:’ type_label ] .
 #[derive(Debug)]
-pub struct GenericType<'a>(Option<TypeLabel<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct GenericType<'a>(pub Option<TypeLabel<'a>>);
 fn generic_type(s: &str) -> IResult<GenericType> {
     map(preceded(kw("generic"),
                  opt(preceded(char(':'), type_label))),
@@ -1180,6 +1746,8 @@ fn generic_type(s: &str) -> IResult<GenericType> {
 
 // 232 group_qualifier = ’\’ entity_ref .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct GroupQualifier<'a>(pub EntityRef<'a>);
 fn group_qualifier(s: &str) -> IResult<GroupQualifier> {
     map(preceded(char('\\'), entity_ref), GroupQualifier)(s)
@@ -1188,7 +1756,11 @@ fn group_qualifier(s: &str) -> IResult<GroupQualifier> {
 // 233 if_stmt = IF logical_expression THEN stmt { stmt } [ ELSE stmt { stmt } ]
 //               END_IF ’;’ .
 #[derive(Debug)]
-pub struct IfStmt<'a>(LogicalExpression<'a>, Vec<Stmt<'a>>, Option<Vec<Stmt<'a>>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct IfStmt<'a>(pub LogicalExpression<'a>,
+                      pub Vec<Stmt<'a>>,
+                      pub Option<Vec<Stmt<'a>>>);
 fn if_stmt(s: &str) -> IResult<IfStmt> {
     map(tuple((
         kw("if"),
@@ -1206,6 +1778,8 @@ alias!(Increment<'a>, NumericExpression, increment);
 
 // 235 increment_control = variable_id ’:=’ bound_1 TO bound_2 [ BY increment ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct IncrementControl<'a> {
     pub var: VariableId<'a>,
     pub bound1: Bound1<'a>,
@@ -1239,17 +1813,21 @@ alias!(Index2<'a>, Index, index_2);
 
 // 239 index_qualifier = ’[’ index_1 [ ’:’ index_2 ] ’]’ .
 #[derive(Debug)]
-pub struct IndexQualifier<'a>(Index1<'a>, Option<Index2<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct IndexQualifier<'a>(pub Index1<'a>, pub Option<Index2<'a>>);
 fn index_qualifier(s: &str) -> IResult<IndexQualifier> {
     let (s, _) = char('[')(s)?;
     let (s, index1) = index_1(s)?;
-    let (s, index2) = opt(preceded(char(';'), index_2))(s)?;
+    let (s, index2) = opt(preceded(char(':'), index_2))(s)?;
     let (s, _) = char(']')(s)?;
     Ok((s, IndexQualifier(index1, index2)))
 }
 
 // 240
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum InstantiableType<'a> {
     Concrete(ConcreteTypes<'a>),
     EntityRef(EntityRef<'a>),
@@ -1269,6 +1847,8 @@ fn integer_type(s: &str) -> IResult<()> {
 
 // 242 interface_specification = reference_clause | use_clause .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum InterfaceSpecification<'a> {
     ReferenceClause(ReferenceClause<'a>),
     UseClause(UseClause<'a>),
@@ -1281,6 +1861,8 @@ fn interface_specification(s: &str) -> IResult<InterfaceSpecification> {
 
 // 243
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct Interval<'a> {
     pub low: IntervalLow<'a>,
     pub op1: IntervalOp,
@@ -1314,6 +1896,7 @@ alias!(IntervalLow<'a>, SimpleExpression, interval_low);
 
 // 247
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IntervalOp { LessThan, LessThanOrEqual }
 fn interval_op(s: &str) -> IResult<IntervalOp> {
     alt((
@@ -1326,8 +1909,11 @@ fn interval_op(s: &str) -> IResult<IntervalOp> {
 // 248 inverse_attr = attribute_decl ’:’ [ ( SET | BAG ) [ bound_spec ] OF ] entity_ref
 //                    FOR [ entity_ref ’.’ ] attribute_ref ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SetOrBag { Set, Bag }
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct InverseAttr<'a> {
     pub attribute_decl: AttributeDecl<'a>,
     pub bounds: Option<(SetOrBag, Option<BoundSpec<'a>>)>,
@@ -1361,13 +1947,17 @@ fn inverse_attr(s: &str) -> IResult<InverseAttr> {
 
 // 249 inverse_clause = INVERSE inverse_attr { inverse_attr } .
 #[derive(Debug)]
-pub struct InverseClause<'a>(Vec<InverseAttr<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct InverseClause<'a>(pub Vec<InverseAttr<'a>>);
 fn inverse_clause(s: &str) -> IResult<InverseClause> {
     map(preceded(kw("inverse"), many1(inverse_attr)), InverseClause)(s)
 }
 
 // 250
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ListType<'a> {
     pub bounds: Option<BoundSpec<'a>>,
     pub unique: bool,
@@ -1390,6 +1980,7 @@ fn list_type(s: &str) -> IResult<ListType> {
 
 // 251
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Literal {
     String(String),
     Binary(usize),
@@ -1407,7 +1998,9 @@ fn literal(s: &str) -> IResult<Literal> {
 }
 // 252 local_decl = LOCAL local_variable { local_variable } END_LOCAL ’;’
 #[derive(Debug)]
-pub struct LocalDecl<'a>(Vec<LocalVariable<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct LocalDecl<'a>(pub Vec<LocalVariable<'a>>);
 fn local_decl(s: &str) -> IResult<LocalDecl> {
     map(tuple((
         kw("local"),
@@ -1419,6 +2012,8 @@ fn local_decl(s: &str) -> IResult<LocalDecl> {
 // 253 local_variable = variable_id { ’,’ variable_id } ’:’ parameter_type
 //                      [ ’:=’ expression ] ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct LocalVariable<'a> {
     pub variable_id: Vec<VariableId<'a>>,
     pub parameter_type: ParameterType<'a>,
@@ -1443,6 +2038,7 @@ alias!(LogicalExpression<'a>, Expression, logical_expression);
 
 // 255
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LogicalLiteral {
     True, False, Unknown
 }
@@ -1459,6 +2055,7 @@ fn logical_type(s: &str) -> IResult<()> {
 
 // 257
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MultiplicationLikeOp {Mul, Div, IntegerDiv, Mod, And, ComplexEntity }
 fn multiplication_like_op(s: &str) -> IResult<MultiplicationLikeOp> {
     use MultiplicationLikeOp::*;
@@ -1474,6 +2071,8 @@ fn multiplication_like_op(s: &str) -> IResult<MultiplicationLikeOp> {
 
 // 258
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum NamedTypes<'a> {
     Entity(EntityRef<'a>),
     Type(TypeRef<'a>),
@@ -1485,12 +2084,16 @@ fn named_types(s: &str) -> IResult<NamedTypes> {
 
 // 259
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum EntityOrTypeId<'a> {
     Entity(EntityId<'a>),
     Type(EntityId<'a>),
     _Ambiguous(SimpleId<'a>),
 }
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct NamedTypeOrRename<'a> {
     pub named_types: NamedTypes<'a>,
     pub rename: Option<EntityOrTypeId<'a>>,
@@ -1518,7 +2121,9 @@ alias!(NumericExpression<'a>, SimpleExpression);
 
 // 263 one_of = ONEOF ’(’ supertype_expression { ’,’ supertype_expression } ’)’
 #[derive(Debug)]
-pub struct OneOf<'a>(Vec<SupertypeExpression<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct OneOf<'a>(pub Vec<SupertypeExpression<'a>>);
 fn one_of(s: &str) -> IResult<OneOf> {
     map(preceded(
         kw("oneof"),
@@ -1534,6 +2139,8 @@ id_type!(ParameterId, parameter_id);
 
 // 266
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ParameterType<'a> {
     Generalized(GeneralizedTypes<'a>),
     Named(NamedTypes<'a>),
@@ -1550,13 +2157,17 @@ fn parameter_type(s: &str) -> IResult<ParameterType> {
 
 // 267
 #[derive(Debug)]
-pub struct Population<'a>(EntityRef<'a>); // never parsed
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct Population<'a>(pub EntityRef<'a>); // never parsed
 
 // 268
 alias!(PrecisionSpec<'a>, NumericExpression, precision_spec);
 
 // 269 primary = literal | ( qualifiable_factor { qualifier } ) .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum Primary<'a> {
     Literal(Literal),
     Qualifiable(QualifiableFactor<'a>, Vec<Qualifier<'a>>),
@@ -1574,11 +2185,15 @@ fn primary(s: &str) -> IResult<Primary> {
 // 270 procedure_call_stmt = ( built_in_procedure | procedure_ref )
 //                           [ actual_parameter_list ] ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum BuiltInOrProcedureRef<'a> {
     BuiltIn(BuiltInProcedure),
     ProcedureRef(ProcedureRef<'a>),
 }
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ProcedureCallStmt<'a> {
     pub proc: BuiltInOrProcedureRef<'a>,
     pub params: Option<ActualParameterList<'a>>,
@@ -1597,7 +2212,11 @@ fn procedure_call_stmt(s: &str) -> IResult<ProcedureCallStmt> {
 }
 // 271 procedure_decl = procedure_head algorithm_head { stmt } END_PROCEDURE ’;’ .
 #[derive(Debug)]
-pub struct ProcedureDecl<'a>(ProcedureHead<'a>, AlgorithmHead<'a>, Vec<Stmt<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct ProcedureDecl<'a>(pub ProcedureHead<'a>,
+                             pub AlgorithmHead<'a>,
+                             pub Vec<Stmt<'a>>);
 fn procedure_decl(s: &str) -> IResult<ProcedureDecl> {
     map(tuple((
         procedure_head,
@@ -1611,6 +2230,8 @@ fn procedure_decl(s: &str) -> IResult<ProcedureDecl> {
 // 272 procedure_head = PROCEDURE procedure_id [ ’(’ [ VAR ] formal_parameter
 //                      { ’;’ [ VAR ] formal_parameter } ’)’ ] ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ProcedureHead<'a> {
     pub procedure_id: ProcedureId<'a>,
     pub args: Option<Vec<(bool, FormalParameter<'a>)>>,
@@ -1636,6 +2257,8 @@ id_type!(ProcedureId, procedure_id);
 // 274 qualifiable_factor = attribute_ref | constant_factor | function_call |
 //                          general_ref | population .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum QualifiableFactor<'a> {
     // Function calls should go first, since otherwise they get parsed as a
     // bare ref and leave the `(arg1, arg2, ...)` sitting on the stack
@@ -1678,6 +2301,8 @@ fn qualifiable_factor(s: &str) -> IResult<QualifiableFactor> {
 
 // 275 qualified_attribute = SELF group_qualifier attribute_qualifier .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct QualifiedAttribute<'a>(pub GroupQualifier<'a>,
                                   pub AttributeQualifier<'a>);
 fn qualified_attribute(s: &str) -> IResult<QualifiedAttribute> {
@@ -1690,6 +2315,8 @@ fn qualified_attribute(s: &str) -> IResult<QualifiedAttribute> {
 
 // 276
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum Qualifier<'a> {
     Attribute(AttributeQualifier<'a>),
     Group(GroupQualifier<'a>),
@@ -1707,6 +2334,8 @@ fn qualifier(s: &str) -> IResult<Qualifier> {
 // 277 query_expression = QUERY ’(’ variable_id ’<*’ aggregate_source ’|’
 //                        logical_expression ’)’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct QueryExpression<'a> {
     pub var: VariableId<'a>,
     pub aggregate: AggregateSource<'a>,
@@ -1731,6 +2360,8 @@ fn query_expression(s: &str) -> IResult<QueryExpression> {
 
 // 278 real_type = REAL [ ’(’ precision_spec ’)’ ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct RealType<'a>(Option<PrecisionSpec<'a>>);
 fn real_type(s: &str) -> IResult<RealType> {
     map(preceded(kw("real"),
@@ -1740,6 +2371,8 @@ fn real_type(s: &str) -> IResult<RealType> {
 
 // 279 redeclared_attribute = qualified_attribute [ RENAMED attribute_id ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct RedeclaredAttribute<'a>(pub QualifiedAttribute<'a>,
                                    pub Option<AttributeId<'a>>);
 fn redeclared_attribute(s: &str) -> IResult<RedeclaredAttribute> {
@@ -1750,6 +2383,8 @@ fn redeclared_attribute(s: &str) -> IResult<RedeclaredAttribute> {
 
 // 280 referenced_attribute = attribute_ref | qualified_attribute .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ReferencedAttribute<'a> {
     Ref(AttributeRef<'a>),
     Qualified(QualifiedAttribute<'a>),
@@ -1761,10 +2396,21 @@ fn referenced_attribute(s: &str) -> IResult<ReferencedAttribute> {
         map(qualified_attribute, Qualified),
     ))(s)
 }
+impl<'a> ReferencedAttribute<'a> {
+    /// Returns the name of the attribute, without any group qualifier
+    pub fn name(&self) -> &'a str {
+        match self {
+            ReferencedAttribute::Ref(a) => a.0,
+            ReferencedAttribute::Qualified(q) => (q.1).0.0,
+        }
+    }
+}
 
 // 281 reference_clause = REFERENCE FROM schema_ref [ ’(’ resource_or_rename
 //                        { ’,’ resource_or_rename } ’)’ ] ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct ReferenceClause<'a> {
     pub schema_ref: SchemaRef<'a>,
     pub resource_or_rename: Option<Vec<ResourceOrRename<'a>>>,
@@ -1772,7 +2418,7 @@ pub struct ReferenceClause<'a> {
 fn reference_clause(s: &str) -> IResult<ReferenceClause> {
     map(tuple((
         kw("reference"),
-        kw("front"),
+        kw("from"),
         schema_ref,
         opt(parens(list1(',', resource_or_rename))),
         char(';'),
@@ -1784,14 +2430,15 @@ fn reference_clause(s: &str) -> IResult<ReferenceClause> {
 
 // 282
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RelOp { LessThan, GreaterThan, LessThanOrEqual, GreaterThanOrEqual,
              NotEqual, Equal, InstanceEqual, InstanceNotEqual }
 fn rel_op(s: &str) -> IResult<RelOp> {
     use RelOp::*;
     alt((
         // Sorted by length to avoid prefix issues
-        map(tag(":<>:"), |_| InstanceEqual),
-        map(tag(":=:"),  |_| InstanceNotEqual),
+        map(tag(":<>:"), |_| InstanceNotEqual),
+        map(tag(":=:"),  |_| InstanceEqual),
         map(tag("<="),   |_| LessThanOrEqual),
         map(tag(">="),   |_| GreaterThanOrEqual),
         map(tag("<>"),   |_| NotEqual),
@@ -1803,6 +2450,7 @@ fn rel_op(s: &str) -> IResult<RelOp> {
 
 // 283
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RelOpExtended { RelOp(RelOp), In, Like }
 fn rel_op_extended(s: &str) -> IResult<RelOpExtended> {
     use RelOpExtended::*;
@@ -1814,6 +2462,8 @@ fn rel_op_extended(s: &str) -> IResult<RelOpExtended> {
 
 // 284
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum RenameId<'a> {
     Constant(ConstantId<'a>),
     Entity(EntityId<'a>),
@@ -1828,10 +2478,12 @@ fn rename_id(s: &str) -> IResult<RenameId> {
 
 // 285 repeat_control = [ increment_control ] [ while_control ] [ until_control ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct RepeatControl<'a>(
-    Option<IncrementControl<'a>>,
-    Option<WhileControl<'a>>,
-    Option<UntilControl<'a>>);
+    pub Option<IncrementControl<'a>>,
+    pub Option<WhileControl<'a>>,
+    pub Option<UntilControl<'a>>);
 fn repeat_control(s: &str) -> IResult<RepeatControl> {
     map(tuple((
         opt(increment_control),
@@ -1842,7 +2494,9 @@ fn repeat_control(s: &str) -> IResult<RepeatControl> {
 
 // 286 repeat_stmt = REPEAT repeat_control ’;’ stmt { stmt } END_REPEAT ’;’ .
 #[derive(Debug)]
-pub struct RepeatStmt<'a>(RepeatControl<'a>, Vec<Stmt<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct RepeatStmt<'a>(pub RepeatControl<'a>, pub Vec<Stmt<'a>>);
 fn repeat_stmt(s: &str) -> IResult<RepeatStmt> {
     map(tuple((
         kw("repeat"),
@@ -1859,7 +2513,9 @@ alias!(Repetition<'a>, NumericExpression, repetition);
 
 // 288
 #[derive(Debug)]
-pub struct ResourceOrRename<'a>(ResourceRef<'a>, Option<RenameId<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct ResourceOrRename<'a>(pub ResourceRef<'a>, pub Option<RenameId<'a>>);
 fn resource_or_rename(s: &str) -> IResult<ResourceOrRename> {
     map(pair(resource_ref, opt(preceded(kw("as"), rename_id))),
         |(a, b)| ResourceOrRename(a, b))(s)
@@ -1867,6 +2523,8 @@ fn resource_or_rename(s: &str) -> IResult<ResourceOrRename> {
 
 // 289
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ResourceRef<'a> {
     Constant(ConstantRef<'a>),
     Entity(EntityRef<'a>),
@@ -1882,7 +2540,9 @@ fn resource_ref(s: &str) -> IResult<ResourceRef> {
 
 // 290 return_stmt = RETURN [ ’(’ expression ’)’ ] ’;’ .
 #[derive(Debug)]
-pub struct ReturnStmt<'a>(Option<Expression<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct ReturnStmt<'a>(pub Option<Expression<'a>>);
 fn return_stmt(s:  &str) -> IResult<ReturnStmt> {
     map(delimited(
         kw("return"),
@@ -1892,6 +2552,8 @@ fn return_stmt(s:  &str) -> IResult<ReturnStmt> {
 
 // 291 rule_decl = rule_head algorithm_head { stmt } where_clause END_RULE ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct RuleDecl<'a> {
     pub rule_head: RuleHead<'a>,
     pub algorithm_head: AlgorithmHead<'a>,
@@ -1916,6 +2578,8 @@ fn rule_decl(s: &str) -> IResult<RuleDecl> {
 
 // 292 rule_head = RULE rule_id FOR ’(’ entity_ref { ’,’ entity_ref } ’)’ ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct RuleHead<'a> {
     pub rule_id: RuleId<'a>,
     pub entities: Vec<EntityRef<'a>>,
@@ -1941,12 +2605,16 @@ id_type!(RuleLabelId, rule_label_id);
 
 // 295
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum DeclarationOrRuleDecl<'a> {
     Declaration(Declaration<'a>),
     RuleDecl(RuleDecl<'a>),
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SchemaBody<'a> {
     pub interfaces: Vec<InterfaceSpecification<'a>>,
     pub constants: Option<ConstantDecl<'a>>,
@@ -1958,7 +2626,8 @@ fn schema_body(s: &str) -> IResult<SchemaBody> {
         opt(constant_decl),
         many0(alt((
             map(declaration, DeclarationOrRuleDecl::Declaration),
-            map(rule_decl, DeclarationOrRuleDecl::RuleDecl),
+            map(committed("rule", "rule_decl", rule_decl),
+                DeclarationOrRuleDecl::RuleDecl),
         ))),
     )), |(a, b, c)| SchemaBody {
         interfaces: a,
@@ -1968,6 +2637,8 @@ fn schema_body(s: &str) -> IResult<SchemaBody> {
 
 // 296
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SchemaDecl<'a> {
     pub id: SchemaId<'a>,
     pub version: Option<SchemaVersionId>,
@@ -1998,6 +2669,8 @@ alias!(Selector<'a>, Expression, selector);
 
 // 300
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SelectExtension<'a> {
     pub type_ref: TypeRef<'a>,
     pub select_list: Option<SelectList<'a>>,
@@ -2013,6 +2686,8 @@ fn select_extension(s: &str) -> IResult<SelectExtension> {
 
 // 301
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SelectList<'a>(pub Vec<NamedTypes<'a>>);
 fn select_list(s: &str) -> IResult<SelectList> {
     map(parens(list1(',', named_types)), SelectList)(s)
@@ -2021,11 +2696,15 @@ fn select_list(s: &str) -> IResult<SelectList> {
 // 302 select_type = [ EXTENSIBLE [ GENERIC_ENTITY ] ] SELECT [ select_list
 //                   | select_extension ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum SelectListOrExtension<'a> {
     List(SelectList<'a>),
     Extension(SelectExtension<'a>),
 }
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SelectType<'a> {
     pub extensible: bool,
     pub generic_entity: bool,
@@ -2048,6 +2727,8 @@ fn select_type(s: &str) -> IResult<SelectType> {
 
 // 303
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SetType<'a> {
     pub bounds: Option<BoundSpec<'a>>,
     pub instantiable_type: Box<InstantiableType<'a>>,
@@ -2069,7 +2750,11 @@ fn set_type(s: &str) -> IResult<SetType> {
 // not implemented because we're parsing floats using a separate library
 
 // 305 simple_expression = term { add_like_op term } .
+/// The first term, followed by each operator and term in order.  Operators
+/// are left-associative, so `a - b + c` is `(a - b) + c`.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SimpleExpression<'a>(pub Box<Term<'a>>, pub Vec<(AddLikeOp, Term<'a>)>);
 impl<'a> SimpleExpression<'a> {
     fn parse(s: &'a str) -> IResult<Self> {
@@ -2086,11 +2771,15 @@ fn simple_expression(s: &str) -> IResult<SimpleExpression> {
 //                     enumeration_reference | interval | query_expression |
 //                     ( [ unary_op ] ( ’(’ expression ’)’ | primary ) ) .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum ExpressionOrPrimary<'a> {
     Expression(Box<Expression<'a>>),
     Primary(Primary<'a>),
 }
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum SimpleFactor<'a> {
     // Both EntityConstructor and primary -> qualifiable_factor -> function_call
     // can match things of the form function_ref(expression, expression, ...),
@@ -2110,8 +2799,9 @@ fn ambiguous_function_call(s: &str) -> IResult<SimpleFactor> {
         // simple_id already refuses to eat built-in functions
         pair(simple_id, parens(list0(',', expression))),
         // ambiguous_function_call has a special-case to avoid eating a primary
-        // function call, e.g. "cross_product(axis, ref_direction).magnitude"
-        not(peek(alt((char('.'), char('\\')))))),
+        // function call with qualifiers, e.g. "cross_product(a, b).magnitude"
+        // or "bag_to_set(items)[1]"
+        not(peek(alt((char('.'), char('\\'), char('[')))))),
         |(a, b)| SimpleFactor::_AmbiguousFunctionCall(a, b))(s)
 }
 
@@ -2144,6 +2834,8 @@ fn simple_factor(s: &str) -> IResult<SimpleFactor> {
 // 307 simple_types = binary_type | boolean_type | integer_type | logical_type |
 //                    number_type | real_type | string_type .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum SimpleTypes<'a> {
     Binary(BinaryType<'a>), Boolean, Integer, Logical, Number,
     Real(RealType<'a>), String(StringType<'a>),
@@ -2170,6 +2862,8 @@ fn skip_stmt(s: &str) -> IResult<()> {
 //            if_stmt | null_stmt | procedure_call_stmt | repeat_stmt | return_stmt |
 //            skip_stmt .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum Stmt<'a> {
     Alias(AliasStmt<'a>),
     Assignment(AssignmentStmt<'a>),
@@ -2202,6 +2896,7 @@ fn stmt(s: &str) -> IResult<Stmt> {
 
 // 310
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StringLiteral(String);
 impl StringLiteral {
     fn parse(s: &str) -> IResult<Self> {
@@ -2212,6 +2907,8 @@ fn string_literal(s: &str) -> IResult<StringLiteral> { StringLiteral::parse(s) }
 
 // 311 string_type = STRING [ width_spec ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct StringType<'a>(Option<WidthSpec<'a>>);
 fn string_type(s: &str) -> IResult<StringType> {
     map(preceded(kw("string"), opt(width_spec)), StringType)(s)
@@ -2219,6 +2916,8 @@ fn string_type(s: &str) -> IResult<StringType> {
 
 // 312 subsuper = [ supertype_constraint ] [ subtype_declaration ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct Subsuper<'a>(pub Option<SupertypeConstraint<'a>>,
                         pub Option<SubtypeDeclaration<'a>>);
 fn subsuper(s: &str) -> IResult<Subsuper> {
@@ -2228,7 +2927,9 @@ fn subsuper(s: &str) -> IResult<Subsuper> {
 
 // 313 subtype_constraint = OF ’(’ supertype_expression ’)’ .
 #[derive(Debug)]
-pub struct SubtypeConstraint<'a>(SupertypeExpression<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct SubtypeConstraint<'a>(pub SupertypeExpression<'a>);
 fn subtype_constraint(s: &str) -> IResult<SubtypeConstraint> {
     map(preceded(kw("of"), parens(supertype_expression)),
         SubtypeConstraint)(s)
@@ -2237,6 +2938,8 @@ fn subtype_constraint(s: &str) -> IResult<SubtypeConstraint> {
 // 314 subtype_constraint_body = [ abstract_supertype ] [ total_over ]
 //                               [ supertype_expression ’;’ ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SubtypeConstraintBody<'a> {
     pub abstract_super: bool,
     pub total_over: Option<TotalOver<'a>>,
@@ -2257,8 +2960,10 @@ fn subtype_constraint_body(s: &str) -> IResult<SubtypeConstraintBody> {
 // 315 subtype_constraint_decl = subtype_constraint_head subtype_constraint_body
 //                               END_SUBTYPE_CONSTRAINT ’;’ .
 #[derive(Debug)]
-pub struct SubtypeConstraintDecl<'a>(SubtypeConstraintHead<'a>,
-                                     SubtypeConstraintBody<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct SubtypeConstraintDecl<'a>(pub SubtypeConstraintHead<'a>,
+                                     pub SubtypeConstraintBody<'a>);
 fn subtype_constraint_decl(s: &str) -> IResult<SubtypeConstraintDecl> {
     map(tuple((
         subtype_constraint_head,
@@ -2271,7 +2976,9 @@ fn subtype_constraint_decl(s: &str) -> IResult<SubtypeConstraintDecl> {
 // 316 subtype_constraint_head = SUBTYPE_CONSTRAINT subtype_constraint_id FOR
 //                               entity_ref ’;’ .
 #[derive(Debug)]
-pub struct SubtypeConstraintHead<'a>(SubtypeConstraintId<'a>, EntityRef<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct SubtypeConstraintHead<'a>(pub SubtypeConstraintId<'a>, pub EntityRef<'a>);
 fn subtype_constraint_head(s: &str) -> IResult<SubtypeConstraintHead> {
     map(tuple((
         kw("subtype_constraint"),
@@ -2287,6 +2994,8 @@ id_type!(SubtypeConstraintId, subtype_constraint_id);
 
 // 318 subtype_declaration = SUBTYPE OF ’(’ entity_ref { ’,’ entity_ref } ’)’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct SubtypeDeclaration<'a>(pub Vec<EntityRef<'a>>);
 fn subtype_declaration(s: &str) -> IResult<SubtypeDeclaration> {
     map(preceded(tuple((kw("subtype"), kw("of"))),
@@ -2297,6 +3006,8 @@ fn subtype_declaration(s: &str) -> IResult<SubtypeDeclaration> {
 // 319 supertype_constraint = abstract_entity_declaration |
 //                            abstract_supertype_declaration | supertype_rule .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum SupertypeConstraint<'a> {
     AbstractEntity,
     AbstractSupertype(AbstractSupertypeDeclaration<'a>),
@@ -2314,8 +3025,10 @@ fn supertype_constraint(s: &str) -> IResult<SupertypeConstraint> {
 
 // 320 supertype_expression = supertype_factor { ANDOR supertype_factor } .
 #[derive(Debug)]
-pub struct SupertypeExpression<'a>(SupertypeFactor<'a>,
-                               Vec<SupertypeFactor<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct SupertypeExpression<'a>(pub SupertypeFactor<'a>,
+                                   pub Vec<SupertypeFactor<'a>>);
 fn supertype_expression(s: &str) -> IResult<SupertypeExpression> {
     let (s, a) = supertype_factor(s)?;
     let (s, b) = many0(preceded(kw("andor"), supertype_factor))(s)?;
@@ -2324,7 +3037,9 @@ fn supertype_expression(s: &str) -> IResult<SupertypeExpression> {
 
 // 321 supertype_factor = supertype_term { AND supertype_term } .
 #[derive(Debug)]
-pub struct SupertypeFactor<'a>(Vec<SupertypeTerm<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct SupertypeFactor<'a>(pub Vec<SupertypeTerm<'a>>);
 fn supertype_factor(s: &str) -> IResult<SupertypeFactor> {
     map(separated_list1(kw("and"), supertype_term),
         SupertypeFactor)(s)
@@ -2332,13 +3047,17 @@ fn supertype_factor(s: &str) -> IResult<SupertypeFactor> {
 
 // 322 supertype_rule = SUPERTYPE subtype_constraint .
 #[derive(Debug)]
-pub struct SupertypeRule<'a>(SubtypeConstraint<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct SupertypeRule<'a>(pub SubtypeConstraint<'a>);
 fn supertype_rule(s: &str) -> IResult<SupertypeRule> {
     map(preceded(kw("supertype"), subtype_constraint), SupertypeRule)(s)
 }
 
 // 323 supertype_term = entity_ref | one_of | ’(’ supertype_expression ’)’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum SupertypeTerm<'a> {
     Entity(EntityRef<'a>),
     OneOf(OneOf<'a>),
@@ -2353,15 +3072,60 @@ fn supertype_term(s: &str) -> IResult<SupertypeTerm> {
     ))(s)
 }
 
+impl<'a> SupertypeExpression<'a> {
+    /// Returns every entity named in the expression, in order of appearance.
+    /// These are the subtypes which the expression constrains.
+    pub fn entity_refs(&self) -> Vec<EntityRef<'a>> {
+        let mut out = Vec::new();
+        self.collect_entity_refs(&mut out);
+        out
+    }
+    fn collect_entity_refs(&self, out: &mut Vec<EntityRef<'a>>) {
+        for factor in std::iter::once(&self.0).chain(self.1.iter()) {
+            for term in &factor.0 {
+                match term {
+                    SupertypeTerm::Entity(e) => out.push(*e),
+                    SupertypeTerm::OneOf(o) => for e in &o.0 {
+                        e.collect_entity_refs(out);
+                    },
+                    SupertypeTerm::Expression(e) => e.collect_entity_refs(out),
+                }
+            }
+        }
+    }
+}
+impl<'a> SupertypeConstraint<'a> {
+    /// Returns the supertype expression, if one is present
+    pub fn expression(&self) -> Option<&SupertypeExpression<'a>> {
+        match self {
+            SupertypeConstraint::AbstractEntity => None,
+            SupertypeConstraint::AbstractSupertype(a) =>
+                a.0.as_ref().map(|c| &c.0),
+            SupertypeConstraint::SupertypeRule(r) => Some(&(r.0).0),
+        }
+    }
+    /// Checks whether this constraint makes the entity abstract
+    pub fn is_abstract(&self) -> bool {
+        !matches!(self, SupertypeConstraint::SupertypeRule(_))
+    }
+}
+
 // 324 syntax = schema_decl { schema_decl } .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct Syntax<'a>(pub Vec<SchemaDecl<'a>>);
 fn syntax(s: &str) -> IResult<Syntax> {
-    preceded(multispace0, map(many1(schema_decl), Syntax))(s)
+    preceded(multispace0, map(
+        many1(committed("schema", "schema_decl", schema_decl)), Syntax))(s)
 }
 
 // 325 term = factor { multiplication_like_op factor } .
+/// The first factor, followed by each operator and factor in order, which
+/// are applied from left to right (like [`SimpleExpression`])
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct Term<'a>(pub Factor<'a>, pub Vec<(MultiplicationLikeOp, Factor<'a>)>);
 fn term(s: &str) -> IResult<Term> {
     map(pair(factor, many0(pair(multiplication_like_op, factor))),
@@ -2370,7 +3134,9 @@ fn term(s: &str) -> IResult<Term> {
 
 // 326 total_over = TOTAL_OVER ’(’ entity_ref { ’,’ entity_ref } ’)’ ’;’ .
 #[derive(Debug)]
-pub struct TotalOver<'a>(Vec<EntityRef<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct TotalOver<'a>(pub Vec<EntityRef<'a>>);
 fn total_over(s: &str) -> IResult<TotalOver> {
     map(delimited(
             kw("total_over"),
@@ -2381,6 +3147,8 @@ fn total_over(s: &str) -> IResult<TotalOver> {
 
 // 327 type_decl = TYPE type_id ’=’ underlying_type ’;’ [ where_clause ] END_TYPE ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct TypeDecl<'a> {
     pub type_id: TypeId<'a>,
     pub underlying_type: UnderlyingType<'a>,
@@ -2408,21 +3176,37 @@ id_type!(TypeId, type_id);
 
 // 329 type_label = type_label_id | type_label_ref .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum TypeLabel<'a> {
     Id(TypeLabelId<'a>),
     Ref(TypeLabelRef<'a>),
     _Ambiguous(SimpleId<'a>),
 }
+impl<'a> TypeLabel<'a> {
+    /// Returns the label's name.  A label is declared by its first use in a
+    /// function's formal parameters, and refers back to it anywhere else.
+    pub fn name(&self) -> &'a str {
+        match self {
+            TypeLabel::Id(i) => i.0.0,
+            TypeLabel::Ref(r) => r.0,
+            TypeLabel::_Ambiguous(s) => s.0,
+        }
+    }
+}
 fn type_label(s: &str) -> IResult<TypeLabel> {
     map(simple_id, TypeLabel::_Ambiguous)(s)
 }
 
 // 330
 #[derive(Debug)]
-pub struct TypeLabelId<'a>(SimpleId<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct TypeLabelId<'a>(pub SimpleId<'a>);
 
 // 331
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnaryOp { Add, Sub, Not }
 fn unary_op(s: &str) -> IResult<UnaryOp> {
     use UnaryOp::*;
@@ -2435,6 +3219,8 @@ fn unary_op(s: &str) -> IResult<UnaryOp> {
 
 // 332
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub enum UnderlyingType<'a> {
     Concrete(ConcreteTypes<'a>),
     Constructed(ConstructedTypes<'a>),
@@ -2451,7 +3237,9 @@ fn underlying_type(s: &str) -> IResult<UnderlyingType> {
 
 // 333 unique_clause = UNIQUE unique_rule ’;’ { unique_rule ’;’ } .
 #[derive(Debug)]
-pub struct UniqueClause<'a>(Vec<UniqueRule<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct UniqueClause<'a>(pub Vec<UniqueRule<'a>>);
 fn unique_clause(s: &str) -> IResult<UniqueClause> {
     map(preceded(kw("unique"), many1(terminated(unique_rule, char(';')))), UniqueClause)(s)
 }
@@ -2459,6 +3247,8 @@ fn unique_clause(s: &str) -> IResult<UniqueClause> {
 // 334 unique_rule = [ rule_label_id ’:’ ] referenced_attribute { ’,’
 //                   referenced_attribute } .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct UniqueRule<'a> {
     pub label: Option<RuleLabelId<'a>>,
     pub attrs: Vec<ReferencedAttribute<'a>>,
@@ -2471,7 +3261,9 @@ fn unique_rule(s: &str) -> IResult<UniqueRule> {
 
 // 335 until_control = UNTIL logical_expression .
 #[derive(Debug)]
-pub struct UntilControl<'a>(LogicalExpression<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct UntilControl<'a>(pub LogicalExpression<'a>);
 fn until_control(s: &str) -> IResult<UntilControl> {
     map(preceded(kw("until"), logical_expression), UntilControl)(s)
 }
@@ -2479,6 +3271,8 @@ fn until_control(s: &str) -> IResult<UntilControl> {
 // 336 use_clause = USE FROM schema_ref [ ’(’ named_type_or_rename
 //                  { ’,’ named_type_or_rename } ’)’ ] ’;’ .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct UseClause<'a> {
     pub schema_ref: SchemaRef<'a>,
     pub named_type_or_rename: Option<Vec<NamedTypeOrRename<'a>>>,
@@ -2501,7 +3295,9 @@ id_type!(VariableId, variable_id);
 
 // 338 where_clause = WHERE domain_rule ’;’ { domain_rule ’;’ } .
 #[derive(Debug)]
-pub struct WhereClause<'a>(Vec<DomainRule<'a>>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct WhereClause<'a>(pub Vec<DomainRule<'a>>);
 fn where_clause(s: &str) -> IResult<WhereClause> {
     let (s, _) = kw("where")(s)?;
     let (s, v) = many1(terminated(domain_rule, char(';')))(s)?;
@@ -2510,7 +3306,9 @@ fn where_clause(s: &str) -> IResult<WhereClause> {
 
 // 339 while_control = WHILE logical_expression .
 #[derive(Debug)]
-pub struct WhileControl<'a>(LogicalExpression<'a>);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
+pub struct WhileControl<'a>(pub LogicalExpression<'a>);
 fn while_control(s: &str) -> IResult<WhileControl> {
     map(preceded(kw("while"), logical_expression), WhileControl)(s)
 }
@@ -2520,6 +3318,8 @@ alias!(Width<'a>, NumericExpression, width);
 
 // 341 width_spec = ’(’ width ’)’ [ FIXED ] .
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize),
+           serde(bound(deserialize = "'de: 'a")))]
 pub struct WidthSpec<'a> { pub expression: Width<'a>, pub fixed: bool }
 fn width_spec(s: &str) -> IResult<WidthSpec> {
     map(pair(parens(width), opt(kw("fixed"))),
@@ -2770,6 +3570,19 @@ end_entity;  "#).unwrap();
         assert_eq!(e.0, ";");
     }
 
+    #[test]
+    fn test_tokens() {
+        assert_eq!(kw("end")("END_ENTITY;"), build_err("END_ENTITY;", "end"));
+        assert_eq!(kw("end_entity")("END_ENTITY ;").unwrap(), (";", "END_ENTITY"));
+        assert_eq!(kw("end")("end\n  x").unwrap(), ("x", "end"));
+        assert_eq!(tag(":=")(":= 1").unwrap(), ("1", ":="));
+        assert_eq!(char('(')("(\t)").unwrap(), (")", '('));
+        assert_eq!(char('(')(")").unwrap_err(),
+                   nom::Err::Error(ParseFailure::from_char(")", '(')));
+        assert_eq!(word("ab_1 c").unwrap(), (" c", "ab_1"));
+        assert!(word("_a").is_err());
+    }
+
     #[test]
     fn test_octet() {
         assert_eq!(octet("00").unwrap().1, "00");
@@ -2785,12 +3598,37 @@ end_entity;  "#).unwrap();
         assert_eq!(&encoded_string_literal("\"\"").unwrap().1, "");
         assert_eq!(&encoded_string_literal("\"00000041\"").unwrap().1, "A");
         assert_eq!(&encoded_string_literal("\"0000795e00006238\"").unwrap().1, "神戸");
+        assert_eq!(&encoded_string_literal("\"0001f600\"").unwrap().1, "😀");
+        assert!(encoded_string_literal("\"0000d800\"").is_err());
+        assert!(encoded_string_literal("\"00110000\"").is_err());
     }
 
     #[test]
     fn test_simple_string_literal() {
         assert_eq!(simple_string_literal("'omg'").unwrap().1, "omg");
         assert_eq!(simple_string_literal("'om''g'").unwrap().1, "om'g");
+        assert_eq!(simple_string_literal("'`größe` \\S\\'").unwrap().1,
+                   "`größe` \\S\\");
+        assert_eq!(simple_string_literal("'神戸'").unwrap().1, "神戸");
+    }
+
+    #[test]
+    fn test_unicode_ids() {
+        assert_eq!(simple_id("größe").unwrap().1, SimpleId("größe"));
+        assert_eq!(simple_id("длина_2 ").unwrap(), ("", SimpleId("длина_2")));
+        assert!(simple_id("_größe").is_err());
+        assert!(kw("entity")("entityé").is_err());
+
+        let s = strip_comments_and_lower("ENTITY Größe; -- Maß\n".as_bytes());
+        assert_eq!(s, "entity größe; \n");
+
+        // Files which aren't UTF-8 are read as ISO 8859-1
+        let s = strip_comments_and_lower(b"ENTITY Gr\xd6\xdfe; 'M\xc4\xdf'");
+        assert_eq!(s, "entity größe; 'MÄß'");
+
+        let (_, e) = entity_decl("ENTITY Größe; länge : REAL; END_ENTITY;")
+            .unwrap();
+        assert_eq!(e.0.0.0, "Größe");
     }
 
     #[test]
@@ -3190,6 +4028,411 @@ end_function;  "#).unwrap();
                    SimpleId("action_method"));
         assert_eq!(simple_id("action_property").unwrap().1,
                    SimpleId("action_property"));
+
+        // Reserved words are refused, but identifiers which merely start
+        // with one are fine
+        for k in &["end_type", "schema", "reference", "list", "escape",
+                   "generic_entity", "end_subtype_constraint", "typeof"] {
+            assert!(simple_id(k).is_err(), "{} should be reserved", k);
+        }
+        assert_eq!(simple_id("end_type_x").unwrap().1,
+                   SimpleId("end_type_x"));
+        assert_eq!(simple_id("schema1").unwrap().1, SimpleId("schema1"));
+        assert!(RESERVED.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_built_ins() {
+        // Built-in constants, functions, and procedures are parsed to their
+        // own nodes, never to identifiers or ambiguous calls
+        let constant = |s| match simple_factor(s).unwrap() {
+            ("", SimpleFactor::Unary(None, ExpressionOrPrimary::Primary(
+                Primary::Qualifiable(QualifiableFactor::ConstantFactor(
+                    ConstantFactor::BuiltIn(c)), q)))) if q.is_empty() => c,
+            r => panic!("Unexpected parse {:?}", r),
+        };
+        assert!(matches!(constant("pi"), BuiltInConstant::Pi));
+        assert!(matches!(constant("CONST_E"), BuiltInConstant::ConstE));
+        assert!(matches!(constant("self"), BuiltInConstant::Self_));
+        assert!(matches!(constant("?"), BuiltInConstant::Indeterminant));
+
+        let function = |s| match simple_factor(s).unwrap() {
+            ("", SimpleFactor::Unary(None, ExpressionOrPrimary::Primary(
+                Primary::Qualifiable(QualifiableFactor::FunctionCall(
+                    FunctionCall(BuiltInOrFunctionRef::BuiltIn(f), _)), _))))
+                => f,
+            r => panic!("Unexpected parse {:?}", r),
+        };
+        assert!(matches!(function("typeof(x)"), BuiltInFunction::Typeof));
+        assert!(matches!(function("sizeof(a)"), BuiltInFunction::SizeOf));
+        assert!(matches!(function("usedin(a, 'b')"), BuiltInFunction::Usedin));
+        assert!(matches!(function("value_in(a, b)"), BuiltInFunction::ValueIn));
+        assert!(matches!(function("exists(a)[1]"), BuiltInFunction::Exists));
+
+        // User-defined functions still go through the ambiguous branch
+        assert!(matches!(simple_factor("f(x)").unwrap().1,
+                         SimpleFactor::_AmbiguousFunctionCall(..)));
+
+        for (s, p) in &[("insert(l, x, 0);", BuiltInProcedure::Insert),
+                        ("REMOVE(l, 1);", BuiltInProcedure::Remove)] {
+            match procedure_call_stmt(s).unwrap().1.proc {
+                BuiltInOrProcedureRef::BuiltIn(b) =>
+                    assert_eq!(std::mem::discriminant(&b),
+                               std::mem::discriminant(p)),
+                r => panic!("Unexpected procedure {:?}", r),
+            }
+        }
+    }
+
+    #[test]
+    fn test_built_in_function_word() {
+        assert!(matches!(built_in_function("log10(x)").unwrap(),
+                         ("(x)", BuiltInFunction::Log10)));
+        assert!(matches!(built_in_function("value_in(a, b)").unwrap(),
+                         ("(a, b)", BuiltInFunction::ValueIn)));
+        assert!(built_in_function("valuex(a)").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let s = strip_comments_and_lower(b"SCHEMA tiny;
+TYPE color = ENUMERATION OF (red, green); END_TYPE;
+ENTITY circle;
+  radius : REAL;
+DERIVE
+  area : REAL := pi * radius ** 2;
+WHERE
+  wr1 : radius > 0.0;
+END_ENTITY;
+END_SCHEMA;");
+        let syntax = parse(&s).unwrap();
+        let json = serde_json::to_string(&syntax).unwrap();
+        let back: Syntax = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
     }
 
+    #[test]
+    fn test_supertype_expression() {
+        let e = entity_decl(r#"entity b_spline_curve
+abstract supertype of (oneof (uniform_curve, bezier_curve)
+                       andor rational_b_spline_curve)
+subtype of (bounded_curve);
+end_entity;"#).unwrap().1;
+        let c = (e.0).1.0.as_ref().unwrap();
+        assert!(c.is_abstract());
+        let names: Vec<&str> = c.expression().unwrap()
+            .entity_refs().iter().map(|e| e.0).collect();
+        assert_eq!(names,
+                   ["uniform_curve", "bezier_curve", "rational_b_spline_curve"]);
+        assert_eq!((e.0).1.1.as_ref().unwrap().0[0].0, "bounded_curve");
+    }
+
+    #[test]
+    fn test_subtype_constraint_decl() {
+        let (rest, d) = subtype_constraint_decl(r#"subtype_constraint sc1 for agent;
+  abstract supertype;
+  total_over (person, organization);
+  oneof (person, organization) and (robot andor program);
+end_subtype_constraint;"#).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!((d.0).0.0, "sc1");
+        assert_eq!((d.0).1.0, "agent");
+        assert!(d.1.abstract_super);
+        assert_eq!(d.1.total_over.as_ref().unwrap().0.len(), 2);
+        let expr = d.1.supertype.as_ref().unwrap();
+        assert_eq!(expr.0.0.len(), 2);
+        let names: Vec<&str> = expr.entity_refs().iter().map(|e| e.0).collect();
+        assert_eq!(names, ["person", "organization", "robot", "program"]);
+    }
+
+    #[test]
+    fn test_derive_clause() {
+        let e = entity_decl(r#"entity circle subtype of (conic);
+  radius : positive_length_measure;
+derive
+  self\geometric_representation_item.dim : dimension_count := 3;
+  diameter : real := 2.0 * radius;
+end_entity;"#).unwrap().1;
+        let d = &e.1.derive.as_ref().unwrap().0;
+        assert_eq!(d.len(), 2);
+        assert_eq!(d[0].0.name(), "dim");
+        match &d[0].0 {
+            AttributeDecl::Redeclared(r) => assert_eq!((r.0).0.0.0,
+                                                      "geometric_representation_item"),
+            a => panic!("Expected redeclared attribute, got {:?}", a),
+        }
+        assert_eq!(d[1].0.name(), "diameter");
+        assert!(matches!(d[1].1, ParameterType::Simple(SimpleTypes::Real(_))));
+    }
+
+    #[test]
+    fn test_inverse_clause() {
+        let e = entity_decl(r#"entity vertex_loop subtype of (loop);
+  loop_vertex : vertex;
+inverse
+  bounds : set [1:?] of face_bound for bound;
+  owner : action_assignment for assignment_item.assigned_action;
+end_entity;"#).unwrap().1;
+        let inv = &e.1.inverse.as_ref().unwrap().0;
+        assert_eq!(inv.len(), 2);
+        assert_eq!(inv[0].attribute_decl.name(), "bounds");
+        assert!(matches!(inv[0].bounds, Some((SetOrBag::Set, Some(_)))));
+        assert_eq!(inv[0].entity.0, "face_bound");
+        assert_eq!(inv[0].attribute_ref.0, "bound");
+        assert!(inv[1].bounds.is_none());
+        assert_eq!(inv[1].entity_for.as_ref().unwrap().0, "assignment_item");
+        assert_eq!(inv[1].attribute_ref.0, "assigned_action");
+    }
+
+    #[test]
+    fn test_unique_clause() {
+        let e = entity_decl(r#"entity person_and_organization;
+  the_person : person;
+  the_organization : organization;
+  id : identifier;
+unique
+  ur1 : the_person, the_organization;
+  id, self\person_and_organization.the_person;
+end_entity;"#).unwrap().1;
+        let u = &e.1.unique.as_ref().unwrap().0;
+        assert_eq!(u.len(), 2);
+        assert_eq!(u[0].label.as_ref().unwrap().0, "ur1");
+        let names: Vec<&str> = u[0].attrs.iter().map(|a| a.name()).collect();
+        assert_eq!(names, ["the_person", "the_organization"]);
+
+        assert!(u[1].label.is_none());
+        assert!(matches!(u[1].attrs[0], ReferencedAttribute::Ref(_)));
+        match &u[1].attrs[1] {
+            ReferencedAttribute::Qualified(q) =>
+                assert_eq!((q.0).0.0, "person_and_organization"),
+            a => panic!("Expected qualified attribute, got {:?}", a),
+        }
+        assert_eq!(u[1].attrs[1].name(), "the_person");
+    }
+
+    #[test]
+    fn test_constant_decl() {
+        let s = strip_comments_and_lower(br#"SCHEMA ap203_excerpt;
+CONSTANT
+  dummy_gri : geometric_representation_item := representation_item('') ||
+                  geometric_representation_item();
+  schema_prefix : STRING := 'AP203_CONFIGURATION_CONTROLLED_3D_DESIGN.';
+  limits : LIST [2:2] OF INTEGER := [0, 10];
+END_CONSTANT;
+ENTITY representation_item;
+  name : label;
+END_ENTITY;
+END_SCHEMA;"#);
+        let syntax = parse(&s).unwrap();
+        let c = &syntax.0[0].body.constants.as_ref().unwrap().0;
+        let names: Vec<&str> = c.iter().map(|c| c.constant_id.0).collect();
+        assert_eq!(names, ["dummy_gri", "schema_prefix", "limits"]);
+        assert!(matches!(c[1].instantiable_type,
+                         InstantiableType::Concrete(ConcreteTypes::Simple(
+                            SimpleTypes::String(_)))));
+        assert_eq!(syntax.0[0].body.declarations.len(), 1);
+    }
+
+    #[test]
+    fn test_local_decl() {
+        let e = local_decl(r#"local
+  n, m : integer := 0;
+  res : set of point := [];
+  tmp : real;
+end_local;"#).unwrap();
+        assert_eq!(e.0, "");
+        let vs = &(e.1).0;
+        assert_eq!(vs.len(), 3);
+        let ids: Vec<&str> = vs[0].variable_id.iter().map(|v| v.0).collect();
+        assert_eq!(ids, ["n", "m"]);
+        assert!(vs[0].expression.is_some());
+        assert!(vs[1].expression.is_some());
+        assert!(vs[2].expression.is_none());
+    }
+
+    #[test]
+    fn test_alias_stmt() {
+        let e = alias_stmt(r#"alias s for p\placement.location.coordinates;
+  s[1] := 0.0;
+  return (s);
+end_alias;"#).unwrap();
+        assert_eq!(e.0, "");
+        assert_eq!((e.1).variable.0, "s");
+        assert_eq!((e.1).qualifiers.len(), 3);
+        assert_eq!((e.1).stmts.len(), 2);
+
+        let e = function_decl(r#"function zeroed(p : cartesian_point) : real;
+local
+  total : real := 0.0;
+end_local;
+  alias c for p.coordinates;
+    repeat i := 1 to sizeof(c);
+      total := total + c[i];
+    end_repeat;
+  end_alias;
+  return (total);
+end_function;"#).unwrap();
+        assert_eq!(e.0, "");
+        let f = e.1;
+        assert_eq!(f.algorithm_head.local.unwrap().0.len(), 1);
+        assert!(matches!(f.stmts[0], Stmt::Alias(_)));
+    }
+
+    #[test]
+    fn test_preserve_case() {
+        let data = b"SCHEMA Geom;
+CONSTANT Label : STRING := 'Don''t -- (* lower *) me'; END_CONSTANT;
+ENTITY Cartesian_Point;
+  Coords : LIST [1:3] OF REAL;
+WHERE
+  WR1 : SIZEOF(Coords) > 0;
+END_ENTITY;
+END_SCHEMA;";
+
+        // Keywords match regardless of case, and names keep their spelling
+        let s = strip_comments_preserve_case(data);
+        let syntax = parse(&s).unwrap();
+        let schema = &syntax.0[0];
+        assert_eq!(schema.id.0, "Geom");
+        match &schema.body.declarations[0] {
+            DeclarationOrRuleDecl::Declaration(Declaration::Entity(e)) =>
+                assert_eq!((e.0).0.0, "Cartesian_Point"),
+            d => panic!("Unexpected declaration {:?}", d),
+        }
+
+        // Lower-casing leaves string literals alone, including text which
+        // would otherwise look like a comment
+        let s = strip_comments_and_lower(data);
+        assert!(s.contains("entity cartesian_point;"));
+        assert!(s.contains("'Don''t -- (* lower *) me'"));
+        assert!(parse(&s).is_ok());
+
+        // Uppercase keywords can't be used as identifiers either
+        assert!(simple_id("END_ENTITY").is_err());
+        assert!(built_in_function("SIZEOF").is_ok());
+    }
+
+    #[test]
+    fn test_operator_chains() {
+        let e = expression("a - b + c - d").unwrap();
+        assert_eq!(e.0, "");
+        let ops: Vec<_> = (e.1).0.1.iter()
+            .map(|(op, _)| matches!(op, AddLikeOp::Add))
+            .collect();
+        assert_eq!(ops, vec![false, true, false]);
+
+        let e = expression("a * b / c mod d").unwrap();
+        assert_eq!(e.0, "");
+        assert_eq!((e.1).0.0.1.len(), 3);
+
+        let e = expression("a ** 2 ** 3 + 1").unwrap();
+        assert_eq!(e.0, "");
+        let s = &(e.1).0;
+        assert_eq!(s.1.len(), 1);
+        assert_eq!(s.0.0.1.len(), 2);
+    }
+
+    #[test]
+    fn test_expression_productions() {
+        for e in [
+            "a[1]", "a[i:j + 1].b", "self\\foo.bar[2]", "a\\b.c\\d.e",
+            "[]", "[1, 2:3, x]", "f()", "f(a, [1, 2], g(b))",
+            "foo(a)[2]", "foo(1, 2).bar", "foo(1, 2)\\baz.x",
+            "sizeof(query(t <* typeof(x) | t like 'A*')) = 0",
+            "usedin(self, 'a.b.c') * ['x']", "{1 <= x < 3}", "-f(x) ** 2",
+        ] {
+            assert_eq!(expression(e).unwrap().0, "", "{}", e);
+        }
+
+        let e = expression("s[2:4]").unwrap();
+        assert_eq!(e.0, "");
+        let f = &(e.1).0.0.0.0;
+        match f {
+            SimpleFactor::Unary(None, ExpressionOrPrimary::Primary(
+                Primary::Qualifiable(_, qs))) =>
+                assert!(matches!(&qs[..], [Qualifier::Index(i)] if i.1.is_some())),
+            f => panic!("Unexpected {:?}", f),
+        }
+    }
+
+    #[test]
+    fn test_generalized_types() {
+        for f in [
+            "FUNCTION bag_to_set(the_bag : BAG OF GENERIC:intype)
+                : SET OF GENERIC:intype;
+             LOCAL the_set : SET OF GENERIC:intype := []; END_LOCAL;
+             RETURN (the_set); END_FUNCTION;",
+            "FUNCTION f(a : AGGREGATE:agg OF GENERIC:gen)
+                : AGGREGATE:agg OF GENERIC:gen; RETURN (a); END_FUNCTION;",
+            "FUNCTION f(a : ARRAY OF GENERIC; b : GENERIC_ENTITY) : LOGICAL;
+             RETURN (TRUE); END_FUNCTION;",
+            "FUNCTION f(a : ARRAY [1:?] OF OPTIONAL UNIQUE GENERIC_ENTITY)
+                : LIST [0:?] OF UNIQUE GENERIC_ENTITY:e; RETURN (a);
+             END_FUNCTION;",
+            "FUNCTION f(a : LIST OF LIST OF GENERIC:g)
+                : SET [1:?] OF AGGREGATE OF GENERIC_ENTITY; RETURN (a);
+             END_FUNCTION;",
+        ] {
+            let (rest, _) = function_decl(f).unwrap();
+            assert_eq!(rest, "");
+        }
+
+        let (_, f) = function_decl("FUNCTION f(a : ARRAY OF GENERIC:t)
+            : GENERIC:t; RETURN (a[1]); END_FUNCTION;").unwrap();
+        let p = &f.function_head.params.as_ref().unwrap()[0].1;
+        let a = match p {
+            ParameterType::Generalized(GeneralizedTypes::GeneralAggregation(
+                GeneralAggregationTypes::Array(a))) => a,
+            p => panic!("Unexpected type {:?}", p),
+        };
+        assert!(a.bounds.is_none());
+        match &*a.parameter_type {
+            ParameterType::Generalized(GeneralizedTypes::Generic(g)) =>
+                assert_eq!(g.0.as_ref().map(|t| t.name()), Some("t")),
+            p => panic!("Unexpected type {:?}", p),
+        }
+    }
+
+    #[test]
+    fn test_parse_recovering() {
+        let s = strip_comments_and_lower(b"SCHEMA a;
+USE FROM b;
+ENTITY good1; x : REAL; END_ENTITY;
+ENTITY bad1; x : REAL END_ENTITY;
+FUNCTION bad2(x : REAL) : REAL;
+  FUNCTION inner : REAL; RETURN (1); END_FUNCTION;
+  RETURN (x +); END_FUNCTION;
+TYPE good2 = STRING; END_TYPE;
+ENTITY bad3; s : STRING; WHERE wr1: s <> 'END_ENTITY;'; oops END_ENTITY;
+ENTITY good3; END_ENTITY;
+END_SCHEMA;
+SCHEMA 1bad; END_SCHEMA;
+SCHEMA c; ENTITY good4; END_ENTITY;");
+        let (syntax, errors) = parse_recovering(&s);
+        let names: Vec<Vec<&str>> = syntax.0.iter()
+            .map(|s| s.body.declarations.iter()
+                .map(|d| match d {
+                    DeclarationOrRuleDecl::Declaration(Declaration::Entity(e))
+                        => (e.0).0.0,
+                    DeclarationOrRuleDecl::Declaration(Declaration::Type(t))
+                        => t.type_id.0,
+                    d => panic!("Unexpected declaration {:?}", d),
+                })
+                .collect())
+            .collect();
+        assert_eq!(names, [vec!["good1", "good2", "good3"], vec!["good4"]]);
+        assert_eq!(syntax.0[0].body.interfaces.len(), 1);
+
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [4, 7, 9, 12, 13]);
+        assert_eq!(errors[0].expected, "';'");
+        assert_eq!(errors[4].expected, "end_schema");
+
+        // Valid input gives no errors
+        let (syntax, errors) = parse_recovering(
+            "schema a; entity b; end_entity; end_schema;");
+        assert!(errors.is_empty());
+        assert_eq!(syntax.0[0].body.declarations.len(), 1);
+    }
 }
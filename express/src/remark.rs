@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use crate::parse::{
+    strip_comments, Declaration, DeclarationOrRuleDecl, Syntax,
+};
+
+/// A comment (or "remark", in EXPRESS terms) from the original file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Remark {
+    /// Byte offset in the comment-stripped text where the remark was removed
+    pub offset: usize,
+    /// Tag for tagged remarks (`(*"schema.entity" ... *)`), in lower-case
+    pub tag: Option<String>,
+    /// Text of the remark, with the tag and surrounding whitespace removed
+    pub text: String,
+}
+
+impl Remark {
+    pub(crate) fn new(offset: usize, data: &[u8]) -> Self {
+        let text = String::from_utf8_lossy(data);
+        let text = text.trim();
+        let (tag, text) = match text.strip_prefix('"')
+            .and_then(|t| t.split_once('"'))
+        {
+            Some((tag, rest)) => (Some(tag.trim().to_lowercase()), rest.trim()),
+            None => (None, text),
+        };
+        Self { offset, tag, text: text.to_owned() }
+    }
+}
+
+/// Like [`strip_comments_and_lower`](crate::parse::strip_comments_and_lower),
+/// but also returns every remark which was removed, in file order.
+pub fn strip_comments_keep_remarks(data: &[u8]) -> (String, Vec<Remark>) {
+    let mut remarks = Vec::new();
+    let s = strip_comments(data, true, Some(&mut remarks));
+    (s, remarks)
+}
+
+/// Documentation for declarations, built by attaching remarks to the parsed
+/// syntax tree
+#[derive(Clone, Debug, Default)]
+pub struct Docs(HashMap<String, Vec<String>>);
+
+impl Docs {
+    /// Attaches remarks to declarations in `syntax`, which must have been
+    /// parsed from `s` (the output of [`strip_comments_keep_remarks`]).
+    ///
+    /// Tagged remarks are attached to the item named by their tag; a leading
+    /// schema name is removed, so `"ap214.cartesian_point"` documents the
+    /// `cartesian_point` entity, while `"cartesian_point.name"` documents its
+    /// `name` attribute.
+    ///
+    /// Untagged remarks which immediately precede a declaration document
+    /// that declaration.  Otherwise, they're attached to the declaration which
+    /// contains them (or to the schema, if they're outside of any
+    /// declaration).
+    pub fn new(s: &str, syntax: &Syntax, remarks: &[Remark]) -> Self {
+        // Find the position of every declaration's name in the input text,
+        // using the fact that the syntax tree borrows from it.
+        let mut decls = Vec::new();
+        for schema in &syntax.0 {
+            if let Some(offset) = offset_in(s, schema.id.0) {
+                decls.push((offset, "schema", schema.id.0));
+            }
+            for d in &schema.body.declarations {
+                let (kw, name) = declaration_name(d);
+                if let Some(offset) = offset_in(s, name) {
+                    decls.push((offset, kw, name));
+                }
+            }
+        }
+        decls.sort_unstable();
+
+        let schemas: Vec<&str> = syntax.0.iter().map(|s| s.id.0).collect();
+        let mut out: HashMap<String, Vec<String>> = HashMap::new();
+        for r in remarks.iter().filter(|r| !r.text.is_empty()) {
+            let key = if let Some(tag) = &r.tag {
+                match tag.split_once('.') {
+                    Some((schema, rest)) if schemas.contains(&schema) =>
+                        rest.to_owned(),
+                    _ => tag.clone(),
+                }
+            } else {
+                // Index of the first declaration after this remark
+                let i = decls.partition_point(|d| d.0 < r.offset);
+                let leading = decls.get(i).map(|(offset, kw, _)|
+                    s[r.offset..*offset].trim() == *kw);
+                match (leading, i) {
+                    (Some(true), _) => decls[i].2.to_owned(),
+                    (_, 0) => continue,
+                    (_, i) => decls[i - 1].2.to_owned(),
+                }
+            };
+            out.entry(key).or_default().extend(
+                r.text.lines().map(|line| line.trim().to_owned()));
+        }
+        Self(out)
+    }
+
+    /// Returns documentation lines for the given name, which is either a
+    /// declaration (`entity`) or an attribute (`entity.attr`)
+    pub fn get(&self, name: &str) -> &[String] {
+        self.0.get(name).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Returns the offset of `name` within `s`, if it's a slice of `s`
+fn offset_in(s: &str, name: &str) -> Option<usize> {
+    let offset = (name.as_ptr() as usize).checked_sub(s.as_ptr() as usize)?;
+    if offset + name.len() <= s.len() {
+        Some(offset)
+    } else {
+        None
+    }
+}
+
+/// Returns the keyword which introduces a declaration, along with its name
+fn declaration_name<'a>(d: &DeclarationOrRuleDecl<'a>) -> (&'static str, &'a str) {
+    match d {
+        DeclarationOrRuleDecl::Declaration(d) => match d {
+            Declaration::Entity(e) => ("entity", (e.0).0.0),
+            Declaration::Function(f) => ("function", f.function_head.id.0),
+            Declaration::Procedure(p) => ("procedure", p.0.procedure_id.0),
+            Declaration::SubtypeConstraint(c) =>
+                ("subtype_constraint", (c.0).0.0),
+            Declaration::Type(t) => ("type", t.type_id.0),
+        },
+        DeclarationOrRuleDecl::RuleDecl(r) => ("rule", r.rule_head.rule_id.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse;
+
+    #[test]
+    fn test_remarks() {
+        let (s, remarks) = strip_comments_keep_remarks(br#"SCHEMA foo;
+(* A point in space *)
+ENTITY point;
+  x : REAL; -- the X coordinate
+END_ENTITY;
+-- Distances are always positive
+TYPE distance = REAL;
+END_TYPE;
+(*"foo.point.x" Measured in mm *)
+END_SCHEMA;"#);
+        assert_eq!(remarks.len(), 4);
+        assert_eq!(remarks[3].tag.as_deref(), Some("foo.point.x"));
+        assert_eq!(remarks[3].text, "Measured in mm");
+
+        let syntax = parse(&s).unwrap();
+        let docs = Docs::new(&s, &syntax, &remarks);
+        assert_eq!(docs.get("point"), ["A point in space", "the X coordinate"]);
+        assert_eq!(docs.get("distance"), ["Distances are always positive"]);
+        assert_eq!(docs.get("point.x"), ["Measured in mm"]);
+        assert!(docs.get("foo").is_empty());
+    }
+}
@@ -0,0 +1,914 @@
+use std::collections::HashMap;
+
+use crate::{error::locate, parse::*};
+
+/// A named declaration, which can be the target of a reference
+#[derive(Copy, Clone, Debug)]
+pub enum Decl<'a> {
+    Entity(&'a EntityDecl<'a>),
+    Type(&'a TypeDecl<'a>),
+    Function(&'a FunctionDecl<'a>),
+    Procedure(&'a ProcedureDecl<'a>),
+    Rule(&'a RuleDecl<'a>),
+    Constant(&'a ConstantBody<'a>),
+    SubtypeConstraint(&'a SubtypeConstraintDecl<'a>),
+
+    /// A name imported from a schema which isn't part of the parsed file, so
+    /// we don't know what kind of declaration it is
+    External { schema: &'a str, name: &'a str },
+}
+
+impl<'a> Decl<'a> {
+    /// Returns the declared name (which may differ from the name used to
+    /// look it up, if it was renamed with `AS` when imported)
+    pub fn name(&self) -> &'a str {
+        match self {
+            Decl::Entity(e) => (e.0).0.0,
+            Decl::Type(t) => t.type_id.0,
+            Decl::Function(f) => f.function_head.id.0,
+            Decl::Procedure(p) => p.0.procedure_id.0,
+            Decl::Rule(r) => r.rule_head.rule_id.0,
+            Decl::Constant(c) => c.constant_id.0,
+            Decl::SubtypeConstraint(c) => (c.0).0.0,
+            Decl::External { name, .. } => name,
+        }
+    }
+}
+
+impl<'a> From<&'a Declaration<'a>> for Decl<'a> {
+    fn from(d: &'a Declaration<'a>) -> Self {
+        match d {
+            Declaration::Entity(e) => Decl::Entity(e),
+            Declaration::Function(f) => Decl::Function(f),
+            Declaration::Procedure(p) => Decl::Procedure(p),
+            Declaration::SubtypeConstraint(c) => Decl::SubtypeConstraint(c),
+            Declaration::Type(t) => Decl::Type(t),
+        }
+    }
+}
+
+/// What sort of declaration a reference expects to find
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RefKind {
+    Entity,
+    Type,
+    /// Either an entity or a defined type.  The parser can't tell these
+    /// apart in most type positions (e.g. `x : SET OF foo`).
+    Named,
+    /// A function call, which may also be an entity constructor
+    Function,
+    Procedure,
+    /// An item in a `USE` or `REFERENCE` clause
+    Interface,
+}
+
+impl RefKind {
+    fn accepts(self, d: &Decl) -> bool {
+        matches!((self, d),
+            (_, Decl::External { .. }) | (RefKind::Interface, _) |
+            (RefKind::Entity, Decl::Entity(_)) |
+            (RefKind::Type, Decl::Type(_)) |
+            (RefKind::Named, Decl::Entity(_) | Decl::Type(_)) |
+            (RefKind::Function, Decl::Function(_) | Decl::Entity(_)) |
+            (RefKind::Procedure, Decl::Procedure(_)))
+    }
+}
+
+/// A reference which couldn't be resolved
+#[derive(Clone, Debug)]
+pub struct Unresolved<'a> {
+    pub name: &'a str,
+    pub kind: RefKind,
+    /// Schema in which the reference appears
+    pub schema: &'a str,
+    /// The declaration which was found instead, if the name exists but is the
+    /// wrong kind of thing (e.g. a function used as an attribute type)
+    pub found: Option<Decl<'a>>,
+}
+
+impl<'a> Unresolved<'a> {
+    /// Returns the line number (starting from 1) of the reference, given the
+    /// same text that was passed to the parser
+    pub fn line(&self, input: &str) -> Option<usize> {
+        locate(input, self.name).map(|(line, _)| line)
+    }
+}
+
+/// Declarations which are visible at the top level of a schema, including
+/// anything imported through its interface specifications
+#[derive(Debug, Default)]
+pub struct Scope<'a> {
+    pub names: HashMap<&'a str, Decl<'a>>,
+    /// Set if the schema imports everything from a schema which isn't part of
+    /// the parsed file.  In that case, unknown names can't be reported.
+    pub open: bool,
+}
+
+/// Maps names to declarations, for every schema in a [`Syntax`]
+#[derive(Debug, Default)]
+pub struct SymbolTable<'a> {
+    pub schemas: HashMap<&'a str, Scope<'a>>,
+}
+
+impl<'a> SymbolTable<'a> {
+    /// Looks up a name at the top level of the given schema
+    pub fn lookup(&self, schema: &str, name: &str) -> Option<Decl<'a>> {
+        self.schemas.get(schema).and_then(|s| s.names.get(name)).copied()
+    }
+
+    /// Builds a table from every schema's declarations, then resolves their
+    /// `USE` and `REFERENCE` clauses.  Imports are followed transitively, so
+    /// an item which schema `a` uses from `b` is visible to a schema that
+    /// uses everything from `a`.  Interface items which don't exist in their
+    /// source schema are returned as [`Unresolved`].
+    fn build(syntax: &'a Syntax<'a>) -> (Self, Vec<Unresolved<'a>>) {
+        let mut out = Self::default();
+        for s in &syntax.0 {
+            let mut scope = Scope::default();
+            declare_all(&mut scope.names, s.body.constants.as_ref(),
+                        s.body.declarations.iter().map(|d| match d {
+                            DeclarationOrRuleDecl::Declaration(d) => Ok(d),
+                            DeclarationOrRuleDecl::RuleDecl(r) => Err(r),
+                        }));
+            out.schemas.insert(s.id.0, scope);
+        }
+
+        // Repeat until nothing changes, so that chains of imports settle
+        let mut unresolved = Vec::new();
+        loop {
+            let mut changed = false;
+            unresolved.clear();
+            for s in &syntax.0 {
+                for i in &s.body.interfaces {
+                    let (from, items, everything) = interface(i);
+                    let from_scope = match out.schemas.get(from) {
+                        Some(f) => f,
+                        None => {
+                            // We can't see inside this schema, so take
+                            // imported names on trust
+                            let scope = out.schemas.get_mut(s.id.0).unwrap();
+                            if items.is_empty() {
+                                changed |= !scope.open;
+                                scope.open = true;
+                            }
+                            for (name, rename) in items {
+                                let d = Decl::External { schema: from, name };
+                                changed |= scope.names.insert(rename, d)
+                                    .is_none();
+                            }
+                            continue;
+                        },
+                    };
+                    let mut found = Vec::new();
+                    if items.is_empty() {
+                        found.extend(from_scope.names.iter()
+                            .filter(|(_, d)| everything ||
+                                    matches!(d, Decl::Entity(_) | Decl::Type(_)))
+                            .map(|(k, d)| (*k, *d)));
+                    }
+                    for (name, rename) in items {
+                        match from_scope.names.get(name) {
+                            Some(d) => found.push((rename, *d)),
+                            None if from_scope.open => found.push(
+                                (rename, Decl::External { schema: from, name })),
+                            None => unresolved.push(Unresolved {
+                                name,
+                                kind: RefKind::Interface,
+                                schema: s.id.0,
+                                found: None,
+                            }),
+                        }
+                    }
+                    let open = from_scope.open;
+                    let scope = out.schemas.get_mut(s.id.0).unwrap();
+                    if open && !scope.open {
+                        scope.open = true;
+                        changed = true;
+                    }
+                    for (k, d) in found {
+                        if !scope.names.contains_key(k) {
+                            scope.names.insert(k, d);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        (out, unresolved)
+    }
+}
+
+/// Unpacks an interface specification into the source schema, a list of
+/// `(name, local name)` items, and whether a bare import brings in every
+/// kind of declaration (`REFERENCE`) or only entities and types (`USE`).
+pub(crate) fn interface<'a>(i: &InterfaceSpecification<'a>)
+    -> (&'a str, Vec<(&'a str, &'a str)>, bool)
+{
+    match i {
+        InterfaceSpecification::UseClause(u) => {
+            let items = u.named_type_or_rename.iter().flatten()
+                .map(|n| {
+                    let name = match &n.named_types {
+                        NamedTypes::Entity(e) => e.0,
+                        NamedTypes::Type(t) => t.0,
+                        NamedTypes::_Ambiguous(s) => s.0,
+                    };
+                    let rename = match &n.rename {
+                        Some(EntityOrTypeId::Entity(e)) |
+                        Some(EntityOrTypeId::Type(e)) => e.0,
+                        Some(EntityOrTypeId::_Ambiguous(s)) => s.0,
+                        None => name,
+                    };
+                    (name, rename)
+                })
+                .collect();
+            (u.schema_ref.0, items, false)
+        },
+        InterfaceSpecification::ReferenceClause(r) => {
+            let items = r.resource_or_rename.iter().flatten()
+                .map(|r| {
+                    let name = match &r.0 {
+                        ResourceRef::Constant(c) => c.0,
+                        ResourceRef::Entity(e) => e.0,
+                        ResourceRef::Function(f) => f.0,
+                        ResourceRef::Procedure(p) => p.0,
+                        ResourceRef::Type(t) => t.0,
+                        ResourceRef::_Ambiguous(s) => s.0,
+                    };
+                    let rename = match &r.1 {
+                        Some(RenameId::Constant(c)) => c.0,
+                        Some(RenameId::Entity(e)) => e.0,
+                        Some(RenameId::Function(f)) => f.0,
+                        Some(RenameId::Procedure(p)) => p.0,
+                        Some(RenameId::Type(t)) => t.0,
+                        Some(RenameId::_Ambiguous(s)) => s.0,
+                        None => name,
+                    };
+                    (name, rename)
+                })
+                .collect();
+            (r.schema_ref.0, items, true)
+        },
+    }
+}
+
+/// Adds a block of constants and declarations to a scope
+fn declare_all<'a, I>(names: &mut HashMap<&'a str, Decl<'a>>,
+                      constants: Option<&'a ConstantDecl<'a>>, decls: I)
+    where I: Iterator<Item=Result<&'a Declaration<'a>, &'a RuleDecl<'a>>>
+{
+    for c in constants.iter().flat_map(|c| c.0.iter()) {
+        names.insert(c.constant_id.0, Decl::Constant(c));
+    }
+    for d in decls {
+        let d = match d {
+            Ok(d) => Decl::from(d),
+            Err(r) => Decl::Rule(r),
+        };
+        names.insert(d.name(), d);
+    }
+}
+
+/// Builds a symbol table for every schema in the syntax tree, then checks
+/// every reference to an entity, type, function, or procedure against it.
+///
+/// Returns the symbol table and every reference which couldn't be resolved
+/// (in the order in which they appear).  Variables, attributes, constants,
+/// and enumeration items aren't checked, since the parser can't tell them
+/// apart inside expressions.
+///
+/// ```
+/// use express::{parse::{parse, strip_comments_and_lower}, resolve::resolve};
+///
+/// let s = strip_comments_and_lower(b"SCHEMA s;
+/// ENTITY e;
+///   x : length_measure;
+///   y : REAL;
+/// END_ENTITY;
+/// END_SCHEMA;");
+/// let syntax = parse(&s).unwrap();
+/// let (_table, unresolved) = resolve(&syntax);
+/// assert_eq!(unresolved.len(), 1);
+/// assert_eq!(unresolved[0].name, "length_measure");
+/// ```
+pub fn resolve<'a>(syntax: &'a Syntax<'a>)
+    -> (SymbolTable<'a>, Vec<Unresolved<'a>>)
+{
+    let (table, mut unresolved) = SymbolTable::build(syntax);
+    for s in &syntax.0 {
+        let mut r = Resolver {
+            table: &table,
+            schema: s.id.0,
+            scopes: Vec::new(),
+            out: Vec::new(),
+            refs: None,
+        };
+        r.schema_body(&s.body);
+        unresolved.extend(r.out);
+    }
+    (table, unresolved)
+}
+
+/// Returns every name which a declaration may use from the top level of its
+/// schema, in the order in which they appear (possibly with duplicates).
+///
+/// This is a superset of the references that [`resolve`] checks: bare names
+/// in expressions are included too, since they may be constants.  As a
+/// result, some names may turn out to be attributes, parameters, or
+/// enumeration items, so callers should ignore names which they can't find.
+pub(crate) fn references<'a>(d: Decl<'a>) -> Vec<&'a str> {
+    let table = SymbolTable::default();
+    let mut r = Resolver {
+        table: &table,
+        schema: "",
+        scopes: Vec::new(),
+        out: Vec::new(),
+        refs: Some(Vec::new()),
+    };
+    r.decl(d);
+    r.refs.unwrap_or_default()
+}
+
+/// Walks a single schema, checking references as it goes
+struct Resolver<'a, 'b> {
+    table: &'b SymbolTable<'a>,
+    schema: &'a str,
+    /// Local declarations in functions, procedures, and rules (innermost last)
+    scopes: Vec<HashMap<&'a str, Decl<'a>>>,
+    out: Vec<Unresolved<'a>>,
+    /// If present, names are collected here instead of being checked
+    refs: Option<Vec<&'a str>>,
+}
+
+impl<'a, 'b> Resolver<'a, 'b> {
+    fn check(&mut self, name: &'a str, kind: RefKind) {
+        if self.refs.is_some() {
+            return self.mention(name);
+        }
+        let found = self.scopes.iter().rev()
+            .find_map(|s| s.get(name))
+            .copied()
+            .or_else(|| self.table.lookup(self.schema, name));
+        match found {
+            Some(d) if kind.accepts(&d) => (),
+            None if self.table.schemas[self.schema].open => (),
+            found => self.out.push(Unresolved {
+                name, kind, schema: self.schema, found
+            }),
+        }
+    }
+
+    /// Records a name which isn't declared locally, if collecting references.
+    /// This is also used for names which can't be checked, because the parser
+    /// doesn't know what they refer to.
+    fn mention(&mut self, name: &'a str) {
+        if let Some(refs) = &mut self.refs {
+            if !self.scopes.iter().any(|s| s.contains_key(name)) {
+                refs.push(name);
+            }
+        }
+    }
+
+    fn schema_body(&mut self, b: &'a SchemaBody<'a>) {
+        self.constants(b.constants.as_ref());
+        for d in &b.declarations {
+            match d {
+                DeclarationOrRuleDecl::Declaration(d) => self.declaration(d),
+                DeclarationOrRuleDecl::RuleDecl(r) => self.rule(r),
+            }
+        }
+    }
+
+    fn constants(&mut self, c: Option<&'a ConstantDecl<'a>>) {
+        for c in c.iter().flat_map(|c| c.0.iter()) {
+            self.decl(Decl::Constant(c));
+        }
+    }
+
+    fn declaration(&mut self, d: &'a Declaration<'a>) {
+        self.decl(Decl::from(d))
+    }
+
+    fn decl(&mut self, d: Decl<'a>) {
+        match d {
+            Decl::Entity(e) => self.entity(e),
+            Decl::Function(f) => {
+                let h = &f.function_head;
+                for p in h.params.iter().flatten() {
+                    self.parameter_type(&p.1);
+                }
+                self.parameter_type(&h.out);
+                self.algorithm(&f.algorithm_head, &f.stmts, None);
+            },
+            Decl::Procedure(p) => {
+                for (_, p) in (p.0).args.iter().flatten() {
+                    self.parameter_type(&p.1);
+                }
+                self.algorithm(&p.1, &p.2, None);
+            },
+            Decl::SubtypeConstraint(c) => {
+                self.check((c.0).1.0, RefKind::Entity);
+                for e in c.1.total_over.iter().flat_map(|t| t.0.iter()) {
+                    self.check(e.0, RefKind::Entity);
+                }
+                if let Some(e) = &c.1.supertype {
+                    self.supertype_expression(e);
+                }
+            },
+            Decl::Type(t) => {
+                match &t.underlying_type {
+                    UnderlyingType::Concrete(c) => self.concrete_types(c),
+                    UnderlyingType::Constructed(c) => self.constructed_types(c),
+                }
+                self.where_clause(t.where_clause.as_ref());
+            },
+            Decl::Rule(r) => self.rule(r),
+            Decl::Constant(c) => {
+                self.instantiable_type(&c.instantiable_type);
+                self.expression(&c.expression);
+            },
+            Decl::External { .. } => (),
+        }
+    }
+
+    fn rule(&mut self, r: &'a RuleDecl<'a>) {
+        for e in &r.rule_head.entities {
+            self.check(e.0, RefKind::Entity);
+        }
+        self.algorithm(&r.algorithm_head, &r.stmt, Some(&r.where_clause));
+    }
+
+    /// Checks the body of a function, procedure, or rule, with its local
+    /// declarations in scope
+    fn algorithm(&mut self, h: &'a AlgorithmHead<'a>, stmts: &'a [Stmt<'a>],
+                 where_clause: Option<&'a WhereClause<'a>>)
+    {
+        let mut scope = HashMap::new();
+        declare_all(&mut scope, h.constant.as_ref(),
+                    h.declaration.iter().map(Ok));
+        self.scopes.push(scope);
+
+        for d in &h.declaration {
+            self.declaration(d);
+        }
+        self.constants(h.constant.as_ref());
+        for v in h.local.iter().flat_map(|l| l.0.iter()) {
+            self.parameter_type(&v.parameter_type);
+            if let Some(e) = &v.expression {
+                self.expression(e);
+            }
+        }
+        self.stmts(stmts);
+        self.where_clause(where_clause);
+
+        self.scopes.pop();
+    }
+
+    fn entity(&mut self, e: &'a EntityDecl<'a>) {
+        let Subsuper(sup, sub) = &(e.0).1;
+        match sup {
+            Some(SupertypeConstraint::AbstractSupertype(
+                AbstractSupertypeDeclaration(Some(c)))) |
+            Some(SupertypeConstraint::SupertypeRule(SupertypeRule(c))) =>
+                self.supertype_expression(&c.0),
+            _ => (),
+        }
+        for s in sub.iter().flat_map(|s| s.0.iter()) {
+            self.check(s.0, RefKind::Entity);
+        }
+
+        let b = &e.1;
+        for a in &b.explicit_attr {
+            for d in &a.attributes {
+                self.attribute_decl(d);
+            }
+            self.parameter_type(&a.parameter_type);
+        }
+        for d in b.derive.iter().flat_map(|d| d.0.iter()) {
+            self.attribute_decl(&d.0);
+            self.parameter_type(&d.1);
+            self.expression(&d.2);
+        }
+        for i in b.inverse.iter().flat_map(|i| i.0.iter()) {
+            self.attribute_decl(&i.attribute_decl);
+            if let Some((_, Some(b))) = &i.bounds {
+                self.bound_spec(b);
+            }
+            self.check(i.entity.0, RefKind::Entity);
+            if let Some(e) = &i.entity_for {
+                self.check(e.0, RefKind::Entity);
+            }
+        }
+        for u in b.unique.iter().flat_map(|u| u.0.iter()) {
+            for a in &u.attrs {
+                if let ReferencedAttribute::Qualified(q) = a {
+                    self.check((q.0).0.0, RefKind::Entity);
+                }
+            }
+        }
+        self.where_clause(b.where_.as_ref());
+    }
+
+    fn attribute_decl(&mut self, a: &'a AttributeDecl<'a>) {
+        if let AttributeDecl::Redeclared(r) = a {
+            self.check((r.0).0.0.0, RefKind::Entity);
+        }
+    }
+
+    fn supertype_expression(&mut self, e: &'a SupertypeExpression<'a>) {
+        for f in std::iter::once(&e.0).chain(e.1.iter()) {
+            for t in &f.0 {
+                match t {
+                    SupertypeTerm::Entity(e) => self.check(e.0, RefKind::Entity),
+                    SupertypeTerm::OneOf(o) => for e in &o.0 {
+                        self.supertype_expression(e);
+                    },
+                    SupertypeTerm::Expression(e) =>
+                        self.supertype_expression(e),
+                }
+            }
+        }
+    }
+
+    fn where_clause(&mut self, w: Option<&'a WhereClause<'a>>) {
+        for r in w.iter().flat_map(|w| w.0.iter()) {
+            self.expression(&r.expression);
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Types
+
+    fn named_types(&mut self, n: &'a NamedTypes<'a>) {
+        match n {
+            NamedTypes::Entity(e) => self.check(e.0, RefKind::Entity),
+            NamedTypes::Type(t) => self.check(t.0, RefKind::Type),
+            NamedTypes::_Ambiguous(s) => self.check(s.0, RefKind::Named),
+        }
+    }
+
+    fn parameter_type(&mut self, p: &'a ParameterType<'a>) {
+        match p {
+            ParameterType::Generalized(g) => match g {
+                GeneralizedTypes::Aggregate(a) => self.parameter_type(&a.1),
+                GeneralizedTypes::GeneralAggregation(a) => match a {
+                    GeneralAggregationTypes::Array(a) => {
+                        self.bound_spec_opt(a.bounds.as_ref());
+                        self.parameter_type(&a.parameter_type);
+                    },
+                    GeneralAggregationTypes::Bag(b) => {
+                        self.bound_spec_opt(b.0.as_ref());
+                        self.parameter_type(&b.1);
+                    },
+                    GeneralAggregationTypes::List(l) => {
+                        self.bound_spec_opt(l.bounds.as_ref());
+                        self.parameter_type(&l.parameter_type);
+                    },
+                    GeneralAggregationTypes::Set(s) => {
+                        self.bound_spec_opt(s.bounds.as_ref());
+                        self.parameter_type(&s.parameter_type);
+                    },
+                },
+                GeneralizedTypes::GenericEntity(_) |
+                GeneralizedTypes::Generic(_) => (),
+            },
+            ParameterType::Named(n) => self.named_types(n),
+            ParameterType::Simple(_) => (),
+        }
+    }
+
+    fn instantiable_type(&mut self, i: &'a InstantiableType<'a>) {
+        match i {
+            InstantiableType::Concrete(c) => self.concrete_types(c),
+            InstantiableType::EntityRef(e) => self.check(e.0, RefKind::Entity),
+        }
+    }
+
+    fn concrete_types(&mut self, c: &'a ConcreteTypes<'a>) {
+        match c {
+            ConcreteTypes::Aggregation(a) => match a {
+                AggregationTypes::Array(a) => {
+                    self.bound_spec(&a.bounds);
+                    self.instantiable_type(&a.instantiable_type);
+                },
+                AggregationTypes::Bag(b) => {
+                    self.bound_spec_opt(b.0.as_ref());
+                    self.instantiable_type(&b.1);
+                },
+                AggregationTypes::List(l) => {
+                    self.bound_spec_opt(l.bounds.as_ref());
+                    self.instantiable_type(&l.instantiable_type);
+                },
+                AggregationTypes::Set(s) => {
+                    self.bound_spec_opt(s.bounds.as_ref());
+                    self.instantiable_type(&s.instantiable_type);
+                },
+            },
+            ConcreteTypes::Simple(_) => (),
+            // The parser reads any name here as a type_ref, even though it
+            // may also be an entity
+            ConcreteTypes::TypeRef(t) => self.check(t.0, RefKind::Named),
+        }
+    }
+
+    fn constructed_types(&mut self, c: &'a ConstructedTypes<'a>) {
+        match c {
+            ConstructedTypes::Enumeration(e) => {
+                if let Some(EnumerationItemsOrExtension::Extension(x)) =
+                    &e.items_or_extension
+                {
+                    self.check(x.type_ref.0, RefKind::Type);
+                }
+            },
+            ConstructedTypes::Select(s) => match &s.list_or_extension {
+                SelectListOrExtension::List(l) => for n in &l.0 {
+                    self.named_types(n);
+                },
+                SelectListOrExtension::Extension(x) => {
+                    self.check(x.type_ref.0, RefKind::Type);
+                    for n in x.select_list.iter().flat_map(|l| l.0.iter()) {
+                        self.named_types(n);
+                    }
+                },
+            },
+        }
+    }
+
+    fn bound_spec(&mut self, b: &'a BoundSpec<'a>) {
+        self.simple_expression(&(b.0).0.0);
+        self.simple_expression(&(b.1).0.0);
+    }
+
+    fn bound_spec_opt(&mut self, b: Option<&'a BoundSpec<'a>>) {
+        if let Some(b) = b {
+            self.bound_spec(b);
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Statements
+
+    fn stmts(&mut self, stmts: &'a [Stmt<'a>]) {
+        for s in stmts {
+            self.stmt(s);
+        }
+    }
+
+    fn stmt(&mut self, s: &'a Stmt<'a>) {
+        match s {
+            Stmt::Alias(a) => {
+                self.qualifiers(&a.qualifiers);
+                self.stmts(&a.stmts);
+            },
+            Stmt::Assignment(a) => {
+                self.qualifiers(&a.qualifiers);
+                self.expression(&a.expression);
+            },
+            Stmt::Case(c) => {
+                self.expression(&c.selector.0);
+                for a in &c.actions {
+                    for label in &a.0 {
+                        self.expression(&label.0);
+                    }
+                    self.stmt(&a.1);
+                }
+                if let Some(s) = &c.otherwise {
+                    self.stmt(s);
+                }
+            },
+            Stmt::Compound(c) => self.stmts(&c.0),
+            Stmt::If(i) => {
+                self.expression(&i.0.0);
+                self.stmts(&i.1);
+                if let Some(e) = &i.2 {
+                    self.stmts(e);
+                }
+            },
+            Stmt::ProcedureCall(p) => {
+                if let BuiltInOrProcedureRef::ProcedureRef(r) = &p.proc {
+                    self.check(r.0, RefKind::Procedure);
+                }
+                for a in p.params.iter().flat_map(|p| p.0.iter()) {
+                    self.expression(&a.0);
+                }
+            },
+            Stmt::Repeat(r) => {
+                let RepeatControl(inc, w, u) = &r.0;
+                if let Some(i) = inc {
+                    self.simple_expression(&i.bound1.0.0);
+                    self.simple_expression(&i.bound2.0.0);
+                    if let Some(step) = &i.increment {
+                        self.simple_expression(&step.0.0);
+                    }
+                }
+                if let Some(w) = w {
+                    self.expression(&(w.0).0);
+                }
+                if let Some(u) = u {
+                    self.expression(&(u.0).0);
+                }
+                self.stmts(&r.1);
+            },
+            Stmt::Return(r) => if let Some(e) = &r.0 {
+                self.expression(e);
+            },
+            Stmt::Escape | Stmt::Null | Stmt::Skip => (),
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Expressions
+
+    fn expression(&mut self, e: &'a Expression<'a>) {
+        self.simple_expression(&e.0);
+        if let Some((_, b)) = &e.1 {
+            self.simple_expression(b);
+        }
+    }
+
+    fn simple_expression(&mut self, e: &'a SimpleExpression<'a>) {
+        for t in std::iter::once(&*e.0).chain(e.1.iter().map(|t| &t.1)) {
+            for f in std::iter::once(&t.0).chain(t.1.iter().map(|f| &f.1)) {
+                for g in std::iter::once(&f.0).chain(f.1.iter()) {
+                    self.simple_factor(g);
+                }
+            }
+        }
+    }
+
+    fn simple_factor(&mut self, f: &'a SimpleFactor<'a>) {
+        match f {
+            SimpleFactor::_AmbiguousFunctionCall(id, args) => {
+                self.check(id.0, RefKind::Function);
+                for a in args {
+                    self.expression(a);
+                }
+            },
+            SimpleFactor::AggregateInitializer(a) => for e in &a.0 {
+                self.expression(&e.0);
+                if let Some(r) = &e.1 {
+                    self.simple_expression(&r.0.0);
+                }
+            },
+            SimpleFactor::EntityConstructor(c) => {
+                self.check(c.entity_ref.0, RefKind::Entity);
+                for a in &c.args {
+                    self.expression(a);
+                }
+            },
+            SimpleFactor::EnumerationReference(r) => if let Some(t) = &r.0 {
+                self.check(t.0, RefKind::Type);
+            },
+            SimpleFactor::Interval(i) => {
+                self.simple_expression(&i.low.0);
+                self.simple_expression(&i.item.0);
+                self.simple_expression(&i.high.0);
+            },
+            SimpleFactor::QueryExpression(q) => {
+                self.simple_expression(&q.aggregate.0);
+                self.expression(&q.logical_expression.0);
+            },
+            SimpleFactor::Unary(_, ExpressionOrPrimary::Expression(e)) =>
+                self.expression(e),
+            SimpleFactor::Unary(_, ExpressionOrPrimary::Primary(p)) =>
+                if let Primary::Qualifiable(f, qs) = p {
+                    self.qualifiable_factor(f);
+                    self.qualifiers(qs);
+                },
+        }
+    }
+
+    fn qualifiable_factor(&mut self, f: &'a QualifiableFactor<'a>) {
+        match f {
+            QualifiableFactor::FunctionCall(c) => {
+                if let BuiltInOrFunctionRef::Ref(r) = &c.0 {
+                    self.check(r.0, RefKind::Function);
+                }
+                for a in &(c.1).0 {
+                    self.expression(&a.0);
+                }
+            },
+            QualifiableFactor::Population(p) =>
+                self.check((p.0).0, RefKind::Entity),
+            QualifiableFactor::ConstantFactor(ConstantFactor::ConstantRef(c)) =>
+                self.mention(c.0),
+            QualifiableFactor::_Ambiguous(s) => self.mention(s),
+            QualifiableFactor::AttributeRef(_) |
+            QualifiableFactor::ConstantFactor(_) |
+            QualifiableFactor::GeneralRef(_) => (),
+        }
+    }
+
+    fn qualifiers(&mut self, qs: &'a [Qualifier<'a>]) {
+        for q in qs {
+            match q {
+                Qualifier::Attribute(_) => (),
+                Qualifier::Group(g) => self.check((g.0).0, RefKind::Entity),
+                Qualifier::Index(i) => {
+                    self.simple_expression(&(i.0).0.0.0);
+                    if let Some(j) = &i.1 {
+                        self.simple_expression(&j.0.0.0);
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    #[test]
+    fn test_resolve() {
+        let s = strip_comments_and_lower(br#"SCHEMA geom;
+TYPE length_measure = REAL; END_TYPE;
+TYPE label = STRING; END_TYPE;
+ENTITY point;
+  x, y : length_measure;
+WHERE
+  wr1 : positive(x) AND missing_fn(y);
+END_ENTITY;
+ENTITY named_point SUBTYPE OF (point, label);
+  name : label;
+  owner : shape;
+END_ENTITY;
+FUNCTION positive(v : length_measure) : BOOLEAN;
+  TYPE local_t = INTEGER; END_TYPE;
+  LOCAL
+    n : local_t;
+  END_LOCAL;
+  RETURN (v > 0);
+END_FUNCTION;
+END_SCHEMA;
+
+SCHEMA app;
+USE FROM geom (point AS pt);
+REFERENCE FROM elsewhere (colour);
+ENTITY thing;
+  at : pt;
+  c : colour;
+  p : point;
+END_ENTITY;
+END_SCHEMA;
+"#);
+        let syntax = parse(&s).unwrap();
+        let (table, unresolved) = resolve(&syntax);
+
+        assert!(matches!(table.lookup("geom", "point"), Some(Decl::Entity(_))));
+        assert!(matches!(table.lookup("geom", "positive"),
+                         Some(Decl::Function(_))));
+        assert!(table.lookup("geom", "local_t").is_none());
+        match table.lookup("app", "pt") {
+            Some(d @ Decl::Entity(_)) => assert_eq!(d.name(), "point"),
+            d => panic!("Unexpected decl {:?}", d),
+        }
+        assert!(matches!(table.lookup("app", "colour"),
+                         Some(Decl::External { schema: "elsewhere", .. })));
+
+        let names: Vec<(&str, &str, RefKind)> = unresolved.iter()
+            .map(|u| (u.schema, u.name, u.kind))
+            .collect();
+        assert_eq!(names, [
+            ("geom", "missing_fn", RefKind::Function),
+            ("geom", "label", RefKind::Entity),
+            ("geom", "shape", RefKind::Named),
+            ("app", "point", RefKind::Named),
+        ]);
+        assert!(unresolved[0].found.is_none());
+        assert!(matches!(unresolved[1].found, Some(Decl::Type(_))));
+        let lines: Vec<usize> = unresolved.iter()
+            .map(|u| u.line(&s).unwrap())
+            .collect();
+        assert_eq!(lines, [7, 9, 11, 28]);
+    }
+
+    #[test]
+    fn test_transitive_imports() {
+        let s = strip_comments_and_lower(br#"SCHEMA a;
+USE FROM b;
+ENTITY x; y : leaf; z : other; END_ENTITY;
+END_SCHEMA;
+SCHEMA b;
+USE FROM c (leaf, nope);
+END_SCHEMA;
+SCHEMA c;
+ENTITY leaf; END_ENTITY;
+END_SCHEMA;
+"#);
+        let syntax = parse(&s).unwrap();
+        let (table, unresolved) = resolve(&syntax);
+        assert!(matches!(table.lookup("a", "leaf"), Some(Decl::Entity(_))));
+        let names: Vec<(&str, &str, RefKind)> = unresolved.iter()
+            .map(|u| (u.schema, u.name, u.kind))
+            .collect();
+        assert_eq!(names, [
+            ("b", "nope", RefKind::Interface),
+            ("a", "other", RefKind::Named),
+        ]);
+    }
+}
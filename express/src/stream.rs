@@ -0,0 +1,338 @@
+//! Parses EXPRESS incrementally from a [`Read`] source.
+//!
+//! The syntax tree borrows from its input, so the whole file normally has to
+//! be in memory as one `&str` before anything can be parsed.  This module
+//! instead splits the input into top-level items (a schema header, an
+//! interface, the constant block, one declaration, or `END_SCHEMA`) as soon
+//! as each one has been read, which suits editors and other tools that want
+//! to work through a large schema without waiting for all of it.
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> {
+//! use express::{parse::SchemaItem, stream::StreamParser};
+//! let file = std::fs::File::open("ap242.exp")?;
+//! for fragment in StreamParser::new(file) {
+//!     let fragment = fragment?;
+//!     match fragment.parse() {
+//!         Ok(SchemaItem::Schema(id, _)) => println!("schema {}", id.0),
+//!         Ok(_) => (),
+//!         Err(e) => eprintln!("line {}: expected {}", e.line, e.expected),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::io::{self, Read};
+
+use crate::{
+    error::Error,
+    parse::{schema_item, strip_comments, SchemaItem},
+};
+
+/// Number of bytes to read at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The text of one top-level item, with comments removed and converted to
+/// lower-case (like [`strip_comments_and_lower`]).
+///
+/// [`strip_comments_and_lower`]: crate::parse::strip_comments_and_lower
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fragment {
+    text: String,
+    line: usize,
+}
+
+impl Fragment {
+    /// Returns the item's text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the line in the original input where the item begins
+    /// (counting from 1), including any whitespace or comments before it
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Parses the item.  On failure, the error's `line` is a line number in
+    /// the original input, but its `offset` is relative to [`Self::text`].
+    pub fn parse(&self) -> Result<SchemaItem<'_>, Error> {
+        schema_item(&self.text).map_err(|mut e| {
+            e.line += self.line - 1;
+            e
+        })
+    }
+}
+
+/// Where the scanner is within the input
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum State {
+    Code,
+    String(u8),
+    LineComment,
+    BlockComment,
+}
+
+/// Splits an EXPRESS file into [`Fragment`]s as it's read, yielding each
+/// one once the `;` that ends it has arrived.
+///
+/// Items are found by looking at keywords, comments, and string literals,
+/// without parsing: a declaration such as `ENTITY` runs until the matching
+/// `END_ENTITY;`, counting nested declarations of the same kind (e.g. local
+/// functions), while a header or interface runs to the next `;`.  Text which
+/// isn't a complete item at the end of the input is yielded as a final
+/// fragment, so that parsing it reports the error.
+pub struct StreamParser<R> {
+    reader: R,
+    eof: bool,
+
+    /// Raw input which hasn't been yielded yet
+    buf: Vec<u8>,
+    /// Line number at the start of `buf`
+    line: usize,
+
+    /// How far the current item has been scanned, and the state there
+    pos: usize,
+    state: State,
+    /// First keyword of the current item, in lower-case
+    opener: Option<String>,
+    /// Nesting depth of the opener's declaration kind
+    depth: usize,
+    /// Whether the closing `END_*` keyword has been seen, so the item ends
+    /// at the next `;`
+    closed: bool,
+}
+
+impl<R: Read> StreamParser<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            eof: false,
+            buf: Vec::new(),
+            line: 1,
+            pos: 0,
+            state: State::Code,
+            opener: None,
+            depth: 0,
+            closed: false,
+        }
+    }
+
+    /// Scans the current item, returning its length if it's complete.
+    /// Returns `None` if more input is needed.
+    fn scan(&mut self) -> Option<usize> {
+        // Two-byte tokens and words may be cut off by the end of the buffer,
+        // in which case we wait for more input (unless there isn't any)
+        let more = !self.eof;
+        while self.pos < self.buf.len() {
+            let b = &self.buf;
+            let i = self.pos;
+            let next = b.get(i + 1).copied();
+            if next.is_none() && more && matches!(b[i], b'(' | b'-' | b'*') {
+                return None;
+            }
+            match (self.state, b[i]) {
+                (State::String(q), c) => if c == q {
+                    self.state = State::Code;
+                },
+                (State::LineComment, b'\n') => self.state = State::Code,
+                (State::LineComment, _) => (),
+                (State::BlockComment, b'*') if next == Some(b')') => {
+                    self.state = State::Code;
+                    self.pos += 1;
+                },
+                (State::BlockComment, _) => (),
+                (State::Code, q @ (b'\'' | b'"')) => self.state = State::String(q),
+                (State::Code, b'-') if next == Some(b'-') =>
+                    self.state = State::LineComment,
+                (State::Code, b'(') if next == Some(b'*') => {
+                    self.state = State::BlockComment;
+                    self.pos += 1;
+                },
+                (State::Code, b';') => {
+                    let ends = match self.opener.as_deref() {
+                        Some(o) if declaration_kind(o) => self.closed,
+                        _ => true,
+                    };
+                    if ends {
+                        return Some(i + 1);
+                    }
+                },
+                (State::Code, c) if is_word(c) => {
+                    let len = b[i..].iter().position(|c| !is_word(*c));
+                    let len = match len {
+                        Some(len) => len,
+                        None if more => return None,
+                        None => b.len() - i,
+                    };
+                    let word = String::from_utf8_lossy(&b[i..i + len])
+                        .to_ascii_lowercase();
+                    self.word(word);
+                    self.pos += len;
+                    continue;
+                },
+                (State::Code, _) => (),
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    /// Updates the nesting state on reaching a keyword or identifier
+    fn word(&mut self, word: String) {
+        match self.opener.as_deref() {
+            None => {
+                self.depth = 1;
+                self.opener = Some(word);
+            },
+            Some(o) if declaration_kind(o) && !self.closed => {
+                if word == o {
+                    self.depth += 1;
+                } else if word.strip_prefix("end_") == Some(o) {
+                    self.depth -= 1;
+                    self.closed = self.depth == 0;
+                }
+            },
+            _ => (),
+        }
+    }
+
+    /// Removes the first `n` bytes of the buffer as a fragment
+    fn take(&mut self, n: usize) -> Fragment {
+        let raw: Vec<u8> = self.buf.drain(..n).collect();
+        let line = self.line;
+        self.line += memchr::memchr_iter(b'\n', &raw).count();
+        self.pos = 0;
+        self.state = State::Code;
+        self.opener = None;
+        self.depth = 0;
+        self.closed = false;
+        Fragment { text: strip_comments(&raw, true, None), line }
+    }
+}
+
+impl<R: Read> Iterator for StreamParser<R> {
+    type Item = io::Result<Fragment>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(n) = self.scan() {
+                return Some(Ok(self.take(n)));
+            }
+            if self.eof {
+                // Leftover text (other than whitespace and comments) is an
+                // incomplete item
+                let f = self.take(self.buf.len());
+                return if f.text.trim().is_empty() { None } else { Some(Ok(f)) };
+            }
+            let start = self.buf.len();
+            self.buf.resize(start + CHUNK_SIZE, 0);
+            let n = loop {
+                match self.reader.read(&mut self.buf[start..]) {
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    r => break r,
+                }
+            };
+            match n {
+                Ok(n) => {
+                    self.buf.truncate(start + n);
+                    self.eof = n == 0;
+                },
+                Err(e) => {
+                    self.buf.truncate(start);
+                    return Some(Err(e));
+                },
+            }
+        }
+    }
+}
+
+/// Checks whether a keyword opens a declaration which ends with `END_*`
+fn declaration_kind(w: &str) -> bool {
+    matches!(w, "entity" | "type" | "function" | "procedure" | "rule" |
+                "constant" | "subtype_constraint")
+}
+
+/// Checks whether a byte may be part of a keyword or identifier.  Non-ASCII
+/// bytes are included, since identifiers may contain non-ASCII letters.
+fn is_word(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse, strip_comments_and_lower};
+
+    /// Reads a few bytes at a time, to exercise items which span chunks
+    struct Trickle<'a>(&'a [u8]);
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_stream_fixture() {
+        let data = include_bytes!("../tests/fixtures/grammar.exp");
+        let stripped = strip_comments_and_lower(data);
+        let syntax = parse(&stripped).unwrap();
+        let schema = &syntax.0[0];
+
+        let fragments: Vec<Fragment> = StreamParser::new(Trickle(data))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        let items: Vec<SchemaItem> = fragments.iter()
+            .map(|f| f.parse().unwrap())
+            .collect();
+        match &items[0] {
+            SchemaItem::Schema(id, _) => assert_eq!(id.0, schema.id.0),
+            i => panic!("Unexpected item {:?}", i),
+        }
+        assert!(matches!(items.last(), Some(SchemaItem::EndSchema)));
+        let count = |f: fn(&SchemaItem) -> bool| items.iter()
+            .filter(|i| f(i))
+            .count();
+        assert_eq!(count(|i| matches!(i, SchemaItem::Interface(_))),
+                   schema.body.interfaces.len());
+        assert_eq!(count(|i| matches!(i, SchemaItem::Declaration(_))),
+                   schema.body.declarations.len());
+
+        // Line numbers refer to the original file
+        let lines: Vec<&str> = std::str::from_utf8(data).unwrap()
+            .lines()
+            .collect();
+        for f in &fragments {
+            let first = f.text.split_whitespace().next().unwrap();
+            let skipped = f.text.len() - f.text.trim_start().len();
+            let line = f.line + f.text[..skipped].matches('\n').count();
+            assert!(lines[line - 1].to_lowercase().contains(first),
+                    "{:?} is not on line {}", first, line);
+        }
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let data = b"SCHEMA s;\n\
+            (* ENTITY x; *)\n\
+            FUNCTION f : INTEGER;\n  FUNCTION g : INTEGER; RETURN (1); END_FUNCTION;\n\
+            \x20 RETURN ('END_FUNCTION;'); -- END_FUNCTION;\n\
+            END_FUNCTION;\n\
+            ENTITY e;\n  x : ;\nEND_ENTITY;\n\
+            TYPE t = INTEGER;";
+        let fragments: Vec<Fragment> = StreamParser::new(&data[..])
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(fragments.len(), 4);
+        assert_eq!(fragments.iter().map(|f| f.line).collect::<Vec<_>>(),
+                   [1, 1, 6, 9]);
+        assert!(matches!(fragments[1].parse(), Ok(SchemaItem::Declaration(_))));
+
+        // An error within an item, and an item which is cut off
+        assert_eq!(fragments[2].parse().unwrap_err().line, 8);
+        assert_eq!(fragments[3].parse().unwrap_err().expected, "end_type");
+    }
+}
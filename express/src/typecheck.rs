@@ -0,0 +1,998 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::locate,
+    parse::*,
+    resolve::{Decl, SymbolTable},
+};
+
+/// Defined types are followed through at most this many levels of
+/// indirection, which protects us from cycles in broken schemas
+const MAX_DEPTH: usize = 16;
+
+/// A coarse EXPRESS type, which is precise enough to catch mixups between
+/// numeric, logical, string, and aggregate values
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ty<'a> {
+    /// `INTEGER`, `REAL`, or `NUMBER`
+    Number,
+    /// `BOOLEAN` or `LOGICAL`
+    Logical,
+    String,
+    Binary,
+    Aggregate(Box<Ty<'a>>),
+    Entity(&'a str),
+    /// An enumeration, by type name
+    Enumeration(&'a str),
+    /// Anything that can't be inferred (e.g. `SELECT` types, generics, or
+    /// `?`), which is compatible with every other type
+    Unknown,
+}
+
+impl<'a> Ty<'a> {
+    /// Checks whether two types could hold the same value.  Entities and
+    /// enumerations are treated loosely, since we don't track subtypes or
+    /// extensible enumerations.
+    pub fn compatible(&self, other: &Ty) -> bool {
+        use Ty::*;
+        match (self, other) {
+            (Unknown, _) | (_, Unknown) => true,
+            (Aggregate(a), Aggregate(b)) => a.compatible(b),
+            (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+
+    /// Returns the element type of an aggregate (or `Unknown`)
+    fn element(&self) -> Option<Ty<'a>> {
+        match self {
+            Ty::Aggregate(t) => Some((**t).clone()),
+            Ty::Unknown => Some(Ty::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> std::fmt::Display for Ty<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Ty::Number => write!(f, "a number"),
+            Ty::Logical => write!(f, "a logical"),
+            Ty::String => write!(f, "a string"),
+            Ty::Binary => write!(f, "a binary"),
+            Ty::Aggregate(t) => write!(f, "an aggregate of {}", t),
+            Ty::Entity(e) => write!(f, "an entity ({})", e),
+            Ty::Enumeration(e) => write!(f, "an enumeration ({})", e),
+            Ty::Unknown => write!(f, "an unknown type"),
+        }
+    }
+}
+
+/// A type mismatch, found within a particular expression
+#[derive(Clone, Debug)]
+pub struct TypeError<'a> {
+    /// Source text of the innermost expression containing the error
+    pub span: &'a str,
+    pub message: String,
+}
+
+impl<'a> TypeError<'a> {
+    /// Returns the (line, column) of the error, given the same text that was
+    /// passed to the parser
+    pub fn location(&self, input: &str) -> Option<(usize, usize)> {
+        locate(input, self.span)
+    }
+}
+
+impl<'a> std::fmt::Display for TypeError<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} (in `{}`)", self.message, self.span)
+    }
+}
+
+/// Type-checks every WHERE rule, derived attribute, constant, and function,
+/// procedure, or rule body in the syntax tree.  Names are looked up in a
+/// symbol table from [`resolve`](crate::resolve::resolve); anything which
+/// can't be resolved is given the [`Ty::Unknown`] type, so undefined names
+/// don't produce type errors here as well.
+pub fn check<'a>(syntax: &'a Syntax<'a>, table: &SymbolTable<'a>)
+    -> Vec<TypeError<'a>>
+{
+    let mut out = Vec::new();
+    for s in &syntax.0 {
+        let mut c = Checker {
+            table,
+            schema: s.id.0,
+            decls: Vec::new(),
+            scopes: Vec::new(),
+            returns: None,
+            span: "",
+            attrs: HashMap::new(),
+            out: Vec::new(),
+        };
+        c.schema_body(&s.body);
+        out.extend(c.out);
+    }
+    out
+}
+
+struct Checker<'a, 'b> {
+    table: &'b SymbolTable<'a>,
+    schema: &'a str,
+    /// Local declarations in functions, procedures, and rules
+    decls: Vec<HashMap<&'a str, Decl<'a>>>,
+    /// Types of attributes, parameters, and variables (innermost last)
+    scopes: Vec<HashMap<&'a str, Ty<'a>>>,
+    /// Return type of the enclosing function, if any
+    returns: Option<Ty<'a>>,
+    /// Source text of the expression being checked
+    span: &'a str,
+    /// Cache of attribute types for each entity (including inherited ones)
+    attrs: HashMap<&'a str, HashMap<&'a str, Ty<'a>>>,
+    out: Vec<TypeError<'a>>,
+}
+
+impl<'a, 'b> Checker<'a, 'b> {
+    fn error(&mut self, message: String) {
+        self.out.push(TypeError { span: self.span, message });
+    }
+
+    /// Records an error if `got` isn't compatible with `want`
+    fn expect(&mut self, want: &Ty, got: &Ty, what: &str) {
+        if !want.compatible(got) {
+            self.error(format!("{} should be {}, but is {}", what, want, got));
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Lookups
+
+    fn decl(&self, name: &str) -> Option<Decl<'a>> {
+        self.decls.iter().rev()
+            .find_map(|d| d.get(name))
+            .copied()
+            .or_else(|| self.table.lookup(self.schema, name))
+    }
+
+    /// Finds the type of a bare name in an expression
+    fn name(&self, name: &str) -> Ty<'a> {
+        if let Some(t) = self.scopes.iter().rev().find_map(|s| s.get(name)) {
+            return t.clone();
+        }
+        match self.decl(name) {
+            Some(Decl::Constant(c)) => self.instantiable_type(
+                &c.instantiable_type, 0),
+            // Otherwise, this is probably an enumeration item
+            _ => Ty::Unknown,
+        }
+    }
+
+    fn named(&self, name: &str, depth: usize) -> Ty<'a> {
+        if depth > MAX_DEPTH {
+            return Ty::Unknown;
+        }
+        match self.decl(name) {
+            Some(d @ Decl::Entity(_)) => Ty::Entity(d.name()),
+            Some(Decl::Type(t)) => match &t.underlying_type {
+                UnderlyingType::Concrete(c) => self.concrete_types(c, depth + 1),
+                UnderlyingType::Constructed(ConstructedTypes::Enumeration(_)) =>
+                    Ty::Enumeration(t.type_id.0),
+                UnderlyingType::Constructed(ConstructedTypes::Select(_)) =>
+                    Ty::Unknown,
+            },
+            _ => Ty::Unknown,
+        }
+    }
+
+    fn simple_types(&self, s: &SimpleTypes) -> Ty<'a> {
+        match s {
+            SimpleTypes::Binary(_) => Ty::Binary,
+            SimpleTypes::Boolean | SimpleTypes::Logical => Ty::Logical,
+            SimpleTypes::Integer | SimpleTypes::Number | SimpleTypes::Real(_) =>
+                Ty::Number,
+            SimpleTypes::String(_) => Ty::String,
+        }
+    }
+
+    fn concrete_types(&self, c: &'a ConcreteTypes<'a>, depth: usize) -> Ty<'a> {
+        match c {
+            ConcreteTypes::Aggregation(a) => {
+                let t = match a {
+                    AggregationTypes::Array(a) => &a.instantiable_type,
+                    AggregationTypes::Bag(b) => &b.1,
+                    AggregationTypes::List(l) => &l.instantiable_type,
+                    AggregationTypes::Set(s) => &s.instantiable_type,
+                };
+                Ty::Aggregate(Box::new(self.instantiable_type(t, depth + 1)))
+            },
+            ConcreteTypes::Simple(s) => self.simple_types(s),
+            ConcreteTypes::TypeRef(t) => self.named(t.0, depth + 1),
+        }
+    }
+
+    fn instantiable_type(&self, i: &'a InstantiableType<'a>, depth: usize)
+        -> Ty<'a>
+    {
+        match i {
+            InstantiableType::Concrete(c) => self.concrete_types(c, depth),
+            InstantiableType::EntityRef(e) => self.named(e.0, depth),
+        }
+    }
+
+    fn parameter_type(&self, p: &'a ParameterType<'a>) -> Ty<'a> {
+        match p {
+            ParameterType::Generalized(g) => match g {
+                GeneralizedTypes::Aggregate(a) =>
+                    Ty::Aggregate(Box::new(self.parameter_type(&a.1))),
+                GeneralizedTypes::GeneralAggregation(a) => {
+                    let t = match a {
+                        GeneralAggregationTypes::Array(a) => &a.parameter_type,
+                        GeneralAggregationTypes::Bag(b) => &b.1,
+                        GeneralAggregationTypes::List(l) => &l.parameter_type,
+                        GeneralAggregationTypes::Set(s) => &s.parameter_type,
+                    };
+                    Ty::Aggregate(Box::new(self.parameter_type(t)))
+                },
+                GeneralizedTypes::GenericEntity(_) |
+                GeneralizedTypes::Generic(_) => Ty::Unknown,
+            },
+            ParameterType::Named(n) => match n {
+                NamedTypes::Entity(e) => self.named(e.0, 0),
+                NamedTypes::Type(t) => self.named(t.0, 0),
+                NamedTypes::_Ambiguous(s) => self.named(s.0, 0),
+            },
+            ParameterType::Simple(s) => self.simple_types(s),
+        }
+    }
+
+    /// Collects the attributes of an entity (and its supertypes)
+    fn collect_attrs(&self, entity: &str, out: &mut HashMap<&'a str, Ty<'a>>,
+                     depth: usize)
+    {
+        let e = match self.decl(entity) {
+            Some(Decl::Entity(e)) if depth <= MAX_DEPTH => e,
+            _ => return,
+        };
+        for s in ((e.0).1).1.iter().flat_map(|s| s.0.iter()) {
+            self.collect_attrs(s.0, out, depth + 1);
+        }
+        let b = &e.1;
+        for a in &b.explicit_attr {
+            let t = self.parameter_type(&a.parameter_type);
+            for d in &a.attributes {
+                out.insert(d.name(), t.clone());
+            }
+        }
+        for d in b.derive.iter().flat_map(|d| d.0.iter()) {
+            out.insert(d.0.name(), self.parameter_type(&d.1));
+        }
+        for i in b.inverse.iter().flat_map(|i| i.0.iter()) {
+            let t = Ty::Entity(i.entity.0);
+            out.insert(i.attribute_decl.name(), match i.bounds {
+                Some(_) => Ty::Aggregate(Box::new(t)),
+                None => t,
+            });
+        }
+    }
+
+    fn attributes(&mut self, entity: &'a str) -> &HashMap<&'a str, Ty<'a>> {
+        if !self.attrs.contains_key(entity) {
+            let mut out = HashMap::new();
+            self.collect_attrs(entity, &mut out, 0);
+            self.attrs.insert(entity, out);
+        }
+        &self.attrs[entity]
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Declarations
+
+    fn schema_body(&mut self, b: &'a SchemaBody<'a>) {
+        self.constants(b.constants.as_ref());
+        for d in &b.declarations {
+            match d {
+                DeclarationOrRuleDecl::Declaration(d) => self.declaration(d),
+                DeclarationOrRuleDecl::RuleDecl(r) => self.rule(r),
+            }
+        }
+    }
+
+    fn constants(&mut self, c: Option<&'a ConstantDecl<'a>>) {
+        for c in c.iter().flat_map(|c| c.0.iter()) {
+            let want = self.instantiable_type(&c.instantiable_type, 0);
+            let got = self.expression(&c.expression);
+            self.span = c.expression.2;
+            self.expect(&want, &got, &format!("Constant {}", c.constant_id.0));
+        }
+    }
+
+    fn where_clause(&mut self, w: Option<&'a WhereClause<'a>>) {
+        for r in w.iter().flat_map(|w| w.0.iter()) {
+            let t = self.expression(&r.expression);
+            self.span = r.expression.2;
+            let what = match &r.rule_label_id {
+                Some(label) => format!("Rule {}", label.0),
+                None => "Rule".to_owned(),
+            };
+            self.expect(&Ty::Logical, &t, &what);
+        }
+    }
+
+    fn declaration(&mut self, d: &'a Declaration<'a>) {
+        match d {
+            Declaration::Entity(e) => {
+                let name = (e.0).0.0;
+                let mut scope = self.attributes(name).clone();
+                scope.insert("self", Ty::Entity(name));
+                self.scopes.push(scope);
+
+                for d in (e.1).derive.iter().flat_map(|d| d.0.iter()) {
+                    let want = self.parameter_type(&d.1);
+                    let got = self.expression(&d.2);
+                    self.span = d.2.2;
+                    self.expect(&want, &got,
+                                &format!("Derived attribute {}", d.0.name()));
+                }
+                self.where_clause((e.1).where_.as_ref());
+                self.scopes.pop();
+            },
+            Declaration::Type(t) => {
+                let mut scope = HashMap::new();
+                scope.insert("self", self.named(t.type_id.0, 0));
+                self.scopes.push(scope);
+                self.where_clause(t.where_clause.as_ref());
+                self.scopes.pop();
+            },
+            Declaration::Function(f) => {
+                let h = &f.function_head;
+                let params = h.params.iter().flatten()
+                    .flat_map(|p| p.0.iter().map(move |id| (id.0, &p.1)));
+                let returns = self.parameter_type(&h.out);
+                self.algorithm(params, &f.algorithm_head, &f.stmts,
+                               Some(returns));
+            },
+            Declaration::Procedure(p) => {
+                let params = (p.0).args.iter().flatten()
+                    .flat_map(|(_, p)| p.0.iter().map(move |id| (id.0, &p.1)));
+                self.algorithm(params, &p.1, &p.2, None);
+            },
+            Declaration::SubtypeConstraint(_) => (),
+        }
+    }
+
+    fn rule(&mut self, r: &'a RuleDecl<'a>) {
+        // Inside a rule, each entity name refers to all of its instances
+        let mut scope = HashMap::new();
+        for e in &r.rule_head.entities {
+            scope.insert(e.0, Ty::Aggregate(Box::new(Ty::Entity(e.0))));
+        }
+        self.scopes.push(scope);
+        self.algorithm(std::iter::empty(), &r.algorithm_head, &r.stmt, None);
+
+        // The WHERE clause can see the rule's local variables, so we check it
+        // in a fresh copy of that scope
+        let mut scope = HashMap::new();
+        for v in r.algorithm_head.local.iter().flat_map(|l| l.0.iter()) {
+            let t = self.parameter_type(&v.parameter_type);
+            for id in &v.variable_id {
+                scope.insert(id.0, t.clone());
+            }
+        }
+        self.scopes.push(scope);
+        self.where_clause(Some(&r.where_clause));
+        self.scopes.pop();
+        self.scopes.pop();
+    }
+
+    /// Checks the body of a function, procedure, or rule
+    fn algorithm<I>(&mut self, params: I, h: &'a AlgorithmHead<'a>,
+                    stmts: &'a [Stmt<'a>], returns: Option<Ty<'a>>)
+        where I: Iterator<Item=(&'a str, &'a ParameterType<'a>)>
+    {
+        // Local declarations come first, since parameters may use them
+        let mut decls = HashMap::new();
+        for c in h.constant.iter().flat_map(|c| c.0.iter()) {
+            decls.insert(c.constant_id.0, Decl::Constant(c));
+        }
+        for d in &h.declaration {
+            let d = match d {
+                Declaration::Entity(e) => Decl::Entity(e),
+                Declaration::Function(f) => Decl::Function(f),
+                Declaration::Procedure(p) => Decl::Procedure(p),
+                Declaration::SubtypeConstraint(c) => Decl::SubtypeConstraint(c),
+                Declaration::Type(t) => Decl::Type(t),
+            };
+            decls.insert(d.name(), d);
+        }
+        self.decls.push(decls);
+
+        let mut scope = HashMap::new();
+        for (name, p) in params {
+            scope.insert(name, self.parameter_type(p));
+        }
+        self.scopes.push(scope);
+
+        let prev = std::mem::replace(&mut self.returns, returns);
+        for d in &h.declaration {
+            self.declaration(d);
+        }
+        self.constants(h.constant.as_ref());
+        for v in h.local.iter().flat_map(|l| l.0.iter()) {
+            let t = self.parameter_type(&v.parameter_type);
+            if let Some(e) = &v.expression {
+                let got = self.expression(e);
+                self.span = e.2;
+                self.expect(&t, &got,
+                            &format!("Initial value of {}", v.variable_id[0].0));
+            }
+            for id in &v.variable_id {
+                self.scopes.last_mut().unwrap().insert(id.0, t.clone());
+            }
+        }
+        for s in stmts {
+            self.stmt(s);
+        }
+        self.returns = prev;
+
+        self.scopes.pop();
+        self.decls.pop();
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Statements
+
+    fn general_ref(&self, g: &GeneralRef) -> Ty<'a> {
+        match g {
+            GeneralRef::Parameter(p) => self.name(p.0),
+            GeneralRef::Variable(v) => self.name(v.0),
+            GeneralRef::_SimpleId(s) => self.name(s.0),
+        }
+    }
+
+    fn condition(&mut self, e: &'a Expression<'a>, what: &str) {
+        let t = self.expression(e);
+        self.span = e.2;
+        self.expect(&Ty::Logical, &t, what);
+    }
+
+    fn stmt(&mut self, s: &'a Stmt<'a>) {
+        match s {
+            Stmt::Alias(a) => {
+                let t = self.general_ref(&a.general);
+                let t = self.qualifiers(t, &a.qualifiers);
+                let mut scope = HashMap::new();
+                scope.insert(a.variable.0, t);
+                self.scopes.push(scope);
+                for s in &a.stmts {
+                    self.stmt(s);
+                }
+                self.scopes.pop();
+            },
+            Stmt::Assignment(a) => {
+                let t = self.general_ref(&a.general_ref);
+                let want = self.qualifiers(t, &a.qualifiers);
+                let got = self.expression(&a.expression);
+                self.span = a.expression.2;
+                self.expect(&want, &got, "Assigned value");
+            },
+            Stmt::Case(c) => {
+                let t = self.expression(&c.selector.0);
+                for a in &c.actions {
+                    for label in &a.0 {
+                        let got = self.expression(&label.0);
+                        self.span = label.0.2;
+                        self.expect(&t, &got, "Case label");
+                    }
+                    self.stmt(&a.1);
+                }
+                if let Some(s) = &c.otherwise {
+                    self.stmt(s);
+                }
+            },
+            Stmt::Compound(c) => for s in &c.0 {
+                self.stmt(s);
+            },
+            Stmt::If(i) => {
+                self.condition(&i.0.0, "IF condition");
+                for s in i.1.iter().chain(i.2.iter().flatten()) {
+                    self.stmt(s);
+                }
+            },
+            Stmt::ProcedureCall(p) => {
+                let args: Vec<&Expression> = p.params.iter()
+                    .flat_map(|p| p.0.iter().map(|a| &a.0))
+                    .collect();
+                match &p.proc {
+                    BuiltInOrProcedureRef::ProcedureRef(r) => {
+                        let params = match self.decl(r.0) {
+                            Some(Decl::Procedure(d)) =>
+                                (d.0).args.iter().flatten()
+                                    .flat_map(|(_, p)| p.0.iter()
+                                        .map(move |_| &p.1))
+                                    .collect(),
+                            _ => vec![],
+                        };
+                        self.arguments(r.0, &params, &args);
+                    },
+                    BuiltInOrProcedureRef::BuiltIn(_) => for a in args {
+                        self.expression(a);
+                    },
+                }
+            },
+            Stmt::Repeat(r) => {
+                let RepeatControl(inc, w, u) = &r.0;
+                let mut scope = HashMap::new();
+                if let Some(i) = inc {
+                    for b in [&i.bound1.0, &i.bound2.0].iter()
+                        .copied()
+                        .chain(i.increment.iter().map(|i| &i.0))
+                    {
+                        let t = self.simple_expression(&b.0);
+                        self.expect(&Ty::Number, &t, "Loop bound");
+                    }
+                    scope.insert(i.var.0, Ty::Number);
+                }
+                self.scopes.push(scope);
+                if let Some(w) = w {
+                    self.condition(&(w.0).0, "WHILE condition");
+                }
+                for s in &r.1 {
+                    self.stmt(s);
+                }
+                if let Some(u) = u {
+                    self.condition(&(u.0).0, "UNTIL condition");
+                }
+                self.scopes.pop();
+            },
+            Stmt::Return(r) => if let Some(e) = &r.0 {
+                let got = self.expression(e);
+                self.span = e.2;
+                if let Some(want) = self.returns.clone() {
+                    self.expect(&want, &got, "Return value");
+                }
+            },
+            Stmt::Escape | Stmt::Null | Stmt::Skip => (),
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Expressions
+
+    fn expression(&mut self, e: &'a Expression<'a>) -> Ty<'a> {
+        let prev = std::mem::replace(&mut self.span, e.2);
+        let a = self.simple_expression(&e.0);
+        let out = match &e.1 {
+            None => a,
+            Some((op, b)) => {
+                let b = self.simple_expression(b);
+                match op {
+                    RelOpExtended::RelOp(_) => if !a.compatible(&b) {
+                        self.error(format!("Cannot compare {} with {}", a, b));
+                    },
+                    RelOpExtended::In => match b.element() {
+                        Some(t) => if !t.compatible(&a) {
+                            self.error(format!("{} can't be a member of {}",
+                                               a, b));
+                        },
+                        None => self.error(format!(
+                            "Right side of IN should be an aggregate, but is {}",
+                            b)),
+                    },
+                    RelOpExtended::Like => {
+                        self.expect(&Ty::String, &a, "Left side of LIKE");
+                        self.expect(&Ty::String, &b, "Right side of LIKE");
+                    },
+                }
+                Ty::Logical
+            },
+        };
+        self.span = prev;
+        out
+    }
+
+    fn simple_expression(&mut self, e: &'a SimpleExpression<'a>) -> Ty<'a> {
+        let mut a = self.term(&e.0);
+        for (op, t) in &e.1 {
+            let b = self.term(t);
+            a = match op {
+                AddLikeOp::Add | AddLikeOp::Sub => {
+                    let add = matches!(op, AddLikeOp::Add);
+                    match (&a, &b) {
+                        (Ty::Number, Ty::Number) => Ty::Number,
+                        (Ty::String, Ty::String) if add => Ty::String,
+                        // Union or difference, with an aggregate or an element
+                        (Ty::Aggregate(_), _) if a.compatible(&b) ||
+                            a.element().unwrap().compatible(&b) => a,
+                        (_, Ty::Aggregate(_)) if add &&
+                            b.element().unwrap().compatible(&a) => b,
+                        (Ty::Unknown, _) | (_, Ty::Unknown) => Ty::Unknown,
+                        _ => {
+                            self.error(format!("Cannot {} {} and {}",
+                                if add { "add" } else { "subtract" }, a, b));
+                            Ty::Unknown
+                        },
+                    }
+                },
+                AddLikeOp::Or | AddLikeOp::Xor => {
+                    self.expect(&Ty::Logical, &a, "Left side of OR");
+                    self.expect(&Ty::Logical, &b, "Right side of OR");
+                    Ty::Logical
+                },
+            };
+        }
+        a
+    }
+
+    fn term(&mut self, t: &'a Term<'a>) -> Ty<'a> {
+        let mut a = self.factor(&t.0);
+        for (op, f) in &t.1 {
+            let b = self.factor(f);
+            use MultiplicationLikeOp::*;
+            a = match op {
+                Mul => match (&a, &b) {
+                    (Ty::Number, Ty::Number) => Ty::Number,
+                    // Intersection
+                    (Ty::Aggregate(_), Ty::Aggregate(_)) if a.compatible(&b) =>
+                        a,
+                    (Ty::Unknown, _) | (_, Ty::Unknown) => Ty::Unknown,
+                    _ => {
+                        self.error(format!("Cannot multiply {} and {}", a, b));
+                        Ty::Unknown
+                    },
+                },
+                Div | IntegerDiv | Mod => {
+                    self.expect(&Ty::Number, &a, "Dividend");
+                    self.expect(&Ty::Number, &b, "Divisor");
+                    Ty::Number
+                },
+                And => {
+                    self.expect(&Ty::Logical, &a, "Left side of AND");
+                    self.expect(&Ty::Logical, &b, "Right side of AND");
+                    Ty::Logical
+                },
+                // Builds a complex entity instance
+                ComplexEntity => Ty::Unknown,
+            };
+        }
+        a
+    }
+
+    fn factor(&mut self, f: &'a Factor<'a>) -> Ty<'a> {
+        let mut a = self.simple_factor(&f.0);
+        for b in &f.1 {
+            let b = self.simple_factor(b);
+            self.expect(&Ty::Number, &a, "Base of **");
+            self.expect(&Ty::Number, &b, "Exponent of **");
+            a = Ty::Number;
+        }
+        a
+    }
+
+    fn simple_factor(&mut self, f: &'a SimpleFactor<'a>) -> Ty<'a> {
+        match f {
+            SimpleFactor::_AmbiguousFunctionCall(id, args) => {
+                let args: Vec<_> = args.iter().collect();
+                self.call(id.0, &args)
+            },
+            SimpleFactor::AggregateInitializer(a) => {
+                let mut t = Ty::Unknown;
+                for e in &a.0 {
+                    let u = self.expression(&e.0);
+                    if let Some(r) = &e.1 {
+                        let n = self.simple_expression(&r.0.0);
+                        self.expect(&Ty::Number, &n, "Repetition count");
+                    }
+                    if t == Ty::Unknown {
+                        t = u;
+                    }
+                }
+                Ty::Aggregate(Box::new(t))
+            },
+            SimpleFactor::EntityConstructor(c) => {
+                for a in &c.args {
+                    self.expression(a);
+                }
+                Ty::Entity(c.entity_ref.0)
+            },
+            SimpleFactor::EnumerationReference(r) => match &r.0 {
+                Some(t) => Ty::Enumeration(t.0),
+                None => self.name((r.1).0),
+            },
+            SimpleFactor::Interval(i) => {
+                let ts = [self.simple_expression(&i.low.0),
+                          self.simple_expression(&i.item.0),
+                          self.simple_expression(&i.high.0)];
+                if !ts[0].compatible(&ts[1]) || !ts[1].compatible(&ts[2]) ||
+                   !ts[0].compatible(&ts[2])
+                {
+                    self.error(format!("Interval mixes {}, {}, and {}",
+                                       ts[0], ts[1], ts[2]));
+                }
+                Ty::Logical
+            },
+            SimpleFactor::QueryExpression(q) => {
+                let src = self.simple_expression(&q.aggregate.0);
+                let elem = match src.element() {
+                    Some(t) => t,
+                    None => {
+                        self.error(format!(
+                            "QUERY source should be an aggregate, but is {}",
+                            src));
+                        Ty::Unknown
+                    },
+                };
+                let mut scope = HashMap::new();
+                scope.insert(q.var.0, elem.clone());
+                self.scopes.push(scope);
+                self.condition(&q.logical_expression.0, "QUERY condition");
+                self.scopes.pop();
+                Ty::Aggregate(Box::new(elem))
+            },
+            SimpleFactor::Unary(op, e) => {
+                let t = match e {
+                    ExpressionOrPrimary::Expression(e) => self.expression(e),
+                    ExpressionOrPrimary::Primary(p) => self.primary(p),
+                };
+                match op {
+                    Some(UnaryOp::Not) => {
+                        self.expect(&Ty::Logical, &t, "Operand of NOT");
+                        Ty::Logical
+                    },
+                    Some(UnaryOp::Add) | Some(UnaryOp::Sub) => {
+                        self.expect(&Ty::Number, &t, "Operand of unary sign");
+                        Ty::Number
+                    },
+                    None => t,
+                }
+            },
+        }
+    }
+
+    fn primary(&mut self, p: &'a Primary<'a>) -> Ty<'a> {
+        match p {
+            Primary::Literal(Literal::String(_)) => Ty::String,
+            Primary::Literal(Literal::Binary(_)) => Ty::Binary,
+            Primary::Literal(Literal::Logical(_)) => Ty::Logical,
+            Primary::Literal(Literal::Real(_)) => Ty::Number,
+            Primary::Qualifiable(f, qs) => {
+                let t = self.qualifiable_factor(f);
+                self.qualifiers(t, qs)
+            },
+        }
+    }
+
+    fn qualifiable_factor(&mut self, f: &'a QualifiableFactor<'a>) -> Ty<'a> {
+        match f {
+            QualifiableFactor::FunctionCall(c) => {
+                let args: Vec<_> = (c.1).0.iter().map(|a| &a.0).collect();
+                match &c.0 {
+                    BuiltInOrFunctionRef::BuiltIn(b) => self.built_in(b, &args),
+                    BuiltInOrFunctionRef::Ref(r) => self.call(r.0, &args),
+                }
+            },
+            QualifiableFactor::AttributeRef(a) => self.name(a.0),
+            QualifiableFactor::ConstantFactor(c) => match c {
+                ConstantFactor::BuiltIn(BuiltInConstant::ConstE) |
+                ConstantFactor::BuiltIn(BuiltInConstant::Pi) => Ty::Number,
+                ConstantFactor::BuiltIn(BuiltInConstant::Self_) =>
+                    self.name("self"),
+                ConstantFactor::BuiltIn(BuiltInConstant::Indeterminant) =>
+                    Ty::Unknown,
+                ConstantFactor::ConstantRef(r) => self.name(r.0),
+            },
+            QualifiableFactor::GeneralRef(g) => self.general_ref(g),
+            QualifiableFactor::Population(p) =>
+                Ty::Aggregate(Box::new(Ty::Entity((p.0).0))),
+            QualifiableFactor::_Ambiguous(s) => self.name(s),
+        }
+    }
+
+    fn qualifiers(&mut self, mut t: Ty<'a>, qs: &'a [Qualifier<'a>]) -> Ty<'a> {
+        for q in qs {
+            t = match q {
+                Qualifier::Attribute(a) => match t {
+                    Ty::Entity(e) => self.attributes(e).get((a.0).0)
+                        .cloned()
+                        .unwrap_or(Ty::Unknown),
+                    Ty::Unknown => Ty::Unknown,
+                    t => {
+                        self.error(format!("Cannot access attribute {} of {}",
+                                           (a.0).0, t));
+                        Ty::Unknown
+                    },
+                },
+                Qualifier::Group(g) => Ty::Entity((g.0).0),
+                Qualifier::Index(i) => {
+                    let idx = std::iter::once(&(i.0).0)
+                        .chain(i.1.iter().map(|j| &j.0));
+                    for e in idx {
+                        let n = self.simple_expression(&(e.0).0);
+                        self.expect(&Ty::Number, &n, "Index");
+                    }
+                    match t {
+                        Ty::Aggregate(e) => *e,
+                        Ty::String | Ty::Binary | Ty::Unknown => t,
+                        t => {
+                            self.error(format!("Cannot index into {}", t));
+                            Ty::Unknown
+                        },
+                    }
+                },
+            };
+        }
+        t
+    }
+
+    /// Checks arguments against a list of parameter types.  Extra or missing
+    /// arguments are ignored here.
+    fn arguments(&mut self, name: &str, params: &[&'a ParameterType<'a>],
+                 args: &[&'a Expression<'a>])
+    {
+        for (i, a) in args.iter().enumerate() {
+            let got = self.expression(a);
+            if let Some(p) = params.get(i) {
+                let want = self.parameter_type(p);
+                self.span = a.2;
+                self.expect(&want, &got,
+                            &format!("Argument {} of {}", i + 1, name));
+            }
+        }
+    }
+
+    fn call(&mut self, name: &'a str, args: &[&'a Expression<'a>]) -> Ty<'a> {
+        let span = self.span;
+        let out = match self.decl(name) {
+            Some(Decl::Function(f)) => {
+                let params: Vec<_> = f.function_head.params.iter().flatten()
+                    .flat_map(|p| p.0.iter().map(move |_| &p.1))
+                    .collect();
+                self.arguments(name, &params, args);
+                self.parameter_type(&f.function_head.out)
+            },
+            Some(d @ Decl::Entity(_)) => {
+                for a in args {
+                    self.expression(a);
+                }
+                Ty::Entity(d.name())
+            },
+            _ => {
+                for a in args {
+                    self.expression(a);
+                }
+                Ty::Unknown
+            },
+        };
+        self.span = span;
+        out
+    }
+
+    fn built_in(&mut self, b: &BuiltInFunction, args: &[&'a Expression<'a>])
+        -> Ty<'a>
+    {
+        use BuiltInFunction::*;
+        let ts: Vec<Ty> = args.iter().map(|a| self.expression(a)).collect();
+        let first = ts.first().cloned().unwrap_or(Ty::Unknown);
+        let name = format!("{:?}", b).to_uppercase();
+        let what = format!("Argument of {}", name);
+        match b {
+            Abs | Acos | Asin | Atan | Cos | Exp | Log | Log2 | Log10 | Odd |
+            Sin | Sqrt | Tan => for t in &ts {
+                self.expect(&Ty::Number, t, &what);
+            },
+            Hibound | HiIndex | LoBound | LoIndex | SizeOf | ValueIn |
+            ValueUnique if first.element().is_none() =>
+                self.error(format!("{} should be an aggregate, but is {}",
+                                   what, first)),
+            Blength => self.expect(&Ty::Binary, &first, &what),
+            Length | Value => self.expect(&Ty::String, &first, &what),
+            _ => (),
+        }
+        match b {
+            Abs | Acos | Asin | Atan | Blength | Cos | Exp | Hibound |
+            HiIndex | Length | LoBound | LoIndex | Log | Log2 | Log10 | SizeOf |
+            Sin | Sqrt | Tan | Value => Ty::Number,
+            Exists | Odd | ValueIn | ValueUnique => Ty::Logical,
+            Format => Ty::String,
+            Nvl => first,
+            RolesOf | Typeof => Ty::Aggregate(Box::new(Ty::String)),
+            Usedin => Ty::Aggregate(Box::new(Ty::Unknown)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parse::{parse, strip_comments_and_lower},
+        resolve::resolve,
+    };
+
+    fn errors(s: &str) -> Vec<(usize, String)> {
+        let syntax = parse(s).unwrap();
+        let (table, _) = resolve(&syntax);
+        check(&syntax, &table).iter()
+            .map(|e| (e.location(s).unwrap().0, e.message.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let s = strip_comments_and_lower(br#"SCHEMA geom;
+CONSTANT origin : point := point(0.0, 0.0); END_CONSTANT;
+TYPE length_measure = REAL; END_TYPE;
+TYPE positive_length = length_measure;
+WHERE
+  wr1 : self > 0;
+END_TYPE;
+TYPE side = ENUMERATION OF (left, right); END_TYPE;
+ENTITY point;
+  x, y : length_measure;
+DERIVE
+  norm : REAL := SQRT(x**2 + y**2);
+END_ENTITY;
+ENTITY polyline;
+  points : LIST [2:?] OF point;
+  s : side;
+WHERE
+  wr1 : SIZEOF(QUERY(p <* points | p.x < 0.0)) = 0;
+  wr2 : (s = left) OR (s = right);
+  wr3 : NOT (points[1] :=: points[HIINDEX(points)]);
+  wr4 : 'GEOM.POINT' IN TYPEOF(points[1]);
+END_ENTITY;
+FUNCTION total(p : polyline) : REAL;
+  LOCAL
+    t : REAL := 0.0;
+  END_LOCAL;
+  REPEAT i := 1 TO SIZEOF(p.points);
+    IF p.points[i].norm > 1.0 THEN
+      t := t + p.points[i].norm;
+    END_IF;
+  END_REPEAT;
+  RETURN (t);
+END_FUNCTION;
+RULE few_points FOR (point);
+WHERE
+  wr1 : SIZEOF(point) < 100;
+END_RULE;
+END_SCHEMA;
+"#);
+        assert_eq!(errors(&s), []);
+    }
+
+    #[test]
+    fn test_errors() {
+        let s = strip_comments_and_lower(br#"SCHEMA geom;
+ENTITY point;
+  x, y : REAL;
+  visible : BOOLEAN;
+WHERE
+  wr1 : x + visible > 0;
+  wr2 : x;
+  wr3 : SIZEOF(x) = 1;
+  wr4 : x.y > 0;
+END_ENTITY;
+FUNCTION f(p : point) : INTEGER;
+  IF p.x THEN
+    RETURN ('oops');
+  END_IF;
+  RETURN (g(p.visible));
+END_FUNCTION;
+FUNCTION g(n : NUMBER) : INTEGER;
+  RETURN (n);
+END_FUNCTION;
+END_SCHEMA;
+"#);
+        assert_eq!(errors(&s), [
+            (6, "Cannot add a number and a logical".to_owned()),
+            (7, "Rule wr2 should be a logical, but is a number".to_owned()),
+            (8, "Argument of SIZEOF should be an aggregate, but is a number"
+                .to_owned()),
+            (9, "Cannot access attribute y of a number".to_owned()),
+            (12, "IF condition should be a logical, but is a number"
+                .to_owned()),
+            (13, "Return value should be a number, but is a string".to_owned()),
+            (15, "Argument 1 of g should be a number, but is a logical"
+                .to_owned()),
+        ]);
+    }
+}
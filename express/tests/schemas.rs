@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use express::{
+    error::render_error,
+    parse::{parse, parse_recovering, strip_comments_and_lower, Declaration,
+            DeclarationOrRuleDecl, Syntax},
+    resolve::resolve,
+    typecheck::check,
+};
+
+/// Number of each kind of top-level declaration in a file
+#[derive(Debug, Default, Eq, PartialEq)]
+struct Counts {
+    entities: usize,
+    types: usize,
+    functions: usize,
+    procedures: usize,
+    rules: usize,
+    subtype_constraints: usize,
+}
+
+impl Counts {
+    fn new(syntax: &Syntax) -> Self {
+        let mut out = Self::default();
+        for d in syntax.0.iter().flat_map(|s| s.body.declarations.iter()) {
+            match d {
+                DeclarationOrRuleDecl::Declaration(d) => match d {
+                    Declaration::Entity(_) => out.entities += 1,
+                    Declaration::Type(_) => out.types += 1,
+                    Declaration::Function(_) => out.functions += 1,
+                    Declaration::Procedure(_) => out.procedures += 1,
+                    Declaration::SubtypeConstraint(_) =>
+                        out.subtype_constraints += 1,
+                },
+                DeclarationOrRuleDecl::RuleDecl(_) => out.rules += 1,
+            }
+        }
+        out
+    }
+}
+
+/// Reads and parses a schema, panicking with a rendered error on failure
+fn load(path: &Path) -> String {
+    let data = std::fs::read(path)
+        .unwrap_or_else(|e| panic!("Could not read {:?}: {}", path, e));
+    let s = strip_comments_and_lower(&data);
+    if let Err(e) = parse(&s) {
+        panic!("Failed to parse {:?}:\n{}", path, render_error(&s, &e));
+    }
+    s
+}
+
+#[test]
+fn grammar_fixture() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/grammar.exp");
+    let s = load(&path);
+    let syntax = parse(&s).unwrap();
+
+    assert_eq!(syntax.0.len(), 1);
+    let schema = &syntax.0[0];
+    assert_eq!(schema.id.0, "grammar_test");
+    assert_eq!(schema.body.interfaces.len(), 3);
+    assert_eq!(schema.body.constants.as_ref().map(|c| c.0.len()), Some(6));
+    assert_eq!(Counts::new(&syntax), Counts {
+        entities: 6,
+        types: 18,
+        functions: 4,
+        procedures: 1,
+        rules: 1,
+        subtype_constraints: 1,
+    });
+
+    // Recovering parsing gives the same result on valid input
+    let (recovered, errors) = parse_recovering(&s);
+    assert!(errors.is_empty(), "Errors: {:?}", errors);
+    assert_eq!(Counts::new(&recovered), Counts::new(&syntax));
+    assert_eq!(recovered.0[0].body.interfaces.len(), 3);
+
+    let (table, unresolved) = resolve(&syntax);
+    assert!(unresolved.is_empty(), "Unresolved: {:?}", unresolved);
+    let errors = check(&syntax, &table);
+    assert!(errors.is_empty(), "Type errors: {:?}", errors);
+}
+
+/// Directory of long-form schemas.  The standard application protocols
+/// aren't redistributable, so they're not checked in; see
+/// `tests/schemas/README.md` for where to find them.
+fn schema_dir() -> PathBuf {
+    match std::env::var_os("EXPRESS_SCHEMAS") {
+        Some(d) => d.into(),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/schemas"),
+    }
+}
+
+/// Expected declaration counts for known long-form schemas.  The AP214
+/// counts match the generated `step/src/ap214.rs`.
+fn expected(name: &str) -> Option<Counts> {
+    match name {
+        "10303-214e3-aim-long.exp" => Some(Counts {
+            entities: 915,
+            types: 192,
+            ..Counts::default()
+        }),
+        _ => None,
+    }
+}
+
+/// The schemas aren't checked in, so this only runs when asked to, e.g. with
+/// `EXPRESS_SCHEMAS=path/to/schemas cargo test -- --ignored`
+#[test]
+#[ignore = "needs EXPRESS_SCHEMAS"]
+fn long_form_schemas() {
+    let dir = schema_dir();
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(&dir) {
+        Ok(d) => d.filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|e| e == "exp").unwrap_or(false))
+            .collect(),
+        Err(_) => vec![],
+    };
+    assert!(!paths.is_empty(), "No long-form schemas in {:?}", dir);
+    paths.sort();
+
+    for path in &paths {
+        let s = load(path);
+        let syntax = parse(&s).unwrap();
+        let counts = Counts::new(&syntax);
+        eprintln!("{:?}: {:?}", path.file_name().unwrap(), counts);
+        assert!(counts.entities > 0, "No entities in {:?}", path);
+
+        // Long-form schemas are self-contained, so every name must resolve
+        let (_, unresolved) = resolve(&syntax);
+        assert!(unresolved.is_empty(), "Unresolved names in {:?}: {:?}",
+                path, unresolved.iter().map(|u| u.name).collect::<Vec<_>>());
+
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if let Some(e) = expected(name) {
+            assert_eq!(counts.entities, e.entities, "Entities in {}", name);
+            assert_eq!(counts.types, e.types, "Types in {}", name);
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use nalgebra_glm as glm;
-use glm::Vec2;
+use glm::{Vec2, DVec3};
 use winit::{
     dpi::{PhysicalSize},
     event::{ElementState, ModifiersState, WindowEvent, DeviceEvent, VirtualKeyCode, MouseScrollDelta},
 };
 
-use triangulate::mesh::Mesh;
-use crate::{backdrop::Backdrop, camera::Camera, model::Model};
+use triangulate::{diagnostic::Diagnostic, mesh::Mesh};
+use crate::{backdrop::Backdrop, camera::Camera, dialog, model::Model};
+
+/// Result of loading a file in the background
+pub type Loaded = (Mesh, Vec<Diagnostic>);
 
 pub struct App {
     start_time: std::time::SystemTime,
@@ -16,11 +19,21 @@ pub struct App {
     swapchain_format: wgpu::TextureFormat,
     swapchain: wgpu::SwapChain,
 
-    loader: Option<std::thread::JoinHandle<Mesh>>,
+    loader: Option<std::thread::JoinHandle<Loaded>>,
     model: Option<Model>,
     backdrop: Backdrop,
     camera: Camera,
 
+    /// Window title when no diagnostic is selected
+    title: String,
+    /// Problems found while loading the model.  These can be listed in a
+    /// panel (with D) to pick one, or stepped through with Tab and Shift+Tab,
+    /// and the camera zooms to whichever one is selected.
+    diagnostics: Vec<Diagnostic>,
+    selected: Option<usize>,
+    /// Bounding box of the whole model, for returning to the default view
+    bounds: Option<(DVec3, DVec3)>,
+
     depth: (wgpu::Texture, wgpu::TextureView),
     size: PhysicalSize<u32>,
 
@@ -38,7 +51,8 @@ pub enum Reply {
 impl App {
     pub fn new(start_time: std::time::SystemTime, size: PhysicalSize<u32>,
                adapter: wgpu::Adapter, surface: wgpu::Surface,
-               device: wgpu::Device, loader: std::thread::JoinHandle<Mesh>)
+               device: wgpu::Device, loader: std::thread::JoinHandle<Loaded>,
+               title: String)
         -> Self
     {
         let swapchain_format = adapter.get_swap_chain_preferred_format(&surface)
@@ -59,6 +73,10 @@ impl App {
             loader: Some(loader),
             model: None,
             camera: Camera::new(size.width as f32, size.height as f32),
+            title,
+            diagnostics: Vec::new(),
+            selected: None,
+            bounds: None,
             surface,
             device,
             size,
@@ -95,8 +113,32 @@ impl App {
             WindowEvent::KeyboardInput { input, .. } => {
                 if self.modifiers.logo() && input.virtual_keycode == Some(VirtualKeyCode::Q) {
                     Reply::Quit
-                } else {
+                } else if input.state != ElementState::Pressed {
                     Reply::Continue
+                } else {
+                    match input.virtual_keycode {
+                        Some(VirtualKeyCode::Tab) =>
+                            self.step_diagnostic(!self.modifiers.shift()),
+                        Some(VirtualKeyCode::D) => self.show_diagnostics(),
+                        Some(VirtualKeyCode::Escape) => self.select(None),
+                        Some(VirtualKeyCode::F) => self.zoom_to_fit(),
+                        Some(VirtualKeyCode::Z) => self.zoom_to_selection(),
+                        Some(VirtualKeyCode::X) => match &mut self.model {
+                            Some(m) => {
+                                m.toggle_xray();
+                                Reply::Redraw
+                            },
+                            None => Reply::Continue,
+                        },
+                        Some(VirtualKeyCode::M) => match &mut self.model {
+                            Some(m) => {
+                                m.toggle_shading();
+                                Reply::Redraw
+                            },
+                            None => Reply::Continue,
+                        },
+                        _ => Reply::Continue,
+                    }
                 }
             },
             WindowEvent::MouseInput { button, state, .. } => {
@@ -121,6 +163,90 @@ impl App {
         }
     }
 
+    /// Returns the window title, which shows the selected diagnostic (if any)
+    pub fn title(&self) -> String {
+        match self.selected {
+            Some(i) => format!("[{}/{}] {}", i + 1, self.diagnostics.len(),
+                               self.diagnostics[i]),
+            None if !self.diagnostics.is_empty() =>
+                format!("{} ({} problems, press D to list or Tab to view)",
+                        self.title, self.diagnostics.len()),
+            None => self.title.clone(),
+        }
+    }
+
+    /// Selects the next (or previous) diagnostic, wrapping around
+    fn step_diagnostic(&mut self, forward: bool) -> Reply {
+        let n = self.diagnostics.len();
+        if n == 0 {
+            return Reply::Continue;
+        }
+        let i = match (self.selected, forward) {
+            (None, true) => 0,
+            (None, false) => n - 1,
+            (Some(i), true) => (i + 1) % n,
+            (Some(i), false) => (i + n - 1) % n,
+        };
+        self.select(Some(i))
+    }
+
+    /// Shows every diagnostic in a list, then selects (and zooms to) the one
+    /// that the user picks.  The list is a native dialog, so the viewer stops
+    /// redrawing until it's closed.
+    fn show_diagnostics(&mut self) -> Reply {
+        if self.diagnostics.is_empty() {
+            return Reply::Continue;
+        }
+        let rows: Vec<String> = self.diagnostics.iter()
+            .map(|d| match d.bounds {
+                Some(_) => d.to_string(),
+                None => format!("{} (location unknown)", d),
+            })
+            .collect();
+        let text = format!("{} problems were found while loading the model.  \
+                            Pick one to zoom to it.", rows.len());
+        match dialog::choose("Problems", &text, &rows) {
+            Some(i) => self.select(Some(i)),
+            None => Reply::Redraw,
+        }
+    }
+
+    /// Selects a diagnostic and zooms to its location, or returns to the
+    /// full view of the model if `i` is `None`
+    fn select(&mut self, i: Option<usize>) -> Reply {
+        self.selected = i;
+        self.zoom_to_selection()
+    }
+
+    /// Frames the whole model, without changing the selection
+    fn zoom_to_fit(&mut self) -> Reply {
+        if let Some((lo, hi)) = self.bounds {
+            self.camera.fit_bounds(lo, hi);
+        }
+        Reply::Redraw
+    }
+
+    /// Frames the selected diagnostic, or the whole model if nothing is
+    /// selected (or the diagnostic's location is unknown)
+    fn zoom_to_selection(&mut self) -> Reply {
+        let target = match self.selected {
+            Some(i) => self.diagnostics[i].bounds.map(|(lo, hi)| {
+                // Keep some of the surrounding model in view, and avoid
+                // zooming infinitely far into a single point
+                let min_size = self.bounds
+                    .map(|(a, b)| (b - a).max() * 0.05)
+                    .unwrap_or(1.0);
+                let pad = DVec3::repeat(min_size.max((hi - lo).max() * 0.5));
+                (lo - pad, hi + pad)
+            }),
+            None => self.bounds,
+        };
+        if let Some((lo, hi)) = target {
+            self.camera.fit_bounds(lo, hi);
+        }
+        Reply::Redraw
+    }
+
     fn resize(&mut self, size: PhysicalSize<u32>) {
         self.size = size;
         self.swapchain = Self::rebuild_swapchain_(
@@ -178,7 +304,7 @@ impl App {
             &wgpu::CommandEncoderDescriptor { label: None });
 
         self.backdrop.draw(&frame, &self.depth.1, &mut encoder);
-        if let Some(model) = &self.model {
+        if let Some(model) = &mut self.model {
             model.draw(&self.camera, &queue, &frame, &self.depth.1, &mut encoder);
         }
         let drew_model = self.model.is_some();
@@ -196,19 +322,27 @@ impl App {
         // the model until the _second_ frame.
         if !self.first_frame && self.model.is_none() {
             println!("Waiting for mesh");
-            let mesh = self.loader.take()
+            let (mesh, diagnostics) = self.loader.take()
                 .unwrap()
                 .join()
                 .expect("Failed to load mesh");
-            let model = Model::new(&self.device, self.swapchain_format,
-                                   &mesh.verts, &mesh.triangles);
+            let model = Model::new(&self.device, self.swapchain_format, &mesh);
             self.model = Some(model);
             self.camera.fit_verts(&mesh.verts);
+            self.bounds = mesh.verts.iter()
+                .map(|v| v.pos)
+                .fold(None, |b, p| Some(match b {
+                    None => (p, p),
+                    Some((lo, hi)) => (glm::min2(&lo, &p), glm::max2(&hi, &p)),
+                }));
+            self.diagnostics = diagnostics;
             self.first_frame = true;
         } else {
             self.first_frame = false;
         }
 
+        #[cfg(feature = "profiling")]
+        profiling::finish_frame!();
         !drew_model
     }
 }
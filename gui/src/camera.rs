@@ -1,6 +1,6 @@
 use itertools::Itertools;
 use nalgebra_glm as glm;
-use glm::{Vec2, Vec3, Vec4, Mat4};
+use glm::{Vec2, Vec3, Vec4, DVec3, Mat4};
 use winit::event::MouseButton;
 
 use triangulate::mesh::Vertex;
@@ -10,7 +10,7 @@ enum MouseState {
     Unknown,
     Free(Vec2),
     Rotate(Vec2),
-    Pan(Vec2, Vec3),
+    Pan(Vec2, DVec3),
 }
 
 pub struct Camera {
@@ -27,8 +27,13 @@ pub struct Camera {
     /// Model scale
     scale: f32,
 
-    /// Center of view volume
-    center: Vec3,
+    /// Center of view volume.  This is kept in double precision, because it's
+    /// subtracted from the model before anything is converted to `f32`.
+    center: DVec3,
+
+    /// Bounding sphere (center and radius) of the whole scene, which is used
+    /// to pick the depth range
+    scene: Option<(DVec3, f64)>,
 
     mouse: MouseState,
 }
@@ -41,7 +46,8 @@ impl Camera {
             pitch: 0.0,
             yaw: 0.0,
             scale: 1.0,
-            center: Vec3::zeros(),
+            center: DVec3::zeros(),
+            scene: None,
             mouse: MouseState::Unknown,
         }
     }
@@ -51,7 +57,8 @@ impl Camera {
         if let MouseState::Free(pos) = &self.mouse {
             match button {
                 MouseButton::Left => Some(MouseState::Rotate(*pos)),
-                MouseButton::Right => Some(MouseState::Pan(*pos, self.mouse_pos(*pos))),
+                MouseButton::Right => Some(MouseState::Pan(
+                    *pos, self.center + self.mouse_pos(*pos).cast())),
                 _ => None,
             }.map(|m| self.mouse = m);
         }
@@ -76,7 +83,8 @@ impl Camera {
             .expect("Failed to invert mouse matrix")
     }
 
-    /// Converts a normalized mouse position into 3D
+    /// Converts a normalized mouse position into 3D, relative to the center
+    /// of the view
     pub fn mouse_pos(&self, pos_norm: Vec2) -> Vec3 {
         (self.mat_i() * Vec4::new(pos_norm.x, pos_norm.y, 0.0, 1.0)).xyz()
     }
@@ -89,7 +97,7 @@ impl Camera {
         // Pan or rotate depending on current mouse state
         match &self.mouse {
             MouseState::Pan(_pos, orig) => {
-                let current_pos = self.mouse_pos(new_pos);
+                let current_pos = self.center + self.mouse_pos(new_pos).cast();
                 let delta_pos = orig - current_pos;
                 self.center += delta_pos;
             },
@@ -120,13 +128,27 @@ impl Camera {
         let xb = verts.iter().map(|v| v.pos.x).minmax().into_option().unwrap();
         let yb = verts.iter().map(|v| v.pos.y).minmax().into_option().unwrap();
         let zb = verts.iter().map(|v| v.pos.z).minmax().into_option().unwrap();
-        let dx = xb.1 - xb.0;
-        let dy = yb.1 - yb.0;
-        let dz = zb.1 - zb.0;
-        self.scale = (1.0 / dx.max(dy).max(dz)) as f32;
-        self.center = Vec3::new((xb.0 + xb.1) as f32 / 2.0,
-                                (yb.0 + yb.1) as f32 / 2.0,
-                                (zb.0 + zb.1) as f32 / 2.0);
+        let lo = DVec3::new(xb.0, yb.0, zb.0);
+        let hi = DVec3::new(xb.1, yb.1, zb.1);
+        self.set_scene(lo, hi);
+        self.fit_bounds(lo, hi);
+    }
+
+    /// Records the extent of the whole scene, which is used to fit the depth
+    /// range around the model (rather than clipping it)
+    pub fn set_scene(&mut self, lo: DVec3, hi: DVec3) {
+        self.scene = Some(((lo + hi) / 2.0, (hi - lo).norm() / 2.0));
+    }
+
+    /// Centers the view on a bounding box, scaled so that it fills the window
+    pub fn fit_bounds(&mut self, lo: DVec3, hi: DVec3) {
+        let d = hi - lo;
+        let size = d.x.max(d.y).max(d.z);
+        // A single point (or an empty box) has no size, so keep the old zoom
+        if size > 0.0 && size.is_finite() {
+            self.scale = (1.0 / size) as f32;
+        }
+        self.center = (lo + hi) / 2.0;
     }
 
     pub fn set_size(&mut self, width: f32, height: f32) {
@@ -134,31 +156,52 @@ impl Camera {
         self.height = height;
     }
 
+    /// Returns the center of the view, in model coordinates
+    pub fn center(&self) -> DVec3 {
+        self.center
+    }
+
+    /// Returns the rotation and scale of the model about [`Camera::center`].
+    ///
+    /// Recentering isn't included: positions must have the center subtracted
+    /// in double precision before they're handed to this matrix, otherwise
+    /// large models jitter as `f32` rounding errors are magnified.
     pub fn model_matrix(&self) -> Mat4 {
         let i = Mat4::identity();
         // The transforms below are applied bottom-to-top when thinking about
-        // the model, i.e. it's translated, then scaled, then rotated, etc.
+        // the model, i.e. it's scaled, then rotated, etc.
 
         // Scale to compensate for model size
         glm::scale(&i, &Vec3::new(self.scale, self.scale, self.scale)) *
 
         // Rotation!
         glm::rotate_x(&i, self.yaw) *
-        glm::rotate_y(&i, self.pitch) *
-
-        // Recenter model
-        glm::translate(&i, &-self.center)
+        glm::rotate_y(&i, self.pitch)
     }
 
     /// Returns a matrix which compensates for window aspect ratio and clipping
     pub fn view_matrix(&self) -> Mat4 {
         let i = Mat4::identity();
+
+        // Fit the scene's bounding sphere into the depth range, so that the
+        // model is never clipped, no matter how far we zoom in or how large
+        // it is.  This also uses all of the depth buffer's precision.
+        let (z_center, z_scale) = match self.scene {
+            Some((center, radius)) if radius > 0.0 => {
+                let c = self.model_matrix()
+                    * (center - self.center).cast::<f32>().push(1.0);
+                (c.z, 0.49 / (radius as f32 * self.scale))
+            },
+            _ => (0.0, 0.1),
+        };
+
         // The Z clipping range is 0-1, so push forward
         glm::translate(&i, &Vec3::new(0.0, 0.0, 0.5)) *
 
-        // Scale to compensate for aspect ratio and reduce Z scale to improve
-        // clipping
-        glm::scale(&i, &Vec3::new(1.0, self.width / self.height, 0.1))
+        // Scale to compensate for aspect ratio and to fit the scene's depth
+        glm::scale(&i, &Vec3::new(1.0, self.width / self.height, z_scale)) *
+
+        glm::translate(&i, &Vec3::new(0.0, 0.0, -z_center))
     }
 
     pub fn spin(&mut self, dx: f32, dy: f32) {
@@ -175,6 +218,7 @@ impl Camera {
         let mut delta_mouse = (self.mat() * delta.to_homogeneous()).xyz();
         delta_mouse.z = 0.0;
 
-        self.center += (self.mat_i() * delta_mouse.to_homogeneous()).xyz();
+        self.center += (self.mat_i() * delta_mouse.to_homogeneous()).xyz()
+            .cast();
     }
 }
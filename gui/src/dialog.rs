@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Runs a dialog program, returning its trimmed standard output if it exited
+/// successfully and printed anything.  Dialog programs exit with an error
+/// when the user presses Cancel, so that's also reported as `None`.
+fn run(cmd: &mut Command) -> Option<String> {
+    let out = cmd.stderr(Stdio::null()).output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let s = String::from_utf8(out.stdout).ok()?;
+    let s = s.trim_end_matches(&['\n', '\r'][..]);
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_owned())
+    }
+}
+
+/// Runs a dialog program which doesn't print anything, returning `true` if
+/// it ran successfully
+fn show(cmd: &mut Command) -> bool {
+    cmd.stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Shows a native Open dialog for STEP files.  Returns `None` if the user
+/// cancelled, or if there's no way to show a dialog on this system.
+///
+/// The dialog is provided by a helper program (AppleScript on macOS,
+/// PowerShell on Windows, and `zenity` or `kdialog` elsewhere), so this
+/// blocks until the user makes a choice.
+pub fn open_step() -> Option<PathBuf> {
+    let title = "Open STEP file";
+    let path = if cfg!(target_os = "macos") {
+        run(Command::new("osascript").arg("-e").arg(format!(
+            "POSIX path of (choose file with prompt \"{}\" \
+             of type {{\"step\", \"stp\", \"public.item\"}})", title)))
+    } else if cfg!(target_os = "windows") {
+        run(Command::new("powershell").args(["-NoProfile", "-STA", "-Command"])
+            .arg(format!(
+                "Add-Type -AssemblyName System.Windows.Forms; \
+                 $d = New-Object System.Windows.Forms.OpenFileDialog; \
+                 $d.Title = '{}'; \
+                 $d.Filter = 'STEP files (*.step;*.stp)|*.step;*.stp|\
+                              All files (*.*)|*.*'; \
+                 if ($d.ShowDialog() -eq 'OK') {{ $d.FileName }}", title)))
+    } else {
+        run(Command::new("zenity")
+            .args(["--file-selection", "--title", title])
+            .args(["--file-filter", "STEP files | *.step *.stp *.STEP *.STP"])
+            .args(["--file-filter", "All files | *"]))
+        .or_else(|| run(Command::new("kdialog")
+            .args(["--title", title, "--getopenfilename", "."])
+            .arg("STEP files (*.step *.stp *.STEP *.STP)")))
+    };
+    path.map(PathBuf::from)
+}
+
+/// Shows a list of rows for the user to pick from, returning the index of the
+/// chosen row.  Returns `None` if the user cancelled, or if there's no way to
+/// show a list on this system.  Like [`open_step`], this blocks until the
+/// user makes a choice.
+pub fn choose(title: &str, text: &str, rows: &[String]) -> Option<usize> {
+    // Rows are numbered, so that dialogs which return the chosen text (rather
+    // than an index) can still be mapped back to a row
+    let numbered: Vec<String> = rows.iter()
+        .enumerate()
+        .map(|(i, r)| format!("{}: {}", i + 1, r))
+        .collect();
+    let chosen = if cfg!(target_os = "macos") {
+        let list: Vec<String> = numbered.iter()
+            .map(|r| format!("\"{}\"",
+                             r.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        run(Command::new("osascript").arg("-e").arg(format!(
+            "choose from list {{{}}} with title \"{}\" with prompt \"{}\"",
+            list.join(", "), title, text)))
+    } else if cfg!(target_os = "windows") {
+        let list: Vec<String> = numbered.iter()
+            .map(|r| format!("'{}'", r.replace('\'', "''")))
+            .collect();
+        run(Command::new("powershell").args(["-NoProfile", "-Command"])
+            .arg(format!("@({}) | Out-GridView -Title '{}' -OutputMode Single",
+                         list.join(", "), title)))
+    } else {
+        run(Command::new("zenity")
+            .args(["--list", "--title", title, "--text", text])
+            .args(["--column", title, "--width", "800", "--height", "400"])
+            .args(&numbered))
+        .or_else(|| run(Command::new("kdialog")
+            .args(["--title", title, "--menu", text])
+            .args(rows.iter().enumerate()
+                .flat_map(|(i, r)| vec![(i + 1).to_string(), r.clone()]))))
+    };
+    // AppleScript prints "false" when cancelled, which fails to parse here
+    let i: usize = chosen?.split(':').next()?.trim().parse().ok()?;
+    i.checked_sub(1).filter(|i| *i < rows.len())
+}
+
+/// Reports an error to the user.  When the viewer is launched from a file
+/// manager there's no terminal to see `stderr`, so this also tries to show a
+/// message box.
+pub fn error(msg: &str) {
+    eprintln!("{}", msg);
+    let title = "Foxtrot";
+    if cfg!(target_os = "macos") {
+        show(Command::new("osascript").arg("-e").arg(format!(
+            "display alert \"{}\" message \"{}\" as critical",
+            title, msg.replace('\\', "\\\\").replace('"', "\\\""))));
+    } else if cfg!(target_os = "windows") {
+        show(Command::new("powershell").args(["-NoProfile", "-Command"])
+            .arg(format!(
+                "Add-Type -AssemblyName System.Windows.Forms; \
+                 [void][System.Windows.Forms.MessageBox]::Show('{}', '{}')",
+                msg.replace('\'', "''"), title)));
+    } else if !show(Command::new("zenity")
+            .args(["--error", "--title", title, "--text", msg]))
+    {
+        show(Command::new("kdialog").args(["--title", title, "--error", msg]));
+    }
+}
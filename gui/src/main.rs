@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::time::SystemTime;
 use winit::{
     event::{Event},
@@ -8,13 +9,13 @@ use winit::{
 pub(crate) mod app;
 pub(crate) mod backdrop;
 pub(crate) mod camera;
+pub(crate) mod dialog;
 pub(crate) mod model;
 
 use crate::app::App;
-use triangulate::mesh::Mesh;
 
 async fn run(start: SystemTime, event_loop: EventLoop<()>, window: Window,
-             loader: std::thread::JoinHandle<Mesh>)
+             loader: std::thread::JoinHandle<app::Loaded>, title: String)
 {
     let size = window.inner_size();
     let (surface, adapter) = {
@@ -44,7 +45,9 @@ async fn run(start: SystemTime, event_loop: EventLoop<()>, window: Window,
         .await
         .expect("Failed to create device");
 
-    let mut app = App::new(start, size, adapter, surface, device, loader);
+    let mut shown_title = title.clone();
+    let mut app = App::new(start, size, adapter, surface, device, loader,
+                           title);
 
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
@@ -63,6 +66,14 @@ async fn run(start: SystemTime, event_loop: EventLoop<()>, window: Window,
             Event::DeviceEvent { event, .. } => app.device_event(event),
             _ => (),
         }
+
+        // The title changes once the model is loaded (if there were any
+        // problems), and when stepping through diagnostics
+        let title = app.title();
+        if title != shown_title {
+            window.set_title(&title);
+            shown_title = title;
+        }
     });
 }
 
@@ -70,33 +81,87 @@ fn main() {
     let start = SystemTime::now();
     env_logger::init();
 
+    // When launched from the Finder, older versions of macOS pass a process
+    // serial number argument, which would otherwise be rejected by clap
+    let args = std::env::args().filter(|a| !a.starts_with("-psn_"));
     let matches = clap::App::new("gui")
         .author("Matt Keeter <matt@formlabs.com>")
         .about("Renders a STEP file")
+        .arg(clap::Arg::with_name("audit-normals")
+            .long("audit-normals")
+            .help("highlight triangles which disagree with their vertex normals"))
         .arg(clap::Arg::with_name("input")
             .takes_value(true)
-            .required(true))
-        .get_matches();
-    let input = matches.value_of("input")
-        .expect("Could not get input file")
-        .to_owned();
+            .help("STEP file to open (if omitted, shows an Open dialog)"))
+        .get_matches_from(args);
+
+    // With no input file (e.g. when launched from a file manager or dock),
+    // ask the user to pick one
+    let input = match matches.value_of("input") {
+        Some(i) => PathBuf::from(i),
+        None => match dialog::open_step() {
+            Some(i) => i,
+            None => return,
+        },
+    };
+    let title = match input.file_name() {
+        Some(name) => format!("Foxtrot - {}", name.to_string_lossy()),
+        None => "Foxtrot".to_owned(),
+    };
+    let audit = matches.is_present("audit-normals");
 
     // Kick off the loader thread immediately, so that the STEP file is parsed
     // and triangulated in the background while we wait for a GPU context
-    let loader = std::thread::spawn(|| {
+    let loader = std::thread::spawn(move || {
+        #[cfg(feature = "profiling")]
+        profiling::register_thread!("loader");
         println!("Loading mesh!");
         use step::step_file::StepFile;
         use triangulate::triangulate::triangulate;
 
-        let data = std::fs::read(input).expect("Could not open file");
+        let data = match StepFile::read(&input) {
+            Ok(data) => data,
+            Err(e) => {
+                dialog::error(&format!("Could not open {}: {}",
+                                       input.display(), e));
+                std::process::exit(1);
+            },
+        };
         let flat = StepFile::strip_flatten(&data);
         let step = StepFile::parse(&flat);
-        let (mesh, _stats) = triangulate(&step);
-        mesh
+        let (mut mesh, stats) = triangulate(&step);
+        if !stats.diagnostics.is_empty() {
+            println!("Found {} problems (press D in the viewer to list them, \
+                      or Tab to step through them):", stats.diagnostics.len());
+            for d in &stats.diagnostics {
+                println!("  {}", d);
+            }
+        }
+        let mut diagnostics = stats.diagnostics;
+
+        if audit {
+            use triangulate::audit::{audit_normals, faces, highlight};
+            let issues = audit_normals(&mesh, std::f64::consts::FRAC_PI_2);
+            for (face, (count, _)) in faces(&issues) {
+                println!("Face #{} has {} flipped triangles", face, count);
+            }
+            highlight(&mut mesh, &issues);
+        }
+
+        // The GPU works in single precision, so move models which are far
+        // from the origin before we lose detail converting to f32
+        if mesh.precision_report().map(|r| r.at_risk()).unwrap_or(false) {
+            let offset = mesh.recenter();
+            println!("Recentered model by {:?}", offset.as_slice());
+            for d in &mut diagnostics {
+                d.translate(-offset);
+            }
+        }
+        (mesh, diagnostics)
     });
 
     let event_loop = EventLoop::new();
     let window = winit::window::Window::new(&event_loop).unwrap();
-    window.set_title("Foxtrot");
-    pollster::block_on(run(start, event_loop, window, loader));
+    window.set_title(&title);
+    pollster::block_on(run(start, event_loop, window, loader, title));
 }
@@ -1,11 +1,12 @@
 use std::borrow::Cow;
+use std::ops::Range;
 
 use bytemuck::{Pod, Zeroable};
 use nalgebra_glm as glm;
-use glm::{Vec4, Mat4};
+use glm::{Vec4, Mat4, DVec3};
 use wgpu::util::DeviceExt;
 
-use triangulate::mesh::{Vertex, Triangle};
+use triangulate::mesh::{Mesh, Vertex, Triangle};
 
 use crate::camera::Camera;
 
@@ -17,52 +18,186 @@ struct GPUVertex {
     color: [f32; 4],
 }
 
+/// When the view center is this far from the origin of the vertex buffer
+/// (measured in half-window widths), the buffer is rebuilt around the center
+/// to keep `f32` rounding errors well below a pixel.
+const REBASE_DISTANCE: f32 = 8.0;
+
 impl GPUVertex {
-    fn from_vertex(v: &Vertex) -> Self {
+    /// Builds a GPU vertex, with its position relative to `origin`.  The
+    /// subtraction happens in double precision, so nearby vertices stay
+    /// distinct even if they're far from the model's origin.
+    fn from_vertex(v: &Vertex, origin: DVec3, alpha: f32) -> Self {
+        let pos = v.pos - origin;
         Self {
-            pos: [v.pos.x as f32, v.pos.y as f32, v.pos.z as f32, 1.0],
+            pos: [pos.x as f32, pos.y as f32, pos.z as f32, 1.0],
             norm: [v.norm.x as f32, v.norm.y as f32, v.norm.z as f32, 1.0],
-            color: [v.color.x as f32, v.color.y as f32, v.color.z as f32, 1.0],
+            color: [v.color.x as f32, v.color.y as f32, v.color.z as f32, alpha],
+        }
+    }
+}
+
+/// How the model's surfaces are shaded
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Shading {
+    /// Brightness depends only on the angle to the viewer
+    Lambert,
+    /// A studio-style material capture, with key and fill lights, a specular
+    /// highlight, and darkened silhouettes, which makes the shape of complex
+    /// parts easier to read
+    Matcap,
+}
+
+impl Shading {
+    /// Value passed to the shader to select this mode
+    fn uniform(&self) -> f32 {
+        match self {
+            Shading::Lambert => 0.0,
+            Shading::Matcap => 1.0,
+        }
+    }
+}
+
+/// Opacity of every part in X-ray mode
+const XRAY_OPACITY: f32 = 0.3;
+
+/// A run of triangles which is drawn as a unit.  Translucent groups are
+/// sorted by depth before drawing, since they must be blended back-to-front.
+struct Group {
+    triangles: Range<usize>,
+    opacity: f32,
+    /// Center of the group's bounding box, which is used to sort it
+    center: DVec3,
+    /// Range of the group's indices in the index buffer, if it's translucent
+    /// (opaque groups are all drawn at once)
+    indices: Option<Range<u32>>,
+}
+
+impl Group {
+    /// Splits a mesh into one group per part, plus an opaque group for any
+    /// triangles which aren't in a part
+    fn from_mesh(mesh: &Mesh) -> Vec<Self> {
+        let mut parts: Vec<_> = mesh.parts.iter()
+            .map(|p| (p.triangles.clone(), p.opacity as f32))
+            .collect();
+        parts.sort_by_key(|p| p.0.start);
+
+        let mut ranges = Vec::new();
+        let mut next = 0;
+        for (r, opacity) in parts {
+            if r.start > next {
+                ranges.push((next..r.start, 1.0));
+            }
+            next = next.max(r.end);
+            ranges.push((r, opacity));
+        }
+        if next < mesh.triangles.len() {
+            ranges.push((next..mesh.triangles.len(), 1.0));
+        }
+
+        ranges.into_iter().map(|(triangles, opacity)| {
+            let (lo, hi) = mesh.triangles[triangles.clone()].iter()
+                .flat_map(|t| t.verts.iter())
+                .map(|v| mesh.verts[*v as usize].pos)
+                .fold((DVec3::repeat(f64::INFINITY),
+                       DVec3::repeat(f64::NEG_INFINITY)),
+                      |(lo, hi), p| (lo.inf(&p), hi.sup(&p)));
+            Group { triangles, opacity, center: (lo + hi) / 2.0, indices: None }
+        }).collect()
+    }
+}
+
+/// Builds vertex and index data for the GPU.  Opaque triangles come first in
+/// the index data, followed by each translucent group (whose index ranges
+/// are stored in the group).  Returns the number of opaque indices as well.
+fn layout(verts: &[Vertex], tris: &[Triangle], groups: &mut [Group],
+          origin: DVec3, xray: bool) -> (Vec<GPUVertex>, Vec<u32>, u32)
+{
+    let mut alpha = vec![1.0; verts.len()];
+    let mut opaque = Vec::new();
+    let mut translucent = Vec::new();
+    for g in groups.iter_mut() {
+        let opacity = if xray { g.opacity.min(XRAY_OPACITY) } else { g.opacity };
+        let indices = tris[g.triangles.clone()].iter()
+            .flat_map(|t| t.verts.iter())
+            .copied();
+        if opacity < 1.0 {
+            let start = translucent.len() as u32;
+            for i in indices {
+                alpha[i as usize] = opacity;
+                translucent.push(i);
+            }
+            g.indices = Some(start..translucent.len() as u32);
+        } else {
+            opaque.extend(indices);
+            g.indices = None;
         }
     }
+
+    // Translucent indices are stored after the opaque ones
+    let opaque_count = opaque.len() as u32;
+    for r in groups.iter_mut().filter_map(|g| g.indices.as_mut()) {
+        *r = r.start + opaque_count..r.end + opaque_count;
+    }
+    opaque.extend(translucent);
+
+    let vertex_data = verts.iter().zip(alpha)
+        .map(|(v, a)| GPUVertex::from_vertex(v, origin, a))
+        .collect();
+    (vertex_data, opaque, opaque_count)
 }
 
 pub struct Model {
+    /// Original vertices, kept in double precision so that the vertex buffer
+    /// can be rebuilt around a new origin
+    verts: Vec<Vertex>,
+    tris: Vec<Triangle>,
+    groups: Vec<Group>,
+    /// Point which was subtracted from every position in `vertex_buf`
+    origin: DVec3,
+    /// Draws every part as translucent, to see inside of assemblies
+    xray: bool,
+    /// Set when the vertex and index buffers need to be rebuilt
+    dirty: bool,
+    shading: Shading,
+
     vertex_buf: wgpu::Buffer,
     index_buf: wgpu::Buffer,
     uniform_buf: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
-    index_count: u32,
+    /// Number of indices at the start of `index_buf` which are opaque
+    opaque_count: u32,
     render_pipeline: wgpu::RenderPipeline,
+    /// Pipeline for translucent triangles, which are blended with what's
+    /// behind them and don't write to the depth buffer
+    blend_pipeline: wgpu::RenderPipeline,
 }
 
 impl Model {
     pub fn new(device: &wgpu::Device, swapchain_format: wgpu::TextureFormat,
-               verts: &[Vertex], tris: &[Triangle]) -> Self {
+               mesh: &Mesh) -> Self {
 
-        let vertex_data: Vec<GPUVertex> = verts.into_iter()
-            .map(GPUVertex::from_vertex)
-            .collect();
-        let index_data: Vec<u32> = tris.into_iter()
-            .flat_map(|t| t.verts.iter())
-            .copied()
-            .collect();
+        let origin = DVec3::zeros();
+        let mut groups = Group::from_mesh(mesh);
+        let (vertex_data, index_data, opaque_count) = layout(
+            &mesh.verts, &mesh.triangles, &mut groups, origin, false);
 
         let vertex_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Vertex buffer"),
             contents: bytemuck::cast_slice(&vertex_data),
-            usage: wgpu::BufferUsage::VERTEX,
+            usage: wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST,
         });
 
         let index_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Index buffer"),
             contents: bytemuck::cast_slice(&index_data),
-            usage: wgpu::BufferUsage::INDEX,
+            usage: wgpu::BufferUsage::INDEX | wgpu::BufferUsage::COPY_DST,
         });
 
         let uniform_buf = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Uniform Buffer"),
-            size: std::mem::size_of::<Mat4>() as wgpu::BufferAddress * 2,
+            size: std::mem::size_of::<Mat4>() as wgpu::BufferAddress * 2
+                + std::mem::size_of::<Vec4>() as wgpu::BufferAddress,
             usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
             mapped_at_creation: false,
         });
@@ -72,12 +207,14 @@ impl Model {
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStage::VERTEX,
+                    visibility: wgpu::ShaderStage::VERTEX
+                              | wgpu::ShaderStage::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
                         min_binding_size: wgpu::BufferSize::new(
-                            std::mem::size_of::<Mat4>() as u64 * 2),
+                            std::mem::size_of::<Mat4>() as u64 * 2
+                            + std::mem::size_of::<Vec4>() as u64),
                     },
                     count: None,
                 },
@@ -145,55 +282,119 @@ impl Model {
             flags: wgpu::ShaderFlags::all(),
         });
 
-        let render_pipeline = device.create_render_pipeline(
-            &wgpu::RenderPipelineDescriptor {
+        // The two pipelines only differ in how they treat blending and depth
+        let make_pipeline = |blend: Option<wgpu::BlendState>| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
                 label: None,
                 layout: Some(&pipeline_layout),
                 vertex: wgpu::VertexState {
                     module: &shader,
                     entry_point: "vs_main",
-                    buffers: &[vertex_buf_layout],
+                    buffers: std::slice::from_ref(&vertex_buf_layout),
                 },
                 fragment: Some(wgpu::FragmentState {
                     module: &shader,
                     entry_point: "fs_main",
-                    targets: &[swapchain_format.into()],
+                    targets: &[wgpu::ColorTargetState {
+                        format: swapchain_format,
+                        blend,
+                        write_mask: wgpu::ColorWrite::ALL,
+                    }],
                 }),
                 primitive: wgpu::PrimitiveState::default(),
                 depth_stencil: Some(wgpu::DepthStencilState {
                     format: wgpu::TextureFormat::Depth32Float,
-                    depth_write_enabled: true,
+                    depth_write_enabled: blend.is_none(),
                     depth_compare: wgpu::CompareFunction::Greater,
                     stencil: wgpu::StencilState::default(),
                     bias: wgpu::DepthBiasState::default(),
                 }),
                 multisample: wgpu::MultisampleState::default(),
-        });
+            })
+        };
+        let render_pipeline = make_pipeline(None);
+        let blend_pipeline = make_pipeline(
+            Some(wgpu::BlendState::ALPHA_BLENDING));
 
         Model {
+            verts: mesh.verts.clone(),
+            tris: mesh.triangles.clone(),
+            groups,
+            origin,
+            xray: false,
+            dirty: false,
+            shading: Shading::Lambert,
             render_pipeline,
+            blend_pipeline,
             index_buf,
             vertex_buf,
             uniform_buf,
             bind_group,
-            index_count: tris.len() as u32 * 3,
+            opaque_count,
         }
     }
 
-    pub fn draw(&self, camera: &Camera,
+    /// Toggles X-ray mode, where every part is drawn as translucent
+    pub fn toggle_xray(&mut self) {
+        self.xray = !self.xray;
+        self.dirty = true;
+    }
+
+    /// Switches between Lambert and matcap shading
+    pub fn toggle_shading(&mut self) {
+        self.shading = match self.shading {
+            Shading::Lambert => Shading::Matcap,
+            Shading::Matcap => Shading::Lambert,
+        };
+    }
+
+    /// Rebuilds the vertex and index buffers, e.g. around a new origin
+    fn upload(&mut self, queue: &wgpu::Queue) {
+        let (vertex_data, index_data, opaque_count) = layout(
+            &self.verts, &self.tris, &mut self.groups, self.origin, self.xray);
+        queue.write_buffer(&self.vertex_buf, 0,
+            bytemuck::cast_slice(&vertex_data));
+        queue.write_buffer(&self.index_buf, 0,
+            bytemuck::cast_slice(&index_data));
+        self.opaque_count = opaque_count;
+        self.dirty = false;
+    }
+
+    pub fn draw(&mut self, camera: &Camera,
                 queue: &wgpu::Queue,
                 frame: &wgpu::SwapChainTexture,
                 depth_view: &wgpu::TextureView,
                 encoder: &mut wgpu::CommandEncoder)
     {
+        // Vertex positions are relative to `self.origin`, so the only
+        // translation left for the GPU is the (small) offset between that
+        // origin and the view center.  If that offset grows large on screen,
+        // then re-upload the vertices around the current center.
+        let model_mat = camera.model_matrix();
+        let offset = |origin: DVec3| (origin - camera.center()).cast::<f32>();
+        if (model_mat * offset(self.origin).to_homogeneous()).xyz().norm()
+            > REBASE_DISTANCE
+        {
+            self.origin = camera.center();
+            self.dirty = true;
+        }
+        if self.dirty {
+            self.upload(queue);
+        }
+
         // Update the uniform buffer with our new matrix
         let view_mat = camera.view_matrix();
-        let model_mat = camera.model_matrix();
+        let model_mat = model_mat * glm::translate(
+            &Mat4::identity(), &offset(self.origin));
         queue.write_buffer(&self.uniform_buf, 0,
             bytemuck::cast_slice(view_mat.as_slice()));
         queue.write_buffer(&self.uniform_buf,
             std::mem::size_of::<Mat4>() as wgpu::BufferAddress,
             bytemuck::cast_slice(model_mat.as_slice()));
+        let settings = Vec4::new(self.shading.uniform(), 0.0, 0.0, 0.0);
+        queue.write_buffer(&self.uniform_buf,
+            2 * std::mem::size_of::<Mat4>() as wgpu::BufferAddress,
+            bytemuck::cast_slice(settings.as_slice()));
 
         let mut rpass = encoder.begin_render_pass(
             &wgpu::RenderPassDescriptor {
@@ -220,6 +421,24 @@ impl Model {
         rpass.set_index_buffer(self.index_buf.slice(..), wgpu::IndexFormat::Uint32);
         rpass.set_vertex_buffer(0, self.vertex_buf.slice(..));
         rpass.set_bind_group(0, &self.bind_group, &[]);
-        rpass.draw_indexed(0..self.index_count, 0, 0..1);
+        rpass.draw_indexed(0..self.opaque_count, 0, 0..1);
+
+        // Translucent groups are drawn afterwards, from back to front (where
+        // larger Z values are closer to the viewer)
+        let rot = camera.model_matrix();
+        let mut translucent: Vec<_> = self.groups.iter()
+            .filter_map(|g| g.indices.clone().map(|i| {
+                let c = offset(g.center).to_homogeneous();
+                ((rot * c).z, i)
+            }))
+            .collect();
+        if !translucent.is_empty() {
+            translucent.sort_by(|a, b| a.0.partial_cmp(&b.0)
+                .unwrap_or(std::cmp::Ordering::Equal));
+            rpass.set_pipeline(&self.blend_pipeline);
+            for (_, i) in translucent {
+                rpass.draw_indexed(i, 0, 0..1);
+            }
+        }
     }
 }
@@ -8,6 +8,8 @@ struct VertexOutput {
 struct Locals {
     view_mat: mat4x4<f32>;
     model_mat: mat4x4<f32>;
+    // x selects the shading mode: 0 = Lambert, 1 = matcap
+    settings: vec4<f32>;
 };
 [[group(0), binding(0)]]
 var r_locals: Locals;
@@ -25,7 +27,25 @@ fn vs_main(
     return out;
 }
 
+// Procedural studio material capture, which only depends on the normal in
+// view space (so lights stay fixed relative to the camera)
+fn matcap(normal: vec3<f32>, color: vec3<f32>) -> vec3<f32> {
+    var n: vec3<f32> = normalize(normal);
+    if (n.z < 0.0) {
+        n = n * -1.0;
+    }
+    let key = max(dot(n, normalize(vec3<f32>(-0.4, 0.6, 0.7))), 0.0);
+    let fill = 0.5 + 0.5 * n.y;
+    let rim = pow(1.0 - n.z, 3.0);
+    let spec = pow(key, 24.0);
+    let shade = (0.15 + 0.5 * key + 0.3 * fill) * (1.0 - 0.6 * rim);
+    return color * shade + vec3<f32>(0.3 * spec);
+}
+
 [[stage(fragment)]]
 fn fs_main(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    return vec4<f32>(abs(in.normal.z) * in.color.xyz, 1.0);
+    if (r_locals.settings.x > 0.5) {
+        return vec4<f32>(matcap(in.normal.xyz, in.color.xyz), in.color.w);
+    }
+    return vec4<f32>(abs(in.normal.z) * in.color.xyz, in.color.w);
 }
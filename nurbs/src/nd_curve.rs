@@ -34,6 +34,17 @@ impl<const D: usize> NDBSplineCurve<D> {
     /// of order `p + 1` respectively.
     ///
     /// ALGORITHM A3.1
+    ///
+    /// ```
+    /// use nalgebra_glm::{vec3, DVec3};
+    /// use nurbs::{KnotVector, NDBSplineCurve};
+    ///
+    /// // A quadratic Bézier curve, written as a B-spline
+    /// let knots = KnotVector::from_multiplicities(2, &[0.0, 1.0], &[3, 3]);
+    /// let c = NDBSplineCurve::new(true, knots, vec![
+    ///     vec3(0.0, 0.0, 0.0), vec3(1.0, 2.0, 0.0), vec3(2.0, 0.0, 0.0)]);
+    /// assert_eq!(c.curve_point(0.5), DVec3::new(1.0, 1.0, 0.0));
+    /// ```
     pub fn curve_point(&self, u: f64) -> TVec<f64, D> {
         let p = self.knots.degree();
 
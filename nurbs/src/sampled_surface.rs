@@ -1,5 +1,5 @@
 use nalgebra_glm::{dot, length, length2, DMat2x2, DVec2, DVec3};
-use crate::{abstract_surface::AbstractSurface, nd_surface::NDBSplineSurface};
+use crate::{abstract_surface::AbstractSurface, nd_surface::NDBSplineSurface, KnotVector};
 use log::error;
 
 #[derive(Debug, Clone)]
@@ -159,6 +159,44 @@ impl<const N: usize> SampledSurface<N>
             .unwrap().0;
         self.uv_from_point_newtons_method(p, best_uv)
     }
+
+    /// Finds UV coordinates like [`uv_from_point`](Self::uv_from_point),
+    /// but starting from the closest of `n` x `n` samples per knot span,
+    /// which are evaluated on the fly.  This is slower, but can succeed when
+    /// the cached samples are too sparse to find a good starting point.
+    pub fn uv_from_point_grid(&self, p: DVec3, n: usize) -> Option<DVec2> {
+        let n = n.max(2);
+        let lerp = |knots: &KnotVector, i: usize, k: usize| {
+            let frac = (k as f64) / (n as f64 - 1.0);
+            knots[i] * (1.0 - frac) + knots[i + 1] * frac
+        };
+        let (u_knots, v_knots) = (&self.surf.u_knots, &self.surf.v_knots);
+
+        let mut best = None;
+        let mut best_dist = f64::INFINITY;
+        for i in 0..u_knots.len() - 1 {
+            if u_knots[i] == u_knots[i + 1] {
+                continue;
+            }
+            for j in 0..v_knots.len() - 1 {
+                if v_knots[j] == v_knots[j + 1] {
+                    continue;
+                }
+                for a in 0..n {
+                    for b in 0..n {
+                        let uv = DVec2::new(lerp(u_knots, i, a),
+                                            lerp(v_knots, j, b));
+                        let d = (self.surf.point(uv) - p).norm();
+                        if d < best_dist {
+                            best_dist = d;
+                            best = Some(uv);
+                        }
+                    }
+                }
+            }
+        }
+        best.and_then(|uv| self.uv_from_point_newtons_method(p, uv))
+    }
 }
 
 /// Builds the symmetric matrix [[a, b], [b, d]]
@@ -0,0 +1,52 @@
+use clap::{Arg, App};
+use express::{
+    error::render_error,
+    parse::{parse, strip_comments_and_lower},
+};
+use step::{raw::records, rules::check_rules, step_file::StepFile};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = App::new("check_rules")
+        .about("Checks a STEP file against the rules in an EXPRESS schema")
+        .arg(Arg::with_name("schema")
+            .takes_value(true)
+            .required(true))
+        .arg(Arg::with_name("input")
+            .takes_value(true)
+            .required(true))
+        .arg(Arg::with_name("name")
+            .long("name")
+            .takes_value(true)
+            .help("Schema to check against, if the file contains several"))
+        .get_matches();
+    let schema = matches.value_of("schema")
+        .expect("Could not get schema file");
+    let input = matches.value_of("input")
+        .expect("Could not get input file");
+
+    let text = strip_comments_and_lower(&std::fs::read(schema)?);
+    let syntax = match parse(&text) {
+        Ok(s) => s,
+        Err(e) => {
+            eprint!("{}", render_error(&text, &e));
+            std::process::exit(1);
+        },
+    };
+    let name = match matches.value_of("name") {
+        Some(n) => n,
+        None => syntax.0.first().ok_or("No schema in file")?.id.0,
+    };
+
+    let data = StepFile::read(input)?;
+    let flat = StepFile::strip_flatten(&data);
+    let rs = records(&flat);
+    let report = check_rules(&syntax, name, &rs)
+        .ok_or_else(|| format!("No schema named {}", name))?;
+    for v in &report.violations {
+        println!("{}", v);
+    }
+    println!("{} instances, {} rules checked, {} violated, {} unknown",
+             rs.len(), report.checked, report.violations.len(),
+             report.unknown);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use clap::{Arg, App};
+use express::{
+    error::render_error,
+    parse::{parse, strip_comments_and_lower},
+};
+use step::{
+    conformance::validate, dynamic::DynSchema, raw::records,
+    step_file::StepFile,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = App::new("dyn_step")
+        .about("Parses a STEP file using an EXPRESS schema loaded at runtime")
+        .arg(Arg::with_name("schema")
+            .takes_value(true)
+            .required(true))
+        .arg(Arg::with_name("input")
+            .takes_value(true)
+            .required(true))
+        .arg(Arg::with_name("name")
+            .long("name")
+            .takes_value(true)
+            .help("Schema to parse with, if the file contains several"))
+        .arg(Arg::with_name("check")
+            .long("check")
+            .help("Checks each instance against the schema, instead of printing it"))
+        .get_matches();
+    let schema = matches.value_of("schema")
+        .expect("Could not get schema file");
+    let input = matches.value_of("input")
+        .expect("Could not get input file");
+
+    let text = strip_comments_and_lower(&std::fs::read(schema)?);
+    let syntax = match parse(&text) {
+        Ok(s) => s,
+        Err(e) => {
+            eprint!("{}", render_error(&text, &e));
+            std::process::exit(1);
+        },
+    };
+    let name = match matches.value_of("name") {
+        Some(n) => n,
+        None => syntax.0.first().ok_or("No schema in file")?.id.0,
+    };
+    let schema = DynSchema::new(&syntax, name)
+        .ok_or_else(|| format!("No schema named {}", name))?;
+
+    let data = StepFile::read(input)?;
+    let flat = StepFile::strip_flatten(&data);
+    if matches.is_present("check") {
+        let report = validate(&schema, &records(&flat));
+        for d in &report.diagnostics {
+            println!("{}", d);
+        }
+        eprintln!("Checked {} instances, {} with problems",
+                  report.checked, report.diagnostics.len());
+        return Ok(());
+    }
+    for e in schema.parse(&flat) {
+        match e {
+            Ok(e) => {
+                println!("#{} {}", e.id, e.parts.join(" "));
+                for a in &e.attributes {
+                    println!("    {}.{} = {:?}", a.entity, a.name, a.value);
+                }
+            },
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    Ok(())
+}
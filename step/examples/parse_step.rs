@@ -6,6 +6,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = App::new("parse_step")
         .author("Matt Keeter <matt@formlabs.com>")
         .about("Tests STEP parsing")
+        .arg(Arg::with_name("dump")
+            .long("dump")
+            .help("prints this entity and the entities which it refers to")
+            .takes_value(true))
+        .arg(Arg::with_name("depth")
+            .long("depth")
+            .help("how many levels of references to print with --dump")
+            .takes_value(true)
+            .default_value("3"))
         .arg(Arg::with_name("input")
             .takes_value(true)
             .required(true))
@@ -15,14 +24,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let start = SystemTime::now();
 
-    let data = std::fs::read(input)?;
-    let flat = StepFile::strip_flatten(&data);
-    let entities = StepFile::parse(&flat);
-    println!("Got {} entities", entities.0.len());
+    // Safety: this is a short-lived command-line tool, so we assume that the
+    // input file isn't being modified while it runs
+    let flat = unsafe { StepFile::open_mmap(input)? };
+    let entities = flat.parse();
+    println!("Got {} entities", entities.iter_by_id().count());
 
     let end = SystemTime::now();
     let since_the_epoch = end.duration_since(start).expect("Time went backwards");
     println!("time {:?}", since_the_epoch);
+
+    if let Some(id) = matches.value_of("dump") {
+        let id = id.trim_start_matches('#').parse()?;
+        let depth = matches.value_of("depth").unwrap().parse()?;
+        print!("{}", entities.dump(id, depth));
+    }
     Ok(())
 }
 
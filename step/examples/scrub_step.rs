@@ -0,0 +1,46 @@
+use clap::{Arg, App};
+use step::{scrub::{scrub, ScrubOptions}, step_file::StepFile};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = App::new("scrub_step")
+        .author("Matt Keeter <matt@formlabs.com>")
+        .about("Removes names and metadata from a STEP file, for sharing in bug reports")
+        .arg(Arg::with_name("output")
+            .short("o")
+            .long("out")
+            .help("STEP file to write (defaults to standard output)")
+            .takes_value(true))
+        .arg(Arg::with_name("keep-metadata")
+            .long("keep-metadata")
+            .help("keeps people, dates, documents, and properties (with their text blanked)"))
+        .arg(Arg::with_name("jitter")
+            .long("jitter")
+            .help("moves each point by up to this distance along each axis")
+            .takes_value(true))
+        .arg(Arg::with_name("seed")
+            .long("seed")
+            .help("seed for --jitter")
+            .takes_value(true)
+            .default_value("0"))
+        .arg(Arg::with_name("input")
+            .takes_value(true)
+            .required(true))
+        .get_matches();
+    let input = matches.value_of("input")
+        .expect("Could not get input file");
+
+    let options = ScrubOptions {
+        keep_metadata: matches.is_present("keep-metadata"),
+        jitter: matches.value_of("jitter").map(|j| j.parse()).transpose()?
+            .unwrap_or(0.0),
+        seed: matches.value_of("seed").unwrap().parse()?,
+    };
+    let data = StepFile::read(input)?;
+    let out = scrub(&StepFile::strip_flatten(&data), &options);
+    if let Some(path) = matches.value_of("output") {
+        std::fs::write(path, out)?;
+    } else {
+        print!("{}", out);
+    }
+    Ok(())
+}
@@ -3,7 +3,7 @@ use step::step_file::StepFile;
 
 pub fn to_dot(s: &StepFile) -> String {
     let mut out = "digraph {\n".to_owned();
-    for (i, e) in s.0.iter().enumerate() {
+    for (i, e) in s.iter_by_id() {
         let d = format!("{:?}", e);
         let name = d.split("(").next().unwrap();
 
@@ -33,7 +33,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .expect("Could not get input file");
 
     let start = std::time::SystemTime::now();
-    let data = std::fs::read(input)?;
+    let data = StepFile::read(input)?;
     let flat = StepFile::strip_flatten(&data);
     let entities = StepFile::parse(&flat);
     let end = std::time::SystemTime::now();
@@ -0,0 +1,33 @@
+#!/bin/sh
+# Regenerates step/src/ap214.rs from the long-form AP214 schema, which isn't
+# redistributable and so isn't checked in (see "Code generation" in the
+# README for where to get it).
+#
+# Usage: step/gen_ap214.sh path/to/APs/10303-214e3-aim-long.exp
+#
+# The output is compared against AP214_SHA256, the hash of the checked-in
+# file.  A mismatch means that the schema or the generator has changed:
+# review the diff, then update the hash.
+set -eu
+
+AP214_SHA256=f788e32178d19c773817b7806e7b6ab6fe4ab15b01b84ca71c67962661c5ec93
+
+if [ $# -ne 1 ]; then
+    echo "usage: $0 path/to/APs/10303-214e3-aim-long.exp" >&2
+    exit 1
+fi
+
+root=$(cd "$(dirname "$0")/.." && pwd)
+out="$root/step/src/ap214.rs"
+cargo run --release --manifest-path "$root/Cargo.toml" -p express \
+    --example gen_exp -- "$1" "$out"
+
+if command -v sha256sum > /dev/null; then
+    hash=$(sha256sum "$out" | cut -d' ' -f1)
+else
+    hash=$(shasum -a 256 "$out" | cut -d' ' -f1)
+fi
+if [ "$hash" != "$AP214_SHA256" ]; then
+    echo "$out has SHA-256 $hash, expected $AP214_SHA256" >&2
+    exit 1
+fi
@@ -1,9 +1,11 @@
 // Autogenerated file, do not hand-edit!
+// Regenerate step/src/ap214.rs with step/gen_ap214.sh (see the README).
 use crate::{
     id::{Id, HasId},
-    parse::{IResult, Logical, Derived, Parse, ParseFromChunks, nom_alt_err,
+    parse::{IResult, Logical, Derived, Value, Parse, ParseFromChunks, nom_alt_err,
             parse_enum_tag, param_from_chunks, parse_complex_mapping},
     step_file::FromEntity,
+    check::ParamKind,
 };
 use nom::{
     branch::{alt},
@@ -75,7 +77,7 @@ impl<'a> HasId for AcosFunction_<'a> {
 #[derive(Debug)]
 pub struct Action_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -93,7 +95,7 @@ impl<'a> ParseFromChunks<'a> for Action_<'a> {
         let mut i = 0;
         let (s, _) = tag("ACTION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -141,7 +143,7 @@ impl<'a> HasId for ActionAssignment_<'a> {
 #[derive(Debug)]
 pub struct ActionDirective_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub analysis: Text<'a>,
     pub comment: Text<'a>,
     pub requests: Vec<VersionedActionRequest<'a>>,
@@ -161,7 +163,7 @@ impl<'a> ParseFromChunks<'a> for ActionDirective_<'a> {
         let mut i = 0;
         let (s, _) = tag("ACTION_DIRECTIVE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, analysis) = param_from_chunks::<Text<'a>>(false, s, &mut i, strs)?;
         let (s, comment) = param_from_chunks::<Text<'a>>(false, s, &mut i, strs)?;
         let (s, requests) = param_from_chunks::<Vec<VersionedActionRequest<'a>>>(true, s, &mut i, strs)?;
@@ -190,7 +192,7 @@ pub type ActionItem<'a> = Id<ActionItem_<'a>>;
 #[derive(Debug)]
 pub struct ActionMethod_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub consequence: Text<'a>,
     pub purpose: Text<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -209,7 +211,7 @@ impl<'a> ParseFromChunks<'a> for ActionMethod_<'a> {
         let mut i = 0;
         let (s, _) = tag("ACTION_METHOD(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, consequence) = param_from_chunks::<Text<'a>>(false, s, &mut i, strs)?;
         let (s, purpose) = param_from_chunks::<Text<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -231,7 +233,7 @@ impl<'a> HasId for ActionMethod_<'a> {
 #[derive(Debug)]
 pub struct ActionMethodRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_method: ActionMethod<'a>,
     pub related_method: ActionMethod<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -250,7 +252,7 @@ impl<'a> ParseFromChunks<'a> for ActionMethodRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("ACTION_METHOD_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_method) = param_from_chunks::<ActionMethod<'a>>(false, s, &mut i, strs)?;
         let (s, related_method) = param_from_chunks::<ActionMethod<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -350,7 +352,7 @@ impl<'a> HasId for ActionPropertyRepresentation_<'a> {
 #[derive(Debug)]
 pub struct ActionRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_action: Action<'a>,
     pub related_action: Action<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -369,7 +371,7 @@ impl<'a> ParseFromChunks<'a> for ActionRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("ACTION_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_action) = param_from_chunks::<Action<'a>>(false, s, &mut i, strs)?;
         let (s, related_action) = param_from_chunks::<Action<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -490,7 +492,7 @@ impl<'a> HasId for ActionRequestStatus_<'a> {
 #[derive(Debug)]
 pub struct ActionResource_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub usage: Vec<SupportedItem<'a>>,
     pub kind: ActionResourceType<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -509,7 +511,7 @@ impl<'a> ParseFromChunks<'a> for ActionResource_<'a> {
         let mut i = 0;
         let (s, _) = tag("ACTION_RESOURCE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, usage) = param_from_chunks::<Vec<SupportedItem<'a>>>(false, s, &mut i, strs)?;
         let (s, kind) = param_from_chunks::<ActionResourceType<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -633,18 +635,18 @@ impl<'a> HasId for ActionStatus_<'a> {
 }
 #[derive(Debug)]
 pub struct Address_<'a> { // entity
-    pub internal_location: Option<Label<'a>>,
-    pub street_number: Option<Label<'a>>,
-    pub street: Option<Label<'a>>,
-    pub postal_box: Option<Label<'a>>,
-    pub town: Option<Label<'a>>,
-    pub region: Option<Label<'a>>,
-    pub postal_code: Option<Label<'a>>,
-    pub country: Option<Label<'a>>,
-    pub facsimile_number: Option<Label<'a>>,
-    pub telephone_number: Option<Label<'a>>,
-    pub electronic_mail_address: Option<Label<'a>>,
-    pub telex_number: Option<Label<'a>>,
+    pub internal_location: Value<Label<'a>>,
+    pub street_number: Value<Label<'a>>,
+    pub street: Value<Label<'a>>,
+    pub postal_box: Value<Label<'a>>,
+    pub town: Value<Label<'a>>,
+    pub region: Value<Label<'a>>,
+    pub postal_code: Value<Label<'a>>,
+    pub country: Value<Label<'a>>,
+    pub facsimile_number: Value<Label<'a>>,
+    pub telephone_number: Value<Label<'a>>,
+    pub electronic_mail_address: Value<Label<'a>>,
+    pub telex_number: Value<Label<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Address<'a> = Id<Address_<'a>>;
@@ -660,18 +662,18 @@ impl<'a> ParseFromChunks<'a> for Address_<'a> {
     fn parse_chunks(strs: &[&'a str]) -> IResult<'a, Self> {
         let mut i = 0;
         let (s, _) = tag("ADDRESS(")(strs[0])?;
-        let (s, internal_location) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, street_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, street) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, postal_box) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, town) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, region) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, postal_code) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, country) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, facsimile_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, telephone_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, electronic_mail_address) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, telex_number) = param_from_chunks::<Option<Label<'a>>>(true, s, &mut i, strs)?;
+        let (s, internal_location) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, street_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, street) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, postal_box) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, town) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, region) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, postal_code) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, country) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, facsimile_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, telephone_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, electronic_mail_address) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, telex_number) = param_from_chunks::<Value<Label<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             internal_location,
             street_number,
@@ -787,7 +789,9 @@ pub enum AheadOrBehind<'a> { // enum
     Ahead,
     Exact,
     Behind,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for AheadOrBehind<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -797,7 +801,7 @@ impl<'a> Parse<'a> for AheadOrBehind<'a> {
             "AHEAD" => Ahead,
             "EXACT" => Exact,
             "BEHIND" => Behind,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -809,7 +813,7 @@ impl<'a> HasId for AheadOrBehind<'a> {
 #[derive(Debug)]
 pub struct AlternateProductRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub definition: Option<Text<'a>>,
+    pub definition: Value<Text<'a>>,
     pub alternate: Product<'a>,
     pub base: Product<'a>,
     pub basis: Text<'a>,
@@ -829,7 +833,7 @@ impl<'a> ParseFromChunks<'a> for AlternateProductRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("ALTERNATE_PRODUCT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, definition) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, definition) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, alternate) = param_from_chunks::<Product<'a>>(false, s, &mut i, strs)?;
         let (s, base) = param_from_chunks::<Product<'a>>(false, s, &mut i, strs)?;
         let (s, basis) = param_from_chunks::<Text<'a>>(true, s, &mut i, strs)?;
@@ -958,7 +962,9 @@ pub enum AngleRelator<'a> { // enum
     Equal,
     Large,
     Small,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for AngleRelator<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -968,7 +974,7 @@ impl<'a> Parse<'a> for AngleRelator<'a> {
             "EQUAL" => Equal,
             "LARGE" => Large,
             "SMALL" => Small,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -1013,7 +1019,7 @@ impl<'a> HasId for AngularDimension_<'a> {
 #[derive(Debug)]
 pub struct AngularLocation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     pub angle_selection: AngleRelator<'a>,
@@ -1033,7 +1039,7 @@ impl<'a> ParseFromChunks<'a> for AngularLocation_<'a> {
         let mut i = 0;
         let (s, _) = tag("ANGULAR_LOCATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, angle_selection) = param_from_chunks::<AngleRelator<'a>>(true, s, &mut i, strs)?;
@@ -1373,7 +1379,7 @@ pub struct AnnotationPlane_<'a> { // entity
     pub representation_item__name: Label<'a>,
     pub styles: Vec<PresentationStyleAssignment<'a>>,
     pub item: RepresentationItem<'a>,
-    pub elements: Option<Vec<AnnotationPlaneElement<'a>>>,
+    pub elements: Value<Vec<AnnotationPlaneElement<'a>>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type AnnotationPlane<'a> = Id<AnnotationPlane_<'a>>;
@@ -1393,7 +1399,7 @@ impl<'a> ParseFromChunks<'a> for AnnotationPlane_<'a> {
         let (s, representation_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         let (s, styles) = param_from_chunks::<Vec<PresentationStyleAssignment<'a>>>(false, s, &mut i, strs)?;
         let (s, item) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
-        let (s, elements) = param_from_chunks::<Option<Vec<AnnotationPlaneElement<'a>>>>(true, s, &mut i, strs)?;
+        let (s, elements) = param_from_chunks::<Value<Vec<AnnotationPlaneElement<'a>>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             representation_item__name,
             styles,
@@ -1651,7 +1657,7 @@ pub type AnnotationTextOccurrenceItem<'a> = Id<AnnotationTextOccurrenceItem_<'a>
 #[derive(Debug)]
 pub struct Apex_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -1670,7 +1676,7 @@ impl<'a> ParseFromChunks<'a> for Apex_<'a> {
         let mut i = 0;
         let (s, _) = tag("APEX(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -1754,7 +1760,7 @@ impl<'a> HasId for ApplicationContextElement_<'a> {
 #[derive(Debug)]
 pub struct ApplicationContextRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_context: ApplicationContext<'a>,
     pub related_context: ApplicationContext<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -1773,7 +1779,7 @@ impl<'a> ParseFromChunks<'a> for ApplicationContextRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("APPLICATION_CONTEXT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_context) = param_from_chunks::<ApplicationContext<'a>>(false, s, &mut i, strs)?;
         let (s, related_context) = param_from_chunks::<ApplicationContext<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -1935,7 +1941,7 @@ impl<'a> HasId for AppliedApprovalAssignment_<'a> {
 #[derive(Debug)]
 pub struct AppliedArea_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -1954,7 +1960,7 @@ impl<'a> ParseFromChunks<'a> for AppliedArea_<'a> {
         let mut i = 0;
         let (s, _) = tag("APPLIED_AREA(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -2820,7 +2826,7 @@ impl<'a> HasId for ApprovalPersonOrganization_<'a> {
 #[derive(Debug)]
 pub struct ApprovalRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_approval: Approval<'a>,
     pub related_approval: Approval<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -2839,7 +2845,7 @@ impl<'a> ParseFromChunks<'a> for ApprovalRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("APPROVAL_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_approval) = param_from_chunks::<Approval<'a>>(false, s, &mut i, strs)?;
         let (s, related_approval) = param_from_chunks::<Approval<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -2920,7 +2926,9 @@ impl<'a> HasId for ApprovalStatus_<'a> {
 pub enum ApproximationMethod<'a> { // enum
     ChordalDeviation,
     ChordalLength,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for ApproximationMethod<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -2929,7 +2937,7 @@ impl<'a> Parse<'a> for ApproximationMethod<'a> {
         let e = match tag {
             "CHORDAL_DEVIATION" => ChordalDeviation,
             "CHORDAL_LENGTH" => ChordalLength,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -3176,10 +3184,10 @@ impl<'a> HasId for AsinFunction_<'a> {
 pub struct AssemblyComponentUsage_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
-    pub reference_designator: Option<Identifier<'a>>,
+    pub reference_designator: Value<Identifier<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type AssemblyComponentUsage<'a> = Id<AssemblyComponentUsage_<'a>>;
@@ -3197,10 +3205,10 @@ impl<'a> ParseFromChunks<'a> for AssemblyComponentUsage_<'a> {
         let (s, _) = tag("ASSEMBLY_COMPONENT_USAGE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
-        let (s, reference_designator) = param_from_chunks::<Option<Identifier<'a>>>(true, s, &mut i, strs)?;
+        let (s, reference_designator) = param_from_chunks::<Value<Identifier<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -3224,7 +3232,7 @@ impl<'a> HasId for AssemblyComponentUsage_<'a> {
 #[derive(Debug)]
 pub struct AssemblyComponentUsageSubstitute_<'a> { // entity
     pub name: Label<'a>,
-    pub definition: Option<Text<'a>>,
+    pub definition: Value<Text<'a>>,
     pub base: AssemblyComponentUsage<'a>,
     pub substitute: AssemblyComponentUsage<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -3243,7 +3251,7 @@ impl<'a> ParseFromChunks<'a> for AssemblyComponentUsageSubstitute_<'a> {
         let mut i = 0;
         let (s, _) = tag("ASSEMBLY_COMPONENT_USAGE_SUBSTITUTE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, definition) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, definition) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, base) = param_from_chunks::<AssemblyComponentUsage<'a>>(false, s, &mut i, strs)?;
         let (s, substitute) = param_from_chunks::<AssemblyComponentUsage<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -3436,7 +3444,7 @@ impl<'a> HasId for AttributeValueAssignment_<'a> {
 #[derive(Debug)]
 pub struct AttributeValueRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type AttributeValueRole<'a> = Id<AttributeValueRole_<'a>>;
@@ -3453,7 +3461,7 @@ impl<'a> ParseFromChunks<'a> for AttributeValueRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("ATTRIBUTE_VALUE_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -3470,7 +3478,7 @@ impl<'a> HasId for AttributeValueRole_<'a> {
 pub struct Axis1Placement_<'a> { // entity
     pub name: Label<'a>,
     pub location: CartesianPoint<'a>,
-    pub axis: Option<Direction<'a>>,
+    pub axis: Value<Direction<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Axis1Placement<'a> = Id<Axis1Placement_<'a>>;
@@ -3488,7 +3496,7 @@ impl<'a> ParseFromChunks<'a> for Axis1Placement_<'a> {
         let (s, _) = tag("AXIS1_PLACEMENT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         let (s, location) = param_from_chunks::<CartesianPoint<'a>>(false, s, &mut i, strs)?;
-        let (s, axis) = param_from_chunks::<Option<Direction<'a>>>(true, s, &mut i, strs)?;
+        let (s, axis) = param_from_chunks::<Value<Direction<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             location,
@@ -3511,7 +3519,7 @@ pub type Axis2Placement<'a> = Id<Axis2Placement_<'a>>;
 pub struct Axis2Placement2d_<'a> { // entity
     pub name: Label<'a>,
     pub location: CartesianPoint<'a>,
-    pub ref_direction: Option<Direction<'a>>,
+    pub ref_direction: Value<Direction<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Axis2Placement2d<'a> = Id<Axis2Placement2d_<'a>>;
@@ -3529,7 +3537,7 @@ impl<'a> ParseFromChunks<'a> for Axis2Placement2d_<'a> {
         let (s, _) = tag("AXIS2_PLACEMENT_2D(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         let (s, location) = param_from_chunks::<CartesianPoint<'a>>(false, s, &mut i, strs)?;
-        let (s, ref_direction) = param_from_chunks::<Option<Direction<'a>>>(true, s, &mut i, strs)?;
+        let (s, ref_direction) = param_from_chunks::<Value<Direction<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             location,
@@ -3548,8 +3556,8 @@ impl<'a> HasId for Axis2Placement2d_<'a> {
 pub struct Axis2Placement3d_<'a> { // entity
     pub name: Label<'a>,
     pub location: CartesianPoint<'a>,
-    pub axis: Option<Direction<'a>>,
-    pub ref_direction: Option<Direction<'a>>,
+    pub axis: Value<Direction<'a>>,
+    pub ref_direction: Value<Direction<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Axis2Placement3d<'a> = Id<Axis2Placement3d_<'a>>;
@@ -3567,8 +3575,8 @@ impl<'a> ParseFromChunks<'a> for Axis2Placement3d_<'a> {
         let (s, _) = tag("AXIS2_PLACEMENT_3D(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         let (s, location) = param_from_chunks::<CartesianPoint<'a>>(false, s, &mut i, strs)?;
-        let (s, axis) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
-        let (s, ref_direction) = param_from_chunks::<Option<Direction<'a>>>(true, s, &mut i, strs)?;
+        let (s, axis) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, ref_direction) = param_from_chunks::<Value<Direction<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             location,
@@ -3642,7 +3650,9 @@ pub enum BSplineCurveForm<'a> { // enum
     ParabolicArc,
     HyperbolicArc,
     Unspecified,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for BSplineCurveForm<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -3655,7 +3665,7 @@ impl<'a> Parse<'a> for BSplineCurveForm<'a> {
             "PARABOLIC_ARC" => ParabolicArc,
             "HYPERBOLIC_ARC" => HyperbolicArc,
             "UNSPECIFIED" => Unspecified,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -3795,7 +3805,9 @@ pub enum BSplineSurfaceForm<'a> { // enum
     QuadricSurf,
     SurfOfLinearExtrusion,
     Unspecified,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for BSplineSurfaceForm<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -3813,7 +3825,7 @@ impl<'a> Parse<'a> for BSplineSurfaceForm<'a> {
             "QUADRIC_SURF" => QuadricSurf,
             "SURF_OF_LINEAR_EXTRUSION" => SurfOfLinearExtrusion,
             "UNSPECIFIED" => Unspecified,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -3931,7 +3943,7 @@ impl<'a> HasId for BackgroundColour_<'a> {
 #[derive(Debug)]
 pub struct BarringHole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type BarringHole<'a> = Id<BarringHole_<'a>>;
@@ -3948,7 +3960,7 @@ impl<'a> ParseFromChunks<'a> for BarringHole_<'a> {
         let mut i = 0;
         let (s, _) = tag("BARRING_HOLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -3964,7 +3976,7 @@ impl<'a> HasId for BarringHole_<'a> {
 #[derive(Debug)]
 pub struct Bead_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Bead<'a> = Id<Bead_<'a>>;
@@ -3981,7 +3993,7 @@ impl<'a> ParseFromChunks<'a> for Bead_<'a> {
         let mut i = 0;
         let (s, _) = tag("BEAD(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -3997,7 +4009,7 @@ impl<'a> HasId for Bead_<'a> {
 #[derive(Debug)]
 pub struct BeadEnd_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -4016,7 +4028,7 @@ impl<'a> ParseFromChunks<'a> for BeadEnd_<'a> {
         let mut i = 0;
         let (s, _) = tag("BEAD_END(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -4388,7 +4400,9 @@ pub enum BooleanOperator<'a> { // enum
     Union,
     Intersection,
     Difference,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for BooleanOperator<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -4398,7 +4412,7 @@ impl<'a> Parse<'a> for BooleanOperator<'a> {
             "UNION" => Union,
             "INTERSECTION" => Intersection,
             "DIFFERENCE" => Difference,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -4475,7 +4489,7 @@ impl<'a> HasId for BooleanVariable_<'a> {
 #[derive(Debug)]
 pub struct Boss_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Boss<'a> = Id<Boss_<'a>>;
@@ -4492,7 +4506,7 @@ impl<'a> ParseFromChunks<'a> for Boss_<'a> {
         let mut i = 0;
         let (s, _) = tag("BOSS(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -4508,7 +4522,7 @@ impl<'a> HasId for Boss_<'a> {
 #[derive(Debug)]
 pub struct BossTop_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -4527,7 +4541,7 @@ impl<'a> ParseFromChunks<'a> for BossTop_<'a> {
         let mut i = 0;
         let (s, _) = tag("BOSS_TOP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -5290,11 +5304,11 @@ impl<'a> HasId for CartesianPoint_<'a> {
 pub struct CartesianTransformationOperator_<'a> { // entity
     pub representation_item__name: Label<'a>,
     pub functionally_defined_transformation__name: Label<'a>,
-    pub description: Option<Text<'a>>,
-    pub axis1: Option<Direction<'a>>,
-    pub axis2: Option<Direction<'a>>,
+    pub description: Value<Text<'a>>,
+    pub axis1: Value<Direction<'a>>,
+    pub axis2: Value<Direction<'a>>,
     pub local_origin: CartesianPoint<'a>,
-    pub scale: Option<f64>,
+    pub scale: Value<f64>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CartesianTransformationOperator<'a> = Id<CartesianTransformationOperator_<'a>>;
@@ -5314,11 +5328,11 @@ impl<'a> ParseFromChunks<'a> for CartesianTransformationOperator_<'a> {
         let (s, representation_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, functionally_defined_transformation__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
-        let (s, axis1) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
-        let (s, axis2) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, axis1) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, axis2) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
         let (s, local_origin) = param_from_chunks::<CartesianPoint<'a>>(false, s, &mut i, strs)?;
-        let (s, scale) = param_from_chunks::<Option<f64>>(true, s, &mut i, strs)?;
+        let (s, scale) = param_from_chunks::<Value<f64>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             representation_item__name,
             functionally_defined_transformation__name,
@@ -5346,11 +5360,11 @@ impl<'a> HasId for CartesianTransformationOperator_<'a> {
 pub struct CartesianTransformationOperator2d_<'a> { // entity
     pub representation_item__name: Label<'a>,
     pub functionally_defined_transformation__name: Label<'a>,
-    pub description: Option<Text<'a>>,
-    pub axis1: Option<Direction<'a>>,
-    pub axis2: Option<Direction<'a>>,
+    pub description: Value<Text<'a>>,
+    pub axis1: Value<Direction<'a>>,
+    pub axis2: Value<Direction<'a>>,
     pub local_origin: CartesianPoint<'a>,
-    pub scale: Option<f64>,
+    pub scale: Value<f64>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CartesianTransformationOperator2d<'a> = Id<CartesianTransformationOperator2d_<'a>>;
@@ -5370,11 +5384,11 @@ impl<'a> ParseFromChunks<'a> for CartesianTransformationOperator2d_<'a> {
         let (s, representation_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, functionally_defined_transformation__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
-        let (s, axis1) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
-        let (s, axis2) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, axis1) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, axis2) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
         let (s, local_origin) = param_from_chunks::<CartesianPoint<'a>>(false, s, &mut i, strs)?;
-        let (s, scale) = param_from_chunks::<Option<f64>>(true, s, &mut i, strs)?;
+        let (s, scale) = param_from_chunks::<Value<f64>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             representation_item__name,
             functionally_defined_transformation__name,
@@ -5402,12 +5416,12 @@ impl<'a> HasId for CartesianTransformationOperator2d_<'a> {
 pub struct CartesianTransformationOperator3d_<'a> { // entity
     pub representation_item__name: Label<'a>,
     pub functionally_defined_transformation__name: Label<'a>,
-    pub description: Option<Text<'a>>,
-    pub axis1: Option<Direction<'a>>,
-    pub axis2: Option<Direction<'a>>,
+    pub description: Value<Text<'a>>,
+    pub axis1: Value<Direction<'a>>,
+    pub axis2: Value<Direction<'a>>,
     pub local_origin: CartesianPoint<'a>,
-    pub scale: Option<f64>,
-    pub axis3: Option<Direction<'a>>,
+    pub scale: Value<f64>,
+    pub axis3: Value<Direction<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CartesianTransformationOperator3d<'a> = Id<CartesianTransformationOperator3d_<'a>>;
@@ -5427,12 +5441,12 @@ impl<'a> ParseFromChunks<'a> for CartesianTransformationOperator3d_<'a> {
         let (s, representation_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, functionally_defined_transformation__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
-        let (s, axis1) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
-        let (s, axis2) = param_from_chunks::<Option<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, axis1) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
+        let (s, axis2) = param_from_chunks::<Value<Direction<'a>>>(false, s, &mut i, strs)?;
         let (s, local_origin) = param_from_chunks::<CartesianPoint<'a>>(false, s, &mut i, strs)?;
-        let (s, scale) = param_from_chunks::<Option<f64>>(false, s, &mut i, strs)?;
-        let (s, axis3) = param_from_chunks::<Option<Direction<'a>>>(true, s, &mut i, strs)?;
+        let (s, scale) = param_from_chunks::<Value<f64>>(false, s, &mut i, strs)?;
+        let (s, axis3) = param_from_chunks::<Value<Direction<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             representation_item__name,
             functionally_defined_transformation__name,
@@ -5509,7 +5523,9 @@ impl<'a> HasId for CelsiusTemperatureMeasureWithUnit_<'a> {
 pub enum CentralOrParallel<'a> { // enum
     Central,
     Parallel,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for CentralOrParallel<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -5518,7 +5534,7 @@ impl<'a> Parse<'a> for CentralOrParallel<'a> {
         let e = match tag {
             "CENTRAL" => Central,
             "PARALLEL" => Parallel,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -5530,7 +5546,7 @@ impl<'a> HasId for CentralOrParallel<'a> {
 #[derive(Debug)]
 pub struct CentreOfSymmetry_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -5549,7 +5565,7 @@ impl<'a> ParseFromChunks<'a> for CentreOfSymmetry_<'a> {
         let mut i = 0;
         let (s, _) = tag("CENTRE_OF_SYMMETRY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -5670,7 +5686,7 @@ impl<'a> HasId for CertificationType_<'a> {
 #[derive(Debug)]
 pub struct Chamfer_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -5689,7 +5705,7 @@ impl<'a> ParseFromChunks<'a> for Chamfer_<'a> {
         let mut i = 0;
         let (s, _) = tag("CHAMFER(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -5711,7 +5727,7 @@ impl<'a> HasId for Chamfer_<'a> {
 #[derive(Debug)]
 pub struct ChamferOffset_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -5730,7 +5746,7 @@ impl<'a> ParseFromChunks<'a> for ChamferOffset_<'a> {
         let mut i = 0;
         let (s, _) = tag("CHAMFER_OFFSET(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -5835,9 +5851,9 @@ pub type CharacterizedActionDefinition<'a> = Id<CharacterizedActionDefinition_<'
 #[derive(Debug)]
 pub struct CharacterizedClass_<'a> { // entity
     pub characterized_object__name: Label<'a>,
-    pub characterized_object__description: Option<Text<'a>>,
+    pub characterized_object__description: Value<Text<'a>>,
     pub group__name: Label<'a>,
-    pub group__description: Option<Text<'a>>,
+    pub group__description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CharacterizedClass<'a> = Id<CharacterizedClass_<'a>>;
@@ -5856,11 +5872,11 @@ impl<'a> ParseFromChunks<'a> for CharacterizedClass_<'a> {
         #[allow(non_snake_case)]
         let (s, characterized_object__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, characterized_object__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, characterized_object__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, group__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, group__description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, group__description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             characterized_object__name,
             characterized_object__description,
@@ -5888,7 +5904,7 @@ pub type CharacterizedMaterialProperty<'a> = Id<CharacterizedMaterialProperty_<'
 #[derive(Debug)]
 pub struct CharacterizedObject_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CharacterizedObject<'a> = Id<CharacterizedObject_<'a>>;
@@ -5905,7 +5921,7 @@ impl<'a> ParseFromChunks<'a> for CharacterizedObject_<'a> {
         let mut i = 0;
         let (s, _) = tag("CHARACTERIZED_OBJECT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -5966,7 +5982,7 @@ impl<'a> HasId for Circle_<'a> {
 #[derive(Debug)]
 pub struct CircularClosedProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -5985,7 +6001,7 @@ impl<'a> ParseFromChunks<'a> for CircularClosedProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("CIRCULAR_CLOSED_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -6007,7 +6023,7 @@ impl<'a> HasId for CircularClosedProfile_<'a> {
 #[derive(Debug)]
 pub struct CircularPattern_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CircularPattern<'a> = Id<CircularPattern_<'a>>;
@@ -6024,7 +6040,7 @@ impl<'a> ParseFromChunks<'a> for CircularPattern_<'a> {
         let mut i = 0;
         let (s, _) = tag("CIRCULAR_PATTERN(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -6085,7 +6101,7 @@ impl<'a> HasId for CircularRunoutTolerance_<'a> {
 #[derive(Debug)]
 pub struct Class_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Class<'a> = Id<Class_<'a>>;
@@ -6102,7 +6118,7 @@ impl<'a> ParseFromChunks<'a> for Class_<'a> {
         let mut i = 0;
         let (s, _) = tag("CLASS(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -6118,7 +6134,7 @@ impl<'a> HasId for Class_<'a> {
 #[derive(Debug)]
 pub struct ClassSystem_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ClassSystem<'a> = Id<ClassSystem_<'a>>;
@@ -6135,7 +6151,7 @@ impl<'a> ParseFromChunks<'a> for ClassSystem_<'a> {
         let mut i = 0;
         let (s, _) = tag("CLASS_SYSTEM(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -6229,7 +6245,7 @@ pub type ClassificationItem<'a> = Id<ClassificationItem_<'a>>;
 #[derive(Debug)]
 pub struct ClassificationRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ClassificationRole<'a> = Id<ClassificationRole_<'a>>;
@@ -6246,7 +6262,7 @@ impl<'a> ParseFromChunks<'a> for ClassificationRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("CLASSIFICATION_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -6262,7 +6278,7 @@ impl<'a> HasId for ClassificationRole_<'a> {
 #[derive(Debug)]
 pub struct ClosedPathProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -6281,7 +6297,7 @@ impl<'a> ParseFromChunks<'a> for ClosedPathProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("CLOSED_PATH_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -6476,7 +6492,7 @@ impl<'a> HasId for ColourSpecification_<'a> {
 #[derive(Debug)]
 pub struct CommonDatum_<'a> { // entity
     pub shape_aspect__name: Label<'a>,
-    pub shape_aspect__description: Option<Text<'a>>,
+    pub shape_aspect__description: Value<Text<'a>>,
     pub shape_aspect__of_shape: ProductDefinitionShape<'a>,
     pub shape_aspect__product_definitional: Logical,
     pub identification: Identifier<'a>,
@@ -6498,7 +6514,7 @@ impl<'a> ParseFromChunks<'a> for CommonDatum_<'a> {
         #[allow(non_snake_case)]
         let (s, shape_aspect__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, shape_aspect__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, shape_aspect__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, shape_aspect__of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
@@ -6839,7 +6855,7 @@ impl<'a> HasId for CompositeCurveSegment_<'a> {
 #[derive(Debug)]
 pub struct CompositeHole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CompositeHole<'a> = Id<CompositeHole_<'a>>;
@@ -6856,7 +6872,7 @@ impl<'a> ParseFromChunks<'a> for CompositeHole_<'a> {
         let mut i = 0;
         let (s, _) = tag("COMPOSITE_HOLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -6872,7 +6888,7 @@ impl<'a> HasId for CompositeHole_<'a> {
 #[derive(Debug)]
 pub struct CompositeShapeAspect_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -6891,7 +6907,7 @@ impl<'a> ParseFromChunks<'a> for CompositeShapeAspect_<'a> {
         let mut i = 0;
         let (s, _) = tag("COMPOSITE_SHAPE_ASPECT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -7057,7 +7073,7 @@ impl<'a> HasId for CompositeTextWithExtent_<'a> {
 #[derive(Debug)]
 pub struct CompoundFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type CompoundFeature<'a> = Id<CompoundFeature_<'a>>;
@@ -7074,7 +7090,7 @@ impl<'a> ParseFromChunks<'a> for CompoundFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("COMPOUND_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -7257,7 +7273,7 @@ impl<'a> HasId for ConcentricityTolerance_<'a> {
 #[derive(Debug)]
 pub struct ConceptFeatureOperator_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ConceptFeatureOperator<'a> = Id<ConceptFeatureOperator_<'a>>;
@@ -7274,7 +7290,7 @@ impl<'a> ParseFromChunks<'a> for ConceptFeatureOperator_<'a> {
         let mut i = 0;
         let (s, _) = tag("CONCEPT_FEATURE_OPERATOR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -7290,7 +7306,7 @@ impl<'a> HasId for ConceptFeatureOperator_<'a> {
 #[derive(Debug)]
 pub struct ConceptFeatureRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_concept_feature: ProductConceptFeature<'a>,
     pub related_product_concept_feature: ProductConceptFeature<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -7309,7 +7325,7 @@ impl<'a> ParseFromChunks<'a> for ConceptFeatureRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("CONCEPT_FEATURE_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_concept_feature) = param_from_chunks::<ProductConceptFeature<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_concept_feature) = param_from_chunks::<ProductConceptFeature<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -7331,7 +7347,7 @@ impl<'a> HasId for ConceptFeatureRelationship_<'a> {
 #[derive(Debug)]
 pub struct ConceptFeatureRelationshipWithCondition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_concept_feature: ProductConceptFeature<'a>,
     pub related_product_concept_feature: ProductConceptFeature<'a>,
     pub conditional_operator: ConceptFeatureOperator<'a>,
@@ -7351,7 +7367,7 @@ impl<'a> ParseFromChunks<'a> for ConceptFeatureRelationshipWithCondition_<'a> {
         let mut i = 0;
         let (s, _) = tag("CONCEPT_FEATURE_RELATIONSHIP_WITH_CONDITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_concept_feature) = param_from_chunks::<ProductConceptFeature<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_concept_feature) = param_from_chunks::<ProductConceptFeature<'a>>(false, s, &mut i, strs)?;
         let (s, conditional_operator) = param_from_chunks::<ConceptFeatureOperator<'a>>(true, s, &mut i, strs)?;
@@ -7377,7 +7393,7 @@ impl<'a> HasId for ConceptFeatureRelationshipWithCondition_<'a> {
 pub struct ConditionalConceptFeature_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub condition: ConceptFeatureRelationshipWithCondition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -7396,7 +7412,7 @@ impl<'a> ParseFromChunks<'a> for ConditionalConceptFeature_<'a> {
         let (s, _) = tag("CONDITIONAL_CONCEPT_FEATURE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, condition) = param_from_chunks::<ConceptFeatureRelationshipWithCondition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -7418,9 +7434,9 @@ impl<'a> HasId for ConditionalConceptFeature_<'a> {
 pub struct ConfigurableItem_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub item_concept: ProductConcept<'a>,
-    pub purpose: Option<Label<'a>>,
+    pub purpose: Value<Label<'a>>,
     pub item_concept_feature: Vec<ProductConceptFeatureAssociation<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -7439,9 +7455,9 @@ impl<'a> ParseFromChunks<'a> for ConfigurableItem_<'a> {
         let (s, _) = tag("CONFIGURABLE_ITEM(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_concept) = param_from_chunks::<ProductConcept<'a>>(false, s, &mut i, strs)?;
-        let (s, purpose) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, purpose) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         let (s, item_concept_feature) = param_from_chunks::<Vec<ProductConceptFeatureAssociation<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -7611,9 +7627,9 @@ impl<'a> HasId for ConfigurationInterpolation_<'a> {
 pub struct ConfigurationItem_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub item_concept: ProductConcept<'a>,
-    pub purpose: Option<Label<'a>>,
+    pub purpose: Value<Label<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ConfigurationItem<'a> = Id<ConfigurationItem_<'a>>;
@@ -7631,9 +7647,9 @@ impl<'a> ParseFromChunks<'a> for ConfigurationItem_<'a> {
         let (s, _) = tag("CONFIGURATION_ITEM(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_concept) = param_from_chunks::<ProductConcept<'a>>(false, s, &mut i, strs)?;
-        let (s, purpose) = param_from_chunks::<Option<Label<'a>>>(true, s, &mut i, strs)?;
+        let (s, purpose) = param_from_chunks::<Value<Label<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -7947,7 +7963,7 @@ impl<'a> HasId for ConstructiveGeometryRepresentation_<'a> {
 #[derive(Debug)]
 pub struct ConstructiveGeometryRepresentationRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -7966,7 +7982,7 @@ impl<'a> ParseFromChunks<'a> for ConstructiveGeometryRepresentationRelationship_
         let mut i = 0;
         let (s, _) = tag("CONSTRUCTIVE_GEOMETRY_REPRESENTATION_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -8313,7 +8329,7 @@ impl<'a> HasId for ConversionBasedUnit_<'a> {
 #[derive(Debug)]
 pub struct CoordinatedUniversalTimeOffset_<'a> { // entity
     pub hour_offset: i64,
-    pub minute_offset: Option<i64>,
+    pub minute_offset: Value<i64>,
     pub sense: AheadOrBehind<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -8331,7 +8347,7 @@ impl<'a> ParseFromChunks<'a> for CoordinatedUniversalTimeOffset_<'a> {
         let mut i = 0;
         let (s, _) = tag("COORDINATED_UNIVERSAL_TIME_OFFSET(")(strs[0])?;
         let (s, hour_offset) = param_from_chunks::<i64>(false, s, &mut i, strs)?;
-        let (s, minute_offset) = param_from_chunks::<Option<i64>>(false, s, &mut i, strs)?;
+        let (s, minute_offset) = param_from_chunks::<Value<i64>>(false, s, &mut i, strs)?;
         let (s, sense) = param_from_chunks::<AheadOrBehind<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             hour_offset,
@@ -8826,7 +8842,7 @@ impl<'a> HasId for CurveToleranceParameter<'a> {
 #[derive(Debug)]
 pub struct CylindricalPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -8846,7 +8862,7 @@ impl<'a> ParseFromChunks<'a> for CylindricalPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("CYLINDRICAL_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -9270,7 +9286,7 @@ pub type DateTimeSelect<'a> = Id<DateTimeSelect_<'a>>;
 #[derive(Debug)]
 pub struct DatedEffectivity_<'a> { // entity
     pub id: Identifier<'a>,
-    pub effectivity_end_date: Option<DateTimeOrEventOccurrence<'a>>,
+    pub effectivity_end_date: Value<DateTimeOrEventOccurrence<'a>>,
     pub effectivity_start_date: DateTimeOrEventOccurrence<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -9288,7 +9304,7 @@ impl<'a> ParseFromChunks<'a> for DatedEffectivity_<'a> {
         let mut i = 0;
         let (s, _) = tag("DATED_EFFECTIVITY(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, effectivity_end_date) = param_from_chunks::<Option<DateTimeOrEventOccurrence<'a>>>(false, s, &mut i, strs)?;
+        let (s, effectivity_end_date) = param_from_chunks::<Value<DateTimeOrEventOccurrence<'a>>>(false, s, &mut i, strs)?;
         let (s, effectivity_start_date) = param_from_chunks::<DateTimeOrEventOccurrence<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -9307,7 +9323,7 @@ impl<'a> HasId for DatedEffectivity_<'a> {
 #[derive(Debug)]
 pub struct Datum_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     pub identification: Identifier<'a>,
@@ -9327,7 +9343,7 @@ impl<'a> ParseFromChunks<'a> for Datum_<'a> {
         let mut i = 0;
         let (s, _) = tag("DATUM(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(false, s, &mut i, strs)?;
         let (s, identification) = param_from_chunks::<Identifier<'a>>(true, s, &mut i, strs)?;
@@ -9352,7 +9368,7 @@ impl<'a> HasId for Datum_<'a> {
 #[derive(Debug)]
 pub struct DatumFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -9371,7 +9387,7 @@ impl<'a> ParseFromChunks<'a> for DatumFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("DATUM_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -9459,7 +9475,7 @@ impl<'a> HasId for DatumReference_<'a> {
 #[derive(Debug)]
 pub struct DatumTarget_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     pub target_id: Identifier<'a>,
@@ -9479,7 +9495,7 @@ impl<'a> ParseFromChunks<'a> for DatumTarget_<'a> {
         let mut i = 0;
         let (s, _) = tag("DATUM_TARGET(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(false, s, &mut i, strs)?;
         let (s, target_id) = param_from_chunks::<Identifier<'a>>(true, s, &mut i, strs)?;
@@ -9847,7 +9863,7 @@ pub type DerivedPropertySelect<'a> = Id<DerivedPropertySelect_<'a>>;
 #[derive(Debug)]
 pub struct DerivedShapeAspect_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -9866,7 +9882,7 @@ impl<'a> ParseFromChunks<'a> for DerivedShapeAspect_<'a> {
         let mut i = 0;
         let (s, _) = tag("DERIVED_SHAPE_ASPECT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -10335,7 +10351,9 @@ impl<'a> HasId for DimensionCurveTerminator_<'a> {
 pub enum DimensionExtentUsage<'a> { // enum
     Origin,
     Target,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for DimensionExtentUsage<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -10344,7 +10362,7 @@ impl<'a> Parse<'a> for DimensionExtentUsage<'a> {
         let e = match tag {
             "ORIGIN" => Origin,
             "TARGET" => Target,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -10579,7 +10597,7 @@ impl<'a> HasId for DimensionalExponents_<'a> {
 #[derive(Debug)]
 pub struct DimensionalLocation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -10598,7 +10616,7 @@ impl<'a> ParseFromChunks<'a> for DimensionalLocation_<'a> {
         let mut i = 0;
         let (s, _) = tag("DIMENSIONAL_LOCATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -10620,7 +10638,7 @@ impl<'a> HasId for DimensionalLocation_<'a> {
 #[derive(Debug)]
 pub struct DimensionalLocationWithPath_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     pub path: ShapeAspect<'a>,
@@ -10640,7 +10658,7 @@ impl<'a> ParseFromChunks<'a> for DimensionalLocationWithPath_<'a> {
         let mut i = 0;
         let (s, _) = tag("DIMENSIONAL_LOCATION_WITH_PATH(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, path) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
@@ -10735,7 +10753,7 @@ impl<'a> HasId for DimensionalSizeWithPath_<'a> {
 #[derive(Debug)]
 pub struct DirectedAction_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     pub directive: ActionDirective<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -10754,7 +10772,7 @@ impl<'a> ParseFromChunks<'a> for DirectedAction_<'a> {
         let mut i = 0;
         let (s, _) = tag("DIRECTED_ACTION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(false, s, &mut i, strs)?;
         let (s, directive) = param_from_chunks::<ActionDirective<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -10776,7 +10794,7 @@ impl<'a> HasId for DirectedAction_<'a> {
 #[derive(Debug)]
 pub struct DirectedAngle_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -10795,7 +10813,7 @@ impl<'a> ParseFromChunks<'a> for DirectedAngle_<'a> {
         let mut i = 0;
         let (s, _) = tag("DIRECTED_ANGLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -10817,7 +10835,7 @@ impl<'a> HasId for DirectedAngle_<'a> {
 #[derive(Debug)]
 pub struct DirectedDimensionalLocation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -10836,7 +10854,7 @@ impl<'a> ParseFromChunks<'a> for DirectedDimensionalLocation_<'a> {
         let mut i = 0;
         let (s, _) = tag("DIRECTED_DIMENSIONAL_LOCATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -10981,7 +10999,7 @@ impl<'a> HasId for DivExpression_<'a> {
 pub struct Document_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub kind: DocumentType<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -11000,7 +11018,7 @@ impl<'a> ParseFromChunks<'a> for Document_<'a> {
         let (s, _) = tag("DOCUMENT(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, kind) = param_from_chunks::<DocumentType<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -11023,10 +11041,10 @@ impl<'a> HasId for Document_<'a> {
 pub struct DocumentFile_<'a> { // entity
     pub id: Identifier<'a>,
     pub document__name: Label<'a>,
-    pub document__description: Option<Text<'a>>,
+    pub document__description: Value<Text<'a>>,
     pub kind: DocumentType<'a>,
     pub characterized_object__name: Label<'a>,
-    pub characterized_object__description: Option<Text<'a>>,
+    pub characterized_object__description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type DocumentFile<'a> = Id<DocumentFile_<'a>>;
@@ -11046,12 +11064,12 @@ impl<'a> ParseFromChunks<'a> for DocumentFile_<'a> {
         #[allow(non_snake_case)]
         let (s, document__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, document__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, document__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, kind) = param_from_chunks::<DocumentType<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, characterized_object__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, characterized_object__description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, characterized_object__description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             document__name,
@@ -11075,7 +11093,7 @@ impl<'a> HasId for DocumentFile_<'a> {
 #[derive(Debug)]
 pub struct DocumentProductAssociation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_document: Document<'a>,
     pub related_product: ProductOrFormationOrDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -11094,7 +11112,7 @@ impl<'a> ParseFromChunks<'a> for DocumentProductAssociation_<'a> {
         let mut i = 0;
         let (s, _) = tag("DOCUMENT_PRODUCT_ASSOCIATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_document) = param_from_chunks::<Document<'a>>(false, s, &mut i, strs)?;
         let (s, related_product) = param_from_chunks::<ProductOrFormationOrDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -11116,7 +11134,7 @@ impl<'a> HasId for DocumentProductAssociation_<'a> {
 #[derive(Debug)]
 pub struct DocumentProductEquivalence_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_document: Document<'a>,
     pub related_product: ProductOrFormationOrDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -11135,7 +11153,7 @@ impl<'a> ParseFromChunks<'a> for DocumentProductEquivalence_<'a> {
         let mut i = 0;
         let (s, _) = tag("DOCUMENT_PRODUCT_EQUIVALENCE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_document) = param_from_chunks::<Document<'a>>(false, s, &mut i, strs)?;
         let (s, related_product) = param_from_chunks::<ProductOrFormationOrDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -11194,7 +11212,7 @@ pub type DocumentReferenceItem<'a> = Id<DocumentReferenceItem_<'a>>;
 #[derive(Debug)]
 pub struct DocumentRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_document: Document<'a>,
     pub related_document: Document<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -11213,7 +11231,7 @@ impl<'a> ParseFromChunks<'a> for DocumentRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("DOCUMENT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_document) = param_from_chunks::<Document<'a>>(false, s, &mut i, strs)?;
         let (s, related_document) = param_from_chunks::<Document<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -11367,7 +11385,7 @@ impl<'a> HasId for DocumentUsageConstraintAssignment_<'a> {
 #[derive(Debug)]
 pub struct DocumentUsageRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type DocumentUsageRole<'a> = Id<DocumentUsageRole_<'a>>;
@@ -11384,7 +11402,7 @@ impl<'a> ParseFromChunks<'a> for DocumentUsageRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("DOCUMENT_USAGE_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -11585,7 +11603,7 @@ impl<'a> HasId for DraughtingModel_<'a> {
 #[derive(Debug)]
 pub struct DraughtingModelItemAssociation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: RepresentedDefinition<'a>,
     pub used_representation: Representation<'a>,
     pub identified_item: RepresentationItem<'a>,
@@ -11605,7 +11623,7 @@ impl<'a> ParseFromChunks<'a> for DraughtingModelItemAssociation_<'a> {
         let mut i = 0;
         let (s, _) = tag("DRAUGHTING_MODEL_ITEM_ASSOCIATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<RepresentedDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, used_representation) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, identified_item) = param_from_chunks::<RepresentationItem<'a>>(true, s, &mut i, strs)?;
@@ -11926,7 +11944,7 @@ pub type DraughtingTitledItem<'a> = Id<DraughtingTitledItem_<'a>>;
 #[derive(Debug)]
 pub struct DrawingDefinition_<'a> { // entity
     pub drawing_number: Identifier<'a>,
-    pub drawing_type: Option<Label<'a>>,
+    pub drawing_type: Value<Label<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type DrawingDefinition<'a> = Id<DrawingDefinition_<'a>>;
@@ -11943,7 +11961,7 @@ impl<'a> ParseFromChunks<'a> for DrawingDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("DRAWING_DEFINITION(")(strs[0])?;
         let (s, drawing_number) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, drawing_type) = param_from_chunks::<Option<Label<'a>>>(true, s, &mut i, strs)?;
+        let (s, drawing_type) = param_from_chunks::<Value<Label<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             drawing_number,
             drawing_type,
@@ -11960,7 +11978,7 @@ impl<'a> HasId for DrawingDefinition_<'a> {
 pub struct DrawingRevision_<'a> { // entity
     pub revision_identifier: Identifier<'a>,
     pub drawing_identifier: DrawingDefinition<'a>,
-    pub intended_scale: Option<Text<'a>>,
+    pub intended_scale: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type DrawingRevision<'a> = Id<DrawingRevision_<'a>>;
@@ -11978,7 +11996,7 @@ impl<'a> ParseFromChunks<'a> for DrawingRevision_<'a> {
         let (s, _) = tag("DRAWING_REVISION(")(strs[0])?;
         let (s, revision_identifier) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, drawing_identifier) = param_from_chunks::<DrawingDefinition<'a>>(false, s, &mut i, strs)?;
-        let (s, intended_scale) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, intended_scale) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             revision_identifier,
             drawing_identifier,
@@ -12333,7 +12351,7 @@ impl<'a> HasId for EdgeLoop_<'a> {
 #[derive(Debug)]
 pub struct EdgeRound_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -12352,7 +12370,7 @@ impl<'a> ParseFromChunks<'a> for EdgeRound_<'a> {
         let mut i = 0;
         let (s, _) = tag("EDGE_ROUND(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -12465,7 +12483,7 @@ impl<'a> HasId for EffectivityContextAssignment_<'a> {
 #[derive(Debug)]
 pub struct EffectivityContextRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type EffectivityContextRole<'a> = Id<EffectivityContextRole_<'a>>;
@@ -12482,7 +12500,7 @@ impl<'a> ParseFromChunks<'a> for EffectivityContextRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("EFFECTIVITY_CONTEXT_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -12502,7 +12520,7 @@ pub type EffectivityItem<'a> = Id<EffectivityItem_<'a>>;
 #[derive(Debug)]
 pub struct EffectivityRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub related_effectivity: Effectivity<'a>,
     pub relating_effectivity: Effectivity<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -12521,7 +12539,7 @@ impl<'a> ParseFromChunks<'a> for EffectivityRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("EFFECTIVITY_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, related_effectivity) = param_from_chunks::<Effectivity<'a>>(false, s, &mut i, strs)?;
         let (s, relating_effectivity) = param_from_chunks::<Effectivity<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -12616,7 +12634,7 @@ impl<'a> HasId for ElectricCurrentUnit_<'a> {
 #[derive(Debug)]
 pub struct ElementDelivery_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -12634,7 +12652,7 @@ impl<'a> ParseFromChunks<'a> for ElementDelivery_<'a> {
         let mut i = 0;
         let (s, _) = tag("ELEMENT_DELIVERY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -12831,7 +12849,7 @@ impl<'a> HasId for EvaluatedDegeneratePcurve_<'a> {
 pub struct EventOccurrence_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type EventOccurrence<'a> = Id<EventOccurrence_<'a>>;
@@ -12849,7 +12867,7 @@ impl<'a> ParseFromChunks<'a> for EventOccurrence_<'a> {
         let (s, _) = tag("EVENT_OCCURRENCE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -12933,7 +12951,7 @@ impl<'a> HasId for EventOccurrenceContextAssignment_<'a> {
 #[derive(Debug)]
 pub struct EventOccurrenceContextRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type EventOccurrenceContextRole<'a> = Id<EventOccurrenceContextRole_<'a>>;
@@ -12950,7 +12968,7 @@ impl<'a> ParseFromChunks<'a> for EventOccurrenceContextRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("EVENT_OCCURRENCE_CONTEXT_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -12970,7 +12988,7 @@ pub type EventOccurrenceItem<'a> = Id<EventOccurrenceItem_<'a>>;
 #[derive(Debug)]
 pub struct EventOccurrenceRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type EventOccurrenceRole<'a> = Id<EventOccurrenceRole_<'a>>;
@@ -12987,7 +13005,7 @@ impl<'a> ParseFromChunks<'a> for EventOccurrenceRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("EVENT_OCCURRENCE_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -13003,7 +13021,7 @@ impl<'a> HasId for EventOccurrenceRole_<'a> {
 #[derive(Debug)]
 pub struct ExclusiveProductConceptFeatureCategory_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ExclusiveProductConceptFeatureCategory<'a> = Id<ExclusiveProductConceptFeatureCategory_<'a>>;
@@ -13020,7 +13038,7 @@ impl<'a> ParseFromChunks<'a> for ExclusiveProductConceptFeatureCategory_<'a> {
         let mut i = 0;
         let (s, _) = tag("EXCLUSIVE_PRODUCT_CONCEPT_FEATURE_CATEGORY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -13036,7 +13054,7 @@ impl<'a> HasId for ExclusiveProductConceptFeatureCategory_<'a> {
 #[derive(Debug)]
 pub struct ExecutedAction_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -13054,7 +13072,7 @@ impl<'a> ParseFromChunks<'a> for ExecutedAction_<'a> {
         let mut i = 0;
         let (s, _) = tag("EXECUTED_ACTION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -13159,7 +13177,7 @@ impl<'a> HasId for ExpressionConversionBasedUnit_<'a> {
 #[derive(Debug)]
 pub struct Extension_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -13178,7 +13196,7 @@ impl<'a> ParseFromChunks<'a> for Extension_<'a> {
         let mut i = 0;
         let (s, _) = tag("EXTENSION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -13303,7 +13321,7 @@ impl<'a> HasId for ExternallyDefinedCharacterGlyph_<'a> {
 #[derive(Debug)]
 pub struct ExternallyDefinedClass_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub item_id: SourceItem<'a>,
     pub source: ExternalSource<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -13322,7 +13340,7 @@ impl<'a> ParseFromChunks<'a> for ExternallyDefinedClass_<'a> {
         let mut i = 0;
         let (s, _) = tag("EXTERNALLY_DEFINED_CLASS(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_id) = param_from_chunks::<SourceItem<'a>>(false, s, &mut i, strs)?;
         let (s, source) = param_from_chunks::<ExternalSource<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -13418,7 +13436,7 @@ impl<'a> HasId for ExternallyDefinedDimensionDefinition_<'a> {
 #[derive(Debug)]
 pub struct ExternallyDefinedFeatureDefinition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub item_id: SourceItem<'a>,
     pub source: ExternalSource<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -13437,7 +13455,7 @@ impl<'a> ParseFromChunks<'a> for ExternallyDefinedFeatureDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("EXTERNALLY_DEFINED_FEATURE_DEFINITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_id) = param_from_chunks::<SourceItem<'a>>(false, s, &mut i, strs)?;
         let (s, source) = param_from_chunks::<ExternalSource<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -13460,7 +13478,7 @@ impl<'a> HasId for ExternallyDefinedFeatureDefinition_<'a> {
 pub struct ExternallyDefinedGeneralProperty_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub item_id: SourceItem<'a>,
     pub source: ExternalSource<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -13480,7 +13498,7 @@ impl<'a> ParseFromChunks<'a> for ExternallyDefinedGeneralProperty_<'a> {
         let (s, _) = tag("EXTERNALLY_DEFINED_GENERAL_PROPERTY(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_id) = param_from_chunks::<SourceItem<'a>>(false, s, &mut i, strs)?;
         let (s, source) = param_from_chunks::<ExternalSource<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -13574,7 +13592,7 @@ impl<'a> HasId for ExternallyDefinedItem_<'a> {
 #[derive(Debug)]
 pub struct ExternallyDefinedItemRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_item: ExternallyDefinedItem<'a>,
     pub related_item: ExternallyDefinedItem<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -13593,7 +13611,7 @@ impl<'a> ParseFromChunks<'a> for ExternallyDefinedItemRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("EXTERNALLY_DEFINED_ITEM_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_item) = param_from_chunks::<ExternallyDefinedItem<'a>>(false, s, &mut i, strs)?;
         let (s, related_item) = param_from_chunks::<ExternallyDefinedItem<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -14123,7 +14141,7 @@ impl<'a> HasId for FacetedBrepShapeRepresentation_<'a> {
 #[derive(Debug)]
 pub struct FeatureComponentDefinition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type FeatureComponentDefinition<'a> = Id<FeatureComponentDefinition_<'a>>;
@@ -14140,7 +14158,7 @@ impl<'a> ParseFromChunks<'a> for FeatureComponentDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("FEATURE_COMPONENT_DEFINITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -14156,7 +14174,7 @@ impl<'a> HasId for FeatureComponentDefinition_<'a> {
 #[derive(Debug)]
 pub struct FeatureComponentRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -14175,7 +14193,7 @@ impl<'a> ParseFromChunks<'a> for FeatureComponentRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("FEATURE_COMPONENT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -14197,7 +14215,7 @@ impl<'a> HasId for FeatureComponentRelationship_<'a> {
 #[derive(Debug)]
 pub struct FeatureDefinition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type FeatureDefinition<'a> = Id<FeatureDefinition_<'a>>;
@@ -14214,7 +14232,7 @@ impl<'a> ParseFromChunks<'a> for FeatureDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("FEATURE_DEFINITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -14230,7 +14248,7 @@ impl<'a> HasId for FeatureDefinition_<'a> {
 #[derive(Debug)]
 pub struct FeatureInPanel_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type FeatureInPanel<'a> = Id<FeatureInPanel_<'a>>;
@@ -14247,7 +14265,7 @@ impl<'a> ParseFromChunks<'a> for FeatureInPanel_<'a> {
         let mut i = 0;
         let (s, _) = tag("FEATURE_IN_PANEL(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -14263,7 +14281,7 @@ impl<'a> HasId for FeatureInPanel_<'a> {
 #[derive(Debug)]
 pub struct FeaturePattern_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type FeaturePattern<'a> = Id<FeaturePattern_<'a>>;
@@ -14280,7 +14298,7 @@ impl<'a> ParseFromChunks<'a> for FeaturePattern_<'a> {
         let mut i = 0;
         let (s, _) = tag("FEATURE_PATTERN(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -14296,7 +14314,7 @@ impl<'a> HasId for FeaturePattern_<'a> {
 #[derive(Debug)]
 pub struct FeaturedShape_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: CharacterizedDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -14314,7 +14332,7 @@ impl<'a> ParseFromChunks<'a> for FeaturedShape_<'a> {
         let mut i = 0;
         let (s, _) = tag("FEATURED_SHAPE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<CharacterizedDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -14530,7 +14548,7 @@ pub type FillStyleSelect<'a> = Id<FillStyleSelect_<'a>>;
 #[derive(Debug)]
 pub struct Fillet_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -14549,7 +14567,7 @@ impl<'a> ParseFromChunks<'a> for Fillet_<'a> {
         let mut i = 0;
         let (s, _) = tag("FILLET(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -14710,7 +14728,7 @@ impl<'a> HasId for FoundedKinematicPath_<'a> {
 #[derive(Debug)]
 pub struct FullyConstrainedPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -14730,7 +14748,7 @@ impl<'a> ParseFromChunks<'a> for FullyConstrainedPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("FULLY_CONSTRAINED_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -14755,7 +14773,7 @@ impl<'a> HasId for FullyConstrainedPair_<'a> {
 #[derive(Debug)]
 pub struct FunctionallyDefinedTransformation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type FunctionallyDefinedTransformation<'a> = Id<FunctionallyDefinedTransformation_<'a>>;
@@ -14772,7 +14790,7 @@ impl<'a> ParseFromChunks<'a> for FunctionallyDefinedTransformation_<'a> {
         let mut i = 0;
         let (s, _) = tag("FUNCTIONALLY_DEFINED_TRANSFORMATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -14788,7 +14806,7 @@ impl<'a> HasId for FunctionallyDefinedTransformation_<'a> {
 #[derive(Debug)]
 pub struct GearPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -14813,7 +14831,7 @@ impl<'a> ParseFromChunks<'a> for GearPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("GEAR_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -14923,7 +14941,7 @@ impl<'a> HasId for GearPairValue_<'a> {
 #[derive(Debug)]
 pub struct GeneralFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type GeneralFeature<'a> = Id<GeneralFeature_<'a>>;
@@ -14940,7 +14958,7 @@ impl<'a> ParseFromChunks<'a> for GeneralFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("GENERAL_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -14957,7 +14975,7 @@ impl<'a> HasId for GeneralFeature_<'a> {
 pub struct GeneralMaterialProperty_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type GeneralMaterialProperty<'a> = Id<GeneralMaterialProperty_<'a>>;
@@ -14975,7 +14993,7 @@ impl<'a> ParseFromChunks<'a> for GeneralMaterialProperty_<'a> {
         let (s, _) = tag("GENERAL_MATERIAL_PROPERTY(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -14994,7 +15012,7 @@ impl<'a> HasId for GeneralMaterialProperty_<'a> {
 pub struct GeneralProperty_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type GeneralProperty<'a> = Id<GeneralProperty_<'a>>;
@@ -15012,7 +15030,7 @@ impl<'a> ParseFromChunks<'a> for GeneralProperty_<'a> {
         let (s, _) = tag("GENERAL_PROPERTY(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -15030,7 +15048,7 @@ impl<'a> HasId for GeneralProperty_<'a> {
 #[derive(Debug)]
 pub struct GeneralPropertyAssociation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub base_definition: GeneralProperty<'a>,
     pub derived_definition: DerivedPropertySelect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -15049,7 +15067,7 @@ impl<'a> ParseFromChunks<'a> for GeneralPropertyAssociation_<'a> {
         let mut i = 0;
         let (s, _) = tag("GENERAL_PROPERTY_ASSOCIATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, base_definition) = param_from_chunks::<GeneralProperty<'a>>(false, s, &mut i, strs)?;
         let (s, derived_definition) = param_from_chunks::<DerivedPropertySelect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -15071,7 +15089,7 @@ impl<'a> HasId for GeneralPropertyAssociation_<'a> {
 #[derive(Debug)]
 pub struct GeneralPropertyRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_property: GeneralProperty<'a>,
     pub related_property: GeneralProperty<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -15090,7 +15108,7 @@ impl<'a> ParseFromChunks<'a> for GeneralPropertyRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("GENERAL_PROPERTY_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_property) = param_from_chunks::<GeneralProperty<'a>>(false, s, &mut i, strs)?;
         let (s, related_property) = param_from_chunks::<GeneralProperty<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -15221,7 +15239,7 @@ impl<'a> HasId for GenericVariable_<'a> {
 #[derive(Debug)]
 pub struct GeometricAlignment_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -15240,7 +15258,7 @@ impl<'a> ParseFromChunks<'a> for GeometricAlignment_<'a> {
         let mut i = 0;
         let (s, _) = tag("GEOMETRIC_ALIGNMENT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -15295,7 +15313,7 @@ impl<'a> HasId for GeometricCurveSet_<'a> {
 #[derive(Debug)]
 pub struct GeometricIntersection_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -15314,7 +15332,7 @@ impl<'a> ParseFromChunks<'a> for GeometricIntersection_<'a> {
         let mut i = 0;
         let (s, _) = tag("GEOMETRIC_INTERSECTION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -15336,7 +15354,7 @@ impl<'a> HasId for GeometricIntersection_<'a> {
 #[derive(Debug)]
 pub struct GeometricItemSpecificUsage_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: RepresentedDefinition<'a>,
     pub used_representation: Representation<'a>,
     pub identified_item: RepresentationItem<'a>,
@@ -15356,7 +15374,7 @@ impl<'a> ParseFromChunks<'a> for GeometricItemSpecificUsage_<'a> {
         let mut i = 0;
         let (s, _) = tag("GEOMETRIC_ITEM_SPECIFIC_USAGE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<RepresentedDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, used_representation) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, identified_item) = param_from_chunks::<RepresentationItem<'a>>(true, s, &mut i, strs)?;
@@ -15874,7 +15892,7 @@ impl<'a> HasId for GlobalUnitAssignedContext_<'a> {
 #[derive(Debug)]
 pub struct Group_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Group<'a> = Id<Group_<'a>>;
@@ -15891,7 +15909,7 @@ impl<'a> ParseFromChunks<'a> for Group_<'a> {
         let mut i = 0;
         let (s, _) = tag("GROUP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -15940,7 +15958,7 @@ pub type GroupItem<'a> = Id<GroupItem_<'a>>;
 #[derive(Debug)]
 pub struct GroupRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_group: Group<'a>,
     pub related_group: Group<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -15959,7 +15977,7 @@ impl<'a> ParseFromChunks<'a> for GroupRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("GROUP_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_group) = param_from_chunks::<Group<'a>>(false, s, &mut i, strs)?;
         let (s, related_group) = param_from_chunks::<Group<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -16100,7 +16118,7 @@ impl<'a> HasId for HiddenElementOverRidingStyledItem_<'a> {
 #[derive(Debug)]
 pub struct HoleBottom_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -16119,7 +16137,7 @@ impl<'a> ParseFromChunks<'a> for HoleBottom_<'a> {
         let mut i = 0;
         let (s, _) = tag("HOLE_BOTTOM(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -16141,7 +16159,7 @@ impl<'a> HasId for HoleBottom_<'a> {
 #[derive(Debug)]
 pub struct HoleInPanel_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type HoleInPanel<'a> = Id<HoleInPanel_<'a>>;
@@ -16158,7 +16176,7 @@ impl<'a> ParseFromChunks<'a> for HoleInPanel_<'a> {
         let mut i = 0;
         let (s, _) = tag("HOLE_IN_PANEL(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -16174,11 +16192,11 @@ impl<'a> HasId for HoleInPanel_<'a> {
 #[derive(Debug)]
 pub struct HomokineticPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
-    pub input_skew_angle: Option<PlaneAngleMeasure<'a>>,
+    pub input_skew_angle: Value<PlaneAngleMeasure<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type HomokineticPair<'a> = Id<HomokineticPair_<'a>>;
@@ -16195,11 +16213,11 @@ impl<'a> ParseFromChunks<'a> for HomokineticPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("HOMOKINETIC_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
-        let (s, input_skew_angle) = param_from_chunks::<Option<PlaneAngleMeasure<'a>>>(true, s, &mut i, strs)?;
+        let (s, input_skew_angle) = param_from_chunks::<Value<PlaneAngleMeasure<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -16349,7 +16367,7 @@ pub type IdentificationItem<'a> = Id<IdentificationItem_<'a>>;
 #[derive(Debug)]
 pub struct IdentificationRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type IdentificationRole<'a> = Id<IdentificationRole_<'a>>;
@@ -16366,7 +16384,7 @@ impl<'a> ParseFromChunks<'a> for IdentificationRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("IDENTIFICATION_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -16394,7 +16412,7 @@ impl<'a> HasId for Identifier<'a> {
 pub struct InclusionProductConceptFeature_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub condition: ConceptFeatureRelationshipWithCondition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -16413,7 +16431,7 @@ impl<'a> ParseFromChunks<'a> for InclusionProductConceptFeature_<'a> {
         let (s, _) = tag("INCLUSION_PRODUCT_CONCEPT_FEATURE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, condition) = param_from_chunks::<ConceptFeatureRelationshipWithCondition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -16497,11 +16515,11 @@ impl<'a> HasId for InitialState_<'a> {
 #[derive(Debug)]
 pub struct InstancedFeature_<'a> { // entity
     pub shape_aspect__name: Label<'a>,
-    pub shape_aspect__description: Option<Text<'a>>,
+    pub shape_aspect__description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     pub characterized_object__name: Label<'a>,
-    pub characterized_object__description: Option<Text<'a>>,
+    pub characterized_object__description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type InstancedFeature<'a> = Id<InstancedFeature_<'a>>;
@@ -16520,13 +16538,13 @@ impl<'a> ParseFromChunks<'a> for InstancedFeature_<'a> {
         #[allow(non_snake_case)]
         let (s, shape_aspect__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, shape_aspect__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, shape_aspect__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, characterized_object__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, characterized_object__description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, characterized_object__description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             shape_aspect__name,
             shape_aspect__description,
@@ -16687,7 +16705,9 @@ pub enum InterpolationType<'a> { // enum
     Undefined,
     Synchronous,
     Linear,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for InterpolationType<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -16697,7 +16717,7 @@ impl<'a> Parse<'a> for InterpolationType<'a> {
             "UNDEFINED" => Undefined,
             "SYNCHRONOUS" => Synchronous,
             "LINEAR" => Linear,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -16816,7 +16836,7 @@ pub type InvisibleItem<'a> = Id<InvisibleItem_<'a>>;
 #[derive(Debug)]
 pub struct ItemDefinedTransformation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -16835,7 +16855,7 @@ impl<'a> ParseFromChunks<'a> for ItemDefinedTransformation_<'a> {
         let mut i = 0;
         let (s, _) = tag("ITEM_DEFINED_TRANSFORMATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -16857,7 +16877,7 @@ impl<'a> HasId for ItemDefinedTransformation_<'a> {
 #[derive(Debug)]
 pub struct ItemIdentifiedRepresentationUsage_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: RepresentedDefinition<'a>,
     pub used_representation: Representation<'a>,
     pub identified_item: RepresentationItem<'a>,
@@ -16877,7 +16897,7 @@ impl<'a> ParseFromChunks<'a> for ItemIdentifiedRepresentationUsage_<'a> {
         let mut i = 0;
         let (s, _) = tag("ITEM_IDENTIFIED_REPRESENTATION_USAGE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<RepresentedDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, used_representation) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, identified_item) = param_from_chunks::<RepresentationItem<'a>>(true, s, &mut i, strs)?;
@@ -16902,7 +16922,7 @@ impl<'a> HasId for ItemIdentifiedRepresentationUsage_<'a> {
 #[derive(Debug)]
 pub struct Joggle_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Joggle<'a> = Id<Joggle_<'a>>;
@@ -16919,7 +16939,7 @@ impl<'a> ParseFromChunks<'a> for Joggle_<'a> {
         let mut i = 0;
         let (s, _) = tag("JOGGLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -16935,7 +16955,7 @@ impl<'a> HasId for Joggle_<'a> {
 #[derive(Debug)]
 pub struct JoggleTermination_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -16954,7 +16974,7 @@ impl<'a> ParseFromChunks<'a> for JoggleTermination_<'a> {
         let mut i = 0;
         let (s, _) = tag("JOGGLE_TERMINATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -17120,7 +17140,7 @@ impl<'a> HasId for KinematicFrameBackgroundRepresentation_<'a> {
 #[derive(Debug)]
 pub struct KinematicFrameBackgroundRepresentationAssociation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     pub transformation_operator: Transformation<'a>,
@@ -17140,7 +17160,7 @@ impl<'a> ParseFromChunks<'a> for KinematicFrameBackgroundRepresentationAssociati
         let mut i = 0;
         let (s, _) = tag("KINEMATIC_FRAME_BACKGROUND_REPRESENTATION_ASSOCIATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, transformation_operator) = param_from_chunks::<Transformation<'a>>(true, s, &mut i, strs)?;
@@ -17167,7 +17187,7 @@ impl<'a> HasId for KinematicFrameBackgroundRepresentationAssociation_<'a> {
 pub struct KinematicFrameBasedTransformation_<'a> { // entity
     pub representation_item__name: Label<'a>,
     pub functionally_defined_transformation__name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transformator: RigidPlacement<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -17188,7 +17208,7 @@ impl<'a> ParseFromChunks<'a> for KinematicFrameBasedTransformation_<'a> {
         let (s, representation_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, functionally_defined_transformation__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transformator) = param_from_chunks::<RigidPlacement<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             representation_item__name,
@@ -17340,7 +17360,7 @@ impl<'a> HasId for KinematicLinkRepresentation_<'a> {
 #[derive(Debug)]
 pub struct KinematicLinkRepresentationAssociation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -17359,7 +17379,7 @@ impl<'a> ParseFromChunks<'a> for KinematicLinkRepresentationAssociation_<'a> {
         let mut i = 0;
         let (s, _) = tag("KINEMATIC_LINK_REPRESENTATION_ASSOCIATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -17414,7 +17434,7 @@ impl<'a> HasId for KinematicLinkRepresentationRelation_<'a> {
 #[derive(Debug)]
 pub struct KinematicPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -17434,7 +17454,7 @@ impl<'a> ParseFromChunks<'a> for KinematicPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("KINEMATIC_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -17488,7 +17508,7 @@ impl<'a> HasId for KinematicPath_<'a> {
 #[derive(Debug)]
 pub struct KinematicPropertyDefinition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: CharacterizedDefinition<'a>,
     pub ground_definition: CharacterizedDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -17507,7 +17527,7 @@ impl<'a> ParseFromChunks<'a> for KinematicPropertyDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("KINEMATIC_PROPERTY_DEFINITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<CharacterizedDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, ground_definition) = param_from_chunks::<CharacterizedDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -17598,7 +17618,9 @@ pub enum KnotType<'a> { // enum
     QuasiUniformKnots,
     PiecewiseBezierKnots,
     Unspecified,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for KnotType<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -17609,7 +17631,7 @@ impl<'a> Parse<'a> for KnotType<'a> {
             "QUASI_UNIFORM_KNOTS" => QuasiUniformKnots,
             "PIECEWISE_BEZIER_KNOTS" => PiecewiseBezierKnots,
             "UNSPECIFIED" => Unspecified,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -17665,7 +17687,7 @@ impl<'a> HasId for Label<'a> {
 #[derive(Debug)]
 pub struct Language_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Language<'a> = Id<Language_<'a>>;
@@ -17682,7 +17704,7 @@ impl<'a> ParseFromChunks<'a> for Language_<'a> {
         let mut i = 0;
         let (s, _) = tag("LANGUAGE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -18225,7 +18247,9 @@ pub enum LimitCondition<'a> { // enum
     MaximumMaterialCondition,
     LeastMaterialCondition,
     RegardlessOfFeatureSize,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for LimitCondition<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -18235,7 +18259,7 @@ impl<'a> Parse<'a> for LimitCondition<'a> {
             "MAXIMUM_MATERIAL_CONDITION" => MaximumMaterialCondition,
             "LEAST_MATERIAL_CONDITION" => LeastMaterialCondition,
             "REGARDLESS_OF_FEATURE_SIZE" => RegardlessOfFeatureSize,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -18458,8 +18482,8 @@ impl<'a> HasId for LiteralNumber_<'a> {
 #[derive(Debug)]
 pub struct LocalTime_<'a> { // entity
     pub hour_component: HourInDay<'a>,
-    pub minute_component: Option<MinuteInHour<'a>>,
-    pub second_component: Option<SecondInMinute<'a>>,
+    pub minute_component: Value<MinuteInHour<'a>>,
+    pub second_component: Value<SecondInMinute<'a>>,
     pub zone: CoordinatedUniversalTimeOffset<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -18477,8 +18501,8 @@ impl<'a> ParseFromChunks<'a> for LocalTime_<'a> {
         let mut i = 0;
         let (s, _) = tag("LOCAL_TIME(")(strs[0])?;
         let (s, hour_component) = param_from_chunks::<HourInDay<'a>>(false, s, &mut i, strs)?;
-        let (s, minute_component) = param_from_chunks::<Option<MinuteInHour<'a>>>(false, s, &mut i, strs)?;
-        let (s, second_component) = param_from_chunks::<Option<SecondInMinute<'a>>>(false, s, &mut i, strs)?;
+        let (s, minute_component) = param_from_chunks::<Value<MinuteInHour<'a>>>(false, s, &mut i, strs)?;
+        let (s, second_component) = param_from_chunks::<Value<SecondInMinute<'a>>>(false, s, &mut i, strs)?;
         let (s, zone) = param_from_chunks::<CoordinatedUniversalTimeOffset<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             hour_component,
@@ -18536,7 +18560,7 @@ impl<'a> HasId for LocationShapeRepresentation_<'a> {
 #[derive(Debug)]
 pub struct Locator_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Locator<'a> = Id<Locator_<'a>>;
@@ -18553,7 +18577,7 @@ impl<'a> ParseFromChunks<'a> for Locator_<'a> {
         let mut i = 0;
         let (s, _) = tag("LOCATOR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -18796,7 +18820,7 @@ impl<'a> HasId for LuminousIntensityUnit_<'a> {
 pub struct MakeFromUsageOption_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
     pub ranking: i64,
@@ -18819,7 +18843,7 @@ impl<'a> ParseFromChunks<'a> for MakeFromUsageOption_<'a> {
         let (s, _) = tag("MAKE_FROM_USAGE_OPTION(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, ranking) = param_from_chunks::<i64>(false, s, &mut i, strs)?;
@@ -19025,7 +19049,9 @@ pub enum MarkerType<'a> { // enum
     Ring,
     Square,
     Triangle,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for MarkerType<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -19039,7 +19065,7 @@ impl<'a> Parse<'a> for MarkerType<'a> {
             "RING" => Ring,
             "SQUARE" => Square,
             "TRIANGLE" => Triangle,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -19198,7 +19224,7 @@ impl<'a> HasId for MaterialDesignationCharacterization_<'a> {
 #[derive(Debug)]
 pub struct MaterialProperty_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: CharacterizedDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -19216,7 +19242,7 @@ impl<'a> ParseFromChunks<'a> for MaterialProperty_<'a> {
         let mut i = 0;
         let (s, _) = tag("MATERIAL_PROPERTY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<CharacterizedDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -19607,7 +19633,7 @@ impl<'a> HasId for Mechanism_<'a> {
 #[derive(Debug)]
 pub struct MechanismBasePlacement_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub transformation_operator: Transformation<'a>,
     pub base_of_mechanism: Mechanism<'a>,
@@ -19627,7 +19653,7 @@ impl<'a> ParseFromChunks<'a> for MechanismBasePlacement_<'a> {
         let mut i = 0;
         let (s, _) = tag("MECHANISM_BASE_PLACEMENT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, _) = param_from_chunks::<Derived>(false, s, &mut i, strs)?;
         let (s, transformation_operator) = param_from_chunks::<Transformation<'a>>(false, s, &mut i, strs)?;
@@ -19825,7 +19851,7 @@ impl<'a> HasId for ModifiedGeometricTolerance_<'a> {
 #[derive(Debug)]
 pub struct ModifiedPattern_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ModifiedPattern<'a> = Id<ModifiedPattern_<'a>>;
@@ -19842,7 +19868,7 @@ impl<'a> ParseFromChunks<'a> for ModifiedPattern_<'a> {
         let mut i = 0;
         let (s, _) = tag("MODIFIED_PATTERN(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -19906,7 +19932,7 @@ impl<'a> HasId for MonthInYearNumber<'a> {
 #[derive(Debug)]
 pub struct MotionLinkRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     pub related_frame: RigidPlacement<'a>,
@@ -19926,7 +19952,7 @@ impl<'a> ParseFromChunks<'a> for MotionLinkRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("MOTION_LINK_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, related_frame) = param_from_chunks::<RigidPlacement<'a>>(true, s, &mut i, strs)?;
@@ -20293,10 +20319,10 @@ impl<'a> HasId for NamedUnitVariable_<'a> {
 pub struct NextAssemblyUsageOccurrence_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
-    pub reference_designator: Option<Identifier<'a>>,
+    pub reference_designator: Value<Identifier<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type NextAssemblyUsageOccurrence<'a> = Id<NextAssemblyUsageOccurrence_<'a>>;
@@ -20314,10 +20340,10 @@ impl<'a> ParseFromChunks<'a> for NextAssemblyUsageOccurrence_<'a> {
         let (s, _) = tag("NEXT_ASSEMBLY_USAGE_OCCURRENCE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
-        let (s, reference_designator) = param_from_chunks::<Option<Identifier<'a>>>(true, s, &mut i, strs)?;
+        let (s, reference_designator) = param_from_chunks::<Value<Identifier<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -20341,7 +20367,7 @@ impl<'a> HasId for NextAssemblyUsageOccurrence_<'a> {
 #[derive(Debug)]
 pub struct NgonClosedProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -20360,7 +20386,7 @@ impl<'a> ParseFromChunks<'a> for NgonClosedProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("NGON_CLOSED_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -20462,7 +20488,9 @@ impl<'a> HasId for NotExpression_<'a> {
 #[derive(Debug)]
 pub enum NullStyle<'a> { // enum
     Null,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for NullStyle<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -20470,7 +20498,7 @@ impl<'a> Parse<'a> for NullStyle<'a> {
         let (s, tag) = parse_enum_tag(s)?;
         let e = match tag {
             "NULL" => Null,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -20565,7 +20593,7 @@ impl<'a> HasId for NumericVariable_<'a> {
 #[derive(Debug)]
 pub struct ObjectRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ObjectRole<'a> = Id<ObjectRole_<'a>>;
@@ -20582,7 +20610,7 @@ impl<'a> ParseFromChunks<'a> for ObjectRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("OBJECT_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -20787,7 +20815,7 @@ impl<'a> HasId for OneDirectionRepeatFactor_<'a> {
 #[derive(Debug)]
 pub struct OpenPathProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -20806,7 +20834,7 @@ impl<'a> ParseFromChunks<'a> for OpenPathProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("OPEN_PATH_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -20922,9 +20950,9 @@ impl<'a> HasId for OrdinateDimension_<'a> {
 }
 #[derive(Debug)]
 pub struct Organization_<'a> { // entity
-    pub id: Option<Identifier<'a>>,
+    pub id: Value<Identifier<'a>>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Organization<'a> = Id<Organization_<'a>>;
@@ -20940,9 +20968,9 @@ impl<'a> ParseFromChunks<'a> for Organization_<'a> {
     fn parse_chunks(strs: &[&'a str]) -> IResult<'a, Self> {
         let mut i = 0;
         let (s, _) = tag("ORGANIZATION(")(strs[0])?;
-        let (s, id) = param_from_chunks::<Option<Identifier<'a>>>(false, s, &mut i, strs)?;
+        let (s, id) = param_from_chunks::<Value<Identifier<'a>>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -20997,7 +21025,7 @@ pub type OrganizationItem<'a> = Id<OrganizationItem_<'a>>;
 #[derive(Debug)]
 pub struct OrganizationRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_organization: Organization<'a>,
     pub related_organization: Organization<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -21016,7 +21044,7 @@ impl<'a> ParseFromChunks<'a> for OrganizationRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("ORGANIZATION_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_organization) = param_from_chunks::<Organization<'a>>(false, s, &mut i, strs)?;
         let (s, related_organization) = param_from_chunks::<Organization<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -21066,20 +21094,20 @@ impl<'a> HasId for OrganizationRole_<'a> {
 }
 #[derive(Debug)]
 pub struct OrganizationalAddress_<'a> { // entity
-    pub internal_location: Option<Label<'a>>,
-    pub street_number: Option<Label<'a>>,
-    pub street: Option<Label<'a>>,
-    pub postal_box: Option<Label<'a>>,
-    pub town: Option<Label<'a>>,
-    pub region: Option<Label<'a>>,
-    pub postal_code: Option<Label<'a>>,
-    pub country: Option<Label<'a>>,
-    pub facsimile_number: Option<Label<'a>>,
-    pub telephone_number: Option<Label<'a>>,
-    pub electronic_mail_address: Option<Label<'a>>,
-    pub telex_number: Option<Label<'a>>,
+    pub internal_location: Value<Label<'a>>,
+    pub street_number: Value<Label<'a>>,
+    pub street: Value<Label<'a>>,
+    pub postal_box: Value<Label<'a>>,
+    pub town: Value<Label<'a>>,
+    pub region: Value<Label<'a>>,
+    pub postal_code: Value<Label<'a>>,
+    pub country: Value<Label<'a>>,
+    pub facsimile_number: Value<Label<'a>>,
+    pub telephone_number: Value<Label<'a>>,
+    pub electronic_mail_address: Value<Label<'a>>,
+    pub telex_number: Value<Label<'a>>,
     pub organizations: Vec<Organization<'a>>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type OrganizationalAddress<'a> = Id<OrganizationalAddress_<'a>>;
@@ -21095,20 +21123,20 @@ impl<'a> ParseFromChunks<'a> for OrganizationalAddress_<'a> {
     fn parse_chunks(strs: &[&'a str]) -> IResult<'a, Self> {
         let mut i = 0;
         let (s, _) = tag("ORGANIZATIONAL_ADDRESS(")(strs[0])?;
-        let (s, internal_location) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, street_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, street) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, postal_box) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, town) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, region) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, postal_code) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, country) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, facsimile_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, telephone_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, electronic_mail_address) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, telex_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, internal_location) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, street_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, street) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, postal_box) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, town) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, region) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, postal_code) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, country) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, facsimile_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, telephone_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, electronic_mail_address) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, telex_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         let (s, organizations) = param_from_chunks::<Vec<Organization<'a>>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             internal_location,
             street_number,
@@ -21148,7 +21176,7 @@ impl<'a> HasId for OrganizationalAddress_<'a> {
 #[derive(Debug)]
 pub struct OrganizationalProject_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub responsible_organizations: Vec<Organization<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -21166,7 +21194,7 @@ impl<'a> ParseFromChunks<'a> for OrganizationalProject_<'a> {
         let mut i = 0;
         let (s, _) = tag("ORGANIZATIONAL_PROJECT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, responsible_organizations) = param_from_chunks::<Vec<Organization<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -21222,7 +21250,7 @@ pub type OrganizationalProjectItem<'a> = Id<OrganizationalProjectItem_<'a>>;
 #[derive(Debug)]
 pub struct OrganizationalProjectRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_organizational_project: OrganizationalProject<'a>,
     pub related_organizational_project: OrganizationalProject<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -21241,7 +21269,7 @@ impl<'a> ParseFromChunks<'a> for OrganizationalProjectRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("ORGANIZATIONAL_PROJECT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_organizational_project) = param_from_chunks::<OrganizationalProject<'a>>(false, s, &mut i, strs)?;
         let (s, related_organizational_project) = param_from_chunks::<OrganizationalProject<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -21263,7 +21291,7 @@ impl<'a> HasId for OrganizationalProjectRelationship_<'a> {
 #[derive(Debug)]
 pub struct OrganizationalProjectRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type OrganizationalProjectRole<'a> = Id<OrganizationalProjectRole_<'a>>;
@@ -21280,7 +21308,7 @@ impl<'a> ParseFromChunks<'a> for OrganizationalProjectRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("ORGANIZATIONAL_PROJECT_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -21599,7 +21627,7 @@ impl<'a> HasId for OverRidingStyledItem_<'a> {
 pub struct PackageProductConceptFeature_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type PackageProductConceptFeature<'a> = Id<PackageProductConceptFeature_<'a>>;
@@ -21617,7 +21645,7 @@ impl<'a> ParseFromChunks<'a> for PackageProductConceptFeature_<'a> {
         let (s, _) = tag("PACKAGE_PRODUCT_CONCEPT_FEATURE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -21734,7 +21762,7 @@ impl<'a> HasId for Parabola_<'a> {
 #[derive(Debug)]
 pub struct ParallelOffset_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     pub offset: MeasureWithUnit<'a>,
@@ -21754,7 +21782,7 @@ impl<'a> ParseFromChunks<'a> for ParallelOffset_<'a> {
         let mut i = 0;
         let (s, _) = tag("PARALLEL_OFFSET(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(false, s, &mut i, strs)?;
         let (s, offset) = param_from_chunks::<MeasureWithUnit<'a>>(true, s, &mut i, strs)?;
@@ -21868,7 +21896,7 @@ impl<'a> HasId for ParametricRepresentationContext_<'a> {
 #[derive(Debug)]
 pub struct PartialCircularProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -21887,7 +21915,7 @@ impl<'a> ParseFromChunks<'a> for PartialCircularProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("PARTIAL_CIRCULAR_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -21942,7 +21970,7 @@ impl<'a> HasId for Path_<'a> {
 #[derive(Debug)]
 pub struct PathFeatureComponent_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -21961,7 +21989,7 @@ impl<'a> ParseFromChunks<'a> for PathFeatureComponent_<'a> {
         let mut i = 0;
         let (s, _) = tag("PATH_FEATURE_COMPONENT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -22020,7 +22048,7 @@ impl<'a> HasId for PathShapeRepresentation_<'a> {
 #[derive(Debug)]
 pub struct PatternOffsetMembership_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -22039,7 +22067,7 @@ impl<'a> ParseFromChunks<'a> for PatternOffsetMembership_<'a> {
         let mut i = 0;
         let (s, _) = tag("PATTERN_OFFSET_MEMBERSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -22061,7 +22089,7 @@ impl<'a> HasId for PatternOffsetMembership_<'a> {
 #[derive(Debug)]
 pub struct PatternOmitMembership_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -22080,7 +22108,7 @@ impl<'a> ParseFromChunks<'a> for PatternOmitMembership_<'a> {
         let mut i = 0;
         let (s, _) = tag("PATTERN_OMIT_MEMBERSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -22143,7 +22171,7 @@ pub type PcurveOrSurface<'a> = Id<PcurveOrSurface_<'a>>;
 #[derive(Debug)]
 pub struct PerpendicularTo_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -22162,7 +22190,7 @@ impl<'a> ParseFromChunks<'a> for PerpendicularTo_<'a> {
         let mut i = 0;
         let (s, _) = tag("PERPENDICULAR_TO(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -22229,11 +22257,11 @@ impl<'a> HasId for PerpendicularityTolerance_<'a> {
 #[derive(Debug)]
 pub struct Person_<'a> { // entity
     pub id: Identifier<'a>,
-    pub last_name: Option<Label<'a>>,
-    pub first_name: Option<Label<'a>>,
-    pub middle_names: Option<Vec<Label<'a>>>,
-    pub prefix_titles: Option<Vec<Label<'a>>>,
-    pub suffix_titles: Option<Vec<Label<'a>>>,
+    pub last_name: Value<Label<'a>>,
+    pub first_name: Value<Label<'a>>,
+    pub middle_names: Value<Vec<Label<'a>>>,
+    pub prefix_titles: Value<Vec<Label<'a>>>,
+    pub suffix_titles: Value<Vec<Label<'a>>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Person<'a> = Id<Person_<'a>>;
@@ -22250,11 +22278,11 @@ impl<'a> ParseFromChunks<'a> for Person_<'a> {
         let mut i = 0;
         let (s, _) = tag("PERSON(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, last_name) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, first_name) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, middle_names) = param_from_chunks::<Option<Vec<Label<'a>>>>(false, s, &mut i, strs)?;
-        let (s, prefix_titles) = param_from_chunks::<Option<Vec<Label<'a>>>>(false, s, &mut i, strs)?;
-        let (s, suffix_titles) = param_from_chunks::<Option<Vec<Label<'a>>>>(true, s, &mut i, strs)?;
+        let (s, last_name) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, first_name) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, middle_names) = param_from_chunks::<Value<Vec<Label<'a>>>>(false, s, &mut i, strs)?;
+        let (s, prefix_titles) = param_from_chunks::<Value<Vec<Label<'a>>>>(false, s, &mut i, strs)?;
+        let (s, suffix_titles) = param_from_chunks::<Value<Vec<Label<'a>>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             last_name,
@@ -22311,22 +22339,22 @@ impl<'a> HasId for PersonAndOrganization_<'a> {
 #[allow(non_snake_case)]
 #[derive(Debug)]
 pub struct PersonAndOrganizationAddress_<'a> { // entity
-    pub address__internal_location: Option<Label<'a>>,
-    pub address__street_number: Option<Label<'a>>,
-    pub address__street: Option<Label<'a>>,
-    pub address__postal_box: Option<Label<'a>>,
-    pub address__town: Option<Label<'a>>,
-    pub address__region: Option<Label<'a>>,
-    pub address__postal_code: Option<Label<'a>>,
-    pub address__country: Option<Label<'a>>,
-    pub address__facsimile_number: Option<Label<'a>>,
-    pub address__telephone_number: Option<Label<'a>>,
-    pub address__electronic_mail_address: Option<Label<'a>>,
-    pub address__telex_number: Option<Label<'a>>,
+    pub address__internal_location: Value<Label<'a>>,
+    pub address__street_number: Value<Label<'a>>,
+    pub address__street: Value<Label<'a>>,
+    pub address__postal_box: Value<Label<'a>>,
+    pub address__town: Value<Label<'a>>,
+    pub address__region: Value<Label<'a>>,
+    pub address__postal_code: Value<Label<'a>>,
+    pub address__country: Value<Label<'a>>,
+    pub address__facsimile_number: Value<Label<'a>>,
+    pub address__telephone_number: Value<Label<'a>>,
+    pub address__electronic_mail_address: Value<Label<'a>>,
+    pub address__telex_number: Value<Label<'a>>,
     pub organizations: Vec<Organization<'a>>,
-    pub organizational_address__description: Option<Text<'a>>,
+    pub organizational_address__description: Value<Text<'a>>,
     pub people: Vec<Person<'a>>,
-    pub personal_address__description: Option<Text<'a>>,
+    pub personal_address__description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type PersonAndOrganizationAddress<'a> = Id<PersonAndOrganizationAddress_<'a>>;
@@ -22343,35 +22371,35 @@ impl<'a> ParseFromChunks<'a> for PersonAndOrganizationAddress_<'a> {
         let mut i = 0;
         let (s, _) = tag("PERSON_AND_ORGANIZATION_ADDRESS(")(strs[0])?;
         #[allow(non_snake_case)]
-        let (s, address__internal_location) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__internal_location) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__street_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__street_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__street) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__street) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__postal_box) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__postal_box) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__town) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__town) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__region) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__region) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__postal_code) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__postal_code) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__country) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__country) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__facsimile_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__facsimile_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__telephone_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__telephone_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__electronic_mail_address) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__electronic_mail_address) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, address__telex_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, address__telex_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         let (s, organizations) = param_from_chunks::<Vec<Organization<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, organizational_address__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, organizational_address__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, people) = param_from_chunks::<Vec<Person<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, personal_address__description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, personal_address__description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             address__internal_location,
             address__street_number,
@@ -22484,20 +22512,20 @@ pub type PersonOrganizationSelect<'a> = Id<PersonOrganizationSelect_<'a>>;
 
 #[derive(Debug)]
 pub struct PersonalAddress_<'a> { // entity
-    pub internal_location: Option<Label<'a>>,
-    pub street_number: Option<Label<'a>>,
-    pub street: Option<Label<'a>>,
-    pub postal_box: Option<Label<'a>>,
-    pub town: Option<Label<'a>>,
-    pub region: Option<Label<'a>>,
-    pub postal_code: Option<Label<'a>>,
-    pub country: Option<Label<'a>>,
-    pub facsimile_number: Option<Label<'a>>,
-    pub telephone_number: Option<Label<'a>>,
-    pub electronic_mail_address: Option<Label<'a>>,
-    pub telex_number: Option<Label<'a>>,
+    pub internal_location: Value<Label<'a>>,
+    pub street_number: Value<Label<'a>>,
+    pub street: Value<Label<'a>>,
+    pub postal_box: Value<Label<'a>>,
+    pub town: Value<Label<'a>>,
+    pub region: Value<Label<'a>>,
+    pub postal_code: Value<Label<'a>>,
+    pub country: Value<Label<'a>>,
+    pub facsimile_number: Value<Label<'a>>,
+    pub telephone_number: Value<Label<'a>>,
+    pub electronic_mail_address: Value<Label<'a>>,
+    pub telex_number: Value<Label<'a>>,
     pub people: Vec<Person<'a>>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type PersonalAddress<'a> = Id<PersonalAddress_<'a>>;
@@ -22513,20 +22541,20 @@ impl<'a> ParseFromChunks<'a> for PersonalAddress_<'a> {
     fn parse_chunks(strs: &[&'a str]) -> IResult<'a, Self> {
         let mut i = 0;
         let (s, _) = tag("PERSONAL_ADDRESS(")(strs[0])?;
-        let (s, internal_location) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, street_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, street) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, postal_box) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, town) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, region) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, postal_code) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, country) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, facsimile_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, telephone_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, electronic_mail_address) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
-        let (s, telex_number) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, internal_location) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, street_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, street) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, postal_box) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, town) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, region) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, postal_code) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, country) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, facsimile_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, telephone_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, electronic_mail_address) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, telex_number) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         let (s, people) = param_from_chunks::<Vec<Person<'a>>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             internal_location,
             street_number,
@@ -22566,7 +22594,7 @@ impl<'a> HasId for PersonalAddress_<'a> {
 #[derive(Debug)]
 pub struct PhysicallyModelledProductDefinition_<'a> { // entity
     pub id: Identifier<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub formation: ProductDefinitionFormation<'a>,
     pub frame_of_reference: ProductDefinitionContext<'a>,
     pub documentation_ids: Vec<Document<'a>>,
@@ -22586,7 +22614,7 @@ impl<'a> ParseFromChunks<'a> for PhysicallyModelledProductDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("PHYSICALLY_MODELLED_PRODUCT_DEFINITION(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, formation) = param_from_chunks::<ProductDefinitionFormation<'a>>(false, s, &mut i, strs)?;
         let (s, frame_of_reference) = param_from_chunks::<ProductDefinitionContext<'a>>(false, s, &mut i, strs)?;
         let (s, documentation_ids) = param_from_chunks::<Vec<Document<'a>>>(true, s, &mut i, strs)?;
@@ -22611,7 +22639,7 @@ impl<'a> HasId for PhysicallyModelledProductDefinition_<'a> {
 #[derive(Debug)]
 pub struct PlacedDatumTargetFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     pub target_id: Identifier<'a>,
@@ -22631,7 +22659,7 @@ impl<'a> ParseFromChunks<'a> for PlacedDatumTargetFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("PLACED_DATUM_TARGET_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(false, s, &mut i, strs)?;
         let (s, target_id) = param_from_chunks::<Identifier<'a>>(true, s, &mut i, strs)?;
@@ -22656,7 +22684,7 @@ impl<'a> HasId for PlacedDatumTargetFeature_<'a> {
 #[derive(Debug)]
 pub struct PlacedFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -22675,7 +22703,7 @@ impl<'a> ParseFromChunks<'a> for PlacedFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("PLACED_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -22771,7 +22799,7 @@ impl<'a> HasId for PlanarBox_<'a> {
 #[derive(Debug)]
 pub struct PlanarCurvePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -22794,7 +22822,7 @@ impl<'a> ParseFromChunks<'a> for PlanarCurvePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("PLANAR_CURVE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -22902,7 +22930,7 @@ impl<'a> HasId for PlanarExtent_<'a> {
 #[derive(Debug)]
 pub struct PlanarPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -22922,7 +22950,7 @@ impl<'a> ParseFromChunks<'a> for PlanarPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("PLANAR_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -23250,7 +23278,7 @@ impl<'a> HasId for PlusMinusTolerance_<'a> {
 #[derive(Debug)]
 pub struct Pocket_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Pocket<'a> = Id<Pocket_<'a>>;
@@ -23267,7 +23295,7 @@ impl<'a> ParseFromChunks<'a> for Pocket_<'a> {
         let mut i = 0;
         let (s, _) = tag("POCKET(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -23283,7 +23311,7 @@ impl<'a> HasId for Pocket_<'a> {
 #[derive(Debug)]
 pub struct PocketBottom_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -23302,7 +23330,7 @@ impl<'a> ParseFromChunks<'a> for PocketBottom_<'a> {
         let mut i = 0;
         let (s, _) = tag("POCKET_BOTTOM(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -23390,7 +23418,7 @@ impl<'a> HasId for PointOnCurve_<'a> {
 #[derive(Debug)]
 pub struct PointOnPlanarCurvePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -23412,7 +23440,7 @@ impl<'a> ParseFromChunks<'a> for PointOnPlanarCurvePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("POINT_ON_PLANAR_CURVE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -23578,7 +23606,7 @@ impl<'a> HasId for PointOnSurface_<'a> {
 #[derive(Debug)]
 pub struct PointOnSurfacePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -23599,7 +23627,7 @@ impl<'a> ParseFromChunks<'a> for PointOnSurfacePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("POINT_ON_SURFACE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -24397,7 +24425,9 @@ pub enum PreferredSurfaceCurveRepresentation<'a> { // enum
     Curve3d,
     PcurveS1,
     PcurveS2,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for PreferredSurfaceCurveRepresentation<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -24407,7 +24437,7 @@ impl<'a> Parse<'a> for PreferredSurfaceCurveRepresentation<'a> {
             "CURVE_3D" => Curve3d,
             "PCURVE_S1" => PcurveS1,
             "PCURVE_S2" => PcurveS2,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -24813,7 +24843,7 @@ pub type PresentedItemSelect<'a> = Id<PresentedItemSelect_<'a>>;
 #[derive(Debug)]
 pub struct PrismaticPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -24833,7 +24863,7 @@ impl<'a> ParseFromChunks<'a> for PrismaticPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRISMATIC_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -24928,7 +24958,7 @@ impl<'a> HasId for PrismaticPairValue_<'a> {
 #[derive(Debug)]
 pub struct ProcessOperation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub consequence: Text<'a>,
     pub purpose: Text<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -24947,7 +24977,7 @@ impl<'a> ParseFromChunks<'a> for ProcessOperation_<'a> {
         let mut i = 0;
         let (s, _) = tag("PROCESS_OPERATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, consequence) = param_from_chunks::<Text<'a>>(false, s, &mut i, strs)?;
         let (s, purpose) = param_from_chunks::<Text<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -24969,7 +24999,7 @@ impl<'a> HasId for ProcessOperation_<'a> {
 #[derive(Debug)]
 pub struct ProcessPlan_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -24987,7 +25017,7 @@ impl<'a> ParseFromChunks<'a> for ProcessPlan_<'a> {
         let mut i = 0;
         let (s, _) = tag("PROCESS_PLAN(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -25089,7 +25119,7 @@ impl<'a> HasId for ProcessPropertyAssociation_<'a> {
 pub struct Product_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub frame_of_reference: Vec<ProductContext<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -25108,7 +25138,7 @@ impl<'a> ParseFromChunks<'a> for Product_<'a> {
         let (s, _) = tag("PRODUCT(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, frame_of_reference) = param_from_chunks::<Vec<ProductContext<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -25129,7 +25159,7 @@ impl<'a> HasId for Product_<'a> {
 #[derive(Debug)]
 pub struct ProductCategory_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ProductCategory<'a> = Id<ProductCategory_<'a>>;
@@ -25146,7 +25176,7 @@ impl<'a> ParseFromChunks<'a> for ProductCategory_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_CATEGORY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -25162,7 +25192,7 @@ impl<'a> HasId for ProductCategory_<'a> {
 #[derive(Debug)]
 pub struct ProductCategoryRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub category: ProductCategory<'a>,
     pub sub_category: ProductCategory<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25181,7 +25211,7 @@ impl<'a> ParseFromChunks<'a> for ProductCategoryRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_CATEGORY_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, category) = param_from_chunks::<ProductCategory<'a>>(false, s, &mut i, strs)?;
         let (s, sub_category) = param_from_chunks::<ProductCategory<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25205,10 +25235,10 @@ impl<'a> HasId for ProductCategoryRelationship_<'a> {
 pub struct ProductClass_<'a> { // entity
     pub id: Identifier<'a>,
     pub product_concept__name: Label<'a>,
-    pub product_concept__description: Option<Text<'a>>,
+    pub product_concept__description: Value<Text<'a>>,
     pub market_context: ProductConceptContext<'a>,
     pub characterized_object__name: Label<'a>,
-    pub characterized_object__description: Option<Text<'a>>,
+    pub characterized_object__description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ProductClass<'a> = Id<ProductClass_<'a>>;
@@ -25228,12 +25258,12 @@ impl<'a> ParseFromChunks<'a> for ProductClass_<'a> {
         #[allow(non_snake_case)]
         let (s, product_concept__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, product_concept__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, product_concept__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, market_context) = param_from_chunks::<ProductConceptContext<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, characterized_object__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, characterized_object__description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, characterized_object__description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             product_concept__name,
@@ -25258,7 +25288,7 @@ impl<'a> HasId for ProductClass_<'a> {
 pub struct ProductConcept_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub market_context: ProductConceptContext<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -25277,7 +25307,7 @@ impl<'a> ParseFromChunks<'a> for ProductConcept_<'a> {
         let (s, _) = tag("PRODUCT_CONCEPT(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, market_context) = param_from_chunks::<ProductConceptContext<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -25336,7 +25366,7 @@ impl<'a> HasId for ProductConceptContext_<'a> {
 pub struct ProductConceptFeature_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ProductConceptFeature<'a> = Id<ProductConceptFeature_<'a>>;
@@ -25354,7 +25384,7 @@ impl<'a> ParseFromChunks<'a> for ProductConceptFeature_<'a> {
         let (s, _) = tag("PRODUCT_CONCEPT_FEATURE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -25372,7 +25402,7 @@ impl<'a> HasId for ProductConceptFeature_<'a> {
 #[derive(Debug)]
 pub struct ProductConceptFeatureAssociation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub concept: ProductConcept<'a>,
     pub feature: ProductConceptFeature<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25391,7 +25421,7 @@ impl<'a> ParseFromChunks<'a> for ProductConceptFeatureAssociation_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_CONCEPT_FEATURE_ASSOCIATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, concept) = param_from_chunks::<ProductConcept<'a>>(false, s, &mut i, strs)?;
         let (s, feature) = param_from_chunks::<ProductConceptFeature<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25413,7 +25443,7 @@ impl<'a> HasId for ProductConceptFeatureAssociation_<'a> {
 #[derive(Debug)]
 pub struct ProductConceptFeatureCategory_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ProductConceptFeatureCategory<'a> = Id<ProductConceptFeatureCategory_<'a>>;
@@ -25430,7 +25460,7 @@ impl<'a> ParseFromChunks<'a> for ProductConceptFeatureCategory_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_CONCEPT_FEATURE_CATEGORY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -25479,7 +25509,7 @@ impl<'a> HasId for ProductConceptFeatureCategoryUsage_<'a> {
 #[derive(Debug)]
 pub struct ProductConceptRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_concept: ProductConcept<'a>,
     pub related_product_concept: ProductConcept<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25498,7 +25528,7 @@ impl<'a> ParseFromChunks<'a> for ProductConceptRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_CONCEPT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_concept) = param_from_chunks::<ProductConcept<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_concept) = param_from_chunks::<ProductConcept<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25557,7 +25587,7 @@ impl<'a> HasId for ProductContext_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinition_<'a> { // entity
     pub id: Identifier<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub formation: ProductDefinitionFormation<'a>,
     pub frame_of_reference: ProductDefinitionContext<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25576,7 +25606,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, formation) = param_from_chunks::<ProductDefinitionFormation<'a>>(false, s, &mut i, strs)?;
         let (s, frame_of_reference) = param_from_chunks::<ProductDefinitionContext<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25672,7 +25702,7 @@ impl<'a> HasId for ProductDefinitionContextAssociation_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionContextRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ProductDefinitionContextRole<'a> = Id<ProductDefinitionContextRole_<'a>>;
@@ -25689,7 +25719,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionContextRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_CONTEXT_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -25738,7 +25768,7 @@ impl<'a> HasId for ProductDefinitionEffectivity_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionFormation_<'a> { // entity
     pub id: Identifier<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_product: Product<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -25756,7 +25786,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionFormation_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_FORMATION(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_product) = param_from_chunks::<Product<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -25776,7 +25806,7 @@ impl<'a> HasId for ProductDefinitionFormation_<'a> {
 pub struct ProductDefinitionFormationRelationship_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition_formation: ProductDefinitionFormation<'a>,
     pub related_product_definition_formation: ProductDefinitionFormation<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25796,7 +25826,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionFormationRelationship_<'a> {
         let (s, _) = tag("PRODUCT_DEFINITION_FORMATION_RELATIONSHIP(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition_formation) = param_from_chunks::<ProductDefinitionFormation<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition_formation) = param_from_chunks::<ProductDefinitionFormation<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25820,7 +25850,7 @@ impl<'a> HasId for ProductDefinitionFormationRelationship_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionFormationWithSpecifiedSource_<'a> { // entity
     pub id: Identifier<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_product: Product<'a>,
     pub make_or_buy: Source<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25839,7 +25869,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionFormationWithSpecifiedSource_<
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_FORMATION_WITH_SPECIFIED_SOURCE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_product) = param_from_chunks::<Product<'a>>(false, s, &mut i, strs)?;
         let (s, make_or_buy) = param_from_chunks::<Source<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25861,7 +25891,7 @@ impl<'a> HasId for ProductDefinitionFormationWithSpecifiedSource_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionOccurrenceRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub occurrence: ProductDefinition<'a>,
     pub occurrence_usage: AssemblyComponentUsage<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25880,7 +25910,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionOccurrenceRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_OCCURRENCE_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, occurrence) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, occurrence_usage) = param_from_chunks::<AssemblyComponentUsage<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25902,7 +25932,7 @@ impl<'a> HasId for ProductDefinitionOccurrenceRelationship_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionProcess_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     pub identification: Identifier<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25921,7 +25951,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionProcess_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_PROCESS(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(false, s, &mut i, strs)?;
         let (s, identification) = param_from_chunks::<Identifier<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25944,7 +25974,7 @@ impl<'a> HasId for ProductDefinitionProcess_<'a> {
 pub struct ProductDefinitionRelationship_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -25964,7 +25994,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionRelationship_<'a> {
         let (s, _) = tag("PRODUCT_DEFINITION_RELATIONSHIP(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -25989,11 +26019,11 @@ impl<'a> HasId for ProductDefinitionRelationship_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionResource_<'a> { // entity
     pub name: Label<'a>,
-    pub action_resource__description: Option<Text<'a>>,
+    pub action_resource__description: Value<Text<'a>>,
     pub usage: Vec<SupportedItem<'a>>,
     pub kind: ActionResourceType<'a>,
     pub id: Identifier<'a>,
-    pub product_definition__description: Option<Text<'a>>,
+    pub product_definition__description: Value<Text<'a>>,
     pub formation: ProductDefinitionFormation<'a>,
     pub frame_of_reference: ProductDefinitionContext<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -26013,12 +26043,12 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionResource_<'a> {
         let (s, _) = tag("PRODUCT_DEFINITION_RESOURCE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, action_resource__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, action_resource__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, usage) = param_from_chunks::<Vec<SupportedItem<'a>>>(false, s, &mut i, strs)?;
         let (s, kind) = param_from_chunks::<ActionResourceType<'a>>(false, s, &mut i, strs)?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, product_definition__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, product_definition__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, formation) = param_from_chunks::<ProductDefinitionFormation<'a>>(false, s, &mut i, strs)?;
         let (s, frame_of_reference) = param_from_chunks::<ProductDefinitionContext<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -26048,7 +26078,7 @@ impl<'a> HasId for ProductDefinitionResource_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionShape_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: CharacterizedDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -26066,7 +26096,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionShape_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_SHAPE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<CharacterizedDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -26084,7 +26114,7 @@ impl<'a> HasId for ProductDefinitionShape_<'a> {
 }
 #[derive(Debug)]
 pub struct ProductDefinitionSubstitute_<'a> { // entity
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub context_relationship: ProductDefinitionRelationship<'a>,
     pub substitute_definition: ProductDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -26102,7 +26132,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionSubstitute_<'a> {
     fn parse_chunks(strs: &[&'a str]) -> IResult<'a, Self> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_SUBSTITUTE(")(strs[0])?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, context_relationship) = param_from_chunks::<ProductDefinitionRelationship<'a>>(false, s, &mut i, strs)?;
         let (s, substitute_definition) = param_from_chunks::<ProductDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -26123,7 +26153,7 @@ impl<'a> HasId for ProductDefinitionSubstitute_<'a> {
 pub struct ProductDefinitionUsage_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -26143,7 +26173,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionUsage_<'a> {
         let (s, _) = tag("PRODUCT_DEFINITION_USAGE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -26167,7 +26197,7 @@ impl<'a> HasId for ProductDefinitionUsage_<'a> {
 #[derive(Debug)]
 pub struct ProductDefinitionWithAssociatedDocuments_<'a> { // entity
     pub id: Identifier<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub formation: ProductDefinitionFormation<'a>,
     pub frame_of_reference: ProductDefinitionContext<'a>,
     pub documentation_ids: Vec<Document<'a>>,
@@ -26187,7 +26217,7 @@ impl<'a> ParseFromChunks<'a> for ProductDefinitionWithAssociatedDocuments_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_DEFINITION_WITH_ASSOCIATED_DOCUMENTS(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, formation) = param_from_chunks::<ProductDefinitionFormation<'a>>(false, s, &mut i, strs)?;
         let (s, frame_of_reference) = param_from_chunks::<ProductDefinitionContext<'a>>(false, s, &mut i, strs)?;
         let (s, documentation_ids) = param_from_chunks::<Vec<Document<'a>>>(true, s, &mut i, strs)?;
@@ -26214,11 +26244,11 @@ impl<'a> HasId for ProductDefinitionWithAssociatedDocuments_<'a> {
 pub struct ProductIdentification_<'a> { // entity
     pub id: Identifier<'a>,
     pub configuration_item__name: Label<'a>,
-    pub configuration_item__description: Option<Text<'a>>,
+    pub configuration_item__description: Value<Text<'a>>,
     pub item_concept: ProductConcept<'a>,
-    pub purpose: Option<Label<'a>>,
+    pub purpose: Value<Label<'a>>,
     pub characterized_object__name: Label<'a>,
-    pub characterized_object__description: Option<Text<'a>>,
+    pub characterized_object__description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ProductIdentification<'a> = Id<ProductIdentification_<'a>>;
@@ -26238,13 +26268,13 @@ impl<'a> ParseFromChunks<'a> for ProductIdentification_<'a> {
         #[allow(non_snake_case)]
         let (s, configuration_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, configuration_item__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, configuration_item__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_concept) = param_from_chunks::<ProductConcept<'a>>(false, s, &mut i, strs)?;
-        let (s, purpose) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, purpose) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, characterized_object__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, characterized_object__description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, characterized_object__description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             configuration_item__name,
@@ -26275,7 +26305,9 @@ pub type ProductOrFormationOrDefinition<'a> = Id<ProductOrFormationOrDefinition_
 pub enum ProductOrPresentationSpace<'a> { // enum
     ProductShapeSpace,
     PresentationAreaSpace,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for ProductOrPresentationSpace<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -26284,7 +26316,7 @@ impl<'a> Parse<'a> for ProductOrPresentationSpace<'a> {
         let e = match tag {
             "PRODUCT_SHAPE_SPACE" => ProductShapeSpace,
             "PRESENTATION_AREA_SPACE" => PresentationAreaSpace,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -26296,7 +26328,7 @@ impl<'a> HasId for ProductOrPresentationSpace<'a> {
 #[derive(Debug)]
 pub struct ProductProcessPlan_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     pub identification: Identifier<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -26315,7 +26347,7 @@ impl<'a> ParseFromChunks<'a> for ProductProcessPlan_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_PROCESS_PLAN(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(false, s, &mut i, strs)?;
         let (s, identification) = param_from_chunks::<Identifier<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -26337,7 +26369,7 @@ impl<'a> HasId for ProductProcessPlan_<'a> {
 #[derive(Debug)]
 pub struct ProductRelatedProductCategory_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub products: Vec<Product<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -26355,7 +26387,7 @@ impl<'a> ParseFromChunks<'a> for ProductRelatedProductCategory_<'a> {
         let mut i = 0;
         let (s, _) = tag("PRODUCT_RELATED_PRODUCT_CATEGORY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, products) = param_from_chunks::<Vec<Product<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -26376,11 +26408,11 @@ impl<'a> HasId for ProductRelatedProductCategory_<'a> {
 pub struct ProductSpecification_<'a> { // entity
     pub configuration_item__id: Identifier<'a>,
     pub configuration_item__name: Label<'a>,
-    pub configuration_item__description: Option<Text<'a>>,
+    pub configuration_item__description: Value<Text<'a>>,
     pub configuration_item__item_concept: ProductConcept<'a>,
-    pub configuration_item__purpose: Option<Label<'a>>,
+    pub configuration_item__purpose: Value<Label<'a>>,
     pub characterized_object__name: Label<'a>,
-    pub characterized_object__description: Option<Text<'a>>,
+    pub characterized_object__description: Value<Text<'a>>,
     pub item_concept_feature: Vec<ProductConceptFeatureAssociation<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -26402,15 +26434,15 @@ impl<'a> ParseFromChunks<'a> for ProductSpecification_<'a> {
         #[allow(non_snake_case)]
         let (s, configuration_item__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, configuration_item__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, configuration_item__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, configuration_item__item_concept) = param_from_chunks::<ProductConcept<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, configuration_item__purpose) = param_from_chunks::<Option<Label<'a>>>(false, s, &mut i, strs)?;
+        let (s, configuration_item__purpose) = param_from_chunks::<Value<Label<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, characterized_object__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
-        let (s, characterized_object__description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, characterized_object__description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, item_concept_feature) = param_from_chunks::<Vec<ProductConceptFeatureAssociation<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             configuration_item__id,
@@ -26551,10 +26583,10 @@ impl<'a> HasId for ProjectionDirectedCallout_<'a> {
 pub struct PromissoryUsageOccurrence_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
-    pub reference_designator: Option<Identifier<'a>>,
+    pub reference_designator: Value<Identifier<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type PromissoryUsageOccurrence<'a> = Id<PromissoryUsageOccurrence_<'a>>;
@@ -26572,10 +26604,10 @@ impl<'a> ParseFromChunks<'a> for PromissoryUsageOccurrence_<'a> {
         let (s, _) = tag("PROMISSORY_USAGE_OCCURRENCE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
-        let (s, reference_designator) = param_from_chunks::<Option<Identifier<'a>>>(true, s, &mut i, strs)?;
+        let (s, reference_designator) = param_from_chunks::<Value<Identifier<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -26599,7 +26631,7 @@ impl<'a> HasId for PromissoryUsageOccurrence_<'a> {
 #[derive(Debug)]
 pub struct PropertyDefinition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub definition: CharacterizedDefinition<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -26617,7 +26649,7 @@ impl<'a> ParseFromChunks<'a> for PropertyDefinition_<'a> {
         let mut i = 0;
         let (s, _) = tag("PROPERTY_DEFINITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, definition) = param_from_chunks::<CharacterizedDefinition<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -26714,7 +26746,7 @@ pub type PropertyOrShapeSelect<'a> = Id<PropertyOrShapeSelect_<'a>>;
 #[derive(Debug)]
 pub struct PropertyProcess_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     pub identification: Identifier<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -26733,7 +26765,7 @@ impl<'a> ParseFromChunks<'a> for PropertyProcess_<'a> {
         let mut i = 0;
         let (s, _) = tag("PROPERTY_PROCESS(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(false, s, &mut i, strs)?;
         let (s, identification) = param_from_chunks::<Identifier<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -26826,10 +26858,10 @@ impl<'a> HasId for QualitativeUncertainty_<'a> {
 pub struct QuantifiedAssemblyComponentUsage_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
-    pub reference_designator: Option<Identifier<'a>>,
+    pub reference_designator: Value<Identifier<'a>>,
     pub quantity: MeasureWithUnit<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -26848,10 +26880,10 @@ impl<'a> ParseFromChunks<'a> for QuantifiedAssemblyComponentUsage_<'a> {
         let (s, _) = tag("QUANTIFIED_ASSEMBLY_COMPONENT_USAGE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
-        let (s, reference_designator) = param_from_chunks::<Option<Identifier<'a>>>(false, s, &mut i, strs)?;
+        let (s, reference_designator) = param_from_chunks::<Value<Identifier<'a>>>(false, s, &mut i, strs)?;
         let (s, quantity) = param_from_chunks::<MeasureWithUnit<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
@@ -26984,7 +27016,7 @@ impl<'a> HasId for QuasiUniformSurface_<'a> {
 #[derive(Debug)]
 pub struct RackAndPinionPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -27005,7 +27037,7 @@ impl<'a> ParseFromChunks<'a> for RackAndPinionPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("RACK_AND_PINION_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -27400,7 +27432,7 @@ impl<'a> HasId for RealNumericVariable_<'a> {
 #[derive(Debug)]
 pub struct RectangularClosedProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -27419,7 +27451,7 @@ impl<'a> ParseFromChunks<'a> for RectangularClosedProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("RECTANGULAR_CLOSED_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -27474,7 +27506,7 @@ impl<'a> HasId for RectangularCompositeSurface_<'a> {
 #[derive(Debug)]
 pub struct RectangularPattern_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type RectangularPattern<'a> = Id<RectangularPattern_<'a>>;
@@ -27491,7 +27523,7 @@ impl<'a> ParseFromChunks<'a> for RectangularPattern_<'a> {
         let mut i = 0;
         let (s, _) = tag("RECTANGULAR_PATTERN(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -27602,7 +27634,7 @@ impl<'a> HasId for ReferencedModifiedDatum_<'a> {
 pub struct RelativeEventOccurrence_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub base_event: EventOccurrence<'a>,
     pub offset: TimeMeasureWithUnit<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -27622,7 +27654,7 @@ impl<'a> ParseFromChunks<'a> for RelativeEventOccurrence_<'a> {
         let (s, _) = tag("RELATIVE_EVENT_OCCURRENCE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, base_event) = param_from_chunks::<EventOccurrence<'a>>(false, s, &mut i, strs)?;
         let (s, offset) = param_from_chunks::<TimeMeasureWithUnit<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -27651,7 +27683,7 @@ pub type RenderingPropertiesSelect<'a> = Id<RenderingPropertiesSelect_<'a>>;
 #[derive(Debug)]
 pub struct RepItemGroup_<'a> { // entity
     pub group__name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub representation_item__name: Label<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -27670,7 +27702,7 @@ impl<'a> ParseFromChunks<'a> for RepItemGroup_<'a> {
         let (s, _) = tag("REP_ITEM_GROUP(")(strs[0])?;
         #[allow(non_snake_case)]
         let (s, group__name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         #[allow(non_snake_case)]
         let (s, representation_item__name) = param_from_chunks::<Label<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -27731,7 +27763,7 @@ impl<'a> HasId for ReparametrisedCompositeCurveSegment_<'a> {
 #[derive(Debug)]
 pub struct ReplicateFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type ReplicateFeature<'a> = Id<ReplicateFeature_<'a>>;
@@ -27748,7 +27780,7 @@ impl<'a> ParseFromChunks<'a> for ReplicateFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("REPLICATE_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -27896,7 +27928,7 @@ impl<'a> HasId for RepresentationMap_<'a> {
 #[derive(Debug)]
 pub struct RepresentationRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -27915,7 +27947,7 @@ impl<'a> ParseFromChunks<'a> for RepresentationRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("REPRESENTATION_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -27937,7 +27969,7 @@ impl<'a> HasId for RepresentationRelationship_<'a> {
 #[derive(Debug)]
 pub struct RepresentationRelationshipWithTransformation_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     pub transformation_operator: Transformation<'a>,
@@ -27957,7 +27989,7 @@ impl<'a> ParseFromChunks<'a> for RepresentationRelationshipWithTransformation_<'
         let mut i = 0;
         let (s, _) = tag("REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, transformation_operator) = param_from_chunks::<Transformation<'a>>(true, s, &mut i, strs)?;
@@ -28142,7 +28174,7 @@ impl<'a> HasId for ResourceRequirementType_<'a> {
 #[derive(Debug)]
 pub struct ResultingPath_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     pub related_frame: RigidPlacement<'a>,
@@ -28163,7 +28195,7 @@ impl<'a> ParseFromChunks<'a> for ResultingPath_<'a> {
         let mut i = 0;
         let (s, _) = tag("RESULTING_PATH(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, related_frame) = param_from_chunks::<RigidPlacement<'a>>(false, s, &mut i, strs)?;
@@ -28191,7 +28223,7 @@ impl<'a> HasId for ResultingPath_<'a> {
 #[derive(Debug)]
 pub struct Retention_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub chosen_method: ActionMethod<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -28209,7 +28241,7 @@ impl<'a> ParseFromChunks<'a> for Retention_<'a> {
         let mut i = 0;
         let (s, _) = tag("RETENTION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, chosen_method) = param_from_chunks::<ActionMethod<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
@@ -28258,7 +28290,7 @@ pub type ReversibleTopologyItem<'a> = Id<ReversibleTopologyItem_<'a>>;
 #[derive(Debug)]
 pub struct RevolutePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -28278,7 +28310,7 @@ impl<'a> ParseFromChunks<'a> for RevolutePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("REVOLUTE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -28455,7 +28487,7 @@ impl<'a> HasId for RevolvedFaceSolid_<'a> {
 #[derive(Debug)]
 pub struct Rib_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Rib<'a> = Id<Rib_<'a>>;
@@ -28472,7 +28504,7 @@ impl<'a> ParseFromChunks<'a> for Rib_<'a> {
         let mut i = 0;
         let (s, _) = tag("RIB(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -28664,7 +28696,7 @@ pub type RoleSelect<'a> = Id<RoleSelect_<'a>>;
 #[derive(Debug)]
 pub struct RollingCurvePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -28687,7 +28719,7 @@ impl<'a> ParseFromChunks<'a> for RollingCurvePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("ROLLING_CURVE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -28754,7 +28786,7 @@ impl<'a> HasId for RollingCurvePairValue_<'a> {
 #[derive(Debug)]
 pub struct RollingSurfacePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -28777,7 +28809,7 @@ impl<'a> ParseFromChunks<'a> for RollingSurfacePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("ROLLING_SURFACE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -28904,7 +28936,7 @@ impl<'a> HasId for RotationalRangeMeasure<'a> {
 #[derive(Debug)]
 pub struct RoundHole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type RoundHole<'a> = Id<RoundHole_<'a>>;
@@ -28921,7 +28953,7 @@ impl<'a> ParseFromChunks<'a> for RoundHole_<'a> {
         let mut i = 0;
         let (s, _) = tag("ROUND_HOLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -28937,7 +28969,7 @@ impl<'a> HasId for RoundHole_<'a> {
 #[derive(Debug)]
 pub struct RoundedUProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -28956,7 +28988,7 @@ impl<'a> ParseFromChunks<'a> for RoundedUProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("ROUNDED_U_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29167,7 +29199,7 @@ impl<'a> HasId for RunoutZoneOrientationReferenceDirection_<'a> {
 #[derive(Debug)]
 pub struct ScrewPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -29188,7 +29220,7 @@ impl<'a> ParseFromChunks<'a> for ScrewPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("SCREW_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -29487,7 +29519,7 @@ impl<'a> HasId for SecurityClassificationLevel_<'a> {
 pub struct SerialNumberedEffectivity_<'a> { // entity
     pub id: Identifier<'a>,
     pub effectivity_start_id: Identifier<'a>,
-    pub effectivity_end_id: Option<Identifier<'a>>,
+    pub effectivity_end_id: Value<Identifier<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type SerialNumberedEffectivity<'a> = Id<SerialNumberedEffectivity_<'a>>;
@@ -29505,7 +29537,7 @@ impl<'a> ParseFromChunks<'a> for SerialNumberedEffectivity_<'a> {
         let (s, _) = tag("SERIAL_NUMBERED_EFFECTIVITY(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, effectivity_start_id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
-        let (s, effectivity_end_id) = param_from_chunks::<Option<Identifier<'a>>>(true, s, &mut i, strs)?;
+        let (s, effectivity_end_id) = param_from_chunks::<Value<Identifier<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             effectivity_start_id,
@@ -29554,7 +29586,9 @@ impl<'a> HasId for SetRepresentationItem<'a> {
 pub enum ShadingCurveMethod<'a> { // enum
     ConstantColour,
     LinearColour,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for ShadingCurveMethod<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -29563,7 +29597,7 @@ impl<'a> Parse<'a> for ShadingCurveMethod<'a> {
         let e = match tag {
             "CONSTANT_COLOUR" => ConstantColour,
             "LINEAR_COLOUR" => LinearColour,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -29578,7 +29612,9 @@ pub enum ShadingSurfaceMethod<'a> { // enum
     ColourShading,
     DotShading,
     NormalShading,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for ShadingSurfaceMethod<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -29589,7 +29625,7 @@ impl<'a> Parse<'a> for ShadingSurfaceMethod<'a> {
             "COLOUR_SHADING" => ColourShading,
             "DOT_SHADING" => DotShading,
             "NORMAL_SHADING" => NormalShading,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -29601,7 +29637,7 @@ impl<'a> HasId for ShadingSurfaceMethod<'a> {
 #[derive(Debug)]
 pub struct ShapeAspect_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29620,7 +29656,7 @@ impl<'a> ParseFromChunks<'a> for ShapeAspect_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_ASPECT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29642,7 +29678,7 @@ impl<'a> HasId for ShapeAspect_<'a> {
 #[derive(Debug)]
 pub struct ShapeAspectAssociativity_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29661,7 +29697,7 @@ impl<'a> ParseFromChunks<'a> for ShapeAspectAssociativity_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_ASPECT_ASSOCIATIVITY(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29683,7 +29719,7 @@ impl<'a> HasId for ShapeAspectAssociativity_<'a> {
 #[derive(Debug)]
 pub struct ShapeAspectDerivingRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29702,7 +29738,7 @@ impl<'a> ParseFromChunks<'a> for ShapeAspectDerivingRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_ASPECT_DERIVING_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29724,7 +29760,7 @@ impl<'a> HasId for ShapeAspectDerivingRelationship_<'a> {
 #[derive(Debug)]
 pub struct ShapeAspectRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29743,7 +29779,7 @@ impl<'a> ParseFromChunks<'a> for ShapeAspectRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_ASPECT_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29765,7 +29801,7 @@ impl<'a> HasId for ShapeAspectRelationship_<'a> {
 #[derive(Debug)]
 pub struct ShapeAspectTransition_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29784,7 +29820,7 @@ impl<'a> ParseFromChunks<'a> for ShapeAspectTransition_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_ASPECT_TRANSITION(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29806,7 +29842,7 @@ impl<'a> HasId for ShapeAspectTransition_<'a> {
 #[derive(Debug)]
 pub struct ShapeDefiningRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_shape_aspect: ShapeAspect<'a>,
     pub related_shape_aspect: ShapeAspect<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29825,7 +29861,7 @@ impl<'a> ParseFromChunks<'a> for ShapeDefiningRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_DEFINING_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(false, s, &mut i, strs)?;
         let (s, related_shape_aspect) = param_from_chunks::<ShapeAspect<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -29958,7 +29994,7 @@ impl<'a> HasId for ShapeRepresentation_<'a> {
 #[derive(Debug)]
 pub struct ShapeRepresentationRelationship_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub rep_1: Representation<'a>,
     pub rep_2: Representation<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -29977,7 +30013,7 @@ impl<'a> ParseFromChunks<'a> for ShapeRepresentationRelationship_<'a> {
         let mut i = 0;
         let (s, _) = tag("SHAPE_REPRESENTATION_RELATIONSHIP(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, rep_1) = param_from_chunks::<Representation<'a>>(false, s, &mut i, strs)?;
         let (s, rep_2) = param_from_chunks::<Representation<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -30088,7 +30124,9 @@ pub enum SiPrefix<'a> { // enum
     Pico,
     Femto,
     Atto,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for SiPrefix<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -30111,7 +30149,7 @@ impl<'a> Parse<'a> for SiPrefix<'a> {
             "PICO" => Pico,
             "FEMTO" => Femto,
             "ATTO" => Atto,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -30122,7 +30160,7 @@ impl<'a> HasId for SiPrefix<'a> {
 
 #[derive(Debug)]
 pub struct SiUnit_<'a> { // entity
-    pub prefix: Option<SiPrefix<'a>>,
+    pub prefix: Value<SiPrefix<'a>>,
     pub name: SiUnitName<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
@@ -30140,7 +30178,7 @@ impl<'a> ParseFromChunks<'a> for SiUnit_<'a> {
         let mut i = 0;
         let (s, _) = tag("SI_UNIT(")(strs[0])?;
         let (s, _) = param_from_chunks::<Derived>(false, s, &mut i, strs)?;
-        let (s, prefix) = param_from_chunks::<Option<SiPrefix<'a>>>(false, s, &mut i, strs)?;
+        let (s, prefix) = param_from_chunks::<Value<SiPrefix<'a>>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<SiUnitName<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             prefix,
@@ -30184,7 +30222,9 @@ pub enum SiUnitName<'a> { // enum
     Becquerel,
     Gray,
     Sievert,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for SiUnitName<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -30219,7 +30259,7 @@ impl<'a> Parse<'a> for SiUnitName<'a> {
             "BECQUEREL" => Becquerel,
             "GRAY" => Gray,
             "SIEVERT" => Sievert,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -30440,7 +30480,7 @@ impl<'a> HasId for SlashExpression_<'a> {
 #[derive(Debug)]
 pub struct SlidingCurvePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -30463,7 +30503,7 @@ impl<'a> ParseFromChunks<'a> for SlidingCurvePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("SLIDING_CURVE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -30534,7 +30574,7 @@ impl<'a> HasId for SlidingCurvePairValue_<'a> {
 #[derive(Debug)]
 pub struct SlidingSurfacePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -30557,7 +30597,7 @@ impl<'a> ParseFromChunks<'a> for SlidingSurfacePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("SLIDING_SURFACE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -30632,7 +30672,7 @@ impl<'a> HasId for SlidingSurfacePairValue_<'a> {
 #[derive(Debug)]
 pub struct Slot_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Slot<'a> = Id<Slot_<'a>>;
@@ -30649,7 +30689,7 @@ impl<'a> ParseFromChunks<'a> for Slot_<'a> {
         let mut i = 0;
         let (s, _) = tag("SLOT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -30665,7 +30705,7 @@ impl<'a> HasId for Slot_<'a> {
 #[derive(Debug)]
 pub struct SlotEnd_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -30684,7 +30724,7 @@ impl<'a> ParseFromChunks<'a> for SlotEnd_<'a> {
         let mut i = 0;
         let (s, _) = tag("SLOT_END(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -30847,7 +30887,9 @@ pub enum Source<'a> { // enum
     Made,
     Bought,
     NotKnown,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for Source<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -30857,7 +30899,7 @@ impl<'a> Parse<'a> for Source<'a> {
             "MADE" => Made,
             "BOUGHT" => Bought,
             "NOT_KNOWN" => NotKnown,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -30911,10 +30953,10 @@ impl<'a> HasId for SpatialRotation<'a> {
 pub struct SpecifiedHigherUsageOccurrence_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_product_definition: ProductDefinition<'a>,
     pub related_product_definition: ProductDefinition<'a>,
-    pub reference_designator: Option<Identifier<'a>>,
+    pub reference_designator: Value<Identifier<'a>>,
     pub upper_usage: AssemblyComponentUsage<'a>,
     pub next_usage: NextAssemblyUsageOccurrence<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -30934,10 +30976,10 @@ impl<'a> ParseFromChunks<'a> for SpecifiedHigherUsageOccurrence_<'a> {
         let (s, _) = tag("SPECIFIED_HIGHER_USAGE_OCCURRENCE(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
         let (s, related_product_definition) = param_from_chunks::<ProductDefinition<'a>>(false, s, &mut i, strs)?;
-        let (s, reference_designator) = param_from_chunks::<Option<Identifier<'a>>>(false, s, &mut i, strs)?;
+        let (s, reference_designator) = param_from_chunks::<Value<Identifier<'a>>>(false, s, &mut i, strs)?;
         let (s, upper_usage) = param_from_chunks::<AssemblyComponentUsage<'a>>(false, s, &mut i, strs)?;
         let (s, next_usage) = param_from_chunks::<NextAssemblyUsageOccurrence<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -31008,7 +31050,7 @@ impl<'a> HasId for Sphere_<'a> {
 #[derive(Debug)]
 pub struct SphericalPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -31028,7 +31070,7 @@ impl<'a> ParseFromChunks<'a> for SphericalPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("SPHERICAL_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -31229,7 +31271,7 @@ impl<'a> HasId for SquareRootFunction_<'a> {
 #[derive(Debug)]
 pub struct SquareUProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -31248,7 +31290,7 @@ impl<'a> ParseFromChunks<'a> for SquareUProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("SQUARE_U_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -31860,7 +31902,7 @@ impl<'a> HasId for SurfaceOfRevolution_<'a> {
 #[derive(Debug)]
 pub struct SurfacePair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -31883,7 +31925,7 @@ impl<'a> ParseFromChunks<'a> for SurfacePair_<'a> {
         let mut i = 0;
         let (s, _) = tag("SURFACE_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
@@ -32116,7 +32158,9 @@ pub enum SurfaceSide<'a> { // enum
     Positive,
     Negative,
     Both,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for SurfaceSide<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -32126,7 +32170,7 @@ impl<'a> Parse<'a> for SurfaceSide<'a> {
             "POSITIVE" => Positive,
             "NEGATIVE" => Negative,
             "BOTH" => Both,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -32693,7 +32737,7 @@ pub struct SweptDiskSolid_<'a> { // entity
     pub name: Label<'a>,
     pub directrix: Curve<'a>,
     pub radius: PositiveLengthMeasure<'a>,
-    pub inner_radius: Option<PositiveLengthMeasure<'a>>,
+    pub inner_radius: Value<PositiveLengthMeasure<'a>>,
     pub start_param: f64,
     pub end_param: f64,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -32714,7 +32758,7 @@ impl<'a> ParseFromChunks<'a> for SweptDiskSolid_<'a> {
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         let (s, directrix) = param_from_chunks::<Curve<'a>>(false, s, &mut i, strs)?;
         let (s, radius) = param_from_chunks::<PositiveLengthMeasure<'a>>(false, s, &mut i, strs)?;
-        let (s, inner_radius) = param_from_chunks::<Option<PositiveLengthMeasure<'a>>>(false, s, &mut i, strs)?;
+        let (s, inner_radius) = param_from_chunks::<Value<PositiveLengthMeasure<'a>>>(false, s, &mut i, strs)?;
         let (s, start_param) = param_from_chunks::<f64>(false, s, &mut i, strs)?;
         let (s, end_param) = param_from_chunks::<f64>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -32983,7 +33027,7 @@ impl<'a> HasId for SymbolTarget_<'a> {
 #[derive(Debug)]
 pub struct SymmetricShapeAspect_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -33002,7 +33046,7 @@ impl<'a> ParseFromChunks<'a> for SymmetricShapeAspect_<'a> {
         let mut i = 0;
         let (s, _) = tag("SYMMETRIC_SHAPE_ASPECT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -33135,7 +33179,7 @@ impl<'a> HasId for TanFunction_<'a> {
 #[derive(Debug)]
 pub struct Tangent_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -33154,7 +33198,7 @@ impl<'a> ParseFromChunks<'a> for Tangent_<'a> {
         let mut i = 0;
         let (s, _) = tag("TANGENT(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -33176,7 +33220,7 @@ impl<'a> HasId for Tangent_<'a> {
 #[derive(Debug)]
 pub struct Taper_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -33195,7 +33239,7 @@ impl<'a> ParseFromChunks<'a> for Taper_<'a> {
         let mut i = 0;
         let (s, _) = tag("TAPER(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -33217,7 +33261,7 @@ impl<'a> HasId for Taper_<'a> {
 #[derive(Debug)]
 pub struct TeeProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -33236,7 +33280,7 @@ impl<'a> ParseFromChunks<'a> for TeeProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("TEE_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -33606,7 +33650,9 @@ pub enum TextPath<'a> { // enum
     Right,
     Up,
     Down,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for TextPath<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -33617,7 +33663,7 @@ impl<'a> Parse<'a> for TextPath<'a> {
             "RIGHT" => Right,
             "UP" => Up,
             "DOWN" => Down,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -33916,7 +33962,7 @@ impl<'a> HasId for ThermodynamicTemperatureUnit_<'a> {
 #[derive(Debug)]
 pub struct Thread_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type Thread<'a> = Id<Thread_<'a>>;
@@ -33933,7 +33979,7 @@ impl<'a> ParseFromChunks<'a> for Thread_<'a> {
         let mut i = 0;
         let (s, _) = tag("THREAD(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -33950,7 +33996,7 @@ impl<'a> HasId for Thread_<'a> {
 pub struct TimeInterval_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type TimeInterval<'a> = Id<TimeInterval_<'a>>;
@@ -33968,7 +34014,7 @@ impl<'a> ParseFromChunks<'a> for TimeInterval_<'a> {
         let (s, _) = tag("TIME_INTERVAL(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -34056,7 +34102,7 @@ pub type TimeIntervalItem<'a> = Id<TimeIntervalItem_<'a>>;
 #[derive(Debug)]
 pub struct TimeIntervalRole_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type TimeIntervalRole<'a> = Id<TimeIntervalRole_<'a>>;
@@ -34073,7 +34119,7 @@ impl<'a> ParseFromChunks<'a> for TimeIntervalRole_<'a> {
         let mut i = 0;
         let (s, _) = tag("TIME_INTERVAL_ROLE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -34090,10 +34136,10 @@ impl<'a> HasId for TimeIntervalRole_<'a> {
 pub struct TimeIntervalWithBounds_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
-    pub primary_bound: Option<DateTimeOrEventOccurrence<'a>>,
-    pub secondary_bound: Option<DateTimeOrEventOccurrence<'a>>,
-    pub duration: Option<TimeMeasureWithUnit<'a>>,
+    pub description: Value<Text<'a>>,
+    pub primary_bound: Value<DateTimeOrEventOccurrence<'a>>,
+    pub secondary_bound: Value<DateTimeOrEventOccurrence<'a>>,
+    pub duration: Value<TimeMeasureWithUnit<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type TimeIntervalWithBounds<'a> = Id<TimeIntervalWithBounds_<'a>>;
@@ -34111,10 +34157,10 @@ impl<'a> ParseFromChunks<'a> for TimeIntervalWithBounds_<'a> {
         let (s, _) = tag("TIME_INTERVAL_WITH_BOUNDS(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
-        let (s, primary_bound) = param_from_chunks::<Option<DateTimeOrEventOccurrence<'a>>>(false, s, &mut i, strs)?;
-        let (s, secondary_bound) = param_from_chunks::<Option<DateTimeOrEventOccurrence<'a>>>(false, s, &mut i, strs)?;
-        let (s, duration) = param_from_chunks::<Option<TimeMeasureWithUnit<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, primary_bound) = param_from_chunks::<Value<DateTimeOrEventOccurrence<'a>>>(false, s, &mut i, strs)?;
+        let (s, secondary_bound) = param_from_chunks::<Value<DateTimeOrEventOccurrence<'a>>>(false, s, &mut i, strs)?;
+        let (s, duration) = param_from_chunks::<Value<TimeMeasureWithUnit<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             name,
@@ -34298,7 +34344,7 @@ impl<'a> HasId for ToleranceValue_<'a> {
 #[derive(Debug)]
 pub struct ToleranceZone_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     pub defining_tolerance: Vec<GeometricTolerance<'a>>,
@@ -34319,7 +34365,7 @@ impl<'a> ParseFromChunks<'a> for ToleranceZone_<'a> {
         let mut i = 0;
         let (s, _) = tag("TOLERANCE_ZONE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(false, s, &mut i, strs)?;
         let (s, defining_tolerance) = param_from_chunks::<Vec<GeometricTolerance<'a>>>(false, s, &mut i, strs)?;
@@ -34572,7 +34618,9 @@ pub enum TransitionCode<'a> { // enum
     Continuous,
     ContSameGradient,
     ContSameGradientSameCurvature,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for TransitionCode<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -34583,7 +34631,7 @@ impl<'a> Parse<'a> for TransitionCode<'a> {
             "CONTINUOUS" => Continuous,
             "CONT_SAME_GRADIENT" => ContSameGradient,
             "CONT_SAME_GRADIENT_SAME_CURVATURE" => ContSameGradientSameCurvature,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -34595,7 +34643,7 @@ impl<'a> HasId for TransitionCode<'a> {
 #[derive(Debug)]
 pub struct TransitionFeature_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -34614,7 +34662,7 @@ impl<'a> ParseFromChunks<'a> for TransitionFeature_<'a> {
         let mut i = 0;
         let (s, _) = tag("TRANSITION_FEATURE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -34710,7 +34758,9 @@ pub enum TrimmingPreference<'a> { // enum
     Cartesian,
     Parameter,
     Unspecified,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for TrimmingPreference<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -34720,7 +34770,7 @@ impl<'a> Parse<'a> for TrimmingPreference<'a> {
             "CARTESIAN" => Cartesian,
             "PARAMETER" => Parameter,
             "UNSPECIFIED" => Unspecified,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -34991,7 +35041,7 @@ pub struct UncertaintyMeasureWithUnit_<'a> { // entity
     pub value_component: MeasureValue<'a>,
     pub unit_component: Unit<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type UncertaintyMeasureWithUnit<'a> = Id<UncertaintyMeasureWithUnit_<'a>>;
@@ -35010,7 +35060,7 @@ impl<'a> ParseFromChunks<'a> for UncertaintyMeasureWithUnit_<'a> {
         let (s, value_component) = param_from_chunks::<MeasureValue<'a>>(false, s, &mut i, strs)?;
         let (s, unit_component) = param_from_chunks::<Unit<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             value_component,
             unit_component,
@@ -35063,7 +35113,7 @@ impl<'a> HasId for UncertaintyQualifier_<'a> {
 #[derive(Debug)]
 pub struct UnconstrainedPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
@@ -35083,7 +35133,7 @@ impl<'a> ParseFromChunks<'a> for UnconstrainedPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("UNCONSTRAINED_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(true, s, &mut i, strs)?;
@@ -35251,11 +35301,11 @@ pub type Unit<'a> = Id<Unit_<'a>>;
 #[derive(Debug)]
 pub struct UniversalPair_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub transform_item_1: RepresentationItem<'a>,
     pub transform_item_2: RepresentationItem<'a>,
     pub joint: KinematicJoint<'a>,
-    pub input_skew_angle: Option<PlaneAngleMeasure<'a>>,
+    pub input_skew_angle: Value<PlaneAngleMeasure<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type UniversalPair<'a> = Id<UniversalPair_<'a>>;
@@ -35272,11 +35322,11 @@ impl<'a> ParseFromChunks<'a> for UniversalPair_<'a> {
         let mut i = 0;
         let (s, _) = tag("UNIVERSAL_PAIR(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, transform_item_1) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, transform_item_2) = param_from_chunks::<RepresentationItem<'a>>(false, s, &mut i, strs)?;
         let (s, joint) = param_from_chunks::<KinematicJoint<'a>>(false, s, &mut i, strs)?;
-        let (s, input_skew_angle) = param_from_chunks::<Option<PlaneAngleMeasure<'a>>>(true, s, &mut i, strs)?;
+        let (s, input_skew_angle) = param_from_chunks::<Value<PlaneAngleMeasure<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             name,
             description,
@@ -35382,7 +35432,9 @@ impl<'a> HasId for UniversalPairValue_<'a> {
 #[derive(Debug)]
 pub enum UnlimitedRange<'a> { // enum
     Unlimited,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for UnlimitedRange<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -35390,7 +35442,7 @@ impl<'a> Parse<'a> for UnlimitedRange<'a> {
         let (s, tag) = parse_enum_tag(s)?;
         let e = match tag {
             "UNLIMITED" => Unlimited,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -35649,7 +35701,7 @@ impl<'a> HasId for VectorStyle_<'a> {
 #[derive(Debug)]
 pub struct VeeProfile_<'a> { // entity
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub of_shape: ProductDefinitionShape<'a>,
     pub product_definitional: Logical,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -35668,7 +35720,7 @@ impl<'a> ParseFromChunks<'a> for VeeProfile_<'a> {
         let mut i = 0;
         let (s, _) = tag("VEE_PROFILE(")(strs[0])?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, of_shape) = param_from_chunks::<ProductDefinitionShape<'a>>(false, s, &mut i, strs)?;
         let (s, product_definitional) = param_from_chunks::<Logical>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -35692,7 +35744,7 @@ pub struct VersionedActionRequest_<'a> { // entity
     pub id: Identifier<'a>,
     pub version: Label<'a>,
     pub purpose: Text<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     _marker: std::marker::PhantomData<&'a ()>,
 }
 pub type VersionedActionRequest<'a> = Id<VersionedActionRequest_<'a>>;
@@ -35711,7 +35763,7 @@ impl<'a> ParseFromChunks<'a> for VersionedActionRequest_<'a> {
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, version) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
         let (s, purpose) = param_from_chunks::<Text<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(true, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(true, s, &mut i, strs)?;
         Ok((s, Self {
             id,
             version,
@@ -35732,7 +35784,7 @@ impl<'a> HasId for VersionedActionRequest_<'a> {
 pub struct VersionedActionRequestRelationship_<'a> { // entity
     pub id: Identifier<'a>,
     pub name: Label<'a>,
-    pub description: Option<Text<'a>>,
+    pub description: Value<Text<'a>>,
     pub relating_versioned_action_request: VersionedActionRequest<'a>,
     pub related_versioned_action_request: VersionedActionRequest<'a>,
     _marker: std::marker::PhantomData<&'a ()>,
@@ -35752,7 +35804,7 @@ impl<'a> ParseFromChunks<'a> for VersionedActionRequestRelationship_<'a> {
         let (s, _) = tag("VERSIONED_ACTION_REQUEST_RELATIONSHIP(")(strs[0])?;
         let (s, id) = param_from_chunks::<Identifier<'a>>(false, s, &mut i, strs)?;
         let (s, name) = param_from_chunks::<Label<'a>>(false, s, &mut i, strs)?;
-        let (s, description) = param_from_chunks::<Option<Text<'a>>>(false, s, &mut i, strs)?;
+        let (s, description) = param_from_chunks::<Value<Text<'a>>>(false, s, &mut i, strs)?;
         let (s, relating_versioned_action_request) = param_from_chunks::<VersionedActionRequest<'a>>(false, s, &mut i, strs)?;
         let (s, related_versioned_action_request) = param_from_chunks::<VersionedActionRequest<'a>>(true, s, &mut i, strs)?;
         Ok((s, Self {
@@ -36086,7 +36138,9 @@ pub enum YprEnumeration<'a> { // enum
     Yaw,
     Pitch,
     Roll,
-    _Unused(std::marker::PhantomData<&'a ()>),
+    /// Value which isn't part of the schema, e.g. from a typo or
+    /// an extended enumeration
+    Unknown(&'a str),
 }
 impl<'a> Parse<'a> for YprEnumeration<'a> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
@@ -36096,7 +36150,7 @@ impl<'a> Parse<'a> for YprEnumeration<'a> {
             "YAW" => Yaw,
             "PITCH" => Pitch,
             "ROLL" => Roll,
-            _ => return nom_alt_err(s),
+            t => Unknown(t),
         };
         Ok((s, e))
     }
@@ -38679,6 +38733,4590 @@ pub fn superclasses_of(s: &str) -> &[&str] {
         _ => &[],
     }
 }
+
+/// Returns the name, kind, and optionality of each attribute of an entity,
+/// in the order in which they're written in a STEP file, or `None` if the
+/// entity isn't part of this schema.
+pub fn attributes_of(s: &str)
+    -> Option<&'static [(&'static str, ParamKind, bool)]>
+{
+    use ParamKind::*;
+    let out: &'static [(&'static str, ParamKind, bool)] = match s {
+        "ABS_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "ACOS_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "ACTION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+        ],
+        "ACTION_ASSIGNMENT" => &[
+            ("assigned_action", Ref, false),
+        ],
+        "ACTION_DIRECTIVE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("analysis", Str, false),
+            ("comment", Str, false),
+            ("requests", List, false),
+        ],
+        "ACTION_METHOD" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("consequence", Str, false),
+            ("purpose", Str, false),
+        ],
+        "ACTION_METHOD_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_method", Ref, false),
+            ("related_method", Ref, false),
+        ],
+        "ACTION_PROPERTY" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("definition", Ref, false),
+        ],
+        "ACTION_PROPERTY_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("property", Ref, false),
+            ("representation", Ref, false),
+        ],
+        "ACTION_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_action", Ref, false),
+            ("related_action", Ref, false),
+        ],
+        "ACTION_REQUEST_ASSIGNMENT" => &[
+            ("assigned_action_request", Ref, false),
+        ],
+        "ACTION_REQUEST_SOLUTION" => &[
+            ("method", Ref, false),
+            ("request", Ref, false),
+        ],
+        "ACTION_REQUEST_STATUS" => &[
+            ("status", Str, false),
+            ("assigned_request", Ref, false),
+        ],
+        "ACTION_RESOURCE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("usage", List, false),
+            ("kind", Ref, false),
+        ],
+        "ACTION_RESOURCE_REQUIREMENT" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("kind", Ref, false),
+            ("operations", List, false),
+        ],
+        "ACTION_RESOURCE_TYPE" => &[
+            ("name", Str, false),
+        ],
+        "ACTION_STATUS" => &[
+            ("status", Str, false),
+            ("assigned_action", Ref, false),
+        ],
+        "ADDRESS" => &[
+            ("internal_location", Str, true),
+            ("street_number", Str, true),
+            ("street", Str, true),
+            ("postal_box", Str, true),
+            ("town", Str, true),
+            ("region", Str, true),
+            ("postal_code", Str, true),
+            ("country", Str, true),
+            ("facsimile_number", Str, true),
+            ("telephone_number", Str, true),
+            ("electronic_mail_address", Str, true),
+            ("telex_number", Str, true),
+        ],
+        "ADVANCED_BREP_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "ADVANCED_FACE" => &[
+            ("name", Str, false),
+            ("bounds", List, false),
+            ("face_geometry", Ref, false),
+            ("same_sense", Logical, false),
+        ],
+        "ALTERNATE_PRODUCT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("definition", Str, true),
+            ("alternate", Ref, false),
+            ("base", Ref, false),
+            ("basis", Str, false),
+        ],
+        "AMOUNT_OF_SUBSTANCE_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "AMOUNT_OF_SUBSTANCE_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "AND_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "ANGULAR_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "ANGULAR_LOCATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+            ("angle_selection", Enum, false),
+        ],
+        "ANGULAR_SIZE" => &[
+            ("applies_to", Ref, false),
+            ("name", Str, false),
+            ("angle_selection", Enum, false),
+        ],
+        "ANGULARITY_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "ANNOTATION_CURVE_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "ANNOTATION_FILL_AREA" => &[
+            ("name", Str, false),
+            ("boundaries", List, false),
+        ],
+        "ANNOTATION_FILL_AREA_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("fill_style_target", Ref, false),
+        ],
+        "ANNOTATION_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "ANNOTATION_OCCURRENCE_ASSOCIATIVITY" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_annotation_occurrence", Ref, false),
+            ("related_annotation_occurrence", Ref, false),
+        ],
+        "ANNOTATION_OCCURRENCE_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_annotation_occurrence", Ref, false),
+            ("related_annotation_occurrence", Ref, false),
+        ],
+        "ANNOTATION_PLANE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("elements", List, true),
+        ],
+        "ANNOTATION_SUBFIGURE_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "ANNOTATION_SYMBOL" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "ANNOTATION_SYMBOL_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "ANNOTATION_TEXT" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "ANNOTATION_TEXT_CHARACTER" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+            ("alignment", Str, false),
+        ],
+        "ANNOTATION_TEXT_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "APEX" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "APPLICATION_CONTEXT" => &[
+            ("application", Str, false),
+        ],
+        "APPLICATION_CONTEXT_ELEMENT" => &[
+            ("name", Str, false),
+            ("frame_of_reference", Ref, false),
+        ],
+        "APPLICATION_CONTEXT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_context", Ref, false),
+            ("related_context", Ref, false),
+        ],
+        "APPLICATION_PROTOCOL_DEFINITION" => &[
+            ("status", Str, false),
+            ("application_interpreted_model_schema_name", Str, false),
+            ("application_protocol_year", Integer, false),
+            ("application", Ref, false),
+        ],
+        "APPLIED_ACTION_ASSIGNMENT" => &[
+            ("assigned_action", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_ACTION_REQUEST_ASSIGNMENT" => &[
+            ("assigned_action_request", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_APPROVAL_ASSIGNMENT" => &[
+            ("assigned_approval", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_AREA" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "APPLIED_CERTIFICATION_ASSIGNMENT" => &[
+            ("assigned_certification", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_CLASSIFICATION_ASSIGNMENT" => &[
+            ("assigned_class", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_CONTRACT_ASSIGNMENT" => &[
+            ("assigned_contract", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_DATE_AND_TIME_ASSIGNMENT" => &[
+            ("assigned_date_and_time", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_DATE_ASSIGNMENT" => &[
+            ("assigned_date", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_DOCUMENT_REFERENCE" => &[
+            ("assigned_document", Ref, false),
+            ("source", Str, false),
+            ("items", List, false),
+        ],
+        "APPLIED_DOCUMENT_USAGE_CONSTRAINT_ASSIGNMENT" => &[
+            ("assigned_document_usage", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_EFFECTIVITY_ASSIGNMENT" => &[
+            ("assigned_effectivity", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_EVENT_OCCURRENCE_ASSIGNMENT" => &[
+            ("assigned_event_occurrence", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_EXTERNAL_IDENTIFICATION_ASSIGNMENT" => &[
+            ("assigned_id", Str, false),
+            ("role", Ref, false),
+            ("source", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_GROUP_ASSIGNMENT" => &[
+            ("assigned_group", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_IDENTIFICATION_ASSIGNMENT" => &[
+            ("assigned_id", Str, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_INEFFECTIVITY_ASSIGNMENT" => &[
+            ("assigned_effectivity", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_NAME_ASSIGNMENT" => &[
+            ("assigned_name", Str, false),
+            ("items", List, false),
+        ],
+        "APPLIED_ORGANIZATION_ASSIGNMENT" => &[
+            ("assigned_organization", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_ORGANIZATIONAL_PROJECT_ASSIGNMENT" => &[
+            ("assigned_organizational_project", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_PERSON_AND_ORGANIZATION_ASSIGNMENT" => &[
+            ("assigned_person_and_organization", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_PRESENTED_ITEM" => &[
+            ("items", List, false),
+        ],
+        "APPLIED_SECURITY_CLASSIFICATION_ASSIGNMENT" => &[
+            ("assigned_security_classification", Ref, false),
+            ("items", List, false),
+        ],
+        "APPLIED_TIME_INTERVAL_ASSIGNMENT" => &[
+            ("assigned_time_interval", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "APPROVAL" => &[
+            ("status", Ref, false),
+            ("level", Str, false),
+        ],
+        "APPROVAL_ASSIGNMENT" => &[
+            ("assigned_approval", Ref, false),
+        ],
+        "APPROVAL_DATE_TIME" => &[
+            ("date_time", Ref, false),
+            ("dated_approval", Ref, false),
+        ],
+        "APPROVAL_PERSON_ORGANIZATION" => &[
+            ("person_organization", Ref, false),
+            ("authorized_approval", Ref, false),
+            ("role", Ref, false),
+        ],
+        "APPROVAL_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_approval", Ref, false),
+            ("related_approval", Ref, false),
+        ],
+        "APPROVAL_ROLE" => &[
+            ("role", Str, false),
+        ],
+        "APPROVAL_STATUS" => &[
+            ("name", Str, false),
+        ],
+        "APPROXIMATION_TOLERANCE" => &[
+            ("tolerance", Ref, false),
+        ],
+        "APPROXIMATION_TOLERANCE_DEVIATION" => &[
+            ("tessellation_type", Enum, false),
+            ("tolerances", List, false),
+            ("definition_space", Enum, false),
+        ],
+        "APPROXIMATION_TOLERANCE_PARAMETER" => &[
+            ("tolerances", List, false),
+        ],
+        "AREA_IN_SET" => &[
+            ("area", Ref, false),
+            ("in_set", Ref, false),
+        ],
+        "AREA_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "AREA_UNIT" => &[
+            ("elements", List, false),
+        ],
+        "ASIN_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "ASSEMBLY_COMPONENT_USAGE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+            ("reference_designator", Str, true),
+        ],
+        "ASSEMBLY_COMPONENT_USAGE_SUBSTITUTE" => &[
+            ("name", Str, false),
+            ("definition", Str, true),
+            ("base", Ref, false),
+            ("substitute", Ref, false),
+        ],
+        "ATAN_FUNCTION" => &[
+            ("operands", List, false),
+        ],
+        "ATTRIBUTE_CLASSIFICATION_ASSIGNMENT" => &[
+            ("assigned_class", Ref, false),
+            ("attribute_name", Str, false),
+            ("role", Ref, false),
+        ],
+        "ATTRIBUTE_LANGUAGE_ASSIGNMENT" => &[
+            ("assigned_class", Ref, false),
+            ("attribute_name", Str, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "ATTRIBUTE_VALUE_ASSIGNMENT" => &[
+            ("attribute_name", Str, false),
+            ("attribute_value", Any, false),
+            ("role", Ref, false),
+        ],
+        "ATTRIBUTE_VALUE_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "AXIS1_PLACEMENT" => &[
+            ("name", Str, false),
+            ("location", Ref, false),
+            ("axis", Ref, true),
+        ],
+        "AXIS2_PLACEMENT_2D" => &[
+            ("name", Str, false),
+            ("location", Ref, false),
+            ("ref_direction", Ref, true),
+        ],
+        "AXIS2_PLACEMENT_3D" => &[
+            ("name", Str, false),
+            ("location", Ref, false),
+            ("axis", Ref, true),
+            ("ref_direction", Ref, true),
+        ],
+        "B_SPLINE_CURVE" => &[
+            ("name", Str, false),
+            ("degree", Integer, false),
+            ("control_points_list", List, false),
+            ("curve_form", Enum, false),
+            ("closed_curve", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "B_SPLINE_CURVE_WITH_KNOTS" => &[
+            ("name", Str, false),
+            ("degree", Integer, false),
+            ("control_points_list", List, false),
+            ("curve_form", Enum, false),
+            ("closed_curve", Logical, false),
+            ("self_intersect", Logical, false),
+            ("knot_multiplicities", List, false),
+            ("knots", List, false),
+            ("knot_spec", Enum, false),
+        ],
+        "B_SPLINE_SURFACE" => &[
+            ("name", Str, false),
+            ("u_degree", Integer, false),
+            ("v_degree", Integer, false),
+            ("control_points_list", List, false),
+            ("surface_form", Enum, false),
+            ("u_closed", Logical, false),
+            ("v_closed", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "B_SPLINE_SURFACE_WITH_KNOTS" => &[
+            ("name", Str, false),
+            ("u_degree", Integer, false),
+            ("v_degree", Integer, false),
+            ("control_points_list", List, false),
+            ("surface_form", Enum, false),
+            ("u_closed", Logical, false),
+            ("v_closed", Logical, false),
+            ("self_intersect", Logical, false),
+            ("u_multiplicities", List, false),
+            ("v_multiplicities", List, false),
+            ("u_knots", List, false),
+            ("v_knots", List, false),
+            ("knot_spec", Enum, false),
+        ],
+        "BACKGROUND_COLOUR" => &[
+            ("presentation", Ref, false),
+        ],
+        "BARRING_HOLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "BEAD" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "BEAD_END" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "BEZIER_CURVE" => &[
+            ("name", Str, false),
+            ("degree", Integer, false),
+            ("control_points_list", List, false),
+            ("curve_form", Enum, false),
+            ("closed_curve", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "BEZIER_SURFACE" => &[
+            ("name", Str, false),
+            ("u_degree", Integer, false),
+            ("v_degree", Integer, false),
+            ("control_points_list", List, false),
+            ("surface_form", Enum, false),
+            ("u_closed", Logical, false),
+            ("v_closed", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "BINARY_BOOLEAN_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "BINARY_FUNCTION_CALL" => &[
+            ("operands", List, false),
+        ],
+        "BINARY_GENERIC_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "BINARY_NUMERIC_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "BLOCK" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("x", Real, false),
+            ("y", Real, false),
+            ("z", Real, false),
+        ],
+        "BOOLEAN_DEFINED_FUNCTION" => &[
+        ],
+        "BOOLEAN_EXPRESSION" => &[
+        ],
+        "BOOLEAN_LITERAL" => &[
+            ("the_value", Logical, false),
+        ],
+        "BOOLEAN_RESULT" => &[
+            ("name", Str, false),
+            ("operator", Enum, false),
+            ("first_operand", Ref, false),
+            ("second_operand", Ref, false),
+        ],
+        "BOOLEAN_VARIABLE" => &[
+        ],
+        "BOSS" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "BOSS_TOP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "BOUNDARY_CURVE" => &[
+            ("name", Str, false),
+            ("segments", List, false),
+            ("self_intersect", Logical, false),
+        ],
+        "BOUNDED_CURVE" => &[
+            ("name", Str, false),
+        ],
+        "BOUNDED_PCURVE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("reference_to_curve", Ref, false),
+        ],
+        "BOUNDED_SURFACE" => &[
+            ("name", Str, false),
+        ],
+        "BOUNDED_SURFACE_CURVE" => &[
+            ("name", Str, false),
+            ("curve_3d", Ref, false),
+            ("associated_geometry", List, false),
+            ("master_representation", Enum, false),
+        ],
+        "BOX_DOMAIN" => &[
+            ("corner", Ref, false),
+            ("xlength", Real, false),
+            ("ylength", Real, false),
+            ("zlength", Real, false),
+        ],
+        "BOXED_HALF_SPACE" => &[
+            ("name", Str, false),
+            ("base_surface", Ref, false),
+            ("agreement_flag", Logical, false),
+            ("enclosure", Ref, false),
+        ],
+        "BREP_WITH_VOIDS" => &[
+            ("name", Str, false),
+            ("outer", Ref, false),
+            ("voids", List, false),
+        ],
+        "CALENDAR_DATE" => &[
+            ("year_component", Integer, false),
+            ("day_component", Integer, false),
+            ("month_component", Integer, false),
+        ],
+        "CAMERA_IMAGE" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "CAMERA_IMAGE_2D_WITH_SCALE" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "CAMERA_IMAGE_3D_WITH_SCALE" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "CAMERA_MODEL" => &[
+            ("name", Str, false),
+        ],
+        "CAMERA_MODEL_D2" => &[
+            ("name", Str, false),
+            ("view_window", Ref, false),
+            ("view_window_clipping", Logical, false),
+        ],
+        "CAMERA_MODEL_D3" => &[
+            ("name", Str, false),
+            ("view_reference_system", Ref, false),
+            ("perspective_of_volume", Ref, false),
+        ],
+        "CAMERA_MODEL_D3_WITH_HLHSR" => &[
+            ("name", Str, false),
+            ("view_reference_system", Ref, false),
+            ("perspective_of_volume", Ref, false),
+            ("hidden_line_surface_removal", Logical, false),
+        ],
+        "CAMERA_USAGE" => &[
+            ("mapping_origin", Ref, false),
+            ("mapped_representation", Ref, false),
+        ],
+        "CARTESIAN_POINT" => &[
+            ("name", Str, false),
+            ("coordinates", List, false),
+        ],
+        "CARTESIAN_TRANSFORMATION_OPERATOR" => &[
+            ("name", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("axis1", Ref, true),
+            ("axis2", Ref, true),
+            ("local_origin", Ref, false),
+            ("scale", Real, true),
+        ],
+        "CARTESIAN_TRANSFORMATION_OPERATOR_2D" => &[
+            ("name", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("axis1", Ref, true),
+            ("axis2", Ref, true),
+            ("local_origin", Ref, false),
+            ("scale", Real, true),
+        ],
+        "CARTESIAN_TRANSFORMATION_OPERATOR_3D" => &[
+            ("name", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("axis1", Ref, true),
+            ("axis2", Ref, true),
+            ("local_origin", Ref, false),
+            ("scale", Real, true),
+            ("axis3", Ref, true),
+        ],
+        "CELSIUS_TEMPERATURE_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "CENTRE_OF_SYMMETRY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "CERTIFICATION" => &[
+            ("name", Str, false),
+            ("purpose", Str, false),
+            ("kind", Ref, false),
+        ],
+        "CERTIFICATION_ASSIGNMENT" => &[
+            ("assigned_certification", Ref, false),
+        ],
+        "CERTIFICATION_TYPE" => &[
+            ("description", Str, false),
+        ],
+        "CHAMFER" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "CHAMFER_OFFSET" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "CHARACTER_GLYPH_SYMBOL" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+            ("character_box", Ref, false),
+            ("baseline_ratio", Real, false),
+        ],
+        "CHARACTERIZED_CLASS" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CHARACTERIZED_OBJECT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CIRCLE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("radius", Real, false),
+        ],
+        "CIRCULAR_CLOSED_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "CIRCULAR_PATTERN" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CIRCULAR_RUNOUT_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "CLASS" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CLASS_SYSTEM" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CLASS_USAGE_EFFECTIVITY_CONTEXT_ASSIGNMENT" => &[
+            ("assigned_effectivity_assignment", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "CLASSIFICATION_ASSIGNMENT" => &[
+            ("assigned_class", Ref, false),
+            ("role", Ref, false),
+        ],
+        "CLASSIFICATION_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CLOSED_PATH_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "CLOSED_SHELL" => &[
+            ("name", Str, false),
+            ("cfs_faces", List, false),
+        ],
+        "COAXIALITY_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "COLOUR" => &[
+        ],
+        "COLOUR_RGB" => &[
+            ("name", Str, false),
+            ("red", Real, false),
+            ("green", Real, false),
+            ("blue", Real, false),
+        ],
+        "COLOUR_SPECIFICATION" => &[
+            ("name", Str, false),
+        ],
+        "COMMON_DATUM" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("identification", Str, false),
+        ],
+        "COMPARISON_EQUAL" => &[
+            ("operands", List, false),
+        ],
+        "COMPARISON_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "COMPARISON_GREATER" => &[
+            ("operands", List, false),
+        ],
+        "COMPARISON_GREATER_EQUAL" => &[
+            ("operands", List, false),
+        ],
+        "COMPARISON_LESS" => &[
+            ("operands", List, false),
+        ],
+        "COMPARISON_LESS_EQUAL" => &[
+            ("operands", List, false),
+        ],
+        "COMPARISON_NOT_EQUAL" => &[
+            ("operands", List, false),
+        ],
+        "COMPOSITE_CURVE" => &[
+            ("name", Str, false),
+            ("segments", List, false),
+            ("self_intersect", Logical, false),
+        ],
+        "COMPOSITE_CURVE_ON_SURFACE" => &[
+            ("name", Str, false),
+            ("segments", List, false),
+            ("self_intersect", Logical, false),
+        ],
+        "COMPOSITE_CURVE_SEGMENT" => &[
+            ("transition", Enum, false),
+            ("same_sense", Logical, false),
+            ("parent_curve", Ref, false),
+        ],
+        "COMPOSITE_HOLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "COMPOSITE_SHAPE_ASPECT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "COMPOSITE_TEXT" => &[
+            ("name", Str, false),
+            ("collected_text", List, false),
+        ],
+        "COMPOSITE_TEXT_WITH_ASSOCIATED_CURVES" => &[
+            ("name", Str, false),
+            ("collected_text", List, false),
+            ("associated_curves", List, false),
+        ],
+        "COMPOSITE_TEXT_WITH_BLANKING_BOX" => &[
+            ("name", Str, false),
+            ("collected_text", List, false),
+            ("blanking", Ref, false),
+        ],
+        "COMPOSITE_TEXT_WITH_EXTENT" => &[
+            ("name", Str, false),
+            ("collected_text", List, false),
+            ("extent", Ref, false),
+        ],
+        "COMPOUND_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "COMPOUND_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+            ("item_element", Any, false),
+        ],
+        "COMPOUND_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "CONCAT_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "CONCENTRICITY_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "CONCEPT_FEATURE_OPERATOR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "CONCEPT_FEATURE_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_concept_feature", Ref, false),
+            ("related_product_concept_feature", Ref, false),
+        ],
+        "CONCEPT_FEATURE_RELATIONSHIP_WITH_CONDITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_concept_feature", Ref, false),
+            ("related_product_concept_feature", Ref, false),
+            ("conditional_operator", Ref, false),
+        ],
+        "CONDITIONAL_CONCEPT_FEATURE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("condition", Ref, false),
+        ],
+        "CONFIGURABLE_ITEM" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_concept", Ref, false),
+            ("purpose", Str, true),
+            ("item_concept_feature", List, false),
+        ],
+        "CONFIGURATION_DEFINITION" => &[
+            ("pair_values", List, false),
+            ("t_parameter", Any, false),
+        ],
+        "CONFIGURATION_DESIGN" => &[
+            ("configuration", Ref, false),
+            ("design", Ref, false),
+        ],
+        "CONFIGURATION_EFFECTIVITY" => &[
+            ("id", Str, false),
+            ("usage", Ref, false),
+            ("configuration", Ref, false),
+        ],
+        "CONFIGURATION_INTERPOLATION" => &[
+            ("previous_configuration_definition", Ref, false),
+            ("next_configuration_definition", Ref, false),
+            ("interpolation", Enum, false),
+        ],
+        "CONFIGURATION_ITEM" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_concept", Ref, false),
+            ("purpose", Str, true),
+        ],
+        "CONFIGURED_EFFECTIVITY_ASSIGNMENT" => &[
+            ("assigned_effectivity", Ref, false),
+            ("items", List, false),
+        ],
+        "CONFIGURED_EFFECTIVITY_CONTEXT_ASSIGNMENT" => &[
+            ("assigned_effectivity_assignment", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "CONIC" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+        ],
+        "CONICAL_SURFACE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("radius", Real, false),
+            ("semi_angle", Real, false),
+        ],
+        "CONNECTED_EDGE_SET" => &[
+            ("name", Str, false),
+            ("ces_edges", List, false),
+        ],
+        "CONNECTED_FACE_SET" => &[
+            ("name", Str, false),
+            ("cfs_faces", List, false),
+        ],
+        "CONNECTED_FACE_SUB_SET" => &[
+            ("name", Str, false),
+            ("cfs_faces", List, false),
+            ("parent_face_set", Ref, false),
+        ],
+        "CONSTRUCTIVE_GEOMETRY_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "CONSTRUCTIVE_GEOMETRY_REPRESENTATION_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+        ],
+        "CONTACT_RATIO_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "CONTEXT_DEPENDENT_INVISIBILITY" => &[
+            ("invisible_items", List, false),
+            ("presentation_context", Ref, false),
+        ],
+        "CONTEXT_DEPENDENT_OVER_RIDING_STYLED_ITEM" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("over_ridden_style", Ref, false),
+            ("style_context", List, false),
+        ],
+        "CONTEXT_DEPENDENT_SHAPE_REPRESENTATION" => &[
+            ("representation_relation", Ref, false),
+            ("represented_product_relation", Ref, false),
+        ],
+        "CONTEXT_DEPENDENT_UNIT" => &[
+            ("dimensions", Ref, false),
+            ("name", Str, false),
+        ],
+        "CONTRACT" => &[
+            ("name", Str, false),
+            ("purpose", Str, false),
+            ("kind", Ref, false),
+        ],
+        "CONTRACT_ASSIGNMENT" => &[
+            ("assigned_contract", Ref, false),
+        ],
+        "CONTRACT_TYPE" => &[
+            ("description", Str, false),
+        ],
+        "CONVERSION_BASED_UNIT" => &[
+            ("dimensions", Derived, false),
+            ("name", Str, false),
+            ("conversion_factor", Ref, false),
+        ],
+        "COORDINATED_UNIVERSAL_TIME_OFFSET" => &[
+            ("hour_offset", Integer, false),
+            ("minute_offset", Integer, true),
+            ("sense", Enum, false),
+        ],
+        "COS_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "CSG_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "CSG_SOLID" => &[
+            ("name", Str, false),
+            ("tree_root_expression", Ref, false),
+        ],
+        "CURVE" => &[
+            ("name", Str, false),
+        ],
+        "CURVE_BOUNDED_SURFACE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("boundaries", List, false),
+            ("implicit_outer", Logical, false),
+        ],
+        "CURVE_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "CURVE_REPLICA" => &[
+            ("name", Str, false),
+            ("parent_curve", Ref, false),
+            ("transformation", Ref, false),
+        ],
+        "CURVE_STYLE" => &[
+            ("name", Str, false),
+            ("curve_font", Ref, false),
+            ("curve_width", Any, false),
+            ("curve_colour", Ref, false),
+        ],
+        "CURVE_STYLE_FONT" => &[
+            ("name", Str, false),
+            ("pattern_list", List, false),
+        ],
+        "CURVE_STYLE_FONT_PATTERN" => &[
+            ("visible_segment_length", Real, false),
+            ("invisible_segment_length", Real, false),
+        ],
+        "CURVE_STYLE_RENDERING" => &[
+            ("rendering_method", Enum, false),
+            ("rendering_properties", Ref, false),
+        ],
+        "CURVE_SWEPT_SOLID_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "CYLINDRICAL_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "CYLINDRICAL_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_actual_translation", Any, false),
+            ("upper_limit_actual_translation", Any, false),
+            ("lower_limit_actual_rotation", Any, false),
+            ("upper_limit_actual_rotation", Any, false),
+        ],
+        "CYLINDRICAL_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_translation", Real, false),
+            ("actual_rotation", Real, false),
+        ],
+        "CYLINDRICAL_SURFACE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("radius", Real, false),
+        ],
+        "CYLINDRICITY_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "DATA_ENVIRONMENT" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("elements", List, false),
+        ],
+        "DATE" => &[
+            ("year_component", Integer, false),
+        ],
+        "DATE_AND_TIME" => &[
+            ("date_component", Ref, false),
+            ("time_component", Ref, false),
+        ],
+        "DATE_AND_TIME_ASSIGNMENT" => &[
+            ("assigned_date_and_time", Ref, false),
+            ("role", Ref, false),
+        ],
+        "DATE_ASSIGNMENT" => &[
+            ("assigned_date", Ref, false),
+            ("role", Ref, false),
+        ],
+        "DATE_ROLE" => &[
+            ("name", Str, false),
+        ],
+        "DATE_TIME_ROLE" => &[
+            ("name", Str, false),
+        ],
+        "DATED_EFFECTIVITY" => &[
+            ("id", Str, false),
+            ("effectivity_end_date", Ref, true),
+            ("effectivity_start_date", Ref, false),
+        ],
+        "DATUM" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("identification", Str, false),
+        ],
+        "DATUM_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "DATUM_FEATURE_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DATUM_REFERENCE" => &[
+            ("precedence", Integer, false),
+            ("referenced_datum", Ref, false),
+        ],
+        "DATUM_TARGET" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("target_id", Str, false),
+        ],
+        "DATUM_TARGET_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DEFAULT_TOLERANCE_TABLE" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DEFAULT_TOLERANCE_TABLE_CELL" => &[
+            ("name", Str, false),
+            ("item_element", Any, false),
+        ],
+        "DEFINED_CHARACTER_GLYPH" => &[
+            ("name", Str, false),
+            ("definition", Ref, false),
+            ("placement", Ref, false),
+        ],
+        "DEFINED_FUNCTION" => &[
+        ],
+        "DEFINED_SYMBOL" => &[
+            ("name", Str, false),
+            ("definition", Ref, false),
+            ("target", Ref, false),
+        ],
+        "DEFINITIONAL_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DEGENERATE_PCURVE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("reference_to_curve", Ref, false),
+        ],
+        "DEGENERATE_TOROIDAL_SURFACE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("major_radius", Real, false),
+            ("minor_radius", Real, false),
+            ("select_outer", Logical, false),
+        ],
+        "DERIVED_SHAPE_ASPECT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "DERIVED_UNIT" => &[
+            ("elements", List, false),
+        ],
+        "DERIVED_UNIT_ELEMENT" => &[
+            ("unit", Ref, false),
+            ("exponent", Real, false),
+        ],
+        "DERIVED_UNIT_VARIABLE" => &[
+            ("elements", List, false),
+        ],
+        "DESCRIPTION_ATTRIBUTE" => &[
+            ("attribute_value", Str, false),
+            ("described_item", Ref, false),
+        ],
+        "DESCRIPTIVE_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+        ],
+        "DIAMETER_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DIMENSION_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DIMENSION_CALLOUT_COMPONENT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_draughting_callout", Ref, false),
+            ("related_draughting_callout", Ref, false),
+        ],
+        "DIMENSION_CALLOUT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_draughting_callout", Ref, false),
+            ("related_draughting_callout", Ref, false),
+        ],
+        "DIMENSION_CURVE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "DIMENSION_CURVE_DIRECTED_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DIMENSION_CURVE_TERMINATOR" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("annotated_curve", Ref, false),
+            ("role", Enum, false),
+        ],
+        "DIMENSION_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_draughting_callout", Ref, false),
+            ("related_draughting_callout", Ref, false),
+        ],
+        "DIMENSION_RELATED_TOLERANCE_ZONE_ELEMENT" => &[
+            ("related_dimension", Ref, false),
+            ("related_element", Ref, false),
+        ],
+        "DIMENSION_TEXT_ASSOCIATIVITY" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "DIMENSIONAL_CHARACTERISTIC_REPRESENTATION" => &[
+            ("dimension", Ref, false),
+            ("representation", Ref, false),
+        ],
+        "DIMENSIONAL_EXPONENTS" => &[
+            ("length_exponent", Real, false),
+            ("mass_exponent", Real, false),
+            ("time_exponent", Real, false),
+            ("electric_current_exponent", Real, false),
+            ("thermodynamic_temperature_exponent", Real, false),
+            ("amount_of_substance_exponent", Real, false),
+            ("luminous_intensity_exponent", Real, false),
+        ],
+        "DIMENSIONAL_LOCATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "DIMENSIONAL_LOCATION_WITH_PATH" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+            ("path", Ref, false),
+        ],
+        "DIMENSIONAL_SIZE" => &[
+            ("applies_to", Ref, false),
+            ("name", Str, false),
+        ],
+        "DIMENSIONAL_SIZE_WITH_PATH" => &[
+            ("applies_to", Ref, false),
+            ("name", Str, false),
+            ("path", Ref, false),
+        ],
+        "DIRECTED_ACTION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+            ("directive", Ref, false),
+        ],
+        "DIRECTED_ANGLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "DIRECTED_DIMENSIONAL_LOCATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "DIRECTION" => &[
+            ("name", Str, false),
+            ("direction_ratios", List, false),
+        ],
+        "DIRECTION_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DIV_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "DOCUMENT" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("kind", Ref, false),
+        ],
+        "DOCUMENT_FILE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("kind", Ref, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "DOCUMENT_PRODUCT_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_document", Ref, false),
+            ("related_product", Ref, false),
+        ],
+        "DOCUMENT_PRODUCT_EQUIVALENCE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_document", Ref, false),
+            ("related_product", Ref, false),
+        ],
+        "DOCUMENT_REFERENCE" => &[
+            ("assigned_document", Ref, false),
+            ("source", Str, false),
+        ],
+        "DOCUMENT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_document", Ref, false),
+            ("related_document", Ref, false),
+        ],
+        "DOCUMENT_REPRESENTATION_TYPE" => &[
+            ("name", Str, false),
+            ("represented_document", Ref, false),
+        ],
+        "DOCUMENT_TYPE" => &[
+            ("product_data_type", Str, false),
+        ],
+        "DOCUMENT_USAGE_CONSTRAINT" => &[
+            ("source", Ref, false),
+            ("subject_element", Str, false),
+            ("subject_element_value", Str, false),
+        ],
+        "DOCUMENT_USAGE_CONSTRAINT_ASSIGNMENT" => &[
+            ("assigned_document_usage", Ref, false),
+            ("role", Ref, false),
+        ],
+        "DOCUMENT_USAGE_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "DRAUGHTING_ANNOTATION_OCCURRENCE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "DRAUGHTING_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DRAUGHTING_CALLOUT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_draughting_callout", Ref, false),
+            ("related_draughting_callout", Ref, false),
+        ],
+        "DRAUGHTING_ELEMENTS" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "DRAUGHTING_MODEL" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DRAUGHTING_MODEL_ITEM_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+            ("identified_item", Ref, false),
+        ],
+        "DRAUGHTING_PRE_DEFINED_COLOUR" => &[
+            ("name", Str, false),
+        ],
+        "DRAUGHTING_PRE_DEFINED_CURVE_FONT" => &[
+            ("name", Str, false),
+        ],
+        "DRAUGHTING_PRE_DEFINED_TEXT_FONT" => &[
+            ("name", Str, false),
+        ],
+        "DRAUGHTING_SPECIFICATION_REFERENCE" => &[
+            ("assigned_document", Ref, false),
+            ("source", Str, false),
+            ("specified_items", List, false),
+        ],
+        "DRAUGHTING_SUBFIGURE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DRAUGHTING_SYMBOL_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DRAUGHTING_TEXT_LITERAL_WITH_DELINEATION" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+            ("delineation", Str, false),
+        ],
+        "DRAUGHTING_TITLE" => &[
+            ("items", List, false),
+            ("language", Str, false),
+            ("contents", Str, false),
+        ],
+        "DRAWING_DEFINITION" => &[
+            ("drawing_number", Str, false),
+            ("drawing_type", Str, true),
+        ],
+        "DRAWING_REVISION" => &[
+            ("revision_identifier", Str, false),
+            ("drawing_identifier", Ref, false),
+            ("intended_scale", Str, true),
+        ],
+        "DRAWING_REVISION_SEQUENCE" => &[
+            ("predecessor", Ref, false),
+            ("successor", Ref, false),
+        ],
+        "DRAWING_SHEET_LAYOUT" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "DRAWING_SHEET_REVISION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+            ("revision_identifier", Str, false),
+        ],
+        "DRAWING_SHEET_REVISION_USAGE" => &[
+            ("area", Ref, false),
+            ("in_set", Ref, false),
+            ("sheet_number", Str, false),
+        ],
+        "EDGE" => &[
+            ("name", Str, false),
+            ("edge_start", Ref, false),
+            ("edge_end", Ref, false),
+        ],
+        "EDGE_BASED_WIREFRAME_MODEL" => &[
+            ("name", Str, false),
+            ("ebwm_boundary", List, false),
+        ],
+        "EDGE_BASED_WIREFRAME_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "EDGE_CURVE" => &[
+            ("name", Str, false),
+            ("edge_start", Ref, false),
+            ("edge_end", Ref, false),
+            ("edge_geometry", Ref, false),
+            ("same_sense", Logical, false),
+        ],
+        "EDGE_LOOP" => &[
+            ("name", Str, false),
+            ("edge_list", List, false),
+        ],
+        "EDGE_ROUND" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "EFFECTIVITY" => &[
+            ("id", Str, false),
+        ],
+        "EFFECTIVITY_ASSIGNMENT" => &[
+            ("assigned_effectivity", Ref, false),
+        ],
+        "EFFECTIVITY_CONTEXT_ASSIGNMENT" => &[
+            ("assigned_effectivity_assignment", Ref, false),
+            ("role", Ref, false),
+        ],
+        "EFFECTIVITY_CONTEXT_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "EFFECTIVITY_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("related_effectivity", Ref, false),
+            ("relating_effectivity", Ref, false),
+        ],
+        "ELECTRIC_CURRENT_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "ELECTRIC_CURRENT_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "ELEMENT_DELIVERY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+        ],
+        "ELEMENTARY_SURFACE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+        ],
+        "ELLIPSE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("semi_axis_1", Real, false),
+            ("semi_axis_2", Real, false),
+        ],
+        "ENVIRONMENT" => &[
+            ("syntactic_representation", Ref, false),
+            ("semantics", Ref, false),
+        ],
+        "EQUALS_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "EVALUATED_DEGENERATE_PCURVE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("reference_to_curve", Ref, false),
+            ("equivalent_point", Ref, false),
+        ],
+        "EVENT_OCCURRENCE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "EVENT_OCCURRENCE_ASSIGNMENT" => &[
+            ("assigned_event_occurrence", Ref, false),
+            ("role", Ref, false),
+        ],
+        "EVENT_OCCURRENCE_CONTEXT_ASSIGNMENT" => &[
+            ("assigned_event_occurrence_assignment", Ref, false),
+            ("role", Ref, false),
+        ],
+        "EVENT_OCCURRENCE_CONTEXT_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "EVENT_OCCURRENCE_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "EXCLUSIVE_PRODUCT_CONCEPT_FEATURE_CATEGORY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "EXECUTED_ACTION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+        ],
+        "EXP_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "EXPRESSION" => &[
+        ],
+        "EXPRESSION_CONVERSION_BASED_UNIT" => &[
+            ("dimensions", Ref, false),
+            ("name", Str, false),
+        ],
+        "EXTENSION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "EXTERNAL_IDENTIFICATION_ASSIGNMENT" => &[
+            ("assigned_id", Str, false),
+            ("role", Ref, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNAL_SOURCE" => &[
+            ("source_id", Any, false),
+        ],
+        "EXTERNALLY_DEFINED_CHARACTER_GLYPH" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_CLASS" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_CURVE_FONT" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_DIMENSION_DEFINITION" => &[
+            ("applies_to", Ref, false),
+            ("name", Str, false),
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_FEATURE_DEFINITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_GENERAL_PROPERTY" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_HATCH_STYLE" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+            ("name", Str, false),
+        ],
+        "EXTERNALLY_DEFINED_ITEM" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_ITEM_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_item", Ref, false),
+            ("related_item", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_STYLE" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_SYMBOL" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_TEXT_FONT" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+        ],
+        "EXTERNALLY_DEFINED_TILE_STYLE" => &[
+            ("item_id", Any, false),
+            ("source", Ref, false),
+            ("name", Str, false),
+        ],
+        "EXTRUDED_AREA_SOLID" => &[
+            ("name", Str, false),
+            ("swept_area", Ref, false),
+            ("extruded_direction", Ref, false),
+            ("depth", Real, false),
+        ],
+        "EXTRUDED_FACE_SOLID" => &[
+            ("name", Str, false),
+            ("swept_face", Ref, false),
+            ("extruded_direction", Ref, false),
+            ("depth", Real, false),
+        ],
+        "FACE" => &[
+            ("name", Str, false),
+            ("bounds", List, false),
+        ],
+        "FACE_BASED_SURFACE_MODEL" => &[
+            ("name", Str, false),
+            ("fbsm_faces", List, false),
+        ],
+        "FACE_BOUND" => &[
+            ("name", Str, false),
+            ("bound", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "FACE_OUTER_BOUND" => &[
+            ("name", Str, false),
+            ("bound", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "FACE_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "FACE_SURFACE" => &[
+            ("name", Str, false),
+            ("bounds", List, false),
+            ("face_geometry", Ref, false),
+            ("same_sense", Logical, false),
+        ],
+        "FACETED_BREP" => &[
+            ("name", Str, false),
+            ("outer", Ref, false),
+        ],
+        "FACETED_BREP_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "FEATURE_COMPONENT_DEFINITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "FEATURE_COMPONENT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "FEATURE_DEFINITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "FEATURE_IN_PANEL" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "FEATURE_PATTERN" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "FEATURED_SHAPE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+        ],
+        "FILL_AREA_STYLE" => &[
+            ("name", Str, false),
+            ("fill_styles", List, false),
+        ],
+        "FILL_AREA_STYLE_COLOUR" => &[
+            ("name", Str, false),
+            ("fill_colour", Ref, false),
+        ],
+        "FILL_AREA_STYLE_HATCHING" => &[
+            ("name", Str, false),
+            ("hatch_line_appearance", Ref, false),
+            ("start_of_next_hatch_line", Ref, false),
+            ("point_of_reference_hatch_line", Ref, false),
+            ("pattern_start", Ref, false),
+            ("hatch_line_angle", Real, false),
+        ],
+        "FILL_AREA_STYLE_TILE_SYMBOL_WITH_STYLE" => &[
+            ("name", Str, false),
+            ("symbol", Ref, false),
+        ],
+        "FILL_AREA_STYLE_TILES" => &[
+            ("name", Str, false),
+            ("tiling_pattern", Ref, false),
+            ("tiles", List, false),
+            ("tiling_scale", Real, false),
+        ],
+        "FILLET" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "FLATNESS_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "FORMAT_FUNCTION" => &[
+            ("operands", List, false),
+        ],
+        "FOUNDED_ITEM" => &[
+        ],
+        "FOUNDED_KINEMATIC_PATH" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "FULLY_CONSTRAINED_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "FUNCTIONALLY_DEFINED_TRANSFORMATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "GEAR_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("radius_first_link", Real, false),
+            ("radius_second_link", Real, false),
+            ("bevel", Real, false),
+            ("helical_angle", Real, false),
+            ("gear_ratio", Real, false),
+        ],
+        "GEAR_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_actual_rotation_1", Any, false),
+            ("upper_limit_actual_rotation_1", Any, false),
+        ],
+        "GEAR_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_rotation_1", Real, false),
+        ],
+        "GENERAL_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "GENERAL_MATERIAL_PROPERTY" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "GENERAL_PROPERTY" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "GENERAL_PROPERTY_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("base_definition", Ref, false),
+            ("derived_definition", Ref, false),
+        ],
+        "GENERAL_PROPERTY_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_property", Ref, false),
+            ("related_property", Ref, false),
+        ],
+        "GENERIC_CHARACTER_GLYPH_SYMBOL" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "GENERIC_EXPRESSION" => &[
+        ],
+        "GENERIC_LITERAL" => &[
+        ],
+        "GENERIC_VARIABLE" => &[
+        ],
+        "GEOMETRIC_ALIGNMENT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "GEOMETRIC_CURVE_SET" => &[
+            ("name", Str, false),
+            ("elements", List, false),
+        ],
+        "GEOMETRIC_INTERSECTION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "GEOMETRIC_ITEM_SPECIFIC_USAGE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+            ("identified_item", Ref, false),
+        ],
+        "GEOMETRIC_REPRESENTATION_CONTEXT" => &[
+            ("context_identifier", Str, false),
+            ("context_type", Str, false),
+            ("coordinate_space_dimension", Integer, false),
+        ],
+        "GEOMETRIC_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+        ],
+        "GEOMETRIC_SET" => &[
+            ("name", Str, false),
+            ("elements", List, false),
+        ],
+        "GEOMETRIC_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "GEOMETRIC_TOLERANCE_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_geometric_tolerance", Ref, false),
+            ("related_geometric_tolerance", Ref, false),
+        ],
+        "GEOMETRIC_TOLERANCE_WITH_DATUM_REFERENCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "GEOMETRIC_TOLERANCE_WITH_DEFINED_UNIT" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("unit_size", Ref, false),
+        ],
+        "GEOMETRICAL_TOLERANCE_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "GEOMETRICALLY_BOUNDED_2D_WIREFRAME_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "GEOMETRICALLY_BOUNDED_SURFACE_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "GEOMETRICALLY_BOUNDED_WIREFRAME_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT" => &[
+            ("context_identifier", Str, false),
+            ("context_type", Str, false),
+            ("uncertainty", List, false),
+        ],
+        "GLOBAL_UNIT_ASSIGNED_CONTEXT" => &[
+            ("context_identifier", Str, false),
+            ("context_type", Str, false),
+            ("units", List, false),
+        ],
+        "GROUP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "GROUP_ASSIGNMENT" => &[
+            ("assigned_group", Ref, false),
+        ],
+        "GROUP_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_group", Ref, false),
+            ("related_group", Ref, false),
+        ],
+        "HALF_SPACE_SOLID" => &[
+            ("name", Str, false),
+            ("base_surface", Ref, false),
+            ("agreement_flag", Logical, false),
+        ],
+        "HARDNESS_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "HIDDEN_ELEMENT_OVER_RIDING_STYLED_ITEM" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("over_ridden_style", Ref, false),
+            ("style_context", List, false),
+        ],
+        "HOLE_BOTTOM" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "HOLE_IN_PANEL" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "HOMOKINETIC_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("input_skew_angle", Real, true),
+        ],
+        "HYPERBOLA" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("semi_axis", Real, false),
+            ("semi_imag_axis", Real, false),
+        ],
+        "ID_ATTRIBUTE" => &[
+            ("attribute_value", Str, false),
+            ("identified_item", Ref, false),
+        ],
+        "IDENTIFICATION_ASSIGNMENT" => &[
+            ("assigned_id", Str, false),
+            ("role", Ref, false),
+        ],
+        "IDENTIFICATION_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "INCLUSION_PRODUCT_CONCEPT_FEATURE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("condition", Ref, false),
+        ],
+        "INDEX_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "INITIAL_STATE" => &[
+            ("applies_to_mechanism", Ref, false),
+            ("pair_values", List, false),
+        ],
+        "INSTANCED_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "INT_LITERAL" => &[
+            ("the_value", Real, false),
+        ],
+        "INT_NUMERIC_VARIABLE" => &[
+        ],
+        "INT_VALUE_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "INTEGER_DEFINED_FUNCTION" => &[
+        ],
+        "INTERPOLATED_CONFIGURATION_SEQUENCE" => &[
+            ("interpolation", List, false),
+        ],
+        "INTERSECTION_CURVE" => &[
+            ("name", Str, false),
+            ("curve_3d", Ref, false),
+            ("associated_geometry", List, false),
+            ("master_representation", Enum, false),
+        ],
+        "INTERVAL_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "INVISIBILITY" => &[
+            ("invisible_items", List, false),
+        ],
+        "ITEM_DEFINED_TRANSFORMATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+        ],
+        "ITEM_IDENTIFIED_REPRESENTATION_USAGE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+            ("identified_item", Ref, false),
+        ],
+        "JOGGLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "JOGGLE_TERMINATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "KINEMATIC_ANALYSIS_CONSISTENCY" => &[
+            ("control", Ref, false),
+            ("result", Ref, false),
+        ],
+        "KINEMATIC_ANALYSIS_RESULT" => &[
+            ("analysed_mechanism", Ref, false),
+            ("contained_kinematic_results", List, false),
+        ],
+        "KINEMATIC_CONTROL" => &[
+            ("controlled_mechanism", Ref, false),
+            ("contained_kinematic_programs", List, false),
+        ],
+        "KINEMATIC_FRAME_BACKGROUND_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "KINEMATIC_FRAME_BACKGROUND_REPRESENTATION_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+            ("transformation_operator", Ref, false),
+        ],
+        "KINEMATIC_FRAME_BASED_TRANSFORMATION" => &[
+            ("name", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transformator", Ref, false),
+        ],
+        "KINEMATIC_GROUND_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "KINEMATIC_JOINT" => &[
+            ("first_link", Ref, false),
+            ("second_link", Ref, false),
+        ],
+        "KINEMATIC_LINK" => &[
+        ],
+        "KINEMATIC_LINK_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "KINEMATIC_LINK_REPRESENTATION_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+        ],
+        "KINEMATIC_LINK_REPRESENTATION_RELATION" => &[
+            ("topological_aspects", Ref, false),
+            ("geometric_aspects", Ref, false),
+        ],
+        "KINEMATIC_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "KINEMATIC_PATH" => &[
+            ("name", Str, false),
+        ],
+        "KINEMATIC_PROPERTY_DEFINITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+            ("ground_definition", Ref, false),
+        ],
+        "KINEMATIC_PROPERTY_REPRESENTATION_RELATION" => &[
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+        ],
+        "KINEMATIC_STRUCTURE" => &[
+            ("joints", List, false),
+        ],
+        "KNOWN_SOURCE" => &[
+            ("source_id", Any, false),
+            ("name", Str, false),
+        ],
+        "LANGUAGE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "LANGUAGE_ASSIGNMENT" => &[
+            ("assigned_class", Ref, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "LEADER_CURVE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "LEADER_DIRECTED_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "LEADER_DIRECTED_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "LEADER_TERMINATOR" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("annotated_curve", Ref, false),
+        ],
+        "LENGTH_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "LENGTH_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "LENGTH_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "LIGHT_SOURCE" => &[
+            ("name", Str, false),
+            ("light_colour", Ref, false),
+        ],
+        "LIGHT_SOURCE_AMBIENT" => &[
+            ("name", Str, false),
+            ("light_colour", Ref, false),
+        ],
+        "LIGHT_SOURCE_DIRECTIONAL" => &[
+            ("name", Str, false),
+            ("light_colour", Ref, false),
+            ("orientation", Ref, false),
+        ],
+        "LIGHT_SOURCE_POSITIONAL" => &[
+            ("name", Str, false),
+            ("light_colour", Ref, false),
+            ("position", Ref, false),
+            ("constant_attenuation", Real, false),
+            ("distance_attenuation", Real, false),
+        ],
+        "LIGHT_SOURCE_SPOT" => &[
+            ("name", Str, false),
+            ("light_colour", Ref, false),
+            ("position", Ref, false),
+            ("orientation", Ref, false),
+            ("concentration_exponent", Real, false),
+            ("constant_attenuation", Real, false),
+            ("distance_attenuation", Real, false),
+            ("spread_angle", Real, false),
+        ],
+        "LIKE_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "LIMITS_AND_FITS" => &[
+            ("form_variance", Str, false),
+            ("zone_variance", Str, false),
+            ("grade", Str, false),
+            ("source", Str, false),
+        ],
+        "LINE" => &[
+            ("name", Str, false),
+            ("pnt", Ref, false),
+            ("dir", Ref, false),
+        ],
+        "LINE_PROFILE_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "LINEAR_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "LITERAL_NUMBER" => &[
+            ("the_value", Real, false),
+        ],
+        "LOCAL_TIME" => &[
+            ("hour_component", Integer, false),
+            ("minute_component", Integer, true),
+            ("second_component", Real, true),
+            ("zone", Ref, false),
+        ],
+        "LOCATION_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "LOCATOR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "LOG10_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "LOG2_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "LOG_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "LOOP" => &[
+            ("name", Str, false),
+        ],
+        "LOT_EFFECTIVITY" => &[
+            ("id", Str, false),
+            ("effectivity_lot_id", Str, false),
+            ("effectivity_lot_size", Ref, false),
+        ],
+        "LUMINOUS_INTENSITY_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "LUMINOUS_INTENSITY_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "MAKE_FROM_USAGE_OPTION" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+            ("ranking", Integer, false),
+            ("ranking_rationale", Str, false),
+            ("quantity", Ref, false),
+        ],
+        "MANIFOLD_SOLID_BREP" => &[
+            ("name", Str, false),
+            ("outer", Ref, false),
+        ],
+        "MANIFOLD_SUBSURFACE_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "MANIFOLD_SURFACE_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "MAPPED_ITEM" => &[
+            ("name", Str, false),
+            ("mapping_source", Ref, false),
+            ("mapping_target", Ref, false),
+        ],
+        "MASS_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "MASS_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "MATERIAL_DESIGNATION" => &[
+            ("name", Str, false),
+            ("definitions", List, false),
+        ],
+        "MATERIAL_DESIGNATION_CHARACTERIZATION" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("designation", Ref, false),
+            ("property", Ref, false),
+        ],
+        "MATERIAL_PROPERTY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+        ],
+        "MATERIAL_PROPERTY_REPRESENTATION" => &[
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+            ("dependent_environment", Ref, false),
+        ],
+        "MAXIMUM_FUNCTION" => &[
+            ("operands", List, false),
+        ],
+        "MEASURE_QUALIFICATION" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("qualified_measure", Ref, false),
+            ("qualifiers", List, false),
+        ],
+        "MEASURE_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "MECHANICAL_DESIGN_GEOMETRIC_PRESENTATION_AREA" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "MECHANICAL_DESIGN_GEOMETRIC_PRESENTATION_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "MECHANISM" => &[
+            ("structure_definition", Ref, false),
+            ("base", Ref, false),
+            ("containing_property", Ref, false),
+        ],
+        "MECHANISM_BASE_PLACEMENT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Derived, false),
+            ("transformation_operator", Ref, false),
+            ("base_of_mechanism", Ref, false),
+        ],
+        "MINIMUM_FUNCTION" => &[
+            ("operands", List, false),
+        ],
+        "MINUS_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "MINUS_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "MOD_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "MODIFIED_GEOMETRIC_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("modifier", Enum, false),
+        ],
+        "MODIFIED_PATTERN" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "MOMENTS_OF_INERTIA_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "MOTION_LINK_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+            ("related_frame", Ref, false),
+        ],
+        "MULT_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "MULTI_LANGUAGE_ATTRIBUTE_ASSIGNMENT" => &[
+            ("attribute_name", Str, false),
+            ("attribute_value", Any, false),
+            ("role", Ref, false),
+            ("items", List, false),
+        ],
+        "MULTIPLE_ARITY_BOOLEAN_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "MULTIPLE_ARITY_FUNCTION_CALL" => &[
+            ("operands", List, false),
+        ],
+        "MULTIPLE_ARITY_GENERIC_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "MULTIPLE_ARITY_NUMERIC_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "NAME_ASSIGNMENT" => &[
+            ("assigned_name", Str, false),
+        ],
+        "NAME_ATTRIBUTE" => &[
+            ("attribute_value", Str, false),
+            ("named_item", Ref, false),
+        ],
+        "NAMED_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "NAMED_UNIT_VARIABLE" => &[
+            ("dimensions", Ref, false),
+        ],
+        "NEXT_ASSEMBLY_USAGE_OCCURRENCE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+            ("reference_designator", Str, true),
+        ],
+        "NGON_CLOSED_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "NON_MANIFOLD_SURFACE_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "NOT_EXPRESSION" => &[
+            ("operand", Ref, false),
+        ],
+        "NUMERIC_DEFINED_FUNCTION" => &[
+        ],
+        "NUMERIC_EXPRESSION" => &[
+        ],
+        "NUMERIC_VARIABLE" => &[
+        ],
+        "OBJECT_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "ODD_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "OFFSET_CURVE_2D" => &[
+            ("name", Str, false),
+            ("basis_curve", Ref, false),
+            ("distance", Real, false),
+            ("self_intersect", Logical, false),
+        ],
+        "OFFSET_CURVE_3D" => &[
+            ("name", Str, false),
+            ("basis_curve", Ref, false),
+            ("distance", Real, false),
+            ("self_intersect", Logical, false),
+            ("ref_direction", Ref, false),
+        ],
+        "OFFSET_SURFACE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("distance", Real, false),
+            ("self_intersect", Logical, false),
+        ],
+        "ONE_DIRECTION_REPEAT_FACTOR" => &[
+            ("name", Str, false),
+            ("repeat_factor", Ref, false),
+        ],
+        "OPEN_PATH_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "OPEN_SHELL" => &[
+            ("name", Str, false),
+            ("cfs_faces", List, false),
+        ],
+        "OR_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "ORDINATE_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "ORGANIZATION" => &[
+            ("id", Str, true),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "ORGANIZATION_ASSIGNMENT" => &[
+            ("assigned_organization", Ref, false),
+            ("role", Ref, false),
+        ],
+        "ORGANIZATION_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_organization", Ref, false),
+            ("related_organization", Ref, false),
+        ],
+        "ORGANIZATION_ROLE" => &[
+            ("name", Str, false),
+        ],
+        "ORGANIZATIONAL_ADDRESS" => &[
+            ("internal_location", Str, true),
+            ("street_number", Str, true),
+            ("street", Str, true),
+            ("postal_box", Str, true),
+            ("town", Str, true),
+            ("region", Str, true),
+            ("postal_code", Str, true),
+            ("country", Str, true),
+            ("facsimile_number", Str, true),
+            ("telephone_number", Str, true),
+            ("electronic_mail_address", Str, true),
+            ("telex_number", Str, true),
+            ("organizations", List, false),
+            ("description", Str, true),
+        ],
+        "ORGANIZATIONAL_PROJECT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("responsible_organizations", List, false),
+        ],
+        "ORGANIZATIONAL_PROJECT_ASSIGNMENT" => &[
+            ("assigned_organizational_project", Ref, false),
+            ("role", Ref, false),
+        ],
+        "ORGANIZATIONAL_PROJECT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_organizational_project", Ref, false),
+            ("related_organizational_project", Ref, false),
+        ],
+        "ORGANIZATIONAL_PROJECT_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "ORIENTED_CLOSED_SHELL" => &[
+            ("name", Str, false),
+            ("cfs_faces", Derived, false),
+            ("closed_shell_element", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ORIENTED_EDGE" => &[
+            ("name", Str, false),
+            ("edge_start", Derived, false),
+            ("edge_end", Derived, false),
+            ("edge_element", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ORIENTED_FACE" => &[
+            ("name", Str, false),
+            ("bounds", Derived, false),
+            ("face_element", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ORIENTED_OPEN_SHELL" => &[
+            ("name", Str, false),
+            ("cfs_faces", Derived, false),
+            ("open_shell_element", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ORIENTED_PATH" => &[
+            ("name", Str, false),
+            ("edge_list", Derived, false),
+            ("path_element", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ORIENTED_SURFACE" => &[
+            ("name", Str, false),
+            ("orientation", Logical, false),
+        ],
+        "OUTER_BOUNDARY_CURVE" => &[
+            ("name", Str, false),
+            ("segments", List, false),
+            ("self_intersect", Logical, false),
+        ],
+        "OVER_RIDING_STYLED_ITEM" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("over_ridden_style", Ref, false),
+        ],
+        "PACKAGE_PRODUCT_CONCEPT_FEATURE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PAIR_ACTUATOR" => &[
+            ("actuated_pair", Ref, false),
+            ("name", Str, false),
+        ],
+        "PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+        ],
+        "PARABOLA" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("focal_dist", Real, false),
+        ],
+        "PARALLEL_OFFSET" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("offset", Ref, false),
+        ],
+        "PARALLELISM_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "PARAMETRIC_REPRESENTATION_CONTEXT" => &[
+            ("context_identifier", Str, false),
+            ("context_type", Str, false),
+        ],
+        "PARTIAL_CIRCULAR_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "PATH" => &[
+            ("name", Str, false),
+            ("edge_list", List, false),
+        ],
+        "PATH_FEATURE_COMPONENT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "PATH_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "PATTERN_OFFSET_MEMBERSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "PATTERN_OMIT_MEMBERSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "PCURVE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("reference_to_curve", Ref, false),
+        ],
+        "PERPENDICULAR_TO" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "PERPENDICULARITY_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "PERSON" => &[
+            ("id", Str, false),
+            ("last_name", Str, true),
+            ("first_name", Str, true),
+            ("middle_names", List, true),
+            ("prefix_titles", List, true),
+            ("suffix_titles", List, true),
+        ],
+        "PERSON_AND_ORGANIZATION" => &[
+            ("the_person", Ref, false),
+            ("the_organization", Ref, false),
+        ],
+        "PERSON_AND_ORGANIZATION_ADDRESS" => &[
+            ("internal_location", Str, true),
+            ("street_number", Str, true),
+            ("street", Str, true),
+            ("postal_box", Str, true),
+            ("town", Str, true),
+            ("region", Str, true),
+            ("postal_code", Str, true),
+            ("country", Str, true),
+            ("facsimile_number", Str, true),
+            ("telephone_number", Str, true),
+            ("electronic_mail_address", Str, true),
+            ("telex_number", Str, true),
+            ("organizations", List, false),
+            ("description", Str, true),
+            ("people", List, false),
+            ("description", Str, true),
+        ],
+        "PERSON_AND_ORGANIZATION_ASSIGNMENT" => &[
+            ("assigned_person_and_organization", Ref, false),
+            ("role", Ref, false),
+        ],
+        "PERSON_AND_ORGANIZATION_ROLE" => &[
+            ("name", Str, false),
+        ],
+        "PERSONAL_ADDRESS" => &[
+            ("internal_location", Str, true),
+            ("street_number", Str, true),
+            ("street", Str, true),
+            ("postal_box", Str, true),
+            ("town", Str, true),
+            ("region", Str, true),
+            ("postal_code", Str, true),
+            ("country", Str, true),
+            ("facsimile_number", Str, true),
+            ("telephone_number", Str, true),
+            ("electronic_mail_address", Str, true),
+            ("telex_number", Str, true),
+            ("people", List, false),
+            ("description", Str, true),
+        ],
+        "PHYSICALLY_MODELLED_PRODUCT_DEFINITION" => &[
+            ("id", Str, false),
+            ("description", Str, true),
+            ("formation", Ref, false),
+            ("frame_of_reference", Ref, false),
+            ("documentation_ids", List, false),
+        ],
+        "PLACED_DATUM_TARGET_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("target_id", Str, false),
+        ],
+        "PLACED_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "PLACEMENT" => &[
+            ("name", Str, false),
+            ("location", Ref, false),
+        ],
+        "PLANAR_BOX" => &[
+            ("name", Str, false),
+            ("size_in_x", Real, false),
+            ("size_in_y", Real, false),
+            ("placement", Ref, false),
+        ],
+        "PLANAR_CURVE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("curve_1", Ref, false),
+            ("curve_2", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "PLANAR_CURVE_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("range_on_curve_1", Ref, false),
+            ("range_on_curve_2", Ref, false),
+        ],
+        "PLANAR_EXTENT" => &[
+            ("name", Str, false),
+            ("size_in_x", Real, false),
+            ("size_in_y", Real, false),
+        ],
+        "PLANAR_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "PLANAR_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_actual_rotation", Any, false),
+            ("upper_limit_actual_rotation", Any, false),
+            ("lower_limit_actual_translation_x", Any, false),
+            ("upper_limit_actual_translation_x", Any, false),
+            ("lower_limit_actual_translation_y", Any, false),
+            ("upper_limit_actual_translation_y", Any, false),
+        ],
+        "PLANAR_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_rotation", Real, false),
+            ("actual_translation_x", Real, false),
+            ("actual_translation_y", Real, false),
+        ],
+        "PLANAR_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "PLANE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+        ],
+        "PLANE_ANGLE_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "PLANE_ANGLE_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "PLUS_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "PLUS_MINUS_TOLERANCE" => &[
+            ("range", Ref, false),
+            ("toleranced_dimension", Ref, false),
+        ],
+        "POCKET" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "POCKET_BOTTOM" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "POINT" => &[
+            ("name", Str, false),
+        ],
+        "POINT_ON_CURVE" => &[
+            ("name", Str, false),
+            ("basis_curve", Ref, false),
+            ("point_parameter", Real, false),
+        ],
+        "POINT_ON_PLANAR_CURVE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("pair_curve", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "POINT_ON_PLANAR_CURVE_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("range_on_pair_curve", Ref, false),
+            ("lower_limit_yaw", Any, false),
+            ("upper_limit_yaw", Any, false),
+            ("lower_limit_pitch", Any, false),
+            ("upper_limit_pitch", Any, false),
+            ("lower_limit_roll", Any, false),
+            ("upper_limit_roll", Any, false),
+        ],
+        "POINT_ON_PLANAR_CURVE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_point_on_curve", Ref, false),
+            ("input_orientation", Any, false),
+        ],
+        "POINT_ON_SURFACE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("point_parameter_u", Real, false),
+            ("point_parameter_v", Real, false),
+        ],
+        "POINT_ON_SURFACE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("pair_surface", Ref, false),
+        ],
+        "POINT_ON_SURFACE_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("range_on_pair_surface", Ref, false),
+            ("lower_limit_yaw", Any, false),
+            ("upper_limit_yaw", Any, false),
+            ("lower_limit_pitch", Any, false),
+            ("upper_limit_pitch", Any, false),
+            ("lower_limit_roll", Any, false),
+            ("upper_limit_roll", Any, false),
+        ],
+        "POINT_ON_SURFACE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_point_on_surface", Ref, false),
+            ("input_orientation", Any, false),
+        ],
+        "POINT_PLACEMENT_SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "POINT_REPLICA" => &[
+            ("name", Str, false),
+            ("parent_pt", Ref, false),
+            ("transformation", Ref, false),
+        ],
+        "POINT_STYLE" => &[
+            ("name", Str, false),
+            ("marker", Any, false),
+            ("marker_size", Any, false),
+            ("marker_colour", Ref, false),
+        ],
+        "POLY_LOOP" => &[
+            ("name", Str, false),
+            ("polygon", List, false),
+        ],
+        "POLYLINE" => &[
+            ("name", Str, false),
+            ("points", List, false),
+        ],
+        "POSITION_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "POWER_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "PRE_DEFINED_COLOUR" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_CURVE_FONT" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_DIMENSION_SYMBOL" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_GEOMETRICAL_TOLERANCE_SYMBOL" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_ITEM" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_MARKER" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_POINT_MARKER_SYMBOL" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_PRESENTATION_STYLE" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_SURFACE_CONDITION_SYMBOL" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_SYMBOL" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_TERMINATOR_SYMBOL" => &[
+            ("name", Str, false),
+        ],
+        "PRE_DEFINED_TEXT_FONT" => &[
+            ("name", Str, false),
+        ],
+        "PRECISION_QUALIFIER" => &[
+            ("precision_value", Integer, false),
+        ],
+        "PRESENTATION_AREA" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "PRESENTATION_LAYER_ASSIGNMENT" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("assigned_items", List, false),
+        ],
+        "PRESENTATION_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "PRESENTATION_SET" => &[
+        ],
+        "PRESENTATION_SIZE" => &[
+            ("unit", Ref, false),
+            ("size", Ref, false),
+        ],
+        "PRESENTATION_STYLE_ASSIGNMENT" => &[
+            ("styles", List, false),
+        ],
+        "PRESENTATION_STYLE_BY_CONTEXT" => &[
+            ("styles", List, false),
+            ("style_context", Ref, false),
+        ],
+        "PRESENTATION_VIEW" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "PRESENTED_ITEM" => &[
+        ],
+        "PRESENTED_ITEM_REPRESENTATION" => &[
+            ("presentation", Ref, false),
+            ("item", Ref, false),
+        ],
+        "PRISMATIC_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "PRISMATIC_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_actual_translation", Any, false),
+            ("upper_limit_actual_translation", Any, false),
+        ],
+        "PRISMATIC_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_translation", Real, false),
+        ],
+        "PROCESS_OPERATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("consequence", Str, false),
+            ("purpose", Str, false),
+        ],
+        "PROCESS_PLAN" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+        ],
+        "PROCESS_PRODUCT_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("defined_product", Ref, false),
+            ("process", Ref, false),
+        ],
+        "PROCESS_PROPERTY_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("process", Ref, false),
+            ("property_or_shape", Ref, false),
+        ],
+        "PRODUCT" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("frame_of_reference", List, false),
+        ],
+        "PRODUCT_CATEGORY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PRODUCT_CATEGORY_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("category", Ref, false),
+            ("sub_category", Ref, false),
+        ],
+        "PRODUCT_CLASS" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("market_context", Ref, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PRODUCT_CONCEPT" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("market_context", Ref, false),
+        ],
+        "PRODUCT_CONCEPT_CONTEXT" => &[
+            ("name", Str, false),
+            ("frame_of_reference", Ref, false),
+            ("market_segment_type", Str, false),
+        ],
+        "PRODUCT_CONCEPT_FEATURE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PRODUCT_CONCEPT_FEATURE_ASSOCIATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("concept", Ref, false),
+            ("feature", Ref, false),
+        ],
+        "PRODUCT_CONCEPT_FEATURE_CATEGORY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PRODUCT_CONCEPT_FEATURE_CATEGORY_USAGE" => &[
+            ("assigned_group", Ref, false),
+            ("items", List, false),
+        ],
+        "PRODUCT_CONCEPT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_concept", Ref, false),
+            ("related_product_concept", Ref, false),
+        ],
+        "PRODUCT_CONTEXT" => &[
+            ("name", Str, false),
+            ("frame_of_reference", Ref, false),
+            ("discipline_type", Str, false),
+        ],
+        "PRODUCT_DEFINITION" => &[
+            ("id", Str, false),
+            ("description", Str, true),
+            ("formation", Ref, false),
+            ("frame_of_reference", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_CONTEXT" => &[
+            ("name", Str, false),
+            ("frame_of_reference", Ref, false),
+            ("life_cycle_stage", Str, false),
+        ],
+        "PRODUCT_DEFINITION_CONTEXT_ASSOCIATION" => &[
+            ("definition", Ref, false),
+            ("frame_of_reference", Ref, false),
+            ("role", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_CONTEXT_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PRODUCT_DEFINITION_EFFECTIVITY" => &[
+            ("id", Str, false),
+            ("usage", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_FORMATION" => &[
+            ("id", Str, false),
+            ("description", Str, true),
+            ("of_product", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_FORMATION_RELATIONSHIP" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition_formation", Ref, false),
+            ("related_product_definition_formation", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_FORMATION_WITH_SPECIFIED_SOURCE" => &[
+            ("id", Str, false),
+            ("description", Str, true),
+            ("of_product", Ref, false),
+            ("make_or_buy", Enum, false),
+        ],
+        "PRODUCT_DEFINITION_OCCURRENCE_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("occurrence", Ref, false),
+            ("occurrence_usage", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_PROCESS" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+            ("identification", Str, false),
+        ],
+        "PRODUCT_DEFINITION_RELATIONSHIP" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_RESOURCE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("usage", List, false),
+            ("kind", Ref, false),
+            ("id", Str, false),
+            ("description", Str, true),
+            ("formation", Ref, false),
+            ("frame_of_reference", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_SHAPE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_SUBSTITUTE" => &[
+            ("description", Str, true),
+            ("context_relationship", Ref, false),
+            ("substitute_definition", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_USAGE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+        ],
+        "PRODUCT_DEFINITION_WITH_ASSOCIATED_DOCUMENTS" => &[
+            ("id", Str, false),
+            ("description", Str, true),
+            ("formation", Ref, false),
+            ("frame_of_reference", Ref, false),
+            ("documentation_ids", List, false),
+        ],
+        "PRODUCT_IDENTIFICATION" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_concept", Ref, false),
+            ("purpose", Str, true),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "PRODUCT_PROCESS_PLAN" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+            ("identification", Str, false),
+        ],
+        "PRODUCT_RELATED_PRODUCT_CATEGORY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("products", List, false),
+        ],
+        "PRODUCT_SPECIFICATION" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_concept", Ref, false),
+            ("purpose", Str, true),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("item_concept_feature", List, false),
+        ],
+        "PROJECTED_ZONE_DEFINITION" => &[
+            ("zone", Ref, false),
+            ("boundaries", List, false),
+            ("projection_end", Ref, false),
+            ("projected_length", Ref, false),
+        ],
+        "PROJECTION_CURVE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "PROJECTION_DIRECTED_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "PROMISSORY_USAGE_OCCURRENCE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+            ("reference_designator", Str, true),
+        ],
+        "PROPERTY_DEFINITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("definition", Ref, false),
+        ],
+        "PROPERTY_DEFINITION_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("relating_property_definition", Ref, false),
+            ("related_property_definition", Ref, false),
+        ],
+        "PROPERTY_DEFINITION_REPRESENTATION" => &[
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+        ],
+        "PROPERTY_PROCESS" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+            ("identification", Str, false),
+        ],
+        "QUALIFIED_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+            ("qualifiers", List, false),
+        ],
+        "QUALITATIVE_UNCERTAINTY" => &[
+            ("measure_name", Str, false),
+            ("description", Str, false),
+            ("uncertainty_value", Str, false),
+        ],
+        "QUANTIFIED_ASSEMBLY_COMPONENT_USAGE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+            ("reference_designator", Str, true),
+            ("quantity", Ref, false),
+        ],
+        "QUASI_UNIFORM_CURVE" => &[
+            ("name", Str, false),
+            ("degree", Integer, false),
+            ("control_points_list", List, false),
+            ("curve_form", Enum, false),
+            ("closed_curve", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "QUASI_UNIFORM_SURFACE" => &[
+            ("name", Str, false),
+            ("u_degree", Integer, false),
+            ("v_degree", Integer, false),
+            ("control_points_list", List, false),
+            ("surface_form", Enum, false),
+            ("u_closed", Logical, false),
+            ("v_closed", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "RACK_AND_PINION_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("pinion_radius", Real, false),
+        ],
+        "RACK_AND_PINION_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_rack_displacement", Any, false),
+            ("upper_limit_rack_displacement", Any, false),
+        ],
+        "RACK_AND_PINION_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_displacement", Real, false),
+        ],
+        "RADIUS_DIMENSION" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "RATIO_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "RATIO_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "RATIONAL_B_SPLINE_CURVE" => &[
+            ("name", Str, false),
+            ("degree", Integer, false),
+            ("control_points_list", List, false),
+            ("curve_form", Enum, false),
+            ("closed_curve", Logical, false),
+            ("self_intersect", Logical, false),
+            ("weights_data", List, false),
+        ],
+        "RATIONAL_B_SPLINE_SURFACE" => &[
+            ("name", Str, false),
+            ("u_degree", Integer, false),
+            ("v_degree", Integer, false),
+            ("control_points_list", List, false),
+            ("surface_form", Enum, false),
+            ("u_closed", Logical, false),
+            ("v_closed", Logical, false),
+            ("self_intersect", Logical, false),
+            ("weights_data", List, false),
+        ],
+        "REAL_DEFINED_FUNCTION" => &[
+        ],
+        "REAL_LITERAL" => &[
+            ("the_value", Real, false),
+        ],
+        "REAL_NUMERIC_VARIABLE" => &[
+        ],
+        "RECTANGULAR_CLOSED_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "RECTANGULAR_COMPOSITE_SURFACE" => &[
+            ("name", Str, false),
+            ("segments", List, false),
+        ],
+        "RECTANGULAR_PATTERN" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "RECTANGULAR_TRIMMED_SURFACE" => &[
+            ("name", Str, false),
+            ("basis_surface", Ref, false),
+            ("u1", Real, false),
+            ("u2", Real, false),
+            ("v1", Real, false),
+            ("v2", Real, false),
+            ("usense", Logical, false),
+            ("vsense", Logical, false),
+        ],
+        "REFERENCED_MODIFIED_DATUM" => &[
+            ("precedence", Integer, false),
+            ("referenced_datum", Ref, false),
+            ("modifier", Enum, false),
+        ],
+        "RELATIVE_EVENT_OCCURRENCE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("base_event", Ref, false),
+            ("offset", Ref, false),
+        ],
+        "REP_ITEM_GROUP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("name", Str, false),
+        ],
+        "REPARAMETRISED_COMPOSITE_CURVE_SEGMENT" => &[
+            ("transition", Enum, false),
+            ("same_sense", Logical, false),
+            ("parent_curve", Ref, false),
+            ("param_length", Real, false),
+        ],
+        "REPLICATE_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "REPRESENTATION_CONTEXT" => &[
+            ("context_identifier", Str, false),
+            ("context_type", Str, false),
+        ],
+        "REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+        ],
+        "REPRESENTATION_MAP" => &[
+            ("mapping_origin", Ref, false),
+            ("mapped_representation", Ref, false),
+        ],
+        "REPRESENTATION_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+        ],
+        "REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+            ("transformation_operator", Ref, false),
+        ],
+        "REQUIREMENT_FOR_ACTION_RESOURCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("kind", Ref, false),
+            ("operations", List, false),
+            ("resources", List, false),
+        ],
+        "RESOURCE_PROPERTY" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("resource", Ref, false),
+        ],
+        "RESOURCE_PROPERTY_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("property", Ref, false),
+            ("representation", Ref, false),
+        ],
+        "RESOURCE_REQUIREMENT_TYPE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+        ],
+        "RESULTING_PATH" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+            ("related_frame", Ref, false),
+            ("controlling_joints", List, false),
+        ],
+        "RETENTION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("chosen_method", Ref, false),
+        ],
+        "REVOLUTE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "REVOLUTE_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_actual_rotation", Any, false),
+            ("upper_limit_actual_rotation", Any, false),
+        ],
+        "REVOLUTE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_rotation", Real, false),
+        ],
+        "REVOLVED_AREA_SOLID" => &[
+            ("name", Str, false),
+            ("swept_area", Ref, false),
+            ("axis", Ref, false),
+            ("angle", Real, false),
+        ],
+        "REVOLVED_FACE_SOLID" => &[
+            ("name", Str, false),
+            ("swept_face", Ref, false),
+            ("axis", Ref, false),
+            ("angle", Real, false),
+        ],
+        "RIB" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "RIGHT_ANGULAR_WEDGE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("x", Real, false),
+            ("y", Real, false),
+            ("z", Real, false),
+            ("ltx", Real, false),
+        ],
+        "RIGHT_CIRCULAR_CONE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("height", Real, false),
+            ("radius", Real, false),
+            ("semi_angle", Real, false),
+        ],
+        "RIGHT_CIRCULAR_CYLINDER" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("height", Real, false),
+            ("radius", Real, false),
+        ],
+        "ROLE_ASSOCIATION" => &[
+            ("role", Ref, false),
+            ("item_with_role", Ref, false),
+        ],
+        "ROLLING_CURVE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("curve_1", Ref, false),
+            ("curve_2", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ROLLING_CURVE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_point_on_curve_1", Ref, false),
+        ],
+        "ROLLING_SURFACE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("surface_1", Ref, false),
+            ("surface_2", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "ROLLING_SURFACE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_point_on_surface", Ref, false),
+            ("actual_rotation", Real, false),
+        ],
+        "ROTATION_ABOUT_DIRECTION" => &[
+            ("direction_of_axis", Ref, false),
+            ("rotation_angle", Real, false),
+        ],
+        "ROUND_HOLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "ROUNDED_U_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "ROUNDNESS_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "RULED_SURFACE_SWEPT_AREA_SOLID" => &[
+            ("name", Str, false),
+            ("swept_area", Ref, false),
+            ("directrix", Ref, false),
+            ("start_param", Real, false),
+            ("end_param", Real, false),
+            ("reference_surface", Ref, false),
+        ],
+        "RUNOUT_ZONE_DEFINITION" => &[
+            ("zone", Ref, false),
+            ("boundaries", List, false),
+            ("orientation", Ref, false),
+        ],
+        "RUNOUT_ZONE_ORIENTATION" => &[
+            ("angle", Ref, false),
+        ],
+        "RUNOUT_ZONE_ORIENTATION_REFERENCE_DIRECTION" => &[
+            ("angle", Ref, false),
+            ("orientation_defining_relationship", Ref, false),
+        ],
+        "SCREW_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("pitch", Real, false),
+        ],
+        "SCREW_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_actual_rotation", Any, false),
+            ("upper_limit_actual_rotation", Any, false),
+        ],
+        "SCREW_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_rotation", Real, false),
+        ],
+        "SEAM_CURVE" => &[
+            ("name", Str, false),
+            ("curve_3d", Ref, false),
+            ("associated_geometry", List, false),
+            ("master_representation", Enum, false),
+        ],
+        "SEAM_EDGE" => &[
+            ("name", Str, false),
+            ("edge_start", Ref, false),
+            ("edge_end", Ref, false),
+            ("edge_element", Ref, false),
+            ("orientation", Logical, false),
+            ("pcurve_reference", Ref, false),
+        ],
+        "SECURITY_CLASSIFICATION" => &[
+            ("name", Str, false),
+            ("purpose", Str, false),
+            ("security_level", Ref, false),
+        ],
+        "SECURITY_CLASSIFICATION_ASSIGNMENT" => &[
+            ("assigned_security_classification", Ref, false),
+        ],
+        "SECURITY_CLASSIFICATION_LEVEL" => &[
+            ("name", Str, false),
+        ],
+        "SERIAL_NUMBERED_EFFECTIVITY" => &[
+            ("id", Str, false),
+            ("effectivity_start_id", Str, false),
+            ("effectivity_end_id", Str, true),
+        ],
+        "SHAPE_ASPECT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "SHAPE_ASPECT_ASSOCIATIVITY" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "SHAPE_ASPECT_DERIVING_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "SHAPE_ASPECT_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "SHAPE_ASPECT_TRANSITION" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "SHAPE_DEFINING_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_shape_aspect", Ref, false),
+            ("related_shape_aspect", Ref, false),
+        ],
+        "SHAPE_DEFINITION_REPRESENTATION" => &[
+            ("definition", Ref, false),
+            ("used_representation", Ref, false),
+        ],
+        "SHAPE_DIMENSION_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "SHAPE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "SHAPE_REPRESENTATION_RELATIONSHIP" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("rep_1", Ref, false),
+            ("rep_2", Ref, false),
+        ],
+        "SHAPE_REPRESENTATION_WITH_PARAMETERS" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "SHELL_BASED_SURFACE_MODEL" => &[
+            ("name", Str, false),
+            ("sbsm_boundary", List, false),
+        ],
+        "SI_UNIT" => &[
+            ("dimensions", Derived, false),
+            ("prefix", Enum, true),
+            ("name", Enum, false),
+        ],
+        "SIMPLE_BOOLEAN_EXPRESSION" => &[
+        ],
+        "SIMPLE_GENERIC_EXPRESSION" => &[
+        ],
+        "SIMPLE_NUMERIC_EXPRESSION" => &[
+        ],
+        "SIMPLE_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+        ],
+        "SIMPLE_STRING_EXPRESSION" => &[
+        ],
+        "SIN_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "SLASH_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "SLIDING_CURVE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("curve_1", Ref, false),
+            ("curve_2", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "SLIDING_CURVE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_point_on_curve_1", Ref, false),
+            ("actual_point_on_curve_2", Ref, false),
+        ],
+        "SLIDING_SURFACE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("surface_1", Ref, false),
+            ("surface_2", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "SLIDING_SURFACE_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_point_on_surface_1", Ref, false),
+            ("actual_point_on_surface_2", Ref, false),
+            ("actual_rotation", Real, false),
+        ],
+        "SLOT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "SLOT_END" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "SOLID_ANGLE_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "SOLID_ANGLE_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "SOLID_MODEL" => &[
+            ("name", Str, false),
+        ],
+        "SOLID_REPLICA" => &[
+            ("name", Str, false),
+            ("parent_solid", Ref, false),
+            ("transformation", Ref, false),
+        ],
+        "SPECIFIED_HIGHER_USAGE_OCCURRENCE" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_product_definition", Ref, false),
+            ("related_product_definition", Ref, false),
+            ("reference_designator", Str, true),
+            ("upper_usage", Ref, false),
+            ("next_usage", Ref, false),
+        ],
+        "SPHERE" => &[
+            ("name", Str, false),
+            ("radius", Real, false),
+            ("centre", Ref, false),
+        ],
+        "SPHERICAL_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "SPHERICAL_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_yaw", Any, false),
+            ("upper_limit_yaw", Any, false),
+            ("lower_limit_pitch", Any, false),
+            ("upper_limit_pitch", Any, false),
+            ("lower_limit_roll", Any, false),
+            ("upper_limit_roll", Any, false),
+        ],
+        "SPHERICAL_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("input_orientation", Any, false),
+        ],
+        "SPHERICAL_SURFACE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("radius", Real, false),
+        ],
+        "SQL_MAPPABLE_DEFINED_FUNCTION" => &[
+        ],
+        "SQUARE_ROOT_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "SQUARE_U_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "STANDARD_UNCERTAINTY" => &[
+            ("measure_name", Str, false),
+            ("description", Str, false),
+            ("uncertainty_value", Real, false),
+        ],
+        "STRAIGHTNESS_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "STRING_DEFINED_FUNCTION" => &[
+        ],
+        "STRING_EXPRESSION" => &[
+        ],
+        "STRING_LITERAL" => &[
+            ("the_value", Str, false),
+        ],
+        "STRING_VARIABLE" => &[
+        ],
+        "STRUCTURED_DIMENSION_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "STYLED_ITEM" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+        ],
+        "SUBEDGE" => &[
+            ("name", Str, false),
+            ("edge_start", Ref, false),
+            ("edge_end", Ref, false),
+            ("parent_edge", Ref, false),
+        ],
+        "SUBFACE" => &[
+            ("name", Str, false),
+            ("bounds", List, false),
+            ("parent_face", Ref, false),
+        ],
+        "SUBSTRING_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        "SURFACE" => &[
+            ("name", Str, false),
+        ],
+        "SURFACE_CONDITION_CALLOUT" => &[
+            ("name", Str, false),
+            ("contents", List, false),
+        ],
+        "SURFACE_CURVE" => &[
+            ("name", Str, false),
+            ("curve_3d", Ref, false),
+            ("associated_geometry", List, false),
+            ("master_representation", Enum, false),
+        ],
+        "SURFACE_CURVE_SWEPT_AREA_SOLID" => &[
+            ("name", Str, false),
+            ("swept_area", Ref, false),
+            ("directrix", Ref, false),
+            ("start_param", Real, false),
+            ("end_param", Real, false),
+            ("reference_surface", Ref, false),
+        ],
+        "SURFACE_OF_LINEAR_EXTRUSION" => &[
+            ("name", Str, false),
+            ("swept_curve", Ref, false),
+            ("extrusion_axis", Ref, false),
+        ],
+        "SURFACE_OF_REVOLUTION" => &[
+            ("name", Str, false),
+            ("swept_curve", Ref, false),
+            ("axis_position", Ref, false),
+        ],
+        "SURFACE_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("surface_1", Ref, false),
+            ("surface_2", Ref, false),
+            ("orientation", Logical, false),
+        ],
+        "SURFACE_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("range_on_surface_1", Ref, false),
+            ("range_on_surface_2", Ref, false),
+            ("lower_limit_actual_rotation", Any, false),
+            ("upper_limit_actual_rotation", Any, false),
+        ],
+        "SURFACE_PATCH" => &[
+            ("parent_surface", Ref, false),
+            ("u_transition", Enum, false),
+            ("v_transition", Enum, false),
+            ("u_sense", Logical, false),
+            ("v_sense", Logical, false),
+        ],
+        "SURFACE_PROFILE_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+        ],
+        "SURFACE_RENDERING_PROPERTIES" => &[
+            ("rendered_colour", Ref, false),
+        ],
+        "SURFACE_REPLICA" => &[
+            ("name", Str, false),
+            ("parent_surface", Ref, false),
+            ("transformation", Ref, false),
+        ],
+        "SURFACE_SIDE_STYLE" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+        ],
+        "SURFACE_STYLE_BOUNDARY" => &[
+            ("style_of_boundary", Ref, false),
+        ],
+        "SURFACE_STYLE_CONTROL_GRID" => &[
+            ("style_of_control_grid", Ref, false),
+        ],
+        "SURFACE_STYLE_FILL_AREA" => &[
+            ("fill_area", Ref, false),
+        ],
+        "SURFACE_STYLE_PARAMETER_LINE" => &[
+            ("style_of_parameter_lines", Ref, false),
+            ("direction_counts", List, false),
+        ],
+        "SURFACE_STYLE_REFLECTANCE_AMBIENT" => &[
+            ("ambient_reflectance", Real, false),
+        ],
+        "SURFACE_STYLE_REFLECTANCE_AMBIENT_DIFFUSE" => &[
+            ("ambient_reflectance", Real, false),
+            ("diffuse_reflectance", Real, false),
+        ],
+        "SURFACE_STYLE_REFLECTANCE_AMBIENT_DIFFUSE_SPECULAR" => &[
+            ("ambient_reflectance", Real, false),
+            ("diffuse_reflectance", Real, false),
+            ("specular_reflectance", Real, false),
+            ("specular_exponent", Real, false),
+            ("specular_colour", Ref, false),
+        ],
+        "SURFACE_STYLE_RENDERING" => &[
+            ("rendering_method", Enum, false),
+            ("surface_colour", Ref, false),
+        ],
+        "SURFACE_STYLE_RENDERING_WITH_PROPERTIES" => &[
+            ("rendering_method", Enum, false),
+            ("surface_colour", Ref, false),
+            ("properties", List, false),
+        ],
+        "SURFACE_STYLE_SEGMENTATION_CURVE" => &[
+            ("style_of_segmentation_curve", Ref, false),
+        ],
+        "SURFACE_STYLE_SILHOUETTE" => &[
+            ("style_of_silhouette", Ref, false),
+        ],
+        "SURFACE_STYLE_TRANSPARENT" => &[
+            ("transparency", Real, false),
+        ],
+        "SURFACE_STYLE_USAGE" => &[
+            ("side", Enum, false),
+            ("style", Ref, false),
+        ],
+        "SURFACE_TEXTURE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "SWEPT_AREA_SOLID" => &[
+            ("name", Str, false),
+            ("swept_area", Ref, false),
+        ],
+        "SWEPT_DISK_SOLID" => &[
+            ("name", Str, false),
+            ("directrix", Ref, false),
+            ("radius", Real, false),
+            ("inner_radius", Real, true),
+            ("start_param", Real, false),
+            ("end_param", Real, false),
+        ],
+        "SWEPT_FACE_SOLID" => &[
+            ("name", Str, false),
+            ("swept_face", Ref, false),
+        ],
+        "SWEPT_SURFACE" => &[
+            ("name", Str, false),
+            ("swept_curve", Ref, false),
+        ],
+        "SYMBOL_COLOUR" => &[
+            ("colour_of_symbol", Ref, false),
+        ],
+        "SYMBOL_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "SYMBOL_REPRESENTATION_MAP" => &[
+            ("mapping_origin", Ref, false),
+            ("mapped_representation", Ref, false),
+        ],
+        "SYMBOL_STYLE" => &[
+            ("name", Str, false),
+            ("style_of_symbol", Ref, false),
+        ],
+        "SYMBOL_TARGET" => &[
+            ("name", Str, false),
+            ("placement", Ref, false),
+            ("x_scale", Real, false),
+            ("y_scale", Real, false),
+        ],
+        "SYMMETRIC_SHAPE_ASPECT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "SYMMETRY_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "TACTILE_APPEARANCE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "TAN_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "TANGENT" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "TAPER" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "TEE_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "TERMINATOR_SYMBOL" => &[
+            ("name", Str, false),
+            ("styles", List, false),
+            ("item", Ref, false),
+            ("annotated_curve", Ref, false),
+        ],
+        "TEXT_LITERAL" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+        ],
+        "TEXT_LITERAL_WITH_ASSOCIATED_CURVES" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+            ("associated_curves", List, false),
+        ],
+        "TEXT_LITERAL_WITH_BLANKING_BOX" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+            ("blanking", Ref, false),
+        ],
+        "TEXT_LITERAL_WITH_DELINEATION" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+            ("delineation", Str, false),
+        ],
+        "TEXT_LITERAL_WITH_EXTENT" => &[
+            ("name", Str, false),
+            ("literal", Str, false),
+            ("placement", Ref, false),
+            ("alignment", Str, false),
+            ("path", Enum, false),
+            ("font", Ref, false),
+            ("extent", Ref, false),
+        ],
+        "TEXT_STRING_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "TEXT_STYLE" => &[
+            ("name", Str, false),
+            ("character_appearance", Ref, false),
+        ],
+        "TEXT_STYLE_FOR_DEFINED_FONT" => &[
+            ("text_colour", Ref, false),
+        ],
+        "TEXT_STYLE_WITH_BOX_CHARACTERISTICS" => &[
+            ("name", Str, false),
+            ("character_appearance", Ref, false),
+            ("characteristics", List, false),
+        ],
+        "TEXT_STYLE_WITH_MIRROR" => &[
+            ("name", Str, false),
+            ("character_appearance", Ref, false),
+            ("mirror_placement", Ref, false),
+        ],
+        "TEXT_STYLE_WITH_SPACING" => &[
+            ("name", Str, false),
+            ("character_appearance", Ref, false),
+            ("character_spacing", Any, false),
+        ],
+        "THERMODYNAMIC_TEMPERATURE_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "THERMODYNAMIC_TEMPERATURE_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "THREAD" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "TIME_INTERVAL" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "TIME_INTERVAL_ASSIGNMENT" => &[
+            ("assigned_time_interval", Ref, false),
+            ("role", Ref, false),
+        ],
+        "TIME_INTERVAL_BASED_EFFECTIVITY" => &[
+            ("id", Str, false),
+            ("effectivity_period", Ref, false),
+        ],
+        "TIME_INTERVAL_ROLE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "TIME_INTERVAL_WITH_BOUNDS" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("primary_bound", Ref, true),
+            ("secondary_bound", Ref, true),
+            ("duration", Ref, true),
+        ],
+        "TIME_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "TIME_UNIT" => &[
+            ("dimensions", Ref, false),
+        ],
+        "TOLERANCE_VALUE" => &[
+            ("lower_bound", Ref, false),
+            ("upper_bound", Ref, false),
+        ],
+        "TOLERANCE_ZONE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+            ("defining_tolerance", List, false),
+            ("form", Ref, false),
+        ],
+        "TOLERANCE_ZONE_DEFINITION" => &[
+            ("zone", Ref, false),
+            ("boundaries", List, false),
+        ],
+        "TOLERANCE_ZONE_FORM" => &[
+            ("name", Str, false),
+        ],
+        "TOPOLOGICAL_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+        ],
+        "TOROIDAL_SURFACE" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("major_radius", Real, false),
+            ("minor_radius", Real, false),
+        ],
+        "TORUS" => &[
+            ("name", Str, false),
+            ("position", Ref, false),
+            ("major_radius", Real, false),
+            ("minor_radius", Real, false),
+        ],
+        "TOTAL_RUNOUT_TOLERANCE" => &[
+            ("name", Str, false),
+            ("description", Str, false),
+            ("magnitude", Ref, false),
+            ("toleranced_shape_aspect", Ref, false),
+            ("datum_system", List, false),
+        ],
+        "TRANSITION_FEATURE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "TRIMMED_CURVE" => &[
+            ("name", Str, false),
+            ("basis_curve", Ref, false),
+            ("trim_1", List, false),
+            ("trim_2", List, false),
+            ("sense_agreement", Logical, false),
+            ("master_representation", Enum, false),
+        ],
+        "TWO_DIRECTION_REPEAT_FACTOR" => &[
+            ("name", Str, false),
+            ("repeat_factor", Ref, false),
+            ("second_repeat_factor", Ref, false),
+        ],
+        "TYPE_QUALIFIER" => &[
+            ("name", Str, false),
+        ],
+        "UNARY_BOOLEAN_EXPRESSION" => &[
+            ("operand", Ref, false),
+        ],
+        "UNARY_FUNCTION_CALL" => &[
+            ("operand", Ref, false),
+        ],
+        "UNARY_GENERIC_EXPRESSION" => &[
+            ("operand", Ref, false),
+        ],
+        "UNARY_NUMERIC_EXPRESSION" => &[
+            ("operand", Ref, false),
+        ],
+        "UNCERTAINTY_ASSIGNED_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+            ("uncertainty", List, false),
+        ],
+        "UNCERTAINTY_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+            ("name", Str, false),
+            ("description", Str, true),
+        ],
+        "UNCERTAINTY_QUALIFIER" => &[
+            ("measure_name", Str, false),
+            ("description", Str, false),
+        ],
+        "UNCONSTRAINED_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+        ],
+        "UNCONSTRAINED_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("actual_placement", Ref, false),
+        ],
+        "UNIFORM_CURVE" => &[
+            ("name", Str, false),
+            ("degree", Integer, false),
+            ("control_points_list", List, false),
+            ("curve_form", Enum, false),
+            ("closed_curve", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "UNIFORM_SURFACE" => &[
+            ("name", Str, false),
+            ("u_degree", Integer, false),
+            ("v_degree", Integer, false),
+            ("control_points_list", List, false),
+            ("surface_form", Enum, false),
+            ("u_closed", Logical, false),
+            ("v_closed", Logical, false),
+            ("self_intersect", Logical, false),
+        ],
+        "UNIVERSAL_PAIR" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("transform_item_1", Ref, false),
+            ("transform_item_2", Ref, false),
+            ("joint", Ref, false),
+            ("input_skew_angle", Real, true),
+        ],
+        "UNIVERSAL_PAIR_RANGE" => &[
+            ("applies_to_pair", Ref, false),
+            ("lower_limit_first_rotation", Any, false),
+            ("upper_limit_first_rotation", Any, false),
+            ("lower_limit_second_rotation", Any, false),
+            ("upper_limit_second_rotation", Any, false),
+        ],
+        "UNIVERSAL_PAIR_VALUE" => &[
+            ("applies_to_pair", Ref, false),
+            ("first_rotation_angle", Real, false),
+            ("second_rotation_angle", Real, false),
+        ],
+        "VALUE_FUNCTION" => &[
+            ("operand", Ref, false),
+        ],
+        "VALUE_RANGE" => &[
+            ("name", Str, false),
+            ("item_element", Any, false),
+        ],
+        "VALUE_REPRESENTATION_ITEM" => &[
+            ("name", Str, false),
+            ("value_component", Any, false),
+        ],
+        "VARIABLE" => &[
+        ],
+        "VARIABLE_SEMANTICS" => &[
+        ],
+        "VECTOR" => &[
+            ("name", Str, false),
+            ("orientation", Ref, false),
+            ("magnitude", Real, false),
+        ],
+        "VECTOR_STYLE" => &[
+            ("name", Str, false),
+            ("name", Str, false),
+            ("curve_font", Ref, false),
+            ("curve_width", Any, false),
+            ("curve_colour", Ref, false),
+        ],
+        "VEE_PROFILE" => &[
+            ("name", Str, false),
+            ("description", Str, true),
+            ("of_shape", Ref, false),
+            ("product_definitional", Logical, false),
+        ],
+        "VERSIONED_ACTION_REQUEST" => &[
+            ("id", Str, false),
+            ("version", Str, false),
+            ("purpose", Str, false),
+            ("description", Str, true),
+        ],
+        "VERSIONED_ACTION_REQUEST_RELATIONSHIP" => &[
+            ("id", Str, false),
+            ("name", Str, false),
+            ("description", Str, true),
+            ("relating_versioned_action_request", Ref, false),
+            ("related_versioned_action_request", Ref, false),
+        ],
+        "VERTEX" => &[
+            ("name", Str, false),
+        ],
+        "VERTEX_LOOP" => &[
+            ("name", Str, false),
+            ("loop_vertex", Ref, false),
+        ],
+        "VERTEX_POINT" => &[
+            ("name", Str, false),
+            ("vertex_geometry", Ref, false),
+        ],
+        "VIEW_VOLUME" => &[
+            ("projection_type", Enum, false),
+            ("projection_point", Ref, false),
+            ("view_plane_distance", Real, false),
+            ("front_plane_distance", Real, false),
+            ("front_plane_clipping", Logical, false),
+            ("back_plane_distance", Real, false),
+            ("back_plane_clipping", Logical, false),
+            ("view_volume_sides_clipping", Logical, false),
+            ("view_window", Ref, false),
+        ],
+        "VISUAL_APPEARANCE_REPRESENTATION" => &[
+            ("name", Str, false),
+            ("items", List, false),
+            ("context_of_items", Ref, false),
+        ],
+        "VOLUME_MEASURE_WITH_UNIT" => &[
+            ("value_component", Any, false),
+            ("unit_component", Ref, false),
+        ],
+        "VOLUME_UNIT" => &[
+            ("elements", List, false),
+        ],
+        "XOR_EXPRESSION" => &[
+            ("operands", List, false),
+        ],
+        _ => return None,
+    };
+    Some(out)
+}
 impl<'a> Entity<'a> {
     pub fn upstream(&self) -> Vec<usize> {
         let mut out = Vec::new();
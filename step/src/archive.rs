@@ -0,0 +1,537 @@
+//! Reads STEP files which have been compressed, either as a gzip stream
+//! (`.stp.gz`) or inside a ZIP archive (`.stpz`, or a `.zip` exported by a
+//! PLM system).
+//!
+//! Both formats use DEFLATE, which is decoded here rather than pulling in a
+//! compression library.  Only what's needed to read a single STEP file is
+//! supported: ZIP archives must not be encrypted, split, or use ZIP64.
+use std::borrow::Cow;
+use std::convert::TryInto;
+use std::io;
+
+/// Largest output which [`decompress`] will produce, so that a small crafted
+/// file can't exhaust memory.  This matches the largest file which can be
+/// stored in a ZIP archive without ZIP64.
+pub const DEFAULT_LIMIT: usize = u32::MAX as usize;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Checks whether the data is a gzip stream or ZIP archive
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(GZIP_MAGIC) || data.starts_with(ZIP_MAGIC)
+}
+
+/// Decompresses a gzip stream or ZIP archive, returning any other data as
+/// it is.  From an archive, the first file with a STEP extension (`.stp`,
+/// `.step`, `.p21`, or `.stpx`) is returned, or the first file if none has
+/// one.
+///
+/// Output is limited to [`DEFAULT_LIMIT`] bytes; use
+/// [`decompress_with_limit`] to change that.
+pub fn decompress(data: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    decompress_with_limit(data, DEFAULT_LIMIT)
+}
+
+/// Decompresses data like [`decompress`], failing with
+/// [`io::ErrorKind::InvalidData`] as soon as the output would be longer
+/// than `limit` bytes.  A ZIP entry is also limited to the size which the
+/// archive records for it.
+pub fn decompress_with_limit(data: &[u8], limit: usize)
+    -> io::Result<Cow<'_, [u8]>>
+{
+    profile_scope!("decompress");
+    if data.starts_with(GZIP_MAGIC) {
+        gunzip(data, limit).map(Cow::Owned)
+    } else if data.starts_with(ZIP_MAGIC) {
+        unzip(data, limit).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(data))
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn u16_at(data: &[u8], i: usize) -> io::Result<usize> {
+    data.get(i..i + 2)
+        .map(|b| u16::from_le_bytes(b.try_into().unwrap()) as usize)
+        .ok_or_else(|| invalid("truncated header"))
+}
+
+fn u32_at(data: &[u8], i: usize) -> io::Result<u32> {
+    data.get(i..i + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| invalid("truncated header"))
+}
+
+/// Decompresses a gzip file, which may hold several members
+fn gunzip(mut data: &[u8], limit: usize) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    while data.starts_with(GZIP_MAGIC) {
+        if data.get(2) != Some(&8) {
+            return Err(invalid("unknown gzip compression method"));
+        }
+        let flags = data.get(3).copied().unwrap_or(0);
+        let mut i = 10;
+        if flags & 4 != 0 { // FEXTRA
+            i += 2 + u16_at(data, i)?;
+        }
+        for bit in &[8, 16] { // FNAME, FCOMMENT
+            if flags & bit != 0 {
+                let n = data.get(i..).and_then(|d| d.iter().position(|c| *c == 0))
+                    .ok_or_else(|| invalid("truncated gzip header"))?;
+                i += n + 1;
+            }
+        }
+        if flags & 2 != 0 { // FHCRC
+            i += 2;
+        }
+        let start = out.len();
+        let used = inflate(data.get(i..).unwrap_or(&[]), &mut out, limit)?;
+        i += used;
+        if u32_at(data, i)? != crc32(&out[start..]) {
+            return Err(invalid("gzip checksum mismatch"));
+        }
+        if u32_at(data, i + 4)? != (out.len() - start) as u32 {
+            return Err(invalid("gzip length mismatch"));
+        }
+        data = &data[i + 8..];
+    }
+    Ok(out)
+}
+
+/// Extracts the STEP file from a ZIP archive
+fn unzip(data: &[u8], limit: usize) -> io::Result<Vec<u8>> {
+    // The central directory (found through the record at the end of the
+    // file) has the sizes, which local headers may leave out
+    let eocd = (0..data.len().saturating_sub(21)).rev()
+        .find(|i| data[*i..].starts_with(b"PK\x05\x06"))
+        .ok_or_else(|| invalid("missing ZIP central directory"))?;
+    let count = u16_at(data, eocd + 10)?;
+    let mut i = u32_at(data, eocd + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(data, i)? != 0x0201_4b50 {
+            return Err(invalid("bad ZIP central directory entry"));
+        }
+        let name_len = u16_at(data, i + 28)?;
+        let name = data.get(i + 46..i + 46 + name_len)
+            .ok_or_else(|| invalid("truncated ZIP entry"))?;
+        entries.push((String::from_utf8_lossy(name).into_owned(), i));
+        i += 46 + name_len + u16_at(data, i + 30)? + u16_at(data, i + 32)?;
+    }
+    let is_step = |n: &str| {
+        let n = n.to_ascii_lowercase();
+        n.ends_with(".stp") || n.ends_with(".step") || n.ends_with(".p21")
+            || n.ends_with(".stpx")
+    };
+    let (_, entry) = entries.iter()
+        .find(|(n, _)| is_step(n))
+        .or_else(|| entries.iter().find(|(n, _)| !n.ends_with('/')))
+        .ok_or_else(|| invalid("empty ZIP archive"))?;
+
+    let flags = u16_at(data, entry + 8)?;
+    let method = u16_at(data, entry + 10)?;
+    let crc = u32_at(data, entry + 16)?;
+    let compressed_size = u32_at(data, entry + 20)? as usize;
+    let size = u32_at(data, entry + 24)? as usize;
+    let local = u32_at(data, entry + 42)? as usize;
+    if flags & 1 != 0 {
+        return Err(invalid("encrypted ZIP archives aren't supported"));
+    }
+    if u32_at(data, local)? != 0x0403_4b50 {
+        return Err(invalid("bad ZIP local header"));
+    }
+    let start = local + 30 + u16_at(data, local + 26)? +
+        u16_at(data, local + 28)?;
+    let body = data.get(start..).ok_or_else(|| invalid("truncated ZIP"))?;
+
+    if size > limit {
+        return Err(invalid("ZIP entry is too large"));
+    }
+    let out = match method {
+        0 => body.get(..size)
+            .ok_or_else(|| invalid("truncated ZIP"))?
+            .to_vec(),
+        8 => {
+            // Inflating stops once the output passes the recorded size, but
+            // a crafted archive could still claim up to 4 GiB, so don't
+            // reserve more than a generous compression ratio would give
+            let mut out = Vec::with_capacity(
+                size.min(compressed_size.saturating_mul(64)));
+            inflate(body, &mut out, size)?;
+            out
+        },
+        _ => return Err(invalid("unknown ZIP compression method")),
+    };
+    if out.len() != size || crc32(&out) != crc {
+        return Err(invalid("ZIP checksum mismatch"));
+    }
+    Ok(out)
+}
+
+/// Lookup table for [`crc32`], built at compile time
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |c, b|
+        CRC_TABLE[((c ^ *b as u32) & 0xff) as usize] ^ (c >> 8))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// DEFLATE (RFC 1951)
+
+struct Bits<'a> {
+    data: &'a [u8],
+    /// Position in bits
+    pos: usize,
+}
+
+impl Bits<'_> {
+    fn bits(&mut self, n: usize) -> io::Result<usize> {
+        let mut v = 0;
+        for k in 0..n {
+            let byte = self.data.get(self.pos / 8)
+                .ok_or_else(|| invalid("truncated DEFLATE stream"))?;
+            v |= ((*byte as usize >> (self.pos % 8)) & 1) << k;
+            self.pos += 1;
+        }
+        Ok(v)
+    }
+
+    /// Returns the number of bytes used, rounding up a partial byte
+    fn bytes_used(&self) -> usize {
+        self.pos.div_ceil(8)
+    }
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length
+/// and the symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for l in lengths {
+            counts[*l as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for i in 1..15 {
+            offsets[i + 1] = offsets[i] + counts[i];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (s, l) in lengths.iter().enumerate() {
+            if *l != 0 {
+                symbols[offsets[*l as usize] as usize] = s as u16;
+                offsets[*l as usize] += 1;
+            }
+        }
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> io::Result<usize> {
+        // Codes are read one bit at a time, most significant bit first
+        let mut code = 0;
+        let mut first = 0;
+        let mut index = 0;
+        for len in 1..16 {
+            code |= bits.bits(1)?;
+            let count = self.counts[len] as usize;
+            if code < first + count {
+                return Ok(self.symbols[index + code - first] as usize);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid("bad Huffman code"))
+    }
+}
+
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59,
+    67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [usize; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4,
+    5, 5, 5, 5, 0];
+const DIST_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513,
+    769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+const DIST_EXTRA: [usize; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10,
+    11, 11, 12, 12, 13, 13];
+
+/// Order in which code length code lengths are stored
+const CLEN_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Decodes a raw DEFLATE stream, appending to `out`.  Returns the number of
+/// bytes of input which were used, or an error if `out` would grow past
+/// `limit` bytes.
+fn inflate(data: &[u8], out: &mut Vec<u8>, limit: usize)
+    -> io::Result<usize>
+{
+    let mut bits = Bits { data, pos: 0 };
+    loop {
+        let last = bits.bits(1)? == 1;
+        match bits.bits(2)? {
+            0 => {
+                let i = bits.bytes_used();
+                let len = u16_at(data, i)?;
+                if u16_at(data, i + 2)? != !len & 0xffff {
+                    return Err(invalid("bad stored block length"));
+                }
+                check_limit(out.len() + len, limit)?;
+                out.extend(data.get(i + 4..i + 4 + len)
+                    .ok_or_else(|| invalid("truncated DEFLATE stream"))?);
+                bits.pos = (i + 4 + len) * 8;
+            },
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].iter_mut().for_each(|l| *l = 8);
+                lengths[144..256].iter_mut().for_each(|l| *l = 9);
+                lengths[256..280].iter_mut().for_each(|l| *l = 7);
+                lengths[280..].iter_mut().for_each(|l| *l = 8);
+                let lit = Huffman::new(&lengths);
+                let dist = Huffman::new(&[5; 30]);
+                inflate_block(&mut bits, &lit, &dist, out, limit)?;
+            },
+            2 => {
+                let (lit, dist) = dynamic_codes(&mut bits)?;
+                inflate_block(&mut bits, &lit, &dist, out, limit)?;
+            },
+            _ => return Err(invalid("bad DEFLATE block type")),
+        }
+        if last {
+            return Ok(bits.bytes_used());
+        }
+    }
+}
+
+/// Reads the Huffman codes at the start of a dynamic block
+fn dynamic_codes(bits: &mut Bits) -> io::Result<(Huffman, Huffman)> {
+    let nlen = bits.bits(5)? + 257;
+    let ndist = bits.bits(5)? + 1;
+    let ncode = bits.bits(4)? + 4;
+    let mut clens = [0u8; 19];
+    for i in &CLEN_ORDER[..ncode] {
+        clens[*i] = bits.bits(3)? as u8;
+    }
+    let clen = Huffman::new(&clens);
+
+    let mut lengths = Vec::with_capacity(nlen + ndist);
+    while lengths.len() < nlen + ndist {
+        let (value, repeat) = match clen.decode(bits)? {
+            s @ 0..=15 => (s as u8, 1),
+            16 => {
+                let prev = *lengths.last()
+                    .ok_or_else(|| invalid("repeat with no previous length"))?;
+                (prev, 3 + bits.bits(2)?)
+            },
+            17 => (0, 3 + bits.bits(3)?),
+            _ => (0, 11 + bits.bits(7)?),
+        };
+        lengths.resize(lengths.len() + repeat, value);
+    }
+    if lengths.len() > nlen + ndist {
+        return Err(invalid("too many code lengths"));
+    }
+    Ok((Huffman::new(&lengths[..nlen]), Huffman::new(&lengths[nlen..])))
+}
+
+fn check_limit(len: usize, limit: usize) -> io::Result<()> {
+    if len > limit {
+        Err(invalid("decompressed data is too large"))
+    } else {
+        Ok(())
+    }
+}
+
+fn inflate_block(bits: &mut Bits, lit: &Huffman, dist: &Huffman,
+                 out: &mut Vec<u8>, limit: usize) -> io::Result<()>
+{
+    loop {
+        match lit.decode(bits)? {
+            s @ 0..=255 => {
+                check_limit(out.len() + 1, limit)?;
+                out.push(s as u8);
+            },
+            256 => return Ok(()),
+            s => {
+                let s = s - 257;
+                if s >= LENGTH_BASE.len() {
+                    return Err(invalid("bad length code"));
+                }
+                let len = LENGTH_BASE[s] + bits.bits(LENGTH_EXTRA[s])?;
+                let d = dist.decode(bits)?;
+                if d >= DIST_BASE.len() {
+                    return Err(invalid("bad distance code"));
+                }
+                let d = DIST_BASE[d] + bits.bits(DIST_EXTRA[d])?;
+                if d > out.len() {
+                    return Err(invalid("distance before start of output"));
+                }
+                check_limit(out.len() + len, limit)?;
+                // The copy may overlap its own output, e.g. to repeat a run
+                let start = out.len() - d;
+                for i in 0..len {
+                    out.push(out[start + i]);
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(name: &str) -> Vec<u8> {
+        std::fs::read(format!("{}/../examples/{}", env!("CARGO_MANIFEST_DIR"),
+                              name)).unwrap()
+    }
+
+    #[test]
+    fn test_gzip() {
+        let plain = example("cuboid.step");
+        let gz = example("cuboid.step.gz");
+        assert!(is_compressed(&gz));
+        assert_eq!(decompress(&gz).unwrap(), &plain[..]);
+
+        // Two members, each from a fixed-Huffman block
+        let member = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x73,
+            0x71, 0x0c, 0x71, 0xb4, 0x06, 0x00, 0xc0, 0x27, 0x25, 0x7b, 0x05,
+            0x00, 0x00, 0x00];
+        let gz = [member, member].concat();
+        assert_eq!(decompress(&gz).unwrap(), &b"DATA;DATA;"[..]);
+
+        let mut bad = example("cuboid.step.gz");
+        let n = bad.len();
+        bad[n - 8] ^= 1;
+        assert!(decompress(&bad).is_err());
+    }
+
+    #[test]
+    fn test_zip() {
+        let plain = example("cuboid.step");
+        let zip = example("cuboid.stpz");
+        assert!(is_compressed(&zip));
+        assert_eq!(decompress(&zip).unwrap(), &plain[..]);
+
+        // An entry which claims to be 4 GiB is rejected once it's inflated
+        let mut bad = zip.clone();
+        let entry = (0..bad.len())
+            .find(|i| bad[*i..].starts_with(b"PK\x01\x02"))
+            .unwrap();
+        bad[entry + 24..entry + 28].copy_from_slice(&[0xff; 4]);
+        assert!(decompress(&bad).is_err());
+    }
+
+    /// Builds a gzip stream of `n` back-references, each repeating the
+    /// previous 258 bytes, which inflates to far more than its own size
+    fn bomb(n: usize) -> (Vec<u8>, usize) {
+        let mut out = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0x03];
+        let (mut acc, mut nbits) = (0u32, 0);
+        let mut put = |value: u32, len: usize, out: &mut Vec<u8>| {
+            // Huffman codes are packed starting from their top bit
+            for i in (0..len).rev() {
+                acc |= ((value >> i) & 1) << nbits;
+                nbits += 1;
+                if nbits == 8 {
+                    out.push(acc as u8);
+                    acc = 0;
+                    nbits = 0;
+                }
+            }
+        };
+        put(0b1, 1, &mut out); // last block
+        put(0b10, 2, &mut out); // fixed Huffman codes (01, reversed)
+        put(0x30 + b'a' as u32, 8, &mut out);
+        for _ in 0..n {
+            put(0b1100_0101, 8, &mut out); // length 258
+            put(0, 5, &mut out); // distance 1
+        }
+        put(0, 7, &mut out); // end of block
+        put(0, 7, &mut out); // padding
+        let len = 1 + 258 * n;
+        out.extend(&crc32(&vec![b'a'; len]).to_le_bytes());
+        out.extend(&(len as u32).to_le_bytes());
+        (out, len)
+    }
+
+    #[test]
+    fn test_limit() {
+        // About 6 KiB of input, which inflates to 1 MiB
+        let (gz, len) = bomb(4096);
+        assert!(gz.len() < 8 * 1024);
+        assert_eq!(decompress(&gz).unwrap().len(), len);
+        assert_eq!(decompress_with_limit(&gz, len).unwrap().len(), len);
+        let err = decompress_with_limit(&gz, 64 * 1024).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // A ZIP entry is limited to its recorded size as well
+        let zip = example("cuboid.stpz");
+        let plain = example("cuboid.step");
+        assert!(decompress_with_limit(&zip, plain.len()).is_ok());
+        assert!(decompress_with_limit(&zip, plain.len() - 1).is_err());
+        let mut bad = zip.clone();
+        let entry = (0..bad.len())
+            .find(|i| bad[*i..].starts_with(b"PK\x01\x02"))
+            .unwrap();
+        bad[entry + 24..entry + 28].copy_from_slice(&100u32.to_le_bytes());
+        let err = decompress(&bad).unwrap_err();
+        assert_eq!(err.to_string(), "decompressed data is too large");
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_uncompressed() {
+        let plain = example("cuboid.step");
+        assert!(!is_compressed(&plain));
+        assert!(matches!(decompress(&plain).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_stored_and_overlapping() {
+        // A stored block, then a fixed block which repeats "ab" with an
+        // overlapping copy
+        let mut out = Vec::new();
+        let data = [0, 2, 0, 0xfd, 0xff, b'x', b'y',
+                    0x4b, 0x4c, 0x4a, 0x84, 0x43, 0x00];
+        inflate(&data, &mut out, 14).unwrap();
+        assert_eq!(out, b"xyabababababab");
+
+        out.clear();
+        let err = inflate(&data, &mut out, 13).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    ap214::attributes_of,
+    raw::{record, Record},
+    writer::Param,
+};
+
+/// How an attribute's value is written in a STEP file, as known to the
+/// generated schema code (see [`attributes_of`])
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ParamKind {
+    /// Reference to another instance (an entity, or a `SELECT` of entities)
+    Ref,
+    Real,
+    Integer,
+    Str,
+    /// Binary value, written as a quoted hex string
+    Binary,
+    /// Logical or boolean value (`.T.`, `.F.`, or `.U.`)
+    Logical,
+    Enum,
+    List,
+    /// Attribute which is re-declared as derived, which must be written `*`
+    Derived,
+    /// Anything which could be a member of a `SELECT`, including typed values
+    Any,
+}
+
+impl ParamKind {
+    /// Checks whether a parameter could be a value of this kind
+    pub fn accepts(&self, p: &Param) -> bool {
+        match (self, p) {
+            (ParamKind::Derived, p) => *p == Param::Derived,
+            (_, Param::Derived) => false,
+            (ParamKind::Any, _) => true,
+            (ParamKind::Ref, Param::Ref(_)) |
+            (ParamKind::Real, Param::Real(_)) |
+            (ParamKind::Real, Param::Integer(_)) |
+            (ParamKind::Integer, Param::Integer(_)) |
+            (ParamKind::Str, Param::String(_)) |
+            (ParamKind::Binary, Param::String(_)) |
+            (ParamKind::Logical, Param::Logical(_)) |
+            (ParamKind::Enum, Param::Enum(_)) |
+            (ParamKind::List, Param::List(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ParamKind::Ref => "a reference",
+            ParamKind::Real => "a real",
+            ParamKind::Integer => "an integer",
+            ParamKind::Str => "a string",
+            ParamKind::Binary => "a binary",
+            ParamKind::Logical => "a logical",
+            ParamKind::Enum => "an enumeration",
+            ParamKind::List => "a list",
+            ParamKind::Derived => "derived (*)",
+            ParamKind::Any => "a value",
+        }
+    }
+}
+
+/// What's wrong with an instance's parameter list
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// The instance has the wrong number of parameters, e.g. because the
+    /// list was truncated
+    Count { expected: usize, found: usize },
+    /// A parameter can't be a value of the attribute's type, e.g. because
+    /// the list was shifted.  `index` is 0-based.
+    Kind { index: usize, attribute: &'static str, expected: ParamKind,
+           found: Param },
+}
+
+/// An instance whose parameters don't match its entity's attributes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub id: usize,
+    pub entity: String,
+    pub problem: Problem,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{}: ", self.id)?;
+        match &self.problem {
+            Problem::Count { expected, found } =>
+                write!(f, "{} has {} parameters, expected {}",
+                       self.entity, found, expected),
+            Problem::Kind { index, attribute, expected, found } => {
+                let mut s = String::new();
+                found.write(&mut s);
+                write!(f, "parameter {} ({}) of {} should be {}, found {}",
+                       index + 1, attribute, self.entity, expected.name(), s)
+            },
+        }
+    }
+}
+
+/// Checks a record's parameters against the schema's attributes, returning
+/// the first problem.  Only simple instances are checked: the parts of a
+/// complex instance hold a subset of each entity's attributes, so they
+/// can't be compared directly.  Entities which aren't in the schema are
+/// also skipped, since they'll fail to parse anyway.
+///
+/// Unset values (`$`) are accepted for any attribute, since exporters often
+/// write them for required attributes which they don't know.
+pub fn check_record(r: &Record) -> Option<Mismatch> {
+    let (name, params) = match r.parts.as_slice() {
+        [p] => p,
+        _ => return None,
+    };
+    let attrs = attributes_of(name)?;
+    let err = |problem| Some(Mismatch {
+        id: r.id, entity: name.clone(), problem,
+    });
+    if attrs.len() != params.len() {
+        return err(Problem::Count {
+            expected: attrs.len(), found: params.len(),
+        });
+    }
+    for (index, (p, (attribute, kind, _optional))) in
+        params.iter().zip(attrs).enumerate()
+    {
+        if *p != Param::Unset && !kind.accepts(p) {
+            return err(Problem::Kind {
+                index, attribute, expected: *kind, found: p.clone(),
+            });
+        }
+    }
+    None
+}
+
+/// Checks a single block from the DATA section.  Blocks which can't be read
+/// at all aren't reported here.
+pub(crate) fn check_block(b: &[u8]) -> Option<Mismatch> {
+    let s = std::str::from_utf8(b).ok()?;
+    check_record(&record(s).ok()?.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::step_file::{ParseOptions, StepFile};
+
+    #[test]
+    fn test_check_params() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=AXIS2_PLACEMENT_3D('',#1,#2,$);
+#4=CARTESIAN_POINT((1.,0.,0.));
+#5=AXIS2_PLACEMENT_3D('',#2,$,(1.,0.,0.));
+#6=(NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.)LENGTH_UNIT());
+#7=VERTEX_POINT('',#1);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let step = StepFile::parse(&flat);
+        assert!(step.mismatches().is_empty());
+
+        let opts = ParseOptions { check_params: true, ..Default::default() };
+        let step = StepFile::parse_with_options(&flat, &opts).unwrap();
+        let m = step.mismatches();
+        assert_eq!(m.len(), 2);
+        assert_eq!(m[0].id, 4);
+        assert_eq!(m[0].problem, Problem::Count { expected: 2, found: 1 });
+        assert_eq!(m[0].to_string(),
+                   "#4: CARTESIAN_POINT has 1 parameters, expected 2");
+        assert_eq!(m[1].id, 5);
+        assert_eq!(m[1].to_string(),
+                   "#5: parameter 4 (ref_direction) of AXIS2_PLACEMENT_3D \
+                    should be a reference, found (1.,0.,0.)");
+    }
+}
@@ -0,0 +1,618 @@
+//! Checks entity instances against an EXPRESS schema which is loaded at
+//! runtime (see [`dynamic`](crate::dynamic)), to find which parts of a
+//! broken export don't match their schema.
+//!
+//! Unlike [`check_record`](crate::check::check_record), which only knows the
+//! broad kind of each attribute in the compiled schema, this follows defined
+//! types, enumerations, and selects, checks aggregate sizes and unset
+//! attributes, and checks that references point at instances of the right
+//! type.  `WHERE` clauses and rules are checked separately, by
+//! [`check_rules`](crate::rules::check_rules).
+use std::collections::{HashMap, HashSet};
+
+use express::{dictionary::parameter_type, parse::*, resolve::Decl};
+
+use crate::{
+    dynamic::{DynSchema, Error},
+    raw::Record,
+    writer::Param,
+};
+
+/// Limit on nested types and values, which stops runaway recursion
+const MAX_DEPTH: usize = 64;
+
+/// What's wrong with one attribute (or the parameter list) of an instance.
+/// Attributes are named with the entity which declares them, e.g.
+/// `representation_item.name`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// The instance's type (or one part of a complex instance) isn't an
+    /// entity in the schema
+    UnknownEntity(String),
+    /// The instance has the wrong number of parameters for its type (or,
+    /// for a complex instance, for one of its parts)
+    Count { entity: String, expected: usize, found: usize },
+    /// A required attribute is unset (`$`)
+    Missing { attribute: String },
+    /// An attribute is written as `*`, but no subtype redeclares it as derived
+    Derived { attribute: String },
+    /// An attribute is redeclared as derived, but has a value instead of `*`
+    NotDerived { attribute: String },
+    /// A value can't belong to the attribute's type, which is given in
+    /// EXPRESS syntax
+    Type { attribute: String, expected: String, found: Param },
+    /// A value isn't one of an enumeration's items
+    Enum { attribute: String, expected: String, value: String },
+    /// An aggregate (or an aggregate nested within the attribute) has the
+    /// wrong number of elements
+    Size { attribute: String, expected: String, found: usize },
+    /// A reference to an instance which isn't in the file
+    Dangling { attribute: String, id: usize },
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Problem::UnknownEntity(e) => write!(f, "unknown entity {}", e),
+            Problem::Count { entity, expected, found } =>
+                write!(f, "{} has {} parameters, expected {}",
+                       entity, found, expected),
+            Problem::Missing { attribute } =>
+                write!(f, "{} is required, but unset", attribute),
+            Problem::Derived { attribute } =>
+                write!(f, "{} is written as *, but isn't derived", attribute),
+            Problem::NotDerived { attribute } =>
+                write!(f, "{} is derived, so it should be written as *",
+                       attribute),
+            Problem::Type { attribute, expected, found } => {
+                let mut s = String::new();
+                found.write(&mut s);
+                write!(f, "{} should be {}, found {}", attribute, expected, s)
+            },
+            Problem::Enum { attribute, expected, value } =>
+                write!(f, "{} should be one of the items of {}, found .{}.",
+                       attribute, expected, value.to_uppercase()),
+            Problem::Size { attribute, expected, found } =>
+                write!(f, "{} should be {}, found {} elements",
+                       attribute, expected, found),
+            Problem::Dangling { attribute, id } =>
+                write!(f, "{} refers to #{}, which doesn't exist",
+                       attribute, id),
+        }
+    }
+}
+
+/// Every problem with a single instance
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub id: usize,
+    /// The instance's type as written in the file, with the parts of a
+    /// complex instance separated by spaces
+    pub entity: String,
+    pub problems: Vec<Problem>,
+}
+
+impl std::fmt::Display for Diagnostic {
+    /// Writes one line per problem
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, p) in self.problems.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "#{} {}: {}", self.id, self.entity, p)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of [`validate`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Number of instances which were checked
+    pub checked: usize,
+    /// Instances with at least one problem, in ascending ID order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Checks each instance (e.g. from [`records`](crate::raw::records))
+/// against a schema: its attribute count, whether required attributes are
+/// set, and whether each value belongs to its attribute's type.
+///
+/// Values which can't be checked (e.g. of types which aren't in the syntax
+/// tree, or of extensible enumerations and selects, which may be extended
+/// elsewhere) are accepted.
+pub fn validate(schema: &DynSchema, records: &[Record]) -> Report {
+    let v = Validator {
+        schema,
+        types: records.iter()
+            .map(|r| (r.id, r.parts.iter()
+                .flat_map(|(name, _)| schema.types(name).unwrap_or(&[]))
+                .copied()
+                .collect()))
+            .collect(),
+    };
+    let mut out = Report { checked: records.len(), diagnostics: Vec::new() };
+    for r in records {
+        let problems = v.record(r);
+        if !problems.is_empty() {
+            out.diagnostics.push(Diagnostic {
+                id: r.id,
+                entity: r.parts.iter()
+                    .map(|p| p.0.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                problems,
+            });
+        }
+    }
+    out.diagnostics.sort_by_key(|d| d.id);
+    out
+}
+
+/// A type to check a value against, from any of the places in the syntax
+/// tree where types are written
+#[derive(Copy, Clone)]
+enum Ty<'a> {
+    /// Generic types, which only appear in functions
+    Any,
+    Simple(&'a SimpleTypes<'a>),
+    Aggregate(&'a AggregationTypes<'a>),
+    /// Aggregates as written in attribute declarations
+    General(&'a GeneralAggregationTypes<'a>),
+    /// An entity or defined type
+    Named(&'a str),
+}
+
+impl<'a> Ty<'a> {
+    fn parameter(p: &'a ParameterType<'a>) -> Self {
+        match p {
+            ParameterType::Generalized(GeneralizedTypes::GeneralAggregation(a))
+                => Ty::General(a),
+            ParameterType::Generalized(_) => Ty::Any,
+            ParameterType::Named(n) => Ty::Named(named_types(n)),
+            ParameterType::Simple(s) => Ty::Simple(s),
+        }
+    }
+
+    fn concrete(c: &'a ConcreteTypes<'a>) -> Self {
+        match c {
+            ConcreteTypes::Aggregation(a) => Ty::Aggregate(a),
+            ConcreteTypes::Simple(s) => Ty::Simple(s),
+            ConcreteTypes::TypeRef(t) => Ty::Named(t.0),
+        }
+    }
+
+    fn instantiable(i: &'a InstantiableType<'a>) -> Self {
+        match i {
+            InstantiableType::Concrete(c) => Ty::concrete(c),
+            InstantiableType::EntityRef(e) => Ty::Named(e.0),
+        }
+    }
+}
+
+/// Why a value doesn't belong to a type
+enum Fault<'a> {
+    Type,
+    /// Enumeration type and (lowercase) value
+    Enum(&'a str, String),
+    Size(usize),
+    Dangling(usize),
+}
+
+struct Validator<'s, 'a> {
+    schema: &'s DynSchema<'a>,
+    /// Every type of each instance, including supertypes.  Instances of
+    /// unknown entities have no types.
+    types: HashMap<usize, Vec<&'a str>>,
+}
+
+impl<'s, 'a> Validator<'s, 'a> {
+    fn record(&self, r: &Record) -> Vec<Problem> {
+        let e = match self.schema.entity(r.clone()) {
+            Ok(e) => e,
+            Err(Error::UnknownEntity { name, .. }) =>
+                return vec![Problem::UnknownEntity(name)],
+            Err(Error::WrongCount { entity, expected, found, .. }) =>
+                return vec![Problem::Count { entity, expected, found }],
+        };
+
+        // Attributes which a subtype redeclares as derived, as (declaring
+        // entity, attribute)
+        let derived: HashSet<(&str, &str)> = e.types.iter()
+            .filter_map(|t| self.schema.entity_decl(t))
+            .filter_map(|d| d.1.derive.as_ref())
+            .flat_map(|d| d.0.iter())
+            .filter_map(|d| match &d.0 {
+                AttributeDecl::Redeclared(r) =>
+                    Some((((r.0).0).0.0, ((r.0).1).0.0)),
+                AttributeDecl::Id(_) => None,
+            })
+            .collect();
+
+        let mut out = Vec::new();
+        for a in &e.attributes {
+            let attribute = || format!("{}.{}", a.entity, a.name);
+            let decl = match self.explicit_attr(a.entity, a.name) {
+                Some(d) => d,
+                None => continue,
+            };
+            match (&a.value, derived.contains(&(a.entity, a.name))) {
+                (Param::Derived, true) => (),
+                (Param::Derived, false) =>
+                    out.push(Problem::Derived { attribute: attribute() }),
+                (_, true) =>
+                    out.push(Problem::NotDerived { attribute: attribute() }),
+                (Param::Unset, false) => if !decl.optional {
+                    out.push(Problem::Missing { attribute: attribute() });
+                },
+                (p, false) => {
+                    let t = Ty::parameter(&decl.parameter_type);
+                    let expected = || parameter_type(&decl.parameter_type);
+                    match self.check(p, t, 0) {
+                        Ok(()) => (),
+                        Err(Fault::Type) => out.push(Problem::Type {
+                            attribute: attribute(), expected: expected(),
+                            found: p.clone(),
+                        }),
+                        Err(Fault::Enum(t, value)) => out.push(Problem::Enum {
+                            attribute: attribute(), expected: t.to_owned(),
+                            value,
+                        }),
+                        Err(Fault::Size(found)) => out.push(Problem::Size {
+                            attribute: attribute(), expected: expected(), found,
+                        }),
+                        Err(Fault::Dangling(id)) => out.push(Problem::Dangling {
+                            attribute: attribute(), id,
+                        }),
+                    }
+                },
+            }
+        }
+        out
+    }
+
+    /// Finds the declaration of an explicit attribute
+    fn explicit_attr(&self, entity: &str, attr: &str)
+        -> Option<&'a ExplicitAttr<'a>>
+    {
+        self.schema.entity_decl(entity)?.1.explicit_attr.iter()
+            .find(|a| a.attributes.iter().any(|d| match d {
+                AttributeDecl::Id(i) => i.0 == attr,
+                AttributeDecl::Redeclared(_) => false,
+            }))
+    }
+
+    fn check(&self, p: &Param, t: Ty<'a>, depth: usize) -> Result<(), Fault<'a>> {
+        if depth >= MAX_DEPTH {
+            return Ok(());
+        }
+        match t {
+            Ty::Any => Ok(()),
+            Ty::Simple(s) if simple(s, p) => Ok(()),
+            Ty::Simple(_) => Err(Fault::Type),
+            Ty::Aggregate(a) => {
+                let (array, bounds, elem) = match a {
+                    AggregationTypes::Array(a) =>
+                        (true, Some(&a.bounds), &*a.instantiable_type),
+                    AggregationTypes::Bag(b) => (false, b.0.as_ref(), &*b.1),
+                    AggregationTypes::List(l) =>
+                        (false, l.bounds.as_ref(), &*l.instantiable_type),
+                    AggregationTypes::Set(s) =>
+                        (false, s.bounds.as_ref(), &*s.instantiable_type),
+                };
+                self.aggregate(p, array, bounds, Ty::instantiable(elem), depth)
+            },
+            Ty::General(a) => {
+                let (array, bounds, elem) = match a {
+                    GeneralAggregationTypes::Array(a) =>
+                        (true, a.bounds.as_ref(), &*a.parameter_type),
+                    GeneralAggregationTypes::Bag(b) => (false, b.0.as_ref(), &*b.1),
+                    GeneralAggregationTypes::List(l) =>
+                        (false, l.bounds.as_ref(), &*l.parameter_type),
+                    GeneralAggregationTypes::Set(s) =>
+                        (false, s.bounds.as_ref(), &*s.parameter_type),
+                };
+                self.aggregate(p, array, bounds, Ty::parameter(elem), depth)
+            },
+            Ty::Named(n) => match self.schema.decl(n) {
+                Some(Decl::Entity(_)) =>
+                    self.reference(p, |types| types.contains(&n)),
+                Some(Decl::Type(t)) => self.defined(p, t, depth),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    fn aggregate(&self, p: &Param, array: bool,
+                 bounds: Option<&'a BoundSpec<'a>>, elem: Ty<'a>, depth: usize)
+        -> Result<(), Fault<'a>>
+    {
+        let v = match p {
+            Param::List(v) => v,
+            _ => return Err(Fault::Type),
+        };
+        if let Some(b) = bounds {
+            let lo = literal(&((b.0).0).0);
+            let hi = literal(&((b.1).0).0);
+            let n = v.len() as i64;
+            let ok = if array {
+                // An array's bounds are its first and last index
+                match (lo, hi) {
+                    (Some(lo), Some(hi)) => n == hi - lo + 1,
+                    _ => true,
+                }
+            } else {
+                lo.is_none_or(|lo| n >= lo) && hi.is_none_or(|hi| n <= hi)
+            };
+            if !ok {
+                return Err(Fault::Size(v.len()));
+            }
+        }
+        for e in v.iter().filter(|e| **e != Param::Unset) {
+            self.check(e, elem, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that a value refers to an instance with suitable types.
+    /// Instances of unknown entities are reported by themselves, so any
+    /// reference to them is accepted.
+    fn reference<F: Fn(&[&'a str]) -> bool>(&self, p: &Param, ok: F)
+        -> Result<(), Fault<'a>>
+    {
+        match p {
+            Param::Ref(id) => match self.types.get(id) {
+                None => Err(Fault::Dangling(*id)),
+                Some(ts) if ts.is_empty() || ok(ts) => Ok(()),
+                Some(_) => Err(Fault::Type),
+            },
+            _ => Err(Fault::Type),
+        }
+    }
+
+    fn defined(&self, p: &Param, t: &'a TypeDecl<'a>, depth: usize)
+        -> Result<(), Fault<'a>>
+    {
+        match &t.underlying_type {
+            UnderlyingType::Concrete(c) =>
+                self.check(p, Ty::concrete(c), depth + 1),
+            UnderlyingType::Constructed(ConstructedTypes::Enumeration(e)) =>
+                match p {
+                    Param::Enum(v) => {
+                        let v = v.to_lowercase();
+                        if e.extensible ||
+                           self.enum_items(t, depth).contains(&v.as_str())
+                        {
+                            Ok(())
+                        } else {
+                            Err(Fault::Enum(t.type_id.0, v))
+                        }
+                    },
+                    _ => Err(Fault::Type),
+                },
+            UnderlyingType::Constructed(ConstructedTypes::Select(_)) => {
+                let mut options = Options::default();
+                self.select_options(t, &mut options, depth);
+                match p {
+                    Param::Ref(_) => self.reference(p, |ts|
+                        options.open ||
+                        ts.iter().any(|t| options.entities.contains(t))),
+                    Param::Typed(name, inner) => {
+                        let name = name.to_lowercase();
+                        match self.schema.decl(&name) {
+                            Some(Decl::Type(t))
+                                if options.types.contains(&t.type_id.0) =>
+                                self.defined(inner, t, depth + 1),
+                            _ if options.open => Ok(()),
+                            _ => Err(Fault::Type),
+                        }
+                    },
+                    _ => Err(Fault::Type),
+                }
+            },
+        }
+    }
+
+    /// Returns the items of an enumeration, including those of the
+    /// enumeration which it's based on
+    fn enum_items(&self, t: &'a TypeDecl<'a>, depth: usize) -> Vec<&'a str> {
+        let e = match &t.underlying_type {
+            UnderlyingType::Constructed(ConstructedTypes::Enumeration(e)) => e,
+            _ => return Vec::new(),
+        };
+        let (items, base) = match &e.items_or_extension {
+            None => (None, None),
+            Some(EnumerationItemsOrExtension::Items(i)) => (Some(i), None),
+            Some(EnumerationItemsOrExtension::Extension(x)) =>
+                (x.enumeration_items.as_ref(), Some(x.type_ref.0)),
+        };
+        let mut out: Vec<&'a str> = items.iter()
+            .flat_map(|i| i.0.iter())
+            .map(|i| i.0)
+            .collect();
+        if let (Some(Decl::Type(b)), true) =
+            (base.and_then(|b| self.schema.decl(b)), depth < MAX_DEPTH)
+        {
+            out.extend(self.enum_items(b, depth + 1));
+        }
+        out
+    }
+
+    /// Collects the options of a select, following nested selects and the
+    /// select which it's based on
+    fn select_options(&self, t: &'a TypeDecl<'a>, out: &mut Options<'a>,
+                      depth: usize)
+    {
+        let s = match &t.underlying_type {
+            UnderlyingType::Constructed(ConstructedTypes::Select(s)) => s,
+            _ => return,
+        };
+        if depth >= MAX_DEPTH || out.visited.contains(&t.type_id.0) {
+            return;
+        }
+        out.visited.push(t.type_id.0);
+        out.open |= s.extensible || s.generic_entity;
+        let (list, base) = match &s.list_or_extension {
+            SelectListOrExtension::List(l) => (Some(l), None),
+            SelectListOrExtension::Extension(x) =>
+                (x.select_list.as_ref(), Some(x.type_ref.0)),
+        };
+        let names = list.iter()
+            .flat_map(|l| l.0.iter())
+            .map(named_types)
+            .chain(base);
+        for n in names {
+            match self.schema.decl(n) {
+                Some(Decl::Entity(_)) => out.entities.push(n),
+                Some(Decl::Type(t)) => {
+                    out.types.push(t.type_id.0);
+                    self.select_options(t, out, depth + 1);
+                },
+                // Options which aren't in the syntax tree can't be checked
+                _ => out.open = true,
+            }
+        }
+    }
+}
+
+/// Everything which a select can hold
+#[derive(Default)]
+struct Options<'a> {
+    entities: Vec<&'a str>,
+    /// Defined types, which are written as typed values
+    types: Vec<&'a str>,
+    /// Set if the select accepts values which aren't listed
+    open: bool,
+    visited: Vec<&'a str>,
+}
+
+fn named_types<'a>(n: &NamedTypes<'a>) -> &'a str {
+    match n {
+        NamedTypes::Entity(e) => e.0,
+        NamedTypes::Type(t) => t.0,
+        NamedTypes::_Ambiguous(s) => s.0,
+    }
+}
+
+/// Checks whether a value can belong to a simple type
+fn simple(s: &SimpleTypes, p: &Param) -> bool {
+    matches!((s, p),
+        (SimpleTypes::Integer, Param::Integer(_)) |
+        (SimpleTypes::Real(_) | SimpleTypes::Number,
+         Param::Real(_) | Param::Integer(_)) |
+        (SimpleTypes::String(_) | SimpleTypes::Binary(_), Param::String(_)) |
+        (SimpleTypes::Boolean, Param::Logical(Some(_))) |
+        (SimpleTypes::Logical, Param::Logical(_)))
+}
+
+/// Reads an aggregate bound which is written as an integer literal, or
+/// returns `None` for `?` and anything more complicated
+fn literal(e: &SimpleExpression) -> Option<i64> {
+    let Term(f, ops) = &*e.0;
+    if !e.1.is_empty() || !ops.is_empty() || !f.1.is_empty() {
+        return None;
+    }
+    match &f.0 {
+        SimpleFactor::Unary(sign, ExpressionOrPrimary::Primary(
+            Primary::Literal(Literal::Real(r)))) if r.fract() == 0.0 =>
+            Some(if matches!(sign, Some(UnaryOp::Sub)) { -*r } else { *r } as i64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use express::{parse_schema, strip_comments_and_lower};
+    use crate::{raw::records, step_file::StepFile};
+
+    const SCHEMA: &[u8] = br#"SCHEMA shapes;
+TYPE tag = STRING;
+END_TYPE;
+TYPE distance = REAL;
+END_TYPE;
+TYPE side = ENUMERATION OF (left, right);
+END_TYPE;
+TYPE size_select = SELECT (distance, tag);
+END_TYPE;
+TYPE shape_select = SELECT (circle, size_select);
+END_TYPE;
+ENTITY item
+  ABSTRACT SUPERTYPE;
+  name : tag;
+  note : OPTIONAL STRING;
+END_ENTITY;
+ENTITY point
+  SUBTYPE OF (item);
+  coords : LIST [2:3] OF distance;
+END_ENTITY;
+ENTITY circle
+  SUBTYPE OF (item);
+  center : point;
+  radius : distance;
+  facing : OPTIONAL side;
+END_ENTITY;
+ENTITY unit_circle
+  SUBTYPE OF (circle);
+DERIVE
+  SELF\circle.radius : distance := 1.0;
+END_ENTITY;
+ENTITY group
+  SUBTYPE OF (item);
+  members : SET [1:?] OF shape_select;
+  flags : ARRAY [1:2] OF BOOLEAN;
+END_ENTITY;
+END_SCHEMA;
+"#;
+
+    fn validate_str(data: &[u8]) -> Vec<String> {
+        let text = strip_comments_and_lower(SCHEMA);
+        let syntax = parse_schema(&text).unwrap();
+        let schema = DynSchema::new(&syntax, "shapes").unwrap();
+        let report = validate(&schema, &records(&StepFile::strip_flatten(data)));
+        report.diagnostics.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let d = validate_str(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=POINT('p',$,(0.,1));
+#2=CIRCLE('c','a note',#1,2.5,.LEFT.);
+#3=UNIT_CIRCLE('u',$,#1,*,$);
+#4=GROUP('g',$,(#2,#3,DISTANCE(1.),TAG('x')),(.T.,.F.));
+ENDSEC;END-ISO-10303-21;");
+        assert!(d.is_empty(), "{:?}", d);
+    }
+
+    #[test]
+    fn test_problems() {
+        let d = validate_str(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=POINT($,$,(0.,1.,2.,3.));
+#2=CIRCLE('c',$,#2,'big',.UP.);
+#3=UNIT_CIRCLE('u',$,#9,2.0,$);
+#4=CIRCLE('c',$,#1,*,$);
+#5=GROUP('g',$,(#1,DISTANCE('x'),2.),(.T.,.U.,.F.));
+#6=LINE('l');
+#7=POINT('p');
+ENDSEC;END-ISO-10303-21;");
+        assert_eq!(d, [
+            "#1 POINT: item.name is required, but unset\n\
+             #1 POINT: point.coords should be LIST [2:3] OF distance, \
+             found 4 elements",
+            "#2 CIRCLE: circle.center should be point, found #2\n\
+             #2 CIRCLE: circle.radius should be distance, found 'big'\n\
+             #2 CIRCLE: circle.facing should be one of the items of side, \
+             found .UP.",
+            "#3 UNIT_CIRCLE: circle.center refers to #9, which doesn't exist\n\
+             #3 UNIT_CIRCLE: circle.radius is derived, so it should be \
+             written as *",
+            "#4 CIRCLE: circle.radius is written as *, but isn't derived",
+            "#5 GROUP: group.members should be SET [1:?] OF shape_select, \
+             found (#1,DISTANCE('x'),2.)\n\
+             #5 GROUP: group.flags should be ARRAY [1:2] OF BOOLEAN, \
+             found 3 elements",
+            "#6 LINE: unknown entity LINE",
+            "#7 POINT: POINT has 1 parameters, expected 3",
+        ]);
+    }
+}
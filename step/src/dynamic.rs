@@ -0,0 +1,412 @@
+//! Parses STEP files against an EXPRESS schema which is loaded at runtime,
+//! rather than the one compiled into [`ap214`](crate::ap214).
+//!
+//! Each instance becomes a [`DynEntity`], which has its types and a list of
+//! named attributes with raw [`Param`] values.  This knows nothing about
+//! what the entities mean, but works with any application protocol (or
+//! any other schema) that can be parsed by the `express` crate.
+//!
+//! ```
+//! use express::{parse_schema, strip_comments_and_lower};
+//! use step::{dynamic::DynSchema, step_file::StepFile, writer::Param};
+//!
+//! let text = strip_comments_and_lower(b"SCHEMA shapes;
+//! ENTITY item; name : STRING; END_ENTITY;
+//! ENTITY circle SUBTYPE OF (item); radius : REAL; END_ENTITY;
+//! END_SCHEMA;");
+//! let syntax = parse_schema(&text).unwrap();
+//! let schema = DynSchema::new(&syntax, "SHAPES").unwrap();
+//!
+//! let flat = StepFile::strip_flatten(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+//! #1=CIRCLE('hole',2.5);
+//! ENDSEC;END-ISO-10303-21;");
+//! let entities = schema.parse(&flat);
+//! let e = entities[0].as_ref().unwrap();
+//! assert!(e.is("item"));
+//! assert_eq!(e.get("name"), Some(&Param::String("hole".to_owned())));
+//! assert_eq!(e.get("circle.radius"), Some(&Param::Real(2.5)));
+//! ```
+use std::collections::HashMap;
+
+use express::{
+    parse::*,
+    resolve::{resolve, Decl, SymbolTable},
+};
+
+use crate::{
+    merge::collect_refs,
+    raw::{records, Record},
+    writer::Param,
+};
+
+/// Limit on the depth of the supertype graph, which stops runaway loops in
+/// malformed schemas
+const MAX_DEPTH: usize = 64;
+
+/// Reasons why an instance doesn't match the schema
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The instance's type isn't an entity in the schema
+    UnknownEntity { id: usize, name: String },
+    /// The instance has the wrong number of parameters for its type (or,
+    /// for a complex instance, for one of its parts)
+    WrongCount { id: usize, entity: String, expected: usize, found: usize },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::UnknownEntity { id, name } =>
+                write!(f, "#{}: unknown entity {}", id, name),
+            Error::WrongCount { id, entity, expected, found } =>
+                write!(f, "#{}: expected {} parameters for {}, found {}",
+                       id, expected, entity, found),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// One explicit attribute of a [`DynEntity`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynAttribute<'a> {
+    /// The entity which declares the attribute, in lowercase
+    pub entity: &'a str,
+    /// The attribute's name, in lowercase
+    pub name: &'a str,
+    /// The value as written in the file.  An attribute which a subtype
+    /// redeclares as derived is [`Param::Derived`].
+    pub value: Param,
+}
+
+/// An entity instance, as parsed by a [`DynSchema`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynEntity<'a> {
+    pub id: usize,
+    /// The types written in the file, in lowercase.  A simple instance has
+    /// one; a complex instance (`#1=(A()B());`) has one per part.
+    pub parts: Vec<&'a str>,
+    /// Every type of the instance, including supertypes, in lowercase
+    pub types: Vec<&'a str>,
+    /// Explicit attributes, in the order of a simple instance's parameters
+    /// (or of the parts of a complex instance)
+    pub attributes: Vec<DynAttribute<'a>>,
+}
+
+impl<'a> DynEntity<'a> {
+    /// Checks whether the instance has a particular type (or a subtype of
+    /// it), ignoring case
+    pub fn is(&self, entity: &str) -> bool {
+        self.types.iter().any(|t| t.eq_ignore_ascii_case(entity))
+    }
+
+    /// Looks up an attribute by name, ignoring case.  The name may be
+    /// qualified with the entity which declares it (e.g. `item.name`), which
+    /// is needed if two of the instance's types use the same name.
+    pub fn get(&self, name: &str) -> Option<&Param> {
+        let (entity, name) = match name.rsplit_once('.') {
+            Some((e, n)) => (Some(e), n),
+            None => (None, name),
+        };
+        self.attributes.iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name) &&
+                      entity.is_none_or(|e| a.entity.eq_ignore_ascii_case(e)))
+            .map(|a| &a.value)
+    }
+
+    /// Returns every instance which this one refers to, in order
+    pub fn refs(&self) -> Vec<usize> {
+        let mut out = Vec::new();
+        for a in &self.attributes {
+            collect_refs(std::slice::from_ref(&a.value), &mut out);
+        }
+        out
+    }
+}
+
+/// The entities of an EXPRESS schema, with what's needed to name the
+/// parameters of their instances
+pub struct DynSchema<'a> {
+    name: &'a str,
+    syntax: &'a Syntax<'a>,
+    table: SymbolTable<'a>,
+    /// Every entity in the syntax tree, whether or not it's visible in this
+    /// schema
+    decls: HashMap<&'a str, &'a EntityDecl<'a>>,
+    /// Each entity and its supertypes, with supertypes first (i.e. in the
+    /// order in which their attributes appear in a STEP file)
+    lineage: HashMap<&'a str, Vec<&'a str>>,
+    /// Explicit attributes which each entity declares itself
+    own: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> DynSchema<'a> {
+    /// Loads a schema from a syntax tree, which should be lowered with
+    /// [`strip_comments_and_lower`](express::parse::strip_comments_and_lower)
+    /// (matching how names are stored in STEP files).  Entities which the
+    /// schema imports from others in the same tree are included.  Returns
+    /// `None` if the schema isn't in the syntax tree.
+    pub fn new(syntax: &'a Syntax<'a>, schema: &str) -> Option<Self> {
+        let s = syntax.0.iter()
+            .find(|s| s.id.0.eq_ignore_ascii_case(schema))?;
+        let (table, _) = resolve(syntax);
+
+        // Every entity in the tree, preferring this schema's declarations
+        // (supertypes of imported entities are needed, even if they aren't
+        // visible here themselves)
+        let mut all: HashMap<&'a str, &'a EntityDecl<'a>> = HashMap::new();
+        for t in syntax.0.iter().filter(|t| t.id.0 != s.id.0).chain([s]) {
+            for d in &t.body.declarations {
+                if let DeclarationOrRuleDecl::Declaration(
+                    Declaration::Entity(e)) = d
+                {
+                    all.insert((e.0).0.0, e);
+                }
+            }
+        }
+
+        let lineage = all.keys()
+            .filter(|n| matches!(table.lookup(s.id.0, n), Some(Decl::Entity(_))))
+            .map(|n| (*n, lineage(&all, n)))
+            .collect();
+        Some(Self {
+            name: s.id.0,
+            syntax,
+            table,
+            lineage,
+            own: all.iter().map(|(n, e)| (*n, own_slots(e))).collect(),
+            decls: all,
+        })
+    }
+
+    /// Returns the schema's name, in lowercase
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Looks up a declaration by (lowercase) name, falling back to other
+    /// schemas in the syntax tree for names which are only used implicitly
+    /// (e.g. the attribute types of an imported entity)
+    pub(crate) fn decl(&self, name: &str) -> Option<Decl<'a>> {
+        self.table.lookup(self.name, name).or_else(|| self.syntax.0.iter()
+            .find_map(|s| self.table.lookup(s.id.0, name)))
+    }
+
+    /// Looks up an entity's declaration by (lowercase) name
+    pub(crate) fn entity_decl(&self, name: &str) -> Option<&'a EntityDecl<'a>> {
+        self.decls.get(name).copied()
+    }
+
+    /// Returns an entity and all of its supertypes, with supertypes first,
+    /// or `None` if the entity isn't in the schema
+    pub fn types(&self, entity: &str) -> Option<&[&'a str]> {
+        self.lineage.get(entity.to_lowercase().as_str()).map(|v| &v[..])
+    }
+
+    /// Returns the explicit attributes of an entity, as (declaring entity,
+    /// attribute), in the order of a simple instance's parameters
+    pub fn attributes(&self, entity: &str) -> Option<Vec<(&'a str, &'a str)>> {
+        self.types(entity).map(|ts| ts.iter()
+            .flat_map(|t| self.own[t].iter().map(move |a| (*t, *a)))
+            .collect())
+    }
+
+    /// Parses every instance in a file which has been preprocessed by
+    /// [`StepFile::strip_flatten`](crate::step_file::StepFile::strip_flatten),
+    /// in ascending ID order.  Instances which don't match the schema are
+    /// returned as errors, without stopping the rest of the file.
+    pub fn parse(&self, data: &[u8]) -> Vec<Result<DynEntity<'a>, Error>> {
+        let mut rs = records(data);
+        rs.sort_by_key(|r| r.id);
+        rs.into_iter().map(|r| self.entity(r)).collect()
+    }
+
+    /// Names the parameters of a single instance
+    pub fn entity(&self, r: Record) -> Result<DynEntity<'a>, Error> {
+        let mut out = DynEntity {
+            id: r.id,
+            parts: Vec::new(),
+            types: Vec::new(),
+            attributes: Vec::new(),
+        };
+        let simple = r.parts.len() == 1;
+        for (name, params) in r.parts {
+            let (entity, types) = self.lineage
+                .get_key_value(name.to_lowercase().as_str())
+                .ok_or(Error::UnknownEntity { id: r.id, name: name.clone() })?;
+            out.parts.push(entity);
+            for t in types {
+                if !out.types.contains(t) {
+                    out.types.push(t);
+                }
+            }
+
+            // A simple instance has every inherited attribute, while each
+            // part of a complex instance only has its own
+            let slots: Vec<(&'a str, &'a str)> = if simple {
+                self.attributes(entity).unwrap_or_default()
+            } else {
+                self.own[entity].iter().map(|a| (*entity, *a)).collect()
+            };
+            if slots.len() != params.len() {
+                return Err(Error::WrongCount {
+                    id: r.id,
+                    entity: name,
+                    expected: slots.len(),
+                    found: params.len(),
+                });
+            }
+            out.attributes.extend(slots.into_iter().zip(params)
+                .map(|((entity, name), value)|
+                     DynAttribute { entity, name, value }));
+        }
+        Ok(out)
+    }
+}
+
+/// Returns the explicit attributes which an entity declares itself
+/// (redeclared attributes keep their supertype's position)
+fn own_slots<'a>(e: &'a EntityDecl<'a>) -> Vec<&'a str> {
+    e.1.explicit_attr.iter()
+        .flat_map(|a| a.attributes.iter())
+        .filter_map(|a| match a {
+            AttributeDecl::Id(i) => Some(i.0),
+            AttributeDecl::Redeclared(_) => None,
+        })
+        .collect()
+}
+
+/// Returns an entity and all of its supertypes, in the order in which their
+/// attributes appear in a STEP file
+fn lineage<'a>(all: &HashMap<&'a str, &'a EntityDecl<'a>>, name: &'a str)
+    -> Vec<&'a str>
+{
+    let mut out = Vec::new();
+    let mut todo = vec![(name, false)];
+    while let Some((n, done)) = todo.pop() {
+        if done {
+            if !out.contains(&n) {
+                out.push(n);
+            }
+        } else if !out.contains(&n) && todo.len() < MAX_DEPTH {
+            todo.push((n, true));
+            let supers: Vec<&'a str> = all.get(n).into_iter()
+                .flat_map(|e| ((e.0).1).1.iter().flat_map(|s| s.0.iter()))
+                .map(|s| s.0)
+                .filter(|s| all.contains_key(s))
+                .collect();
+            todo.extend(supers.into_iter().rev().map(|s| (s, false)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use express::{parse_schema, strip_comments_and_lower};
+    use crate::step_file::StepFile;
+
+    const SCHEMA: &[u8] = br#"SCHEMA shapes;
+ENTITY item
+  ABSTRACT SUPERTYPE;
+  name : STRING;
+END_ENTITY;
+ENTITY tagged;
+  tag : INTEGER;
+END_ENTITY;
+ENTITY point
+  SUBTYPE OF (item);
+  coords : LIST [1:3] OF REAL;
+END_ENTITY;
+ENTITY circle
+  SUBTYPE OF (item, tagged);
+  center : point;
+  radius : REAL;
+END_ENTITY;
+ENTITY unit_circle
+  SUBTYPE OF (circle);
+DERIVE
+  SELF\circle.radius : REAL := 1.0;
+END_ENTITY;
+END_SCHEMA;
+"#;
+
+    /// Parses a file against [`SCHEMA`], then passes the results to `f`
+    fn parse<F: FnOnce(Vec<Result<DynEntity, Error>>)>(data: &[u8], f: F) {
+        let text = strip_comments_and_lower(SCHEMA);
+        let syntax = parse_schema(&text).unwrap();
+        let schema = DynSchema::new(&syntax, "shapes").unwrap();
+        f(schema.parse(&StepFile::strip_flatten(data)))
+    }
+
+    #[test]
+    fn test_schema() {
+        let text = strip_comments_and_lower(SCHEMA);
+        let syntax = parse_schema(&text).unwrap();
+        assert!(DynSchema::new(&syntax, "nope").is_none());
+        let s = DynSchema::new(&syntax, "SHAPES").unwrap();
+        assert_eq!(s.name(), "shapes");
+        assert_eq!(s.types("UNIT_CIRCLE").unwrap(),
+                   ["item", "tagged", "circle", "unit_circle"]);
+        assert_eq!(s.attributes("circle").unwrap(), [
+            ("item", "name"), ("tagged", "tag"),
+            ("circle", "center"), ("circle", "radius"),
+        ]);
+        assert_eq!(s.attributes("unit_circle"), s.attributes("circle"));
+        assert!(s.types("line").is_none());
+    }
+
+    #[test]
+    fn test_parse() {
+        parse(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#3=UNIT_CIRCLE('u',2,#1,*);
+#1=POINT('p',(0.,1.5,2.));
+#2=CIRCLE('c',1,#1,$);
+ENDSEC;END-ISO-10303-21;", |es| {
+            let es: Vec<DynEntity> = es.into_iter().map(Result::unwrap).collect();
+            assert_eq!(es.iter().map(|e| e.id).collect::<Vec<_>>(), [1, 2, 3]);
+
+            assert_eq!(es[0].parts, ["point"]);
+            assert_eq!(es[0].get("coords"), Some(&Param::List(vec![
+                Param::Real(0.0), Param::Real(1.5), Param::Real(2.0)])));
+            assert_eq!(es[1].get("TAGGED.TAG"), Some(&Param::Integer(1)));
+            assert_eq!(es[1].get("radius"), Some(&Param::Unset));
+            assert_eq!(es[1].get("point.coords"), None);
+            assert_eq!(es[1].refs(), [1]);
+            assert!(es[2].is("circle") && es[2].is("Item") && !es[2].is("point"));
+            assert_eq!(es[2].get("radius"), Some(&Param::Derived));
+        });
+    }
+
+    #[test]
+    fn test_complex() {
+        parse(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=(ITEM('x')TAGGED(7));
+ENDSEC;END-ISO-10303-21;", |es| {
+            let e = es[0].as_ref().unwrap();
+            assert_eq!(e.parts, ["item", "tagged"]);
+            assert_eq!(e.types, ["item", "tagged"]);
+            assert_eq!(e.get("name"), Some(&Param::String("x".to_owned())));
+            assert_eq!(e.get("tag"), Some(&Param::Integer(7)));
+        });
+    }
+
+    #[test]
+    fn test_errors() {
+        parse(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=LINE('l');
+#2=POINT('p');
+#3=(ITEM('x',1)TAGGED(7));
+#4=POINT('p',(0.,0.));
+ENDSEC;END-ISO-10303-21;", |es| {
+            assert_eq!(es[0], Err(Error::UnknownEntity {
+                id: 1, name: "LINE".to_owned() }));
+            assert_eq!(es[1], Err(Error::WrongCount {
+                id: 2, entity: "POINT".to_owned(), expected: 2, found: 1 }));
+            assert_eq!(es[2].as_ref().unwrap_err().to_string(),
+                       "#3: expected 1 parameters for ITEM, found 2");
+            assert!(es[3].is_ok());
+        });
+    }
+}
@@ -0,0 +1,561 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use log::warn;
+
+use crate::{
+    header::Header,
+    merge::Merge,
+    raw::{records, Record},
+    step_file::StepFile,
+    writer::{Param, StepWriter},
+};
+
+/// A document which defines part of the model, e.g. one component of an
+/// assembly which was split across several files
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalRef {
+    /// ID of the `DOCUMENT_FILE`
+    pub document: usize,
+    /// File name, as written in the `DOCUMENT_FILE`
+    pub file: String,
+    /// `PRODUCT_DEFINITION`s (by entity ID) whose shapes are defined by the
+    /// document
+    pub definitions: Vec<usize>,
+}
+
+/// Finds every external document in a file's instances (e.g. from
+/// [`records`]), along with the product definitions which refer to them.
+///
+/// Documents are attached to products through `APPLIED_DOCUMENT_REFERENCE`
+/// or `PRODUCT_DEFINITION_WITH_ASSOCIATED_DOCUMENTS`; documents which aren't
+/// attached to anything are skipped.
+pub fn external_refs(records: &[Record]) -> Vec<ExternalRef> {
+    let mut files = Vec::new();
+    let mut definitions: HashMap<usize, Vec<usize>> = HashMap::new();
+    for r in records {
+        for (name, ps) in &r.parts {
+            match (name.as_str(), ps.as_slice()) {
+                ("DOCUMENT_FILE", [Param::String(id), Param::String(n), ..]) => {
+                    let file = if id.is_empty() { n } else { id };
+                    files.push((r.id, file.clone()));
+                },
+                ("APPLIED_DOCUMENT_REFERENCE",
+                 [Param::Ref(doc), _, Param::List(items)]) =>
+                {
+                    let ds = definitions.entry(*doc).or_default();
+                    ds.extend(items.iter().filter_map(|i| match i {
+                        Param::Ref(i) => Some(*i),
+                        _ => None,
+                    }));
+                },
+                ("PRODUCT_DEFINITION_WITH_ASSOCIATED_DOCUMENTS",
+                 [_, _, _, _, Param::List(docs)]) =>
+                {
+                    for d in docs {
+                        if let Param::Ref(d) = d {
+                            definitions.entry(*d).or_default().push(r.id);
+                        }
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+
+    // Only keep references to product definitions
+    let pds: HashSet<usize> = records.iter()
+        .filter(|r| r.parts.iter().any(|p| p.0.starts_with("PRODUCT_DEFINITION")
+            && !p.0.starts_with("PRODUCT_DEFINITION_SHAPE")
+            && !p.0.starts_with("PRODUCT_DEFINITION_FORMATION")
+            && !p.0.starts_with("PRODUCT_DEFINITION_CONTEXT")))
+        .map(|r| r.id)
+        .collect();
+    files.into_iter()
+        .filter_map(|(document, file)| {
+            let mut ds: Vec<usize> = definitions.remove(&document)?
+                .into_iter()
+                .filter(|d| pds.contains(d))
+                .collect();
+            ds.sort_unstable();
+            ds.dedup();
+            if ds.is_empty() {
+                None
+            } else {
+                Some(ExternalRef { document, file, definitions: ds })
+            }
+        })
+        .collect()
+}
+
+/// An entry in the REFERENCE section of a Part 21 edition 3 file, which
+/// lets instances in the DATA section refer to an instance defined by
+/// another file (e.g. `#100=<bolt.stp#shape>;`)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reference {
+    /// ID by which the DATA section refers to the instance
+    pub id: usize,
+    /// File which defines the instance, relative to this one; empty if the
+    /// anchor is in this file (`<#shape>`)
+    pub file: String,
+    /// Name of the anchor, in the ANCHOR section of `file`
+    pub anchor: String,
+}
+
+/// Reads the ANCHOR section of a file which has been preprocessed by
+/// [`StepFile::strip_flatten`], which names the instances which other files
+/// may refer to (e.g. `<shape>=#12;`).  Anchors which name values rather
+/// than instances are skipped.
+pub fn anchors(data: &[u8]) -> HashMap<String, usize> {
+    StepFile::section_blocks(data, b"ANCHOR;").into_iter()
+        .filter_map(|b| std::str::from_utf8(b).ok())
+        .filter_map(|b| {
+            let (name, id) = b.strip_prefix('<')?.split_once(">=#")?;
+            let id = id.strip_suffix(';')?.parse().ok()?;
+            Some((name.to_owned(), id))
+        })
+        .collect()
+}
+
+/// Reads the REFERENCE section of a file which has been preprocessed by
+/// [`StepFile::strip_flatten`]
+pub fn references(data: &[u8]) -> Vec<Reference> {
+    StepFile::section_blocks(data, b"REFERENCE;").into_iter()
+        .filter_map(|b| std::str::from_utf8(b).ok())
+        .filter_map(|b| {
+            let (id, uri) = b.strip_prefix('#')?.split_once("=<")?;
+            let uri = uri.strip_suffix(">;")?;
+            match uri.rsplit_once('#') {
+                Some((file, anchor)) => Some(Reference {
+                    id: id.parse().ok()?,
+                    file: file.to_owned(),
+                    anchor: anchor.to_owned(),
+                }),
+                None => {
+                    warn!("Ignoring reference to {} without an anchor", uri);
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// Loads the contents of an external document
+pub trait Resolver {
+    /// Returns the contents of a document, given its name relative to the
+    /// master file (e.g. `"parts/bolt.stp"`)
+    fn resolve(&mut self, name: &str) -> std::io::Result<Vec<u8>>;
+}
+
+/// Any closure can be used as a resolver, e.g. to fetch documents from a
+/// PDM system
+impl<F: FnMut(&str) -> std::io::Result<Vec<u8>>> Resolver for F {
+    fn resolve(&mut self, name: &str) -> std::io::Result<Vec<u8>> {
+        self(name)
+    }
+}
+
+/// Reads documents from the filesystem, relative to a directory (usually
+/// the one holding the master file), decompressing them if needed
+#[derive(Clone, Debug)]
+pub struct DirResolver(pub PathBuf);
+
+impl Resolver for DirResolver {
+    fn resolve(&mut self, name: &str) -> std::io::Result<Vec<u8>> {
+        StepFile::read(self.0.join(name))
+    }
+}
+
+/// A master file merged with every document which it references (directly
+/// or through other documents)
+#[derive(Debug)]
+pub struct Resolved {
+    pub merge: Merge,
+    /// Names of the merged files, by their index in the [`Merge`].  The
+    /// master file has an empty name.  It comes first, unless its REFERENCE
+    /// section refers to instances in other files, which are merged before
+    /// the files which refer to them.
+    pub files: Vec<String>,
+    /// Links from each referencing product's shape representation to the
+    /// root shape representations of its document, as merged IDs
+    pub links: Vec<(usize, usize)>,
+    /// Documents which couldn't be loaded, with the reason
+    pub missing: Vec<(String, std::io::Error)>,
+}
+
+impl Resolved {
+    /// Builds a writer holding every merged instance, plus a
+    /// `SHAPE_REPRESENTATION_RELATIONSHIP` for each link, so that the
+    /// documents' shapes are drawn in place of the products which refer to
+    /// them
+    pub fn writer(&self, name: &str) -> StepWriter {
+        let mut w = self.merge.writer(name);
+        for (parent, child) in &self.links {
+            w.add("SHAPE_REPRESENTATION_RELATIONSHIP", &[
+                Param::str(""), Param::str("external reference"),
+                Param::Ref(*parent), Param::Ref(*child)]);
+        }
+        w
+    }
+}
+
+/// Loads a master file which has been preprocessed by
+/// [`StepFile::strip_flatten`], along with every document which it refers
+/// to, as one population.  Documents are loaded once each, and names are
+/// relative to the file which refers to them; documents which can't be
+/// loaded are listed in [`Resolved::missing`].
+///
+/// Two kinds of reference are followed:
+/// - documents attached to products (see [`external_refs`]), whose shapes
+///   are linked to the products' shapes (see [`Resolved::links`]);
+/// - entries in the REFERENCE section (see [`references`]), which are
+///   replaced by the instance named by the target file's ANCHOR section.
+pub fn resolve<R: Resolver>(data: &[u8], resolver: &mut R) -> Resolved {
+    let mut loader = Loader {
+        resolver,
+        out: Resolved {
+            merge: Merge::new(),
+            files: Vec::new(),
+            links: Vec::new(),
+            missing: Vec::new(),
+        },
+        loaded: HashMap::new(),
+        loading: Vec::new(),
+        anchors: Vec::new(),
+        roots: Vec::new(),
+        todo: Vec::new(),
+    };
+    loader.load("", data);
+
+    while let Some((file, recs)) = loader.todo.pop() {
+        let dir = parent_dir(&loader.out.files[file]);
+        for e in external_refs(&recs) {
+            let name = dir.join(&e.file).to_string_lossy().into_owned();
+            let child = match loader.open(&name) {
+                Some(c) => c,
+                None => continue,
+            };
+            for p in shape_reps(&recs, &e.definitions) {
+                for c in &loader.roots[child] {
+                    let p = loader.out.merge.new_id(file, p);
+                    let c = loader.out.merge.new_id(child, *c);
+                    if let (Some(p), Some(c)) = (p, c) {
+                        loader.out.links.push((p, c));
+                    }
+                }
+            }
+        }
+    }
+    loader.out
+}
+
+/// State for [`resolve`], which loads each document at most once
+struct Loader<'r, R> {
+    resolver: &'r mut R,
+    out: Resolved,
+    /// Merge index of each document which has been loaded, by name
+    loaded: HashMap<String, usize>,
+    /// Documents whose REFERENCE targets are being loaded, to catch cycles
+    loading: Vec<String>,
+    /// Each merged document's anchors and root shape representations
+    anchors: Vec<HashMap<String, usize>>,
+    roots: Vec<Vec<usize>>,
+    /// Merged documents whose attached documents haven't been followed yet
+    todo: Vec<(usize, Vec<Record>)>,
+}
+
+impl<R: Resolver> Loader<'_, R> {
+    /// Loads a document by name, unless it has been already, returning its
+    /// index in the merge
+    fn open(&mut self, name: &str) -> Option<usize> {
+        if let Some(c) = self.loaded.get(name) {
+            return Some(*c);
+        }
+        let bytes = if self.loading.iter().any(|n| n == name) {
+            Err(std::io::Error::new(std::io::ErrorKind::InvalidData,
+                                    "circular reference"))
+        } else {
+            self.resolver.resolve(name)
+        };
+        match bytes {
+            Ok(bytes) => Some(self.load(name, &StepFile::strip_flatten(&bytes))),
+            Err(err) => {
+                warn!("Could not load external document {}: {}", name, err);
+                self.out.missing.push((name.to_owned(), err));
+                None
+            },
+        }
+    }
+
+    /// Merges a flattened document, after loading the documents which its
+    /// REFERENCE section points into
+    fn load(&mut self, name: &str, data: &[u8]) -> usize {
+        let dir = parent_dir(name);
+        let anchors = anchors(data);
+        let mut external = HashMap::new();
+        let mut local = HashMap::new();
+        self.loading.push(name.to_owned());
+        for r in references(data) {
+            let target = if r.file.is_empty() {
+                anchors.get(&r.anchor).map(|id| (None, *id))
+            } else {
+                let file = dir.join(&r.file).to_string_lossy().into_owned();
+                self.open(&file).and_then(|c| self.anchors[c].get(&r.anchor)
+                    .map(|id| (Some(c), *id)))
+            };
+            match target {
+                Some((None, id)) => {
+                    local.insert(r.id, id);
+                },
+                Some((Some(c), id)) => match self.out.merge.new_id(c, id) {
+                    Some(id) => {
+                        external.insert(r.id, id);
+                    },
+                    None => warn!("Anchor <{}#{}> names a missing instance",
+                                  r.file, r.anchor),
+                },
+                None => warn!("Could not resolve #{}=<{}#{}>",
+                              r.id, r.file, r.anchor),
+            }
+        }
+        self.loading.pop();
+
+        let mut recs = records(data);
+        if !local.is_empty() {
+            for r in &mut recs {
+                for (_, ps) in &mut r.parts {
+                    ps.iter_mut().for_each(|p| substitute(p, &local));
+                }
+            }
+        }
+        let schema = Header::parse(data).schemas.into_iter().next();
+        let c = self.out.merge.add_records_linked(recs.clone(), schema,
+                                                  &external);
+        self.out.files.push(name.to_owned());
+        self.loaded.insert(name.to_owned(), c);
+        self.anchors.push(anchors);
+        self.roots.push(root_reps(&recs));
+        self.todo.push((c, recs));
+        c
+    }
+}
+
+/// Returns the directory holding a document, against which the names in
+/// its references are resolved
+fn parent_dir(name: &str) -> PathBuf {
+    Path::new(name).parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Rewrites references to IDs which are aliases for other instances
+fn substitute(p: &mut Param, aliases: &HashMap<usize, usize>) {
+    match p {
+        Param::Ref(i) => if let Some(j) = aliases.get(i) {
+            *i = *j;
+        },
+        Param::List(v) => v.iter_mut().for_each(|p| substitute(p, aliases)),
+        Param::Typed(_, p) => substitute(p, aliases),
+        _ => (),
+    }
+}
+
+/// Loads a master file and every document which it refers to, looking for
+/// documents relative to the master file's directory
+pub fn resolve_file(path: &Path) -> std::io::Result<Resolved> {
+    let data = StepFile::read(path)?;
+    let flat = StepFile::strip_flatten(&data);
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    Ok(resolve(&flat, &mut DirResolver(dir)))
+}
+
+/// Returns each shape definition's `(product definition, representation)`
+fn shape_definitions(records: &[Record]) -> Vec<(usize, usize)> {
+    let pds: HashMap<usize, usize> = records.iter()
+        .filter_map(|r| r.parts.iter()
+            .find(|p| p.0 == "PRODUCT_DEFINITION_SHAPE")
+            .and_then(|p| match p.1.get(2) {
+                Some(Param::Ref(pd)) => Some((r.id, *pd)),
+                _ => None,
+            }))
+        .collect();
+    records.iter()
+        .filter_map(|r| r.parts.iter()
+            .find(|p| p.0 == "SHAPE_DEFINITION_REPRESENTATION")
+            .and_then(|p| match p.1.as_slice() {
+                [Param::Ref(d), Param::Ref(rep)] =>
+                    pds.get(d).map(|pd| (*pd, *rep)),
+                _ => None,
+            }))
+        .collect()
+}
+
+/// Finds the shape representations of the given product definitions
+fn shape_reps(records: &[Record], definitions: &[usize]) -> Vec<usize> {
+    shape_definitions(records).into_iter()
+        .filter(|(pd, _)| definitions.contains(pd))
+        .map(|(_, rep)| rep)
+        .collect()
+}
+
+/// Finds the shape representations of a document's top-level products,
+/// i.e. those which aren't used as a component of another product
+fn root_reps(records: &[Record]) -> Vec<usize> {
+    let components: HashSet<usize> = records.iter()
+        .flat_map(|r| r.parts.iter())
+        .filter(|p| p.0 == "NEXT_ASSEMBLY_USAGE_OCCURRENCE")
+        .filter_map(|p| match p.1.get(4) {
+            Some(Param::Ref(pd)) => Some(*pd),
+            _ => None,
+        })
+        .collect();
+    shape_definitions(records).into_iter()
+        .filter(|(pd, _)| !components.contains(pd))
+        .map(|(_, rep)| rep)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A product with an (empty) shape representation, optionally defined
+    /// by an external document
+    fn product(name: &str, document: Option<&str>) -> String {
+        let mut w = StepWriter::new(name);
+        let p = w.add("PRODUCT", &[
+            Param::str(name), Param::str(name), Param::str(""),
+            Param::refs(&[])]);
+        let f = w.add("PRODUCT_DEFINITION_FORMATION", &[
+            Param::str(""), Param::str(""), Param::Ref(p)]);
+        let pd = w.add("PRODUCT_DEFINITION", &[
+            Param::str("design"), Param::str(""), Param::Ref(f), Param::Unset]);
+        let pds = w.add("PRODUCT_DEFINITION_SHAPE", &[
+            Param::str(""), Param::str(""), Param::Ref(pd)]);
+        let pt = w.add("CARTESIAN_POINT", &[
+            Param::str(name), Param::reals(&[0.0, 0.0, 0.0])]);
+        let rep = w.add("SHAPE_REPRESENTATION", &[
+            Param::str(name), Param::refs(&[pt]), Param::Unset]);
+        w.add("SHAPE_DEFINITION_REPRESENTATION", &[
+            Param::Ref(pds), Param::Ref(rep)]);
+        if let Some(d) = document {
+            let t = w.add("DOCUMENT_TYPE", &[Param::str("")]);
+            let doc = w.add("DOCUMENT_FILE", &[
+                Param::str(d), Param::str(""), Param::Ref(t), Param::str(""),
+                Param::str(""), Param::str("")]);
+            w.add("APPLIED_DOCUMENT_REFERENCE", &[
+                Param::Ref(doc), Param::str(""), Param::refs(&[pd])]);
+        }
+        w.to_string()
+    }
+
+    #[test]
+    fn test_external_refs() {
+        let flat = StepFile::strip_flatten(product("a", Some("b.stp")).as_bytes());
+        assert_eq!(external_refs(&records(&flat)), [ExternalRef {
+            document: 9, file: "b.stp".to_owned(), definitions: vec![3],
+        }]);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let master = StepFile::strip_flatten(
+            product("a", Some("sub/b.stp")).as_bytes());
+        let mut requested = Vec::new();
+        let mut resolver = |name: &str| {
+            requested.push(name.to_owned());
+            match name {
+                "sub/b.stp" => Ok(product("b", Some("c.stp")).into_bytes()),
+                _ => Err(std::io::ErrorKind::NotFound.into()),
+            }
+        };
+        let r = resolve(&master, &mut resolver);
+        assert_eq!(requested, ["sub/b.stp", "sub/c.stp"]);
+        assert_eq!(r.files, ["", "sub/b.stp"]);
+        assert_eq!(r.missing.len(), 1);
+        assert_eq!(r.missing[0].0, "sub/c.stp");
+
+        // a's shape representation (#6) is linked to b's, which follows the
+        // 10 instances from the master file
+        assert_eq!(r.links, [(6, 16)]);
+        let text = r.writer("resolved").to_string();
+        assert!(text.contains("SHAPE_REPRESENTATION_RELATIONSHIP('',\
+                               'external reference',#6,#16)"));
+    }
+
+    #[test]
+    fn test_anchors_and_references() {
+        let geometry = "ISO-10303-21;
+HEADER;FILE_SCHEMA(('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF'));ENDSEC;
+ANCHOR;
+<shape>=#2;
+<origin> = #1;
+<pi>=3.14;
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=SHAPE_REPRESENTATION('geometry',(#1),$);
+ENDSEC;
+END-ISO-10303-21;";
+        let master = "ISO-10303-21;
+HEADER;FILE_SCHEMA(('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF'));ENDSEC;
+ANCHOR;<top>=#1;ENDSEC;
+REFERENCE;
+#50=<parts/geometry.stp#shape>;
+#51=<#top>;
+#52=<parts/geometry.stp>;
+ENDSEC;
+DATA('main',('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF'));
+#1=CARTESIAN_POINT('top',(1.,0.,0.));
+#2=SHAPE_REPRESENTATION_RELATIONSHIP('','',#50,#51);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(geometry.as_bytes());
+        let a = anchors(&flat);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a["shape"], 2);
+        assert_eq!(a["origin"], 1);
+
+        let flat = StepFile::strip_flatten(master.as_bytes());
+        assert_eq!(references(&flat), [
+            Reference { id: 50, file: "parts/geometry.stp".to_owned(),
+                        anchor: "shape".to_owned() },
+            Reference { id: 51, file: String::new(),
+                        anchor: "top".to_owned() }]);
+        assert_eq!(records(&flat).len(), 2);
+
+        let mut resolver = |name: &str| match name {
+            "parts/geometry.stp" => Ok(geometry.as_bytes().to_vec()),
+            _ => Err(std::io::ErrorKind::NotFound.into()),
+        };
+        let r = resolve(&flat, &mut resolver);
+        assert!(r.missing.is_empty());
+
+        // The referenced file is merged first, so that the master file's
+        // references can point into it
+        assert_eq!(r.files, ["parts/geometry.stp", ""]);
+        let rel = &r.merge.records()[3];
+        assert_eq!(rel.parts[0].0, "SHAPE_REPRESENTATION_RELATIONSHIP");
+        assert_eq!(rel.parts[0].1[2..], [Param::Ref(2), Param::Ref(3)]);
+        assert_eq!(r.merge.records()[1].parts[0].0, "SHAPE_REPRESENTATION");
+        assert_eq!(r.merge.records()[2].parts[0].1[0], Param::str("top"));
+    }
+
+    #[test]
+    fn test_circular_references() {
+        let file = |other: &str| format!("ISO-10303-21;HEADER;ENDSEC;
+ANCHOR;<p>=#1;ENDSEC;REFERENCE;#9=<{}#p>;ENDSEC;DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));#2=VECTOR('',#9,1.);
+ENDSEC;END-ISO-10303-21;", other);
+        let mut resolver = |name: &str| match name {
+            "b.stp" => Ok(file("a.stp").into_bytes()),
+            "a.stp" => Ok(file("b.stp").into_bytes()),
+            _ => Err(std::io::ErrorKind::NotFound.into()),
+        };
+        let flat = StepFile::strip_flatten(file("b.stp").as_bytes());
+        let r = resolve(&flat, &mut resolver);
+        assert_eq!(r.files, ["a.stp", "b.stp", ""]);
+        assert_eq!(r.missing.len(), 1);
+        assert_eq!(r.missing[0].0, "b.stp");
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{raw::header, writer::Param};
+
+/// The contents of a file's HEADER section, which records where the file
+/// came from.  Fields are empty when the corresponding header entity is
+/// missing or doesn't match the standard's layout; strings are kept as
+/// written (including any `\X2\` escapes).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Header {
+    /// `FILE_DESCRIPTION.description`, an informal summary of the contents
+    pub description: Vec<String>,
+    /// `FILE_DESCRIPTION.implementation_level`, e.g. `2;1`
+    pub implementation_level: String,
+
+    /// `FILE_NAME.name`
+    pub name: String,
+    /// `FILE_NAME.time_stamp`, an ISO 8601 date and time such as
+    /// `2021-06-09T14:05:52`
+    pub time_stamp: String,
+    pub author: Vec<String>,
+    pub organization: Vec<String>,
+    /// Name and version of the software which wrote the file
+    pub preprocessor_version: String,
+    /// Name and version of the CAD system in which the model was created
+    pub originating_system: String,
+    pub authorization: String,
+
+    /// `FILE_SCHEMA`, e.g. `AUTOMOTIVE_DESIGN` for AP214 files
+    pub schemas: Vec<String>,
+}
+
+impl Header {
+    /// Reads the header of a file which has been preprocessed by
+    /// [`StepFile::strip_flatten`](crate::step_file::StepFile::strip_flatten)
+    pub fn parse(data: &[u8]) -> Self {
+        Self::from_entities(&header(data))
+    }
+
+    /// Builds a header from the header entities, as returned by
+    /// [`raw::header`](crate::raw::header)
+    pub fn from_entities(entities: &[(String, Vec<Param>)]) -> Self {
+        let mut out = Self::default();
+        for (name, ps) in entities {
+            let s = |i: usize| ps.get(i).map(string).unwrap_or_default();
+            let v = |i: usize| ps.get(i).map(strings).unwrap_or_default();
+            match name.as_str() {
+                "FILE_DESCRIPTION" => {
+                    out.description = v(0);
+                    out.implementation_level = s(1);
+                },
+                "FILE_NAME" => {
+                    out.name = s(0);
+                    out.time_stamp = s(1);
+                    out.author = v(2);
+                    out.organization = v(3);
+                    out.preprocessor_version = s(4);
+                    out.originating_system = s(5);
+                    out.authorization = s(6);
+                },
+                "FILE_SCHEMA" => out.schemas = v(0),
+                _ => (),
+            }
+        }
+        out
+    }
+}
+
+fn string(p: &Param) -> String {
+    match p {
+        Param::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Reads a list of strings, dropping empty ones (which exporters write as a
+/// placeholder, e.g. `('')` for an unknown author)
+fn strings(p: &Param) -> Vec<String> {
+    match p {
+        Param::List(v) => v.iter()
+            .map(string)
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::step_file::StepFile;
+
+    #[test]
+    fn test_header() {
+        let data = b"ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('A bracket', 'rev B'),'2;1');
+FILE_NAME('bracket.step','2021-06-09T14:05:52',('J. Smith'),
+  ('ACME; Inc.'),'ST-DEVELOPER v18','SolidWorks 2020','');
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let h = Header::parse(&flat);
+        assert_eq!(h.description, ["A bracket", "rev B"]);
+        assert_eq!(h.implementation_level, "2;1");
+        assert_eq!(h.name, "bracket.step");
+        assert_eq!(h.time_stamp, "2021-06-09T14:05:52");
+        assert_eq!(h.author, ["J. Smith"]);
+        assert_eq!(h.organization, ["ACME; Inc."]);
+        assert_eq!(h.preprocessor_version, "ST-DEVELOPER v18");
+        assert_eq!(h.originating_system, "SolidWorks 2020");
+        assert_eq!(h.authorization, "");
+        assert_eq!(h.schemas, ["AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }"]);
+
+        assert_eq!(StepFile::parse(&flat).header(), &h);
+    }
+
+    #[test]
+    fn test_partial_header() {
+        let data = b"ISO-10303-21;HEADER;FILE_NAME('x',$,(''),(''),'','','');
+ENDSEC;DATA;ENDSEC;END-ISO-10303-21;";
+        let h = Header::parse(&StepFile::strip_flatten(data));
+        assert_eq!(h.name, "x");
+        assert!(h.time_stamp.is_empty());
+        assert!(h.author.is_empty());
+        assert!(h.schemas.is_empty());
+    }
+}
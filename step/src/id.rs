@@ -1,13 +1,43 @@
 use arrayvec::ArrayVec;
 
+/// Reserved index for an unset reference (`$`)
+const UNSET: usize = usize::MAX;
+/// Reserved index for a derived reference (`*`)
+const DERIVED: usize = usize::MAX - 1;
+
+/// A typed reference to an entity instance.
+///
+/// A reference in the file may be a placeholder (`$` or `*`) instead of an
+/// instance ID; these are stored as reserved indices, which never match an
+/// entity, so [`StepFile::entity`](crate::step_file::StepFile::entity) returns
+/// `None` for them.
 #[derive(Debug)]
 pub struct Id<T>(pub usize, std::marker::PhantomData<*const T>);
 impl<T> Id<T> {
     pub fn new(i: usize) -> Self {
         Id(i, std::marker::PhantomData)
     }
-    pub fn empty() -> Self {
-        Id::new(0)
+    /// Builds an unset reference (`$`)
+    pub fn unset() -> Self {
+        Id::new(UNSET)
+    }
+    /// Builds a derived reference (`*`)
+    pub fn derived() -> Self {
+        Id::new(DERIVED)
+    }
+    pub fn is_unset(&self) -> bool {
+        self.0 == UNSET
+    }
+    pub fn is_derived(&self) -> bool {
+        self.0 == DERIVED
+    }
+    /// Returns this reference, or `None` if it's a placeholder
+    pub fn get(&self) -> Option<Self> {
+        if self.is_unset() || self.is_derived() {
+            None
+        } else {
+            Some(*self)
+        }
     }
     pub fn cast<V>(&self) -> Id<V> {
         Id::new(self.0)
@@ -29,6 +59,16 @@ impl<T> PartialEq for Id<T> {
     }
 }
 impl<T> Eq for Id<T> {}
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
 impl<T> std::hash::Hash for Id<T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.0.hash(state);
@@ -40,7 +80,9 @@ pub(crate) trait HasId {
 }
 impl<T> HasId for Id<T> {
     fn append_ids(&self, v: &mut Vec<usize>) {
-        v.push(self.0)
+        if let Some(i) = self.get() {
+            v.push(i.0)
+        }
     }
 }
 impl<T: HasId> HasId for Vec<T> {
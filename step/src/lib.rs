@@ -1,4 +1,32 @@
+/// Opens a profiling scope, which lasts until the end of the enclosing block.
+/// This compiles to nothing unless the `profiling` feature is enabled, in
+/// which case it's passed to whichever backend of the `profiling` crate
+/// the final binary has turned on (puffin, tracing, Tracy, ...).
+macro_rules! profile_scope {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "profiling")]
+        profiling::scope!($($arg)*);
+    };
+}
+
 pub mod parse;
 pub mod step_file;
 pub mod ap214; // autogenerated!
 pub mod id;
+pub mod writer;
+pub mod raw;
+pub mod header;
+pub mod stream;
+pub mod mmap;
+pub mod archive;
+pub mod xml;
+pub mod real;
+pub mod rules;
+pub mod check;
+pub mod merge;
+pub mod scrub;
+pub mod dynamic;
+pub mod conformance;
+pub mod external;
+pub mod validation;
+pub mod pmi;
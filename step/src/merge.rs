@@ -0,0 +1,480 @@
+use std::collections::HashMap;
+
+use log::warn;
+
+use crate::{
+    header::Header,
+    raw::{records, Record},
+    step_file::StepFile,
+    writer::{Param, StepWriter},
+};
+
+/// Entities which describe shared context (units, representation contexts,
+/// and so on), rather than part of a model.  Identical context instances
+/// from different files are merged into one.
+const CONTEXTS: &[&str] = &[
+    "APPLICATION_CONTEXT",
+    "APPLICATION_PROTOCOL_DEFINITION",
+    "AREA_UNIT",
+    "CONVERSION_BASED_UNIT",
+    "DERIVED_UNIT",
+    "DERIVED_UNIT_ELEMENT",
+    "DESIGN_CONTEXT",
+    "DIMENSIONAL_EXPONENTS",
+    "GEOMETRIC_REPRESENTATION_CONTEXT",
+    "GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT",
+    "GLOBAL_UNIT_ASSIGNED_CONTEXT",
+    "LENGTH_UNIT",
+    "MASS_UNIT",
+    "MECHANICAL_CONTEXT",
+    "NAMED_UNIT",
+    "PARAMETRIC_REPRESENTATION_CONTEXT",
+    "PLANE_ANGLE_UNIT",
+    "PRODUCT_CONTEXT",
+    "PRODUCT_DEFINITION_CONTEXT",
+    "RATIO_UNIT",
+    "REPRESENTATION_CONTEXT",
+    "SI_UNIT",
+    "SOLID_ANGLE_UNIT",
+    "THERMODYNAMIC_TEMPERATURE_UNIT",
+    "TIME_UNIT",
+    "UNCERTAINTY_MEASURE_WITH_UNIT",
+    "VOLUME_UNIT",
+];
+
+/// Checks whether every part of an instance is a [context](CONTEXTS) entity
+fn is_context(r: &Record) -> bool {
+    r.parts.iter().all(|(name, _)| CONTEXTS.contains(&name.as_str()))
+}
+
+/// Combines the DATA sections of several STEP files into one, e.g. to build
+/// an assembly from separate part files.
+///
+/// Each file's instances are renumbered to follow those of the previous
+/// files, and references are rewritten to match.  Unit and context
+/// instances which are identical (after renumbering) to one from an earlier
+/// file are shared, so that merged parts use the same units.  The mapping
+/// between old and new IDs is kept in both directions.
+///
+/// Headers are combined as well (see [`Merge::header`]).  Files can be added
+/// as flattened text or as a parsed [`StepFile`]; [`StepFile::merge`] is a
+/// shortcut for merging two parsed files.
+#[derive(Debug, Default)]
+pub struct Merge {
+    /// Merged instances, in order of their (1-based) IDs
+    records: Vec<Record>,
+    /// For each merged ID, the file and ID of the instance it came from.
+    /// Shared instances keep their first origin.
+    origins: Vec<(usize, usize)>,
+    /// For each file, a map from its IDs to merged IDs
+    forward: Vec<HashMap<usize, usize>>,
+    /// Context instances by their text, to find duplicates
+    contexts: HashMap<String, usize>,
+    /// The header of each file
+    headers: Vec<Header>,
+}
+
+impl Merge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file which has been preprocessed by
+    /// [`StepFile::strip_flatten`](crate::step_file::StepFile::strip_flatten),
+    /// returning its index (for use with [`Merge::new_id`]).
+    pub fn add(&mut self, data: &[u8]) -> usize {
+        self.add_with_header(records(data), Header::parse(data),
+                             &HashMap::new())
+    }
+
+    /// Adds a parsed file, returning its index
+    pub fn add_step(&mut self, s: &StepFile) -> usize {
+        self.add_with_header(s.records(), s.header().clone(), &HashMap::new())
+    }
+
+    /// Adds a set of instances, e.g. from [`records`], returning the index
+    /// of the file which they belong to
+    pub fn add_records(&mut self, records: Vec<Record>, schema: Option<String>)
+        -> usize
+    {
+        self.add_records_linked(records, schema, &HashMap::new())
+    }
+
+    /// Adds a set of instances, like [`Merge::add_records`], where some IDs
+    /// stand for instances which have already been merged (e.g. entries in
+    /// a REFERENCE section).  `external` maps those IDs to merged IDs, and
+    /// references to them are rewritten accordingly.
+    pub fn add_records_linked(&mut self, records: Vec<Record>,
+                              schema: Option<String>,
+                              external: &HashMap<usize, usize>) -> usize
+    {
+        let header = Header {
+            schemas: schema.into_iter().collect(),
+            ..Header::default()
+        };
+        self.add_with_header(records, header, external)
+    }
+
+    fn add_with_header(&mut self, records: Vec<Record>, header: Header,
+                       external: &HashMap<usize, usize>) -> usize
+    {
+        let file = self.forward.len();
+        self.forward.push(external.clone());
+        self.headers.push(header);
+
+        let by_id: HashMap<usize, &Record> = records.iter()
+            .map(|r| (r.id, r))
+            .collect();
+        let mut visiting = Vec::new();
+        let mut pending = Vec::new();
+        for r in &records {
+            self.assign(file, r.id, &by_id, &mut visiting, &mut pending);
+        }
+
+        // Now that every instance has an ID, references can be rewritten
+        let fwd = &self.forward[file];
+        for (new_id, r) in pending {
+            self.records[new_id - 1].parts = remap_parts(r, fwd);
+        }
+        file
+    }
+
+    /// Assigns a merged ID to an instance from the given file.  A context
+    /// instance is only assigned once the instances that it refers to have
+    /// been, so that it can be compared with earlier contexts; any other
+    /// instance is added to `pending`, to have its references rewritten
+    /// once the whole file has been assigned.
+    fn assign<'r>(&mut self, file: usize, id: usize,
+                  by_id: &HashMap<usize, &'r Record>,
+                  visiting: &mut Vec<usize>,
+                  pending: &mut Vec<(usize, &'r Record)>)
+    {
+        if self.forward[file].contains_key(&id) {
+            return;
+        }
+        let r = match by_id.get(&id) {
+            Some(r) => *r,
+            None => return,
+        };
+        // A reference cycle stops the search, and the instance isn't shared
+        if is_context(r) && !visiting.contains(&id) {
+            visiting.push(id);
+            let mut refs = Vec::new();
+            for (_, ps) in &r.parts {
+                collect_refs(ps, &mut refs);
+            }
+            for i in &refs {
+                self.assign(file, *i, by_id, visiting, pending);
+            }
+            visiting.pop();
+
+            let fwd = &self.forward[file];
+            if !self.forward[file].contains_key(&id) &&
+               refs.iter().all(|i| fwd.contains_key(i))
+            {
+                let new_id = self.records.len() + 1;
+                let out = Record { id: new_id, parts: remap_parts(r, fwd) };
+                let key = context_key(&out);
+                match self.contexts.get(&key) {
+                    Some(prev) => {
+                        self.forward[file].insert(id, *prev);
+                    },
+                    None => {
+                        self.contexts.insert(key, new_id);
+                        self.forward[file].insert(id, new_id);
+                        self.records.push(out);
+                        self.origins.push((file, id));
+                    },
+                }
+                return;
+            }
+        }
+        if !self.forward[file].contains_key(&id) {
+            let new_id = self.records.len() + 1;
+            self.forward[file].insert(id, new_id);
+            self.records.push(Record { id: new_id, parts: vec![] });
+            self.origins.push((file, id));
+            pending.push((new_id, r));
+        }
+    }
+
+    /// Looks up the merged ID of an instance from one of the input files
+    pub fn new_id(&self, file: usize, id: usize) -> Option<usize> {
+        self.forward.get(file).and_then(|f| f.get(&id)).cloned()
+    }
+
+    /// Looks up the file and original ID of a merged instance
+    pub fn old_id(&self, id: usize) -> Option<(usize, usize)> {
+        id.checked_sub(1).and_then(|i| self.origins.get(i)).cloned()
+    }
+
+    /// Returns the merged instances, whose IDs start at 1
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Combines the headers of every file.  Descriptions, authors, and
+    /// organizations are collected from all of them (without repeats), as
+    /// are the originating systems and authorizations, which are joined
+    /// with `"; "`.  The implementation level is the first one given.  The
+    /// name, time stamp, and preprocessor describe the merged file rather
+    /// than its inputs, so they're left empty.
+    pub fn header(&self) -> Header {
+        fn union(out: &mut Vec<String>, v: &[String]) {
+            for s in v {
+                if !s.is_empty() && !out.contains(s) {
+                    out.push(s.clone());
+                }
+            }
+        }
+        let mut out = Header::default();
+        let (mut systems, mut authorizations) = (Vec::new(), Vec::new());
+        for h in &self.headers {
+            union(&mut out.description, &h.description);
+            union(&mut out.author, &h.author);
+            union(&mut out.organization, &h.organization);
+            union(&mut out.schemas, &h.schemas);
+            union(&mut systems, std::slice::from_ref(&h.originating_system));
+            union(&mut authorizations, std::slice::from_ref(&h.authorization));
+            if out.implementation_level.is_empty() {
+                out.implementation_level = h.implementation_level.clone();
+            }
+        }
+        out.originating_system = systems.join("; ");
+        out.authorization = authorizations.join("; ");
+        out
+    }
+
+    /// Builds a writer holding every merged instance with its new ID, so
+    /// that more instances (e.g. an assembly structure linking the parts)
+    /// can be added before writing it out.  The header is
+    /// [combined](Merge::header) from the input files, and the schema is
+    /// taken from them if they have one.
+    pub fn writer(&self, name: &str) -> StepWriter {
+        let mut w = StepWriter::new(name);
+        w.header = self.header();
+        let mut schemas = self.headers.iter().flat_map(|h| h.schemas.first());
+        if let Some(s) = schemas.next() {
+            if schemas.any(|t| t != s) {
+                warn!("Merging files with different schemas; using {}", s);
+            }
+            w.schema = s.clone();
+        }
+        for r in &self.records {
+            write_record(&mut w, r);
+        }
+        w
+    }
+}
+
+fn write_record(w: &mut StepWriter, r: &Record) {
+    match r.parts.as_slice() {
+        [(name, ps)] => w.add(name, ps),
+        parts => {
+            let parts: Vec<(&str, &[Param])> = parts.iter()
+                .map(|(name, ps)| (name.as_str(), ps.as_slice()))
+                .collect();
+            w.add_complex(&parts)
+        },
+    };
+}
+
+/// Renders an instance without its ID, to compare it with others
+fn context_key(r: &Record) -> String {
+    let mut out = String::new();
+    for (name, ps) in &r.parts {
+        out.push_str(name);
+        Param::List(ps.clone()).write(&mut out);
+    }
+    out
+}
+
+pub(crate) fn collect_refs(ps: &[Param], out: &mut Vec<usize>) {
+    for p in ps {
+        match p {
+            Param::Ref(i) => out.push(*i),
+            Param::List(v) => collect_refs(v, out),
+            Param::Typed(_, p) => collect_refs(std::slice::from_ref(&**p), out),
+            _ => (),
+        }
+    }
+}
+
+fn remap_parts(r: &Record, fwd: &HashMap<usize, usize>)
+    -> Vec<(String, Vec<Param>)>
+{
+    r.parts.iter()
+        .map(|(name, ps)| (name.clone(),
+                           ps.iter().map(|p| remap(p, fwd)).collect()))
+        .collect()
+}
+
+/// Rewrites references in a parameter.  References to instances which
+/// aren't in the file can't be kept, so they're replaced with `$`.
+fn remap(p: &Param, fwd: &HashMap<usize, usize>) -> Param {
+    match p {
+        Param::Ref(i) => match fwd.get(i) {
+            Some(i) => Param::Ref(*i),
+            None => {
+                warn!("Dropping reference to missing instance #{}", i);
+                Param::Unset
+            },
+        },
+        Param::List(v) => Param::List(v.iter().map(|p| remap(p, fwd)).collect()),
+        Param::Typed(t, p) => Param::Typed(t.clone(), Box::new(remap(p, fwd))),
+        p => p.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ap214::Entity, step_file::StepFile};
+
+    fn part(name: &str) -> String {
+        part_writer(name).to_string()
+    }
+
+    fn part_writer(name: &str) -> StepWriter {
+        let mut w = StepWriter::new(name);
+        let len = w.add_complex(&[
+            ("LENGTH_UNIT", &[]),
+            ("NAMED_UNIT", &[Param::Derived]),
+            ("SI_UNIT", &[Param::enum_("MILLI"), Param::enum_("METRE")])]);
+        let ctx = w.add_complex(&[
+            ("GEOMETRIC_REPRESENTATION_CONTEXT", &[Param::Integer(3)]),
+            ("GLOBAL_UNIT_ASSIGNED_CONTEXT", &[Param::refs(&[len])]),
+            ("REPRESENTATION_CONTEXT", &[Param::str(""), Param::str("")])]);
+        let p = w.add("CARTESIAN_POINT", &[
+            Param::str(name), Param::reals(&[0.0, 0.0, 0.0])]);
+        w.add("SHAPE_REPRESENTATION", &[
+            Param::str(name), Param::refs(&[p]), Param::Ref(ctx)]);
+        w
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = StepFile::strip_flatten(part("a").as_bytes());
+        let b = StepFile::strip_flatten(part("b").as_bytes());
+        let mut m = Merge::new();
+        assert_eq!(m.add(&a), 0);
+        assert_eq!(m.add(&b), 1);
+
+        // The unit and context are shared, so the second file only adds
+        // its point and shape representation
+        assert_eq!(m.records().len(), 6);
+        assert_eq!(m.new_id(1, 1), Some(1));
+        assert_eq!(m.new_id(1, 2), Some(2));
+        assert_eq!(m.new_id(1, 3), Some(5));
+        assert_eq!(m.new_id(1, 4), Some(6));
+        assert_eq!(m.new_id(1, 5), None);
+        assert_eq!(m.old_id(6), Some((1, 4)));
+        assert_eq!(m.old_id(2), Some((0, 2)));
+        assert_eq!(m.old_id(7), None);
+        assert_eq!(m.records()[5].parts[0].1, [
+            Param::str("b"), Param::refs(&[5]), Param::Ref(2)]);
+
+        let mut w = m.writer("merged");
+        assert_eq!(w.schema, "AUTOMOTIVE_DESIGN");
+        let extra = w.add("CARTESIAN_POINT", &[
+            Param::str("c"), Param::reals(&[1.0, 0.0, 0.0])]);
+        assert_eq!(extra, 7);
+
+        let text = w.to_string();
+        let flat = StepFile::strip_flatten(text.as_bytes());
+        let step = StepFile::parse(&flat);
+        match &step.entities[6] {
+            Entity::ShapeRepresentation(s) => {
+                assert_eq!(s.name.0, "b");
+                assert_eq!(s.context_of_items.0, 2);
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_step_file_merge() {
+        let with_header = |name: &str, author: &str, system: &str| {
+            let mut w = part_writer(name);
+            w.header.description = vec![format!("Part {}", name)];
+            w.header.author = vec![author.to_owned(), "shared".to_owned()];
+            w.header.originating_system = system.to_owned();
+            w.to_string()
+        };
+        let a = StepFile::strip_flatten(with_header("a", "ann", "cad").as_bytes());
+        let b = StepFile::strip_flatten(with_header("b", "bob", "cad").as_bytes());
+        let (a, b) = (StepFile::parse(&a), StepFile::parse(&b));
+        assert_eq!(a.records().len(), 4);
+
+        let m = a.merge(&b);
+        assert_eq!(m.records().len(), 6);
+        assert_eq!(m.new_id(1, 4), Some(6));
+        let h = m.header();
+        assert_eq!(h.description, ["Part a", "Part b"]);
+        assert_eq!(h.author, ["ann", "shared", "bob"]);
+        assert_eq!(h.originating_system, "cad");
+        assert_eq!(h.implementation_level, "2;1");
+
+        let text = m.writer("merged").to_string();
+        let read = Header::parse(&StepFile::strip_flatten(text.as_bytes()));
+        assert_eq!(read.name, "merged");
+        assert_eq!(read.description, h.description);
+        assert_eq!(read.author, h.author);
+        assert_eq!(read.schemas, ["AUTOMOTIVE_DESIGN"]);
+    }
+
+    #[test]
+    fn test_is_context() {
+        let r = |names: &[&str]| Record {
+            id: 1,
+            parts: names.iter().map(|n| (n.to_string(), vec![])).collect(),
+        };
+        assert!(is_context(&r(&["LENGTH_UNIT", "NAMED_UNIT", "SI_UNIT"])));
+        assert!(is_context(&r(&["UNCERTAINTY_MEASURE_WITH_UNIT"])));
+        assert!(!is_context(&r(&["LENGTH_MEASURE_WITH_UNIT"])));
+        assert!(!is_context(&r(&["UNIT_VECTOR"])));
+        assert!(!is_context(&r(&["CARTESIAN_POINT"])));
+        assert!(!is_context(&r(&["LENGTH_UNIT", "CARTESIAN_POINT"])));
+    }
+
+    #[test]
+    fn test_merge_overflow() {
+        // Overflowed reals in a shared context are compared (and written)
+        // as the largest finite real
+        let text = "ISO-10303-21;HEADER;ENDSEC;DATA;\
+                    #1=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));\
+                    #2=UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E999),\
+                    #1,'','');ENDSEC;END-ISO-10303-21;";
+        let mut m = Merge::new();
+        m.add(text.as_bytes());
+        m.add(text.as_bytes());
+        assert_eq!(m.records().len(), 2);
+        let text = m.writer("merged").to_string();
+        assert!(text.contains("LENGTH_MEASURE(1.7976931348623157E308)"),
+                "{}", text);
+    }
+
+    #[test]
+    fn test_forward_refs() {
+        // References to later instances, and to missing ones
+        let data = b"ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('CONFIG_CONTROL_DESIGN'));
+ENDSEC;
+DATA;
+#10=VERTEX_POINT('',#20);
+#20=CARTESIAN_POINT('',(0.,0.,0.));
+#30=VERTEX_POINT('',#40);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let mut m = Merge::new();
+        m.add(&flat);
+        m.add(&flat);
+        assert_eq!(m.records().len(), 6);
+        assert_eq!(m.records()[3].parts[0].1,
+                   [Param::str(""), Param::Ref(5)]);
+        assert_eq!(m.records()[5].parts[0].1, [Param::str(""), Param::Unset]);
+        assert_eq!(m.writer("x").schema, "CONFIG_CONTROL_DESIGN");
+    }
+}
@@ -0,0 +1,105 @@
+use std::{fs::File, io, ops::Deref, path::Path};
+
+use memmap2::{MmapMut, MmapOptions};
+
+use crate::{archive, step_file::StepFile, xml};
+
+/// A STEP file which has been memory-mapped and flattened in place, as
+/// returned by [`StepFile::open_mmap`].
+///
+/// The file is mapped copy-on-write, so flattening never modifies it on
+/// disk; only the pages which are written are copied into memory.  A
+/// [`StepFile`] parsed from the mapping borrows from it, so the mapping must
+/// outlive the parsed file.
+///
+/// Compressed files (see [`archive`](crate::archive)) and STEP-XML files
+/// (see [`xml`](crate::xml)) can't be used in place, so they're converted
+/// into memory and flattened there.
+pub struct MappedFile {
+    data: Data,
+    len: usize,
+}
+
+enum Data {
+    Empty,
+    Mapped(MmapMut),
+    /// Converted from another format
+    Owned(Vec<u8>),
+}
+
+impl MappedFile {
+    /// Maps and flattens the file at the given path.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while it's mapped; see
+    /// [`StepFile::open_mmap`].
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // Mapping an empty file fails on some platforms
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { data: Data::Empty, len: 0 });
+        }
+        // Safety: the caller guarantees that nothing else modifies the file
+        // while it's mapped.  A private mapping only copies the pages which
+        // are written, so other pages would otherwise change underneath the
+        // `&[u8]` handed out by `deref`, and truncating the file would make
+        // accesses past its new end raise SIGBUS.
+        let mut map = MmapOptions::new().map_copy(&file)?;
+        if archive::is_compressed(&map) || xml::is_xml(&map) {
+            let mut data = StepFile::decode(&map)?.into_owned();
+            let len = StepFile::strip_flatten_in_place(&mut data);
+            return Ok(Self { data: Data::Owned(data), len });
+        }
+        let len = StepFile::strip_flatten_in_place(&mut map);
+        Ok(Self { data: Data::Mapped(map), len })
+    }
+
+    /// Parses the flattened file, with entities borrowing from the mapping
+    pub fn parse(&self) -> StepFile<'_> {
+        StepFile::parse(self)
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    /// Returns the flattened text
+    fn deref(&self) -> &[u8] {
+        match &self.data {
+            Data::Empty => &[],
+            Data::Mapped(m) => &m[..self.len],
+            Data::Owned(v) => &v[..self.len],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmap() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/cube_hole.step");
+        let m = unsafe { StepFile::open_mmap(path) }.unwrap();
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        assert_eq!(&m[..], &flat[..]);
+
+        let a = m.parse();
+        let b = StepFile::parse(&flat);
+        assert_eq!(format!("{:?}", a.entities), format!("{:?}", b.entities));
+        assert_eq!(a.header(), b.header());
+    }
+
+    #[test]
+    fn test_mmap_compressed() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../examples/");
+        let path = format!("{}cuboid.step", dir);
+        let plain = unsafe { StepFile::open_mmap(path) }.unwrap();
+        for name in &["cuboid.step.gz", "cuboid.stpz"] {
+            let path = format!("{}{}", dir, name);
+            let m = unsafe { StepFile::open_mmap(path) }.unwrap();
+            assert_eq!(&m[..], &plain[..]);
+        }
+    }
+}
@@ -1,9 +1,9 @@
 use std::collections::{HashSet, HashMap};
 use nom::{
     branch::{alt},
-    bytes::complete::{is_not, tag},
+    bytes::complete::is_not,
     character::complete::{char, digit1},
-    combinator::{map, map_res, opt},
+    combinator::{map, map_opt, map_res, opt},
     error::*,
     sequence::{delimited, preceded, tuple},
     multi::{separated_list0},
@@ -34,6 +34,47 @@ impl HasId for Logical {
     fn append_ids(&self, _v: &mut Vec<usize>) { /* Nothing to do here */ }
 }
 
+/// Value of an `OPTIONAL` attribute, which may be omitted from an entity
+/// instance with a placeholder
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Value<T> {
+    Set(T),
+    /// No value was given (`$`)
+    Unset,
+    /// The value is computed by a subtype's `DERIVE` rule (`*`)
+    Derived,
+}
+
+impl<T> Value<T> {
+    /// Returns the value, or `None` if it's a placeholder
+    pub fn get(&self) -> Option<&T> {
+        match self {
+            Value::Set(t) => Some(t),
+            Value::Unset | Value::Derived => None,
+        }
+    }
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Value::Set(t) => Some(t),
+            Value::Unset | Value::Derived => None,
+        }
+    }
+    pub fn is_unset(&self) -> bool {
+        matches!(self, Value::Unset)
+    }
+    pub fn is_derived(&self) -> bool {
+        matches!(self, Value::Derived)
+    }
+}
+
+impl<T: HasId> HasId for Value<T> {
+    fn append_ids(&self, v: &mut Vec<usize>) {
+        if let Value::Set(t) = self {
+            t.append_ids(v);
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 pub(crate) trait Parse<'a> {
@@ -42,9 +83,9 @@ pub(crate) trait Parse<'a> {
 
 impl Parse<'_> for f64 {
     fn parse(s: &str) -> IResult<Self> {
-        match fast_float::parse_partial::<f64, _>(s) {
-            Err(_) => nom_err(s, ErrorKind::Float),
-            Ok((x, n)) => Ok((&s[n..], x)),
+        match crate::real::parse_partial(s) {
+            None => nom_err(s, ErrorKind::Float),
+            Some((x, n)) => Ok((&s[n..], x)),
         }
     }
 }
@@ -113,21 +154,32 @@ impl<'a, T: Parse<'a>> Parse<'a> for Option<T> {
             map(T::parse, |v| Some(v))))(s)
     }
 }
-impl<'a> Parse<'a> for Logical {
+impl<'a, T: Parse<'a>> Parse<'a> for Value<T> {
     fn parse(s: &'a str) -> IResult<'a, Self> {
         alt((
-            map(tag(".T."), |_| Logical(Some(true))),
-            map(tag(".F."), |_| Logical(Some(false))),
-            map(tag(".UNKNOWN."), |_| Logical(None)),
-        ))(s)
+            map(char('$'), |_| Value::Unset),
+            map(char('*'), |_| Value::Derived),
+            map(T::parse, Value::Set)))(s)
+    }
+}
+// Logical values are usually abbreviated, but some exporters spell them out
+impl<'a> Parse<'a> for Logical {
+    fn parse(s: &'a str) -> IResult<'a, Self> {
+        map_opt(parse_enum_tag, |t| match t {
+            "T" | "TRUE" => Some(Logical(Some(true))),
+            "F" | "FALSE" => Some(Logical(Some(false))),
+            "U" | "UNKNOWN" => Some(Logical(None)),
+            _ => None,
+        })(s)
     }
 }
 impl<'a> Parse<'a> for bool {
     fn parse(s: &'a str) -> IResult<'a, Self> {
-        alt((
-            map(tag(".T."), |_| true),
-            map(tag(".F."), |_| false),
-        ))(s)
+        map_opt(parse_enum_tag, |t| match t {
+            "T" | "TRUE" => Some(true),
+            "F" | "FALSE" => Some(false),
+            _ => None,
+        })(s)
     }
 }
 impl<'a, T> Parse<'a> for Id<T> {
@@ -136,8 +188,8 @@ impl<'a, T> Parse<'a> for Id<T> {
             map_res(
                 preceded(char('#'), digit1),
                 |s: &str| s.parse().map(|i| Id::new(i))),
-            // NUL id deserializes to 0
-            map(char('$'), |_| Id::empty())))
+            map(char('$'), |_| Id::unset()),
+            map(char('*'), |_| Id::derived())))
             (s)
     }
 }
@@ -336,4 +388,58 @@ mod tests {
         parse_entity_decl(b"#395359=UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-007),#395356,'distance_accuracy_value','confusion accuracy');").unwrap();
         parse_entity_decl(b"#1632=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));").unwrap();
     }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(Value::<f64>::parse("1.5").unwrap().1, Value::Set(1.5));
+        assert_eq!(Value::<f64>::parse("$").unwrap().1, Value::Unset);
+        assert_eq!(Value::<f64>::parse("*").unwrap().1, Value::Derived);
+        assert_eq!(Value::<f64>::parse("-.5").unwrap().1, Value::Set(-0.5));
+        assert_eq!(Value::<f64>::parse("1D+2").unwrap().1, Value::Set(100.0));
+
+        let (_, (_, e)) = parse_entity_decl(
+            b"#1=AXIS2_PLACEMENT_3D('',#2,$,*);").unwrap();
+        match e {
+            Entity::Axis2Placement3d(a) => {
+                assert_eq!(a.location.0, 2);
+                assert!(a.axis.is_unset());
+                assert!(a.ref_direction.is_derived());
+                assert!(a.ref_direction.get().is_none());
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+
+        let (_, (_, e)) = parse_entity_decl(
+            b"#3=VERTEX_POINT('',$);").unwrap();
+        match e {
+            Entity::VertexPoint(v) => {
+                assert!(v.vertex_geometry.is_unset());
+                assert!(v.vertex_geometry.get().is_none());
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_enums() {
+        use crate::ap214::{SiPrefix, SiUnitName};
+        for (s, v) in &[(".T.", Some(true)), (".TRUE.", Some(true)),
+                        (".F.", Some(false)), (".U.", None),
+                        (".UNKNOWN.", None)] {
+            assert_eq!(Logical::parse(s).unwrap().1, Logical(*v));
+        }
+        assert!(Logical::parse(".X.").is_err());
+        assert!(!bool::parse(".FALSE.").unwrap().1);
+        assert!(bool::parse(".U.").is_err());
+
+        let (_, (_, e)) = parse_entity_decl(
+            b"#1=SI_UNIT(*,.MILLI.,.METER.);").unwrap();
+        match e {
+            Entity::SiUnit(u) => {
+                assert!(matches!(u.prefix, Value::Set(SiPrefix::Milli)));
+                assert!(matches!(u.name, SiUnitName::Unknown("METER")));
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+    }
 }
@@ -0,0 +1,521 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{raw::Record, writer::Param};
+
+/// Entity names of the geometric tolerance types, which may be written as
+/// simple instances or as parts of a complex instance
+const TOLERANCES: &[(&str, ToleranceKind)] = &[
+    ("ANGULARITY_TOLERANCE", ToleranceKind::Angularity),
+    ("CIRCULAR_RUNOUT_TOLERANCE", ToleranceKind::CircularRunout),
+    ("COAXIALITY_TOLERANCE", ToleranceKind::Coaxiality),
+    ("CONCENTRICITY_TOLERANCE", ToleranceKind::Concentricity),
+    ("CYLINDRICITY_TOLERANCE", ToleranceKind::Cylindricity),
+    ("FLATNESS_TOLERANCE", ToleranceKind::Flatness),
+    ("LINE_PROFILE_TOLERANCE", ToleranceKind::LineProfile),
+    ("PARALLELISM_TOLERANCE", ToleranceKind::Parallelism),
+    ("PERPENDICULARITY_TOLERANCE", ToleranceKind::Perpendicularity),
+    ("POSITION_TOLERANCE", ToleranceKind::Position),
+    ("ROUNDNESS_TOLERANCE", ToleranceKind::Roundness),
+    ("STRAIGHTNESS_TOLERANCE", ToleranceKind::Straightness),
+    ("SURFACE_PROFILE_TOLERANCE", ToleranceKind::SurfaceProfile),
+    ("SYMMETRY_TOLERANCE", ToleranceKind::Symmetry),
+    ("TOTAL_RUNOUT_TOLERANCE", ToleranceKind::TotalRunout),
+];
+
+/// Entity names of dimensions, and whether each one is a size (rather than
+/// a location) and angular
+const DIMENSIONS: &[(&str, DimensionKind, bool)] = &[
+    ("DIMENSIONAL_SIZE", DimensionKind::Size, false),
+    ("DIMENSIONAL_SIZE_WITH_PATH", DimensionKind::Size, false),
+    ("ANGULAR_SIZE", DimensionKind::Size, true),
+    ("DIMENSIONAL_LOCATION", DimensionKind::Location, false),
+    ("DIMENSIONAL_LOCATION_WITH_PATH", DimensionKind::Location, false),
+    ("ANGULAR_LOCATION", DimensionKind::Location, true),
+];
+
+/// Name of the item which holds a dimension's nominal value, as given by
+/// the CAx-IF recommended practices
+const NOMINAL_NAME: &str = "nominal value";
+
+/// A value with the entity ID of its unit, which isn't converted
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Measure {
+    pub value: f64,
+    pub unit: Option<usize>,
+}
+
+/// The type of a geometric tolerance
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ToleranceKind {
+    Angularity,
+    CircularRunout,
+    Coaxiality,
+    Concentricity,
+    Cylindricity,
+    Flatness,
+    LineProfile,
+    Parallelism,
+    Perpendicularity,
+    Position,
+    Roundness,
+    Straightness,
+    SurfaceProfile,
+    Symmetry,
+    TotalRunout,
+}
+
+/// Whether a dimension measures the size of one feature or the distance
+/// (or angle) between two
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DimensionKind {
+    Size,
+    Location,
+}
+
+/// A dimension, with its nominal value and plus/minus tolerance if they're
+/// given
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dimension {
+    /// ID of the `DIMENSIONAL_SIZE` or `DIMENSIONAL_LOCATION`
+    pub id: usize,
+    pub name: String,
+    pub kind: DimensionKind,
+    pub angular: bool,
+    pub nominal: Option<Measure>,
+    /// Lower and upper deviations from the nominal value
+    pub tolerance: Option<(Measure, Measure)>,
+    /// Faces (or other geometry) of the measured feature, or of the feature
+    /// which a location is measured from
+    pub faces: Vec<usize>,
+    /// Faces of the feature which a location is measured to (empty for a
+    /// size)
+    pub to_faces: Vec<usize>,
+}
+
+/// A datum, with the faces of the datum features which define it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Datum {
+    /// ID of the `DATUM`
+    pub id: usize,
+    /// Identification letter, e.g. `"A"`
+    pub label: String,
+    pub faces: Vec<usize>,
+}
+
+/// A geometric tolerance (e.g. flatness or position) on some faces
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeometricTolerance {
+    pub id: usize,
+    pub name: String,
+    pub kind: ToleranceKind,
+    pub magnitude: Option<Measure>,
+    /// Labels of the referenced datums, in order of precedence.  A common
+    /// datum (e.g. `"A-B"`) has its labels joined with dashes.
+    pub datums: Vec<String>,
+    /// Faces (or other geometry) of the toleranced feature
+    pub faces: Vec<usize>,
+}
+
+/// Semantic product manufacturing information: dimensions, datums, and
+/// geometric tolerances, without their presentation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pmi {
+    pub dimensions: Vec<Dimension>,
+    pub datums: Vec<Datum>,
+    pub tolerances: Vec<GeometricTolerance>,
+}
+
+impl Pmi {
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_empty() && self.datums.is_empty() &&
+            self.tolerances.is_empty()
+    }
+}
+
+/// Instances by ID, along with the links between shape aspects and the
+/// geometry which they stand for
+struct Index<'a> {
+    by_id: HashMap<usize, &'a Record>,
+    /// Geometry of each shape aspect, from `GEOMETRIC_ITEM_SPECIFIC_USAGE`s
+    usage: HashMap<usize, Vec<usize>>,
+    /// Related aspects of each relating aspect, from
+    /// `SHAPE_ASPECT_RELATIONSHIP`s (e.g. the parts of a composite aspect,
+    /// or the datum which a datum feature defines)
+    related: HashMap<usize, Vec<usize>>,
+    /// The reverse of `related`
+    relating: HashMap<usize, Vec<usize>>,
+}
+
+impl<'a> Index<'a> {
+    fn new(records: &'a [Record]) -> Self {
+        let mut out = Self {
+            by_id: records.iter().map(|r| (r.id, r)).collect(),
+            usage: HashMap::new(),
+            related: HashMap::new(),
+            relating: HashMap::new(),
+        };
+        for r in records {
+            for (name, ps) in &r.parts {
+                match (name.as_str(), ps.as_slice()) {
+                    ("GEOMETRIC_ITEM_SPECIFIC_USAGE",
+                     [_, _, Param::Ref(a), _, Param::Ref(g)]) =>
+                        out.usage.entry(*a).or_default().push(*g),
+                    ("SHAPE_ASPECT_RELATIONSHIP",
+                     [_, _, Param::Ref(a), Param::Ref(b)]) => {
+                        out.related.entry(*a).or_default().push(*b);
+                        out.relating.entry(*b).or_default().push(*a);
+                    },
+                    _ => (),
+                }
+            }
+        }
+        out
+    }
+
+    fn part(&self, id: usize, name: &str) -> Option<&'a [Param]> {
+        self.by_id.get(&id)
+            .and_then(|r| r.parts.iter().find(|p| p.0 == name))
+            .map(|p| p.1.as_slice())
+    }
+
+    /// Finds the geometry of a shape aspect, including that of any aspects
+    /// which it's made from
+    fn faces(&self, aspect: usize) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut seen = HashSet::new();
+        let mut todo = vec![aspect];
+        while let Some(a) = todo.pop() {
+            if !seen.insert(a) {
+                continue;
+            }
+            for g in self.usage.get(&a).into_iter().flatten() {
+                if !out.contains(g) {
+                    out.push(*g);
+                }
+            }
+            todo.extend(self.related.get(&a).into_iter().flatten());
+        }
+        out.sort_unstable();
+        out
+    }
+
+    /// Reads a measure, which is either inline (`LENGTH_MEASURE(1.)`) or a
+    /// reference to a measure with a unit, possibly in a complex instance
+    fn measure(&self, p: &Param) -> Option<Measure> {
+        let r = match p {
+            Param::Typed(_, v) => return real(v)
+                .map(|value| Measure { value, unit: None }),
+            Param::Ref(r) => self.by_id.get(r)?,
+            _ => return None,
+        };
+        r.parts.iter().find_map(|(_, ps)| {
+            let i = ps.iter().position(|p| matches!(p, Param::Typed(..)))?;
+            let value = match &ps[i] {
+                Param::Typed(_, v) => real(v)?,
+                _ => return None,
+            };
+            Some(Measure { value, unit: ref_at(ps, i + 1) })
+        })
+    }
+
+    /// Reads the measure of the item in a representation (by ID) with the
+    /// given name, or of its first item if none has that name
+    fn item_named(&self, rep: usize, name: &str) -> Option<Measure> {
+        let items = self.by_id.get(&rep)?.parts.iter()
+            .find_map(|p| match p.1.get(1) {
+                Some(Param::List(items)) => Some(items),
+                _ => None,
+            })?;
+        let named = items.iter().find(|i| match i {
+            Param::Ref(i) => self.by_id.get(i).is_some_and(|r| r.parts.iter()
+                .any(|p| matches!(p.1.first(), Some(Param::String(s))
+                                  if s.eq_ignore_ascii_case(name)))),
+            _ => false,
+        });
+        named.or_else(|| items.first()).and_then(|i| self.measure(i))
+    }
+
+    /// Reads the labels of the datums referenced by a tolerance, from
+    /// `DATUM_REFERENCE`s (AP214) or a `DATUM_SYSTEM` (AP242)
+    fn datum_labels(&self, refs: &[Param]) -> Vec<String> {
+        let mut out: Vec<(i64, String)> = Vec::new();
+        for r in refs.iter().filter_map(|r| match r {
+            Param::Ref(r) => Some(*r),
+            _ => None,
+        }) {
+            if let Some([Param::Integer(p), Param::Ref(d)]) =
+                self.part(r, "DATUM_REFERENCE")
+            {
+                if let Some(label) = self.datum_label(*d) {
+                    out.push((*p, label));
+                }
+            } else if let Some(Param::List(cs)) = self.part(r, "DATUM_SYSTEM")
+                .and_then(|ps| ps.get(4))
+            {
+                for c in cs {
+                    let base = match c {
+                        Param::Ref(c) => self.part(*c, "DATUM_REFERENCE_COMPARTMENT")
+                            .and_then(|ps| ps.get(4)),
+                        _ => None,
+                    };
+                    if let Some(label) = base.and_then(|b| self.base_label(b)) {
+                        out.push((out.len() as i64 + 1, label));
+                    }
+                }
+            }
+        }
+        out.sort_by_key(|(p, _)| *p);
+        out.into_iter().map(|(_, label)| label).collect()
+    }
+
+    /// Reads the label of a datum compartment's base, which is either a
+    /// datum or a list of `DATUM_REFERENCE_ELEMENT`s for a common datum
+    fn base_label(&self, base: &Param) -> Option<String> {
+        match base {
+            Param::Ref(d) => self.datum_label(*d),
+            Param::List(es) => {
+                let labels: Vec<String> = es.iter()
+                    .filter_map(|e| match e {
+                        Param::Ref(e) => self.part(*e, "DATUM_REFERENCE_ELEMENT")
+                            .and_then(|ps| ps.get(4))
+                            .and_then(|b| self.base_label(b)),
+                        _ => None,
+                    })
+                    .collect();
+                if labels.is_empty() { None } else { Some(labels.join("-")) }
+            },
+            _ => None,
+        }
+    }
+
+    fn datum_label(&self, datum: usize) -> Option<String> {
+        match self.part(datum, "DATUM")?.get(4) {
+            Some(Param::String(s)) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Reads dimensions, datums, and geometric tolerances from a file's
+/// instances (e.g. from [`records`](crate::raw::records)), in both simple
+/// and complex instances.  Each one is linked to geometry through its shape
+/// aspect's `GEOMETRIC_ITEM_SPECIFIC_USAGE`s.  Dimensions and tolerances
+/// whose values are missing are still returned, with their values as
+/// `None`.
+pub fn pmi(records: &[Record]) -> Pmi {
+    let index = Index::new(records);
+    let mut out = Pmi::default();
+
+    // Nominal values and plus/minus tolerances, by dimension
+    let mut nominal = HashMap::new();
+    let mut tolerance = HashMap::new();
+    for r in records {
+        if let Some([Param::Ref(d), Param::Ref(rep)]) =
+            index.part(r.id, "DIMENSIONAL_CHARACTERISTIC_REPRESENTATION")
+        {
+            if let Some(m) = index.item_named(*rep, NOMINAL_NAME) {
+                nominal.insert(*d, m);
+            }
+        }
+        if let Some([Param::Ref(range), Param::Ref(d)]) =
+            index.part(r.id, "PLUS_MINUS_TOLERANCE")
+        {
+            if let Some([lower, upper]) = index.part(*range, "TOLERANCE_VALUE") {
+                if let (Some(lower), Some(upper)) =
+                    (index.measure(lower), index.measure(upper))
+                {
+                    tolerance.insert(*d, (lower, upper));
+                }
+            }
+        }
+    }
+
+    for r in records {
+        for (name, kind, angular) in DIMENSIONS {
+            let ps = match index.part(r.id, name) {
+                Some(ps) => ps,
+                None => continue,
+            };
+            // Sizes are (applies_to, name); locations are (name,
+            // description, relating, related)
+            let (label, from, to) = match kind {
+                DimensionKind::Size => (ps.get(1), ref_at(ps, 0), None),
+                DimensionKind::Location =>
+                    (ps.first(), ref_at(ps, 2), ref_at(ps, 3)),
+            };
+            out.dimensions.push(Dimension {
+                id: r.id,
+                name: string(label),
+                kind: *kind,
+                angular: *angular,
+                nominal: nominal.get(&r.id).copied(),
+                tolerance: tolerance.get(&r.id).copied(),
+                faces: from.map(|a| index.faces(a)).unwrap_or_default(),
+                to_faces: to.map(|a| index.faces(a)).unwrap_or_default(),
+            });
+            break;
+        }
+
+        if let Some(label) = index.datum_label(r.id) {
+            let mut faces: Vec<usize> = index.relating.get(&r.id)
+                .into_iter()
+                .flatten()
+                .flat_map(|f| index.faces(*f))
+                .collect();
+            faces.sort_unstable();
+            faces.dedup();
+            out.datums.push(Datum { id: r.id, label, faces });
+        }
+
+        let kind = match TOLERANCES.iter()
+            .find(|(n, _)| r.parts.iter().any(|p| p.0 == *n))
+        {
+            Some((name, kind)) => (name, *kind),
+            None => continue,
+        };
+        // A complex instance holds the common attributes in its
+        // GEOMETRIC_TOLERANCE part, while a simple instance has them all
+        let simple = index.part(r.id, kind.0).unwrap_or_default();
+        let ps = index.part(r.id, "GEOMETRIC_TOLERANCE").unwrap_or(simple);
+        let refs = match index.part(r.id, "GEOMETRIC_TOLERANCE_WITH_DATUM_REFERENCE") {
+            Some([Param::List(refs)]) => refs.as_slice(),
+            _ => match simple.get(4) {
+                Some(Param::List(refs)) => refs.as_slice(),
+                _ => &[],
+            },
+        };
+        out.tolerances.push(GeometricTolerance {
+            id: r.id,
+            name: string(ps.first()),
+            kind: kind.1,
+            magnitude: ps.get(2).and_then(|m| index.measure(m)),
+            datums: index.datum_labels(refs),
+            faces: ref_at(ps, 3).map(|a| index.faces(a)).unwrap_or_default(),
+        });
+    }
+    out
+}
+
+fn ref_at(ps: &[Param], i: usize) -> Option<usize> {
+    match ps.get(i) {
+        Some(Param::Ref(r)) => Some(*r),
+        _ => None,
+    }
+}
+
+fn string(p: Option<&Param>) -> String {
+    match p {
+        Some(Param::String(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+fn real(p: &Param) -> Option<f64> {
+    match p {
+        Param::Real(v) => Some(*v),
+        Param::Integer(i) => Some(*i as f64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{raw::records, step_file::StepFile};
+
+    #[test]
+    fn test_pmi() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=PRODUCT_DEFINITION_SHAPE('','',$);
+#2=ADVANCED_FACE('top',(),$,.T.);
+#3=ADVANCED_FACE('bottom',(),$,.T.);
+#4=ADVANCED_FACE('hole',(),$,.T.);
+#5=SHAPE_REPRESENTATION('',(#2,#3,#4),$);
+#10=SHAPE_ASPECT('top','',#1,.T.);
+#11=SHAPE_ASPECT('bottom','',#1,.T.);
+#12=SHAPE_ASPECT('hole','',#1,.T.);
+#13=GEOMETRIC_ITEM_SPECIFIC_USAGE('','',#10,#5,#2);
+#14=GEOMETRIC_ITEM_SPECIFIC_USAGE('','',#11,#5,#3);
+#15=GEOMETRIC_ITEM_SPECIFIC_USAGE('','',#12,#5,#4);
+#20=DATUM_FEATURE('','',#1,.T.);
+#21=GEOMETRIC_ITEM_SPECIFIC_USAGE('','',#20,#5,#3);
+#22=DATUM('','',#1,.F.,'A');
+#23=SHAPE_ASPECT_RELATIONSHIP('','',#20,#22);
+#24=DATUM_REFERENCE(1,#22);
+#25=DATUM('','',#1,.F.,'B');
+#26=DATUM_REFERENCE_COMPARTMENT('','',#1,.F.,#25,$);
+#27=DATUM_REFERENCE_COMPARTMENT('','',#1,.F.,#22,$);
+#28=DATUM_SYSTEM('','',#1,.F.,(#27,#26));
+#30=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+#31=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(0.05),#30);
+#32=FLATNESS_TOLERANCE('flat','',#31,#10);
+#33=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(0.1),#30);
+#34=(GEOMETRIC_TOLERANCE('pos','',#33,#12)GEOMETRIC_TOLERANCE_WITH_DATUM_REFERENCE((#24))POSITION_TOLERANCE());
+#35=PERPENDICULARITY_TOLERANCE('perp','',#33,#12,(#28));
+#40=DIMENSIONAL_SIZE(#12,'diameter');
+#41=(LENGTH_MEASURE_WITH_UNIT()MEASURE_REPRESENTATION_ITEM()MEASURE_WITH_UNIT(LENGTH_MEASURE(10.),#30)REPRESENTATION_ITEM('nominal value'));
+#42=SHAPE_DIMENSION_REPRESENTATION('',(#41),$);
+#43=DIMENSIONAL_CHARACTERISTIC_REPRESENTATION(#40,#42);
+#44=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(-0.1),#30);
+#45=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(0.2),#30);
+#46=TOLERANCE_VALUE(#44,#45);
+#47=PLUS_MINUS_TOLERANCE(#46,#40);
+#50=DIMENSIONAL_LOCATION('distance','',#10,#11);
+#51=MEASURE_REPRESENTATION_ITEM('distance',LENGTH_MEASURE(20.),#30);
+#52=SHAPE_DIMENSION_REPRESENTATION('',(#51),$);
+#53=DIMENSIONAL_CHARACTERISTIC_REPRESENTATION(#50,#52);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let pmi = pmi(&records(&flat));
+        let mm = |value| Some(Measure { value, unit: Some(30) });
+
+        assert_eq!(pmi.datums, [
+            Datum { id: 22, label: "A".to_owned(), faces: vec![3] },
+            Datum { id: 25, label: "B".to_owned(), faces: vec![] },
+        ]);
+
+        assert_eq!(pmi.tolerances.len(), 3);
+        assert_eq!(pmi.tolerances[0], GeometricTolerance {
+            id: 32,
+            name: "flat".to_owned(),
+            kind: ToleranceKind::Flatness,
+            magnitude: mm(0.05),
+            datums: vec![],
+            faces: vec![2],
+        });
+        assert_eq!(pmi.tolerances[1].kind, ToleranceKind::Position);
+        assert_eq!(pmi.tolerances[1].magnitude, mm(0.1));
+        assert_eq!(pmi.tolerances[1].datums, ["A"]);
+        assert_eq!(pmi.tolerances[1].faces, [4]);
+        assert_eq!(pmi.tolerances[2].kind, ToleranceKind::Perpendicularity);
+        assert_eq!(pmi.tolerances[2].datums, ["A", "B"]);
+
+        assert_eq!(pmi.dimensions, [
+            Dimension {
+                id: 40,
+                name: "diameter".to_owned(),
+                kind: DimensionKind::Size,
+                angular: false,
+                nominal: mm(10.0),
+                tolerance: Some((mm(-0.1).unwrap(), mm(0.2).unwrap())),
+                faces: vec![4],
+                to_faces: vec![],
+            },
+            Dimension {
+                id: 50,
+                name: "distance".to_owned(),
+                kind: DimensionKind::Location,
+                angular: false,
+                nominal: mm(20.0),
+                tolerance: None,
+                faces: vec![2],
+                to_faces: vec![3],
+            },
+        ]);
+        assert!(!pmi.is_empty());
+    }
+}
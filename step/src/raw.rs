@@ -0,0 +1,208 @@
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{char, digit1},
+    combinator::{map, map_res},
+    multi::{many1, separated_list0},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+};
+
+use crate::{
+    merge::collect_refs,
+    parse::{nom_alt_err, parse_enum_tag, IResult, Parse},
+    step_file::StepFile,
+    writer::Param,
+};
+
+/// An entity instance from the DATA section, read without a compiled-in
+/// schema.  This is slower and bulkier than [`StepFile`], but works for any
+/// schema, e.g. when checking a file against rules which are loaded at
+/// runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub id: usize,
+    /// Entity names (as written in the file) and their parameters.  A
+    /// simple instance has one part; an instance in the external mapping
+    /// (`#1=(A()B(1.0));`) has one part per entity type, each holding only
+    /// the attributes which that entity declares.
+    pub parts: Vec<(String, Vec<Param>)>,
+}
+
+impl Record {
+    /// Returns the IDs of every instance which this one refers to, in order
+    /// (with repeats)
+    pub fn refs(&self) -> Vec<usize> {
+        let mut out = Vec::new();
+        for (_, ps) in &self.parts {
+            collect_refs(ps, &mut out);
+        }
+        out
+    }
+}
+
+fn id(s: &str) -> IResult<'_, usize> {
+    map_res(preceded(char('#'), digit1), |s: &str| s.parse())(s)
+}
+
+fn keyword(s: &str) -> IResult<'_, &str> {
+    take_while1(|c: char| c == '_' || c.is_ascii_alphanumeric())(s)
+}
+
+/// Parses a string literal, replacing escaped quotes (`''`)
+fn string(s: &str) -> IResult<'_, String> {
+    let (mut s, _) = char('\'')(s)?;
+    let mut out = String::new();
+    loop {
+        match s.find('\'') {
+            None => return nom_alt_err(s),
+            Some(i) => {
+                out.push_str(&s[..i]);
+                s = &s[i + 1..];
+                if s.starts_with('\'') {
+                    out.push('\'');
+                    s = &s[1..];
+                } else {
+                    return Ok((s, out));
+                }
+            },
+        }
+    }
+}
+
+/// Parses a number, which is a real if it has a decimal point or exponent
+fn number(s: &str) -> IResult<'_, Param> {
+    let (rest, x) = f64::parse(s)?;
+    let text = &s[..s.len() - rest.len()];
+    if crate::real::is_real(text) {
+        Ok((rest, Param::Real(x)))
+    } else {
+        match text.parse() {
+            Ok(i) => Ok((rest, Param::Integer(i))),
+            Err(_) => Ok((rest, Param::Real(x))),
+        }
+    }
+}
+
+fn param(s: &str) -> IResult<'_, Param> {
+    alt((
+        map(char('$'), |_| Param::Unset),
+        map(char('*'), |_| Param::Derived),
+        map(id, Param::Ref),
+        map(string, Param::String),
+        // Binary literals are kept as their hex digits
+        map(delimited(char('"'), take_while1(|c: char| c != '"'), char('"')),
+            Param::str),
+        map(parse_enum_tag, |e| match e {
+            "T" | "TRUE" => Param::Logical(Some(true)),
+            "F" | "FALSE" => Param::Logical(Some(false)),
+            "U" | "UNKNOWN" => Param::Logical(None),
+            e => Param::enum_(e),
+        }),
+        map(delimited(char('('), separated_list0(char(','), param), char(')')),
+            Param::List),
+        map(pair(keyword, delimited(char('('), param, char(')'))),
+            |(t, p)| Param::typed(t, p)),
+        number,
+    ))(s)
+}
+
+fn part(s: &str) -> IResult<'_, (String, Vec<Param>)> {
+    map(pair(keyword, delimited(char('('),
+                                separated_list0(char(','), param),
+                                char(')'))),
+        |(name, params)| (name.to_owned(), params))(s)
+}
+
+pub(crate) fn record(s: &str) -> IResult<'_, Record> {
+    map(tuple((
+            id,
+            char('='),
+            terminated(alt((
+                delimited(char('('), many1(part), char(')')),
+                map(part, |p| vec![p]))),
+                char(';')))),
+        |(id, _, parts)| Record { id, parts })(s)
+}
+
+/// Reads every instance from the DATA section of a file which has been
+/// preprocessed by [`StepFile::strip_flatten`].  Instances which can't be
+/// parsed are skipped.
+///
+/// ```
+/// use step::{raw::records, step_file::StepFile, writer::Param};
+///
+/// let data = b"ISO-10303-21;HEADER;ENDSEC;DATA;
+/// #5=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+/// ENDSEC;END-ISO-10303-21;";
+/// let r = records(&StepFile::strip_flatten(data));
+/// assert_eq!(r[0].id, 5);
+/// assert_eq!(r[0].parts[2].0, "SI_UNIT");
+/// assert_eq!(r[0].parts[2].1[1], Param::enum_("METRE"));
+/// ```
+pub fn records(data: &[u8]) -> Vec<Record> {
+    StepFile::data_blocks(data).into_iter()
+        .filter_map(|b| std::str::from_utf8(b).ok())
+        .filter_map(|b| record(b).ok().map(|r| r.1))
+        .collect()
+}
+
+/// Reads the entities from the HEADER section (e.g. `FILE_NAME` and
+/// `FILE_SCHEMA`) of a file which has been preprocessed by
+/// [`StepFile::strip_flatten`].  These have no IDs, so each is returned as
+/// a name and its parameters.
+pub fn header(data: &[u8]) -> Vec<(String, Vec<Param>)> {
+    header_entities(&StepFile::header_blocks(data))
+}
+
+/// Parses header entities from the HEADER section's blocks
+pub(crate) fn header_entities(blocks: &[&[u8]]) -> Vec<(String, Vec<Param>)> {
+    blocks.iter()
+        .filter_map(|b| std::str::from_utf8(b).ok())
+        .filter_map(|b| terminated(part, char(';'))(b).ok().map(|r| r.1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records() {
+        let data = b"ISO-10303-21;
+HEADER;
+FILE_NAME('a.step','',(''),(''),'','','');
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('it''s',(1.,-2,3.E1,-.5,1D+2));
+#2=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+#3=MEASURE_WITH_UNIT(LENGTH_MEASURE(2.5),#2);
+#4=SURFACE_CURVE($,(#1,#2),.U.);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let rs = records(&flat);
+        assert_eq!(rs.len(), 4);
+        assert_eq!(rs[0].parts, [("CARTESIAN_POINT".to_owned(), vec![
+            Param::str("it's"),
+            Param::List(vec![Param::Real(1.0), Param::Integer(-2),
+                             Param::Real(30.0), Param::Real(-0.5),
+                             Param::Real(100.0)])])]);
+        let names: Vec<&str> = rs[1].parts.iter()
+            .map(|p| p.0.as_str())
+            .collect();
+        assert_eq!(names, ["LENGTH_UNIT", "NAMED_UNIT", "SI_UNIT"]);
+        assert_eq!(rs[1].parts[1].1, [Param::Derived]);
+        assert_eq!(rs[1].parts[2].1, [Param::enum_("MILLI"),
+                                      Param::enum_("METRE")]);
+        assert_eq!(rs[2].parts[0].1, [
+            Param::typed("LENGTH_MEASURE", Param::Real(2.5)),
+            Param::Ref(2)]);
+        assert_eq!(rs[3].parts[0].1, [
+            Param::Unset, Param::refs(&[1, 2]), Param::Logical(None)]);
+
+        let h = header(&flat);
+        assert_eq!(h.len(), 1);
+        assert_eq!(h[0].0, "FILE_NAME");
+        assert_eq!(h[0].1[0], Param::str("a.step"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Parses real numbers as they appear in STEP files.
+//!
+//! ISO 10303-21 writes reals as `[+-]digits.[digits][E[+-]digits]`, but
+//! exporters in the wild also leave out the leading digits (`-.5`) or use
+//! a Fortran-style `D` exponent (`1D+2`).  Every real in this crate goes
+//! through [`parse_partial`], so they're all read the same way.  Parsing
+//! never depends on the platform's locale: the decimal separator is always
+//! `.`.
+
+/// Returns the length of the longest prefix of `s` which looks like a number,
+/// and whether its exponent is marked with `D` or `d`
+fn scan(s: &[u8]) -> Option<(usize, bool)> {
+    let digits = |i: usize| s[i..].iter().take_while(|c| c.is_ascii_digit())
+        .count();
+    let mut i = 0;
+    if matches!(s.first(), Some(b'+' | b'-')) {
+        i += 1;
+    }
+    let int = digits(i);
+    i += int;
+    let mut frac = 0;
+    if s.get(i) == Some(&b'.') {
+        frac = digits(i + 1);
+        i += 1 + frac;
+    }
+    if int + frac == 0 {
+        return None;
+    }
+    // The exponent is only consumed if it's complete, so that `1E` parses
+    // as `1` followed by `E`
+    let mut fortran = false;
+    if let Some(c @ (b'E' | b'e' | b'D' | b'd')) = s.get(i) {
+        let mut j = i + 1;
+        if matches!(s.get(j), Some(b'+' | b'-')) {
+            j += 1;
+        }
+        let n = digits(j);
+        if n > 0 {
+            fortran = matches!(c, b'D' | b'd');
+            i = j + n;
+        }
+    }
+    Some((i, fortran))
+}
+
+/// Parses a real from the start of `s`, returning its value and the number
+/// of bytes which were used.  Integers are accepted too (as in STEP, where
+/// a real attribute may be written `1`); `inf`, `nan`, and hexadecimal
+/// floats are not.
+pub fn parse_partial(s: &str) -> Option<(f64, usize)> {
+    let (n, fortran) = scan(s.as_bytes())?;
+    let text = &s[..n];
+    let x = if fortran {
+        let text = text.replace(&['D', 'd'][..], "E");
+        fast_float::parse(&text).ok().or_else(|| text.parse().ok())
+    } else {
+        fast_float::parse(text).ok().or_else(|| text.parse().ok())
+    };
+    x.map(|x| (x, n))
+}
+
+/// Parses a whole string (ignoring surrounding whitespace) as a real
+pub fn parse(s: &str) -> Option<f64> {
+    let s = s.trim();
+    match parse_partial(s) {
+        Some((x, n)) if n == s.len() => Some(x),
+        _ => None,
+    }
+}
+
+/// Checks whether a number's text is written as a real (with a decimal
+/// point or an exponent), rather than an integer
+pub fn is_real(text: &str) -> bool {
+    text.contains(&['.', 'E', 'e', 'D', 'd'][..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_real() {
+        for (text, x) in &[
+            ("0.", 0.0), ("1.", 1.0), ("-1.", -1.0), ("+2.5", 2.5),
+            ("1.E-7", 1e-7), ("1.e-7", 1e-7), ("1.E7", 1e7), ("1.E+07", 1e7),
+            ("-.5", -0.5), (".5", 0.5), ("+.25E1", 2.5),
+            ("1D+2", 100.0), ("1.5d-1", 0.15), ("-2.D3", -2000.0),
+            ("12", 12.0), ("-0.", 0.0),
+            ("1.7976931348623157E308", f64::MAX),
+            ("4.9406564584124654E-324", 5e-324),
+            ("0.1000000000000000055511151231257827", 0.1),
+        ] {
+            assert_eq!(parse(text), Some(*x), "{}", text);
+        }
+        assert!(parse("-0.").unwrap().is_sign_negative());
+        assert_eq!(parse("1.E999"), Some(f64::INFINITY));
+
+        for text in &["", ".", "-", "+.", "E5", ".E5", "inf", "NaN",
+                      "infinity", "0x1p3", "1,5", "1.5.", "1..5", "--1"]
+        {
+            assert_eq!(parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_partial() {
+        assert_eq!(parse_partial("1.5,2.5)"), Some((1.5, 3)));
+        assert_eq!(parse_partial("1D+2)"), Some((100.0, 4)));
+        // Incomplete exponents are left for the caller
+        assert_eq!(parse_partial("1E)"), Some((1.0, 1)));
+        assert_eq!(parse_partial("2.D+"), Some((2.0, 2)));
+        assert_eq!(parse_partial("3.0DEG"), Some((3.0, 3)));
+        assert_eq!(parse_partial("1,5"), Some((1.0, 1)));
+        assert_eq!(parse_partial(".T."), None);
+    }
+
+    #[test]
+    fn test_is_real() {
+        assert!(is_real("1."));
+        assert!(is_real("1E5"));
+        assert!(is_real("1D5"));
+        assert!(!is_real("-15"));
+    }
+
+    #[test]
+    fn test_exhaustive_roundtrip() {
+        // Every f64 written in each of the styles which exporters use
+        let mut x = 1.0f64;
+        let mut values = vec![0.0, -0.0, f64::MIN_POSITIVE, f64::EPSILON];
+        for _ in 0..200 {
+            values.push(x);
+            values.push(-x / 3.0);
+            x *= 7.3;
+        }
+        for x in values {
+            for text in &[format!("{:?}", x), format!("{:E}", x),
+                          format!("{:e}", x).replace('e', "D")]
+            {
+                assert_eq!(parse(text).map(f64::to_bits), Some(x.to_bits()),
+                           "{}", text);
+            }
+        }
+    }
+}
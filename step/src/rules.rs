@@ -0,0 +1,1929 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use express::{
+    parse::*,
+    resolve::{resolve, Decl, SymbolTable},
+};
+
+use crate::{raw::Record, writer::Param};
+
+/// Limit on nested function calls and derived attributes, which stops
+/// runaway recursion
+const MAX_DEPTH: usize = 64;
+
+/// Limit on statements executed while evaluating a single rule, which stops
+/// loops that never terminate
+const MAX_STEPS: usize = 1_000_000;
+
+/// A value computed while evaluating a rule
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// An unset attribute, `?`, or anything that couldn't be evaluated
+    Indeterminate,
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Logical(Option<bool>),
+    /// Enumeration item, in lowercase
+    Enum(String),
+    /// Entity instance, by ID
+    Instance(usize),
+    Aggregate(Vec<Value>),
+    /// Value of a defined type which is written out explicitly in the file,
+    /// e.g. `LENGTH_MEASURE(1.0)` as a member of a select.  The type name is
+    /// in lowercase.
+    Typed(String, Box<Value>),
+}
+
+impl Value {
+    fn from_param(p: &Param) -> Self {
+        match p {
+            Param::Unset | Param::Derived => Value::Indeterminate,
+            Param::Ref(i) => Value::Instance(*i),
+            Param::Integer(i) => Value::Integer(*i),
+            Param::Real(f) => Value::Real(*f),
+            Param::String(s) => Value::String(s.clone()),
+            Param::Enum(e) => Value::Enum(e.to_lowercase()),
+            Param::Logical(b) => Value::Logical(*b),
+            Param::List(v) => Value::Aggregate(
+                v.iter().map(Value::from_param).collect()),
+            Param::Typed(t, p) => Value::Typed(
+                t.to_lowercase(), Box::new(Value::from_param(p))),
+        }
+    }
+
+    /// Strips any defined types from the value
+    fn plain(&self) -> &Value {
+        match self {
+            Value::Typed(_, v) => v.plain(),
+            v => v,
+        }
+    }
+
+    pub fn is_indeterminate(&self) -> bool {
+        matches!(self.plain(), Value::Indeterminate)
+    }
+
+    fn number(&self) -> Option<f64> {
+        match self.plain() {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Real(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    fn integer(&self) -> Option<i64> {
+        match self.plain() {
+            Value::Integer(i) => Some(*i),
+            Value::Real(f) if f.fract() == 0.0 => Some(*f as i64),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a logical, treating anything which isn't a
+    /// logical as `UNKNOWN`
+    fn truth(&self) -> Option<bool> {
+        match self.plain() {
+            Value::Logical(b) => *b,
+            _ => None,
+        }
+    }
+
+    fn elements(&self) -> Option<&[Value]> {
+        match self.plain() {
+            Value::Aggregate(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the value is (or contains) a particular instance
+    fn refers_to(&self, id: usize) -> bool {
+        match self.plain() {
+            Value::Instance(i) => *i == id,
+            Value::Aggregate(v) => v.iter().any(|v| v.refers_to(id)),
+            _ => false,
+        }
+    }
+}
+
+/// Compares two values for equality, returning `None` if either one is
+/// indeterminate
+fn equal(a: &Value, b: &Value) -> Option<bool> {
+    use Value::*;
+    match (a.plain(), b.plain()) {
+        (Indeterminate, _) | (_, Indeterminate) => None,
+        (Integer(x), Integer(y)) => Some(x == y),
+        (Integer(_), Real(_)) | (Real(_), Integer(_)) | (Real(_), Real(_)) =>
+            Some(a.number() == b.number()),
+        (String(x), String(y)) | (Enum(x), Enum(y)) => Some(x == y),
+        (Logical(x), Logical(y)) => Some(x == y),
+        (Instance(x), Instance(y)) => Some(x == y),
+        (Aggregate(x), Aggregate(y)) if x.len() == y.len() =>
+            all(x.iter().zip(y.iter()).map(|(x, y)| equal(x, y))),
+        _ => Some(false),
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a.plain(), b.plain()) {
+        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        _ => a.number()?.partial_cmp(&b.number()?),
+    }
+}
+
+// Three-valued logic, where `None` is UNKNOWN
+fn and(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+fn or(a: Option<bool>, b: Option<bool>) -> Option<bool> {
+    match (a, b) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    }
+}
+
+fn all<I: Iterator<Item=Option<bool>>>(iter: I) -> Option<bool> {
+    let mut out = Some(true);
+    for b in iter {
+        out = and(out, b);
+    }
+    out
+}
+fn any<I: Iterator<Item=Option<bool>>>(iter: I) -> Option<bool> {
+    let mut out = Some(false);
+    for b in iter {
+        out = or(out, b);
+    }
+    out
+}
+
+/// Implements the `LIKE` operator's pattern matching
+fn like(s: &[char], p: &[char]) -> bool {
+    let (c, rest) = match p.split_first() {
+        None => return s.is_empty(),
+        Some(c) => c,
+    };
+    match c {
+        '&' => true,
+        '*' => (0..=s.len()).any(|i| like(&s[i..], rest)),
+        '$' => (0..=s.len())
+            .filter(|i| *i == s.len() || s[*i] == ' ')
+            .any(|i| like(&s[i..], rest)),
+        '\\' => match rest.split_first() {
+            Some((e, rest)) => s.first() == Some(e) && like(&s[1..], rest),
+            None => false,
+        },
+        c => match s.first() {
+            None => false,
+            Some(x) => {
+                let ok = match c {
+                    '@' => x.is_alphabetic(),
+                    '^' => x.is_uppercase(),
+                    '?' => true,
+                    '#' => x.is_ascii_digit(),
+                    c => c == x,
+                };
+                ok && like(&s[1..], rest)
+            },
+        },
+    }
+}
+
+/// What kind of rule was violated
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RuleKind {
+    /// A `WHERE` rule in an entity declaration
+    Entity,
+    /// A `WHERE` rule in a defined type, checked against an attribute value
+    Type,
+    /// A `UNIQUE` rule in an entity declaration
+    Unique,
+    /// A global `RULE`
+    Global,
+}
+
+/// A rule which evaluated to `FALSE`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    pub kind: RuleKind,
+    /// The instance which breaks the rule, or `None` for a global rule
+    pub instance: Option<usize>,
+    /// The entity, type, or global rule which declares the rule
+    pub scope: String,
+    /// The rule's label, or its position (e.g. `#2`) if it's unlabelled
+    pub rule: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(i) = self.instance {
+            write!(f, "#{}: ", i)?;
+        }
+        let kind = match self.kind {
+            RuleKind::Entity => "entity",
+            RuleKind::Type => "type",
+            RuleKind::Unique => "unique rule in",
+            RuleKind::Global => "rule",
+        };
+        write!(f, "{} {} violates {}", kind, self.scope, self.rule)
+    }
+}
+
+/// Results from [`check_rules`]
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub violations: Vec<Violation>,
+    /// Number of rules which were evaluated, counting each instance
+    /// separately
+    pub checked: usize,
+    /// Number of rules which evaluated to `UNKNOWN` or couldn't be evaluated
+    /// (e.g. because they call a built-in function which isn't supported).
+    /// These aren't counted as violations.
+    pub unknown: usize,
+}
+
+/// Evaluates the `WHERE` and `UNIQUE` rules of every entity and type in a
+/// schema, along with its global rules, against a set of instances.
+///
+/// The syntax tree should be lowered with
+/// [`strip_comments_and_lower`](express::parse::strip_comments_and_lower),
+/// which matches how names are stored in STEP files.  Only rules which
+/// evaluate to `FALSE` are reported as violations.  Returns `None` if the
+/// schema isn't in the syntax tree.
+pub fn check_rules<'a>(syntax: &'a Syntax<'a>, schema: &str, records: &[Record])
+    -> Option<Report>
+{
+    let s = syntax.0.iter()
+        .find(|s| s.id.0.eq_ignore_ascii_case(schema))?;
+    let (table, _) = resolve(syntax);
+    let mut c = Checker::new(table, s.id.0, records);
+    c.instance_rules();
+    c.unique_rules();
+    c.global_rules(s);
+    Some(c.report)
+}
+
+/// Evaluates the attributes of entity instances, including derived and
+/// inverse attributes, using the same interpreter as [`check_rules`]
+pub struct Interpreter<'a, 'r>(Checker<'a, 'r>);
+
+impl<'a, 'r> Interpreter<'a, 'r> {
+    /// Builds an interpreter for a schema (see [`check_rules`] for how the
+    /// syntax tree should be prepared).  Returns `None` if the schema isn't
+    /// in the syntax tree.
+    pub fn new(syntax: &'a Syntax<'a>, schema: &str, records: &'r [Record])
+        -> Option<Self>
+    {
+        let s = syntax.0.iter()
+            .find(|s| s.id.0.eq_ignore_ascii_case(schema))?;
+        let (table, _) = resolve(syntax);
+        Some(Self(Checker::new(table, s.id.0, records)))
+    }
+
+    /// Returns every entity type of an instance, including supertypes, in
+    /// lowercase
+    pub fn types(&self, id: usize) -> &[&'a str] {
+        self.0.instances.get(&id).map(|i| &i.types[..]).unwrap_or(&[])
+    }
+
+    /// Looks up an attribute of an instance by name.  The name may be
+    /// qualified with the entity which declares it (e.g.
+    /// `representation_item.name`), which is needed if a subtype renames or
+    /// hides it.  Returns `None` if the instance doesn't exist or doesn't
+    /// have that attribute.
+    pub fn attribute(&mut self, id: usize, name: &str) -> Option<Value> {
+        let name = name.to_lowercase();
+        let (group, name) = match name.rsplit_once('.') {
+            Some((g, n)) => (Some(g), n),
+            None => (None, name.as_str()),
+        };
+        self.0.steps = 0;
+        self.0.try_attribute(id, group, name)
+    }
+
+    /// Evaluates every derived attribute of an instance, returning each one
+    /// as (declaring entity, attribute, value)
+    pub fn derived(&mut self, id: usize) -> Vec<(&'a str, &'a str, Value)> {
+        let mut out = Vec::new();
+        for t in self.types(id).to_vec() {
+            let derive = self.0.entity(t).and_then(|e| e.1.derive.as_ref());
+            for d in derive.iter().flat_map(|d| d.0.iter()) {
+                self.0.steps = 0;
+                out.push((t, d.0.name(), self.0.derived(id, d)));
+            }
+        }
+        out
+    }
+}
+
+/// Control flow out of a statement
+enum Flow {
+    Next,
+    Escape,
+    Skip,
+    Return(Value),
+}
+
+struct Instance<'a, 'r> {
+    record: &'r Record,
+    /// Every entity type of the instance, including supertypes
+    types: Vec<&'a str>,
+}
+
+struct Checker<'a, 'r> {
+    table: SymbolTable<'a>,
+    schema: &'a str,
+    instances: HashMap<usize, Instance<'a, 'r>>,
+    /// For each instance, the instances which refer to it
+    users: HashMap<usize, Vec<usize>>,
+
+    /// Each entity and its supertypes, with supertypes first
+    lineage: HashMap<&'a str, Vec<&'a str>>,
+    /// Each entity's explicit attributes, in the order used by a simple
+    /// instance of it in a STEP file
+    slots: HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+    /// Constant values, keyed by their declaration's address (since local
+    /// constants in different functions may share a name)
+    constants: HashMap<*const ConstantBody<'a>, Value>,
+
+    decls: Vec<HashMap<&'a str, Decl<'a>>>,
+    scopes: Vec<HashMap<&'a str, Value>>,
+    /// The value of `SELF`
+    this: Value,
+    depth: usize,
+    steps: usize,
+
+    report: Report,
+}
+
+impl<'a, 'r> Checker<'a, 'r> {
+    fn new(table: SymbolTable<'a>, schema: &'a str, records: &'r [Record])
+        -> Self
+    {
+        let mut out = Self {
+            table,
+            schema,
+            instances: HashMap::new(),
+            users: HashMap::new(),
+            lineage: HashMap::new(),
+            slots: HashMap::new(),
+            constants: HashMap::new(),
+            decls: Vec::new(),
+            scopes: Vec::new(),
+            this: Value::Indeterminate,
+            depth: 0,
+            steps: 0,
+            report: Report::default(),
+        };
+        for r in records {
+            let mut types = Vec::new();
+            for (name, _) in &r.parts {
+                for t in out.lineage(&name.to_lowercase()) {
+                    if !types.contains(&t) {
+                        types.push(t);
+                    }
+                }
+            }
+            out.instances.insert(r.id, Instance { record: r, types });
+
+            let mut refs = Vec::new();
+            for p in r.parts.iter().flat_map(|p| p.1.iter()) {
+                Self::collect_refs(p, &mut refs);
+            }
+            for i in refs {
+                let v = out.users.entry(i).or_default();
+                if v.last() != Some(&r.id) {
+                    v.push(r.id);
+                }
+            }
+        }
+        out
+    }
+
+    fn collect_refs(p: &Param, out: &mut Vec<usize>) {
+        match p {
+            Param::Ref(i) => out.push(*i),
+            Param::List(v) => for p in v {
+                Self::collect_refs(p, out);
+            },
+            Param::Typed(_, p) => Self::collect_refs(p, out),
+            _ => (),
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Schema lookups
+
+    fn decl(&self, name: &str) -> Option<Decl<'a>> {
+        self.decls.iter().rev()
+            .find_map(|d| d.get(name).copied())
+            .or_else(|| self.table.lookup(self.schema, name))
+    }
+
+    fn entity(&self, name: &str) -> Option<&'a EntityDecl<'a>> {
+        match self.decl(name) {
+            Some(Decl::Entity(e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns an entity and all of its supertypes, in the order in which
+    /// their attributes appear in a STEP file
+    fn lineage(&mut self, name: &str) -> Vec<&'a str> {
+        let e = match self.entity(name) {
+            Some(e) => e,
+            None => return Vec::new(),
+        };
+        let name = (e.0).0.0;
+        if let Some(v) = self.lineage.get(name) {
+            return v.clone();
+        }
+        let mut out = Vec::new();
+        let mut todo = vec![(e, false)];
+        while let Some((e, done)) = todo.pop() {
+            let n = (e.0).0.0;
+            if done {
+                if !out.contains(&n) {
+                    out.push(n);
+                }
+            } else if !out.contains(&n) && todo.len() < MAX_DEPTH {
+                todo.push((e, true));
+                let supers = ((e.0).1).1.iter().flat_map(|s| s.0.iter());
+                let supers: Vec<_> = supers
+                    .filter_map(|s| self.entity(s.0))
+                    .collect();
+                todo.extend(supers.into_iter().rev().map(|s| (s, false)));
+            }
+        }
+        self.lineage.insert(name, out.clone());
+        out
+    }
+
+    /// Returns the explicit attributes which an entity declares itself
+    fn own_slots(e: &'a EntityDecl<'a>) -> impl Iterator<Item=&'a str> {
+        e.1.explicit_attr.iter()
+            .flat_map(|a| a.attributes.iter())
+            .filter_map(|a| match a {
+                AttributeDecl::Id(i) => Some(i.0),
+                AttributeDecl::Redeclared(_) => None,
+            })
+    }
+
+    fn slots(&mut self, name: &'a str) -> Vec<(&'a str, &'a str)> {
+        if let Some(v) = self.slots.get(name) {
+            return v.clone();
+        }
+        let mut out = Vec::new();
+        for t in self.lineage(name) {
+            if let Some(e) = self.entity(t) {
+                out.extend(Self::own_slots(e).map(|a| (t, a)));
+            }
+        }
+        self.slots.insert(name, out.clone());
+        out
+    }
+
+    fn has_type(&self, id: usize, entity: &str) -> bool {
+        self.instances.get(&id)
+            .map(|i| i.types.contains(&entity))
+            .unwrap_or(false)
+    }
+
+    fn qualified(&self, name: &str) -> String {
+        format!("{}.{}", self.schema, name).to_uppercase()
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Attributes
+
+    /// Reads the explicit attribute `attr`, declared by `owner`, from an
+    /// instance's record
+    fn explicit(&mut self, id: usize, owner: &'a str, attr: &'a str) -> Value {
+        let record = match self.instances.get(&id) {
+            Some(i) => i.record,
+            None => return Value::Indeterminate,
+        };
+        let param = if let [(name, params)] = &record.parts[..] {
+            let name = match self.entity(&name.to_lowercase()) {
+                Some(e) => (e.0).0.0,
+                None => return Value::Indeterminate,
+            };
+            self.slots(name).iter()
+                .position(|s| *s == (owner, attr))
+                .and_then(|i| params.get(i))
+        } else {
+            let e = self.entity(owner);
+            record.parts.iter()
+                .find(|p| p.0.eq_ignore_ascii_case(owner))
+                .zip(e)
+                .and_then(|(p, e)| Self::own_slots(e)
+                    .position(|a| a == attr)
+                    .and_then(|i| p.1.get(i)))
+        };
+        match param {
+            // The value is computed by a subtype which redeclares the
+            // attribute as a derived attribute
+            Some(Param::Derived) => {
+                let types = self.instances[&id].types.clone();
+                for t in types.iter().rev() {
+                    let d = self.entity(t)
+                        .and_then(|e| e.1.derive.as_ref())
+                        .and_then(|d| d.0.iter().find(|d| match &d.0 {
+                            AttributeDecl::Redeclared(r) =>
+                                ((r.0).0).0.0 == owner && ((r.0).1).0.0 == attr,
+                            AttributeDecl::Id(_) => false,
+                        }));
+                    if let Some(d) = d {
+                        return self.derived(id, d);
+                    }
+                }
+                Value::Indeterminate
+            },
+            Some(p) => Value::from_param(p),
+            None => Value::Indeterminate,
+        }
+    }
+
+    fn derived(&mut self, id: usize, d: &'a DerivedAttr<'a>) -> Value {
+        if self.depth >= MAX_DEPTH {
+            return Value::Indeterminate;
+        }
+        self.depth += 1;
+        let scopes = std::mem::take(&mut self.scopes);
+        let this = std::mem::replace(&mut self.this, Value::Instance(id));
+        let out = self.expression(&d.2);
+        self.this = this;
+        self.scopes = scopes;
+        self.depth -= 1;
+        out
+    }
+
+    fn inverse(&mut self, id: usize, i: &'a InverseAttr<'a>) -> Value {
+        let users = self.users.get(&id).cloned().unwrap_or_default();
+        let mut out = Vec::new();
+        for u in users {
+            if self.has_type(u, i.entity.0) {
+                let group = i.entity_for.as_ref().map(|e| e.0);
+                if self.attribute(u, group, i.attribute_ref.0).refers_to(id) {
+                    out.push(Value::Instance(u));
+                }
+            }
+        }
+        match i.bounds {
+            Some(_) => Value::Aggregate(out),
+            None => out.into_iter().next().unwrap_or(Value::Indeterminate),
+        }
+    }
+
+    /// Looks up an attribute of an instance, optionally restricted to the
+    /// attributes of a particular supertype.  Returns `None` if the instance
+    /// doesn't have an attribute with that name.
+    fn try_attribute(&mut self, id: usize, group: Option<&str>, name: &str)
+        -> Option<Value>
+    {
+        let mut types = self.instances.get(&id)?.types.clone();
+        if let Some(g) = group {
+            let lineage = self.lineage(g);
+            types.retain(|t| lineage.contains(t));
+        }
+
+        // Search from the most specific type, so that redeclarations win
+        for t in types.iter().rev() {
+            let e = match self.entity(t) {
+                Some(e) => e,
+                None => continue,
+            };
+            let derived = e.1.derive.iter()
+                .flat_map(|d| d.0.iter())
+                .find(|d| d.0.name() == name);
+            if let Some(d) = derived {
+                return Some(self.derived(id, d));
+            }
+            let inverse = e.1.inverse.iter()
+                .flat_map(|i| i.0.iter())
+                .find(|i| i.attribute_decl.name() == name);
+            if let Some(i) = inverse {
+                return Some(self.inverse(id, i));
+            }
+            let explicit = e.1.explicit_attr.iter()
+                .flat_map(|a| a.attributes.iter())
+                .find(|a| a.name() == name);
+            match explicit {
+                Some(AttributeDecl::Id(a)) =>
+                    return Some(self.explicit(id, t, a.0)),
+                Some(AttributeDecl::Redeclared(r)) => {
+                    let q = &r.0;
+                    return Some(self.explicit(id, ((q.0).0).0, ((q.1).0).0));
+                },
+                None => (),
+            }
+        }
+        None
+    }
+
+    fn attribute(&mut self, id: usize, group: Option<&str>, name: &str)
+        -> Value
+    {
+        self.try_attribute(id, group, name).unwrap_or(Value::Indeterminate)
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Rules
+
+    fn where_clause(&mut self, w: &'a WhereClause<'a>, kind: RuleKind,
+                    instance: Option<usize>, scope: &str)
+    {
+        for (i, r) in w.0.iter().enumerate() {
+            self.steps = 0;
+            let v = self.expression(&r.expression);
+            self.report.checked += 1;
+            match v.truth() {
+                Some(true) => (),
+                Some(false) => self.report.violations.push(Violation {
+                    kind,
+                    instance,
+                    scope: scope.to_owned(),
+                    rule: r.rule_label_id.as_ref()
+                        .map(|r| r.0.to_owned())
+                        .unwrap_or_else(|| format!("#{}", i + 1)),
+                }),
+                None => self.report.unknown += 1,
+            }
+        }
+    }
+
+    fn ids(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.instances.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Checks entity `WHERE` rules, and type `WHERE` rules on the values of
+    /// explicit attributes
+    fn instance_rules(&mut self) {
+        for id in self.ids() {
+            for t in self.instances[&id].types.clone() {
+                let e = match self.entity(t) {
+                    Some(e) => e,
+                    None => continue,
+                };
+                if let Some(w) = &e.1.where_ {
+                    self.this = Value::Instance(id);
+                    self.where_clause(w, RuleKind::Entity, Some(id), t);
+                }
+                for a in &e.1.explicit_attr {
+                    let named = match &a.parameter_type {
+                        ParameterType::Named(_) |
+                        ParameterType::Generalized(_) => &a.parameter_type,
+                        ParameterType::Simple(_) => continue,
+                    };
+                    for d in &a.attributes {
+                        if let AttributeDecl::Id(n) = d {
+                            let v = self.explicit(id, t, n.0);
+                            self.parameter_type(&v, named, id, 0);
+                        }
+                    }
+                }
+            }
+        }
+        self.this = Value::Indeterminate;
+    }
+
+    fn parameter_type(&mut self, v: &Value, p: &'a ParameterType<'a>,
+                      id: usize, depth: usize)
+    {
+        match p {
+            ParameterType::Named(NamedTypes::Type(t)) =>
+                self.type_rules(v, t.0, id, depth),
+            ParameterType::Named(NamedTypes::_Ambiguous(t)) =>
+                self.type_rules(v, t.0, id, depth),
+            ParameterType::Generalized(GeneralizedTypes::GeneralAggregation(g))
+                => {
+                    let p = match g {
+                        GeneralAggregationTypes::Array(a) => &a.parameter_type,
+                        GeneralAggregationTypes::Bag(b) => &b.1,
+                        GeneralAggregationTypes::List(l) => &l.parameter_type,
+                        GeneralAggregationTypes::Set(s) => &s.parameter_type,
+                    };
+                    for e in v.elements().unwrap_or(&[]) {
+                        self.parameter_type(e, p, id, depth + 1);
+                    }
+                },
+            _ => (),
+        }
+    }
+
+    fn instantiable_type(&mut self, v: &Value, t: &'a InstantiableType<'a>,
+                         id: usize, depth: usize)
+    {
+        match t {
+            InstantiableType::Concrete(c) => self.concrete_types(v, c, id, depth),
+            InstantiableType::EntityRef(_) => (),
+        }
+    }
+
+    fn concrete_types(&mut self, v: &Value, c: &'a ConcreteTypes<'a>,
+                      id: usize, depth: usize)
+    {
+        match c {
+            ConcreteTypes::TypeRef(r) => self.type_rules(v, r.0, id, depth),
+            ConcreteTypes::Aggregation(a) => {
+                let t = match a {
+                    AggregationTypes::Array(a) => &a.instantiable_type,
+                    AggregationTypes::Bag(b) => &b.1,
+                    AggregationTypes::List(l) => &l.instantiable_type,
+                    AggregationTypes::Set(s) => &s.instantiable_type,
+                };
+                for e in v.elements().unwrap_or(&[]) {
+                    self.instantiable_type(e, t, id, depth + 1);
+                }
+            },
+            ConcreteTypes::Simple(_) => (),
+        }
+    }
+
+    /// Checks the `WHERE` rules of a defined type (and the types that it's
+    /// built from) against a value, which belongs to instance `id`
+    fn type_rules(&mut self, v: &Value, name: &str, id: usize, depth: usize) {
+        let t = match self.decl(name) {
+            Some(Decl::Type(t)) => t,
+            _ => return,
+        };
+        if v.is_indeterminate() || depth >= MAX_DEPTH {
+            return;
+        }
+        let v = match v {
+            Value::Typed(n, inner) if n == t.type_id.0 => inner.as_ref(),
+            v => v,
+        };
+        match &t.underlying_type {
+            UnderlyingType::Concrete(c) =>
+                self.concrete_types(v, c, id, depth + 1),
+            UnderlyingType::Constructed(ConstructedTypes::Select(_)) =>
+                if let Value::Typed(n, _) = v {
+                    self.type_rules(v, &n.clone(), id, depth + 1);
+                },
+            UnderlyingType::Constructed(ConstructedTypes::Enumeration(_)) => (),
+        }
+        if let Some(w) = &t.where_clause {
+            self.this = v.clone();
+            self.where_clause(w, RuleKind::Type, Some(id), t.type_id.0);
+            self.this = Value::Instance(id);
+        }
+    }
+
+    fn unique_rules(&mut self) {
+        let ids = self.ids();
+        let mut seen: HashSet<&'a str> = HashSet::new();
+        for id in &ids {
+            for t in self.instances[id].types.clone() {
+                let u = match self.entity(t).and_then(|e| e.1.unique.as_ref()) {
+                    Some(u) if seen.insert(t) => u,
+                    _ => continue,
+                };
+                for (i, rule) in u.0.iter().enumerate() {
+                    self.unique_rule(&ids, t, i, rule);
+                }
+            }
+        }
+    }
+
+    fn unique_rule(&mut self, ids: &[usize], entity: &'a str, index: usize,
+                   rule: &'a UniqueRule<'a>)
+    {
+        let mut seen: Vec<(Vec<Value>, usize)> = Vec::new();
+        for id in ids.iter().copied() {
+            if !self.has_type(id, entity) {
+                continue;
+            }
+            let key: Vec<Value> = rule.attrs.iter()
+                .map(|a| match a {
+                    ReferencedAttribute::Ref(r) =>
+                        self.attribute(id, None, r.0),
+                    ReferencedAttribute::Qualified(q) =>
+                        self.attribute(id, Some(((q.0).0).0), ((q.1).0).0),
+                })
+                .collect();
+            self.report.checked += 1;
+            if key.iter().any(|k| k.is_indeterminate()) {
+                self.report.unknown += 1;
+                continue;
+            }
+            let dup = seen.iter().any(|(k, _)| {
+                k.iter().zip(key.iter()).all(|(a, b)| equal(a, b) == Some(true))
+            });
+            if dup {
+                self.report.violations.push(Violation {
+                    kind: RuleKind::Unique,
+                    instance: Some(id),
+                    scope: entity.to_owned(),
+                    rule: rule.label.as_ref()
+                        .map(|r| r.0.to_owned())
+                        .unwrap_or_else(|| format!("#{}", index + 1)),
+                });
+            } else {
+                seen.push((key, id));
+            }
+        }
+    }
+
+    fn global_rules(&mut self, s: &'a SchemaDecl<'a>) {
+        let ids = self.ids();
+        for d in &s.body.declarations {
+            let r = match d {
+                DeclarationOrRuleDecl::RuleDecl(r) => r,
+                DeclarationOrRuleDecl::Declaration(_) => continue,
+            };
+            // Inside a rule, each entity name refers to all of its instances
+            let mut scope = HashMap::new();
+            for e in &r.rule_head.entities {
+                scope.insert(e.0, Value::Aggregate(ids.iter()
+                    .filter(|i| self.has_type(**i, e.0))
+                    .map(|i| Value::Instance(*i))
+                    .collect()));
+            }
+            self.scopes.push(scope);
+            self.steps = 0;
+            self.algorithm_head(&r.algorithm_head);
+            self.stmts(&r.stmt);
+            self.where_clause(&r.where_clause, RuleKind::Global, None,
+                              r.rule_head.rule_id.0);
+            self.scopes.pop();
+            self.decls.pop();
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Algorithms
+
+    /// Declares the local types, constants, and variables of a function,
+    /// procedure, or rule.  This pushes onto `decls` but not `scopes`, so
+    /// the caller must push a scope first.
+    fn algorithm_head(&mut self, h: &'a AlgorithmHead<'a>) {
+        let mut decls = HashMap::new();
+        for c in h.constant.iter().flat_map(|c| c.0.iter()) {
+            decls.insert(c.constant_id.0, Decl::Constant(c));
+        }
+        for d in &h.declaration {
+            let d = Decl::from(d);
+            decls.insert(d.name(), d);
+        }
+        self.decls.push(decls);
+        for v in h.local.iter().flat_map(|l| l.0.iter()) {
+            let x = match &v.expression {
+                Some(e) => self.expression(e),
+                None => Value::Indeterminate,
+            };
+            for id in &v.variable_id {
+                self.scopes.last_mut().unwrap().insert(id.0, x.clone());
+            }
+        }
+    }
+
+    fn call_function(&mut self, f: &'a FunctionDecl<'a>, args: Vec<Value>)
+        -> Value
+    {
+        if self.depth >= MAX_DEPTH {
+            return Value::Indeterminate;
+        }
+        let mut args = args.into_iter();
+        let scope = f.function_head.params.iter().flatten()
+            .flat_map(|p| p.0.iter())
+            .map(|p| (p.0, args.next().unwrap_or(Value::Indeterminate)))
+            .collect();
+        self.depth += 1;
+        let scopes = std::mem::replace(&mut self.scopes, vec![scope]);
+        let this = std::mem::replace(&mut self.this, Value::Indeterminate);
+        self.algorithm_head(&f.algorithm_head);
+        let out = match self.stmts(&f.stmts) {
+            Flow::Return(v) => v,
+            _ => Value::Indeterminate,
+        };
+        self.decls.pop();
+        self.this = this;
+        self.scopes = scopes;
+        self.depth -= 1;
+        out
+    }
+
+    fn call_procedure(&mut self, p: &'a ProcedureDecl<'a>,
+                      args: &'a [Parameter<'a>])
+    {
+        if self.depth >= MAX_DEPTH {
+            return;
+        }
+        let params: Vec<(bool, &'a str)> = p.0.args.iter().flatten()
+            .flat_map(|(var, f)| f.0.iter().map(move |p| (*var, p.0)))
+            .collect();
+        let scope = params.iter().enumerate()
+            .map(|(i, (_, name))| (*name, args.get(i)
+                .map(|a| self.expression(&a.0))
+                .unwrap_or(Value::Indeterminate)))
+            .collect();
+        self.depth += 1;
+        let scopes = std::mem::replace(&mut self.scopes, vec![scope]);
+        let this = std::mem::replace(&mut self.this, Value::Indeterminate);
+        self.algorithm_head(&p.1);
+        self.stmts(&p.2);
+        self.decls.pop();
+        let mut scope = std::mem::replace(&mut self.scopes, scopes)
+            .swap_remove(0);
+        self.this = this;
+        self.depth -= 1;
+
+        // Copy VAR parameters back to the caller's variables
+        for (i, (var, name)) in params.iter().enumerate() {
+            if let (true, Some(a)) = (var, args.get(i)) {
+                if let Some(target) = variable(&a.0) {
+                    let v = scope.remove(name).unwrap_or(Value::Indeterminate);
+                    self.assign(target, v);
+                }
+            }
+        }
+    }
+
+    /// Assigns to the innermost variable with the given name
+    fn assign(&mut self, name: &str, v: Value) {
+        if let Some(s) = self.scopes.iter_mut().rev()
+            .find(|s| s.contains_key(name))
+        {
+            *s.get_mut(name).unwrap() = v;
+        }
+    }
+
+    fn variable_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.scopes.iter_mut().rev().find_map(|s| s.get_mut(name))
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Statements
+
+    fn stmts(&mut self, stmts: &'a [Stmt<'a>]) -> Flow {
+        for s in stmts {
+            match self.stmt(s) {
+                Flow::Next => (),
+                f => return f,
+            }
+        }
+        Flow::Next
+    }
+
+    fn stmt(&mut self, s: &'a Stmt<'a>) -> Flow {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return Flow::Return(Value::Indeterminate);
+        }
+        match s {
+            Stmt::Alias(a) => {
+                let v = self.general_ref(&a.general);
+                let v = self.qualifiers(v, &a.qualifiers);
+                let mut scope = HashMap::new();
+                scope.insert(a.variable.0, v);
+                self.scopes.push(scope);
+                let out = self.stmts(&a.stmts);
+                self.scopes.pop();
+                out
+            },
+            Stmt::Assignment(a) => {
+                let v = self.expression(&a.expression);
+                let name = general_ref_name(&a.general_ref);
+                match &a.qualifiers[..] {
+                    [] => self.assign(name, v),
+                    [Qualifier::Index(i)] => {
+                        let k = self.simple_expression(&(((i.0).0).0).0)
+                            .integer();
+                        let target = self.variable_mut(name);
+                        if let (Some(k), Some(Value::Aggregate(agg))) =
+                            (k, target)
+                        {
+                            if let Some(e) = usize::try_from(k - 1).ok()
+                                .and_then(|k| agg.get_mut(k))
+                            {
+                                *e = v;
+                            }
+                        }
+                    },
+                    // Assigning to an entity's attribute isn't allowed
+                    _ => (),
+                }
+                Flow::Next
+            },
+            Stmt::Case(c) => {
+                let v = self.expression(&c.selector.0);
+                for a in &c.actions {
+                    for label in &a.0 {
+                        let x = self.expression(&label.0);
+                        if equal(&v, &x) == Some(true) {
+                            return self.stmt(&a.1);
+                        }
+                    }
+                }
+                match &c.otherwise {
+                    Some(s) => self.stmt(s),
+                    None => Flow::Next,
+                }
+            },
+            Stmt::Compound(c) => self.stmts(&c.0),
+            Stmt::Escape => Flow::Escape,
+            Stmt::If(i) => {
+                if self.expression(&(i.0).0).truth() == Some(true) {
+                    self.stmts(&i.1)
+                } else if let Some(e) = &i.2 {
+                    self.stmts(e)
+                } else {
+                    Flow::Next
+                }
+            },
+            Stmt::Null => Flow::Next,
+            Stmt::ProcedureCall(p) => {
+                let args = p.params.as_ref().map(|p| &p.0[..]).unwrap_or(&[]);
+                match &p.proc {
+                    BuiltInOrProcedureRef::BuiltIn(b) =>
+                        self.built_in_procedure(b, args),
+                    BuiltInOrProcedureRef::ProcedureRef(r) =>
+                        if let Some(Decl::Procedure(d)) = self.decl(r.0) {
+                            self.call_procedure(d, args);
+                        },
+                }
+                Flow::Next
+            },
+            Stmt::Repeat(r) => self.repeat(r),
+            Stmt::Return(r) => Flow::Return(match &r.0 {
+                Some(e) => self.expression(e),
+                None => Value::Indeterminate,
+            }),
+            Stmt::Skip => Flow::Skip,
+        }
+    }
+
+    fn built_in_procedure(&mut self, b: &BuiltInProcedure,
+                          args: &'a [Parameter<'a>])
+    {
+        let target = match args.first().and_then(|a| variable(&a.0)) {
+            Some(t) => t,
+            None => return,
+        };
+        let vs: Vec<Value> = args[1..].iter()
+            .map(|a| self.expression(&a.0))
+            .collect();
+        let agg = match self.variable_mut(target) {
+            Some(Value::Aggregate(agg)) => agg,
+            _ => return,
+        };
+        // Positions are 0-based for INSERT (which inserts after the given
+        // position) and 1-based for REMOVE
+        match (b, &vs[..]) {
+            (BuiltInProcedure::Insert, [v, p]) => match p.integer() {
+                Some(p) if p >= 0 && (p as usize) <= agg.len() =>
+                    agg.insert(p as usize, v.clone()),
+                _ => (),
+            },
+            (BuiltInProcedure::Remove, [p]) => match p.integer() {
+                Some(p) if p >= 1 && (p as usize) <= agg.len() => {
+                    agg.remove(p as usize - 1);
+                },
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+
+    fn repeat(&mut self, r: &'a RepeatStmt<'a>) -> Flow {
+        let RepeatControl(inc, w, u) = &r.0;
+        let range = match inc {
+            Some(i) => {
+                let lo = self.simple_expression(&i.bound1.0 .0).integer();
+                let hi = self.simple_expression(&i.bound2.0 .0).integer();
+                let step = match &i.increment {
+                    Some(s) => self.simple_expression(&s.0 .0).integer(),
+                    None => Some(1),
+                };
+                match (lo, hi, step) {
+                    // The loop isn't executed if the bounds are indeterminate
+                    (Some(lo), Some(hi), Some(step)) if step != 0 =>
+                        Some((i.var.0, lo, hi, step)),
+                    _ => return Flow::Next,
+                }
+            },
+            None => None,
+        };
+
+        self.scopes.push(HashMap::new());
+        let mut k = range.map(|r| r.1).unwrap_or(0);
+        let out = loop {
+            if let Some((var, _, hi, step)) = range {
+                if (step > 0 && k > hi) || (step < 0 && k < hi) {
+                    break Flow::Next;
+                }
+                self.scopes.last_mut().unwrap().insert(var, Value::Integer(k));
+                k += step;
+            }
+            if let Some(w) = w {
+                if self.expression(&(w.0).0).truth() != Some(true) {
+                    break Flow::Next;
+                }
+            }
+            match self.stmts(&r.1) {
+                Flow::Return(v) => break Flow::Return(v),
+                Flow::Escape => break Flow::Next,
+                Flow::Next | Flow::Skip => (),
+            }
+            if let Some(u) = u {
+                if self.expression(&(u.0).0).truth() == Some(true) {
+                    break Flow::Next;
+                }
+            }
+            if self.steps > MAX_STEPS {
+                break Flow::Return(Value::Indeterminate);
+            }
+        };
+        self.scopes.pop();
+        out
+    }
+
+    ////////////////////////////////////////////////////////////////////////////
+    // Expressions
+
+    fn general_ref(&mut self, g: &'a GeneralRef<'a>) -> Value {
+        self.name(general_ref_name(g))
+    }
+
+    fn name(&mut self, name: &'a str) -> Value {
+        if let Some(v) = self.scopes.iter().rev().find_map(|s| s.get(name)) {
+            return v.clone();
+        }
+        if let Value::Instance(id) = self.this {
+            if let Some(v) = self.try_attribute(id, None, name) {
+                return v;
+            }
+        }
+        match self.decl(name) {
+            Some(Decl::Constant(c)) => self.constant(c),
+            Some(_) => Value::Indeterminate,
+            // Anything else must be an enumeration item, since the schema
+            // has already been checked for unknown names
+            None => Value::Enum(name.to_owned()),
+        }
+    }
+
+    fn constant(&mut self, c: &'a ConstantBody<'a>) -> Value {
+        let key = c as *const _;
+        if let Some(v) = self.constants.get(&key) {
+            return v.clone();
+        }
+        // Insert a placeholder first, in case the constant refers to itself
+        self.constants.insert(key, Value::Indeterminate);
+        let scopes = std::mem::take(&mut self.scopes);
+        let this = std::mem::replace(&mut self.this, Value::Indeterminate);
+        let v = self.expression(&c.expression);
+        self.this = this;
+        self.scopes = scopes;
+        self.constants.insert(key, v.clone());
+        v
+    }
+
+    fn expression(&mut self, e: &'a Expression<'a>) -> Value {
+        let a = self.simple_expression(&e.0);
+        let (op, b) = match &e.1 {
+            None => return a,
+            Some((op, b)) => (op, self.simple_expression(b)),
+        };
+        let out = match op {
+            RelOpExtended::RelOp(r) => match r {
+                RelOp::Equal | RelOp::InstanceEqual => equal(&a, &b),
+                RelOp::NotEqual | RelOp::InstanceNotEqual =>
+                    equal(&a, &b).map(|b| !b),
+                RelOp::LessThan => compare(&a, &b).map(|o| o.is_lt()),
+                RelOp::GreaterThan => compare(&a, &b).map(|o| o.is_gt()),
+                RelOp::LessThanOrEqual => compare(&a, &b).map(|o| o.is_le()),
+                RelOp::GreaterThanOrEqual => compare(&a, &b).map(|o| o.is_ge()),
+            },
+            RelOpExtended::In => match b.elements() {
+                Some(v) => any(v.iter().map(|x| equal(&a, x))),
+                None => None,
+            },
+            RelOpExtended::Like => match (a.plain(), b.plain()) {
+                (Value::String(s), Value::String(p)) => {
+                    let s: Vec<char> = s.chars().collect();
+                    let p: Vec<char> = p.chars().collect();
+                    Some(like(&s, &p))
+                },
+                _ => None,
+            },
+        };
+        Value::Logical(out)
+    }
+
+    fn simple_expression(&mut self, e: &'a SimpleExpression<'a>) -> Value {
+        let mut a = self.term(&e.0);
+        for (op, t) in &e.1 {
+            let b = self.term(t);
+            a = match op {
+                AddLikeOp::Add => add(a, b),
+                AddLikeOp::Sub => sub(a, b),
+                AddLikeOp::Or => Value::Logical(or(a.truth(), b.truth())),
+                AddLikeOp::Xor => Value::Logical(match (a.truth(), b.truth()) {
+                    (Some(a), Some(b)) => Some(a != b),
+                    _ => None,
+                }),
+            };
+        }
+        a
+    }
+
+    fn term(&mut self, t: &'a Term<'a>) -> Value {
+        let mut a = self.factor(&t.0);
+        for (op, f) in &t.1 {
+            let b = self.factor(f);
+            a = match op {
+                MultiplicationLikeOp::Mul => mul(a, b),
+                MultiplicationLikeOp::Div => match (a.number(), b.number()) {
+                    (Some(x), Some(y)) if y != 0.0 => Value::Real(x / y),
+                    _ => Value::Indeterminate,
+                },
+                MultiplicationLikeOp::IntegerDiv =>
+                    match (a.integer(), b.integer()) {
+                        (Some(x), Some(y)) if y != 0 =>
+                            Value::Integer(x.div_euclid(y)),
+                        _ => Value::Indeterminate,
+                    },
+                MultiplicationLikeOp::Mod => match (a.integer(), b.integer()) {
+                    (Some(x), Some(y)) if y != 0 =>
+                        Value::Integer(x.rem_euclid(y)),
+                    _ => Value::Indeterminate,
+                },
+                MultiplicationLikeOp::And =>
+                    Value::Logical(and(a.truth(), b.truth())),
+                // Complex entity instances can't be built at runtime
+                MultiplicationLikeOp::ComplexEntity => Value::Indeterminate,
+            };
+        }
+        a
+    }
+
+    fn factor(&mut self, f: &'a Factor<'a>) -> Value {
+        // Chains are evaluated from left to right, like every other operator
+        // (see the note on `Factor`)
+        let mut a = self.simple_factor(&f.0);
+        for f in &f.1 {
+            let b = self.simple_factor(f);
+            a = match (a.plain(), b.plain()) {
+                (Value::Integer(x), Value::Integer(y)) =>
+                    u32::try_from(*y).ok()
+                        .and_then(|y| x.checked_pow(y))
+                        .map(Value::Integer)
+                        .unwrap_or(Value::Indeterminate),
+                _ => match (a.number(), b.number()) {
+                    (Some(x), Some(y)) => Value::Real(x.powf(y)),
+                    _ => Value::Indeterminate,
+                },
+            };
+        }
+        a
+    }
+
+    fn simple_factor(&mut self, f: &'a SimpleFactor<'a>) -> Value {
+        match f {
+            SimpleFactor::_AmbiguousFunctionCall(id, args) => {
+                let args: Vec<Value> = args.iter()
+                    .map(|a| self.expression(a))
+                    .collect();
+                self.call(id.0, args)
+            },
+            SimpleFactor::AggregateInitializer(a) => {
+                let mut out = Vec::new();
+                for e in &a.0 {
+                    let v = self.expression(&e.0);
+                    let n = match &e.1 {
+                        Some(r) => self.simple_expression(&(r.0).0).integer(),
+                        None => Some(1),
+                    };
+                    match n {
+                        Some(n) => for _ in 0..n.min(MAX_STEPS as i64) {
+                            out.push(v.clone());
+                        },
+                        None => return Value::Indeterminate,
+                    }
+                }
+                Value::Aggregate(out)
+            },
+            SimpleFactor::EntityConstructor(c) => {
+                for a in &c.args {
+                    self.expression(a);
+                }
+                Value::Indeterminate
+            },
+            SimpleFactor::EnumerationReference(r) => match &r.0 {
+                Some(_) => Value::Enum((r.1).0.to_owned()),
+                None => self.name((r.1).0),
+            },
+            SimpleFactor::Interval(i) => {
+                let lo = self.simple_expression(&i.low.0);
+                let x = self.simple_expression(&i.item.0);
+                let hi = self.simple_expression(&i.high.0);
+                let cmp = |a: &Value, op: &IntervalOp, b: &Value|
+                    compare(a, b).map(|o| match op {
+                        IntervalOp::LessThan => o.is_lt(),
+                        IntervalOp::LessThanOrEqual => o.is_le(),
+                    });
+                Value::Logical(and(cmp(&lo, &i.op1, &x), cmp(&x, &i.op2, &hi)))
+            },
+            SimpleFactor::QueryExpression(q) => {
+                let agg = self.simple_expression(&q.aggregate.0);
+                let items = match agg.elements() {
+                    Some(v) => v.to_vec(),
+                    None => return Value::Indeterminate,
+                };
+                let mut out = Vec::new();
+                for item in items {
+                    let mut scope = HashMap::new();
+                    scope.insert(q.var.0, item.clone());
+                    self.scopes.push(scope);
+                    let keep = self.expression(&q.logical_expression.0).truth();
+                    self.scopes.pop();
+                    if keep == Some(true) {
+                        out.push(item);
+                    }
+                }
+                Value::Aggregate(out)
+            },
+            SimpleFactor::Unary(op, e) => {
+                let v = match e {
+                    ExpressionOrPrimary::Expression(e) => self.expression(e),
+                    ExpressionOrPrimary::Primary(p) => self.primary(p),
+                };
+                match op {
+                    None | Some(UnaryOp::Add) => v,
+                    Some(UnaryOp::Sub) => match v.plain() {
+                        Value::Integer(i) => Value::Integer(-i),
+                        Value::Real(f) => Value::Real(-f),
+                        _ => Value::Indeterminate,
+                    },
+                    Some(UnaryOp::Not) =>
+                        Value::Logical(v.truth().map(|b| !b)),
+                }
+            },
+        }
+    }
+
+    fn primary(&mut self, p: &'a Primary<'a>) -> Value {
+        match p {
+            Primary::Literal(l) => match l {
+                Literal::String(s) => Value::String(s.clone()),
+                Literal::Binary(_) => Value::Indeterminate,
+                Literal::Logical(LogicalLiteral::True) => Value::Logical(Some(true)),
+                Literal::Logical(LogicalLiteral::False) =>
+                    Value::Logical(Some(false)),
+                Literal::Logical(LogicalLiteral::Unknown) => Value::Logical(None),
+                // Integer literals are parsed as reals
+                Literal::Real(f) if f.fract() == 0.0 && f.abs() < 1e15 =>
+                    Value::Integer(*f as i64),
+                Literal::Real(f) => Value::Real(*f),
+            },
+            Primary::Qualifiable(f, qs) => {
+                let v = self.qualifiable_factor(f);
+                self.qualifiers(v, qs)
+            },
+        }
+    }
+
+    fn qualifiable_factor(&mut self, f: &'a QualifiableFactor<'a>) -> Value {
+        match f {
+            QualifiableFactor::FunctionCall(c) => {
+                let args: Vec<Value> = (c.1).0.iter()
+                    .map(|a| self.expression(&a.0))
+                    .collect();
+                match &c.0 {
+                    BuiltInOrFunctionRef::BuiltIn(b) => self.built_in(b, args),
+                    BuiltInOrFunctionRef::Ref(r) => self.call(r.0, args),
+                }
+            },
+            QualifiableFactor::AttributeRef(a) => self.name(a.0),
+            QualifiableFactor::ConstantFactor(c) => match c {
+                ConstantFactor::BuiltIn(BuiltInConstant::ConstE) =>
+                    Value::Real(std::f64::consts::E),
+                ConstantFactor::BuiltIn(BuiltInConstant::Pi) =>
+                    Value::Real(std::f64::consts::PI),
+                ConstantFactor::BuiltIn(BuiltInConstant::Self_) =>
+                    self.this.clone(),
+                ConstantFactor::BuiltIn(BuiltInConstant::Indeterminant) =>
+                    Value::Indeterminate,
+                ConstantFactor::ConstantRef(r) => self.name(r.0),
+            },
+            QualifiableFactor::GeneralRef(g) => self.general_ref(g),
+            QualifiableFactor::Population(p) => {
+                let ids = self.ids();
+                Value::Aggregate(ids.into_iter()
+                    .filter(|i| self.has_type(*i, (p.0).0))
+                    .map(Value::Instance)
+                    .collect())
+            },
+            QualifiableFactor::_Ambiguous(s) => self.name(s),
+        }
+    }
+
+    fn qualifiers(&mut self, mut v: Value, qs: &'a [Qualifier<'a>]) -> Value {
+        let mut group = None;
+        for q in qs {
+            v = match q {
+                Qualifier::Attribute(a) => match v.plain() {
+                    Value::Instance(id) =>
+                        self.attribute(*id, group.take(), (a.0).0),
+                    _ => Value::Indeterminate,
+                },
+                Qualifier::Group(g) => {
+                    group = Some((g.0).0);
+                    continue;
+                },
+                Qualifier::Index(i) => {
+                    let lo = self.simple_expression(&(((i.0).0).0).0).integer();
+                    let hi = match &i.1 {
+                        Some(j) => self.simple_expression(&((j.0).0).0).integer(),
+                        None => lo,
+                    };
+                    let (lo, hi) = match (lo, hi) {
+                        (Some(lo), Some(hi)) if lo >= 1 && hi >= lo =>
+                            (lo as usize, hi as usize),
+                        _ => return Value::Indeterminate,
+                    };
+                    match v.plain() {
+                        Value::Aggregate(agg) if i.1.is_none() =>
+                            agg.get(lo - 1).cloned()
+                                .unwrap_or(Value::Indeterminate),
+                        Value::String(s) if hi <= s.chars().count() =>
+                            Value::String(s.chars()
+                                .skip(lo - 1)
+                                .take(hi - lo + 1)
+                                .collect()),
+                        _ => Value::Indeterminate,
+                    }
+                },
+            };
+        }
+        v
+    }
+
+    /// Calls a function by name.  Built-in functions are reserved words, so
+    /// they're parsed separately and never reach this point.  Entity
+    /// constructors can't be evaluated, so they return an indeterminate value.
+    fn call(&mut self, name: &str, args: Vec<Value>) -> Value {
+        match self.decl(name) {
+            Some(Decl::Function(f)) => self.call_function(f, args),
+            _ => Value::Indeterminate,
+        }
+    }
+
+    fn built_in(&mut self, b: &BuiltInFunction, args: Vec<Value>) -> Value {
+        use BuiltInFunction as B;
+        let first = args.first().cloned().unwrap_or(Value::Indeterminate);
+        let real = |f: fn(f64) -> f64| match first.number().map(f) {
+            Some(x) if x.is_finite() => Value::Real(x),
+            _ => Value::Indeterminate,
+        };
+        match b {
+            B::Abs => match first.plain() {
+                Value::Integer(i) => i.checked_abs()
+                    .map(Value::Integer)
+                    .unwrap_or(Value::Indeterminate),
+                _ => real(f64::abs),
+            },
+            B::Acos => real(f64::acos),
+            B::Asin => real(f64::asin),
+            B::Atan => match (first.number(), args.get(1).and_then(|v| v.number())) {
+                (Some(y), Some(x)) => Value::Real(y.atan2(x)),
+                _ => Value::Indeterminate,
+            },
+            B::Cos => real(f64::cos),
+            B::Exp => real(f64::exp),
+            B::Log => real(f64::ln),
+            B::Log2 => real(f64::log2),
+            B::Log10 => real(f64::log10),
+            B::Sin => real(f64::sin),
+            B::Sqrt => real(f64::sqrt),
+            B::Tan => real(f64::tan),
+            B::Exists => Value::Logical(Some(!first.is_indeterminate())),
+            B::Nvl => if first.is_indeterminate() {
+                args.get(1).cloned().unwrap_or(Value::Indeterminate)
+            } else {
+                first
+            },
+            B::HiIndex | B::SizeOf => match first.elements() {
+                Some(v) => Value::Integer(v.len() as i64),
+                None => Value::Indeterminate,
+            },
+            B::LoIndex => match first.elements() {
+                Some(_) => Value::Integer(1),
+                None => Value::Indeterminate,
+            },
+            B::Length => match first.plain() {
+                Value::String(s) => Value::Integer(s.chars().count() as i64),
+                _ => Value::Indeterminate,
+            },
+            B::Odd => match first.integer() {
+                Some(i) => Value::Logical(Some(i % 2 != 0)),
+                None => Value::Indeterminate,
+            },
+            B::Typeof => self.type_of(&first),
+            B::Usedin => self.used_in(&first, args.get(1)),
+            B::RolesOf => self.roles_of(&first),
+            B::Value => match first.plain() {
+                Value::String(s) => match s.trim().parse::<i64>() {
+                    Ok(i) => Value::Integer(i),
+                    Err(_) => crate::real::parse(s)
+                        .map(Value::Real)
+                        .unwrap_or(Value::Indeterminate),
+                },
+                _ => Value::Indeterminate,
+            },
+            B::ValueIn => match first.elements() {
+                Some(v) => {
+                    let x = args.get(1).cloned()
+                        .unwrap_or(Value::Indeterminate);
+                    Value::Logical(any(v.iter().map(|e| equal(e, &x))))
+                },
+                None => Value::Indeterminate,
+            },
+            B::ValueUnique => match first.elements() {
+                Some(v) => {
+                    let mut out = Some(true);
+                    for (i, a) in v.iter().enumerate() {
+                        for b in &v[i + 1..] {
+                            out = and(out, equal(a, b).map(|e| !e));
+                        }
+                    }
+                    Value::Logical(out)
+                },
+                None => Value::Indeterminate,
+            },
+            // These depend on declared widths and bounds, which we don't
+            // track, or on formatting rules which aren't implemented
+            B::Blength | B::Format | B::Hibound | B::LoBound =>
+                Value::Indeterminate,
+        }
+    }
+
+    fn type_of(&mut self, v: &Value) -> Value {
+        let mut out = Vec::new();
+        let mut v = v;
+        while let Value::Typed(name, inner) = v {
+            // A defined type may itself be built on other defined types
+            let mut name = name.as_str();
+            for _ in 0..MAX_DEPTH {
+                let t = match self.decl(name) {
+                    Some(Decl::Type(t)) => t,
+                    _ => break,
+                };
+                out.push(self.qualified(t.type_id.0));
+                match &t.underlying_type {
+                    UnderlyingType::Concrete(ConcreteTypes::TypeRef(r)) =>
+                        name = r.0,
+                    _ => break,
+                }
+            }
+            v = inner;
+        }
+        let simple: &[&str] = match v {
+            Value::Indeterminate => return Value::Indeterminate,
+            Value::Integer(_) => &["INTEGER", "NUMBER"],
+            Value::Real(_) => &["REAL", "NUMBER"],
+            Value::String(_) => &["STRING"],
+            Value::Logical(Some(_)) => &["BOOLEAN", "LOGICAL"],
+            Value::Logical(None) => &["LOGICAL"],
+            Value::Instance(id) => {
+                let types = self.instances.get(id)
+                    .map(|i| i.types.clone())
+                    .unwrap_or_default();
+                out.extend(types.iter().map(|t| self.qualified(t)));
+                &[]
+            },
+            Value::Enum(_) | Value::Aggregate(_) | Value::Typed(..) => &[],
+        };
+        out.extend(simple.iter().map(|s| s.to_string()));
+        Value::Aggregate(out.into_iter().map(Value::String).collect())
+    }
+
+    fn used_in(&mut self, v: &Value, role: Option<&Value>) -> Value {
+        let id = match v.plain() {
+            Value::Instance(id) => *id,
+            _ => return Value::Indeterminate,
+        };
+        let role = match role.map(|r| r.plain()) {
+            Some(Value::String(s)) => s.to_lowercase(),
+            _ => return Value::Indeterminate,
+        };
+        let users = self.users.get(&id).cloned().unwrap_or_default();
+        if role.is_empty() {
+            return Value::Aggregate(users.into_iter()
+                .map(Value::Instance)
+                .collect());
+        }
+        // The role is written as 'SCHEMA.ENTITY.ATTRIBUTE'
+        let mut parts = role.rsplit('.');
+        let (attr, entity) = match (parts.next(), parts.next()) {
+            (Some(a), Some(e)) => (a, e),
+            _ => return Value::Indeterminate,
+        };
+        let mut out = Vec::new();
+        for u in users {
+            if self.has_type(u, entity) &&
+               self.attribute(u, Some(entity), attr).refers_to(id)
+            {
+                out.push(Value::Instance(u));
+            }
+        }
+        Value::Aggregate(out)
+    }
+
+    fn roles_of(&mut self, v: &Value) -> Value {
+        let id = match v.plain() {
+            Value::Instance(id) => *id,
+            _ => return Value::Indeterminate,
+        };
+        let mut out = Vec::new();
+        for u in self.users.get(&id).cloned().unwrap_or_default() {
+            for t in self.instances[&u].types.clone() {
+                let attrs: Vec<&'a str> = match self.entity(t) {
+                    Some(e) => Self::own_slots(e).collect(),
+                    None => continue,
+                };
+                for a in attrs {
+                    let role = self.qualified(&format!("{}.{}", t, a));
+                    if self.explicit(u, t, a).refers_to(id) &&
+                       !out.contains(&role)
+                    {
+                        out.push(role);
+                    }
+                }
+            }
+        }
+        Value::Aggregate(out.into_iter().map(Value::String).collect())
+    }
+}
+
+fn general_ref_name<'a>(g: &GeneralRef<'a>) -> &'a str {
+    match g {
+        GeneralRef::Parameter(p) => p.0,
+        GeneralRef::Variable(v) => v.0,
+        GeneralRef::_SimpleId(s) => s.0,
+    }
+}
+
+/// If an expression is a bare name (without qualifiers), returns that name
+fn variable<'a>(e: &Expression<'a>) -> Option<&'a str> {
+    let s = match e {
+        Expression(s, None, _) if s.1.is_empty() => &s.0,
+        _ => return None,
+    };
+    let f = match s.as_ref() {
+        Term(Factor(f, p), t) if p.is_empty() && t.is_empty() => f,
+        _ => return None,
+    };
+    match f {
+        SimpleFactor::Unary(None, ExpressionOrPrimary::Primary(
+            Primary::Qualifiable(q, qs))) if qs.is_empty() => match q {
+                QualifiableFactor::GeneralRef(g) => Some(general_ref_name(g)),
+                QualifiableFactor::AttributeRef(a) => Some(a.0),
+                QualifiableFactor::_Ambiguous(s) => Some(s),
+                _ => None,
+            },
+        _ => None,
+    }
+}
+
+fn add(a: Value, b: Value) -> Value {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => x.checked_add(y)
+            .map(Value::Integer)
+            .unwrap_or(Value::Indeterminate),
+        (Value::String(x), Value::String(y)) => Value::String(x + &y),
+        (Value::Aggregate(mut x), Value::Aggregate(y)) => {
+            x.extend(y);
+            Value::Aggregate(x)
+        },
+        (Value::Aggregate(mut x), y) if !y.is_indeterminate() => {
+            x.push(y);
+            Value::Aggregate(x)
+        },
+        (x, Value::Aggregate(mut y)) if !x.is_indeterminate() => {
+            y.insert(0, x);
+            Value::Aggregate(y)
+        },
+        (Value::Typed(_, x), y) => add(*x, y),
+        (x, Value::Typed(_, y)) => add(x, *y),
+        (x, y) => match (x.number(), y.number()) {
+            (Some(x), Some(y)) => Value::Real(x + y),
+            _ => Value::Indeterminate,
+        },
+    }
+}
+
+fn sub(a: Value, b: Value) -> Value {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => x.checked_sub(y)
+            .map(Value::Integer)
+            .unwrap_or(Value::Indeterminate),
+        (Value::Aggregate(x), Value::Aggregate(y)) => Value::Aggregate(
+            x.into_iter()
+                .filter(|e| !y.iter().any(|f| equal(e, f) == Some(true)))
+                .collect()),
+        (Value::Aggregate(x), y) => Value::Aggregate(
+            x.into_iter()
+                .filter(|e| equal(e, &y) != Some(true))
+                .collect()),
+        (Value::Typed(_, x), y) => sub(*x, y),
+        (x, Value::Typed(_, y)) => sub(x, *y),
+        (x, y) => match (x.number(), y.number()) {
+            (Some(x), Some(y)) => Value::Real(x - y),
+            _ => Value::Indeterminate,
+        },
+    }
+}
+
+fn mul(a: Value, b: Value) -> Value {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => x.checked_mul(y)
+            .map(Value::Integer)
+            .unwrap_or(Value::Indeterminate),
+        // Multiplying aggregates gives their intersection
+        (Value::Aggregate(x), Value::Aggregate(y)) => Value::Aggregate(
+            x.into_iter()
+                .filter(|e| y.iter().any(|f| equal(e, f) == Some(true)))
+                .collect()),
+        (Value::Typed(_, x), y) => mul(*x, y),
+        (x, Value::Typed(_, y)) => mul(x, *y),
+        (x, y) => match (x.number(), y.number()) {
+            (Some(x), Some(y)) => Value::Real(x * y),
+            _ => Value::Indeterminate,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use express::parse::{parse, strip_comments_and_lower};
+    use crate::{raw::records, step_file::StepFile};
+
+    const SCHEMA: &[u8] = br#"SCHEMA shapes;
+TYPE positive_length = REAL;
+WHERE
+  wr1: SELF > 0.0;
+END_TYPE;
+TYPE label = STRING;
+END_TYPE;
+TYPE side = ENUMERATION OF (left, right);
+END_TYPE;
+ENTITY item
+  SUPERTYPE OF (ONEOF (point, circle));
+  name : label;
+UNIQUE
+  ur1: name;
+END_ENTITY;
+ENTITY point
+  SUBTYPE OF (item);
+  coords : LIST [1:3] OF REAL;
+DERIVE
+  dim : INTEGER := SIZEOF(coords);
+INVERSE
+  centers : SET [0:?] OF circle FOR center;
+WHERE
+  wr1: dim >= 2;
+  wr2: NOT (SELF\item.name = 'bad');
+END_ENTITY;
+ENTITY circle
+  SUBTYPE OF (item);
+  center : point;
+  radius : positive_length;
+  facing : OPTIONAL side;
+WHERE
+  wr1: center.dim = 3;
+  wr2: 'SHAPES.POINT' IN TYPEOF(center);
+  wr3: NOT EXISTS(facing) OR (facing <> right);
+  wr4: check_name(name);
+END_ENTITY;
+FUNCTION check_name(s : STRING) : LOGICAL;
+  LOCAL
+    n : INTEGER := 0;
+  END_LOCAL;
+  REPEAT i := 1 TO LENGTH(s);
+    IF s[i] = 'x' THEN
+      n := n + 1;
+    END_IF;
+  END_REPEAT;
+  RETURN (n < 2);
+END_FUNCTION;
+RULE few_circles FOR (circle, point);
+WHERE
+  wr1: SIZEOF(QUERY(c <* circle | c.radius > 5.0)) <= 1;
+  wr2: SIZEOF(QUERY(p <* point | SIZEOF(USEDIN(p, 'SHAPES.CIRCLE.CENTER')) <> SIZEOF(p.centers))) = 0;
+END_RULE;
+END_SCHEMA;
+"#;
+
+    fn check(data: &[u8]) -> Report {
+        let schema = strip_comments_and_lower(SCHEMA);
+        let syntax = parse(&schema).unwrap();
+        let flat = StepFile::strip_flatten(data);
+        check_rules(&syntax, "SHAPES", &records(&flat)).unwrap()
+    }
+
+    fn violations(r: &Report) -> Vec<String> {
+        r.violations.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_interpreter() {
+        let schema = strip_comments_and_lower(SCHEMA);
+        let syntax = parse(&schema).unwrap();
+        let flat = StepFile::strip_flatten(b"DATA;
+#1=POINT('a',(0.,0.));
+#2=CIRCLE('b',#1,1.5,$);
+#3=CIRCLE('c',#1,2.5,.LEFT.);
+ENDSEC;");
+        let rs = records(&flat);
+        let mut interp = Interpreter::new(&syntax, "shapes", &rs).unwrap();
+        assert_eq!(interp.types(2), ["item", "circle"]);
+        assert_eq!(interp.derived(1), [("point", "dim", Value::Integer(2))]);
+        assert_eq!(interp.attribute(1, "centers"), Some(Value::Aggregate(
+            vec![Value::Instance(2), Value::Instance(3)])));
+        assert_eq!(interp.attribute(2, "item.name"),
+                   Some(Value::String("b".to_owned())));
+        assert_eq!(interp.attribute(2, "facing"), Some(Value::Indeterminate));
+        assert_eq!(interp.attribute(3, "FACING"),
+                   Some(Value::Enum("left".to_owned())));
+        assert_eq!(interp.attribute(2, "dim"), None);
+        assert_eq!(interp.attribute(9, "name"), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let schema = strip_comments_and_lower(b"SCHEMA nums;
+ENTITY n;
+  v : INTEGER;
+DERIVE
+  chain : INTEGER := 2 ** 3 ** 2;
+  huge : INTEGER := 2 ** 4294967297;
+  negative : INTEGER := 2 ** v;
+  absolute : INTEGER := ABS(v);
+END_ENTITY;
+END_SCHEMA;");
+        let syntax = parse(&schema).unwrap();
+        let flat = StepFile::strip_flatten(b"DATA;
+#1=N(-9223372036854775808);
+#2=N(-3);
+ENDSEC;");
+        let rs = records(&flat);
+        let mut interp = Interpreter::new(&syntax, "nums", &rs).unwrap();
+        let values = |interp: &mut Interpreter, id| interp.derived(id)
+            .into_iter()
+            .map(|(_, _, v)| v)
+            .collect::<Vec<_>>();
+        // (2 ** 3) ** 2, rather than 2 ** (3 ** 2) = 512
+        assert_eq!(values(&mut interp, 1), [
+            Value::Integer(64), Value::Indeterminate, Value::Indeterminate,
+            Value::Indeterminate]);
+        assert_eq!(values(&mut interp, 2), [
+            Value::Integer(64), Value::Indeterminate, Value::Indeterminate,
+            Value::Integer(3)]);
+    }
+
+    #[test]
+    fn test_valid() {
+        let r = check(b"DATA;
+#1=POINT('a',(0.,0.,0.));
+#2=CIRCLE('b',#1,1.5,.LEFT.);
+#3=CIRCLE('c',#1,7.,$);
+ENDSEC;");
+        assert_eq!(violations(&r), Vec::<String>::new());
+        assert_eq!(r.unknown, 0);
+        assert!(r.checked > 10);
+    }
+
+    #[test]
+    fn test_violations() {
+        let r = check(b"DATA;
+#1=POINT('bad',(0.,0.));
+#2=CIRCLE('xx',#1,-1.,.RIGHT.);
+#3=CIRCLE('c',#1,7.,$);
+#4=CIRCLE('c',#1,8.,$);
+ENDSEC;");
+        assert_eq!(violations(&r), [
+            "#1: entity point violates wr2",
+            "#2: entity circle violates wr1",
+            "#2: entity circle violates wr3",
+            "#2: entity circle violates wr4",
+            "#2: type positive_length violates wr1",
+            "#3: entity circle violates wr1",
+            "#4: entity circle violates wr1",
+            "#4: unique rule in item violates ur1",
+            "rule few_circles violates wr1",
+        ]);
+    }
+}
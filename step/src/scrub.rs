@@ -0,0 +1,288 @@
+//! Removes identifying information from a STEP file, so that a model which
+//! triggers a bug can be shared without giving away what it is.
+//!
+//! Scrubbing blanks every string (product names and IDs, descriptions,
+//! labels, and the header's author and organization), except for those
+//! which carry meaning for the schema, like context and unit names.  Unless
+//! [`ScrubOptions::keep_metadata`] is set, it also removes people,
+//! organizations, dates, approvals, documents, and properties, along with
+//! anything which only they referred to.  Geometry and topology are kept
+//! as they are, unless [`ScrubOptions::jitter`] is set.
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    header::Header,
+    merge::{collect_refs, Merge},
+    raw::{records, Record},
+    writer::Param,
+};
+
+/// Options for [`scrub`]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ScrubOptions {
+    /// Keeps entities which describe who made the model and when, rather
+    /// than its shape (their strings are still blanked)
+    pub keep_metadata: bool,
+    /// Moves every `CARTESIAN_POINT` by up to this distance along each axis,
+    /// to disguise exact dimensions.  Distances are in the file's units.
+    /// Jitter larger than the model's tolerance will open gaps between
+    /// faces, so it may hide the bug being reported.
+    pub jitter: f64,
+    /// Seed for the jitter, which is otherwise deterministic
+    pub seed: u64,
+}
+
+/// Checks whether an entity records who made a model, when, or why, rather
+/// than what it looks like
+fn is_metadata(name: &str) -> bool {
+    const PREFIXES: &[&str] = &[
+        "ADDRESS", "APPLIED_", "APPROVAL", "CALENDAR_DATE", "CC_DESIGN_",
+        "CERTIFICATION", "CONTRACT", "COORDINATED_UNIVERSAL_TIME_OFFSET",
+        "DATE", "DESCRIPTIVE_REPRESENTATION_ITEM", "DOCUMENT", "LOCAL_TIME",
+        "ORGANIZATION", "PERSON", "PRODUCT_CATEGORY", "PRODUCT_RELATED_",
+        "PROPERTY_DEFINITION", "SECURITY_CLASSIFICATION",
+    ];
+    PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// Checks whether an entity's strings are part of the schema's vocabulary
+/// (e.g. `'distance_accuracy_value'` or `'mechanical'`), so they must be kept
+fn keeps_strings(name: &str) -> bool {
+    name.ends_with("_CONTEXT") || name.contains("PRE_DEFINED_") ||
+        name == "APPLICATION_PROTOCOL_DEFINITION" ||
+        name == "UNCERTAINTY_MEASURE_WITH_UNIT"
+}
+
+/// Scrubs a file which has been preprocessed by
+/// [`StepFile::strip_flatten`](crate::step_file::StepFile::strip_flatten),
+/// returning the text of the new file.  Instances are renumbered from 1.
+///
+/// ```
+/// use step::{scrub::{scrub, ScrubOptions}, step_file::StepFile};
+/// let data = b"ISO-10303-21;HEADER;
+/// FILE_NAME('secret.step','',('Jane'),('ACME'),'','','');
+/// ENDSEC;DATA;
+/// #1=CARTESIAN_POINT('hinge pin',(0.,0.,1.));
+/// #2=PERSON('jd','Doe','Jane',$,$,$);
+/// ENDSEC;END-ISO-10303-21;";
+/// let out = scrub(&StepFile::strip_flatten(data), &ScrubOptions::default());
+/// assert!(out.contains("#1=CARTESIAN_POINT('',(0.,0.,1.));"));
+/// assert!(!out.contains("secret") && !out.contains("Jane"));
+/// assert!(!out.contains("PERSON"));
+/// ```
+pub fn scrub(data: &[u8], options: &ScrubOptions) -> String {
+    let mut rs = records(data);
+    if !options.keep_metadata {
+        remove_metadata(&mut rs);
+    }
+    for r in &mut rs {
+        let keep = r.parts.iter().any(|(name, _)| keeps_strings(name));
+        for (name, ps) in &mut r.parts {
+            if !keep {
+                ps.iter_mut().for_each(blank);
+            }
+            if options.jitter != 0.0 && name == "CARTESIAN_POINT" {
+                if let Some(Param::List(coords)) = ps.get_mut(1) {
+                    for (axis, c) in coords.iter_mut().enumerate() {
+                        let x = match c {
+                            Param::Real(x) => *x,
+                            Param::Integer(i) => *i as f64,
+                            _ => continue,
+                        };
+                        let n = noise(options.seed, r.id, axis);
+                        *c = Param::Real(x + n * options.jitter);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut m = Merge::new();
+    m.add_records(rs, Header::parse(data).schemas.into_iter().next());
+    m.writer("").to_string()
+}
+
+/// Replaces strings (including those within lists and typed values) with
+/// empty strings
+fn blank(p: &mut Param) {
+    match p {
+        Param::String(s) => s.clear(),
+        Param::List(v) => v.iter_mut().for_each(blank),
+        Param::Typed(_, p) => blank(p),
+        _ => (),
+    }
+}
+
+/// Removes metadata instances, then any instances which referred to them
+/// (which would otherwise be left with dangling references), then any
+/// instances which are no longer used by anything.
+fn remove_metadata(rs: &mut Vec<Record>) {
+    let refs: HashMap<usize, Vec<usize>> = rs.iter()
+        .map(|r| (r.id, refs(r)))
+        .collect();
+    let mut removed: HashSet<usize> = rs.iter()
+        .filter(|r| r.parts.iter().any(|(name, _)| is_metadata(name)))
+        .map(|r| r.id)
+        .collect();
+
+    // Anything which refers to a removed instance goes too
+    loop {
+        let more: Vec<usize> = refs.iter()
+            .filter(|(id, rs)| !removed.contains(id) &&
+                               rs.iter().any(|r| removed.contains(r)))
+            .map(|(id, _)| *id)
+            .collect();
+        if more.is_empty() {
+            break;
+        }
+        removed.extend(more);
+    }
+
+    // Then remove instances which were only used by removed instances.
+    // Instances which nothing referred to in the first place (e.g. the
+    // top-level SHAPE_DEFINITION_REPRESENTATION) are left alone.
+    let count = |removed: &HashSet<usize>| {
+        let mut users: HashMap<usize, usize> = HashMap::new();
+        for (id, rs) in &refs {
+            if !removed.contains(id) {
+                for r in rs {
+                    *users.entry(*r).or_default() += 1;
+                }
+            }
+        }
+        users
+    };
+    let used_before = count(&HashSet::new());
+    loop {
+        let users = count(&removed);
+        let orphans: Vec<usize> = used_before.keys()
+            .filter(|id| !removed.contains(id) && !users.contains_key(id))
+            .cloned()
+            .collect();
+        if orphans.is_empty() {
+            break;
+        }
+        removed.extend(orphans);
+    }
+    rs.retain(|r| !removed.contains(&r.id));
+}
+
+/// Returns every instance referred to by a record
+fn refs(r: &Record) -> Vec<usize> {
+    let mut out = Vec::new();
+    for (_, ps) in &r.parts {
+        collect_refs(ps, &mut out);
+    }
+    out
+}
+
+/// Returns a value in [-1, 1] which depends only on its inputs (using the
+/// SplitMix64 finalizer)
+fn noise(seed: u64, id: usize, axis: usize) -> f64 {
+    let mut z = seed ^ (id as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (axis as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ap214::Entity, step_file::StepFile};
+
+    fn example(name: &str) -> Vec<u8> {
+        let path = format!("{}/../examples/{}", env!("CARGO_MANIFEST_DIR"),
+                           name);
+        StepFile::strip_flatten(&std::fs::read(path).unwrap())
+    }
+
+    fn count(s: &StepFile, f: fn(&Entity) -> bool) -> usize {
+        s.iter_by_id().filter(|(_, e)| f(e)).count()
+    }
+
+    #[test]
+    fn test_scrub_example() {
+        let flat = example("cube_hole.step");
+        let before = StepFile::parse(&flat);
+        let out = scrub(&flat, &ScrubOptions::default());
+        let out = StepFile::strip_flatten(out.as_bytes());
+        let after = StepFile::parse(&out);
+
+        assert_eq!(after.header().name, "");
+        assert_eq!(after.header().schemas, before.header().schemas);
+        for f in [|e: &Entity| matches!(e, Entity::AdvancedFace(_)),
+                  |e: &Entity| matches!(e, Entity::CartesianPoint(_)),
+                  |e: &Entity| matches!(e, Entity::StyledItem(_)),
+                  |e: &Entity| matches!(e, Entity::ShapeDefinitionRepresentation(_))]
+        {
+            assert_eq!(count(&before, f), count(&after, f));
+        }
+        // Validation properties and product categories are gone
+        for f in [|e: &Entity| matches!(e, Entity::PropertyDefinition(_)),
+                  |e: &Entity| matches!(e, Entity::ProductCategory(_)),
+                  |e: &Entity| matches!(e, Entity::ValueRepresentationItem(_))]
+        {
+            assert!(count(&before, f) > 0);
+            assert_eq!(count(&after, f), 0);
+        }
+        // Every reference still points at something
+        for (_, e) in after.iter_by_id() {
+            for r in e.upstream() {
+                assert!(!matches!(after.entities[r], Entity::_EmptySlot));
+            }
+        }
+        // Only strings from the schema's vocabulary are left
+        for r in records(&out) {
+            let mut strings = Vec::new();
+            fn walk<'a>(p: &'a Param, out: &mut Vec<&'a str>) {
+                match p {
+                    Param::String(s) if !s.is_empty() => out.push(s),
+                    Param::List(v) => v.iter().for_each(|p| walk(p, out)),
+                    Param::Typed(_, p) => walk(p, out),
+                    _ => (),
+                }
+            }
+            r.parts.iter().flat_map(|p| &p.1).for_each(|p| walk(p, &mut strings));
+            assert!(strings.is_empty() ||
+                    r.parts.iter().any(|(n, _)| keeps_strings(n)),
+                    "{:?}", r);
+        }
+    }
+
+    #[test]
+    fn test_keep_metadata() {
+        let flat = example("cube_hole.step");
+        let options = ScrubOptions { keep_metadata: true, ..Default::default() };
+        let out = scrub(&flat, &options);
+        assert_eq!(records(&flat).len(),
+                   records(&StepFile::strip_flatten(out.as_bytes())).len());
+    }
+
+    #[test]
+    fn test_jitter() {
+        let data = b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=CARTESIAN_POINT('',(1.,2,3.));
+#2=CARTESIAN_POINT('',(1.,2,3.));
+ENDSEC;END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let options = ScrubOptions { jitter: 0.1, seed: 7, ..Default::default() };
+        let out = scrub(&flat, &options);
+        assert_eq!(out, scrub(&flat, &options));
+        assert_ne!(out, scrub(&flat, &ScrubOptions { seed: 8, ..options }));
+
+        let flat = StepFile::strip_flatten(out.as_bytes());
+        let s = StepFile::parse(&flat);
+        let coords = |i: usize| match &s.entities[i] {
+            Entity::CartesianPoint(p) =>
+                p.coordinates.iter().map(|c| c.0).collect::<Vec<f64>>(),
+            e => panic!("Unexpected entity {:?}", e),
+        };
+        let (a, b) = (coords(1), coords(2));
+        assert_ne!(a, b);
+        for (c, x) in a.iter().chain(&b).zip([1.0, 2.0, 3.0].iter().cycle()) {
+            assert!((c - x).abs() <= 0.1 && c != x);
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use memchr::{memchr, memchr2, memchr_iter};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use memchr::{memchr, memchr2_iter, memchr_iter};
 use log::warn;
 
 #[cfg(feature = "rayon")]
@@ -6,67 +12,483 @@ use rayon::prelude::*;
 
 use crate::{
     ap214::Entity,
+    archive,
+    check::{check_block, Mismatch},
+    header::Header,
     id::Id,
+    merge::Merge,
+    mmap::MappedFile,
     parse::{parse_entity_decl, parse_entity_fallback},
+    raw::{self, header_entities, Record},
+    xml,
 };
 
+/// Size of the chunks in which [`StepFile::into_blocks`] looks for the ends
+/// of blocks
+const SPLIT_CHUNK_SIZE: usize = 1 << 20;
+
+/// What to do when the DATA section declares the same entity ID twice
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DuplicatePolicy {
+    /// Keep the first declaration, ignoring later ones
+    KeepFirst,
+    /// Keep the last declaration, so later lines override earlier ones
+    KeepLast,
+    /// Refuse to load the file
+    Error,
+}
+
+/// Options for [`StepFile::parse_with_options`]
+#[derive(Copy, Clone, Debug)]
+pub struct ParseOptions {
+    pub duplicates: DuplicatePolicy,
+    /// Checks each instance's parameters against the number and kinds of
+    /// its entity's attributes (see [`StepFile::mismatches`]).  This catches
+    /// truncated or shifted parameter lists, which may otherwise still parse
+    /// and only show up later as bad geometry, but it reads every instance
+    /// twice.
+    pub check_params: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self { duplicates: DuplicatePolicy::KeepFirst, check_params: false }
+    }
+}
+
+/// An entity ID which was declared more than once
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Duplicate {
+    pub id: usize,
+    /// Index of each declaration within the DATA section, in file order
+    pub positions: Vec<usize>,
+}
+
+impl Duplicate {
+    /// Finds the (1-based) line number of each declaration in the original
+    /// file, i.e. the data passed to [`StepFile::strip_flatten`].  This is a
+    /// textual search for `#id=`, so it assumes that the declaration isn't
+    /// split across lines between the ID and the `=`.
+    pub fn lines(&self, raw: &[u8]) -> Vec<usize> {
+        let tag = format!("#{}", self.id);
+        let tag = tag.as_bytes();
+        let mut out = Vec::new();
+        let mut line = 1;
+        let mut i = 0;
+        while i < raw.len() {
+            match raw[i] {
+                b'\n' => line += 1,
+                b'\'' => {
+                    // Skip over strings, which could contain anything
+                    let end = memchr(b'\'', &raw[i + 1..])
+                        .map(|j| i + j + 1)
+                        .unwrap_or(raw.len());
+                    line += raw[i..end].iter().filter(|c| **c == b'\n').count();
+                    i = end;
+                },
+                b'#' if raw[i..].starts_with(tag) => {
+                    let rest = &raw[i + tag.len()..];
+                    let j = rest.iter()
+                        .position(|c| !c.is_ascii_whitespace())
+                        .unwrap_or(rest.len());
+                    // The ID must be followed by '=' (and not more digits)
+                    if rest.get(j) == Some(&b'=') &&
+                       !rest.first().map(|c| c.is_ascii_digit()).unwrap_or(false)
+                    {
+                        out.push(line);
+                    }
+                },
+                _ => (),
+            }
+            i += 1;
+        }
+        out
+    }
+}
+
+/// Returned by [`StepFile::parse_with_options`] when the file declares an
+/// ID more than once and the policy is [`DuplicatePolicy::Error`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DuplicateIds(pub Vec<Duplicate>);
+
+impl std::fmt::Display for DuplicateIds {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Duplicate entity IDs:")?;
+        for d in &self.0 {
+            write!(f, " #{}", d.id)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DuplicateIds {}
+
 #[derive(Debug)]
-pub struct StepFile<'a>(pub Vec<Entity<'a>>);
+pub struct StepFile<'a> {
+    /// Entities, indexed by ID.  Unused IDs are filled with
+    /// [`Entity::_EmptySlot`].  This is read-only outside the crate (through
+    /// [`StepFile::entities`]), so that it stays in sync with `file_order`.
+    pub(crate) entities: Vec<Entity<'a>>,
+
+    /// The HEADER section
+    header: Header,
+
+    /// IDs in the order in which they (first) appeared in the DATA section
+    file_order: Vec<usize>,
+
+    /// Flattened text of each entity, indexed by ID (empty if unused)
+    text: Vec<&'a [u8]>,
+
+    /// IDs which were declared more than once
+    duplicates: Vec<Duplicate>,
+
+    /// Instances whose parameters don't match their entity's attributes
+    mismatches: Vec<Mismatch>,
+}
+
 impl<'a> StepFile<'a> {
     /// Parses a STEP file from a raw array of bytes
     /// `data` must be preprocessed by [`strip_flatten`] first
+    ///
+    /// If an ID is declared more than once, the first declaration wins (see
+    /// [`StepFile::duplicates`]).
+    ///
+    /// ```
+    /// use step::{ap214::CartesianPoint_, id::Id, step_file::StepFile};
+    ///
+    /// let data = b"ISO-10303-21;
+    /// HEADER;
+    /// ENDSEC;
+    /// DATA;
+    /// #1=CARTESIAN_POINT('origin', (0., 1., 2.5));
+    /// ENDSEC;
+    /// END-ISO-10303-21;";
+    /// let flat = StepFile::strip_flatten(data);
+    /// let s = StepFile::parse(&flat);
+    /// let p = s.entity(Id::<CartesianPoint_>::new(1)).unwrap();
+    /// assert_eq!(p.name.0, "origin");
+    /// assert_eq!(p.coordinates[2].0, 2.5);
+    /// ```
     pub fn parse(data: &'a [u8]) -> Self {
-        let blocks = Self::into_blocks(&data);
-        let data_start = blocks.iter()
-            .position(|b| b == b"DATA;")
-            .unwrap_or(0) + 1;
-        let data_end = blocks.iter()
-            .skip(data_start)
-            .position(|b| b == b"ENDSEC;")
-            .unwrap_or(0) + data_start;
+        Self::parse_with_options(data, &ParseOptions::default())
+            .expect("KeepFirst should never fail")
+    }
+
+    /// Reads a file, decompressing it if it's a gzip stream or ZIP archive
+    /// (see [`archive`](crate::archive)) and translating it if it's
+    /// STEP-XML (see [`xml`](crate::xml)).  The result should be passed to
+    /// [`strip_flatten`] before parsing.
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+        let data = std::fs::read(path)?;
+        match Self::decode(&data)? {
+            Cow::Borrowed(_) => Ok(data),
+            Cow::Owned(d) => Ok(d),
+        }
+    }
+
+    /// Decompresses and translates a file's contents as needed, returning
+    /// Part 21 text (borrowed if it already was)
+    pub(crate) fn decode(data: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+        let data = archive::decompress(data)?;
+        if xml::is_xml(&data) {
+            Ok(Cow::Owned(xml::to_part21(&data)?))
+        } else {
+            Ok(data)
+        }
+    }
+
+    /// Memory-maps the file at `path` and flattens it in place, avoiding the
+    /// copies made by reading the file then calling [`strip_flatten`].
+    /// Since the entities borrow from the mapping, this returns the mapping;
+    /// call [`MappedFile::parse`] on it to get the `StepFile`.
+    ///
+    /// # Safety
+    /// The file must not be changed by this or any other process while the
+    /// mapping is alive.  The mapping is copy-on-write, which protects the
+    /// file from being changed by flattening, but doesn't protect the
+    /// mapping from changes to the file: if it's written concurrently, the
+    /// parsed data may change underneath it (which is undefined behavior),
+    /// and if it's truncated, reading the mapping crashes the process with
+    /// `SIGBUS`.  Use [`StepFile::read`] for files which may change, e.g.
+    /// those on network shares or still being written by another program.
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// use step::step_file::StepFile;
+    /// // Safety: nothing else modifies part.step while it's mapped
+    /// let mapped = unsafe { StepFile::open_mmap("part.step")? };
+    /// let s = mapped.parse();
+    /// println!("{} entities", s.iter_by_id().count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub unsafe fn open_mmap<P: AsRef<Path>>(path: P) -> io::Result<MappedFile> {
+        MappedFile::open(path)
+    }
+
+    /// Parses a STEP file, like [`StepFile::parse`], with extra options
+    pub fn parse_with_options(data: &'a [u8], options: &ParseOptions)
+        -> Result<Self, DuplicateIds>
+    {
+        profile_scope!("parse");
+        let all = Self::into_blocks(data);
+        let blocks = Self::data_section(&all);
+        let header = Header::from_entities(
+            &header_entities(Self::header_section(&all)));
 
         // Parse every block, accumulating a Vec of Results.  We parse in
         // single-threaded mode in WASM builds, because there's no thread
         // pool.
         let block_iter = {
-            let block_slice = &blocks[data_start..data_end];
+            let block_slice = blocks;
             #[cfg(feature = "rayon")]
             { block_slice.par_iter() }
             #[cfg(not(feature = "rayon"))]
             { block_slice.iter() }
         };
 
-        let parsed: Vec<(usize, Entity)> = block_iter
-            .filter_map(|b| parse_entity_decl(*b)
-                .or_else(|e| {
-                    warn!("Failed to parse {}: {:?}",
-                        std::str::from_utf8(b).unwrap_or("[INVALID UTF-8]"),
-                              e);
-                    parse_entity_fallback(*b)
-                })
-                .ok())
-            .map(|b| b.1)
-            .collect();
+        let mismatches: Vec<Mismatch> = if options.check_params {
+            #[cfg(feature = "rayon")]
+            let iter = blocks.par_iter();
+            #[cfg(not(feature = "rayon"))]
+            let iter = blocks.iter();
+            iter.filter_map(|b| check_block(b)).collect()
+        } else {
+            vec![]
+        };
+        for m in &mismatches {
+            warn!("{}", m);
+        }
+
+        let parsed: Vec<(usize, (usize, Entity))> = {
+            profile_scope!("parse_entities");
+            block_iter
+                .enumerate()
+                .filter_map(|(i, b)| parse_entity_decl(*b)
+                    .or_else(|e| {
+                        warn!("Failed to parse {}: {:?}",
+                            std::str::from_utf8(b).unwrap_or("[INVALID UTF-8]"),
+                                  e);
+                        parse_entity_fallback(*b)
+                    })
+                    .ok()
+                    .map(|b| (i, b.1)))
+                .collect()
+        };
 
         // Awkward construction because `Entity` is not `Clone`
-        let max_id = parsed.iter().map(|b| b.0).max().unwrap_or(0);
+        let max_id = parsed.iter().map(|b| (b.1).0).max().unwrap_or(0);
         let mut out: Vec<Entity> = (0..=max_id)
             .map(|_| Entity::_EmptySlot)
             .collect();
 
-        for p in parsed.into_iter() {
-            out[p.0] = p.1;
+        // `collect` preserves the order of the blocks, even when parsing in
+        // parallel, so this is the order in which entities appear in the file.
+        let mut text: Vec<&[u8]> = vec![&[]; max_id + 1];
+        let mut first = vec![None; max_id + 1];
+        let mut duplicates: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let mut file_order = Vec::with_capacity(parsed.len());
+        for (pos, (id, e)) in parsed.into_iter() {
+            match first[id] {
+                None => {
+                    first[id] = Some(pos);
+                    file_order.push(id);
+                    out[id] = e;
+                    text[id] = blocks[pos];
+                },
+                Some(f) => {
+                    duplicates.entry(id).or_insert_with(|| vec![f]).push(pos);
+                    if options.duplicates == DuplicatePolicy::KeepLast {
+                        out[id] = e;
+                        text[id] = blocks[pos];
+                    }
+                },
+            }
+        }
+        let duplicates: Vec<Duplicate> = duplicates.into_iter()
+            .map(|(id, positions)| Duplicate { id, positions })
+            .collect();
+        if !duplicates.is_empty() {
+            if options.duplicates == DuplicatePolicy::Error {
+                return Err(DuplicateIds(duplicates));
+            }
+            for d in &duplicates {
+                warn!("Entity #{} is declared {} times; keeping the {}",
+                      d.id, d.positions.len(),
+                      if options.duplicates == DuplicatePolicy::KeepLast {
+                          "last"
+                      } else {
+                          "first"
+                      });
+            }
+        }
+
+        Ok(Self {
+            entities: out, header, file_order, text, duplicates, mismatches,
+        })
+    }
+
+    /// Prints an entity and the entities which it refers to, recursively,
+    /// as indented Part 21 text.  References are followed `depth` levels
+    /// deep; beyond that, `...` marks an entity with further references.
+    /// Each entity is only expanded once: later references to it are
+    /// printed as `#id (see above)`, or `#id (cycle)` if it's one of the
+    /// entities being expanded.
+    ///
+    /// Since this only includes the given entity and its dependencies, it
+    /// can be shared in a bug report without the rest of the model.
+    ///
+    /// ```
+    /// use step::step_file::StepFile;
+    /// let data = b"ISO-10303-21;HEADER;ENDSEC;DATA;
+    /// #1=CARTESIAN_POINT('',(0.,0.,0.));
+    /// #2=DIRECTION('',(0.,0.,1.));
+    /// #3=AXIS2_PLACEMENT_3D('',#1,#2,$);
+    /// #4=PLANE('',#3);
+    /// ENDSEC;END-ISO-10303-21;";
+    /// let flat = StepFile::strip_flatten(data);
+    /// let s = StepFile::parse(&flat);
+    /// assert_eq!(s.dump(4, 1), "\
+    /// #4=PLANE('',#3);
+    ///   #3=AXIS2_PLACEMENT_3D('',#1,#2,$);
+    ///     ...
+    /// ");
+    /// ```
+    pub fn dump(&self, id: usize, depth: usize) -> String {
+        let mut out = String::new();
+        self.dump_into(id, depth, &mut Vec::new(), &mut HashSet::new(),
+                       &mut out);
+        out
+    }
+
+    fn dump_into(&self, id: usize, depth: usize, path: &mut Vec<usize>,
+                 seen: &mut HashSet<usize>, out: &mut String)
+    {
+        let indent = "  ".repeat(path.len());
+        let text = match self.text.get(id) {
+            Some(t) if !t.is_empty() => t,
+            _ => return writeln!(out, "{}#{} (missing)", indent, id).unwrap(),
+        };
+        if path.contains(&id) {
+            return writeln!(out, "{}#{} (cycle)", indent, id).unwrap();
+        } else if !seen.insert(id) {
+            return writeln!(out, "{}#{} (see above)", indent, id).unwrap();
+        }
+        writeln!(out, "{}{}", indent, String::from_utf8_lossy(text)).unwrap();
+
+        let mut refs = self.entities[id].upstream();
+        let mut unique = HashSet::new();
+        refs.retain(|r| unique.insert(*r));
+        if refs.is_empty() {
+            return;
+        } else if path.len() == depth {
+            return writeln!(out, "{}  ...", indent).unwrap();
+        }
+        path.push(id);
+        for r in refs {
+            self.dump_into(r, depth, path, seen, out);
         }
+        path.pop();
+    }
+
+    /// Returns the file's HEADER section, which describes where it came from
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Reads every instance, in file order, as a schema-independent
+    /// [`Record`].  Unlike the parsed entities, records can be written back
+    /// out (e.g. with [`Merge`]).
+    pub fn records(&self) -> Vec<Record> {
+        self.file_order.iter()
+            .filter_map(|i| std::str::from_utf8(self.text[*i]).ok())
+            .filter_map(|b| raw::record(b).ok().map(|r| r.1))
+            .collect()
+    }
+
+    /// Merges this file with another, e.g. to combine part files into an
+    /// assembly.  The other file's instances are renumbered to follow this
+    /// one's, references are rewritten to match, identical units and
+    /// contexts are shared, and the headers are combined.  This file is
+    /// index 0 in the returned [`Merge`] and the other is index 1, for use
+    /// with [`Merge::new_id`]; more files can be added to it, and
+    /// [`Merge::writer`] writes the result.
+    pub fn merge(&self, other: &StepFile) -> Merge {
+        let mut m = Merge::new();
+        m.add_step(self);
+        m.add_step(other);
+        m
+    }
+
+    /// Returns every ID which was declared more than once, in ascending order
+    pub fn duplicates(&self) -> &[Duplicate] {
+        &self.duplicates
+    }
+
+    /// Returns every instance whose parameters don't match its entity's
+    /// attributes, in file order.  This is only populated when parsing with
+    /// [`ParseOptions::check_params`].
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
 
-        Self(out)
+    /// Returns every entity, indexed by ID.  Unused IDs are filled with
+    /// [`Entity::_EmptySlot`]; use [`StepFile::iter_by_id`] to skip them.
+    pub fn entities(&self) -> &[Entity<'a>] {
+        &self.entities
     }
 
-    /// Flattens a STEP file, removing comments and whitespace
+    /// Iterates over every entity in ascending ID order, skipping unused IDs
+    pub fn iter_by_id(&self) -> impl Iterator<Item=(usize, &Entity<'a>)> {
+        self.entities.iter()
+            .enumerate()
+            .filter(|(_i, e)| !matches!(e, Entity::_EmptySlot))
+    }
+
+    /// Iterates over every entity in the order in which it was declared in
+    /// the file's DATA section
+    pub fn iter_file_order(&self) -> impl Iterator<Item=(usize, &Entity<'a>)> {
+        self.file_order.iter()
+            .map(move |i| (*i, &self.entities[*i]))
+    }
+
+    /// Flattens a STEP file, removing comments and whitespace.
+    ///
+    /// String literals are copied as-is, so they may contain spaces and
+    /// text which looks like a comment.  Line breaks within a string are
+    /// removed, since they only wrap long lines in the file and aren't part
+    /// of the string itself.
     pub fn strip_flatten(data: &[u8]) -> Vec<u8> {
-        let mut out = Vec::with_capacity(data.len());
+        let mut out = data.to_vec();
+        let n = Self::strip_flatten_in_place(&mut out);
+        out.truncate(n);
+        out
+    }
+
+    /// Flattens a STEP file in place, like [`StepFile::strip_flatten`],
+    /// returning the length of the flattened text (which begins the buffer).
+    /// Flattening only ever removes bytes, so no extra buffer is needed.
+    pub fn strip_flatten_in_place(data: &mut [u8]) -> usize {
+        profile_scope!("strip_flatten");
+        let mut n = 0;
         let mut i = 0;
         while i < data.len() {
             match data[i] {
+                // An escaped quote ('') ends one string and immediately
+                // begins another, so it doesn't need special handling here
+                b'\'' => {
+                    let end = memchr(b'\'', &data[i + 1..])
+                        .map(|j| i + j + 1)
+                        .unwrap_or(data.len() - 1);
+                    for j in i..=end {
+                        let c = data[j];
+                        if c != b'\n' && c != b'\r' {
+                            data[n] = c;
+                            n += 1;
+                        }
+                    }
+                    i = end;
+                },
                 b'/' => if i + 1 < data.len() && data[i + 1] == b'*' {
                     for j in memchr_iter(b'/', &data[i + 2..]) {
                         if data[i + j + 1] == b'*' {
@@ -74,52 +496,407 @@ impl<'a> StepFile<'a> {
                             break;
                         }
                     }
-                }
-                // TODO: don't skip whitespace inside of strings
+                } else {
+                    // e.g. in a URI in an edition 3 REFERENCE section
+                    data[n] = b'/';
+                    n += 1;
+                },
                 c if c.is_ascii_whitespace() => (),
-                c => out.push(c),
+                c => {
+                    data[n] = c;
+                    n += 1;
+                },
             }
             i += 1;
         }
-        out
+        n
     }
 
     /// Splits a STEP file into individual blocks.  The input must be pre-processed
     /// by [`strip_flatten`] beforehand.
     fn into_blocks(data: &[u8]) -> Vec<&[u8]> {
-        let mut blocks = Vec::new();
-        let mut i = 0;
+        profile_scope!("into_blocks");
         let mut start = 0;
-        while i < data.len() {
-            let next = memchr2(b'\'', b';', &data[i..]).unwrap();
-            match data[i + next] {
-                // Skip over quoted blocks
-                b'\'' => i += next + memchr(b'\'', &data[i + next..]).unwrap() + 1,
-                b';' => {
-                    blocks.push(&data[start..=(i + next)]);
-
-                    i += next + 1; // Skip the semicolon
-                    start = i;
-                },
-                _ => unreachable!(),
-            }
+        Self::block_ends(data, SPLIT_CHUNK_SIZE).into_iter()
+            .map(|end| {
+                let b = &data[start..=end];
+                start = end + 1;
+                b
+            })
+            .collect()
+    }
+
+    /// Finds the semicolon which ends each block, scanning the input in
+    /// chunks of the given size (in parallel, with the `rayon` feature).
+    ///
+    /// A chunk may begin in the middle of a string, which is detected by
+    /// counting quotes in the chunks before it: every quote opens or closes
+    /// a string, including each half of an escaped quote (`''`).
+    fn block_ends(data: &[u8], chunk_size: usize) -> Vec<usize> {
+        #[cfg(feature = "rayon")]
+        let chunks = data.par_chunks(chunk_size);
+        #[cfg(not(feature = "rayon"))]
+        let chunks = data.chunks(chunk_size);
+        let quotes: Vec<bool> = chunks.clone()
+            .map(|c| memchr_iter(b'\'', c).count() % 2 == 1)
+            .collect();
+        let in_string: Vec<bool> = quotes.iter()
+            .scan(false, |s, q| {
+                let prev = *s;
+                *s ^= q;
+                Some(prev)
+            })
+            .collect();
+
+        let ends: Vec<Vec<usize>> = chunks
+            .enumerate()
+            .map(|(i, c)| {
+                let mut in_string = in_string[i];
+                let mut out = Vec::new();
+                for j in memchr2_iter(b'\'', b';', c) {
+                    if c[j] == b'\'' {
+                        in_string = !in_string;
+                    } else if !in_string {
+                        out.push(i * chunk_size + j);
+                    }
+                }
+                out
+            })
+            .collect();
+        ends.concat()
+    }
+
+    /// Returns the blocks of the HEADER section, i.e. one header entity per
+    /// block.  The input must be pre-processed by [`strip_flatten`].
+    pub(crate) fn header_blocks(data: &[u8]) -> Vec<&[u8]> {
+        Self::header_section(&Self::into_blocks(data)).to_vec()
+    }
+
+    /// Returns the blocks of the DATA section, i.e. one entity instance per
+    /// block.  The input must be pre-processed by [`strip_flatten`].
+    pub(crate) fn data_blocks(data: &[u8]) -> Vec<&[u8]> {
+        Self::data_section(&Self::into_blocks(data)).to_vec()
+    }
+
+    /// Returns the blocks of a section other than DATA (e.g. `b"ANCHOR;"`),
+    /// which are empty if the file doesn't have that section.  The input
+    /// must be pre-processed by [`strip_flatten`].
+    pub(crate) fn section_blocks<'b>(data: &'b [u8], name: &[u8])
+        -> Vec<&'b [u8]>
+    {
+        Self::section(&Self::into_blocks(data), name).to_vec()
+    }
+
+    /// Picks the HEADER section's blocks out of [`into_blocks`]'s output
+    fn header_section<'v, 'b>(blocks: &'v [&'b [u8]]) -> &'v [&'b [u8]] {
+        Self::section(blocks, b"HEADER;")
+    }
+
+    /// Picks the blocks between a section's keyword (e.g. `b"HEADER;"`) and
+    /// the following `ENDSEC;`
+    fn section<'v, 'b>(blocks: &'v [&'b [u8]], name: &[u8])
+        -> &'v [&'b [u8]]
+    {
+        match blocks.iter().position(|b| *b == name) {
+            Some(start) => {
+                let rest = &blocks[start + 1..];
+                let end = rest.iter()
+                    .position(|b| b == b"ENDSEC;")
+                    .unwrap_or(rest.len());
+                &rest[..end]
+            },
+            None => &[],
         }
-        blocks
     }
 
+    /// Picks the DATA section's blocks out of [`into_blocks`]'s output
+    fn data_section<'v, 'b>(blocks: &'v [&'b [u8]]) -> &'v [&'b [u8]] {
+        // Edition 3 files may name their DATA section, e.g. `DATA('',());`
+        let data_start = blocks.iter()
+            .position(|b| b == b"DATA;" || b.starts_with(b"DATA("))
+            .unwrap_or(0) + 1;
+        let data_end = blocks.iter()
+            .skip(data_start)
+            .position(|b| b == b"ENDSEC;")
+            .unwrap_or(0) + data_start;
+        let data_end = data_end.min(blocks.len());
+        &blocks[data_start.min(data_end)..data_end]
+    }
+
+    /// Looks up an entity by ID, returning `None` if the ID is missing, is a
+    /// placeholder (`$` or `*`), or refers to an entity of a different type
     pub fn entity<T: FromEntity<'a>>(&'a self, i: Id<T>) -> Option<&'a T> {
-        T::try_from_entity(&self.0[i.0])
+        self.entities.get(i.0).and_then(T::try_from_entity)
+    }
+
+    /// Iterates over every instance of one entity type, in ascending ID
+    /// order, along with typed IDs for them:
+    /// ```
+    /// # use step::{ap214::*, step_file::StepFile};
+    /// # let flat = StepFile::strip_flatten(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+    /// # #3=CARTESIAN_POINT('a',(0.,0.,0.));#4=CARTESIAN_POINT('b',(1.,0.,0.));
+    /// # ENDSEC;END-ISO-10303-21;");
+    /// # let s = StepFile::parse(&flat);
+    /// for (id, p) in s.iter::<CartesianPoint>() {
+    ///     println!("#{} is at {:?}", id.0, p.coordinates);
+    /// }
+    /// assert_eq!(s.iter::<CartesianPoint>().count(), 2);
+    /// ```
+    /// Parts of complex instances are included (see [`Entity::downcast`]),
+    /// but instances of subtypes aren't, since each is its own variant of
+    /// [`Entity`].
+    pub fn iter<I: TypedId<'a>>(&'a self)
+        -> impl Iterator<Item=(I, &'a I::Entity)>
+    {
+        self.iter_by_id()
+            .filter_map(|(i, e)| e.downcast().map(|e| (I::from_index(i), e)))
     }
 }
 
+impl<'a> Entity<'a> {
+    /// Returns this entity as a particular type, or `None` if it's some
+    /// other type.  A complex instance is searched for a part of that type.
+    pub fn downcast<T: FromEntity<'a> + 'a>(&'a self) -> Option<&'a T> {
+        match self {
+            Entity::ComplexEntity(parts) =>
+                parts.iter().find_map(|p| p.downcast()),
+            e => T::try_from_entity(e),
+        }
+    }
+
+    /// Checks whether this entity is (or, if complex, includes) the given
+    /// type
+    pub fn is<T: FromEntity<'a> + 'a>(&'a self) -> bool {
+        self.downcast::<T>().is_some()
+    }
+}
+
+/// Returned when indexing with a placeholder ID
+static EMPTY_SLOT: Entity<'static> = Entity::_EmptySlot;
+
+/// Indexing with a placeholder ID (`$` or `*`) returns `Entity::_EmptySlot`,
+/// so that callers which match on the result can report an error.
 impl<'a, T> std::ops::Index<Id<T>> for StepFile<'a> {
     type Output = Entity<'a>;
 
     fn index(&self, id: Id<T>) -> &Self::Output {
-        &self.0[id.0]
+        match id.get() {
+            Some(id) => &self.entities[id.0],
+            None => &EMPTY_SLOT,
+        }
     }
 }
 
 pub trait FromEntity<'a> {
     fn try_from_entity(e: &'a Entity<'a>) -> Option<&'a Self>;
 }
+
+/// Implemented by the typed IDs in the generated schema (e.g.
+/// [`AdvancedFace`](crate::ap214::AdvancedFace)), linking each to the
+/// entity which it refers to, so that they can name a type in
+/// [`StepFile::iter`]
+pub trait TypedId<'a>: Copy {
+    type Entity: FromEntity<'a> + 'a;
+    fn from_index(i: usize) -> Self;
+}
+
+impl<'a, T: FromEntity<'a> + 'a> TypedId<'a> for Id<T> {
+    type Entity = T;
+    fn from_index(i: usize) -> Self {
+        Id::new(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ap214::{
+        AdvancedFace, CartesianPoint, CartesianPoint_,
+        GlobalUnitAssignedContext, GlobalUnitAssignedContext_,
+    };
+
+    #[test]
+    fn typed_iter() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/cube_hole.step");
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        let s = StepFile::parse(&flat);
+
+        let faces: Vec<_> = s.iter::<AdvancedFace>().collect();
+        assert_eq!(faces.len(), s.entities.iter()
+            .filter(|e| matches!(e, Entity::AdvancedFace(_)))
+            .count());
+        assert!(faces.windows(2).all(|w| w[0].0.0 < w[1].0.0));
+        for (id, f) in &faces {
+            assert!(std::ptr::eq(s.entity(*id).unwrap(), *f));
+        }
+        assert!(s.iter::<CartesianPoint>()
+            .all(|(id, _)| s.entities[id.0].is::<CartesianPoint_>()));
+
+        // #219 is a complex instance, which includes a unit context
+        let e = &s.entities[219];
+        assert!(matches!(e, Entity::ComplexEntity(_)));
+        assert!(e.is::<GlobalUnitAssignedContext_>());
+        assert!(!e.is::<CartesianPoint_>());
+        let (id, ctx) = s.iter::<GlobalUnitAssignedContext>().next().unwrap();
+        assert_eq!(id.0, 219);
+        assert_eq!(ctx.units.len(), 3);
+    }
+
+    #[test]
+    fn dump() {
+        let data = b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#5=VERTEX_POINT('',#1);
+#6=VERTEX_POINT('', #7);
+#9=DIRECTION('',(1.,0.,0.));
+#12=VECTOR('',#9,1.);
+#14=LINE('',#1,#12);
+#18=EDGE_CURVE('',#5,#6,#14,.T.);
+#20=SHAPE_REPRESENTATION_RELATIONSHIP('','',#21,#21);
+#21=SHAPE_REPRESENTATION('',(#20),$);
+ENDSEC;END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let s = StepFile::parse(&flat);
+        assert_eq!(s.dump(18, 2), "\
+#18=EDGE_CURVE('',#5,#6,#14,.T.);
+  #5=VERTEX_POINT('',#1);
+    #1=CARTESIAN_POINT('',(0.,0.,0.));
+  #6=VERTEX_POINT('',#7);
+    #7 (missing)
+  #14=LINE('',#1,#12);
+    #1 (see above)
+    #12=VECTOR('',#9,1.);
+      ...
+");
+        assert_eq!(s.dump(18, 0), "#18=EDGE_CURVE('',#5,#6,#14,.T.);\n  ...\n");
+        assert_eq!(s.dump(20, 5), "\
+#20=SHAPE_REPRESENTATION_RELATIONSHIP('','',#21,#21);
+  #21=SHAPE_REPRESENTATION('',(#20),$);
+    #20 (cycle)
+");
+        assert_eq!(s.dump(2, 5), "#2 (missing)\n");
+    }
+
+    #[test]
+    fn chunked_blocks() {
+        let data = b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=PRODUCT('a;b','it''s;#2=X();','',());
+#2=CARTESIAN_POINT('''',(0.,0.,0.));#3=CARTESIAN_POINT(';',(1.,0.,0.));
+ENDSEC;END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let blocks = StepFile::data_blocks(&flat);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0], &b"#1=PRODUCT('a;b','it''s;#2=X();','',());"[..]);
+
+        // Splitting in small chunks (which often start within a string)
+        // finds the same blocks as splitting the whole buffer at once
+        let whole = StepFile::block_ends(&flat, flat.len());
+        for chunk_size in 1..40 {
+            assert_eq!(StepFile::block_ends(&flat, chunk_size), whole);
+        }
+    }
+
+    #[test]
+    fn iteration_order() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#5=CARTESIAN_POINT('',(1.,0.,0.));
+#2=CARTESIAN_POINT('',(0.,1.,0.));
+#9=CARTESIAN_POINT('',(0.,0.,1.));
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let step = StepFile::parse(&flat);
+        let by_id: Vec<usize> = step.iter_by_id().map(|(i, _)| i).collect();
+        assert_eq!(by_id, vec![2, 5, 9]);
+        let file: Vec<usize> = step.iter_file_order().map(|(i, _)| i).collect();
+        assert_eq!(file, vec![5, 2, 9]);
+    }
+
+    #[test]
+    fn placeholder_lookup() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=VERTEX_POINT('',$);
+#2=VERTEX_POINT('',*);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let step = StepFile::parse(&flat);
+        for i in 1..=2 {
+            let v = match &step.entities[i] {
+                Entity::VertexPoint(v) => v.vertex_geometry,
+                e => panic!("Unexpected entity {:?}", e),
+            };
+            assert!(v.get().is_none());
+            assert!(step.entity(v.cast::<crate::ap214::CartesianPoint_>()).is_none());
+            assert!(matches!(step[v], Entity::_EmptySlot));
+        }
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#5=CARTESIAN_POINT('',(1.,0.,0.));
+#2=CARTESIAN_POINT('#5=',(0.,1.,0.));
+#5 = CARTESIAN_POINT('',(0.,0.,1.));
+#51=CARTESIAN_POINT('',(0.,0.,2.));
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let z = |step: &StepFile| match &step.entities[5] {
+            Entity::CartesianPoint(p) => p.coordinates[2].0,
+            e => panic!("Unexpected entity {:?}", e),
+        };
+
+        let step = StepFile::parse(&flat);
+        assert_eq!(z(&step), 0.0);
+        let file: Vec<usize> = step.iter_file_order().map(|(i, _)| i).collect();
+        assert_eq!(file, vec![5, 2, 51]);
+        let dups = step.duplicates();
+        assert_eq!(dups, [Duplicate { id: 5, positions: vec![0, 2] }]);
+        assert_eq!(dups[0].lines(data), vec![5, 7]);
+
+        let opts = ParseOptions {
+            duplicates: DuplicatePolicy::KeepLast, ..Default::default()
+        };
+        let step = StepFile::parse_with_options(&flat, &opts).unwrap();
+        assert_eq!(z(&step), 1.0);
+
+        let opts = ParseOptions {
+            duplicates: DuplicatePolicy::Error, ..Default::default()
+        };
+        let err = StepFile::parse_with_options(&flat, &opts).unwrap_err();
+        assert_eq!(err.0.len(), 1);
+        assert_eq!(err.to_string(), "Duplicate entity IDs: #5");
+    }
+
+    #[test]
+    fn strings_in_flatten() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('a /* b */ c',(1.,0.,0.)); /* comment */
+#2=CARTESIAN_POINT('wrapped across
+ lines',(0.,1.,0.));
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let step = StepFile::parse(&flat);
+        let name = |i| match &step.entities[i] {
+            Entity::CartesianPoint(p) => p.name.0,
+            e => panic!("Unexpected entity {:?}", e),
+        };
+        assert_eq!(name(1), "a /* b */ c");
+        assert_eq!(name(2), "wrapped across lines");
+    }
+}
@@ -0,0 +1,330 @@
+//! Parses a STEP file incrementally from a [`Read`] source.
+//!
+//! [`StepFile`](crate::step_file::StepFile) needs the whole (flattened) file
+//! in memory, since its entities borrow from the text.  [`StreamParser`]
+//! instead reads a chunk at a time, yielding each DATA instance as soon as
+//! its closing `;` arrives, so memory use doesn't grow with the size of the
+//! file.  Each [`Instance`] owns its text and is decoded on demand, either
+//! into a typed [`Entity`] or a schema-independent [`Record`].
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> {
+//! use step::{ap214::Entity, stream::StreamParser};
+//! let file = std::fs::File::open("huge.step")?;
+//! let mut faces = 0;
+//! for instance in StreamParser::new(file) {
+//!     if let Some((_, Entity::AdvancedFace(_))) = instance?.entity() {
+//!         faces += 1;
+//!     }
+//! }
+//! println!("{} faces", faces);
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::VecDeque;
+use std::io::{self, Read};
+
+use log::warn;
+
+use crate::{
+    ap214::Entity,
+    header::Header,
+    parse::{parse_entity_decl, parse_entity_fallback},
+    raw::{header_entities, record, Record},
+};
+
+/// Number of bytes to read at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// One entity instance from the DATA section
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Instance {
+    text: Vec<u8>,
+    line: usize,
+}
+
+impl Instance {
+    /// Returns the instance's text with comments and whitespace removed,
+    /// as [`StepFile::strip_flatten`] would leave it (e.g.
+    /// `#1=CARTESIAN_POINT('',(0.,0.,0.));`)
+    ///
+    /// [`StepFile::strip_flatten`]: crate::step_file::StepFile::strip_flatten
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// Returns the line on which the instance begins (counting from 1)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Parses the instance, returning its ID and entity.  Instances which
+    /// have an ID but can't otherwise be parsed are returned as
+    /// [`Entity::_FailedToParse`] (as in [`StepFile`]); `None` means that
+    /// not even the ID could be read.
+    ///
+    /// [`StepFile`]: crate::step_file::StepFile
+    pub fn entity(&self) -> Option<(usize, Entity<'_>)> {
+        parse_entity_decl(&self.text)
+            .or_else(|e| {
+                warn!("Failed to parse {} (line {}): {:?}",
+                      String::from_utf8_lossy(&self.text), self.line, e);
+                parse_entity_fallback(&self.text)
+            })
+            .ok()
+            .map(|r| r.1)
+    }
+
+    /// Parses the instance without a schema (see [`Record`])
+    pub fn record(&self) -> Option<Record> {
+        std::str::from_utf8(&self.text).ok()
+            .and_then(|s| record(s).ok())
+            .map(|r| r.1)
+    }
+}
+
+/// Where the scanner is within the input
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum State {
+    Code,
+    String,
+    Comment,
+}
+
+/// Which section of the file the scanner is in
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Section {
+    None,
+    Header,
+    Data,
+}
+
+/// Reads a STEP file incrementally, yielding the instances in its DATA
+/// section (in file order).  The HEADER section is collected along the way
+/// and is available from [`StreamParser::header`].
+pub struct StreamParser<R> {
+    reader: R,
+    eof: bool,
+    chunk: Vec<u8>,
+
+    state: State,
+    section: Section,
+    /// The last byte was `/` (in code) or `*` (in a comment), which may
+    /// begin or end a comment depending on the next byte
+    pending: bool,
+    /// Current line in the input
+    line: usize,
+
+    /// Flattened text of the current block, and the line where it began
+    block: Vec<u8>,
+    block_line: usize,
+
+    header: Vec<Vec<u8>>,
+    ready: VecDeque<Instance>,
+}
+
+impl<R: Read> StreamParser<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            eof: false,
+            chunk: vec![0; CHUNK_SIZE],
+            state: State::Code,
+            section: Section::None,
+            pending: false,
+            line: 1,
+            block: Vec::new(),
+            block_line: 1,
+            header: Vec::new(),
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Returns the HEADER section, once it has been read (i.e. after the
+    /// first instance has been yielded, or after the end of the input)
+    pub fn header(&self) -> Option<Header> {
+        if self.section == Section::Data || self.eof {
+            let blocks: Vec<&[u8]> = self.header.iter()
+                .map(|b| b.as_slice())
+                .collect();
+            Some(Header::from_entities(&header_entities(&blocks)))
+        } else {
+            None
+        }
+    }
+
+    fn push_code(&mut self, c: u8) {
+        if self.block.is_empty() {
+            self.block_line = self.line;
+        }
+        self.block.push(c);
+    }
+
+    /// Scans one byte of input
+    fn scan(&mut self, c: u8) {
+        match self.state {
+            State::String => {
+                if c == b'\'' {
+                    self.state = State::Code;
+                }
+                // Line breaks within strings only wrap long lines
+                if c != b'\n' && c != b'\r' {
+                    self.block.push(c);
+                }
+            },
+            State::Comment => {
+                if self.pending && c == b'/' {
+                    self.state = State::Code;
+                }
+                self.pending = c == b'*';
+            },
+            State::Code => {
+                if self.pending {
+                    self.pending = false;
+                    if c == b'*' {
+                        self.state = State::Comment;
+                        return;
+                    }
+                    self.push_code(b'/');
+                }
+                match c {
+                    b'/' => self.pending = true,
+                    b'\'' => {
+                        self.push_code(c);
+                        self.state = State::String;
+                    },
+                    b';' => {
+                        self.push_code(c);
+                        self.end_block();
+                    },
+                    c if c.is_ascii_whitespace() => (),
+                    c => self.push_code(c),
+                }
+            },
+        }
+    }
+
+    /// Handles a complete block, which ends with `;`
+    fn end_block(&mut self) {
+        let text = std::mem::take(&mut self.block);
+        match (self.section, text.as_slice()) {
+            (_, b"HEADER;") => self.section = Section::Header,
+            (_, b"DATA;") => self.section = Section::Data,
+            (_, t) if t.starts_with(b"DATA(") => self.section = Section::Data,
+            (_, b"ENDSEC;") => self.section = Section::None,
+            (Section::Header, _) => self.header.push(text),
+            (Section::Data, _) => self.ready.push_back(Instance {
+                text, line: self.block_line,
+            }),
+            (Section::None, _) => (),
+        }
+    }
+}
+
+impl<R: Read> Iterator for StreamParser<R> {
+    type Item = io::Result<Instance>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(i) = self.ready.pop_front() {
+                return Some(Ok(i));
+            }
+            if self.eof {
+                return None;
+            }
+            let n = match self.reader.read(&mut self.chunk) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+                Ok(n) => n,
+            };
+            if n == 0 {
+                self.eof = true;
+                if !self.block.is_empty() {
+                    warn!("Ignoring incomplete instance at line {}",
+                          self.block_line);
+                }
+                continue;
+            }
+            for i in 0..n {
+                let c = self.chunk[i];
+                self.scan(c);
+                if c == b'\n' {
+                    self.line += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{raw::records, step_file::StepFile};
+
+    /// Reads a few bytes at a time, to exercise tokens which span chunks
+    struct Trickle<'a>(&'a [u8]);
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(5);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_stream_example() {
+        let data = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"),
+                                         "/../examples/cube_hole.step"))
+            .unwrap();
+        let flat = StepFile::strip_flatten(&data);
+        let expected = records(&flat);
+
+        let mut p = StreamParser::new(Trickle(&data));
+        let first = p.next().unwrap().unwrap();
+        assert_eq!(p.header().unwrap(), Header::parse(&flat));
+
+        let instances: Vec<Instance> = std::iter::once(Ok(first))
+            .chain(p)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(instances.len(), expected.len());
+        for (i, r) in instances.iter().zip(&expected) {
+            assert_eq!(i.record().as_ref(), Some(r));
+        }
+        let s = StepFile::parse(&flat);
+        for i in &instances {
+            let (id, e) = i.entity().unwrap();
+            assert_eq!(format!("{:?}", e), format!("{:?}", s.entities[id]));
+        }
+    }
+
+    #[test]
+    fn test_stream_comments() {
+        let data = b"ISO-10303-21;
+HEADER; /* a comment; with a semicolon */
+FILE_NAME('a;b','',(''),(''),'','','');
+ENDSEC;
+DATA;
+#1 = CARTESIAN_POINT ( 'a /* not */ a' , ( 1. , 2. , 3. ) ) ;
+/* #2=CARTESIAN_POINT('',(0.,0.,0.)); */
+#3=DIRECTION('long
+name',(0.,0.,1.));#4=VECTOR('',#3,2.5/**/);
+ENDSEC;
+END-ISO-10303-21;";
+        let mut p = StreamParser::new(&data[..]);
+        assert!(p.header().is_none());
+        let instances: Vec<Instance> = p.by_ref()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(p.header().unwrap().name, "a;b");
+        let text: Vec<&[u8]> = instances.iter().map(|i| i.text()).collect();
+        assert_eq!(text, [
+            &b"#1=CARTESIAN_POINT('a /* not */ a',(1.,2.,3.));"[..],
+            b"#3=DIRECTION('longname',(0.,0.,1.));",
+            b"#4=VECTOR('',#3,2.5);"]);
+        let lines: Vec<usize> = instances.iter().map(|i| i.line()).collect();
+        assert_eq!(lines, [6, 8, 9]);
+        assert!(matches!(instances[2].entity(), Some((4, Entity::Vector(_)))));
+    }
+}
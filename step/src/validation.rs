@@ -0,0 +1,260 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{raw::Record, writer::Param};
+
+/// Name of the `PROPERTY_DEFINITION`s which hold validation properties, as
+/// given by the CAx-IF recommended practices
+const GVP_NAME: &str = "geometric validation property";
+
+/// Entities which hold the geometry that the triangulator draws
+const SOLIDS: &[&str] = &[
+    "MANIFOLD_SOLID_BREP", "BREP_WITH_VOIDS", "SHELL_BASED_SURFACE_MODEL",
+];
+
+/// A value written by the authoring system, in the file's units
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ValidationValue {
+    Volume(f64),
+    Area(f64),
+    Centroid([f64; 3]),
+}
+
+impl ValidationValue {
+    /// Returns the name of the property, e.g. `"volume"`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ValidationValue::Volume(_) => "volume",
+            ValidationValue::Area(_) => "area",
+            ValidationValue::Centroid(_) => "centroid",
+        }
+    }
+}
+
+/// A geometric validation property (volume, surface area, or centroid),
+/// which the authoring system stored so that a translation can be checked
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationProperty {
+    /// ID of the `PROPERTY_DEFINITION`
+    pub id: usize,
+    /// Name of the product which the property describes, if it's attached
+    /// to a product's shape
+    pub product: Option<String>,
+    /// Solids (by entity ID) which make up the product's shape.  This is
+    /// empty for properties of an assembly, or of anything else whose
+    /// solids can't be found, which describe the whole model instead.
+    pub solids: Vec<usize>,
+    pub value: ValidationValue,
+}
+
+/// Reads every geometric validation property from a file's instances (e.g.
+/// from [`records`](crate::raw::records)).  Values are read from
+/// `VOLUME_MEASURE` and `AREA_MEASURE` items, and from `CARTESIAN_POINT`s
+/// for centroids, in both simple and complex instances; their units aren't
+/// converted.
+pub fn validation_properties(records: &[Record]) -> Vec<ValidationProperty> {
+    let by_id: HashMap<usize, &Record> = records.iter()
+        .map(|r| (r.id, r))
+        .collect();
+    let part = |id: &usize, name: &str| by_id.get(id)
+        .and_then(|r| r.parts.iter().find(|p| p.0 == name))
+        .map(|p| p.1.as_slice());
+
+    // Shape representations of each PRODUCT_DEFINITION_SHAPE, and links
+    // between representations which don't place one inside another
+    let mut shapes: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut links: HashMap<usize, Vec<usize>> = HashMap::new();
+    for r in records {
+        if let Some([Param::Ref(d), Param::Ref(rep)]) =
+            part(&r.id, "SHAPE_DEFINITION_REPRESENTATION")
+        {
+            shapes.entry(*d).or_default().push(*rep);
+        }
+        let placed = r.parts.iter()
+            .any(|p| p.0 == "REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION");
+        let rel = part(&r.id, "SHAPE_REPRESENTATION_RELATIONSHIP")
+            .or_else(|| part(&r.id, "REPRESENTATION_RELATIONSHIP"));
+        if let (false, Some([_, _, Param::Ref(a), Param::Ref(b)])) = (placed, rel) {
+            links.entry(*a).or_default().push(*b);
+            links.entry(*b).or_default().push(*a);
+        }
+    }
+
+    let mut out = Vec::new();
+    for r in records {
+        let (pd, rep) = match part(&r.id, "PROPERTY_DEFINITION_REPRESENTATION") {
+            Some([Param::Ref(pd), Param::Ref(rep)]) => (*pd, *rep),
+            _ => continue,
+        };
+        let definition = match part(&pd, "PROPERTY_DEFINITION") {
+            Some([Param::String(name), _, Param::Ref(d)])
+                if name.eq_ignore_ascii_case(GVP_NAME) => *d,
+            _ => continue,
+        };
+        let items = match by_id.get(&rep).and_then(|r| r.parts.iter()
+            .find_map(|p| match p.1.get(1) {
+                Some(Param::List(items)) => Some(items),
+                _ => None,
+            }))
+        {
+            Some(items) => items,
+            None => continue,
+        };
+
+        // Find the product and its solids, if the property is attached to
+        // a product's shape
+        let product = part(&definition, "PRODUCT_DEFINITION_SHAPE")
+            .and_then(|p| ref_at(p, 2))
+            .and_then(|p| part(&p, "PRODUCT_DEFINITION"))
+            .and_then(|p| ref_at(p, 2))
+            .and_then(|f| by_id.get(&f))
+            .and_then(|f| f.parts.iter().find_map(|p| ref_at(&p.1, 2)))
+            .and_then(|p| part(&p, "PRODUCT"))
+            .and_then(|p| match p.get(1) {
+                Some(Param::String(s)) => Some(s.clone()),
+                _ => None,
+            });
+        let mut solids = Vec::new();
+        let mut seen = HashSet::new();
+        let mut todo = shapes.get(&definition).cloned().unwrap_or_default();
+        while let Some(rep) = todo.pop() {
+            if !seen.insert(rep) {
+                continue;
+            }
+            todo.extend(links.get(&rep).into_iter().flatten());
+            let items = by_id.get(&rep).and_then(|r| r.parts.iter()
+                .find_map(|p| match p.1.get(1) {
+                    Some(Param::List(items)) => Some(items),
+                    _ => None,
+                }));
+            for i in items.into_iter().flatten() {
+                if let Param::Ref(i) = i {
+                    let is_solid = by_id.get(i).map(|r| r.parts.iter()
+                        .any(|p| SOLIDS.contains(&p.0.as_str())))
+                        .unwrap_or(false);
+                    if is_solid && !solids.contains(i) {
+                        solids.push(*i);
+                    }
+                }
+            }
+        }
+        solids.sort_unstable();
+
+        for i in items {
+            let value = match i {
+                Param::Ref(i) => by_id.get(i).and_then(|r| value(r)),
+                _ => None,
+            };
+            if let Some(value) = value {
+                out.push(ValidationProperty {
+                    id: pd,
+                    product: product.clone(),
+                    solids: solids.clone(),
+                    value,
+                });
+            }
+        }
+    }
+    out
+}
+
+fn ref_at(ps: &[Param], i: usize) -> Option<usize> {
+    match ps.get(i) {
+        Some(Param::Ref(r)) => Some(*r),
+        _ => None,
+    }
+}
+
+/// Reads the value of a validation property's representation item
+fn value(r: &Record) -> Option<ValidationValue> {
+    for (name, ps) in &r.parts {
+        if name == "CARTESIAN_POINT" {
+            if let Some(Param::List(xyz)) = ps.get(1) {
+                let xyz: Vec<f64> = xyz.iter().filter_map(real).collect();
+                if let [x, y, z] = xyz[..] {
+                    return Some(ValidationValue::Centroid([x, y, z]));
+                }
+            }
+        }
+        for p in ps {
+            if let Param::Typed(t, v) = p {
+                let v = match real(v) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                if t.eq_ignore_ascii_case("VOLUME_MEASURE") {
+                    return Some(ValidationValue::Volume(v));
+                } else if t.eq_ignore_ascii_case("AREA_MEASURE") {
+                    return Some(ValidationValue::Area(v));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn real(p: &Param) -> Option<f64> {
+    match p {
+        Param::Real(v) => Some(*v),
+        Param::Integer(i) => Some(*i as f64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{raw::records, step_file::StepFile};
+
+    #[test]
+    fn test_validation_properties() {
+        let data = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=PRODUCT('P1','bracket','',());
+#2=PRODUCT_DEFINITION_FORMATION_WITH_SPECIFIED_SOURCE('','',#1,.MADE.);
+#3=PRODUCT_DEFINITION('design','',#2,$);
+#4=PRODUCT_DEFINITION_SHAPE('','',#3);
+#5=SHAPE_REPRESENTATION('',(#6),#9);
+#6=AXIS2_PLACEMENT_3D('',$,$,$);
+#7=ADVANCED_BREP_SHAPE_REPRESENTATION('',(#6,#8),#9);
+#8=MANIFOLD_SOLID_BREP('',$);
+#9=REPRESENTATION_CONTEXT('','');
+#10=SHAPE_DEFINITION_REPRESENTATION(#4,#5);
+#11=SHAPE_REPRESENTATION_RELATIONSHIP('','',#5,#7);
+#20=PROPERTY_DEFINITION('geometric validation property','volume',#4);
+#21=PROPERTY_DEFINITION_REPRESENTATION(#20,#22);
+#22=REPRESENTATION('volume',(#23),#9);
+#23=MEASURE_REPRESENTATION_ITEM('volume measure',VOLUME_MEASURE(1000.),#30);
+#24=PROPERTY_DEFINITION('geometric validation property','area',#4);
+#25=PROPERTY_DEFINITION_REPRESENTATION(#24,#26);
+#26=REPRESENTATION('surface area',(#27),#9);
+#27=(AREA_MEASURE_WITH_UNIT()MEASURE_REPRESENTATION_ITEM()MEASURE_WITH_UNIT(AREA_MEASURE(600),#30)REPRESENTATION_ITEM('surface area measure'));
+#28=PROPERTY_DEFINITION('geometric validation property','centroid',#40);
+#29=PROPERTY_DEFINITION_REPRESENTATION(#28,#31);
+#30=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+#31=REPRESENTATION('centroid',(#32),#9);
+#32=CARTESIAN_POINT('centre point',(5.,5.,5.));
+#40=PRODUCT_DEFINITION_SHAPE('','',$);
+#41=PROPERTY_DEFINITION('mass','',#4);
+#42=PROPERTY_DEFINITION_REPRESENTATION(#41,#22);
+ENDSEC;
+END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let props = validation_properties(&records(&flat));
+        assert_eq!(props.len(), 3);
+        assert_eq!(props[0], ValidationProperty {
+            id: 20,
+            product: Some("bracket".to_owned()),
+            solids: vec![8],
+            value: ValidationValue::Volume(1000.0),
+        });
+        assert_eq!(props[1].value, ValidationValue::Area(600.0));
+        assert_eq!(props[1].solids, [8]);
+        assert_eq!(props[2].id, 28);
+        assert_eq!(props[2].product, None);
+        assert!(props[2].solids.is_empty());
+        assert_eq!(props[2].value, ValidationValue::Centroid([5.0; 3]));
+        assert_eq!(props[2].value.kind(), "centroid");
+    }
+}
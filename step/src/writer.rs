@@ -0,0 +1,436 @@
+use std::fmt::Write;
+
+use crate::header::Header;
+
+/// A single parameter of an entity instance, as written in a Part 21 file
+#[derive(Clone, Debug, PartialEq)]
+pub enum Param {
+    /// Unset optional value (`$`)
+    Unset,
+    /// Value which is derived by a supertype (`*`)
+    Derived,
+    /// Reference to another entity instance (`#123`)
+    Ref(usize),
+    Integer(i64),
+    Real(f64),
+    String(String),
+    /// Enumeration value, without the surrounding dots (`.METRE.`)
+    Enum(String),
+    /// Logical or boolean value (`.T.`, `.F.`, or `.U.`)
+    Logical(Option<bool>),
+    List(Vec<Param>),
+    /// Value of a defined type, e.g. `LENGTH_MEASURE(1.E-6)`
+    Typed(String, Box<Param>),
+}
+
+impl Param {
+    /// Builds a list of references
+    pub fn refs(ids: &[usize]) -> Self {
+        Param::List(ids.iter().map(|i| Param::Ref(*i)).collect())
+    }
+    /// Builds a list of reals
+    pub fn reals(vs: &[f64]) -> Self {
+        Param::List(vs.iter().map(|v| Param::Real(*v)).collect())
+    }
+    pub fn str(s: &str) -> Self {
+        Param::String(s.to_owned())
+    }
+    pub fn enum_(s: &str) -> Self {
+        Param::Enum(s.to_owned())
+    }
+    pub fn bool(b: bool) -> Self {
+        Param::Logical(Some(b))
+    }
+    pub fn typed(name: &str, p: Param) -> Self {
+        Param::Typed(name.to_owned(), Box::new(p))
+    }
+
+    pub(crate) fn write(&self, out: &mut String) {
+        self.write_with(out, RealFormat::default())
+    }
+
+    /// Writes the parameter, formatting reals as given
+    pub(crate) fn write_with(&self, out: &mut String, reals: RealFormat) {
+        match self {
+            Param::Unset => out.push('$'),
+            Param::Derived => out.push('*'),
+            Param::Ref(i) => write!(out, "#{}", i).unwrap(),
+            Param::Integer(i) => write!(out, "{}", i).unwrap(),
+            Param::Real(f) => out.push_str(&reals.format(*f)),
+            Param::String(s) => {
+                out.push('\'');
+                out.push_str(&s.replace('\'', "''"));
+                out.push('\'');
+            },
+            Param::Enum(e) => write!(out, ".{}.", e).unwrap(),
+            Param::Logical(Some(true)) => out.push_str(".T."),
+            Param::Logical(Some(false)) => out.push_str(".F."),
+            Param::Logical(None) => out.push_str(".U."),
+            Param::List(v) => {
+                out.push('(');
+                write_params(v, out, reals);
+                out.push(')');
+            },
+            Param::Typed(name, p) => {
+                out.push_str(name);
+                out.push('(');
+                p.write_with(out, reals);
+                out.push(')');
+            },
+        }
+    }
+}
+
+fn write_params(ps: &[Param], out: &mut String, reals: RealFormat) {
+    for (i, p) in ps.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        p.write_with(out, reals);
+    }
+}
+
+/// How reals are written by [`StepWriter`]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum RealFormat {
+    /// The shortest text which reads back as exactly the same value
+    /// (including the sign of zero), e.g. `0.1` or `1.E-7`
+    #[default]
+    Shortest,
+    /// Rounded to this many digits after the decimal point, with trailing
+    /// zeros removed.  This gives tidier files when the values are known to
+    /// be imprecise, but doesn't round-trip, and values smaller than the
+    /// last digit are written as zero.
+    Fixed(usize),
+}
+
+impl RealFormat {
+    /// Formats a real number in Part 21 syntax, which requires a decimal
+    /// point (e.g. `1.`, `0.25`, `1.E-7`).  The output only depends on the
+    /// value, never on the platform or locale.
+    ///
+    /// Part 21 has no syntax for non-finite values.  Infinities (e.g. from a
+    /// literal like `1.E999`, which overflows when it's parsed) are written
+    /// as the largest finite real with the same sign, and NaN is written as
+    /// `$` (unset).
+    pub fn format(self, f: f64) -> String {
+        if f.is_nan() {
+            return "$".to_owned();
+        }
+        let f = f.clamp(f64::MIN, f64::MAX);
+        match self {
+            RealFormat::Shortest => {
+                let mut buf = ryu::Buffer::new();
+                let s = buf.format_finite(f);
+                let (mantissa, exponent) = match s.find('e') {
+                    Some(i) => (&s[..i], &s[i + 1..]),
+                    None => (s, ""),
+                };
+                let mut out = mantissa.strip_suffix('0')
+                    .filter(|m| m.ends_with('.'))
+                    .unwrap_or(mantissa)
+                    .to_owned();
+                if !out.contains('.') {
+                    out.push('.');
+                }
+                if !exponent.is_empty() {
+                    out.push('E');
+                    out.push_str(exponent);
+                }
+                out
+            },
+            RealFormat::Fixed(digits) => {
+                let mut out = format!("{:.*}", digits, f);
+                if out.contains('.') {
+                    let n = out.trim_end_matches('0').len();
+                    out.truncate(n);
+                } else {
+                    out.push('.');
+                }
+                out
+            },
+        }
+    }
+}
+
+/// Formats a real number in Part 21 syntax, using the shortest text which
+/// reads back exactly (see [`RealFormat::Shortest`])
+pub fn format_real(f: f64) -> String {
+    RealFormat::Shortest.format(f)
+}
+
+/// Builds a STEP file from scratch, assigning entity IDs in order
+///
+/// ```
+/// use step::{ap214::Entity, id::Id, step_file::StepFile, writer::*};
+///
+/// let mut w = StepWriter::new("points.step");
+/// let a = w.add("CARTESIAN_POINT", &[Param::str("a"), Param::reals(&[0.0; 3])]);
+/// let b = w.add("CARTESIAN_POINT", &[Param::str("b"), Param::reals(&[1.0; 3])]);
+/// w.add("POLYLINE", &[Param::str(""), Param::refs(&[a, b])]);
+///
+/// let text = w.to_string();
+/// let flat = StepFile::strip_flatten(text.as_bytes());
+/// let s = StepFile::parse(&flat);
+/// assert!(matches!(s[Id::<()>::new(3)], Entity::Polyline(_)));
+/// ```
+pub struct StepWriter {
+    /// Written to the `FILE_NAME` header entity
+    pub name: String,
+    /// Written to the `FILE_SCHEMA` header entity
+    pub schema: String,
+    /// The other `FILE_DESCRIPTION` and `FILE_NAME` attributes, e.g. the
+    /// authors.  Its `name` and `schemas` aren't used, since `name` and
+    /// `schema` above take their place.
+    pub header: Header,
+    /// Used for reals in entities which are added after it's set
+    pub reals: RealFormat,
+    /// Data section, with one instance (minus the leading ID) per item
+    data: Vec<String>,
+}
+
+impl StepWriter {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            schema: "AUTOMOTIVE_DESIGN".to_owned(),
+            header: Header::default(),
+            reals: RealFormat::default(),
+            data: Vec::new(),
+        }
+    }
+
+    /// Adds a simple entity instance, returning its ID
+    pub fn add(&mut self, name: &str, params: &[Param]) -> usize {
+        let mut out = name.to_owned();
+        out.push('(');
+        write_params(params, &mut out, self.reals);
+        out.push(')');
+        self.data.push(out);
+        self.data.len()
+    }
+
+    /// Adds a complex entity instance, made of multiple partial entities
+    /// (which must be listed in alphabetical order), returning its ID
+    pub fn add_complex(&mut self, parts: &[(&str, &[Param])]) -> usize {
+        let mut out = "(".to_owned();
+        for (name, params) in parts {
+            out.push_str(name);
+            out.push('(');
+            write_params(params, &mut out, self.reals);
+            out.push(')');
+        }
+        out.push(')');
+        self.data.push(out);
+        self.data.len()
+    }
+
+    /// Returns the number of entities which have been added so far
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// Renders the complete file, including headers
+impl std::fmt::Display for StepWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Empty lists are written with a single empty string, as exporters
+        // usually do
+        let list = |v: &[String]| {
+            let mut out = String::new();
+            match v {
+                [] => Param::List(vec![Param::str("")]),
+                v => Param::List(v.iter().map(|s| Param::str(s)).collect()),
+            }.write(&mut out);
+            out
+        };
+        let string = |s: &str| {
+            let mut out = String::new();
+            Param::str(s).write(&mut out);
+            out
+        };
+        let h = &self.header;
+        writeln!(f, "ISO-10303-21;\nHEADER;")?;
+        writeln!(f, "FILE_DESCRIPTION({},{});", list(&h.description),
+                 string(match h.implementation_level.as_str() {
+                     "" => "2;1",
+                     s => s,
+                 }))?;
+        writeln!(f, "FILE_NAME({},{},{},{},{},{},{});", string(&self.name),
+                 string(&h.time_stamp), list(&h.author),
+                 list(&h.organization), string(&h.preprocessor_version),
+                 string(&h.originating_system), string(&h.authorization))?;
+        writeln!(f, "FILE_SCHEMA(('{}'));", self.schema)?;
+        writeln!(f, "ENDSEC;\nDATA;")?;
+        for (i, d) in self.data.iter().enumerate() {
+            writeln!(f, "#{}={};", i + 1, d)?;
+        }
+        writeln!(f, "ENDSEC;\nEND-ISO-10303-21;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ap214::Entity, step_file::StepFile};
+
+    #[test]
+    fn test_format_real() {
+        assert_eq!(format_real(0.0), "0.");
+        assert_eq!(format_real(1.0), "1.");
+        assert_eq!(format_real(-0.25), "-0.25");
+        assert_eq!(format_real(1e-7), "1.E-7");
+        assert_eq!(format_real(1.5e20), "1.5E20");
+        assert_eq!(format_real(-0.0), "-0.");
+        assert_eq!(format_real(100.0), "100.");
+        assert_eq!(format_real(1e16), "1.E16");
+        assert_eq!(format_real(0.1 + 0.2), "0.30000000000000004");
+    }
+
+    #[test]
+    fn test_format_fixed() {
+        let f = RealFormat::Fixed(3);
+        assert_eq!(f.format(0.0), "0.");
+        assert_eq!(f.format(2.0), "2.");
+        assert_eq!(f.format(-0.25), "-0.25");
+        assert_eq!(f.format(0.1 + 0.2), "0.3");
+        assert_eq!(f.format(1.23456), "1.235");
+        assert_eq!(f.format(1e-7), "0.");
+        assert_eq!(f.format(1.5e20), "150000000000000000000.");
+        assert_eq!(RealFormat::Fixed(0).format(2.5), "2.");
+
+        let mut w = StepWriter::new("test");
+        w.reals = f;
+        w.add("CARTESIAN_POINT", &[
+            Param::str(""), Param::reals(&[1.0 / 3.0, 2.0, -1e-9])]);
+        assert!(w.to_string()
+            .contains("#1=CARTESIAN_POINT('',(0.333,2.,-0.));"));
+    }
+
+    #[test]
+    fn test_format_non_finite() {
+        assert_eq!(format_real(f64::INFINITY), format_real(f64::MAX));
+        assert_eq!(format_real(f64::NEG_INFINITY), format_real(f64::MIN));
+        assert_eq!(format_real(f64::NAN), "$");
+        let f = RealFormat::Fixed(3);
+        assert_eq!(f.format(f64::INFINITY), f.format(f64::MAX));
+        assert_eq!(f.format(f64::NAN), "$");
+
+        // Overflowed reals are parsed as infinities, and must survive being
+        // written back out
+        let text = "ISO-10303-21;HEADER;ENDSEC;DATA;\
+                    #1=CARTESIAN_POINT('',(1.E999,-1.E999,0.));ENDSEC;\
+                    END-ISO-10303-21;";
+        let mut w = StepWriter::new("test");
+        for r in crate::raw::records(text.as_bytes()) {
+            w.add(&r.parts[0].0, &r.parts[0].1);
+        }
+        let out = w.to_string();
+        let flat = StepFile::strip_flatten(out.as_bytes());
+        let step = StepFile::parse(&flat);
+        match &step.entities[1] {
+            Entity::CartesianPoint(p) => assert_eq!(
+                p.coordinates.iter().map(|c| c.0).collect::<Vec<_>>(),
+                [f64::MAX, f64::MIN, 0.0]),
+            e => panic!("Unexpected entity {:?}", e),
+        }
+    }
+
+    /// Every finite value must read back with exactly the same bits
+    #[test]
+    fn test_format_bits() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut values = vec![0.0, -0.0, f64::MIN_POSITIVE, f64::MAX,
+                              f64::MIN, f64::EPSILON, 5e-324, -5e-324];
+        for _ in 0..100_000 {
+            // xorshift64, so that the test is reproducible
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            values.push(f64::from_bits(seed));
+        }
+        for f in values.into_iter().filter(|f| f.is_finite()) {
+            let s = format_real(f);
+            assert!(s.contains('.'), "{} has no decimal point", s);
+            let g = crate::real::parse(&s).unwrap();
+            assert_eq!(f.to_bits(), g.to_bits(), "{:?} was written as {}",
+                       f, s);
+        }
+    }
+
+    /// Rewrites every instance in the example files, then checks that the
+    /// result parses to exactly the same values
+    #[test]
+    fn test_round_trip_examples() {
+        use crate::raw::{records, Record};
+        fn write(r: &Record) -> String {
+            let part = |out: &mut String, (name, ps): &(String, Vec<Param>)| {
+                out.push_str(name);
+                Param::List(ps.clone()).write(out);
+            };
+            let mut out = format!("#{}=", r.id);
+            if let [p] = r.parts.as_slice() {
+                part(&mut out, p);
+            } else {
+                out.push('(');
+                r.parts.iter().for_each(|p| part(&mut out, p));
+                out.push(')');
+            }
+            out.push(';');
+            out
+        }
+        for name in &["abstract_pca", "cube_hole", "cuboid"] {
+            let path = format!("{}/../examples/{}.step",
+                               env!("CARGO_MANIFEST_DIR"), name);
+            let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+            let before = records(&flat);
+            assert!(!before.is_empty());
+
+            let text = format!(
+                "ISO-10303-21;HEADER;ENDSEC;DATA;{}ENDSEC;END-ISO-10303-21;",
+                before.iter().map(write).collect::<String>());
+            let after = records(text.as_bytes());
+            // Debug formatting distinguishes every pair of distinct reals
+            // (including 0 and -0), unlike ==
+            assert_eq!(format!("{:?}", before), format!("{:?}", after));
+            assert_eq!(format!("{:?}", StepFile::parse(&flat).entities),
+                       format!("{:?}",
+                               StepFile::parse(text.as_bytes()).entities));
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut w = StepWriter::new("test");
+        let p = w.add("CARTESIAN_POINT", &[
+            Param::str("origin"), Param::reals(&[1.0, -0.5, 1e-7])]);
+        let d = w.add("DIRECTION", &[
+            Param::str(""), Param::reals(&[0.0, 0.0, 1.0])]);
+        let a = w.add("AXIS2_PLACEMENT_3D", &[
+            Param::str(""), Param::Ref(p), Param::Ref(d), Param::Unset]);
+        assert_eq!(w.len(), 3);
+
+        let text = w.to_string();
+        let flat = StepFile::strip_flatten(text.as_bytes());
+        let step = StepFile::parse(&flat);
+        match &step.entities[p] {
+            Entity::CartesianPoint(c) => {
+                let v: Vec<f64> = c.coordinates.iter().map(|c| c.0).collect();
+                assert_eq!(v, vec![1.0, -0.5, 1e-7]);
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+        match &step.entities[a] {
+            Entity::Axis2Placement3d(c) => {
+                assert_eq!(c.location.0, p);
+                assert_eq!(c.axis.get().map(|a| a.0), Some(d));
+                assert!(c.ref_direction.is_unset());
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,584 @@
+//! Reads STEP-XML (ISO 10303-28) instance files.
+//!
+//! The XML is translated into Part 21 text, which is then parsed like any
+//! other STEP file, so the rest of the crate never sees the difference.
+//! [`StepFile::read`](crate::step_file::StepFile::read) and
+//! [`StepFile::open_mmap`](crate::step_file::StepFile::open_mmap) do this
+//! automatically.
+//!
+//! This follows the default (unconfigured) binding of Part 28 edition 2:
+//! - Instances are the children of a `uos` element, named after their
+//!   entity (`Cartesian_point`) and identified by an `id` attribute.
+//!   Complex instances join their entity names with `-`.
+//! - Each attribute is a child element named after the attribute
+//!   (`Coordinates`), which may be left out if it's unset.
+//! - References are elements with a `ref` attribute, e.g.
+//!   `<Location><Cartesian_point ref="i1"/></Location>`.  Instances may also
+//!   be nested directly inside the attribute which refers to them.
+//! - Lists of simple values are written as space-separated text; other
+//!   aggregates have one element per member.
+//! - Values of defined types in a `SELECT` are wrapped in an element named
+//!   after the type, e.g. `<Length_measure-wrapper>2.5</...>`.
+//!
+//! Attributes are matched to their Part 21 positions with the generated
+//! schema tables (see [`attributes_of`]), so only AP214 files can be read.
+use std::collections::HashMap;
+
+use xml::reader::{ParserConfig, XmlEvent};
+
+use crate::{
+    ap214::{attributes_of, superclasses_of},
+    check::ParamKind,
+    real,
+    writer::Param,
+};
+
+/// Error reading a STEP-XML file
+#[derive(Debug)]
+pub enum Error {
+    /// The file isn't well-formed XML
+    Xml(xml::reader::Error),
+    /// There's no `uos` element holding the instances
+    NoData,
+    /// An instance's element doesn't name an entity in the schema
+    UnknownEntity(String),
+    /// A `ref` attribute names an instance which isn't in the file
+    UnknownRef(String),
+    /// An instance has a child element which isn't one of its attributes
+    UnknownAttribute { entity: String, attribute: String },
+    /// An attribute's text can't be read as the attribute's type
+    BadValue { entity: String, attribute: String, text: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::Xml(e) => write!(f, "Invalid XML: {}", e),
+            Error::NoData => write!(f, "No uos element"),
+            Error::UnknownEntity(e) => write!(f, "Unknown entity {}", e),
+            Error::UnknownRef(r) => write!(f, "Reference to missing instance {}", r),
+            Error::UnknownAttribute { entity, attribute } =>
+                write!(f, "{} has no attribute {}", entity, attribute),
+            Error::BadValue { entity, attribute, text } =>
+                write!(f, "Invalid value {:?} for {}.{}", text, entity, attribute),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Checks whether the data looks like an XML document, rather than a
+/// Part 21 file (which begins with `ISO-10303-21;`)
+pub fn is_xml(data: &[u8]) -> bool {
+    let data = data.strip_prefix(b"\xef\xbb\xbf").unwrap_or(data);
+    data.iter().find(|c| !c.is_ascii_whitespace()) == Some(&b'<')
+}
+
+/// Translates a STEP-XML file into Part 21 text, which should then be
+/// flattened and parsed as usual.
+///
+/// ```
+/// use step::{ap214::Entity, step_file::StepFile, xml::to_part21};
+/// let data = br#"<?xml version="1.0"?>
+/// <iso_10303_28 version="2.0">
+///   <uos id="uos_1">
+///     <Cartesian_point id="i1">
+///       <Name>origin</Name>
+///       <Coordinates>0 1 2.5</Coordinates>
+///     </Cartesian_point>
+///   </uos>
+/// </iso_10303_28>"#;
+/// let text = to_part21(data).unwrap();
+/// let flat = StepFile::strip_flatten(&text);
+/// let s = StepFile::parse(&flat);
+/// match &s.entities()[1] {
+///     Entity::CartesianPoint(p) => assert_eq!(p.coordinates[2].0, 2.5),
+///     e => panic!("Unexpected entity {:?}", e),
+/// }
+/// ```
+pub fn to_part21(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let root = Element::parse(data)?;
+    let mut uos = Vec::new();
+    root.find("uos", &mut uos);
+    if uos.is_empty() {
+        return Err(Error::NoData);
+    }
+
+    // IDs are assigned in document order, before anything is converted, so
+    // that references may point forwards
+    let mut c = Converter::default();
+    for u in &uos {
+        for e in &u.children {
+            c.number(e);
+        }
+    }
+    for u in &uos {
+        for e in &u.children {
+            c.instance(e)?;
+        }
+    }
+    c.out.sort_by_key(|(id, _)| *id);
+
+    let mut headers = Vec::new();
+    root.find("iso_10303_28_header", &mut headers);
+    let field = |name: &str| headers.first()
+        .and_then(|h| h.child(name))
+        .map(|e| e.text.trim())
+        .unwrap_or("");
+    let mut out = String::new();
+    out.push_str("ISO-10303-21;\nHEADER;\nFILE_DESCRIPTION((''),'2;1');\n");
+    out.push_str("FILE_NAME(");
+    for (i, f) in ["name", "time_stamp", "author", "organization",
+                   "preprocessor_version", "originating_system",
+                   "authorization"].iter().enumerate()
+    {
+        if i > 0 {
+            out.push(',');
+        }
+        let p = Param::str(field(f));
+        if *f == "author" || *f == "organization" {
+            Param::List(vec![p]).write(&mut out);
+        } else {
+            p.write(&mut out);
+        }
+    }
+    out.push_str(");\nFILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\nENDSEC;\nDATA;\n");
+    for (id, text) in &c.out {
+        out.push_str(&format!("#{}={};\n", id, text));
+    }
+    out.push_str("ENDSEC;\nEND-ISO-10303-21;\n");
+    Ok(out.into_bytes())
+}
+
+/// An XML element, with namespace prefixes removed from its name and
+/// attributes
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        let reader = ParserConfig::new()
+            .cdata_to_characters(true)
+            .ignore_comments(true)
+            .create_reader(data);
+        let mut stack = vec![Element::default()];
+        for event in reader {
+            match event.map_err(Error::Xml)? {
+                XmlEvent::StartElement { name, attributes, .. } =>
+                    stack.push(Element {
+                        name: name.local_name,
+                        attributes: attributes.into_iter()
+                            .map(|a| (a.name.local_name, a.value))
+                            .collect(),
+                        ..Element::default()
+                    }),
+                XmlEvent::EndElement { .. } => {
+                    let e = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(e);
+                },
+                XmlEvent::Characters(s) =>
+                    stack.last_mut().unwrap().text.push_str(&s),
+                _ => (),
+            }
+        }
+        // The reader checks that the document has exactly one root
+        Ok(stack.pop().unwrap().children.pop().unwrap_or_default())
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Collects every element with the given name (not looking inside them)
+    fn find<'a>(&'a self, name: &str, out: &mut Vec<&'a Element>) {
+        if self.name == name {
+            out.push(self);
+        } else {
+            for c in &self.children {
+                c.find(name, out);
+            }
+        }
+    }
+}
+
+/// Returns the entity names of an instance's element, sorted as they're
+/// written in an external mapping, or `None` if it isn't an instance
+fn entity_names(element: &str) -> Option<Vec<String>> {
+    let mut names: Vec<String> = element.split('-')
+        .map(|n| n.to_ascii_uppercase())
+        .collect();
+    if names.iter().all(|n| attributes_of(n).is_some()) {
+        names.sort();
+        Some(names)
+    } else {
+        None
+    }
+}
+
+/// Attributes which an entity declares itself, rather than inheriting
+fn own_attributes(name: &str) -> Vec<(&'static str, ParamKind)> {
+    let inherited: Vec<&str> = superclasses_of(name).iter()
+        .filter_map(|s| attributes_of(s))
+        .flat_map(|a| a.iter().map(|(n, _, _)| *n))
+        .collect();
+    attributes_of(name).unwrap_or(&[]).iter()
+        .filter(|(n, _, _)| !inherited.contains(n))
+        .map(|(n, k, _)| (*n, *k))
+        .collect()
+}
+
+#[derive(Default)]
+struct Converter {
+    ids: HashMap<String, usize>,
+    next: usize,
+    /// Each instance's ID and Part 21 text (without the ID)
+    out: Vec<(usize, String)>,
+}
+
+impl Converter {
+    fn fresh_id(&mut self) -> usize {
+        self.next += 1;
+        self.next
+    }
+
+    /// Assigns IDs to this instance and any nested inside it
+    fn number(&mut self, e: &Element) {
+        if let Some(id) = e.attribute("id") {
+            if entity_names(&e.name).is_some() && !self.ids.contains_key(id) {
+                let n = self.fresh_id();
+                self.ids.insert(id.to_owned(), n);
+            }
+        }
+        for c in &e.children {
+            self.number(c);
+        }
+    }
+
+    /// Converts an instance, returning its ID
+    fn instance(&mut self, e: &Element) -> Result<usize, Error> {
+        let names = entity_names(&e.name)
+            .ok_or_else(|| Error::UnknownEntity(e.name.clone()))?;
+        let id = match e.attribute("id").and_then(|i| self.ids.get(i)) {
+            Some(id) => *id,
+            None => self.fresh_id(),
+        };
+
+        // Every child must be an attribute of one of the entities
+        for c in &e.children {
+            let known = names.iter()
+                .flat_map(|n| attributes_of(n).unwrap_or(&[]))
+                .any(|(a, _, _)| c.name.eq_ignore_ascii_case(a));
+            if !known {
+                return Err(Error::UnknownAttribute {
+                    entity: e.name.clone(), attribute: c.name.clone(),
+                });
+            }
+        }
+
+        let mut text = String::new();
+        if let [name] = names.as_slice() {
+            let attrs: Vec<_> = attributes_of(name).unwrap().iter()
+                .map(|(n, k, _)| (*n, *k))
+                .collect();
+            text.push_str(name);
+            self.params(e, &attrs, &[], &mut text)?;
+        } else {
+            // Attributes which any part re-declares as derived are written
+            // as `*` by the part which declares them
+            let derived: Vec<&str> = names.iter()
+                .flat_map(|n| attributes_of(n).unwrap_or(&[]))
+                .filter(|(_, k, _)| *k == ParamKind::Derived)
+                .map(|(n, _, _)| *n)
+                .collect();
+            text.push('(');
+            for name in &names {
+                text.push_str(name);
+                self.params(e, &own_attributes(name), &derived, &mut text)?;
+            }
+            text.push(')');
+        }
+        self.out.push((id, text));
+        Ok(id)
+    }
+
+    /// Writes the parenthesized parameter list for the given attributes
+    fn params(&mut self, e: &Element, attrs: &[(&str, ParamKind)],
+              derived: &[&str], text: &mut String) -> Result<(), Error>
+    {
+        let mut params = Vec::with_capacity(attrs.len());
+        for (name, kind) in attrs {
+            let p = if *kind == ParamKind::Derived || derived.contains(name) {
+                Param::Derived
+            } else if let Some(c) = e.child(name) {
+                self.value((&e.name, &c.name), c, *kind)?
+                    .unwrap_or(Param::Unset)
+            } else {
+                Param::Unset
+            };
+            params.push(p);
+        }
+        Param::List(params).write(text);
+        Ok(())
+    }
+
+    /// Reads the value of an attribute element (or a member of one), where
+    /// `at` is the entity and attribute for error messages.  `Ok(None)`
+    /// means that the attribute is unset.
+    fn value(&mut self, at: (&str, &str), e: &Element, kind: ParamKind)
+        -> Result<Option<Param>, Error>
+    {
+        let empty = e.children.is_empty() && match kind {
+            ParamKind::Str | ParamKind::Binary => false,
+            _ => e.text.trim().is_empty(),
+        };
+        if empty || (e.attribute("nil") == Some("true") && e.children.is_empty()) {
+            return Ok(None);
+        }
+        let p = match (e.children.as_slice(), kind) {
+            ([], _) => scalar_at(at, &e.text, kind)?,
+            ([c], k) if k != ParamKind::List => self.item(at, c)?,
+            (cs, _) => Param::List(cs.iter()
+                .map(|c| self.item(at, c))
+                .collect::<Result<_, _>>()?),
+        };
+        Ok(Some(p))
+    }
+
+    /// Reads one member of an aggregate or `SELECT`
+    fn item(&mut self, at: (&str, &str), e: &Element) -> Result<Param, Error> {
+        if let Some(r) = e.attribute("ref") {
+            return self.ids.get(r).map(|i| Param::Ref(*i))
+                .ok_or_else(|| Error::UnknownRef(r.to_owned()));
+        }
+        if entity_names(&e.name).is_some() {
+            return self.instance(e).map(Param::Ref);
+        }
+        let name = e.name.to_ascii_lowercase();
+        if let Some(base) = name.strip_suffix("-wrapper") {
+            let kind = match base {
+                "double" | "real" | "number" => Some(ParamKind::Real),
+                "integer" => Some(ParamKind::Integer),
+                "string" => Some(ParamKind::Str),
+                "hexbinary" | "binary" => Some(ParamKind::Binary),
+                "boolean" | "logical" => Some(ParamKind::Logical),
+                _ => None,
+            };
+            return match kind {
+                Some(k) => scalar_at(at, &e.text, k),
+                None => {
+                    let inner = self.value(at, e, ParamKind::Any)?
+                        .unwrap_or(Param::Unset);
+                    Ok(Param::typed(&base.to_ascii_uppercase(), inner))
+                },
+            };
+        }
+        if e.children.is_empty() && !["seq-", "set-", "bag-", "array-", "list-"]
+            .iter().any(|p| name.starts_with(p))
+        {
+            return scalar_at(at, &e.text, ParamKind::Any);
+        }
+        self.value(at, e, ParamKind::List).map(|p| p.unwrap_or(Param::Unset))
+    }
+}
+
+/// Reads a simple value from text, returning an error naming the attribute
+/// if that fails
+fn scalar_at(at: (&str, &str), text: &str, kind: ParamKind)
+    -> Result<Param, Error>
+{
+    scalar(text, kind).ok_or_else(|| Error::BadValue {
+        entity: at.0.to_owned(), attribute: at.1.to_owned(),
+        text: text.to_owned(),
+    })
+}
+
+/// Reads a simple value from text
+fn scalar(text: &str, kind: ParamKind) -> Option<Param> {
+    let t = text.trim();
+    let logical = || match t.to_ascii_lowercase().as_str() {
+        "true" | "t" => Some(Param::Logical(Some(true))),
+        "false" | "f" => Some(Param::Logical(Some(false))),
+        "unknown" | "u" => Some(Param::Logical(None)),
+        _ => None,
+    };
+    match kind {
+        ParamKind::Real => real::parse(t).map(Param::Real),
+        ParamKind::Integer => t.parse().ok().map(Param::Integer),
+        ParamKind::Str | ParamKind::Binary => Some(Param::str(text)),
+        ParamKind::Logical => logical(),
+        ParamKind::Enum if !t.is_empty() =>
+            Some(Param::enum_(&t.to_ascii_uppercase())),
+        ParamKind::List => t.split_ascii_whitespace()
+            .map(|s| scalar(s, ParamKind::Any))
+            .collect::<Option<_>>()
+            .map(Param::List),
+        ParamKind::Derived => Some(Param::Derived),
+        ParamKind::Any => {
+            if !real::is_real(t) {
+                if let Ok(i) = t.parse() {
+                    return Some(Param::Integer(i));
+                }
+            }
+            real::parse(t).map(Param::Real)
+                .or_else(logical)
+                .or_else(|| Some(Param::str(text)))
+        },
+        ParamKind::Enum | ParamKind::Ref => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ap214::Entity, step_file::StepFile};
+
+    const SQUARE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<iso_10303_28 xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+              xmlns:exp="urn:oid:1.0.10303.28.2.1.1" version="2.0">
+  <exp:iso_10303_28_header>
+    <exp:name>square.stpx</exp:name>
+    <exp:time_stamp>2021-06-01T12:00:00</exp:time_stamp>
+    <exp:author>someone</exp:author>
+  </exp:iso_10303_28_header>
+  <uos id="uos_1">
+    <!-- units are complex instances -->
+    <Length_unit-Named_unit-Si_unit id="i5">
+      <Prefix>milli</Prefix>
+      <Name>metre</Name>
+    </Length_unit-Named_unit-Si_unit>
+    <Uncertainty_measure_with_unit id="i6">
+      <Value_component><Length_measure-wrapper>1E-7</Length_measure-wrapper></Value_component>
+      <Unit_component><Length_unit-Named_unit-Si_unit ref="i5" xsi:nil="true"/></Unit_component>
+      <Name>distance_accuracy_value</Name>
+      <Description></Description>
+    </Uncertainty_measure_with_unit>
+    <Axis2_placement_3d id="i10">
+      <Name/>
+      <Location><Cartesian_point ref="i11" xsi:nil="true"/></Location>
+      <Axis>
+        <Direction id="i12"><Name/><Direction_ratios>0 0 1</Direction_ratios></Direction>
+      </Axis>
+    </Axis2_placement_3d>
+    <Cartesian_point id="i11">
+      <Name>origin</Name>
+      <Coordinates>0. 1 -2.5E1</Coordinates>
+    </Cartesian_point>
+    <Polyline id="i13">
+      <Name>a'b</Name>
+      <Points>
+        <Cartesian_point ref="i11"/>
+        <Cartesian_point id="i14"><Name/><Coordinates>1 1 1</Coordinates></Cartesian_point>
+      </Points>
+    </Polyline>
+    <Face_outer_bound id="i15">
+      <Name/>
+      <Bound><Edge_loop ref="i99"/></Bound>
+      <Orientation>false</Orientation>
+    </Face_outer_bound>
+  </uos>
+</iso_10303_28>"#;
+
+    #[test]
+    fn test_is_xml() {
+        assert!(is_xml(b"\xef\xbb\xbf  <?xml version=\"1.0\"?>"));
+        assert!(!is_xml(b"ISO-10303-21;"));
+    }
+
+    #[test]
+    fn test_missing_ref() {
+        assert!(matches!(to_part21(SQUARE.as_bytes()),
+                         Err(Error::UnknownRef(r)) if r == "i99"));
+    }
+
+    #[test]
+    fn test_to_part21() {
+        let xml = SQUARE.replace(r#"<Edge_loop ref="i99"/>"#, "");
+        let text = String::from_utf8(to_part21(xml.as_bytes()).unwrap())
+            .unwrap();
+        let data: Vec<&str> = text.lines()
+            .skip_while(|l| *l != "DATA;")
+            .skip(1)
+            .take_while(|l| *l != "ENDSEC;")
+            .collect();
+        assert_eq!(data, [
+            "#1=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));",
+            "#2=UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-7),#1,\
+             'distance_accuracy_value','');",
+            "#3=AXIS2_PLACEMENT_3D('',#5,#4,$);",
+            "#4=DIRECTION('',(0,0,1));",
+            "#5=CARTESIAN_POINT('origin',(0.,1,-25.));",
+            "#6=POLYLINE('a''b',(#5,#7));",
+            "#7=CARTESIAN_POINT('',(1,1,1));",
+            "#8=FACE_OUTER_BOUND('',$,.F.);",
+        ]);
+
+        let flat = StepFile::strip_flatten(text.as_bytes());
+        let s = StepFile::parse(&flat);
+        assert_eq!(s.header().name, "square.stpx");
+        assert!(s.mismatches().is_empty());
+        match &s.entities[3] {
+            Entity::Axis2Placement3d(a) => {
+                assert_eq!(a.location.0, 5);
+                assert_eq!(a.axis.get().map(|a| a.0), Some(4));
+            },
+            e => panic!("Unexpected entity {:?}", e),
+        }
+        assert!(!matches!(s.entities[1], Entity::_FailedToParse));
+        // XML doesn't distinguish 1 from 1.0, but integers are read as reals
+        match &s.entities[4] {
+            Entity::Direction(d) => assert_eq!(d.direction_ratios[2], 1.0),
+            e => panic!("Unexpected entity {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_read_file() {
+        let xml = SQUARE.replace(r#"<Edge_loop ref="i99"/>"#, "");
+        let path = std::env::temp_dir()
+            .join(format!("foxtrot-xml-{}.stpx", std::process::id()));
+        std::fs::write(&path, &xml).unwrap();
+        let read = StepFile::read(&path).unwrap();
+        let mapped = unsafe { StepFile::open_mmap(&path) }.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read, to_part21(xml.as_bytes()).unwrap());
+        assert_eq!(&mapped[..], &StepFile::strip_flatten(&read)[..]);
+        assert_eq!(mapped.parse().iter_by_id().count(), 8);
+    }
+
+    #[test]
+    fn test_errors() {
+        let bad = SQUARE.replace("Polyline", "Polyliner");
+        assert!(matches!(to_part21(bad.as_bytes()),
+                         Err(Error::UnknownEntity(e)) if e == "Polyliner"));
+        let bad = SQUARE.replace("<Coordinates>1 1 1", "<Coords>1 1 1")
+            .replace("1 1 1</Coordinates>", "1 1 1</Coords>");
+        assert!(matches!(to_part21(bad.as_bytes()),
+                         Err(Error::UnknownAttribute { attribute, .. })
+                             if attribute == "Coords"));
+        let bad = SQUARE.replace(r#"<Edge_loop ref="i99"/>"#, "")
+            .replace("<Orientation>false", "<Orientation>maybe");
+        assert!(matches!(to_part21(bad.as_bytes()),
+                         Err(Error::BadValue { attribute, text, .. })
+                             if attribute == "Orientation" && text == "maybe"));
+        assert!(matches!(to_part21(b"<a><b></a>"), Err(Error::Xml(_))));
+        assert!(matches!(to_part21(b"<a/>"), Err(Error::NoData)));
+    }
+}
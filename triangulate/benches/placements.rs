@@ -0,0 +1,143 @@
+//! Measures placement resolution while walking a deeply instanced assembly.
+//!
+//! The synthetic assembly is a binary tree of products, where each level
+//! uses the next level twice, so the bottom level has 2^depth instances but
+//! the file only has two placements per level.  The default depth is 16; to
+//! use a different depth, pass it as an argument:
+//! ```text
+//! cargo bench -p triangulate --bench placements -- 20
+//! ```
+use std::time::{Duration, Instant};
+
+use nalgebra_glm::DMat4;
+use step::{id::Id, step_file::StepFile};
+use triangulate::{
+    assembly::Assembly,
+    placement::{item_defined_transformation, PlacementCache},
+    product::ProductTree,
+};
+
+/// Number of timed runs; the fastest one is reported
+const RUNS: usize = 5;
+
+/// Entity ID of the first entity for each level
+fn level(i: usize) -> usize {
+    1000 + i * 100
+}
+
+/// Entity ID of the `ITEM_DEFINED_TRANSFORMATION` which places the `k`th
+/// use of level `i + 1` in level `i`
+fn transformation(i: usize, k: usize) -> usize {
+    level(i) + 10 + k * 10 + 2
+}
+
+fn synthetic(depth: usize) -> Vec<u8> {
+    let mut out = String::from("ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=DIRECTION('',(1.,0.,0.));
+#4=AXIS2_PLACEMENT_3D('',#1,#2,#3);
+#10=PRODUCT_DEFINITION_CONTEXT('part definition',#9,'design');\n");
+    for i in 0..=depth {
+        let b = level(i);
+        out += &format!("#{0}=PRODUCT('P{1}','level {1}','',());
+#{2}=PRODUCT_DEFINITION_FORMATION('','',#{0});
+#{3}=PRODUCT_DEFINITION('','',#{2},#10);
+#{4}=PRODUCT_DEFINITION_SHAPE('','',#{3});
+#{5}=SHAPE_REPRESENTATION('',(#4),$);
+#{6}=SHAPE_DEFINITION_REPRESENTATION(#{4},#{5});\n",
+            b, i, b + 1, b + 2, b + 3, b + 4, b + 5);
+        if i == depth {
+            break;
+        }
+        let c = level(i + 1);
+        for k in 0..2 {
+            let e = b + 10 + k * 10;
+            out += &format!("#{0}=CARTESIAN_POINT('',({1}.,{2}.,0.));
+#{3}=AXIS2_PLACEMENT_3D('',#{0},#2,#3);
+#{4}=ITEM_DEFINED_TRANSFORMATION('','',#4,#{3});
+#{5}=(REPRESENTATION_RELATIONSHIP('','',#{6},#{7})REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#{4})SHAPE_REPRESENTATION_RELATIONSHIP());
+#{8}=NEXT_ASSEMBLY_USAGE_OCCURRENCE('{9}','','',#{10},#{11},$);
+#{12}=PRODUCT_DEFINITION_SHAPE('','',#{8});
+#{13}=CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#{5},#{12});\n",
+                e, k, i, e + 1, e + 2, e + 3, c + 4, b + 4,
+                e + 4, k, b + 2, c + 2, e + 5, e + 6);
+        }
+    }
+    out += "ENDSEC;\nEND-ISO-10303-21;\n";
+    out.into_bytes()
+}
+
+/// Places every instance below level `i`, resolving each use's
+/// transformation with `resolve`, and returns the number of instances
+fn walk<F: Fn(usize) -> DMat4>(i: usize, depth: usize, mat: &DMat4,
+                               resolve: &F) -> usize
+{
+    if i == depth {
+        return 1;
+    }
+    (0..2).map(|k| walk(i + 1, depth, &(mat * resolve(transformation(i, k))),
+                        resolve))
+        .sum()
+}
+
+/// Runs `f` several times, returning the fastest time and the last result
+fn best<T, F: FnMut() -> T>(mut f: F) -> (Duration, T) {
+    let mut out = None;
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let r = f();
+        best = best.min(start.elapsed());
+        out = Some(r);
+    }
+    (best, out.unwrap())
+}
+
+fn report(name: &str, t: Duration, n: usize) {
+    println!("{:<16} {:>10.2?} {:>8.1} ns/instance", name, t,
+             t.as_secs_f64() * 1e9 / n as f64);
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, which isn't a depth
+    let depth = std::env::args().skip(1)
+        .find(|a| !a.starts_with('-'))
+        .map(|a| a.parse().expect("Could not parse depth"))
+        .unwrap_or(16);
+    let data = synthetic(depth);
+    let flat = StepFile::strip_flatten(&data);
+    let s = StepFile::parse(&flat);
+    println!("Walking {} levels, best of {} runs", depth, RUNS);
+
+    let root = DMat4::identity();
+    let (t, n) = best(|| walk(0, depth, &root,
+        &|id| item_defined_transformation(&s, Id::new(id))));
+    report("uncached", t, n);
+
+    let (t, n) = best(|| {
+        let cache = PlacementCache::new();
+        walk(0, depth, &root, &|id| cache.item_defined_transformation(&s, Id::new(id)))
+    });
+    report("cached", t, n);
+
+    // Each thread walks one use of the top level's child, sharing a cache
+    let (t, n) = best(|| {
+        let cache = PlacementCache::new();
+        let cache = &cache;
+        let s = &s;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..2).map(|k| scope.spawn(move || {
+                let resolve = |id| cache.item_defined_transformation(s, Id::new(id));
+                walk(1, depth, &resolve(transformation(0, k)), &resolve)
+            })).collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum::<usize>()
+        })
+    });
+    report("cached, threaded", t, n);
+
+    let (t, tree) = best(|| ProductTree::new(&s));
+    report("product tree", t, tree.placements().len());
+    let (t, _) = best(|| Assembly::new(&s));
+    report("assembly", t, n);
+}
@@ -1,7 +1,226 @@
-use clap::{Arg, App};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hasher;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use triangulate::triangulate::triangulate;
-use step::step_file::StepFile;
+use clap::{Arg, App, ArgMatches};
+use fnv::FnvHasher;
+
+use triangulate::{
+    audit::{audit_normals, faces},
+    cache::SolidCache,
+    compressed::{CompressOptions, CompressedMesh},
+    convert::ConvertOptions,
+    estimate::estimate,
+    export::{ExportOptions, IndexBase, Winding},
+    layers::membership,
+    mesh::{Triangle, Vertex},
+    metrics::{self, Metrics},
+    params::{MeshParams, Refinement},
+    quads::{quad_dominant, save_obj_with, QuadParams},
+    stats::Stats,
+    triangulate::{triangulate_solids_with_metrics, triangulate_with_metrics,
+                  triangulate_with_params, wireframe},
+    units::{METRE, MILLIMETRE},
+    validate::compare_properties,
+};
+use step::{external::{resolve, DirResolver}, step_file::StepFile};
+
+/// Collects metrics so that they can be printed at the end of a run
+#[derive(Default)]
+struct Summary {
+    counters: BTreeMap<&'static str, u64>,
+    timers: BTreeMap<&'static str, Duration>,
+}
+
+impl Metrics for Summary {
+    fn counter(&mut self, name: &'static str, value: u64) {
+        *self.counters.entry(name).or_default() += value;
+    }
+    fn timer(&mut self, name: &'static str, elapsed: Duration) {
+        *self.timers.entry(name).or_default() += elapsed;
+    }
+}
+
+/// Hashes a block of data, returning the hash as hex digits
+fn hash(data: &[u8]) -> String {
+    let mut h = FnvHasher::default();
+    h.write(data);
+    format!("{:016x}", h.finish())
+}
+
+/// Hashes every option which changes the output, along with the converter's
+/// version, so that a manifest is invalidated when either one changes
+fn options_hash(matches: &ArgMatches) -> String {
+    let flags = ["recenter", "wireframe", "quads", "compress", "tag-layers"];
+    let mut opts: Vec<String> = flags.iter()
+        .filter(|f| matches.is_present(f))
+        .map(|f| f.to_string())
+        .collect();
+    for k in &["tolerance", "deviation", "units", "winding", "index-base"] {
+        if let Some(t) = matches.value_of(k) {
+            opts.push(format!("{}={}", k, t));
+        }
+    }
+    let mut solids: Vec<&str> = matches.values_of("solids")
+        .map(|s| s.collect())
+        .unwrap_or_default();
+    solids.sort_unstable();
+    opts.push(format!("solids={}", solids.join(",")));
+    opts.push(format!("version={}", env!("CARGO_PKG_VERSION")));
+    hash(opts.join(" ").as_bytes())
+}
+
+/// Records a conversion, so that it can be skipped if it's repeated with the
+/// same input and options
+struct Manifest {
+    input: String,
+    input_hash: String,
+    options_hash: String,
+    /// Output files, with their sizes and hashes
+    outputs: Vec<(String, u64, String)>,
+}
+
+impl Manifest {
+    fn path(output: &str) -> String {
+        format!("{}.manifest.json", output)
+    }
+
+    /// Loads the manifest for an output file, if there is one
+    fn load(output: &str) -> Option<Self> {
+        let text = std::fs::read(Self::path(output)).ok()?;
+        let json: serde_json::Value = serde_json::from_slice(&text).ok()?;
+        let s = |v: &serde_json::Value, k| v[k].as_str().map(str::to_owned);
+        let outputs = json["outputs"].as_array()?.iter()
+            .map(|o| Some((s(o, "path")?, o["bytes"].as_u64()?, s(o, "hash")?)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            input: s(&json, "input")?,
+            input_hash: s(&json, "input_hash")?,
+            options_hash: s(&json, "options_hash")?,
+            outputs,
+        })
+    }
+
+    /// Checks that every output still exists and hasn't been modified
+    fn outputs_intact(&self) -> bool {
+        self.outputs.iter().all(|(path, bytes, h)| match std::fs::read(path) {
+            Ok(data) => data.len() as u64 == *bytes && hash(&data) == *h,
+            Err(_) => false,
+        })
+    }
+
+    /// Saves the manifest beside an output file, including the conversion's
+    /// stats
+    fn save(&self, output: &str, stats: &Stats) -> std::io::Result<()> {
+        let outputs: Vec<serde_json::Value> = self.outputs.iter()
+            .map(|(path, bytes, h)| serde_json::json!({
+                "path": path, "bytes": bytes, "hash": h,
+            }))
+            .collect();
+        let stats: serde_json::Value = serde_json::from_str(&stats.to_json())
+            .expect("Stats should be valid JSON");
+        let json = serde_json::json!({
+            "input": self.input,
+            "input_hash": self.input_hash,
+            "options_hash": self.options_hash,
+            "outputs": outputs,
+            "stats": stats,
+        });
+        std::fs::write(Self::path(output),
+                       serde_json::to_string_pretty(&json)?)
+    }
+}
+
+/// Converts a STEP file to binary glTF, writing a JSON report beside it.
+/// Each output is written to a temporary file and then renamed, so other
+/// programs never see a partial file.
+///
+/// Solids which are unchanged since the file was last converted are reused
+/// from the cache, rather than being triangulated again.
+fn convert_to_glb(input: &Path, cache: &mut SolidCache)
+    -> Result<(), Box<dyn std::error::Error>>
+{
+    let data = StepFile::read(input)?;
+    let flat = StepFile::strip_flatten(&data);
+    let entities = StepFile::parse(&flat);
+    let (mesh, stats) = cache.triangulate(&flat, &entities);
+
+    let write = |ext: &str, bytes: &[u8]| -> std::io::Result<()> {
+        let out = input.with_extension(ext);
+        let tmp = input.with_extension(format!("{}.tmp", ext));
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &out)
+    };
+    write("glb", &mesh.to_glb())?;
+    write("json", stats.to_json().as_bytes())?;
+    println!("Converted {} ({} triangles, {} errors; {} of {} solids \
+              unchanged)", input.display(), mesh.triangles.len(),
+             stats.num_errors, cache.reused(),
+             cache.reused() + cache.triangulated());
+    Ok(())
+}
+
+/// Checks whether a file's glTF output is missing or older than the file
+fn needs_conversion(input: &Path) -> bool {
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified());
+    match (modified(input), modified(&input.with_extension("glb"))) {
+        (Ok(a), Ok(b)) => a > b,
+        _ => true,
+    }
+}
+
+/// Watches a directory, converting STEP files as they appear (or change).
+/// A file is only converted once its size is the same on two consecutive
+/// scans, so that files which are still being copied are left alone.
+fn watch(dir: &Path, interval: Duration) -> ! {
+    println!("Watching {}", dir.display());
+    let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+    let mut caches: HashMap<PathBuf, SolidCache> = HashMap::new();
+    loop {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("Could not read {}: {}", dir.display(), e);
+                std::thread::sleep(interval);
+                continue;
+            },
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let is_step = path.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| matches!(e.to_lowercase().as_str(), "step" | "stp"))
+                .unwrap_or(false);
+            if !is_step || !needs_conversion(&path) {
+                continue;
+            }
+            let len = match std::fs::metadata(&path) {
+                Ok(m) => m.len(),
+                Err(_) => continue,
+            };
+            if pending.insert(path.clone(), len) != Some(len) {
+                continue;
+            }
+            pending.remove(&path);
+
+            // A bad file shouldn't stop the daemon, even if it panics
+            let cache = caches.entry(path.clone()).or_default();
+            let result = std::panic::catch_unwind(AssertUnwindSafe(
+                || convert_to_glb(&path, cache).map_err(|e| e.to_string())));
+            match result {
+                Ok(Ok(())) => (),
+                Ok(Err(e)) => eprintln!("Failed to convert {}: {}",
+                                        path.display(), e),
+                Err(_) => {
+                    eprintln!("Panicked while converting {}", path.display());
+                    caches.remove(&path);
+                },
+            }
+        }
+        std::thread::sleep(interval);
+    }
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
@@ -12,34 +231,377 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .arg(Arg::with_name("output")
             .short("o")
             .long("out")
-            .help("stl file to target")
+            .help("stl file to target (or obj or glb, based on its \
+                   extension)")
             .takes_value(true)
-            .required(true))
+            .required_unless("watch"))
+        .arg(Arg::with_name("recenter")
+            .long("recenter")
+            .help("move the model's bounding box center to the origin"))
+        .arg(Arg::with_name("wireframe")
+            .long("wireframe")
+            .help("only tessellate edges, saving them as lines in an OBJ \
+                   file (or glb, based on the output's extension)"))
+        .arg(Arg::with_name("compress")
+            .long("compress")
+            .help("keep the mesh compressed in memory after triangulation, \
+                   quantizing its positions and normals"))
+        .arg(Arg::with_name("quads")
+            .long("quads")
+            .help("merge pairs of triangles into quads (obj output only)"))
+        .arg(Arg::with_name("tag-layers")
+            .long("tag-layers")
+            .help("tag each part with its layers and groups (glb and obj \
+                   output only)"))
+        .arg(Arg::with_name("audit-normals")
+            .long("audit-normals")
+            .takes_value(true)
+            .value_name("DEGREES")
+            .min_values(0)
+            .help("report faces whose triangles disagree with their vertex \
+                   normals by more than this angle (default 90)"))
+        .arg(Arg::with_name("estimate")
+            .long("estimate")
+            .help("print estimated triangle counts before triangulating"))
+        .arg(Arg::with_name("solids")
+            .long("solids")
+            .takes_value(true)
+            .value_name("IDS")
+            .use_delimiter(true)
+            .help("only triangulate these solids (comma-separated entity IDs)"))
+        .arg(Arg::with_name("external")
+            .long("external")
+            .help("load documents referenced by the input (e.g. assembly \
+                   components in separate files) from beside it"))
+        .arg(Arg::with_name("validate")
+            .long("validate")
+            .help("compare the file's validation properties (volume, area, \
+                   centroid) against the triangulated mesh"))
+        .arg(Arg::with_name("pmi")
+            .long("pmi")
+            .help("print the file's semantic PMI (dimensions, datums, and \
+                   geometric tolerances)"))
+        .arg(Arg::with_name("metrics")
+            .long("metrics")
+            .help("print conversion metrics when finished"))
+        .arg(Arg::with_name("manifest")
+            .long("manifest")
+            .requires("output")
+            .help("write a manifest of hashes beside the output, and skip \
+                   the conversion if the input and options are unchanged"))
+        .arg(Arg::with_name("watch")
+            .long("watch")
+            .takes_value(true)
+            .value_name("DIR")
+            .conflicts_with("input")
+            .help("run as a daemon, converting each STEP file which appears \
+                   in this directory to glb, with a JSON report beside it"))
+        .arg(Arg::with_name("interval")
+            .long("interval")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .requires("watch")
+            .help("how often to scan the watched directory (default 2)"))
+        .arg(Arg::with_name("threads")
+            .long("threads")
+            .takes_value(true)
+            .value_name("N")
+            .help("number of threads for parsing and triangulation (1 runs \
+                   everything on one thread, in a repeatable order)"))
+        .arg(Arg::with_name("tolerance")
+            .long("tolerance")
+            .takes_value(true)
+            .value_name("DISTANCE")
+            .help("distance below which points are treated as the same, in \
+                   model units (default: the model's declared uncertainty)"))
+        .arg(Arg::with_name("deviation")
+            .long("deviation")
+            .takes_value(true)
+            .value_name("DISTANCE")
+            .help("refines curved faces until every triangle is within this \
+                   distance of its surface, in model units"))
+        .arg(Arg::with_name("units")
+            .long("units")
+            .takes_value(true)
+            .possible_values(&["mm", "m"])
+            .help("scales the output into millimeters or meters, using each \
+                   solid's declared length unit"))
+        .arg(Arg::with_name("winding")
+            .long("winding")
+            .takes_value(true)
+            .possible_values(&["ccw", "cw"])
+            .help("order of each triangle's vertices, seen from outside \
+                   (default ccw)"))
+        .arg(Arg::with_name("index-base")
+            .long("index-base")
+            .takes_value(true)
+            .possible_values(&["0", "1"])
+            .help("index of the first vertex in obj output (default 1)"))
         .arg(Arg::with_name("input")
             .takes_value(true)
-            .required(true))
+            .required_unless("watch"))
         .get_matches();
+    if let Some(dir) = matches.value_of("watch") {
+        let interval = matches.value_of("interval")
+            .map(|i| i.parse::<f64>())
+            .transpose()?
+            .unwrap_or(2.0);
+        watch(Path::new(dir), Duration::from_secs_f64(interval));
+    }
     let input = matches.value_of("input")
         .expect("Could not get input file");
 
     let start = std::time::SystemTime::now();
-    let data = std::fs::read(input)?;
-    let flat = StepFile::strip_flatten(&data);
-    let entities = StepFile::parse(&flat);
+    let data = StepFile::read(input)?;
+
+    let manifest = match (matches.is_present("manifest"),
+                          matches.value_of("output"))
+    {
+        (true, Some(o)) => {
+            let m = Manifest {
+                input: input.to_owned(),
+                input_hash: hash(&data),
+                options_hash: options_hash(&matches),
+                outputs: vec![],
+            };
+            let up_to_date = Manifest::load(o).map(|prev|
+                prev.input_hash == m.input_hash &&
+                prev.options_hash == m.options_hash &&
+                prev.outputs_intact())
+                .unwrap_or(false);
+            if up_to_date {
+                println!("{} is up to date", o);
+                return Ok(());
+            }
+            Some((m, o))
+        },
+        _ => None,
+    };
+    // Records the output file in the manifest, if there is one
+    let finish = |manifest: Option<(Manifest, &str)>, stats: &Stats|
+        -> std::io::Result<()>
+    {
+        if let Some((mut m, o)) = manifest {
+            let data = std::fs::read(o)?;
+            m.outputs.push((o.to_owned(), data.len() as u64, hash(&data)));
+            m.save(o, stats)?;
+        }
+        Ok(())
+    };
+    let mut flat = StepFile::strip_flatten(&data);
+    if matches.is_present("external") {
+        let dir = Path::new(input).parent().unwrap_or_else(|| Path::new(""));
+        let r = resolve(&flat, &mut DirResolver(dir.to_path_buf()));
+        println!("Loaded {} external documents", r.files.len() - 1);
+        for (name, err) in &r.missing {
+            println!("  Missing {}: {}", name, err);
+        }
+        flat = StepFile::strip_flatten(r.writer(input).to_string().as_bytes());
+    }
+    let options = ConvertOptions {
+        threads: matches.value_of("threads")
+            .map(|n| n.parse::<usize>())
+            .transpose()?,
+        tolerance: matches.value_of("tolerance")
+            .map(|t| t.parse::<f64>())
+            .transpose()?,
+        ..ConvertOptions::default()
+    };
+    let entities = options.run(|| StepFile::parse(&flat))?;
     let end = std::time::SystemTime::now();
     let since_the_epoch = end.duration_since(start)
         .expect("Time went backwards");
     println!("Loaded + parsed in {:?}", since_the_epoch);
 
+    let export = ExportOptions {
+        winding: match matches.value_of("winding") {
+            Some("cw") => Winding::Clockwise,
+            _ => Winding::CounterClockwise,
+        },
+        index_base: match matches.value_of("index-base") {
+            Some("0") => IndexBase::Zero,
+            _ => IndexBase::One,
+        },
+    };
+
+    if matches.is_present("wireframe") {
+        let start = std::time::SystemTime::now();
+        let (wire, stats) = wireframe(&entities);
+        let end = std::time::SystemTime::now();
+        let since_the_epoch = end.duration_since(start)
+            .expect("Time went backwards");
+        println!("Tessellated edges in {:?}", since_the_epoch);
+
+        match matches.value_of("output") {
+            Some(o) if o.ends_with(".glb") => {
+                wire.save_glb(o)?;
+            },
+            Some(o) => wire.save_obj_with(o, &export)?,
+            None => (),
+        }
+        finish(manifest, &stats)?;
+        return Ok(());
+    }
+
+    if matches.is_present("estimate") {
+        let est = estimate(&entities);
+        println!("Estimated {} triangles across {} solids (cost {:.0})",
+                 est.triangles(), est.solids.len(), est.cost());
+        for s in est.by_cost().iter().take(5) {
+            println!("  #{}: {} faces ({} spline), {} triangles x {}, \
+                      cost {:.0}", s.id, s.faces, s.spline_faces, s.triangles,
+                     s.instances, s.cost);
+        }
+    }
+
+    let mut summary = Summary::default();
     let start = std::time::SystemTime::now();
-    let tri = triangulate(&entities);
+    let solids = matches.values_of("solids")
+        .map(|ids| ids.map(|i| i.trim_start_matches('#').parse::<usize>())
+            .collect::<Result<Vec<_>, _>>())
+        .transpose()?;
+    let deviation = matches.value_of("deviation")
+        .map(|d| d.parse::<f64>())
+        .transpose()?;
+    let length_unit = match matches.value_of("units") {
+        Some("mm") => Some(MILLIMETRE),
+        Some("m") => Some(METRE),
+        _ => None,
+    };
+    let mut tri = match (options.tolerance, deviation, length_unit, &solids) {
+        (None, None, None, Some(ids)) => options.run(
+            || triangulate_solids_with_metrics(&entities, ids, &mut summary))?,
+        (None, None, None, None) => options.run(
+            || triangulate_with_metrics(&entities, &mut summary))?,
+        (tolerance, deviation, length_unit, _) => {
+            let params = MeshParams {
+                tolerance,
+                refine: deviation.map(Refinement::new),
+                length_unit,
+                ..MeshParams::default()
+            };
+            options.run(|| triangulate_with_params(
+                &entities, solids.as_deref(), &params, &mut summary))?
+        },
+    };
     let end = std::time::SystemTime::now();
     let since_the_epoch = end.duration_since(start)
         .expect("Time went backwards");
     println!("Triangulated in {:?}", since_the_epoch);
 
+    if matches.is_present("audit-normals") {
+        let max_angle = matches.value_of("audit-normals")
+            .map(|a| a.parse::<f64>())
+            .transpose()?
+            .unwrap_or(90.0);
+        let issues = audit_normals(&tri.0, max_angle.to_radians());
+        println!("{} triangles have inconsistent normals", issues.len());
+        for (face, (count, angle)) in faces(&issues) {
+            println!("  #{}: {} triangles, up to {:.1}°",
+                     face, count, angle.to_degrees());
+        }
+    }
+
+    if matches.is_present("validate") {
+        let props = step::validation::validation_properties(
+            &step::raw::records(&flat));
+        println!("Checked {} validation properties", props.len());
+        for c in compare_properties(&tri.0, &props) {
+            println!("  #{} {}: expected {:?}, measured {:?} ({:.3}% off)",
+                     c.id, c.product.as_deref().unwrap_or("(model)"),
+                     c.expected, c.measured, c.deviation * 100.0);
+        }
+    }
+
+    if matches.is_present("pmi") {
+        let pmi = step::pmi::pmi(&step::raw::records(&flat));
+        println!("Found {} dimensions, {} datums, and {} geometric tolerances",
+                 pmi.dimensions.len(), pmi.datums.len(), pmi.tolerances.len());
+        for d in &pmi.dimensions {
+            println!("  #{} {:?} '{}': {:?} on faces {:?}",
+                     d.id, d.kind, d.name, d.nominal.map(|m| m.value), d.faces);
+        }
+        for d in &pmi.datums {
+            println!("  #{} datum {} on faces {:?}", d.id, d.label, d.faces);
+        }
+        for t in &pmi.tolerances {
+            println!("  #{} {:?} {:?} |{}| on faces {:?}", t.id, t.kind,
+                     t.magnitude.map(|m| m.value), t.datums.join("|"), t.faces);
+        }
+    }
+
+    if let Some(r) = tri.0.precision_report() {
+        if r.at_risk() {
+            println!("Warning: coordinates up to {:e} with extent {:e}; \
+                      STL precision is only {:e}",
+                     r.max_coord, r.extent, r.f32_resolution);
+        }
+    }
+    if matches.is_present("recenter") {
+        let offset = tri.0.recenter();
+        println!("Recentered with offset [{}, {}, {}]",
+                 offset.x, offset.y, offset.z);
+    }
+
+    if matches.is_present("tag-layers") {
+        let n = membership(&entities).tag(&mut tri.0);
+        println!("Tagged {} parts with layers and groups", n);
+    }
+
+    // From here on, only the compressed mesh is kept, and it's decompressed
+    // as it's written out
+    let compressed = if matches.is_present("compress") {
+        let mesh = std::mem::take(&mut tri.0);
+        let before = mesh.verts.len() * std::mem::size_of::<Vertex>() +
+            mesh.triangles.len() * std::mem::size_of::<Triangle>();
+        let c = CompressedMesh::new(&mesh, &CompressOptions::default());
+        println!("Compressed mesh from {} to {} bytes", before, c.size());
+        Some(c)
+    } else {
+        None
+    };
+
     if let Some(o) = matches.value_of("output") {
-        tri.0.save_stl(o)?;
+        if o.ends_with(".obj") {
+            let decompressed = compressed.as_ref().map(|c| c.to_mesh());
+            let mesh = decompressed.as_ref().unwrap_or(&tri.0);
+            let quads = if matches.is_present("quads") {
+                let q = quad_dominant(mesh, &QuadParams::default());
+                println!("Merged into {} polygons", q.len());
+                Some(q)
+            } else {
+                None
+            };
+            save_obj_with(mesh, quads.as_deref(), o, &export)?;
+            summary.counter(metrics::BYTES_WRITTEN,
+                            std::fs::metadata(o)?.len());
+        } else if o.ends_with(".glb") {
+            let n = match &compressed {
+                Some(c) => c.save_glb_with(o, &export)?,
+                None => tri.0.save_glb_with(o, &export)?,
+            };
+            summary.counter(metrics::BYTES_WRITTEN, n as u64);
+        } else {
+            if matches.is_present("quads") {
+                println!("Warning: --quads is ignored for STL output");
+            }
+            let n = match &compressed {
+                Some(c) => c.save_stl_with(o, &export)?,
+                None => tri.0.save_stl_with(o, &export)?,
+            };
+            summary.counter(metrics::BYTES_WRITTEN, n as u64);
+        }
+    }
+
+    finish(manifest, &tri.1)?;
+
+    if matches.is_present("metrics") {
+        for (name, value) in &summary.counters {
+            println!("{}: {}", name, value);
+        }
+        for (name, value) in &summary.timers {
+            println!("{}: {:?}", name, value);
+        }
     }
 
     Ok(())
@@ -0,0 +1,184 @@
+//! Describes the geometry of individual faces without meshing them, for
+//! tools (e.g. metrology or feature recognition) which need to know what a
+//! face is rather than what it looks like.
+use nalgebra_glm::DVec3;
+use log::warn;
+
+use step::{ap214::*, ap214::Entity, step_file::StepFile};
+
+use crate::{Error, triangulate::axis2_placement_3d};
+
+/// The type and parameters of the surface under a face, as resolved for
+/// triangulation.  Axes and reference directions are normalized; as with
+/// triangulation, a missing axis is +Z and a missing reference direction is
+/// +X.  Lengths are in the file's units and angles are as written.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SurfaceInfo {
+    Plane { origin: DVec3, normal: DVec3, ref_direction: DVec3 },
+    Cylinder { origin: DVec3, axis: DVec3, ref_direction: DVec3, radius: f64 },
+    /// `radius` is measured in the plane of `origin`
+    Cone {
+        origin: DVec3, axis: DVec3, ref_direction: DVec3,
+        radius: f64, semi_angle: f64,
+    },
+    Sphere { center: DVec3, radius: f64 },
+    Torus { center: DVec3, axis: DVec3, major_radius: f64, minor_radius: f64 },
+    /// B-spline or (if `rational`) NURBS surface
+    Spline {
+        u_degree: usize,
+        v_degree: usize,
+        /// Number of distinct knots in each direction
+        knots: (usize, usize),
+        /// Size of the control point grid, as (u, v)
+        control_points: (usize, usize),
+        rational: bool,
+        u_closed: bool,
+        v_closed: bool,
+    },
+}
+
+impl SurfaceInfo {
+    /// Checks whether this is an analytic surface (anything but a spline)
+    pub fn is_analytic(&self) -> bool {
+        !matches!(self, Self::Spline { .. })
+    }
+}
+
+/// Looks up the surface under an `ADVANCED_FACE`, returning
+/// [`Error::UnknownSurfaceType`] if the face isn't an `ADVANCED_FACE` or its
+/// surface is a type which can't be triangulated.
+///
+/// The result ignores the face's `same_sense` flag, so a plane's normal may
+/// point into the solid.
+pub fn face_surface(s: &StepFile, face: usize) -> Result<SurfaceInfo, Error> {
+    let f = s.entities().get(face).and_then(|e| e.downcast::<AdvancedFace_>());
+    let face = match f {
+        Some(f) => f,
+        None => {
+            warn!("#{} is not an AdvancedFace", face);
+            return Err(Error::UnknownSurfaceType);
+        },
+    };
+    surface(s, face.face_geometry)
+}
+
+/// Looks up a surface, as for [`face_surface`]
+pub fn surface(s: &StepFile, surf: Surface) -> Result<SurfaceInfo, Error> {
+    let placement = |p| {
+        let (origin, axis, ref_direction) = axis2_placement_3d(s, p);
+        (origin, axis.normalize(), ref_direction.normalize())
+    };
+    let e = &s[surf];
+    Ok(match e {
+        Entity::Plane(p) => {
+            let (origin, normal, ref_direction) = placement(p.position);
+            SurfaceInfo::Plane { origin, normal, ref_direction }
+        },
+        Entity::CylindricalSurface(c) => {
+            let (origin, axis, ref_direction) = placement(c.position);
+            SurfaceInfo::Cylinder {
+                origin, axis, ref_direction, radius: c.radius.0.0.0,
+            }
+        },
+        Entity::ConicalSurface(c) => {
+            let (origin, axis, ref_direction) = placement(c.position);
+            SurfaceInfo::Cone {
+                origin, axis, ref_direction,
+                radius: c.radius.0, semi_angle: c.semi_angle.0,
+            }
+        },
+        Entity::SphericalSurface(c) => SurfaceInfo::Sphere {
+            center: placement(c.position).0, radius: c.radius.0.0.0,
+        },
+        Entity::ToroidalSurface(c) => {
+            let (center, axis, _) = placement(c.position);
+            SurfaceInfo::Torus {
+                center, axis,
+                major_radius: c.major_radius.0.0.0,
+                minor_radius: c.minor_radius.0.0.0,
+            }
+        },
+        _ => match e.downcast::<BSplineSurfaceWithKnots_>() {
+            // Rational surfaces are complex instances, which include the
+            // B-spline as one part
+            Some(b) => SurfaceInfo::Spline {
+                u_degree: b.u_degree.max(0) as usize,
+                v_degree: b.v_degree.max(0) as usize,
+                knots: (b.u_knots.len(), b.v_knots.len()),
+                control_points: (b.control_points_list.len(),
+                    b.control_points_list.first().map_or(0, |r| r.len())),
+                rational: e.is::<RationalBSplineSurface_>(),
+                u_closed: b.u_closed.0 == Some(true),
+                v_closed: b.v_closed.0 == Some(true),
+            },
+            None => {
+                warn!("Could not get surface from {:?}", e);
+                return Err(Error::UnknownSurfaceType);
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_faces() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/cube_hole.step");
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        let s = StepFile::parse(&flat);
+
+        let faces: Vec<_> = s.iter::<AdvancedFace>()
+            .map(|(id, _)| face_surface(&s, id.0).unwrap())
+            .collect();
+        assert_eq!(faces.len(), 7);
+        assert_eq!(faces.iter()
+            .filter(|f| matches!(f, SurfaceInfo::Plane { .. }))
+            .count(), 6);
+        let (axis, radius) = faces.iter().find_map(|f| match f {
+            SurfaceInfo::Cylinder { axis, radius, .. } => Some((*axis, *radius)),
+            _ => None,
+        }).unwrap();
+        assert_eq!(radius, 0.00635);
+        assert!((axis.norm() - 1.0).abs() < 1e-12);
+        assert!(faces.iter().all(SurfaceInfo::is_analytic));
+
+        // Not a face
+        assert_eq!(face_surface(&s, 1), Err(Error::UnknownSurfaceType));
+        assert_eq!(face_surface(&s, 1_000_000), Err(Error::UnknownSurfaceType));
+    }
+
+    #[test]
+    fn test_splines() {
+        let data = b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=CARTESIAN_POINT('',(1.,0.,0.));
+#3=CARTESIAN_POINT('',(0.,1.,0.));
+#4=CARTESIAN_POINT('',(1.,1.,1.));
+#5=CARTESIAN_POINT('',(0.,2.,0.));
+#6=CARTESIAN_POINT('',(1.,2.,0.));
+#10=B_SPLINE_SURFACE_WITH_KNOTS('',2,1,((#1,#2),(#3,#4),(#5,#6)),
+.UNSPECIFIED.,.F.,.F.,.F.,(3,3),(2,2),(0.,1.),(0.,1.),.UNSPECIFIED.);
+#11=ADVANCED_FACE('',(),#10,.T.);
+#20=(BOUNDED_SURFACE()B_SPLINE_SURFACE(1,1,((#1,#2),(#3,#4)),
+.UNSPECIFIED.,.F.,.T.,.F.)B_SPLINE_SURFACE_WITH_KNOTS((2,2),(2,2),(0.,1.),
+(0.,1.),.UNSPECIFIED.)GEOMETRIC_REPRESENTATION_ITEM()
+RATIONAL_B_SPLINE_SURFACE(((1.,2.),(2.,1.)))REPRESENTATION_ITEM('')
+SURFACE());
+#21=ADVANCED_FACE('',(),#20,.T.);
+ENDSEC;END-ISO-10303-21;";
+        let flat = StepFile::strip_flatten(data);
+        let s = StepFile::parse(&flat);
+
+        assert_eq!(face_surface(&s, 11), Ok(SurfaceInfo::Spline {
+            u_degree: 2, v_degree: 1, knots: (2, 2), control_points: (3, 2),
+            rational: false, u_closed: false, v_closed: false,
+        }));
+        assert_eq!(face_surface(&s, 21), Ok(SurfaceInfo::Spline {
+            u_degree: 1, v_degree: 1, knots: (2, 2), control_points: (2, 2),
+            rational: true, u_closed: false, v_closed: true,
+        }));
+    }
+}
@@ -0,0 +1,620 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use nalgebra_glm as glm;
+use glm::DMat4;
+use log::{info, warn};
+
+use step::{
+    ap214::*, ap214::Entity, header::Header, merge::Merge, raw::records,
+    step_file::{FromEntity, StepFile}, writer::{Param, StepWriter},
+};
+use crate::{placement::PlacementCache, product::product_of};
+
+/// Mapping from representations to their children and the transforms which
+/// place them, by entity ID
+type TransformStack = HashMap<usize, Vec<(usize, DMat4)>>;
+
+fn build_transform_stack(s: &StepFile, flip: bool, cache: &PlacementCache)
+    -> TransformStack
+{
+    // Store a map of parent -> (child, transform)
+    let mut transform_stack: HashMap<_, Vec<_>> = HashMap::new();
+    for r in s.entities().iter()
+        .filter_map(|e|
+            RepresentationRelationshipWithTransformation_::try_from_entity(e))
+    {
+        let (a, b) = if flip {
+            (r.rep_2, r.rep_1)
+        } else {
+            (r.rep_1, r.rep_2)
+        };
+        let mut mat = cache.item_defined_transformation(s,
+            r.transformation_operator.cast());
+        if flip {
+            mat = mat.try_inverse().expect("Could not invert transform matrix");
+        }
+
+        transform_stack.entry(b.0)
+            .or_default()
+            .push((a.0, mat));
+    }
+    transform_stack
+}
+
+fn transform_stack_roots(transform_stack: &TransformStack) -> Vec<usize> {
+    let children: HashSet<_> = transform_stack
+        .values()
+        .flat_map(|v| v.iter())
+        .map(|v| v.0)
+        .collect();
+    let mut roots: Vec<_> = transform_stack
+        .keys()
+        .filter(|k| !children.contains(k))
+        .copied()
+        .collect();
+
+    // Sort so that the traversal order doesn't depend on the HashMap
+    roots.sort_unstable();
+    roots
+}
+
+/// A representation placed in the assembly tree.  A representation which is
+/// used several times (e.g. a part in a subassembly which is itself placed
+/// twice) appears once per instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    /// Entity ID of the representation
+    pub rep: usize,
+    /// Placement relative to the parent node (or to the world, for a root)
+    pub transform: DMat4,
+    /// Whether the representation's own solids are drawn.  A representation
+    /// which places children with transforms is only used as an assembly,
+    /// so its items (usually just axes) are skipped.
+    pub draws_items: bool,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    fn find(&self, rep: usize) -> Option<&Node> {
+        if self.rep == rep {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.find(rep))
+        }
+    }
+
+    fn strip(&mut self) {
+        self.transform = DMat4::identity();
+        self.children.iter_mut().for_each(Node::strip);
+    }
+
+    fn flatten(self, parent: &DMat4, out: &mut Vec<Node>) {
+        let mat = parent * self.transform;
+        if self.draws_items {
+            out.push(Node {
+                rep: self.rep,
+                transform: mat,
+                draws_items: true,
+                children: vec![],
+            });
+        }
+        for c in self.children {
+            c.flatten(&mat, out);
+        }
+    }
+
+    fn reorder(&mut self, rep: usize, order: &[usize]) -> bool {
+        let mut found = false;
+        if self.rep == rep {
+            // Listed children come first, in the given order; the rest
+            // keep their relative order after them.
+            let key = |n: &Node| order.iter()
+                .position(|r| *r == n.rep)
+                .unwrap_or(order.len());
+            self.children.sort_by_key(key);
+            found = true;
+        }
+        for c in &mut self.children {
+            found |= c.reorder(rep, order);
+        }
+        found
+    }
+}
+
+/// The assembly structure of a file, as a tree of representations placed by
+/// `REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION` and linked by
+/// `SHAPE_REPRESENTATION_RELATIONSHIP`.
+///
+/// The tree can be edited (re-rooted at a sub-assembly, flattened, or
+/// reordered) and then triangulated with
+/// [`triangulate_assembly`](crate::triangulate::triangulate_assembly), so
+/// that the edits are reflected in the mesh and anything exported from it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Assembly {
+    pub roots: Vec<Node>,
+}
+
+impl Assembly {
+    /// Builds the assembly tree from a file's relationship entities
+    pub fn new(s: &StepFile) -> Self {
+        Self::with_cache(s, &PlacementCache::new())
+    }
+
+    /// Builds the assembly tree, like [`Assembly::new`], resolving
+    /// placements through a cache which may be shared with other walks of
+    /// the same file
+    pub fn with_cache(s: &StepFile, cache: &PlacementCache) -> Self {
+        let mut transform_stack = build_transform_stack(s, false, cache);
+        let mut roots = transform_stack_roots(&transform_stack);
+        // The transformation graph isn't directional (because STEP is a Good
+        // File Format), so if it's got more than one root, assume it's
+        // backwards.  We are assuming that directions in the graph are
+        // consistent within the file, until we find a counterexample.
+        if roots.len() > 1 {
+            info!("Flipping transform stack");
+            transform_stack = build_transform_stack(s, true, cache);
+            roots = transform_stack_roots(&transform_stack);
+        }
+        if roots.len() > 1 {
+            warn!("Transformation stack has more than one root!");
+        }
+
+        // Store a map of ShapeRepresentationRelationships, which some models
+        // use to map from axes to specific instances
+        let mut shape_rep_relationship: HashMap<usize, Vec<usize>> =
+            HashMap::new();
+        for (r1, r2) in s.entities().iter()
+            .filter_map(|e| ShapeRepresentationRelationship_::try_from_entity(e))
+            .map(|e| (e.rep_1, e.rep_2))
+        {
+            shape_rep_relationship.entry(r1.0).or_default().push(r2.0);
+        }
+
+        let mut path = Vec::new();
+        let roots = roots.into_iter()
+            .map(|r| build_node(r, DMat4::identity(), &transform_stack,
+                                &shape_rep_relationship, &mut path))
+            .collect();
+        Self { roots }
+    }
+
+    /// Finds the first instance of a representation, by entity ID
+    pub fn find(&self, rep: usize) -> Option<&Node> {
+        self.roots.iter().find_map(|n| n.find(rep))
+    }
+
+    /// Makes the first instance of a representation the only root, placed
+    /// at the origin.  Returns `false` (leaving the tree unchanged) if the
+    /// representation isn't in the tree.
+    pub fn reroot(&mut self, rep: usize) -> bool {
+        match self.find(rep).cloned() {
+            Some(mut n) => {
+                n.transform = DMat4::identity();
+                self.roots = vec![n];
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Re-roots the tree at a product, given by its name or ID (as in
+    /// `PRODUCT('id','name',...)`).  The product's shape is found through
+    /// its `SHAPE_DEFINITION_REPRESENTATION`; returns `false` if it has none
+    /// in the tree.
+    pub fn reroot_product(&mut self, s: &StepFile, product: &str) -> bool {
+        let reps: Vec<usize> = s.entities().iter()
+            .filter_map(ShapeDefinitionRepresentation_::try_from_entity)
+            .filter(|d| is_product(s, d.definition.0, product))
+            .map(|d| d.used_representation.0)
+            .collect();
+        reps.into_iter().any(|r| self.reroot(r))
+    }
+
+    /// Bakes every transform into the nodes which draw solids, leaving a
+    /// single level of roots placed in world coordinates.  This is useful
+    /// for exporters which can't represent nested transforms.
+    pub fn bake(&mut self) {
+        let mut out = Vec::new();
+        for n in std::mem::take(&mut self.roots) {
+            n.flatten(&DMat4::identity(), &mut out);
+        }
+        self.roots = out;
+    }
+
+    /// Resets every transform to the identity, so that each part is drawn
+    /// in its own coordinate system
+    pub fn strip(&mut self) {
+        self.roots.iter_mut().for_each(Node::strip);
+    }
+
+    /// Reorders the children of every instance of a representation, moving
+    /// those listed in `order` (by representation ID) to the front.  Returns
+    /// `false` if the representation isn't in the tree.
+    pub fn reorder(&mut self, rep: usize, order: &[usize]) -> bool {
+        let mut found = false;
+        for n in &mut self.roots {
+            found |= n.reorder(rep, order);
+        }
+        found
+    }
+
+    /// Writes a file back out with this tree as its assembly structure.
+    ///
+    /// `data` is the file which the tree was built from, preprocessed by
+    /// [`StepFile::strip_flatten`].  Its relationships between
+    /// representations (and anything which refers to them, like the
+    /// `CONTEXT_DEPENDENT_SHAPE_REPRESENTATION`s which tie them to the
+    /// product structure) are replaced by new ones which build this tree,
+    /// so that [`Assembly::new`] reads it back.  If the tree has several
+    /// roots, or a root which isn't at the origin (e.g. after
+    /// [`Assembly::bake`]), then a new `SHAPE_REPRESENTATION` is added as
+    /// the single root.  Instances are renumbered from 1.
+    pub fn to_step(&self, data: &[u8], name: &str) -> String {
+        let mut rs = records(data);
+        let mut removed: HashSet<usize> = rs.iter()
+            .filter(|r| r.parts.iter().any(|(name, _)|
+                name == "REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION" ||
+                name == "SHAPE_REPRESENTATION_RELATIONSHIP"))
+            .map(|r| r.id)
+            .collect();
+        loop {
+            let n = removed.len();
+            for r in &rs {
+                if r.refs().iter().any(|i| removed.contains(i)) {
+                    removed.insert(r.id);
+                }
+            }
+            if removed.len() == n {
+                break;
+            }
+        }
+        rs.retain(|r| !removed.contains(&r.id));
+
+        let mut merge = Merge::new();
+        let schema = Header::parse(data).schemas.into_iter().next();
+        let file = merge.add_records(rs, schema);
+        let mut w = merge.writer(name);
+        let id = |rep: usize| merge.new_id(file, rep)
+            .expect("Representation is missing from the file");
+
+        let origin = write_placement(&mut w, &DMat4::identity());
+        let mut out = Writer { w, origin, id: &id, done: HashSet::new() };
+        match self.roots.as_slice() {
+            [] => (),
+            [root] if root.transform == DMat4::identity() =>
+                out.node(root),
+            roots => {
+                // Use the context of the first root's representation
+                let context = merge.records()[id(roots[0].rep) - 1].parts
+                    .iter()
+                    .find_map(|(_, ps)| ps.get(2).filter(|p|
+                        matches!(p, Param::Ref(_))))
+                    .cloned()
+                    .unwrap_or(Param::Unset);
+                let top = out.w.add("SHAPE_REPRESENTATION", &[
+                    Param::str(""), Param::refs(&[origin]), context]);
+                for r in roots {
+                    out.placed(r, top);
+                    out.node(r);
+                }
+            },
+        }
+        out.w.to_string()
+    }
+
+    /// Finds every solid (or surface model) drawn by the tree, along with
+    /// the transforms which place its instances in the model.
+    ///
+    /// This is a BTreeMap so that solids are meshed (and their triangles are
+    /// emitted) in ascending ID order, regardless of hashing or threading.
+    pub(crate) fn solids<'a>(&self, s: &'a StepFile)
+        -> BTreeMap<RepresentationItem<'a>, Vec<DMat4>>
+    {
+        let mut to_mesh: BTreeMap<RepresentationItem, Vec<_>> = BTreeMap::new();
+        let mut todo: Vec<_> = self.roots.iter()
+            .map(|n| (n, n.transform))
+            .collect();
+        while let Some((node, mat)) = todo.pop() {
+            for c in &node.children {
+                todo.push((c, mat * c.transform));
+            }
+            if !node.draws_items {
+                continue;
+            }
+            // Bind this transform to the RepresentationItem, which is
+            // either a ManifoldSolidBrep or a ShellBasedSurfaceModel
+            let items = match &s[Representation::new(node.rep)] {
+                Entity::AdvancedBrepShapeRepresentation(b) => &b.items,
+                Entity::ShapeRepresentation(b) => &b.items,
+                Entity::ManifoldSurfaceShapeRepresentation(b) => &b.items,
+                e => panic!("Could not get shape from {:?}", e),
+            };
+
+            for m in items.iter() {
+                match &s[*m] {
+                    Entity::ManifoldSolidBrep(_)
+                    | Entity::BrepWithVoids(_)
+                    | Entity::ShellBasedSurfaceModel(_) =>
+                        to_mesh.entry(*m).or_default().push(mat),
+                    Entity::Axis2Placement3d(_) => (),
+                    e => warn!("Skipping {:?}", e),
+                }
+            }
+        }
+        to_mesh
+    }
+}
+
+fn build_node(rep: usize, transform: DMat4, transform_stack: &TransformStack,
+              shape_rep_relationship: &HashMap<usize, Vec<usize>>,
+              path: &mut Vec<usize>) -> Node
+{
+    path.push(rep);
+    let placed = transform_stack.get(&rep);
+    let mut children = Vec::new();
+    for (c, mat) in shape_rep_relationship.get(&rep).into_iter().flatten()
+        .map(|c| (*c, DMat4::identity()))
+        .chain(placed.into_iter().flatten().cloned())
+    {
+        if path.contains(&c) {
+            warn!("Skipping cyclic reference from #{} to #{}", rep, c);
+        } else {
+            children.push(build_node(c, mat, transform_stack,
+                                     shape_rep_relationship, path));
+        }
+    }
+    path.pop();
+    Node { rep, transform, draws_items: placed.is_none(), children }
+}
+
+/// Writes the relationships which build an assembly tree
+struct Writer<'a> {
+    w: StepWriter,
+    /// Placement at the origin, which each transformation starts from
+    origin: usize,
+    /// Maps representation IDs in the original file to written IDs
+    id: &'a dyn Fn(usize) -> usize,
+    /// Representations whose children have been written.  A representation
+    /// which appears several times in the tree has the same children each
+    /// time, so they're only written once.
+    done: HashSet<usize>,
+}
+
+impl Writer<'_> {
+    fn node(&mut self, n: &Node) {
+        if !self.done.insert(n.rep) {
+            return;
+        }
+        let parent = (self.id)(n.rep);
+        for c in &n.children {
+            // A representation which draws its own items gets children
+            // without transforms, so that it's still drawn when read back
+            if n.draws_items && c.transform == DMat4::identity() {
+                self.w.add("SHAPE_REPRESENTATION_RELATIONSHIP", &[
+                    Param::str(""), Param::str(""),
+                    Param::Ref(parent), Param::Ref((self.id)(c.rep))]);
+            } else {
+                self.placed(c, parent);
+            }
+        }
+        for c in &n.children {
+            self.node(c);
+        }
+    }
+
+    /// Places a node's representation in a parent, with its transform
+    fn placed(&mut self, n: &Node, parent: usize) {
+        let axis = write_placement(&mut self.w, &n.transform);
+        let t = self.w.add("ITEM_DEFINED_TRANSFORMATION", &[
+            Param::str(""), Param::str(""),
+            Param::Ref(self.origin), Param::Ref(axis)]);
+        self.w.add_complex(&[
+            ("REPRESENTATION_RELATIONSHIP", &[
+                Param::str(""), Param::str(""),
+                Param::Ref((self.id)(n.rep)), Param::Ref(parent)]),
+            ("REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION",
+             &[Param::Ref(t)]),
+            ("SHAPE_REPRESENTATION_RELATIONSHIP", &[]),
+        ]);
+    }
+}
+
+/// Writes an `AXIS2_PLACEMENT_3D` for a rigid transform, returning its ID
+fn write_placement(w: &mut StepWriter, mat: &DMat4) -> usize {
+    let col = |i: usize| {
+        let c = mat.column(i);
+        [c[0], c[1], c[2]]
+    };
+    // A degenerate (e.g. zero-scale) transform has no direction to write,
+    // so it falls back to the default axis
+    let unit = |v: [f64; 3], default: [f64; 3]| {
+        let n = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        if n > 0.0 && n.is_finite() {
+            [v[0] / n, v[1] / n, v[2] / n]
+        } else {
+            default
+        }
+    };
+    let location = w.add("CARTESIAN_POINT", &[
+        Param::str(""), Param::reals(&col(3))]);
+    let axis = w.add("DIRECTION", &[
+        Param::str(""), Param::reals(&unit(col(2), [0.0, 0.0, 1.0]))]);
+    let ref_direction = w.add("DIRECTION", &[
+        Param::str(""), Param::reals(&unit(col(0), [1.0, 0.0, 0.0]))]);
+    w.add("AXIS2_PLACEMENT_3D", &[
+        Param::str(""), Param::Ref(location), Param::Ref(axis),
+        Param::Ref(ref_direction)])
+}
+
+/// Checks whether a `SHAPE_DEFINITION_REPRESENTATION`'s definition is the
+/// shape of the given product (by name or ID)
+fn is_product(s: &StepFile, definition: usize, product: &str) -> bool {
+    let pd = match &s[ProductDefinitionShape::new(definition)] {
+        Entity::ProductDefinitionShape(d) => d.definition.0,
+        _ => return false,
+    };
+    match product_of(s, pd) {
+        Some((_, p)) => p.name.0 == product || p.id.0 == product,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sub-assembly (#20) placing one part (#30) twice, itself placed in
+    /// the top-level assembly (#10), along with a part (#40) placed directly
+    const DATA: &[u8] = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=DIRECTION('',(1.,0.,0.));
+#4=AXIS2_PLACEMENT_3D('',#1,#2,#3);
+#5=CARTESIAN_POINT('',(10.,0.,0.));
+#6=AXIS2_PLACEMENT_3D('',#5,#2,#3);
+#7=CARTESIAN_POINT('',(0.,5.,0.));
+#8=AXIS2_PLACEMENT_3D('',#7,#2,#3);
+#10=SHAPE_REPRESENTATION('top',(#4),$);
+#20=SHAPE_REPRESENTATION('sub',(#4),$);
+#30=SHAPE_REPRESENTATION('bolt',(#4),$);
+#40=SHAPE_REPRESENTATION('plate',(#4),$);
+#50=ITEM_DEFINED_TRANSFORMATION('','',#4,#6);
+#51=ITEM_DEFINED_TRANSFORMATION('','',#4,#8);
+#60=(REPRESENTATION_RELATIONSHIP('','',#20,#10)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#50)SHAPE_REPRESENTATION_RELATIONSHIP());
+#61=(REPRESENTATION_RELATIONSHIP('','',#30,#20)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#51)SHAPE_REPRESENTATION_RELATIONSHIP());
+#62=(REPRESENTATION_RELATIONSHIP('','',#30,#20)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#50)SHAPE_REPRESENTATION_RELATIONSHIP());
+#63=(REPRESENTATION_RELATIONSHIP('','',#40,#10)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#51)SHAPE_REPRESENTATION_RELATIONSHIP());
+#70=PRODUCT('SUB-1','sub','',());
+#71=PRODUCT_DEFINITION_FORMATION('','',#70);
+#72=PRODUCT_DEFINITION('','',#71,$);
+#73=PRODUCT_DEFINITION_SHAPE('','',#72);
+#74=SHAPE_DEFINITION_REPRESENTATION(#73,#20);
+ENDSEC;
+END-ISO-10303-21;";
+
+    fn translation(n: &Node) -> (f64, f64, f64) {
+        let c = n.transform.column(3);
+        (c[0], c[1], c[2])
+    }
+
+    fn reps(nodes: &[Node]) -> Vec<usize> {
+        nodes.iter().map(|n| n.rep).collect()
+    }
+
+    #[test]
+    fn test_assembly() {
+        let flat = StepFile::strip_flatten(DATA);
+        let step = StepFile::parse(&flat);
+        let a = Assembly::new(&step);
+        assert_eq!(reps(&a.roots), [10]);
+        assert!(!a.roots[0].draws_items);
+        assert_eq!(reps(&a.roots[0].children), [20, 40]);
+        let sub = a.find(20).unwrap();
+        assert_eq!(translation(sub), (10.0, 0.0, 0.0));
+        assert_eq!(reps(&sub.children), [30, 30]);
+        assert!(sub.children[0].draws_items);
+
+        let mut r = a.clone();
+        assert!(!r.reroot(99));
+        assert_eq!(r, a);
+        assert!(r.reroot_product(&step, "SUB-1"));
+        assert_eq!(reps(&r.roots), [20]);
+        assert_eq!(translation(&r.roots[0]), (0.0, 0.0, 0.0));
+
+        let mut b = a.clone();
+        b.bake();
+        assert_eq!(reps(&b.roots), [30, 30, 40]);
+        assert!(b.roots.iter().all(|n| n.children.is_empty()));
+        assert_eq!(translation(&b.roots[0]), (10.0, 5.0, 0.0));
+        assert_eq!(translation(&b.roots[1]), (20.0, 0.0, 0.0));
+        assert_eq!(translation(&b.roots[2]), (0.0, 5.0, 0.0));
+
+        let mut s = a.clone();
+        s.strip();
+        assert_eq!(translation(s.find(20).unwrap()), (0.0, 0.0, 0.0));
+
+        let mut o = a.clone();
+        assert!(o.reorder(10, &[40]));
+        assert_eq!(reps(&o.roots[0].children), [40, 20]);
+        assert!(!o.reorder(99, &[]));
+    }
+
+    /// Writes an edited tree, then checks that reading the result gives
+    /// the same tree back
+    #[test]
+    fn test_to_step() {
+        let flat = StepFile::strip_flatten(DATA);
+        let step = StepFile::parse(&flat);
+        let a = Assembly::new(&step);
+
+        let read = |a: &Assembly| {
+            let text = a.to_step(&flat, "edited");
+            let flat = StepFile::strip_flatten(text.as_bytes());
+            let records = records(&flat);
+            let out = Assembly::new(&StepFile::parse(&flat));
+            // Representations are renumbered, so name them instead
+            let name = move |rep: usize| records.iter()
+                .find(|r| r.id == rep)
+                .and_then(|r| match r.parts[0].1.first() {
+                    Some(Param::String(s)) => Some(s.clone()),
+                    _ => None,
+                })
+                .unwrap();
+            (out, name)
+        };
+
+        // Reordered
+        let mut o = a.clone();
+        o.reorder(10, &[40]);
+        let (b, name) = read(&o);
+        assert_eq!(b.roots.len(), 1);
+        assert_eq!(name(b.roots[0].rep), "top");
+        let children: Vec<_> = b.roots[0].children.iter()
+            .map(|n| (name(n.rep), translation(n)))
+            .collect();
+        assert_eq!(children, [("plate".to_owned(), (0.0, 5.0, 0.0)),
+                              ("sub".to_owned(), (10.0, 0.0, 0.0))]);
+        let sub = &b.roots[0].children[1];
+        assert_eq!(sub.children.len(), 2);
+        assert!(sub.children.iter().all(|n| name(n.rep) == "bolt"));
+        assert_eq!(translation(&sub.children[0]), (0.0, 5.0, 0.0));
+
+        // Re-rooted, which drops the rest of the tree
+        let mut r = a.clone();
+        r.reroot_product(&step, "SUB-1");
+        let (b, name) = read(&r);
+        assert_eq!(b.roots.len(), 1);
+        assert_eq!(name(b.roots[0].rep), "sub");
+        assert_eq!(b.roots[0].children.len(), 2);
+
+        // Baked, which needs a new root to hold the placements
+        let mut k = a.clone();
+        k.bake();
+        let (b, name) = read(&k);
+        assert_eq!(b.roots.len(), 1);
+        assert_eq!(name(b.roots[0].rep), "");
+        assert_eq!(translation(&b.roots[0]), (0.0, 0.0, 0.0));
+        let children: Vec<_> = b.roots[0].children.iter()
+            .map(|n| (name(n.rep), translation(n), n.draws_items))
+            .collect();
+        assert_eq!(children, [
+            ("bolt".to_owned(), (10.0, 5.0, 0.0), true),
+            ("bolt".to_owned(), (20.0, 0.0, 0.0), true),
+            ("plate".to_owned(), (0.0, 5.0, 0.0), true),
+        ]);
+    }
+
+    #[test]
+    fn test_write_placement() {
+        // A zero-scale transform falls back to the default axes, rather than
+        // writing NaN directions
+        let mut w = StepWriter::new("test");
+        write_placement(&mut w, &DMat4::zeros());
+        let text = w.to_string();
+        assert!(text.contains("#2=DIRECTION('',(0.,0.,1.));"), "{}", text);
+        assert!(text.contains("#3=DIRECTION('',(1.,0.,0.));"), "{}", text);
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use nalgebra_glm::DVec3;
+
+use crate::mesh::Mesh;
+
+/// A triangle whose winding disagrees with its vertex normals
+#[derive(Copy, Clone, Debug)]
+pub struct NormalIssue {
+    /// Index into [`Mesh::triangles`]
+    pub triangle: usize,
+    /// ID of the source face (see [`Triangle::face`](crate::mesh::Triangle))
+    pub face: usize,
+    /// Largest angle (in radians) between the triangle's geometric normal
+    /// and any of its vertex normals
+    pub angle: f64,
+}
+
+/// Compares each triangle's geometric normal (from its winding) against its
+/// stored vertex normals, returning every triangle where they differ by more
+/// than `max_angle` (in radians).
+///
+/// Degenerate triangles and vertices without a normal are skipped, since
+/// there's nothing to compare.
+pub fn audit_normals(mesh: &Mesh, max_angle: f64) -> Vec<NormalIssue> {
+    let mut out = Vec::new();
+    for (i, t) in mesh.triangles.iter().enumerate() {
+        let v = |j: usize| &mesh.verts[t.verts[j] as usize];
+        let n = (v(1).pos - v(0).pos).cross(&(v(2).pos - v(0).pos));
+        if n.norm() == 0.0 {
+            continue;
+        }
+        let angle = (0..3)
+            .map(|j| v(j).norm)
+            .filter(|m| m.norm() != 0.0)
+            .map(|m| nalgebra_glm::angle(&n, &m))
+            .fold(0.0, f64::max);
+        if angle > max_angle {
+            out.push(NormalIssue { triangle: i, face: t.face, angle });
+        }
+    }
+    out
+}
+
+/// Groups issues by source face, returning the number of flagged triangles
+/// and the worst angle for each face
+pub fn faces(issues: &[NormalIssue]) -> BTreeMap<usize, (usize, f64)> {
+    let mut out: BTreeMap<usize, (usize, f64)> = BTreeMap::new();
+    for i in issues {
+        let e = out.entry(i.face).or_insert((0, 0.0));
+        e.0 += 1;
+        e.1 = e.1.max(i.angle);
+    }
+    out
+}
+
+/// Paints the vertices of every flagged triangle red, so that they stand out
+/// when rendered.  Vertices may be shared with neighbouring triangles, which
+/// will pick up a red gradient.
+pub fn highlight(mesh: &mut Mesh, issues: &[NormalIssue]) {
+    for i in issues {
+        for v in mesh.triangles[i.triangle].verts.iter() {
+            mesh.verts[*v as usize].color = DVec3::new(1.0, 0.0, 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Triangle, Vertex};
+    use nalgebra_glm::U32Vec3;
+
+    fn mesh(norm: DVec3, tris: &[([u32; 3], usize)]) -> Mesh {
+        let pts = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0],
+                   [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]];
+        Mesh {
+            verts: pts.iter().map(|p| Vertex {
+                pos: DVec3::new(p[0], p[1], p[2]),
+                norm,
+                color: DVec3::zeros(),
+            }).collect(),
+            triangles: tris.iter().map(|(t, face)| Triangle {
+                verts: U32Vec3::new(t[0], t[1], t[2]),
+                face: *face,
+            }).collect(),
+            parts: Vec::new(),
+            offset: DVec3::zeros(),
+        }
+    }
+
+    #[test]
+    fn test_consistent() {
+        let m = mesh(DVec3::new(0.0, 0.0, 1.0), &[([0, 1, 2], 1), ([0, 2, 3], 1)]);
+        assert!(audit_normals(&m, 0.1).is_empty());
+    }
+
+    #[test]
+    fn test_flipped() {
+        // The second triangle is wound backwards
+        let mut m = mesh(DVec3::new(0.0, 0.0, 1.0),
+                         &[([0, 1, 2], 7), ([0, 3, 2], 9)]);
+        let issues = audit_normals(&m, 0.1);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].triangle, 1);
+        assert_eq!(issues[0].face, 9);
+        assert!((issues[0].angle - std::f64::consts::PI).abs() < 1e-9);
+
+        let f = faces(&issues);
+        assert_eq!(f.len(), 1);
+        assert_eq!(f[&9].0, 1);
+
+        highlight(&mut m, &issues);
+        assert_eq!(m.verts[1].color, DVec3::zeros());
+        assert_eq!(m.verts[3].color, DVec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_missing_normals() {
+        let m = mesh(DVec3::zeros(), &[([0, 3, 2], 1)]);
+        assert!(audit_normals(&m, 0.1).is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::{hash_map::DefaultHasher, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use step::{raw::{records, Record}, step_file::StepFile, writer::Param};
+use crate::{
+    mesh::{Mesh, Triangle},
+    params::MeshParams,
+    stats::Stats,
+    triangulate::{solid_transforms, triangulate_reusing, LocalSolid},
+};
+
+/// A solid's triangulation, along with the entities in its closure (in a
+/// canonical order), so that it can be matched against a renumbered copy
+struct Cached {
+    closure: Vec<usize>,
+    local: LocalSolid,
+}
+
+/// Remembers the triangulation of each solid between runs, so that
+/// reloading an edited file only re-triangulates the solids which changed.
+///
+/// Solids are matched by the contents of every entity which they refer to
+/// (directly or indirectly), ignoring entity IDs, so an exporter which
+/// renumbers the whole file on each save doesn't defeat the cache.  Colors
+/// and placements are read from the new file, so they may change without
+/// any re-triangulation.
+#[derive(Default)]
+pub struct SolidCache {
+    solids: HashMap<u64, Cached>,
+    reused: usize,
+    triangulated: usize,
+}
+
+impl SolidCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Triangulates every solid in the file, like
+    /// [`triangulate`](crate::triangulate::triangulate), reusing solids from
+    /// the previous call where they're unchanged.  `data` is the file's
+    /// text after [`StepFile::strip_flatten`], from which `s` was parsed.
+    ///
+    /// Only solids from this file are kept afterwards, so the cache doesn't
+    /// grow as a file is edited.
+    pub fn triangulate(&mut self, data: &[u8], s: &StepFile) -> (Mesh, Stats) {
+        let records = records(data);
+        let mut closures = Closures {
+            by_id: records.iter().map(|r| (r.id, r)).collect(),
+            hashes: HashMap::new(),
+        };
+        let to_mesh = solid_transforms(s);
+        let keys: HashMap<usize, (u64, Vec<usize>)> = to_mesh.keys()
+            .map(|id| (id.0, (closures.hash(id.0), closures.closure(id.0))))
+            .collect();
+
+        // Look up unchanged solids, renumbering their faces if needed
+        let hits: HashMap<usize, LocalSolid> = keys.iter()
+            .filter_map(|(id, (h, closure))| self.solids.get(h)
+                .filter(|c| c.closure.len() == closure.len())
+                .map(|c| (*id, renumber(c, closure))))
+            .collect();
+        self.reused = hits.len();
+        self.triangulated = keys.len() - hits.len();
+
+        let (mesh, stats, locals) = triangulate_reusing(
+            s, &to_mesh, &MeshParams::default(),
+            |id| hits.get(&id.0).cloned());
+        self.solids = locals.into_iter()
+            .map(|(id, local)| {
+                let (h, closure) = keys[&id.0].clone();
+                (h, Cached { closure, local })
+            })
+            .collect();
+        (mesh, stats)
+    }
+
+    /// Returns the number of solids which were reused by the last call to
+    /// [`SolidCache::triangulate`]
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+
+    /// Returns the number of solids which were triangulated by the last call
+    /// to [`SolidCache::triangulate`]
+    pub fn triangulated(&self) -> usize {
+        self.triangulated
+    }
+}
+
+/// Rewrites a cached solid's face and diagnostic IDs to match a closure with
+/// the same contents, which may have been renumbered
+fn renumber(c: &Cached, closure: &[usize]) -> LocalSolid {
+    if c.closure == closure {
+        return c.local.clone();
+    }
+    let map: HashMap<usize, usize> = c.closure.iter().copied()
+        .zip(closure.iter().copied())
+        .collect();
+    let id = |i: usize| map.get(&i).copied().unwrap_or(i);
+    let (mesh, stats) = c.local.as_ref();
+    let mesh = Mesh {
+        verts: mesh.verts.clone(),
+        triangles: mesh.triangles.iter()
+            .map(|t| Triangle { verts: t.verts, face: id(t.face) })
+            .collect(),
+        parts: mesh.parts.clone(),
+        offset: mesh.offset,
+    };
+    let stats = Stats {
+        diagnostics: stats.diagnostics.iter().cloned()
+            .map(|mut d| { d.id = id(d.id); d })
+            .collect(),
+        ..*stats
+    };
+    Arc::new((mesh, stats))
+}
+
+/// Hashes the contents of entities and everything which they refer to
+struct Closures<'a> {
+    by_id: HashMap<usize, &'a Record>,
+    /// Hash of each entity, or `None` while it's being hashed (to break
+    /// reference cycles)
+    hashes: HashMap<usize, Option<u64>>,
+}
+
+impl Closures<'_> {
+    /// Hashes an entity, with each reference replaced by the hash of the
+    /// entity that it refers to, so that the result doesn't depend on IDs
+    fn hash(&mut self, id: usize) -> u64 {
+        match self.hashes.get(&id) {
+            Some(Some(h)) => return *h,
+            Some(None) => return 0, // reference cycle
+            None => (),
+        }
+        let r = match self.by_id.get(&id) {
+            Some(r) => *r,
+            None => return 1, // missing entity
+        };
+        self.hashes.insert(id, None);
+        let mut h = DefaultHasher::new();
+        for (name, ps) in &r.parts {
+            name.hash(&mut h);
+            ps.len().hash(&mut h);
+            for p in ps {
+                self.hash_param(p, &mut h);
+            }
+        }
+        let out = h.finish();
+        self.hashes.insert(id, Some(out));
+        out
+    }
+
+    fn hash_param(&mut self, p: &Param, h: &mut DefaultHasher) {
+        std::mem::discriminant(p).hash(h);
+        match p {
+            Param::Unset | Param::Derived => (),
+            Param::Ref(i) => self.hash(*i).hash(h),
+            Param::Integer(i) => i.hash(h),
+            Param::Real(f) => f.to_bits().hash(h),
+            Param::String(s) | Param::Enum(s) => s.hash(h),
+            Param::Logical(b) => b.hash(h),
+            Param::List(v) => {
+                v.len().hash(h);
+                for p in v {
+                    self.hash_param(p, h);
+                }
+            },
+            Param::Typed(t, p) => {
+                t.hash(h);
+                self.hash_param(p, h);
+            },
+        }
+    }
+
+    /// Lists every entity which is reachable from `root`, in an order which
+    /// only depends on their contents (depth-first, following references in
+    /// the order in which they're written)
+    fn closure(&self, root: usize) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut seen = HashSet::new();
+        let mut todo = vec![root];
+        while let Some(id) = todo.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            out.push(id);
+            let mut refs = Vec::new();
+            if let Some(r) = self.by_id.get(&id) {
+                for (_, ps) in &r.parts {
+                    collect_refs(ps, &mut refs);
+                }
+            }
+            todo.extend(refs.into_iter().rev());
+        }
+        out
+    }
+}
+
+fn collect_refs(ps: &[Param], out: &mut Vec<usize>) {
+    for p in ps {
+        match p {
+            Param::Ref(i) => out.push(*i),
+            Param::List(v) => collect_refs(v, out),
+            Param::Typed(_, p) => collect_refs(std::slice::from_ref(&**p), out),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::triangulate::triangulate;
+
+    /// Renumbers every entity in a file by adding an offset to its ID
+    fn renumber_file(data: &str, offset: usize) -> String {
+        let mut out = String::new();
+        let mut chars = data.chars().peekable();
+        while let Some(c) = chars.next() {
+            out.push(c);
+            if c == '#' {
+                let mut n = String::new();
+                while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    n.push(*d);
+                    chars.next();
+                }
+                out += &(n.parse::<usize>().unwrap() + offset).to_string();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_solid_cache() {
+        let text = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"), "/../examples/cube_hole.step"))
+            .unwrap();
+        let flat = StepFile::strip_flatten(text.as_bytes());
+        let s = StepFile::parse(&flat);
+        let (expected, _) = triangulate(&s);
+
+        let mut cache = SolidCache::new();
+        let (mesh, _) = cache.triangulate(&flat, &s);
+        assert_eq!((cache.reused(), cache.triangulated()), (0, 1));
+        assert_eq!(mesh.triangles.len(), expected.triangles.len());
+
+        let (mesh, _) = cache.triangulate(&flat, &s);
+        assert_eq!((cache.reused(), cache.triangulated()), (1, 0));
+        assert_eq!(mesh.triangles.len(), expected.triangles.len());
+
+        // A renumbered file still hits the cache, with faces renumbered
+        let moved = renumber_file(&text, 1000);
+        let flat = StepFile::strip_flatten(moved.as_bytes());
+        let s = StepFile::parse(&flat);
+        let (mesh, _) = cache.triangulate(&flat, &s);
+        assert_eq!((cache.reused(), cache.triangulated()), (1, 0));
+        for (a, b) in mesh.triangles.iter().zip(&expected.triangles) {
+            assert_eq!(a.face, b.face + 1000);
+        }
+
+        // Moving a vertex of the solid forces it to be re-triangulated
+        let i = moved.find("VERTEX_POINT('',").unwrap() + 16;
+        let point = &moved[i..i + moved[i..].find(')').unwrap()];
+        let def = format!("{}=CARTESIAN_POINT('',(", point);
+        let j = moved.find(&def).unwrap() + def.len();
+        let edited = format!("{}1{}", &moved[..j], &moved[j..]);
+        let flat = StepFile::strip_flatten(edited.as_bytes());
+        let s = StepFile::parse(&flat);
+        cache.triangulate(&flat, &s);
+        assert_eq!((cache.reused(), cache.triangulated()), (0, 1));
+    }
+}
@@ -0,0 +1,484 @@
+//! Compact storage for meshes which are too big to keep in memory as-is.
+//!
+//! A [`Mesh`] spends 72 bytes on each vertex and 20 on each triangle.  A
+//! [`CompressedMesh`] instead splits the mesh into nodes (one per placed
+//! part), then stores each node as a byte stream:
+//! - positions are quantized within the node's bounding box, and each one is
+//!   written as a variable-length delta from the previous vertex;
+//! - normals are octahedron-encoded and delta-coded the same way;
+//! - colors are stored once per node when they're uniform (as they are for
+//!   triangulated solids);
+//! - triangle indices count back from the highest index seen so far, so a
+//!   vertex's first use is usually a single zero byte.
+//!
+//! Nodes are decompressed one at a time, so exporting a compressed mesh only
+//! ever needs one node's worth of uncompressed memory.
+use std::convert::TryInto;
+use std::io::Write;
+use std::ops::Range;
+
+use nalgebra_glm::{DVec2, DVec3, U32Vec3};
+
+use crate::{
+    export::ExportOptions,
+    mesh::{Mesh, Part, Triangle, Vertex},
+};
+
+/// Options for [`CompressedMesh::new`]
+#[derive(Copy, Clone, Debug)]
+pub struct CompressOptions {
+    /// Bits per position coordinate, spread over each node's bounding box.
+    /// Positions are off by at most half of the box's size divided by
+    /// 2<sup>bits</sup>, e.g. 0.5 µm for a 1 m part with 20 bits.
+    pub position_bits: u32,
+    /// Bits per component of the octahedron-encoded normals
+    pub normal_bits: u32,
+}
+
+impl Default for CompressOptions {
+    fn default() -> Self {
+        Self { position_bits: 20, normal_bits: 12 }
+    }
+}
+
+/// One part of the mesh, compressed on its own
+#[derive(Clone, Debug)]
+struct Node {
+    /// The node's part (whose triangle range is replaced when decoding), or
+    /// `None` for triangles outside of any part
+    part: Option<Part>,
+    verts: usize,
+    triangles: usize,
+    origin: DVec3,
+    /// Size of one quantization step along each axis
+    step: DVec3,
+    /// Color shared by every vertex, if there is one
+    color: Option<DVec3>,
+    data: Vec<u8>,
+}
+
+/// A mesh whose parts are compressed independently; see the module docs
+#[derive(Clone, Debug, Default)]
+pub struct CompressedMesh {
+    nodes: Vec<Node>,
+    normal_bits: u32,
+    /// Copied from [`Mesh::offset`]
+    pub offset: DVec3,
+}
+
+impl CompressedMesh {
+    pub fn new(mesh: &Mesh, options: &CompressOptions) -> Self {
+        assert!((1..=30).contains(&options.position_bits),
+                "position_bits must be between 1 and 30");
+        assert!((2..=16).contains(&options.normal_bits),
+                "normal_bits must be between 2 and 16");
+        let mut nodes = Vec::new();
+        let mut next = 0;
+        for p in &mesh.parts {
+            if p.triangles.start > next {
+                nodes.push(Node::new(mesh, next..p.triangles.start, None,
+                                     options));
+            }
+            nodes.push(Node::new(mesh, p.triangles.clone(), Some(p),
+                                 options));
+            next = next.max(p.triangles.end);
+        }
+        if next < mesh.triangles.len() {
+            nodes.push(Node::new(mesh, next..mesh.triangles.len(), None,
+                                 options));
+        }
+        Self { nodes, normal_bits: options.normal_bits, offset: mesh.offset }
+    }
+
+    /// Returns the number of nodes, each of which can be decompressed on
+    /// its own with [`CompressedMesh::node`]
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn num_triangles(&self) -> usize {
+        self.nodes.iter().map(|n| n.triangles).sum()
+    }
+
+    pub fn num_verts(&self) -> usize {
+        self.nodes.iter().map(|n| n.verts).sum()
+    }
+
+    /// Returns the approximate number of bytes used by the compressed data
+    pub fn size(&self) -> usize {
+        self.nodes.iter()
+            .map(|n| std::mem::size_of::<Node>() + n.data.len())
+            .sum()
+    }
+
+    /// Decompresses one node as a standalone mesh, which has a single part
+    /// (unless the node's triangles weren't in a part to begin with)
+    pub fn node(&self, i: usize) -> Mesh {
+        let mut mesh = Mesh { offset: self.offset, ..Mesh::default() };
+        self.nodes[i].decode(self.normal_bits, &mut mesh);
+        mesh
+    }
+
+    /// Decompresses the whole mesh
+    pub fn to_mesh(&self) -> Mesh {
+        let mut mesh = Mesh { offset: self.offset, ..Mesh::default() };
+        for n in &self.nodes {
+            n.decode(self.normal_bits, &mut mesh);
+        }
+        mesh
+    }
+
+    /// Writes the mesh to a binary STL, decompressing one node at a time.
+    /// Returns the number of bytes written.
+    pub fn save_stl(&self, filename: &str) -> std::io::Result<usize> {
+        self.save_stl_with(filename, &ExportOptions::default())
+    }
+
+    /// Writes the mesh to a binary STL with the given winding order, as in
+    /// [`Mesh::save_stl_with`]
+    pub fn save_stl_with(&self, filename: &str, options: &ExportOptions)
+        -> std::io::Result<usize>
+    {
+        let mut out = std::io::BufWriter::new(std::fs::File::create(filename)?);
+        out.write_all(&[b'x'; 80])?; // header
+        let n: u32 = self.num_triangles().try_into()
+            .expect("Too many triangles");
+        out.write_all(&n.to_le_bytes())?;
+        for i in 0..self.nodes.len() {
+            let mesh = self.node(i);
+            for t in &mesh.triangles {
+                out.write_all(&[0; 12])?; // normal
+                for v in options.wind(t.verts.into()).iter() {
+                    let p = mesh.verts[*v as usize].pos;
+                    for c in p.iter() {
+                        out.write_all(&(*c as f32).to_le_bytes())?;
+                    }
+                }
+                out.write_all(&[0; 2])?; // attributes
+            }
+        }
+        out.flush()?;
+        Ok(84 + 50 * n as usize)
+    }
+
+    /// Writes the mesh as binary glTF.  glTF needs every vertex in a single
+    /// buffer, so this decompresses the whole mesh.
+    pub fn save_glb(&self, filename: &str) -> std::io::Result<usize> {
+        self.save_glb_with(filename, &ExportOptions::default())
+    }
+
+    /// Writes the mesh as binary glTF with the given winding order, as in
+    /// [`Mesh::save_glb_with`]
+    pub fn save_glb_with(&self, filename: &str, options: &ExportOptions)
+        -> std::io::Result<usize>
+    {
+        self.to_mesh().save_glb_with(filename, options)
+    }
+}
+
+impl Node {
+    fn new(mesh: &Mesh, tris: Range<usize>, part: Option<&Part>,
+           options: &CompressOptions) -> Self
+    {
+        // Number the node's vertices in the order in which they're first
+        // used, so that triangle indices stay small
+        let mut local = std::collections::HashMap::new();
+        let mut order = Vec::new();
+        let mut indices = Vec::with_capacity(tris.len() * 3);
+        for t in &mesh.triangles[tris.clone()] {
+            for v in t.verts.iter() {
+                let i = *local.entry(*v).or_insert_with(|| {
+                    order.push(*v as usize);
+                    order.len() - 1
+                });
+                indices.push(i);
+            }
+        }
+        let verts: Vec<&Vertex> = order.iter().map(|i| &mesh.verts[*i]).collect();
+
+        let mut lo = DVec3::repeat(f64::INFINITY);
+        let mut hi = DVec3::repeat(f64::NEG_INFINITY);
+        for v in &verts {
+            lo = lo.inf(&v.pos);
+            hi = hi.sup(&v.pos);
+        }
+        let steps = ((1u64 << options.position_bits) - 1) as f64;
+        let step = if verts.is_empty() {
+            DVec3::zeros()
+        } else {
+            (hi - lo).map(|d| if d > 0.0 { d / steps } else { 1.0 })
+        };
+        let origin = if verts.is_empty() { DVec3::zeros() } else { lo };
+
+        let mut data = Vec::new();
+        let mut prev = [0i64; 3];
+        for v in &verts {
+            for i in 0..3 {
+                let q = ((v.pos[i] - origin[i]) / step[i]).round() as i64;
+                write_signed(q - prev[i], &mut data);
+                prev[i] = q;
+            }
+        }
+        let mut prev = [0i64; 2];
+        for v in &verts {
+            let n = encode_normal(&v.norm, options.normal_bits);
+            for i in 0..2 {
+                write_signed(n[i] - prev[i], &mut data);
+                prev[i] = n[i];
+            }
+        }
+        let color = verts.first()
+            .map(|v| v.color)
+            .filter(|c| verts.iter().all(|v| v.color == *c));
+        if color.is_none() {
+            for v in &verts {
+                for c in v.color.iter() {
+                    data.extend(&c.to_le_bytes());
+                }
+            }
+        }
+        let mut seen = 0;
+        let mut prev_face = 0i64;
+        for (t, idx) in mesh.triangles[tris.clone()].iter()
+            .zip(indices.chunks(3))
+        {
+            for i in idx {
+                // New vertices are numbered in order, so they encode as 0
+                write_unsigned((seen - *i) as u64, &mut data);
+                seen = seen.max(*i + 1);
+            }
+            write_signed(t.face as i64 - prev_face, &mut data);
+            prev_face = t.face as i64;
+        }
+        data.shrink_to_fit();
+
+        Self {
+            part: part.cloned(),
+            verts: verts.len(),
+            triangles: tris.len(),
+            origin,
+            step,
+            color,
+            data,
+        }
+    }
+
+    /// Appends the node's vertices and triangles (and its part, if any) to
+    /// a mesh
+    fn decode(&self, normal_bits: u32, mesh: &mut Mesh) {
+        let mut r = Reader { data: &self.data, pos: 0 };
+        let v_start = mesh.verts.len();
+        let mut q = [0i64; 3];
+        let color = self.color.unwrap_or_else(DVec3::zeros);
+        for _ in 0..self.verts {
+            for c in q.iter_mut() {
+                *c += r.signed();
+            }
+            let pos = self.origin + DVec3::new(q[0] as f64, q[1] as f64,
+                                               q[2] as f64)
+                .component_mul(&self.step);
+            mesh.verts.push(Vertex { pos, norm: DVec3::zeros(), color });
+        }
+        let mut n = [0i64; 2];
+        for v in &mut mesh.verts[v_start..] {
+            for c in n.iter_mut() {
+                *c += r.signed();
+            }
+            v.norm = decode_normal(n, normal_bits);
+        }
+        if self.color.is_none() {
+            for v in &mut mesh.verts[v_start..] {
+                for i in 0..3 {
+                    v.color[i] = r.f64();
+                }
+            }
+        }
+        let t_start = mesh.triangles.len();
+        let mut seen = 0;
+        let mut face = 0i64;
+        for _ in 0..self.triangles {
+            let mut idx = [0u32; 3];
+            for i in idx.iter_mut() {
+                let local = seen - r.unsigned() as usize;
+                seen = seen.max(local + 1);
+                *i = (v_start + local) as u32;
+            }
+            face += r.signed();
+            mesh.triangles.push(Triangle {
+                verts: U32Vec3::new(idx[0], idx[1], idx[2]),
+                face: face as usize,
+            });
+        }
+        if let Some(p) = &self.part {
+            mesh.parts.push(Part {
+                triangles: t_start..mesh.triangles.len(),
+                ..p.clone()
+            });
+        }
+    }
+}
+
+/// Maps a normal onto the octahedron, then quantizes it to `bits` per
+/// component.  Zero-length normals come back as +Z.
+fn encode_normal(n: &DVec3, bits: u32) -> [i64; 2] {
+    let l1 = n.x.abs() + n.y.abs() + n.z.abs();
+    let mut p = if l1 > 0.0 {
+        DVec2::new(n.x / l1, n.y / l1)
+    } else {
+        DVec2::zeros()
+    };
+    if n.z < 0.0 {
+        // Fold the lower hemisphere over the upper one
+        p = DVec2::new((1.0 - p.y.abs()) * p.x.signum(),
+                       (1.0 - p.x.abs()) * p.y.signum());
+    }
+    let scale = ((1i64 << (bits - 1)) - 1) as f64;
+    [(p.x * scale).round() as i64, (p.y * scale).round() as i64]
+}
+
+fn decode_normal(q: [i64; 2], bits: u32) -> DVec3 {
+    let scale = ((1i64 << (bits - 1)) - 1) as f64;
+    let p = DVec2::new(q[0] as f64 / scale, q[1] as f64 / scale);
+    let z = 1.0 - p.x.abs() - p.y.abs();
+    let (x, y) = if z < 0.0 {
+        ((1.0 - p.y.abs()) * p.x.signum(), (1.0 - p.x.abs()) * p.y.signum())
+    } else {
+        (p.x, p.y)
+    };
+    DVec3::new(x, y, z).normalize()
+}
+
+/// Writes an unsigned LEB128 integer
+fn write_unsigned(mut v: u64, out: &mut Vec<u8>) {
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+/// Writes a signed integer, zig-zag encoded so that small negative numbers
+/// stay small
+fn write_signed(v: i64, out: &mut Vec<u8>) {
+    write_unsigned(((v << 1) ^ (v >> 63)) as u64, out)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn unsigned(&mut self) -> u64 {
+        let mut out = 0;
+        let mut shift = 0;
+        loop {
+            let b = self.data[self.pos];
+            self.pos += 1;
+            out |= ((b & 0x7F) as u64) << shift;
+            if b & 0x80 == 0 {
+                return out;
+            }
+            shift += 7;
+        }
+    }
+
+    fn signed(&mut self) -> i64 {
+        let v = self.unsigned();
+        ((v >> 1) as i64) ^ -((v & 1) as i64)
+    }
+
+    fn f64(&mut self) -> f64 {
+        let b = self.data[self.pos..self.pos + 8].try_into().unwrap();
+        self.pos += 8;
+        f64::from_le_bytes(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use step::step_file::StepFile;
+    use crate::triangulate::triangulate;
+
+    #[test]
+    fn test_roundtrip() {
+        let data = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"),
+                                         "/../examples/cube_hole.step"))
+            .unwrap();
+        let flat = StepFile::strip_flatten(&data);
+        let (mesh, _) = triangulate(&StepFile::parse(&flat));
+
+        let options = CompressOptions::default();
+        let c = CompressedMesh::new(&mesh, &options);
+        assert_eq!(c.num_triangles(), mesh.triangles.len());
+        assert!(c.size() * 4 < mesh.verts.len() * 72 +
+                              mesh.triangles.len() * 20);
+
+        let out = c.to_mesh();
+        assert_eq!(out.parts, mesh.parts);
+        let r = mesh.precision_report().unwrap();
+        let tol = r.extent / (1u64 << options.position_bits) as f64;
+        for (a, b) in out.triangles.iter().zip(&mesh.triangles) {
+            assert_eq!(a.face, b.face);
+            for (i, j) in a.verts.iter().zip(b.verts.iter()) {
+                let (u, v) = (&out.verts[*i as usize], &mesh.verts[*j as usize]);
+                assert!((u.pos - v.pos).norm() <= tol);
+                assert!((u.norm - v.norm.normalize()).norm() < 1e-2);
+                assert_eq!(u.color, v.color);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_nodes() {
+        let v = |x: f64, c: f64| Vertex {
+            pos: DVec3::new(x, -x, 0.5),
+            norm: DVec3::new(0.0, 0.0, -1.0),
+            color: DVec3::new(c, 0.0, 0.0),
+        };
+        let t = |a, b, c, face| Triangle {
+            verts: U32Vec3::new(a, b, c), face,
+        };
+        // Triangles before the part aren't in any part, and share vertices
+        // with it; the part's vertices have different colors
+        let mesh = Mesh {
+            verts: vec![v(0.0, 1.0), v(1.0, 1.0), v(2.0, 0.5), v(3.0, 0.25)],
+            triangles: vec![t(0, 1, 2, 7), t(2, 1, 3, 9), t(3, 2, 0, 4)],
+            parts: vec![Part { id: 5, triangles: 1..3, opacity: 0.5,
+                               bounds: None, metadata: Default::default() }],
+            offset: DVec3::new(1.0, 2.0, 3.0),
+        };
+        let c = CompressedMesh::new(&mesh, &CompressOptions::default());
+        assert_eq!(c.num_nodes(), 2);
+        assert_eq!(c.num_verts(), 7);
+        assert_eq!(c.node(0).parts, []);
+        assert_eq!(c.node(1).parts[0].triangles, 0..2);
+
+        let out = c.to_mesh();
+        assert_eq!(out.offset, mesh.offset);
+        assert_eq!(out.parts[0].triangles, 1..3);
+        for (a, b) in out.triangles.iter().zip(&mesh.triangles) {
+            assert_eq!(a.face, b.face);
+            for (i, j) in a.verts.iter().zip(b.verts.iter()) {
+                let (u, v) = (&out.verts[*i as usize], &mesh.verts[*j as usize]);
+                assert!((u.pos - v.pos).norm() < 1e-5);
+                assert!((u.norm - v.norm).norm() < 1e-9);
+                assert_eq!(u.color, v.color);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normals() {
+        for n in &[DVec3::x(), -DVec3::y(), -DVec3::z(),
+                   DVec3::new(1.0, -2.0, -3.0).normalize(),
+                   DVec3::new(-0.3, 0.1, 0.9).normalize()]
+        {
+            let d = decode_normal(encode_normal(n, 12), 12);
+            assert!((d - n).norm() < 2e-3, "{:?} became {:?}", n, d);
+        }
+        assert_eq!(decode_normal(encode_normal(&DVec3::zeros(), 12), 12),
+                   DVec3::z());
+    }
+}
@@ -0,0 +1,112 @@
+use step::step_file::{DuplicateIds, ParseOptions, StepFile};
+
+use crate::{
+    mesh::Mesh, stats::Stats,
+    triangulate::{triangulate, triangulate_with_tolerance},
+};
+
+/// Options for [`convert`]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConvertOptions {
+    pub parse: ParseOptions,
+    /// Number of threads used to parse and triangulate.  `None` uses rayon's
+    /// global thread pool, which has one thread per core unless the
+    /// application configures it otherwise; `Some(n)` builds a dedicated pool
+    /// of `n` threads for each run (with `Some(0)` picking a thread count
+    /// the same way as the global pool).
+    ///
+    /// `Some(1)` does all of the work on a single thread, one item after
+    /// another, which makes runs repeatable under a debugger.  Builds
+    /// without the `parallel` feature (e.g. for WASM) always run on the
+    /// calling thread and ignore this setting.
+    pub threads: Option<usize>,
+    /// Distance below which points are treated as the same, when dropping
+    /// short edges and welding vertices.  `None` uses the model's own
+    /// uncertainty (see [`model_tolerance`]).
+    ///
+    /// [`model_tolerance`]: crate::tolerance::model_tolerance
+    pub tolerance: Option<f64>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConvertError {
+    #[error(transparent)]
+    DuplicateIds(#[from] DuplicateIds),
+
+    #[cfg(feature = "rayon")]
+    #[error("Could not build thread pool: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+}
+
+impl ConvertOptions {
+    /// Runs `f` on the configured threads.  Any parsing or triangulation
+    /// within `f` (e.g. [`StepFile::parse`] or [`triangulate`]) uses them
+    /// instead of the global thread pool.
+    pub fn run<T, F>(&self, f: F) -> Result<T, ConvertError>
+        where F: FnOnce() -> T + Send, T: Send
+    {
+        #[cfg(feature = "rayon")]
+        if let Some(n) = self.threads {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()?;
+            return Ok(pool.install(f));
+        }
+        Ok(f())
+    }
+}
+
+/// Parses a STEP file and triangulates every solid in it
+///
+/// ```
+/// use triangulate::convert::{convert, ConvertOptions};
+///
+/// let data = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"),
+///                                  "/../examples/cuboid.step"))?;
+/// let options = ConvertOptions { threads: Some(1), ..Default::default() };
+/// let (mesh, stats) = convert(&data, &options)?;
+/// assert_eq!(stats.num_errors, 0);
+/// assert_eq!(mesh.triangles.len(), 12);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn convert(data: &[u8], options: &ConvertOptions)
+    -> Result<(Mesh, Stats), ConvertError>
+{
+    options.run(|| {
+        let flat = StepFile::strip_flatten(data);
+        let s = StepFile::parse_with_options(&flat, &options.parse)?;
+        Ok(match options.tolerance {
+            Some(t) => triangulate_with_tolerance(&s, None, t, &mut ()),
+            None => triangulate(&s),
+        })
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_threads() {
+        let data = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"),
+                                         "/../examples/cube_hole.step"))
+            .unwrap();
+        let (expected, _) = convert(&data, &ConvertOptions::default())
+            .unwrap();
+        for threads in &[Some(1), Some(3)] {
+            let options = ConvertOptions {
+                threads: *threads,
+                ..ConvertOptions::default()
+            };
+            let (mesh, stats) = convert(&data, &options).unwrap();
+            assert_eq!(stats.num_errors, 0);
+            assert_eq!(mesh.triangles.len(), expected.triangles.len());
+            for (a, b) in mesh.triangles.iter().zip(&expected.triangles) {
+                assert_eq!((a.verts, a.face), (b.verts, b.face));
+            }
+            for (a, b) in mesh.verts.iter().zip(&expected.verts) {
+                assert_eq!((a.pos, a.norm), (b.pos, b.norm));
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use nalgebra_glm as glm;
+use glm::{DMat4, DVec3, DVec4};
+
+/// How serious a [`Diagnostic`] is
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Severity {
+    /// An entity was ignored, e.g. because its type isn't supported
+    Skipped,
+    /// A face couldn't be triangulated, leaving a hole in the mesh
+    Error,
+    /// Triangulating a face panicked, leaving a hole in the mesh
+    Panic,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Severity::Skipped => write!(f, "skipped"),
+            Severity::Error => write!(f, "error"),
+            Severity::Panic => write!(f, "panic"),
+        }
+    }
+}
+
+/// A problem which was found while triangulating, recorded so that it can be
+/// shown to the user (rather than only logged)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Entity ID of the face, shell, or solid which caused the problem
+    pub id: usize,
+    pub message: String,
+    /// Bounding box (min, max) of the problem in model coordinates, if known.
+    /// For a solid which is placed more than once, this is the location of
+    /// its first instance.
+    pub bounds: Option<(DVec3, DVec3)>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, id: usize, message: String) -> Self {
+        Self { severity, id, message, bounds: None }
+    }
+
+    /// Sets the bounds to enclose the given points, leaving them unset if
+    /// there are no points
+    pub fn with_points<'a, I>(mut self, pts: I) -> Self
+        where I: IntoIterator<Item=&'a DVec3>
+    {
+        self.bounds = bounds(pts);
+        self
+    }
+
+    /// Returns the center of the bounding box, if known
+    pub fn center(&self) -> Option<DVec3> {
+        self.bounds.map(|(lo, hi)| (lo + hi) / 2.0)
+    }
+
+    /// Moves the bounding box by an affine transform, keeping it axis-aligned
+    pub fn transform(&mut self, mat: &DMat4) {
+        if let Some((lo, hi)) = self.bounds {
+            let corners = (0..8).map(|i| {
+                let p = DVec4::new(if i & 1 == 0 { lo.x } else { hi.x },
+                                   if i & 2 == 0 { lo.y } else { hi.y },
+                                   if i & 4 == 0 { lo.z } else { hi.z },
+                                   1.0);
+                (mat * p).xyz()
+            }).collect::<Vec<_>>();
+            self.bounds = bounds(&corners);
+        }
+    }
+
+    /// Moves the bounding box by a fixed offset, e.g. to follow
+    /// [`Mesh::recenter`](crate::mesh::Mesh::recenter)
+    pub fn translate(&mut self, offset: DVec3) {
+        if let Some((lo, hi)) = &mut self.bounds {
+            *lo += offset;
+            *hi += offset;
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{}: {}: {}", self.id, self.severity, self.message)
+    }
+}
+
+/// Finds the bounding box of a set of points
+fn bounds<'a, I>(pts: I) -> Option<(DVec3, DVec3)>
+    where I: IntoIterator<Item=&'a DVec3>
+{
+    pts.into_iter().fold(None, |b, p| Some(match b {
+        None => (*p, *p),
+        Some((lo, hi)) => (glm::min2(&lo, p), glm::max2(&hi, p)),
+    }))
+}
+
+/// Converts the payload of a caught panic into a message
+pub(crate) fn panic_message(e: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        (*s).to_owned()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
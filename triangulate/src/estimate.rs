@@ -0,0 +1,219 @@
+use step::{ap214::*, ap214::Entity, step_file::StepFile};
+
+use crate::triangulate::solid_transforms;
+
+/// Points along a closed circle or ellipse, matching [`Curve::build`]
+///
+/// [`Curve::build`]: crate::curve::Curve::build
+const CLOSED_ARC_POINTS: usize = 64;
+/// Points along an open arc, assuming a quarter circle (e.g. a fillet)
+const OPEN_ARC_POINTS: usize = 16;
+/// Points per knot span along a spline curve
+const SPLINE_POINTS_PER_KNOT: usize = 8;
+/// Points along a curve of unknown type
+const OTHER_CURVE_POINTS: usize = 16;
+/// Longest chain of surface or seam curves followed to find a 3D curve
+const MAX_CURVE_DEPTH: usize = 8;
+
+/// The broad class of a face's surface, which decides how it's meshed
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SurfaceKind {
+    Plane,
+    /// Cylinders, cones, spheres, and tori
+    Analytic,
+    /// B-spline and NURBS surfaces, which need an iterative solve to lower
+    /// each boundary point into the surface's parameter space
+    Spline,
+    Unknown,
+}
+
+impl SurfaceKind {
+    fn of(s: &StepFile, surf: Surface) -> Self {
+        match &s[surf] {
+            Entity::Plane(_) => Self::Plane,
+            Entity::CylindricalSurface(_) | Entity::ConicalSurface(_) |
+            Entity::SphericalSurface(_) | Entity::ToroidalSurface(_) =>
+                Self::Analytic,
+            Entity::BSplineSurfaceWithKnots(_) | Entity::ComplexEntity(_) =>
+                Self::Spline,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Steiner points added to the face's interior, matching
+    /// [`Surface::add_steiner_points`](crate::surface::Surface::add_steiner_points).
+    /// A toroidal face bounded by a closed edge wraps all the way around the
+    /// torus, so it's split in half and each half gets its own points.
+    fn interior_points(s: &StepFile, surf: Surface, closed: bool) -> usize {
+        match &s[surf] {
+            Entity::SphericalSurface(_) => 6 * 6,
+            Entity::ToroidalSurface(_) if closed => 2 * 32 * 32,
+            Entity::ToroidalSurface(_) => 32 * 32,
+            _ => 0,
+        }
+    }
+
+    /// Relative cost of placing one point on this kind of surface
+    fn weight(&self) -> f64 {
+        match self {
+            Self::Plane => 1.0,
+            Self::Analytic | Self::Unknown => 2.0,
+            Self::Spline => 8.0,
+        }
+    }
+}
+
+/// Estimated output and meshing cost for one solid (or surface model)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolidEstimate {
+    /// Entity ID of the solid
+    pub id: usize,
+    /// Number of times the solid is placed in the model.  Each instance is a
+    /// copy of the same mesh, so this multiplies triangles but not cost.
+    pub instances: usize,
+    pub faces: usize,
+    /// Faces on B-spline or NURBS surfaces
+    pub spline_faces: usize,
+    /// Estimated triangles for a single instance
+    pub triangles: usize,
+    /// Estimated meshing cost, in arbitrary units which are only meaningful
+    /// relative to other estimates
+    pub cost: f64,
+}
+
+/// Rough estimates of triangle counts and meshing cost for every solid in a
+/// file.  These are computed from entity counts and surface types without
+/// evaluating any geometry, so they're much faster to build than a mesh.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Estimate {
+    /// Per-solid estimates, in the order that solids are meshed
+    pub solids: Vec<SolidEstimate>,
+}
+
+impl Estimate {
+    /// Total estimated triangles in the output mesh, including every instance
+    pub fn triangles(&self) -> usize {
+        self.solids.iter().map(|s| s.triangles * s.instances).sum()
+    }
+
+    /// Total estimated meshing cost
+    pub fn cost(&self) -> f64 {
+        self.solids.iter().map(|s| s.cost).sum()
+    }
+
+    /// Returns solids from most to least expensive, so that big solids can be
+    /// scheduled first for better load balance
+    pub fn by_cost(&self) -> Vec<&SolidEstimate> {
+        let mut out: Vec<_> = self.solids.iter().collect();
+        out.sort_by(|a, b| b.cost.partial_cmp(&a.cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.id.cmp(&b.id)));
+        out
+    }
+}
+
+/// Estimates the number of points sampled along an edge
+fn edge_points(s: &StepFile, e: EdgeCurve) -> usize {
+    let e = match s.entity(e) {
+        Some(e) => e,
+        None => return 0,
+    };
+    if e.edge_start == e.edge_end {
+        return CLOSED_ARC_POINTS;
+    }
+    // Surface and seam curves point at their 3D curve, which is followed for a
+    // bounded number of steps in case a malformed file has a cycle
+    let mut curve = e.edge_geometry;
+    for _ in 0..MAX_CURVE_DEPTH {
+        curve = match &s[curve] {
+            Entity::Line(_) => return 1,
+            Entity::Circle(_) | Entity::Ellipse(_) => return OPEN_ARC_POINTS,
+            Entity::BSplineCurveWithKnots(c) => return
+                SPLINE_POINTS_PER_KNOT * c.knots.len().saturating_sub(1).max(1),
+            Entity::SurfaceCurve(c) => c.curve_3d,
+            Entity::SeamCurve(c) => c.curve_3d,
+            _ => break,
+        };
+    }
+    OTHER_CURVE_POINTS
+}
+
+/// Checks whether an edge starts and ends at the same vertex
+fn is_closed(s: &StepFile, e: EdgeCurve) -> bool {
+    s.entity(e).map(|e| e.edge_start == e.edge_end).unwrap_or(false)
+}
+
+/// Returns (boundary points, number of loops, whether any edge is closed)
+/// for a face
+fn face_boundary(s: &StepFile, face: &AdvancedFace_) -> (usize, usize, bool) {
+    let mut points = 0;
+    let mut closed = false;
+    for b in &face.bounds {
+        let bound = match &s[*b] {
+            Entity::FaceBound(b) => b.bound,
+            Entity::FaceOuterBound(b) => b.bound,
+            _ => continue,
+        };
+        match &s[bound] {
+            Entity::EdgeLoop(e) => for o in e.edge_list.iter()
+                .filter_map(|o| s.entity(*o))
+            {
+                let e = o.edge_element.cast();
+                points += edge_points(s, e);
+                closed |= is_closed(s, e);
+            },
+            Entity::VertexLoop(_) => points += 1,
+            _ => (),
+        }
+    }
+    (points, face.bounds.len(), closed)
+}
+
+/// Estimates a single solid, which is placed `instances` times
+pub(crate) fn estimate_solid(s: &StepFile, id: RepresentationItem, instances: usize)
+    -> SolidEstimate
+{
+    let mut out = SolidEstimate {
+        id: id.0, instances, faces: 0, spline_faces: 0, triangles: 0, cost: 0.0,
+    };
+    let shells = match &s[id] {
+        Entity::ManifoldSolidBrep(b) => vec![b.outer.cast()],
+        Entity::BrepWithVoids(b) => vec![b.outer.cast()],
+        Entity::ShellBasedSurfaceModel(b) => b.sbsm_boundary.clone(),
+        _ => vec![],
+    };
+    for shell in shells {
+        let faces = match &s[shell] {
+            Entity::ClosedShell(c) => &c.cfs_faces,
+            Entity::OpenShell(c) => &c.cfs_faces,
+            _ => continue,
+        };
+        for f in faces.iter().filter_map(|f| s.entity(f.cast::<AdvancedFace_>())) {
+            let kind = SurfaceKind::of(s, f.face_geometry);
+            let (boundary, loops, closed) = face_boundary(s, f);
+            let interior = SurfaceKind::interior_points(
+                s, f.face_geometry, closed);
+
+            // A triangulation of a polygon with n vertices, h holes, and i
+            // interior points has n + 2i + 2h - 2 triangles
+            let n = boundary + interior;
+            out.triangles += (boundary + 2 * interior + 2 * loops)
+                .saturating_sub(4);
+            out.cost += kind.weight() * n as f64 * (n.max(2) as f64).log2();
+            out.faces += 1;
+            if kind == SurfaceKind::Spline {
+                out.spline_faces += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Estimates triangle counts and meshing cost for every solid which
+/// [`triangulate`](crate::triangulate::triangulate) would mesh
+pub fn estimate(s: &StepFile) -> Estimate {
+    let solids = solid_transforms(s).into_iter()
+        .map(|(id, mats)| estimate_solid(s, id, mats.len()))
+        .collect();
+    Estimate { solids }
+}
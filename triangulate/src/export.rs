@@ -0,0 +1,75 @@
+//! Conventions which downstream tools disagree on, applied the same way by
+//! every exporter (STL, glTF, and OBJ)
+
+/// Order of each face's vertices, seen from outside the model.  Meshes are
+/// always stored counter-clockwise.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+}
+
+/// Index of the first vertex in OBJ elements.  The OBJ format itself is
+/// 1-based, but some loaders expect 0-based indices.  glTF is always
+/// 0-based, and STL doesn't use indices.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IndexBase {
+    Zero,
+    One,
+}
+
+/// Options for the `_with` variants of the exporters, e.g.
+/// [`Mesh::save_stl_with`](crate::mesh::Mesh::save_stl_with).  The default
+/// is counter-clockwise and 1-based, which is what the exporters without
+/// options write.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ExportOptions {
+    pub winding: Winding,
+    pub index_base: IndexBase,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self { winding: Winding::CounterClockwise, index_base: IndexBase::One }
+    }
+}
+
+impl ExportOptions {
+    /// Puts a face's vertices into the order in which they're written.
+    /// Clockwise faces keep their first vertex and reverse the rest, so a
+    /// triangle `[a, b, c]` becomes `[a, c, b]`.
+    pub fn wind<const N: usize>(&self, mut verts: [u32; N]) -> [u32; N] {
+        if self.winding == Winding::Clockwise && N > 1 {
+            verts[1..].reverse();
+        }
+        verts
+    }
+
+    /// Converts a 0-based vertex index into the written index
+    pub fn index(&self, i: u32) -> u32 {
+        match self.index_base {
+            IndexBase::Zero => i,
+            IndexBase::One => i + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        let ccw = ExportOptions::default();
+        assert_eq!(ccw.wind([1, 2, 3]), [1, 2, 3]);
+        assert_eq!(ccw.index(0), 1);
+
+        let cw = ExportOptions {
+            winding: Winding::Clockwise,
+            index_base: IndexBase::Zero,
+        };
+        assert_eq!(cw.wind([1, 2, 3]), [1, 3, 2]);
+        assert_eq!(cw.wind([1, 2, 3, 4]), [1, 4, 3, 2]);
+        assert_eq!(cw.index(0), 0);
+    }
+}
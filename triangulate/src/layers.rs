@@ -0,0 +1,238 @@
+//! Layers and groups, which collect items under a name.
+//!
+//! A `PRESENTATION_LAYER_ASSIGNMENT` puts items on a named layer, which may
+//! be hidden by an `INVISIBILITY`.  A `GROUP` gets its items from any number
+//! of `APPLIED_GROUP_ASSIGNMENT`s.  Either one may list the solid itself, or
+//! something which stands for it (a styled item or a shape representation),
+//! so [`membership`] resolves those down to the items which they contain.
+use std::collections::{BTreeMap, BTreeSet};
+
+use step::{ap214::*, id::Id, step_file::StepFile};
+use crate::{mesh::Mesh, units::representation};
+
+/// Items which are nested more deeply than this are ignored, which also
+/// breaks cycles
+const MAX_DEPTH: usize = 8;
+
+/// A presentation layer, from a `PRESENTATION_LAYER_ASSIGNMENT`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    /// Entity ID of the assignment
+    pub id: usize,
+    pub name: String,
+    pub description: String,
+    /// Entity IDs of the assigned items, as listed in the file
+    pub items: Vec<usize>,
+    /// False if the layer is listed in an `INVISIBILITY`
+    pub visible: bool,
+}
+
+/// A group, with the items from every assignment to it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    /// Entity ID of the `GROUP`
+    pub id: usize,
+    pub name: String,
+    pub description: Option<String>,
+    /// Entity IDs of the assigned items, in the order in which they're first
+    /// assigned
+    pub items: Vec<usize>,
+}
+
+/// Finds every layer, in file order
+pub fn layers(s: &StepFile) -> Vec<Layer> {
+    let invisible: BTreeSet<usize> = s.iter::<Invisibility>()
+        .flat_map(|(_, i)| i.invisible_items.iter().map(|j| j.0))
+        .collect();
+    s.iter::<PresentationLayerAssignment>()
+        .map(|(id, a)| Layer {
+            id: id.0,
+            name: a.name.0.to_owned(),
+            description: a.description.0.to_owned(),
+            items: a.assigned_items.iter().map(|i| i.0).collect(),
+            visible: !invisible.contains(&id.0),
+        })
+        .collect()
+}
+
+/// Finds every group which has items assigned to it, in the order of the
+/// groups in the file
+pub fn groups(s: &StepFile) -> Vec<Group> {
+    let mut items: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (_, a) in s.iter::<AppliedGroupAssignment>() {
+        let v = items.entry(a.assigned_group.0).or_default();
+        for i in &a.items {
+            if !v.contains(&i.0) {
+                v.push(i.0);
+            }
+        }
+    }
+    items.into_iter()
+        .filter_map(|(id, items)| {
+            let g = s.entity(Id::<Group_>::new(id))?;
+            Some(Group {
+                id,
+                name: g.name.0.to_owned(),
+                description: g.description.get().map(|d| d.0.to_owned()),
+                items,
+            })
+        })
+        .collect()
+}
+
+/// Names of the layers and groups which each item belongs to
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Membership {
+    /// Layer names by item entity ID
+    pub layers: BTreeMap<usize, Vec<String>>,
+    /// Group names by item entity ID
+    pub groups: BTreeMap<usize, Vec<String>>,
+}
+
+/// Works out which layers and groups each item belongs to.  An item which
+/// is listed directly is a member, as is every item inside a listed styled
+/// item or shape representation.
+pub fn membership(s: &StepFile) -> Membership {
+    let mut out = Membership::default();
+    for layer in layers(s) {
+        for i in &layer.items {
+            add(s, *i, &layer.name, &mut out.layers, 0);
+        }
+    }
+    for group in groups(s) {
+        for i in &group.items {
+            add(s, *i, &group.name, &mut out.groups, 0);
+        }
+    }
+    out
+}
+
+fn add(s: &StepFile, item: usize, name: &str,
+       out: &mut BTreeMap<usize, Vec<String>>, depth: usize)
+{
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let names = out.entry(item).or_default();
+    if names.iter().any(|n| n == name) {
+        return;
+    }
+    names.push(name.to_owned());
+
+    let e = match s.entities().get(item) {
+        Some(e) => e,
+        None => return,
+    };
+    if let Some(i) = e.downcast::<StyledItem_>() {
+        add(s, i.item.0, name, out, depth + 1);
+    } else if let Some(i) = e.downcast::<OverRidingStyledItem_>() {
+        add(s, i.item.0, name, out, depth + 1);
+    } else if let Some((items, _)) = representation(e) {
+        for i in items {
+            add(s, i.0, name, out, depth + 1);
+        }
+    }
+}
+
+impl Membership {
+    /// Returns the entity IDs of every item on the named layer
+    pub fn on_layer(&self, name: &str) -> Vec<usize> {
+        Self::members(&self.layers, name)
+    }
+
+    /// Returns the entity IDs of every item in the named group
+    pub fn in_group(&self, name: &str) -> Vec<usize> {
+        Self::members(&self.groups, name)
+    }
+
+    fn members(map: &BTreeMap<usize, Vec<String>>, name: &str) -> Vec<usize> {
+        map.iter()
+            .filter(|(_, names)| names.iter().any(|n| n == name))
+            .map(|(i, _)| *i)
+            .collect()
+    }
+
+    /// Tags each part of a mesh with its solid's layers and groups, as
+    /// `layer` and `group` metadata (with names separated by commas), so
+    /// that exporters write them alongside the part.  Returns the number of
+    /// parts which were tagged.
+    pub fn tag(&self, mesh: &mut Mesh) -> usize {
+        let mut tagged = BTreeSet::new();
+        for (key, map) in [("layer", &self.layers), ("group", &self.groups)] {
+            for (id, names) in map {
+                let n = mesh.set_metadata(*id, key, &names.join(","));
+                if n > 0 {
+                    tagged.insert(*id);
+                }
+            }
+        }
+        mesh.parts.iter().filter(|p| tagged.contains(&p.id)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A solid (#10) which is styled (#20) and placed in a representation
+    /// (#30); the styled item is on a hidden layer, and the representation
+    /// is in a group which is assigned twice
+    const DATA: &[u8] = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=CLOSED_SHELL('',());
+#10=MANIFOLD_SOLID_BREP('body',#1);
+#11=MANIFOLD_SOLID_BREP('other',#1);
+#20=STYLED_ITEM('',(),#10);
+#30=SHAPE_REPRESENTATION('',(#10,#11),#99);
+#40=PRESENTATION_LAYER_ASSIGNMENT('hidden','not shown',(#20));
+#41=PRESENTATION_LAYER_ASSIGNMENT('other','',(#11));
+#42=INVISIBILITY((#40));
+#50=GROUP('fasteners',$);
+#51=APPLIED_GROUP_ASSIGNMENT(#50,(#30));
+#52=APPLIED_GROUP_ASSIGNMENT(#50,(#30,#11));
+ENDSEC;
+END-ISO-10303-21;";
+
+    #[test]
+    fn test_membership() {
+        let flat = StepFile::strip_flatten(DATA);
+        let s = StepFile::parse(&flat);
+
+        let layers = layers(&s);
+        assert_eq!(layers.len(), 2);
+        assert_eq!((layers[0].id, layers[0].name.as_str()), (40, "hidden"));
+        assert_eq!(layers[0].description, "not shown");
+        assert_eq!(layers[0].items, [20]);
+        assert!(!layers[0].visible);
+        assert!(layers[1].visible);
+
+        let groups = groups(&s);
+        assert_eq!(groups, [Group {
+            id: 50, name: "fasteners".to_owned(), description: None,
+            items: vec![30, 11],
+        }]);
+
+        let m = membership(&s);
+        assert_eq!(m.on_layer("hidden"), [10, 20]);
+        assert_eq!(m.on_layer("other"), [11]);
+        assert_eq!(m.in_group("fasteners"), [10, 11, 30]);
+        assert_eq!(m.groups[&11], ["fasteners"]);
+        assert!(m.on_layer("missing").is_empty());
+
+        let part = |id| crate::mesh::Part {
+            id, triangles: 0..0, opacity: 1.0, bounds: None,
+            metadata: BTreeMap::new(),
+        };
+        let mut mesh = Mesh {
+            parts: vec![part(10), part(11), part(12)],
+            ..Mesh::default()
+        };
+        assert_eq!(m.tag(&mut mesh), 2);
+        assert_eq!(mesh.parts[0].metadata["layer"], "hidden");
+        assert_eq!(mesh.parts[0].metadata["group"], "fasteners");
+        assert_eq!(mesh.parts[1].metadata["layer"], "other");
+        assert!(mesh.parts[2].metadata.is_empty());
+    }
+}
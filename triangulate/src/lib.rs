@@ -1,8 +1,99 @@
+//! Triangulates the solids and surface models in a STEP file.
+//!
+//! # Examples
+//! This parses a file with a single square face, triangulates it, and
+//! exports the result as binary glTF:
+//! ```
+//! use step::step_file::StepFile;
+//! use triangulate::triangulate::triangulate;
+//!
+//! let data = b"\
+//! ISO-10303-21;
+//! HEADER;
+//! FILE_DESCRIPTION((''),'2;1');
+//! FILE_NAME('square','',(''),(''),'','','');
+//! FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+//! ENDSEC;
+//! DATA;
+//! #1=CARTESIAN_POINT('',(0.,0.,0.));
+//! #2=CARTESIAN_POINT('',(1.,0.,0.));
+//! #3=CARTESIAN_POINT('',(1.,1.,0.));
+//! #4=CARTESIAN_POINT('',(0.,1.,0.));
+//! #5=VERTEX_POINT('',#1);
+//! #6=VERTEX_POINT('',#2);
+//! #7=VERTEX_POINT('',#3);
+//! #8=VERTEX_POINT('',#4);
+//! #9=DIRECTION('',(1.,0.,0.));
+//! #10=DIRECTION('',(0.,1.,0.));
+//! #11=DIRECTION('',(0.,0.,1.));
+//! #12=VECTOR('',#9,1.);
+//! #13=VECTOR('',#10,1.);
+//! #14=LINE('',#1,#12);
+//! #15=LINE('',#2,#13);
+//! #16=LINE('',#4,#12);
+//! #17=LINE('',#1,#13);
+//! #18=EDGE_CURVE('',#5,#6,#14,.T.);
+//! #19=EDGE_CURVE('',#6,#7,#15,.T.);
+//! #20=EDGE_CURVE('',#8,#7,#16,.T.);
+//! #21=EDGE_CURVE('',#5,#8,#17,.T.);
+//! #22=ORIENTED_EDGE('',*,*,#18,.T.);
+//! #23=ORIENTED_EDGE('',*,*,#19,.T.);
+//! #24=ORIENTED_EDGE('',*,*,#20,.F.);
+//! #25=ORIENTED_EDGE('',*,*,#21,.F.);
+//! #26=EDGE_LOOP('',(#22,#23,#24,#25));
+//! #27=FACE_OUTER_BOUND('',#26,.T.);
+//! #28=AXIS2_PLACEMENT_3D('',#1,#11,#9);
+//! #29=PLANE('',#28);
+//! #30=ADVANCED_FACE('',(#27),#29,.T.);
+//! #31=OPEN_SHELL('',(#30));
+//! #32=SHELL_BASED_SURFACE_MODEL('',(#31));
+//! ENDSEC;
+//! END-ISO-10303-21;";
+//! let flat = StepFile::strip_flatten(data);
+//! let entities = StepFile::parse(&flat);
+//! let (mesh, stats) = triangulate(&entities);
+//! assert_eq!((stats.num_faces, stats.num_errors), (1, 0));
+//! assert_eq!(mesh.triangles.len(), 2);
+//!
+//! let glb = mesh.to_glb();
+//! assert_eq!(&glb[..4], b"glTF");
+//! ```
+
+/// Profiling scope until the end of the block (a no-op unless the
+/// `profiling` feature is on; see the macro of the same name in `step`)
+macro_rules! profile_scope {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "profiling")]
+        profiling::scope!($($arg)*);
+    };
+}
+
+pub mod analyze;
+pub mod assembly;
+pub mod audit;
+pub mod cache;
+pub mod compressed;
+pub mod convert;
+pub mod diagnostic;
+pub mod estimate;
+pub mod export;
+pub mod layers;
 pub mod mesh;
+pub mod metrics;
+pub mod params;
+pub mod placement;
+pub mod product;
 pub mod stats;
+pub mod styles;
 pub mod surface;
+pub mod tolerance;
 pub mod triangulate;
+pub mod units;
+pub mod validate;
 pub mod curve;
+pub mod quads;
+pub mod split;
+pub mod wireframe;
 
 #[derive(thiserror::Error, Debug, Eq, PartialEq)]
 pub enum Error {
@@ -1,6 +1,10 @@
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
+use std::ops::Range;
 use nalgebra_glm::{DVec3, U32Vec3};
 
+use crate::{export::ExportOptions, stats::json_string};
+
 #[derive(Copy, Clone, Debug)]
 pub struct Vertex {
     pub pos: DVec3,
@@ -10,12 +14,72 @@ pub struct Vertex {
 #[derive(Copy, Clone, Debug)]
 pub struct Triangle {
     pub verts: U32Vec3,
+    /// ID of the face which produced this triangle, or 0 if unknown
+    pub face: usize,
+}
+
+/// One placed instance of a solid, which can be drawn on its own terms
+/// (e.g. sorted by depth if it's transparent)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Part {
+    /// Entity ID of the solid
+    pub id: usize,
+    /// Range of the part's triangles within [`Mesh::triangles`]
+    pub triangles: Range<usize>,
+    /// Opacity from the model's surface style, from 0 (invisible) to 1
+    pub opacity: f64,
+    /// Bounding box of the part's vertices (in the mesh's frame), or `None`
+    /// if it has no triangles.  This lets viewers cull parts without
+    /// walking their triangles.
+    pub bounds: Option<(DVec3, DVec3)>,
+    /// Key/value pairs attached by the caller (e.g. part numbers looked up
+    /// in another system), which exporters write alongside the part
+    pub metadata: BTreeMap<String, String>,
 }
 
 #[derive(Default)]
 pub struct Mesh {
     pub verts: Vec<Vertex>,
     pub triangles: Vec<Triangle>,
+    /// Placed solids, in the order in which their triangles appear.
+    /// Triangles which aren't in any part should be drawn as opaque.
+    pub parts: Vec<Part>,
+
+    /// Offset which has been subtracted from every vertex position (by
+    /// [`Mesh::recenter`]).  Add it back to recover model coordinates.
+    pub offset: DVec3,
+}
+
+/// Models whose `f32` coordinate resolution is coarser than this fraction of
+/// their size will visibly lose detail when rendered or saved as an STL
+const PRECISION_RISK_RATIO: f64 = 1e-5;
+
+/// Describes how well a mesh's coordinates survive conversion to `f32`
+#[derive(Copy, Clone, Debug)]
+pub struct PrecisionReport {
+    /// Center of the axis-aligned bounding box
+    pub center: DVec3,
+    /// Length of the longest side of the bounding box
+    pub extent: f64,
+    /// Largest absolute value of any coordinate
+    pub max_coord: f64,
+    /// Spacing between adjacent `f32` values near `max_coord`
+    pub f32_resolution: f64,
+}
+
+impl PrecisionReport {
+    /// Checks whether the model is far enough from the origin (relative to
+    /// its size) that converting to `f32` will lose significant precision.
+    /// This is typical of models with geodetic or site offsets baked in.
+    ///
+    /// A model with no size (e.g. a single point) has no detail to lose, so
+    /// it's never at risk, wherever it is.
+    pub fn at_risk(&self) -> bool {
+        if self.extent <= 0.0 {
+            return false;
+        }
+        self.f32_resolution > self.extent * PRECISION_RISK_RATIO
+    }
 }
 
 impl Mesh {
@@ -23,14 +87,199 @@ impl Mesh {
     // (why yes, this _is_ a monoid)
     pub fn combine(mut a: Self, b: Self) -> Self {
         let dv = a.verts.len().try_into().expect("too many triangles");
-        a.verts.extend(b.verts);
+        let dt = a.triangles.len();
+        // Move `b` into the same frame as `a`, in case either was recentered
+        let shift = b.offset - a.offset;
+        a.parts.extend(b.parts.into_iter().map(|p| Part {
+            triangles: p.triangles.start + dt..p.triangles.end + dt,
+            bounds: p.bounds.map(|(lo, hi)| (lo + shift, hi + shift)),
+            ..p
+        }));
+        a.verts.extend(b.verts.into_iter()
+            .map(|v| Vertex { pos: v.pos + shift, ..v }));
         a.triangles.extend(b.triangles.into_iter()
-            .map(|t| Triangle { verts: t.verts.add_scalar(dv) }));
+            .map(|t| Triangle { verts: t.verts.add_scalar(dv), ..t }));
         a
     }
 
-    /// Writes the triangulation to a STL, for debugging
-    pub fn save_stl(&self, filename: &str) -> std::io::Result<()> {
+    /// Measures the bounding box of the mesh and the resolution with which
+    /// its coordinates can be stored as `f32`.  Returns `None` if the mesh
+    /// has no vertices.
+    pub fn precision_report(&self) -> Option<PrecisionReport> {
+        if self.verts.is_empty() {
+            return None;
+        }
+        let mut lo = DVec3::repeat(f64::INFINITY);
+        let mut hi = DVec3::repeat(f64::NEG_INFINITY);
+        for v in &self.verts {
+            lo = lo.inf(&v.pos);
+            hi = hi.sup(&v.pos);
+        }
+        let extent = (hi - lo).max();
+        let max_coord = lo.abs().max().max(hi.abs().max());
+
+        // Distance from max_coord to the next representable f32
+        let f = max_coord as f32;
+        let f32_resolution = (f32::from_bits(f.to_bits() + 1) - f) as f64;
+
+        Some(PrecisionReport {
+            center: (lo + hi) / 2.0,
+            extent,
+            max_coord,
+            f32_resolution,
+        })
+    }
+
+    /// Translates the mesh so that its bounding box is centered on the
+    /// origin, accumulating the translation into [`Mesh::offset`].  Returns
+    /// the new offset.
+    pub fn recenter(&mut self) -> DVec3 {
+        if let Some(r) = self.precision_report() {
+            for v in &mut self.verts {
+                v.pos -= r.center;
+            }
+            for p in &mut self.parts {
+                if let Some((lo, hi)) = &mut p.bounds {
+                    *lo -= r.center;
+                    *hi -= r.center;
+                }
+            }
+            self.offset += r.center;
+        }
+        self.offset
+    }
+
+    /// Snaps vertices which are within `eps` of each other onto the same
+    /// position, closing cracks between faces, then removes triangles which
+    /// have collapsed (i.e. have two corners at the same position).
+    /// Vertices aren't merged, so each face keeps its own normals.  Returns
+    /// the number of triangles which were removed.
+    pub fn weld(&mut self, eps: f64) -> usize {
+        profile_scope!("weld");
+        if eps > 0.0 {
+            // Each vertex snaps to the first earlier vertex within `eps`;
+            // those are found by bucketing positions into cells of size
+            // `eps`, then checking the neighboring cells.
+            let cell = |p: &DVec3| [(p.x / eps).floor() as i64,
+                                    (p.y / eps).floor() as i64,
+                                    (p.z / eps).floor() as i64];
+            let mut grid: HashMap<[i64; 3], Vec<DVec3>> = HashMap::new();
+            for v in &mut self.verts {
+                let c = cell(&v.pos);
+                let mut found = None;
+                'search: for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        for dz in -1..=1 {
+                            let k = [c[0] + dx, c[1] + dy, c[2] + dz];
+                            if let Some(p) = grid.get(&k).and_then(|ps|
+                                ps.iter().find(|p| (*p - v.pos).norm() <= eps))
+                            {
+                                found = Some(*p);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+                match found {
+                    Some(p) => v.pos = p,
+                    None => grid.entry(c).or_default().push(v.pos),
+                }
+            }
+        }
+
+        // Drop collapsed triangles, shifting each part's range to match
+        let verts = &self.verts;
+        let collapsed = |t: &Triangle| {
+            let p = |i: usize| verts[t.verts[i] as usize].pos;
+            p(0) == p(1) || p(1) == p(2) || p(2) == p(0)
+        };
+        let keep: Vec<bool> = self.triangles.iter()
+            .map(|t| !collapsed(t))
+            .collect();
+        let mut before = vec![0; keep.len() + 1];
+        for (i, k) in keep.iter().enumerate() {
+            before[i + 1] = before[i] + *k as usize;
+        }
+        let removed = keep.len() - before[keep.len()];
+        if removed > 0 {
+            let mut i = 0;
+            self.triangles.retain(|_| { i += 1; keep[i - 1] });
+            for p in &mut self.parts {
+                p.triangles = before[p.triangles.start]..before[p.triangles.end];
+            }
+        }
+        if eps > 0.0 || removed > 0 {
+            self.update_bounds();
+        }
+        removed
+    }
+
+    /// Returns the bounding box of the vertices used by a range of
+    /// triangles, or `None` if the range is empty
+    pub fn bounds(&self, triangles: Range<usize>) -> Option<(DVec3, DVec3)> {
+        self.triangles[triangles].iter()
+            .flat_map(|t| t.verts.iter())
+            .map(|v| self.verts[*v as usize].pos)
+            .fold(None, |b, p| Some(match b {
+                Some((lo, hi)) => (p.inf(&lo), p.sup(&hi)),
+                None => (p, p),
+            }))
+    }
+
+    /// Attaches a key/value pair to every instance of the solid with the
+    /// given entity ID, replacing any earlier value for the key.  Returns the
+    /// number of parts which were updated.
+    pub fn set_metadata(&mut self, id: usize, key: &str, value: &str)
+        -> usize
+    {
+        let mut n = 0;
+        for p in self.parts.iter_mut().filter(|p| p.id == id) {
+            p.metadata.insert(key.to_owned(), value.to_owned());
+            n += 1;
+        }
+        n
+    }
+
+    /// Recomputes [`Part::bounds`] for every part, e.g. after editing
+    /// vertex positions
+    pub fn update_bounds(&mut self) {
+        for i in 0..self.parts.len() {
+            self.parts[i].bounds = self.bounds(self.parts[i].triangles.clone());
+        }
+    }
+
+    /// Splits the triangles into consecutive groups: one per part, plus one
+    /// for each run of triangles which aren't in any part
+    fn groups(&self) -> Vec<(Range<usize>, Option<&Part>)> {
+        let mut parts: Vec<&Part> = self.parts.iter().collect();
+        parts.sort_by_key(|p| p.triangles.start);
+
+        let mut out = Vec::new();
+        let mut next = 0;
+        for p in parts {
+            if p.triangles.start > next {
+                out.push((next..p.triangles.start, None));
+            }
+            next = next.max(p.triangles.end);
+            out.push((p.triangles.clone(), Some(p)));
+        }
+        if next < self.triangles.len() {
+            out.push((next..self.triangles.len(), None));
+        }
+        out
+    }
+
+    /// Writes the triangulation to a STL, for debugging.  Returns the number
+    /// of bytes written.
+    pub fn save_stl(&self, filename: &str) -> std::io::Result<usize> {
+        self.save_stl_with(filename, &ExportOptions::default())
+    }
+
+    /// Writes the triangulation to a STL, with the given winding order.
+    /// Returns the number of bytes written.
+    pub fn save_stl_with(&self, filename: &str, options: &ExportOptions)
+        -> std::io::Result<usize>
+    {
         let mut out: Vec<u8> = Vec::new();
         for _ in 0..80 { // header
             out.push('x' as u8);
@@ -40,7 +289,7 @@ impl Mesh {
         out.extend(&u.to_le_bytes());
         for t in self.triangles.iter() {
             out.extend(std::iter::repeat(0).take(12)); // normal
-            for v in t.verts.iter() {
+            for v in options.wind(t.verts.into()).iter() {
                 let v = self.verts[*v as usize];
                 out.extend(&(v.pos.x as f32).to_le_bytes());
                 out.extend(&(v.pos.y as f32).to_le_bytes());
@@ -48,6 +297,396 @@ impl Mesh {
             }
             out.extend(std::iter::repeat(0).take(2)); // attributes
         }
-        std::fs::write(filename, out)
+        std::fs::write(filename, &out)?;
+        Ok(out.len())
+    }
+
+    /// Encodes the triangulation as binary glTF (`.glb`), with per-vertex
+    /// normals and colors.
+    ///
+    /// Each part becomes its own node and mesh (named after the solid's
+    /// entity ID), as does each run of triangles outside of any part.  Their
+    /// `POSITION` accessors cover just the vertices which they use, so the
+    /// accessors' `min` and `max` give a bounding box per node.  A part's
+    /// [`Part::metadata`] is written to its node's `extras`.  If the mesh
+    /// has been [recentered](Mesh::recenter), the nodes are children of a
+    /// root node whose `translation` is the [`Mesh::offset`].
+    pub fn to_glb(&self) -> Vec<u8> {
+        self.to_glb_with(&ExportOptions::default())
+    }
+
+    /// Encodes the triangulation as binary glTF, with the given winding
+    /// order.  glTF treats counter-clockwise faces as front faces, so
+    /// clockwise output is only useful for viewers which cull the other way.
+    pub fn to_glb_with(&self, options: &ExportOptions) -> Vec<u8> {
+        profile_scope!("to_glb");
+        let mut bin: Vec<u8> = Vec::new();
+        for v in &self.verts {
+            for c in v.pos.iter() {
+                bin.extend(&(*c as f32).to_le_bytes());
+            }
+        }
+        for v in &self.verts {
+            // glTF requires unit-length normals
+            let n = if v.norm.norm() > 0.0 {
+                v.norm.normalize()
+            } else {
+                DVec3::new(0.0, 0.0, 1.0)
+            };
+            for c in n.iter() {
+                bin.extend(&(*c as f32).to_le_bytes());
+            }
+        }
+        for v in &self.verts {
+            for c in v.color.iter() {
+                bin.extend(&(c.clamp(0.0, 1.0) as f32).to_le_bytes());
+            }
+        }
+
+        let json = if self.triangles.is_empty() {
+            concat!(r#"{"asset":{"version":"2.0","generator":"foxtrot"},"#,
+                    r#""scene":0,"scenes":[{"nodes":[]}]}"#).to_owned()
+        } else {
+            let attr = self.verts.len() * 12;
+            let view = |i: usize, len: usize, target: u32| format!(
+                r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+                i * attr, len, target);
+            let vec3 = |i: usize, offset: u32, n: u32, extra: &str| format!(
+                concat!(r#"{{"bufferView":{},"byteOffset":{},"#,
+                        r#""componentType":5126,"count":{},"type":"VEC3"{}}}"#),
+                i, offset * 12, n, extra);
+
+            let mut nodes = Vec::new();
+            let mut meshes = Vec::new();
+            let mut accessors = Vec::new();
+            let mut index_bytes = 0;
+            for (i, (tris, part)) in self.groups().into_iter().enumerate() {
+                let tris = &self.triangles[tris];
+                let first = tris.iter().flat_map(|t| t.verts.iter()).min()
+                    .copied().unwrap_or(0);
+                let last = tris.iter().flat_map(|t| t.verts.iter()).max()
+                    .copied().unwrap_or(0);
+
+                // Accessor bounds must match the stored (f32) values
+                let mut lo = [f32::INFINITY; 3];
+                let mut hi = [f32::NEG_INFINITY; 3];
+                for v in &self.verts[first as usize..=last as usize] {
+                    for (j, c) in v.pos.iter().enumerate() {
+                        lo[j] = lo[j].min(*c as f32);
+                        hi[j] = hi[j].max(*c as f32);
+                    }
+                }
+                let n = last - first + 1;
+                accessors.push(vec3(0, first, n, &format!(
+                    r#","min":[{},{},{}],"max":[{},{},{}]"#,
+                    lo[0], lo[1], lo[2], hi[0], hi[1], hi[2])));
+                accessors.push(vec3(1, first, n, ""));
+                accessors.push(vec3(2, first, n, ""));
+                accessors.push(format!(
+                    concat!(r#"{{"bufferView":3,"byteOffset":{},"#,
+                            r#""componentType":5125,"count":{},"type":"SCALAR"}}"#),
+                    index_bytes, tris.len() * 3));
+                index_bytes += tris.len() * 12;
+
+                meshes.push(format!(concat!(
+                    r#"{{"primitives":[{{"attributes":"#,
+                    r#"{{"POSITION":{},"NORMAL":{},"COLOR_0":{}}},"indices":{}}}]}}"#),
+                    4 * i, 4 * i + 1, 4 * i + 2, 4 * i + 3));
+                nodes.push(match part {
+                    Some(p) if !p.metadata.is_empty() => {
+                        let extras: Vec<String> = p.metadata.iter()
+                            .map(|(k, v)| format!("{}:{}", json_string(k),
+                                                  json_string(v)))
+                            .collect();
+                        format!(r##"{{"mesh":{},"name":"#{}","extras":{{{}}}}}"##,
+                                i, p.id, extras.join(","))
+                    },
+                    Some(p) => format!(r##"{{"mesh":{},"name":"#{}"}}"##, i, p.id),
+                    None => format!(r#"{{"mesh":{}}}"#, i),
+                });
+                for t in tris {
+                    for v in options.wind(t.verts.into()).iter() {
+                        bin.extend(&(v - first).to_le_bytes());
+                    }
+                }
+            }
+            let mut scene: Vec<String> = (0..nodes.len())
+                .map(|i| i.to_string())
+                .collect();
+            // A recentered mesh keeps its real-world placement, as a root
+            // node which moves every part back by the offset
+            if self.offset != DVec3::zeros() {
+                nodes.push(format!(concat!(
+                    r#"{{"name":"offset","translation":[{},{},{}],"#,
+                    r#""children":[{}]}}"#),
+                    self.offset.x, self.offset.y, self.offset.z,
+                    scene.join(",")));
+                scene = vec![(nodes.len() - 1).to_string()];
+            }
+
+            format!(concat!(
+                r#"{{"asset":{{"version":"2.0","generator":"foxtrot"}},"#,
+                r#""scene":0,"scenes":[{{"nodes":[{}]}}],"nodes":[{}],"#,
+                r#""meshes":[{}],"#,
+                r#""buffers":[{{"byteLength":{}}}],"#,
+                r#""bufferViews":[{},{},{},{}],"#,
+                r#""accessors":[{}]}}"#),
+                scene.join(","), nodes.join(","), meshes.join(","),
+                bin.len(),
+                view(0, attr, 34962), view(1, attr, 34962),
+                view(2, attr, 34962),
+                view(3, index_bytes, 34963),
+                accessors.join(","))
+        };
+        glb(json, &bin)
+    }
+
+    /// Writes the triangulation as binary glTF.  Returns the number of bytes
+    /// written.
+    pub fn save_glb(&self, filename: &str) -> std::io::Result<usize> {
+        self.save_glb_with(filename, &ExportOptions::default())
+    }
+
+    /// Writes the triangulation as binary glTF, with the given winding
+    /// order.  Returns the number of bytes written.
+    pub fn save_glb_with(&self, filename: &str, options: &ExportOptions)
+        -> std::io::Result<usize>
+    {
+        let out = self.to_glb_with(options);
+        std::fs::write(filename, &out)?;
+        Ok(out.len())
+    }
+}
+
+/// Packs a glTF JSON document and its binary buffer into a `.glb` file
+pub(crate) fn glb(json: String, bin: &[u8]) -> Vec<u8> {
+    let mut json = json.into_bytes();
+    while !json.len().is_multiple_of(4) {
+        json.push(b' ');
+    }
+
+    // Header, then the JSON and binary chunks
+    let total = 12 + 8 + json.len() +
+        if bin.is_empty() { 0 } else { 8 + bin.len() };
+    let mut out = Vec::with_capacity(total);
+    out.extend(b"glTF");
+    out.extend(&2u32.to_le_bytes());
+    out.extend(&(total as u32).to_le_bytes());
+    out.extend(&(json.len() as u32).to_le_bytes());
+    out.extend(b"JSON");
+    out.extend(&json);
+    if !bin.is_empty() {
+        out.extend(&(bin.len() as u32).to_le_bytes());
+        out.extend(b"BIN\0");
+        out.extend(bin);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weld() {
+        let v = |x, y| Vertex {
+            pos: DVec3::new(x, y, 0.0),
+            norm: DVec3::new(0.0, 0.0, 1.0),
+            color: DVec3::zeros(),
+        };
+        let t = |a, b, c| Triangle { verts: U32Vec3::new(a, b, c), face: 0 };
+        // Two squares with a tiny crack between them, plus a sliver across
+        // the crack
+        let mut mesh = Mesh {
+            verts: vec![v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1.0), v(0.0, 1.0),
+                        v(1.0 + 1e-9, 0.0), v(2.0, 0.0), v(2.0, 1.0),
+                        v(1.0 + 1e-9, 1.0)],
+            triangles: vec![t(0, 1, 2), t(0, 2, 3), t(1, 4, 2),
+                            t(4, 5, 6), t(4, 6, 7)],
+            parts: vec![Part { id: 1, triangles: 0..3, opacity: 1.0,
+                               bounds: None, metadata: BTreeMap::new() },
+                        Part { id: 2, triangles: 3..5, opacity: 1.0,
+                               bounds: None, metadata: BTreeMap::new() }],
+            ..Mesh::default()
+        };
+        assert_eq!(mesh.weld(0.0), 0);
+        assert_eq!(mesh.weld(1e-7), 1);
+        assert_eq!(mesh.verts[4].pos, mesh.verts[1].pos);
+        assert_eq!(mesh.verts[7].pos, mesh.verts[2].pos);
+        assert_eq!(mesh.triangles.len(), 4);
+        assert_eq!(mesh.parts[0].triangles, 0..2);
+        assert_eq!(mesh.parts[1].triangles, 2..4);
+    }
+
+    #[test]
+    fn precision() {
+        let mesh = |pts: &[(f64, f64)]| Mesh {
+            verts: pts.iter().map(|&(x, y)| Vertex {
+                pos: DVec3::new(x, y, 0.0),
+                norm: DVec3::new(0.0, 0.0, 1.0),
+                color: DVec3::zeros(),
+            }).collect(),
+            ..Mesh::default()
+        };
+        assert!(mesh(&[]).precision_report().is_none());
+
+        let r = mesh(&[(0.0, 0.0), (1.0, 1.0)]).precision_report().unwrap();
+        assert_eq!(r.extent, 1.0);
+        assert!(!r.at_risk());
+        let r = mesh(&[(1e7, 0.0), (1e7 + 1.0, 1.0)]).precision_report()
+            .unwrap();
+        assert_eq!(r.center, DVec3::new(1e7 + 0.5, 0.5, 0.0));
+        assert!(r.at_risk());
+
+        // Degenerate bounds, where every vertex is at the same point
+        for p in &[(0.0, 0.0), (1e7, 1e7)] {
+            let r = mesh(&[*p, *p]).precision_report().unwrap();
+            assert_eq!(r.extent, 0.0);
+            assert!(!r.at_risk());
+        }
+    }
+
+    #[test]
+    fn glb_layout() {
+        let v = |x, y| Vertex {
+            pos: DVec3::new(x, y, 0.0),
+            norm: DVec3::new(0.0, 0.0, 2.0),
+            color: DVec3::new(1.0, 0.5, 0.0),
+        };
+        let mesh = Mesh {
+            verts: vec![v(0.0, 0.0), v(1.0, 0.0), v(0.0, 1.0)],
+            triangles: vec![Triangle { verts: U32Vec3::new(0, 1, 2), face: 0 }],
+            ..Mesh::default()
+        };
+        let glb = mesh.to_glb();
+        let word = |i: usize|
+            u32::from_le_bytes(glb[i..i + 4].try_into().unwrap());
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(word(8) as usize, glb.len());
+
+        let json_len = word(12) as usize;
+        assert_eq!(json_len % 4, 0);
+        let json = std::str::from_utf8(&glb[20..20 + json_len]).unwrap();
+        assert!(json.contains(r#""min":[0,0,0],"max":[1,1,0]"#));
+
+        // 3 vertices with 3 attributes of 12 bytes, then 3 indices
+        let bin = 20 + json_len;
+        assert_eq!(word(bin) as usize, 3 * 3 * 12 + 12);
+        assert_eq!(&glb[bin + 4..bin + 8], b"BIN\0");
+        assert_eq!(glb.len(), bin + 8 + 3 * 3 * 12 + 12);
+
+        // Normals are rescaled to unit length
+        let nz = f32::from_le_bytes(glb[bin + 8 + 36 + 8..bin + 8 + 36 + 12]
+            .try_into().unwrap());
+        assert_eq!(nz, 1.0);
+
+        // Clockwise winding swaps the last two indices
+        let cw = mesh.to_glb_with(&ExportOptions {
+            winding: crate::export::Winding::Clockwise,
+            ..ExportOptions::default()
+        });
+        assert_eq!(cw.len(), glb.len());
+        assert_eq!(&cw[cw.len() - 12..], [0, 2, 1].iter()
+            .flat_map(|i: &u32| i.to_le_bytes().to_vec())
+            .collect::<Vec<u8>>());
+
+        // Recentering moves the vertices, and the offset is written to a
+        // root node so that the model stays in place
+        let mut moved = Mesh {
+            verts: mesh.verts.iter()
+                .map(|v| Vertex { pos: v.pos + DVec3::new(1e6, 0.0, 2.5), ..*v })
+                .collect(),
+            triangles: mesh.triangles.clone(),
+            ..Mesh::default()
+        };
+        moved.recenter();
+        let glb = moved.to_glb();
+        let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap());
+        let json = std::str::from_utf8(&glb[20..20 + json_len as usize])
+            .unwrap();
+        assert!(json.contains(concat!(
+            r#""scenes":[{"nodes":[1]}],"nodes":[{"mesh":0},"#,
+            r#"{"name":"offset","translation":[1000000.5,0.5,2.5],"#,
+            r#""children":[0]}]"#)), "{}", json);
+        assert!(json.contains(r#""min":[-0.5,-0.5,0],"max":[0.5,0.5,0]"#));
+
+        let empty = Mesh::default().to_glb();
+        assert_eq!(u32::from_le_bytes(empty[8..12].try_into().unwrap()) as usize,
+                   empty.len());
+    }
+
+    #[test]
+    fn part_bounds() {
+        let v = |x, y| Vertex {
+            pos: DVec3::new(x, y, 0.0),
+            norm: DVec3::new(0.0, 0.0, 1.0),
+            color: DVec3::zeros(),
+        };
+        let t = |a, b, c| Triangle { verts: U32Vec3::new(a, b, c), face: 0 };
+        let mut mesh = Mesh {
+            verts: vec![v(0.0, 0.0), v(1.0, 0.0), v(0.0, 1.0),
+                        v(4.0, 4.0), v(5.0, 4.0), v(4.0, 6.0)],
+            triangles: vec![t(0, 1, 2), t(3, 4, 5)],
+            parts: vec![Part { id: 7, triangles: 1..2, opacity: 1.0,
+                               bounds: None, metadata: BTreeMap::new() }],
+            ..Mesh::default()
+        };
+        mesh.update_bounds();
+        assert_eq!(mesh.parts[0].bounds,
+                   Some((DVec3::new(4.0, 4.0, 0.0), DVec3::new(5.0, 6.0, 0.0))));
+        mesh.recenter();
+        assert_eq!(mesh.parts[0].bounds,
+                   Some((DVec3::new(1.5, 1.0, 0.0), DVec3::new(2.5, 3.0, 0.0))));
+
+        // One node per group, each with its own bounds and local indices
+        let glb = mesh.to_glb();
+        let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap());
+        let json = std::str::from_utf8(&glb[20..20 + json_len as usize])
+            .unwrap();
+        assert!(json.contains(concat!(
+            r##""nodes":[{"mesh":0},{"mesh":1,"name":"#7"},"##,
+            r##"{"name":"offset","translation":[2.5,3,0],"children":[0,1]}]"##)));
+        assert!(json.contains(r#""min":[-2.5,-3,0],"max":[-1.5,-2,0]"#));
+        assert!(json.contains(concat!(r#"{"bufferView":0,"byteOffset":36,"#,
+            r#""componentType":5126,"count":3,"type":"VEC3","#,
+            r#""min":[1.5,1,0],"max":[2.5,3,0]}"#)));
+        let indices = &glb[glb.len() - 24..];
+        assert_eq!(indices, [0, 1, 2, 0, 1, 2].iter()
+            .flat_map(|i: &u32| i.to_le_bytes().to_vec())
+            .collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn part_metadata() {
+        let v = |x| Vertex {
+            pos: DVec3::new(x, 0.0, 0.0),
+            norm: DVec3::new(0.0, 0.0, 1.0),
+            color: DVec3::zeros(),
+        };
+        let part = |id, triangles| Part {
+            id, triangles, opacity: 1.0, bounds: None,
+            metadata: BTreeMap::new(),
+        };
+        let mut mesh = Mesh {
+            verts: vec![v(0.0), v(1.0), v(2.0)],
+            triangles: vec![Triangle { verts: U32Vec3::new(0, 1, 2), face: 0 };
+                            3],
+            parts: vec![part(4, 0..1), part(5, 1..2), part(4, 2..3)],
+            ..Mesh::default()
+        };
+        assert_eq!(mesh.set_metadata(4, "erp", "PN-\"17\""), 2);
+        assert_eq!(mesh.set_metadata(9, "erp", "PN-18"), 0);
+        assert_eq!(mesh.parts[2].metadata["erp"], "PN-\"17\"");
+        assert!(mesh.parts[1].metadata.is_empty());
+
+        // Metadata survives combining meshes, and is exported as extras
+        let mesh = Mesh::combine(Mesh::default(), mesh);
+        let glb = mesh.to_glb();
+        let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap());
+        let json = std::str::from_utf8(&glb[20..20 + json_len as usize])
+            .unwrap();
+        assert!(json.contains(
+            r##"{"mesh":0,"name":"#4","extras":{"erp":"PN-\"17\""}}"##));
+        assert!(json.contains(r##"{"mesh":1,"name":"#5"}"##));
     }
 }
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+/// Number of entities in the parsed STEP file
+pub const ENTITIES: &str = "entities";
+/// Number of shells which were visited
+pub const SHELLS: &str = "shells";
+/// Number of faces which were visited (including failures)
+pub const FACES: &str = "faces";
+/// Number of faces which failed to triangulate with an error
+pub const ERRORS: &str = "errors";
+/// Number of faces where triangulation panicked
+pub const PANICS: &str = "panics";
+/// Number of boundary points projected onto a spline surface by re-seeding
+/// from their neighbours (see [`UvFallback`](crate::params::UvFallback))
+pub const UV_RESEEDED: &str = "uv_reseeded";
+/// Number of boundary points projected onto a spline surface by a dense
+/// grid search
+pub const UV_GRID_SEARCHED: &str = "uv_grid_searched";
+/// Number of points inserted by refinement (see
+/// [`Refinement`](crate::params::Refinement))
+pub const REFINED_POINTS: &str = "refined_points";
+/// Number of faces which ran out of refinement budget before reaching
+/// tolerance
+pub const REFINE_OVER_BUDGET: &str = "refine_over_budget";
+/// Number of triangles in the output mesh
+pub const TRIANGLES: &str = "triangles";
+/// Number of bytes written when saving a mesh
+pub const BYTES_WRITTEN: &str = "bytes_written";
+/// Time spent triangulating a whole file
+pub const TRIANGULATE_TIME: &str = "triangulate_time";
+
+/// A sink for counters and timers, so that embedding applications can feed
+/// conversion metrics into their own monitoring (Prometheus, statsd, etc)
+/// instead of scraping the log.
+///
+/// Metric names are the `&'static str` constants in this module.
+pub trait Metrics {
+    /// Adds `value` to the counter named `name`
+    fn counter(&mut self, name: &'static str, value: u64);
+
+    /// Records a single sample for the timer named `name`
+    fn timer(&mut self, name: &'static str, elapsed: Duration);
+}
+
+/// The unit type discards every metric
+impl Metrics for () {
+    fn counter(&mut self, _name: &'static str, _value: u64) {}
+    fn timer(&mut self, _name: &'static str, _elapsed: Duration) {}
+}
+
+/// Runs `f`, recording its wall-clock time under `name`
+pub fn timed<T, F: FnOnce() -> T>(metrics: &mut dyn Metrics,
+                                  name: &'static str, f: F) -> T {
+    let start = Instant::now();
+    let out = f();
+    metrics.timer(name, start.elapsed());
+    out
+}
@@ -0,0 +1,104 @@
+//! Options which control how faces are meshed
+use std::ops::AddAssign;
+
+/// How to recover when a boundary point can't be projected onto a B-spline
+/// or NURBS surface.  Each enabled stage is tried in turn, and a face fails
+/// with [`Error::CouldNotLower`](crate::Error::CouldNotLower) only if every
+/// stage fails for one of its points.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct UvFallback {
+    /// Retries from the UV coordinates of the neighbouring boundary points,
+    /// which are usually close by on the surface
+    pub reseed: bool,
+    /// Retries from the closest point on a grid of this many samples (in
+    /// each direction) per knot span, which is denser than the grid used
+    /// for the first attempt.  Zero skips this stage.
+    pub grid: usize,
+}
+
+impl UvFallback {
+    /// Fails the face as soon as any point can't be projected
+    pub const NONE: Self = Self { reseed: false, grid: 0 };
+}
+
+impl Default for UvFallback {
+    fn default() -> Self {
+        Self { reseed: true, grid: 32 }
+    }
+}
+
+/// Number of boundary points recovered by each stage of a [`UvFallback`]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct UvFallbackCounts {
+    pub reseeded: usize,
+    pub grid_searched: usize,
+}
+
+impl AddAssign for UvFallbackCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.reseeded += other.reseeded;
+        self.grid_searched += other.grid_searched;
+    }
+}
+
+/// Iterative refinement of curved faces.  After each triangulation, every
+/// triangle's centroid is compared with the surface point at its UV
+/// centroid, and triangles which are further than `tolerance` from the
+/// surface are split at the midpoint of their longest edge; this repeats
+/// until every triangle is within tolerance or the budget runs out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Refinement {
+    /// Maximum deviation from the surface, in the file's length units
+    pub tolerance: f64,
+    /// Maximum number of re-triangulations per face
+    pub max_passes: usize,
+    /// Maximum number of points inserted per face
+    pub max_points: usize,
+}
+
+impl Refinement {
+    /// Refines to the given tolerance, with a budget that's generous enough
+    /// for ordinary fillets and spheres
+    pub fn new(tolerance: f64) -> Self {
+        Self { tolerance, max_passes: 16, max_points: 4096 }
+    }
+}
+
+/// What [`Refinement`] did, summed over faces
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RefineCounts {
+    /// Number of points inserted
+    pub points: usize,
+    /// Number of faces which ran out of budget before reaching tolerance
+    pub over_budget: usize,
+    /// Largest deviation of any triangle in the final meshes
+    pub max_deviation: f64,
+}
+
+impl AddAssign for RefineCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.points += other.points;
+        self.over_budget += other.over_budget;
+        self.max_deviation = self.max_deviation.max(other.max_deviation);
+    }
+}
+
+/// Options for [`triangulate_with_params`](crate::triangulate::triangulate_with_params)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MeshParams {
+    /// Used instead of the model's own uncertainty (see
+    /// [`model_tolerance`](crate::tolerance::model_tolerance)) when dropping
+    /// short edges and welding vertices
+    pub tolerance: Option<f64>,
+    pub uv_fallback: UvFallback,
+    /// If set, curved faces are refined until they're within tolerance of
+    /// their surfaces.  Otherwise, spheres and tori are seeded with a fixed
+    /// grid of points and nothing else is refined.
+    pub refine: Option<Refinement>,
+    /// If set, placed geometry is scaled into this length unit, given as
+    /// its size in metres (e.g. [`MILLIMETRE`](crate::units::MILLIMETRE)),
+    /// using the length unit of each solid's representation.  Solids whose
+    /// unit isn't known are left as they are.  Tolerances are still in the
+    /// file's own units.
+    pub length_unit: Option<f64>,
+}
@@ -0,0 +1,138 @@
+//! Resolution of placements into matrices, with a cache which can be shared
+//! between threads
+use std::collections::HashMap;
+use std::sync::{RwLock, atomic::{AtomicUsize, Ordering}};
+
+use nalgebra_glm::DMat4;
+
+use step::{ap214::*, id::Id, step_file::StepFile};
+use crate::{surface::Surface, triangulate::axis2_placement_3d};
+
+/// Builds the matrix which maps an `AXIS2_PLACEMENT_3D`'s local
+/// coordinates into its parent's
+pub fn axis2_placement_matrix(s: &StepFile, t: Id<Axis2Placement3d_>) -> DMat4 {
+    let (location, axis, ref_direction) = axis2_placement_3d(s, t);
+    Surface::make_affine_transform(axis,
+        ref_direction,
+        axis.cross(&ref_direction),
+        location)
+}
+
+/// Builds the matrix of an `ITEM_DEFINED_TRANSFORMATION`, which maps its
+/// first placement onto its second
+pub fn item_defined_transformation(s: &StepFile, t: Id<ItemDefinedTransformation_>) -> DMat4 {
+    let i = s.entity(t).expect("Could not get ItemDefinedTransform");
+    let t1 = axis2_placement_matrix(s, i.transform_item_1.cast());
+    let t2 = axis2_placement_matrix(s, i.transform_item_2.cast());
+    t2 * t1.try_inverse().expect("Could not invert transform matrix")
+}
+
+/// Remembers the matrix of each placement and transformation, by entity ID.
+///
+/// A sub-assembly which is used many times is walked once per use, so
+/// without a cache, every placement inside it is resolved (and inverted)
+/// again for each instance.  The cache is `Sync`, so one cache can be shared
+/// by several threads; a placement which two threads miss at once is simply
+/// resolved twice.
+///
+/// Entity IDs are only meaningful within one file, so a cache mustn't be
+/// used with any other file (including a re-parsed copy of an edited file).
+#[derive(Debug, Default)]
+pub struct PlacementCache {
+    mats: RwLock<HashMap<usize, DMat4>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl PlacementCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached version of [`axis2_placement_matrix`]
+    pub fn axis2_placement(&self, s: &StepFile, t: Id<Axis2Placement3d_>) -> DMat4 {
+        self.get_or_insert(t.0, || axis2_placement_matrix(s, t))
+    }
+
+    /// Cached version of [`item_defined_transformation`], which also caches
+    /// the transformation's two placements
+    pub fn item_defined_transformation(&self, s: &StepFile,
+                                       t: Id<ItemDefinedTransformation_>)
+        -> DMat4
+    {
+        self.get_or_insert(t.0, || {
+            let i = s.entity(t).expect("Could not get ItemDefinedTransform");
+            let t1 = self.axis2_placement(s, i.transform_item_1.cast());
+            let t2 = self.axis2_placement(s, i.transform_item_2.cast());
+            t2 * t1.try_inverse().expect("Could not invert transform matrix")
+        })
+    }
+
+    fn get_or_insert<F: FnOnce() -> DMat4>(&self, id: usize, f: F) -> DMat4 {
+        if let Some(m) = self.mats.read().unwrap().get(&id) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return *m;
+        }
+        // The lock isn't held while resolving, since `f` may recurse
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let m = f();
+        self.mats.write().unwrap().insert(id, m);
+        m
+    }
+
+    /// Number of lookups which were answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups which had to resolve a placement
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Number of cached matrices
+    pub fn len(&self) -> usize {
+        self.mats.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assembly::Assembly, product::ProductTree};
+
+    #[test]
+    fn test_shared_cache() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/abstract_pca.step");
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        let s = StepFile::parse(&flat);
+
+        let cache = PlacementCache::new();
+        let trees: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| ProductTree::with_cache(&s, &cache)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let expected = ProductTree::new(&s);
+        assert!(trees.iter().all(|t| *t == expected));
+        assert!(!cache.is_empty());
+
+        // Every transformation has now been resolved, so walking the file
+        // again doesn't miss
+        let misses = cache.misses();
+        assert_eq!(Assembly::with_cache(&s, &cache), Assembly::new(&s));
+        assert_eq!(cache.misses(), misses);
+        assert!(cache.hits() > 0);
+
+        for (id, _) in s.iter::<ItemDefinedTransformation>() {
+            assert_eq!(cache.item_defined_transformation(&s, id),
+                       item_defined_transformation(&s, id));
+        }
+    }
+}
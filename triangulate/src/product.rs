@@ -0,0 +1,371 @@
+//! The product structure of a file, i.e. which parts and sub-assemblies
+//! make up each assembly, independent of how (or whether) it's triangulated.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use nalgebra_glm::DMat4;
+use log::warn;
+
+use step::{ap214::*, step_file::StepFile};
+use crate::placement::PlacementCache;
+
+/// One use of a product in the tree.  A product which is used several
+/// times (e.g. a bolt placed twice) appears once per use.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProductNode {
+    /// Entity ID of the `PRODUCT_DEFINITION`
+    pub definition: usize,
+    /// Entity ID of the `PRODUCT`
+    pub product: usize,
+    /// Product ID and name, as in `PRODUCT('id','name',...)`
+    pub id: String,
+    pub name: String,
+    /// Name of the `PRODUCT_DEFINITION_CONTEXT`, e.g. `part definition`
+    pub context: String,
+    /// The `NEXT_ASSEMBLY_USAGE_OCCURRENCE` which places this node in its
+    /// parent, or `None` for a root
+    pub occurrence: Option<Occurrence>,
+    /// Entity IDs of the product's shape representations, from its
+    /// `SHAPE_DEFINITION_REPRESENTATION`s
+    pub shapes: Vec<usize>,
+    /// Placement relative to the parent node (or the identity, for a root
+    /// or a use without a `CONTEXT_DEPENDENT_SHAPE_REPRESENTATION`)
+    pub transform: DMat4,
+    pub children: Vec<ProductNode>,
+}
+
+/// A `NEXT_ASSEMBLY_USAGE_OCCURRENCE`, which links a child to its parent
+#[derive(Clone, Debug, PartialEq)]
+pub struct Occurrence {
+    /// Entity ID
+    pub entity: usize,
+    pub id: String,
+    pub name: String,
+    pub reference_designator: Option<String>,
+}
+
+impl ProductNode {
+    fn find(&self, definition: usize) -> Option<&ProductNode> {
+        if self.definition == definition {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.find(definition))
+        }
+    }
+
+    fn find_product(&self, product: &str) -> Option<&ProductNode> {
+        if self.name == product || self.id == product {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.find_product(product))
+        }
+    }
+
+    fn placements(&self, parent: &DMat4, out: &mut Vec<(usize, DMat4)>) {
+        let mat = parent * self.transform;
+        out.push((self.definition, mat));
+        for c in &self.children {
+            c.placements(&mat, out);
+        }
+    }
+}
+
+/// The tree of products linked by `NEXT_ASSEMBLY_USAGE_OCCURRENCE`, with
+/// each use placed by its `CONTEXT_DEPENDENT_SHAPE_REPRESENTATION`.  The
+/// roots are product definitions which aren't used by any other, in
+/// ascending ID order, and children are in the order of their occurrences.
+///
+/// Unlike [`Assembly`](crate::assembly::Assembly), which only follows links
+/// between shape representations, this knows which product each part of the
+/// tree belongs to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProductTree {
+    pub roots: Vec<ProductNode>,
+}
+
+impl ProductTree {
+    /// Builds the product tree from a file's product and relationship
+    /// entities
+    pub fn new(s: &StepFile) -> Self {
+        Self::with_cache(s, &PlacementCache::new())
+    }
+
+    /// Builds the product tree, like [`ProductTree::new`], resolving
+    /// placements through a cache which may be shared with other walks of
+    /// the same file
+    pub fn with_cache(s: &StepFile, cache: &PlacementCache) -> Self {
+        let mut shapes: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (_, d) in s.iter::<ShapeDefinitionRepresentation>() {
+            if let Some(pd) = shape_definition(s, d.definition.0) {
+                shapes.entry(pd).or_default().push(d.used_representation.0);
+            }
+        }
+
+        // Placements, by the ID of the occurrence which they place
+        let mut placements = HashMap::new();
+        for (_, c) in s.iter::<ContextDependentShapeRepresentation>() {
+            let occurrence = match shape_definition(s, c.represented_product_relation.0) {
+                Some(o) => o,
+                None => continue,
+            };
+            let r = match s.entities().get(c.representation_relation.0)
+                .and_then(|e| e.downcast::<RepresentationRelationshipWithTransformation_>())
+            {
+                Some(r) => r,
+                None => continue,
+            };
+            placements.insert(occurrence,
+                (r.rep_1.0, r.rep_2.0, r.transformation_operator.cast()));
+        }
+
+        // Children of each product definition, in occurrence order
+        let mut children: BTreeMap<usize, Vec<_>> = BTreeMap::new();
+        let mut used = HashSet::new();
+        for (id, o) in s.iter::<NextAssemblyUsageOccurrence>() {
+            children.entry(o.relating_product_definition.0)
+                .or_default()
+                .push((id.0, o));
+            used.insert(o.related_product_definition.0);
+        }
+
+        let ctx = Context { s, cache, shapes, placements, children };
+        let mut path = Vec::new();
+        let roots = s.iter::<ProductDefinition>()
+            .map(|(id, _)| id.0)
+            .filter(|id| !used.contains(id))
+            .filter_map(|id| ctx.node(id, None, DMat4::identity(), &mut path))
+            .collect();
+        Self { roots }
+    }
+
+    /// Finds the first use of a product definition, by entity ID
+    pub fn find(&self, definition: usize) -> Option<&ProductNode> {
+        self.roots.iter().find_map(|n| n.find(definition))
+    }
+
+    /// Finds the first use of a product, by its name or ID
+    pub fn find_product(&self, product: &str) -> Option<&ProductNode> {
+        self.roots.iter().find_map(|n| n.find_product(product))
+    }
+
+    /// Returns every use of every product definition, placed in world
+    /// coordinates, in depth-first order
+    pub fn placements(&self) -> Vec<(usize, DMat4)> {
+        let mut out = Vec::new();
+        for n in &self.roots {
+            n.placements(&DMat4::identity(), &mut out);
+        }
+        out
+    }
+}
+
+/// Everything needed to build nodes, indexed by entity ID
+struct Context<'s, 'a> {
+    s: &'s StepFile<'a>,
+    cache: &'s PlacementCache,
+    shapes: HashMap<usize, Vec<usize>>,
+    /// Relationship's `rep_1`, `rep_2`, and transform
+    placements: HashMap<usize, (usize, usize, ItemDefinedTransformation<'a>)>,
+    children: BTreeMap<usize, Vec<(usize, &'s NextAssemblyUsageOccurrence_<'a>)>>,
+}
+
+impl<'s, 'a> Context<'s, 'a> {
+    fn node(&self, definition: usize, occurrence: Option<Occurrence>,
+            transform: DMat4, path: &mut Vec<usize>) -> Option<ProductNode>
+    {
+        let pd = self.s.entities().get(definition)?
+            .downcast::<ProductDefinition_>()?;
+        let p = product_of(self.s, definition);
+        let context = self.s.entities().get(pd.frame_of_reference.0)
+            .and_then(|e| e.downcast::<ProductDefinitionContext_>())
+            .map_or("", |c| c.name.0);
+        let shapes = self.shapes.get(&definition).cloned().unwrap_or_default();
+
+        path.push(definition);
+        let mut children = Vec::new();
+        for (id, o) in self.children.get(&definition).into_iter().flatten() {
+            let child = o.related_product_definition.0;
+            if path.contains(&child) {
+                warn!("Skipping cyclic assembly from #{} to #{}", definition, child);
+                continue;
+            }
+            let occurrence = Occurrence {
+                entity: *id,
+                id: o.id.0.to_owned(),
+                name: o.name.0.to_owned(),
+                reference_designator: o.reference_designator.get()
+                    .map(|r| r.0.to_owned()),
+            };
+            let mat = self.transform(*id, &shapes, child);
+            children.extend(self.node(child, Some(occurrence), mat, path));
+        }
+        path.pop();
+
+        Some(ProductNode {
+            definition,
+            product: p.map_or(0, |(id, _)| id),
+            id: p.map_or("", |(_, p)| p.id.0).to_owned(),
+            name: p.map_or("", |(_, p)| p.name.0).to_owned(),
+            context: context.to_owned(),
+            occurrence,
+            shapes,
+            transform,
+            children,
+        })
+    }
+
+    /// Finds the transform which places a child in its parent
+    fn transform(&self, occurrence: usize, parent_shapes: &[usize],
+                 child: usize) -> DMat4
+    {
+        let (rep_1, rep_2, t) = match self.placements.get(&occurrence) {
+            Some(p) => *p,
+            None => return DMat4::identity(),
+        };
+        let mat = self.cache.item_defined_transformation(self.s, t);
+        // The relationship should go from the child's shape to the
+        // parent's, but some exporters write it the other way around
+        let flipped = parent_shapes.contains(&rep_1) ||
+            self.shapes.get(&child).is_some_and(|s| s.contains(&rep_2));
+        if flipped {
+            mat.try_inverse().expect("Could not invert transform matrix")
+        } else {
+            mat
+        }
+    }
+}
+
+/// Follows a `PRODUCT_DEFINITION_SHAPE` to the entity which it describes
+fn shape_definition(s: &StepFile, shape: usize) -> Option<usize> {
+    s.entities().get(shape)?
+        .downcast::<ProductDefinitionShape_>()
+        .map(|d| d.definition.0)
+}
+
+/// Finds the `PRODUCT` of a `PRODUCT_DEFINITION`, returning its entity ID
+/// and value
+pub(crate) fn product_of<'a>(s: &'a StepFile, definition: usize)
+    -> Option<(usize, &'a Product_<'a>)>
+{
+    let pd = s.entities().get(definition)?.downcast::<ProductDefinition_>()?;
+    let f = s.entities().get(pd.formation.0)?;
+    let p = match f.downcast::<ProductDefinitionFormation_>() {
+        Some(f) => f.of_product,
+        None => f.downcast::<ProductDefinitionFormationWithSpecifiedSource_>()?
+            .of_product,
+    };
+    Some((p.0, s.entities().get(p.0)?.downcast()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A top-level assembly (#103) using a sub-assembly (#113) which uses
+    /// one part (#123) twice, with one placement written backwards
+    const DATA: &[u8] = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=DIRECTION('',(1.,0.,0.));
+#4=AXIS2_PLACEMENT_3D('',#1,#2,#3);
+#5=CARTESIAN_POINT('',(10.,0.,0.));
+#6=AXIS2_PLACEMENT_3D('',#5,#2,#3);
+#7=CARTESIAN_POINT('',(0.,5.,0.));
+#8=AXIS2_PLACEMENT_3D('',#7,#2,#3);
+#10=SHAPE_REPRESENTATION('top',(#4),$);
+#20=SHAPE_REPRESENTATION('sub',(#4),$);
+#30=SHAPE_REPRESENTATION('bolt',(#4),$);
+#50=ITEM_DEFINED_TRANSFORMATION('','',#4,#6);
+#51=ITEM_DEFINED_TRANSFORMATION('','',#4,#8);
+#60=(REPRESENTATION_RELATIONSHIP('','',#20,#10)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#50)SHAPE_REPRESENTATION_RELATIONSHIP());
+#61=(REPRESENTATION_RELATIONSHIP('','',#30,#20)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#51)SHAPE_REPRESENTATION_RELATIONSHIP());
+#62=(REPRESENTATION_RELATIONSHIP('','',#20,#30)REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#50)SHAPE_REPRESENTATION_RELATIONSHIP());
+#99=PRODUCT_DEFINITION_CONTEXT('part definition',#98,'design');
+#100=PRODUCT('TOP-1','top','',());
+#101=PRODUCT_DEFINITION_FORMATION('','',#100);
+#103=PRODUCT_DEFINITION('','',#101,#99);
+#104=PRODUCT_DEFINITION_SHAPE('','',#103);
+#105=SHAPE_DEFINITION_REPRESENTATION(#104,#10);
+#110=PRODUCT('SUB-1','sub','',());
+#111=PRODUCT_DEFINITION_FORMATION('','',#110);
+#113=PRODUCT_DEFINITION('','',#111,#99);
+#114=PRODUCT_DEFINITION_SHAPE('','',#113);
+#115=SHAPE_DEFINITION_REPRESENTATION(#114,#20);
+#120=PRODUCT('BOLT-1','bolt','',());
+#121=PRODUCT_DEFINITION_FORMATION_WITH_SPECIFIED_SOURCE('','',#120,.BOUGHT.);
+#123=PRODUCT_DEFINITION('','',#121,#99);
+#124=PRODUCT_DEFINITION_SHAPE('','',#123);
+#125=SHAPE_DEFINITION_REPRESENTATION(#124,#30);
+#200=NEXT_ASSEMBLY_USAGE_OCCURRENCE('1','sub','',#103,#113,$);
+#201=NEXT_ASSEMBLY_USAGE_OCCURRENCE('2','bolt','',#113,#123,'B1');
+#202=NEXT_ASSEMBLY_USAGE_OCCURRENCE('3','bolt','',#113,#123,'B2');
+#210=PRODUCT_DEFINITION_SHAPE('','',#200);
+#211=PRODUCT_DEFINITION_SHAPE('','',#201);
+#212=PRODUCT_DEFINITION_SHAPE('','',#202);
+#220=CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#60,#210);
+#221=CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#61,#211);
+#222=CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#62,#212);
+ENDSEC;
+END-ISO-10303-21;";
+
+    fn translation(m: &DMat4) -> (f64, f64, f64) {
+        let c = m.column(3);
+        (c[0], c[1], c[2])
+    }
+
+    fn names(nodes: &[ProductNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_product_tree() {
+        let flat = StepFile::strip_flatten(DATA);
+        let step = StepFile::parse(&flat);
+        let t = ProductTree::new(&step);
+        assert_eq!(names(&t.roots), ["top"]);
+        let top = &t.roots[0];
+        assert_eq!((top.definition, top.product), (103, 100));
+        assert_eq!(top.id, "TOP-1");
+        assert_eq!(top.context, "part definition");
+        assert_eq!(top.shapes, [10]);
+        assert_eq!(top.occurrence, None);
+        assert_eq!(names(&top.children), ["sub"]);
+
+        let sub = t.find_product("SUB-1").unwrap();
+        assert_eq!(sub.definition, 113);
+        assert_eq!(translation(&sub.transform), (10.0, 0.0, 0.0));
+        assert_eq!(names(&sub.children), ["bolt", "bolt"]);
+        let bolts = &sub.children;
+        assert_eq!(bolts[0].occurrence, Some(Occurrence {
+            entity: 201, id: "2".to_owned(), name: "bolt".to_owned(),
+            reference_designator: Some("B1".to_owned()),
+        }));
+        assert_eq!(translation(&bolts[0].transform), (0.0, 5.0, 0.0));
+        // #62 is written from parent to child, so it's inverted
+        assert_eq!(translation(&bolts[1].transform), (-10.0, 0.0, 0.0));
+
+        assert_eq!(t.find(123), Some(&bolts[0]));
+        assert_eq!(t.find(999), None);
+        let p: Vec<_> = t.placements().iter()
+            .map(|(d, m)| (*d, translation(m)))
+            .collect();
+        assert_eq!(p, [(103, (0.0, 0.0, 0.0)), (113, (10.0, 0.0, 0.0)),
+                       (123, (10.0, 5.0, 0.0)), (123, (0.0, 0.0, 0.0))]);
+    }
+
+    #[test]
+    fn test_example() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/abstract_pca.step");
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        let step = StepFile::parse(&flat);
+        let t = ProductTree::new(&step);
+        assert_eq!(names(&t.roots), ["PCB"]);
+        assert_eq!(names(&t.roots[0].children), ["Board", "Free-Models"]);
+        let parts = t.find_product("9084755200").unwrap();
+        assert_eq!(names(&parts.children), ["Cylinder"]);
+        assert_eq!(parts.children[0].occurrence.as_ref().unwrap().name, "3");
+    }
+}
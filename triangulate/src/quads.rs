@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use nalgebra_glm as glm;
+use glm::{DVec3, U32Vec3, U32Vec4};
+
+use crate::{export::ExportOptions, mesh::Mesh};
+
+/// A face in a quad-dominant mesh, indexing into [`Mesh::verts`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Polygon {
+    Triangle(U32Vec3),
+    Quad(U32Vec4),
+}
+
+/// Limits on which pairs of triangles may be merged into a quad
+#[derive(Copy, Clone, Debug)]
+pub struct QuadParams {
+    /// Maximum angle (in radians) between the two triangles' normals
+    pub max_fold: f64,
+    /// Maximum deviation (in radians) of any quad corner from a right angle
+    pub max_skew: f64,
+}
+
+impl Default for QuadParams {
+    fn default() -> Self {
+        Self {
+            max_fold: 5f64.to_radians(),
+            max_skew: 45f64.to_radians(),
+        }
+    }
+}
+
+/// Greedily merges pairs of adjacent triangles into quads, preferring the
+/// most rectangular candidates.  Triangles are only merged if they share an
+/// edge (with consistent winding), are nearly coplanar, and form a convex
+/// quad; everything else is passed through as a triangle.
+pub fn quad_dominant(mesh: &Mesh, params: &QuadParams) -> Vec<Polygon> {
+    let pos = |i: u32| mesh.verts[i as usize].pos;
+    let tri_normal = |t: &U32Vec3| {
+        let n = (pos(t[1]) - pos(t[0])).cross(&(pos(t[2]) - pos(t[0])));
+        let len = n.norm();
+        if len > 0.0 { Some(n / len) } else { None }
+    };
+
+    // Map from directed edge to (triangle index, opposite vertex)
+    let mut edges = HashMap::new();
+    for (i, t) in mesh.triangles.iter().enumerate() {
+        for j in 0..3 {
+            edges.insert((t.verts[j], t.verts[(j + 1) % 3]),
+                         (i, t.verts[(j + 2) % 3]));
+        }
+    }
+
+    // Find every valid candidate, scored by its worst corner
+    let mut candidates = Vec::new();
+    for (&(a, b), &(i, c)) in edges.iter() {
+        // Only consider each undirected edge once
+        if a > b {
+            continue;
+        }
+        let (j, d) = match edges.get(&(b, a)) {
+            Some(e) => *e,
+            None => continue,
+        };
+        if i == j {
+            continue;
+        }
+        let (ni, nj) = match (tri_normal(&mesh.triangles[i].verts),
+                              tri_normal(&mesh.triangles[j].verts)) {
+            (Some(ni), Some(nj)) => (ni, nj),
+            _ => continue,
+        };
+        if ni.dot(&nj).min(1.0).acos() > params.max_fold {
+            continue;
+        }
+
+        // Walking around the boundary of both triangles, skipping the
+        // shared edge, gives us the quad
+        let quad = U32Vec4::new(a, d, b, c);
+        if let Some(skew) = skew(&quad, &(ni + nj), pos) {
+            if skew <= params.max_skew {
+                candidates.push((skew, i, j, quad));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap()
+        .then((a.1, a.2).cmp(&(b.1, b.2))));
+
+    let mut used = vec![false; mesh.triangles.len()];
+    let mut out = Vec::new();
+    for (_, i, j, quad) in candidates {
+        if !used[i] && !used[j] {
+            used[i] = true;
+            used[j] = true;
+            out.push(Polygon::Quad(quad));
+        }
+    }
+    out.extend(mesh.triangles.iter()
+        .zip(used.iter())
+        .filter(|(_, u)| !**u)
+        .map(|(t, _)| Polygon::Triangle(t.verts)));
+    out
+}
+
+/// Returns the largest deviation of any corner from a right angle, or `None`
+/// if the quad isn't convex (when seen along `normal`)
+fn skew<F>(quad: &U32Vec4, normal: &DVec3, pos: F) -> Option<f64>
+    where F: Fn(u32) -> DVec3
+{
+    let mut worst: f64 = 0.0;
+    for k in 0..4 {
+        let p = pos(quad[k]);
+        let prev = pos(quad[(k + 3) % 4]) - p;
+        let next = pos(quad[(k + 1) % 4]) - p;
+        if next.cross(&prev).dot(normal) <= 0.0 {
+            return None;
+        }
+        let angle = glm::angle(&prev, &next);
+        worst = worst.max((angle - std::f64::consts::FRAC_PI_2).abs());
+    }
+    Some(worst)
+}
+
+/// Writes a mesh as an OBJ file.  If `polygons` is provided, it's used
+/// instead of the mesh's triangles (e.g. from [`quad_dominant`]).  Each
+/// part's [`Part::metadata`](crate::mesh::Part::metadata) is written as a
+/// comment at the top of the file.
+pub fn save_obj(mesh: &Mesh, polygons: Option<&[Polygon]>, filename: &str)
+    -> std::io::Result<()>
+{
+    save_obj_with(mesh, polygons, filename, &ExportOptions::default())
+}
+
+/// Writes a mesh as an OBJ file, like [`save_obj`], with the given winding
+/// order and index base
+pub fn save_obj_with(mesh: &Mesh, polygons: Option<&[Polygon]>,
+                     filename: &str, options: &ExportOptions)
+    -> std::io::Result<()>
+{
+    let mut out = std::io::BufWriter::new(std::fs::File::create(filename)?);
+    for p in &mesh.parts {
+        for (k, v) in &p.metadata {
+            // Comments end at the end of the line
+            writeln!(out, "# part #{}: {} = {}", p.id,
+                     k.replace(&['\r', '\n'][..], " "),
+                     v.replace(&['\r', '\n'][..], " "))?;
+        }
+    }
+    for v in &mesh.verts {
+        writeln!(out, "v {} {} {}", v.pos.x, v.pos.y, v.pos.z)?;
+    }
+    let write_face = |out: &mut std::io::BufWriter<_>, vs: &[u32]| {
+        write!(out, "f")?;
+        for v in vs {
+            write!(out, " {}", options.index(*v))?;
+        }
+        writeln!(out)
+    };
+    match polygons {
+        Some(polygons) => for p in polygons {
+            match p {
+                Polygon::Triangle(t) =>
+                    write_face(&mut out, &options.wind((*t).into()))?,
+                Polygon::Quad(q) =>
+                    write_face(&mut out, &options.wind((*q).into()))?,
+            }
+        },
+        None => for t in &mesh.triangles {
+            write_face(&mut out, &options.wind(t.verts.into()))?;
+        },
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Triangle, Vertex};
+
+    fn mesh(pts: &[[f64; 3]], tris: &[[u32; 3]]) -> Mesh {
+        Mesh {
+            verts: pts.iter().map(|p| Vertex {
+                pos: DVec3::new(p[0], p[1], p[2]),
+                norm: DVec3::zeros(),
+                color: DVec3::zeros(),
+            }).collect(),
+            triangles: tris.iter()
+                .map(|t| Triangle {
+                    verts: U32Vec3::new(t[0], t[1], t[2]),
+                    face: 0,
+                })
+                .collect(),
+            parts: Vec::new(),
+            offset: DVec3::zeros(),
+        }
+    }
+
+    #[test]
+    fn test_square() {
+        let m = mesh(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0],
+                       [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+                     &[[0, 1, 2], [0, 2, 3]]);
+        let out = quad_dominant(&m, &QuadParams::default());
+        assert_eq!(out.len(), 1);
+        match out[0] {
+            Polygon::Quad(q) => {
+                // Winding is preserved, starting from the shared edge
+                assert_eq!(q, U32Vec4::new(0, 1, 2, 3));
+            },
+            p => panic!("Expected quad, got {:?}", p),
+        }
+    }
+
+    #[test]
+    fn test_folded() {
+        // Two triangles folded at 90 degrees aren't merged
+        let m = mesh(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0],
+                       [1.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                     &[[0, 1, 2], [1, 0, 3]]);
+        let out = quad_dominant(&m, &QuadParams::default());
+        assert_eq!(out.len(), 2);
+        assert!(out.iter().all(|p| matches!(p, Polygon::Triangle(_))));
+    }
+
+    #[test]
+    fn test_sliver() {
+        // This pair forms a very skewed quad, which is rejected
+        let m = mesh(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0],
+                       [5.0, 1.0, 0.0], [4.0, 1.0, 0.0]],
+                     &[[0, 1, 2], [0, 2, 3]]);
+        let out = quad_dominant(&m, &QuadParams::default());
+        assert_eq!(out.len(), 2);
+    }
+
+    #[test]
+    fn test_obj_options() {
+        let m = mesh(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0],
+                       [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+                     &[[0, 1, 2], [0, 2, 3]]);
+        let quads = quad_dominant(&m, &QuadParams::default());
+        let path = std::env::temp_dir()
+            .join(format!("foxtrot-quads-{}.obj", std::process::id()));
+        let path = path.to_str().unwrap();
+        let faces = |polygons: Option<&[Polygon]>, options| {
+            save_obj_with(&m, polygons, path, &options).unwrap();
+            let text = std::fs::read_to_string(path).unwrap();
+            text.lines().filter(|l| l.starts_with('f'))
+                .map(str::to_owned).collect::<Vec<_>>()
+        };
+
+        assert_eq!(faces(None, ExportOptions::default()),
+                   ["f 1 2 3", "f 1 3 4"]);
+        let options = ExportOptions {
+            winding: crate::export::Winding::Clockwise,
+            index_base: crate::export::IndexBase::Zero,
+        };
+        assert_eq!(faces(None, options), ["f 0 2 1", "f 0 3 2"]);
+        assert_eq!(faces(Some(&quads), options), ["f 0 3 2 1"]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,463 @@
+use std::f64::consts::PI;
+
+use nalgebra_glm::DVec3;
+use log::warn;
+
+use crate::surface::Surface;
+
+/// Spherical faces which extend further than this from their projection
+/// center (100°) are split in half before triangulation
+const MAX_SPHERE_EXTENT: f64 = PI * 5.0 / 9.0;
+
+/// Number of points used to sample each closed cut curve, matching the
+/// resolution used for circles in `Curve`
+const CUT_SAMPLES: usize = 64;
+
+/// A piece of a face, as a surface and boundary loops (in the same format as
+/// `face_bound`, i.e. with a duplicate point to close each loop)
+pub type Part = (Surface, Vec<Vec<DVec3>>);
+
+/// Cuts faces which span too much of their surface to be lowered in one
+/// piece: more than a hemisphere of a sphere, or more than half a turn around
+/// the axis of a cylinder or torus.  Each face is cut in half by a plane,
+/// and each half is given its own copy of the surface, to be prepared and
+/// lowered independently.
+///
+/// `loops` must be oriented with the face on their left, when seen from the
+/// side which the face's normal points to.
+///
+/// Returns `None` if the face doesn't need to be split, or if splitting
+/// failed (in which case the face should be triangulated in one piece).
+pub fn split_face(surf: &mut Surface, loops: &[Vec<DVec3>], same_sense: bool)
+    -> Option<Vec<Part>>
+{
+    let cut = match surf {
+        Surface::Sphere { .. } => {
+            if surf.orient_sphere(loops, same_sense)? <= MAX_SPHERE_EXTENT {
+                return None;
+            }
+            match surf {
+                Surface::Sphere { location, radius, center: Some(c), .. } =>
+                    Cut::sphere(*location, *radius, *c),
+                _ => unreachable!(),
+            }
+        },
+        Surface::Cylinder { location, axis, radius, .. } => {
+            let m = cut_normal(loops, *location, *axis)?;
+            Cut::cylinder(*location, *axis, *radius, m, loops)?
+        },
+        Surface::Torus { location, axis, major_radius, minor_radius, .. } => {
+            let m = cut_normal(loops, *location, *axis)?;
+            Cut::torus(*location, *axis, *major_radius, *minor_radius, m)
+        },
+        _ => return None,
+    };
+
+    let mut out = Vec::new();
+    for side in &[true, false] {
+        let loops = match cut.clip(loops, *side == same_sense, *side) {
+            Some(loops) => loops,
+            None => {
+                warn!("Could not split face on {:?}", surf);
+                return None;
+            },
+        };
+        let mut surf = surf.clone();
+        // Each half of a sphere is centered on its own side of the cut
+        if let Surface::Sphere { center: Some(c), .. } = &mut surf {
+            if !side {
+                *c = -*c;
+            }
+        }
+        out.push((surf, loops));
+    }
+    Some(out)
+}
+
+/// Picks a plane (through the axis) which cuts a face on a surface of
+/// revolution in half, returning its normal.  Returns `None` if the face
+/// spans less than half a turn, so doesn't need to be split.
+fn cut_normal(loops: &[Vec<DVec3>], location: DVec3, axis: DVec3)
+    -> Option<DVec3>
+{
+    let bx = axis.cross(&if axis.x.abs() < 0.9 { DVec3::x() } else { DVec3::y() })
+        .normalize();
+    let by = axis.cross(&bx);
+    let mut angles: Vec<f64> = loops.iter().flatten()
+        .map(|p| p - location)
+        .filter(|d| d.cross(&axis).norm() > f64::EPSILON)
+        .map(|d| d.dot(&by).atan2(d.dot(&bx)))
+        .collect();
+    if angles.is_empty() {
+        return None;
+    }
+    if 2.0 * PI - largest_gap(&mut angles, 2.0 * PI).0 <= PI {
+        return None;
+    }
+
+    // The plane hits the surface at two angles (half a turn apart), so we
+    // look for a gap between vertices when angles are wrapped to half a turn,
+    // then put the plane in the middle of it.
+    for a in angles.iter_mut() {
+        *a = a.rem_euclid(PI);
+    }
+    let (_, mid) = largest_gap(&mut angles, PI);
+    let mid = mid + PI / 2.0;
+    Some(bx * mid.cos() + by * mid.sin())
+}
+
+/// Finds the largest gap between angles wrapped to a given period, returning
+/// its size and the angle in its middle
+pub(crate) fn largest_gap(angles: &mut [f64], period: f64) -> (f64, f64) {
+    angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut best = (period - angles[angles.len() - 1] + angles[0],
+                    angles[angles.len() - 1]);
+    for w in angles.windows(2) {
+        if w[1] - w[0] > best.0 {
+            best = (w[1] - w[0], w[0]);
+        }
+    }
+    (best.0, best.1 + best.0 / 2.0)
+}
+
+/// The intersection of a plane and a surface, as a polyline
+struct CutCurve {
+    pts: Vec<DVec3>,
+    /// Distance along the curve to each point
+    t: Vec<f64>,
+    closed: bool,
+}
+
+impl CutCurve {
+    /// Builds a curve, oriented so that the positive side of the plane (with
+    /// normal `m`) is on its left when seen from outside of the surface
+    fn new(mut pts: Vec<DVec3>, closed: bool, m: DVec3, outward: DVec3) -> Self {
+        if (pts[1] - pts[0]).dot(&m.cross(&outward)) < 0.0 {
+            pts.reverse();
+        }
+        let mut t = vec![0.0];
+        for w in pts.windows(2) {
+            t.push(t[t.len() - 1] + (w[1] - w[0]).norm());
+        }
+        Self { pts, t, closed }
+    }
+
+    fn len(&self) -> f64 {
+        let end = self.t[self.t.len() - 1];
+        if self.closed {
+            end + (self.pts[0] - self.pts[self.pts.len() - 1]).norm()
+        } else {
+            end
+        }
+    }
+
+    fn reverse(&mut self) {
+        let len = self.len();
+        self.pts.reverse();
+        self.t.reverse();
+        for t in self.t.iter_mut() {
+            *t = if self.closed { (len - *t) % len } else { len - *t };
+        }
+        if self.closed {
+            // Keep the starting point at t = 0
+            self.pts.rotate_right(1);
+            self.t.rotate_right(1);
+        }
+    }
+
+    /// Returns the distance from `p` to the nearest point on the curve,
+    /// and that point's position along the curve
+    fn locate(&self, p: DVec3) -> (f64, f64) {
+        let n = self.pts.len();
+        let num_segments = if self.closed { n } else { n - 1 };
+        let mut best = (f64::INFINITY, 0.0);
+        for i in 0..num_segments {
+            let (a, b) = (self.pts[i], self.pts[(i + 1) % n]);
+            let len = (b - a).norm();
+            let s = ((p - a).dot(&(b - a)) / (len * len)).clamp(0.0, 1.0);
+            let d = (a + (b - a) * s - p).norm();
+            if d < best.0 {
+                best = (d, self.t[i] + s * len);
+            }
+        }
+        best
+    }
+
+    /// Returns the distance travelled when moving forward from `a` to `b`,
+    /// or `None` if that's not possible on an open curve
+    fn forward(&self, a: f64, b: f64) -> Option<f64> {
+        if self.closed {
+            Some((b - a).rem_euclid(self.len()))
+        } else if b >= a {
+            Some(b - a)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the points strictly between `a` and `b` (moving forward),
+    /// skipping any which are very close to either end
+    fn between(&self, a: f64, b: f64) -> Vec<DVec3> {
+        let span = match self.forward(a, b) {
+            Some(s) => s,
+            None => return vec![],
+        };
+        let eps = self.len() / CUT_SAMPLES as f64 / 4.0;
+        let mut pts: Vec<(f64, DVec3)> = self.t.iter().zip(&self.pts)
+            .filter_map(|(t, p)| self.forward(a, *t).map(|d| (d, *p)))
+            .filter(|(d, _)| *d > eps && *d < span - eps)
+            .collect();
+        pts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        pts.into_iter().map(|(_, p)| p).collect()
+    }
+}
+
+/// A plane which cuts a face in two
+struct Cut {
+    origin: DVec3,
+    normal: DVec3,
+    curves: Vec<CutCurve>,
+}
+
+/// A section of a boundary loop which is on one side of a cut, starting and
+/// ending on the cut
+struct Chain {
+    pts: Vec<DVec3>,
+    entry: (usize, f64),
+    exit: (usize, f64),
+}
+
+impl Cut {
+    /// Cuts a sphere along the great circle which is perpendicular to `c`
+    fn sphere(location: DVec3, radius: f64, c: DVec3) -> Self {
+        let (u, v) = perpendicular(c);
+        let pts = circle(location, u * radius, v * radius);
+        Self {
+            origin: location,
+            normal: c,
+            curves: vec![CutCurve::new(pts, true, c, u)],
+        }
+    }
+
+    /// Cuts a cylinder along two lines, which are parallel to its axis and
+    /// extend past the ends of the face
+    fn cylinder(location: DVec3, axis: DVec3, radius: f64, m: DVec3,
+                loops: &[Vec<DVec3>]) -> Option<Self>
+    {
+        let (lo, hi) = loops.iter().flatten()
+            .map(|p| (p - location).dot(&axis))
+            .fold((f64::INFINITY, f64::NEG_INFINITY),
+                  |(lo, hi), z| (lo.min(z), hi.max(z)));
+        let pad = (hi - lo) * 0.1;
+        if !pad.is_finite() || pad <= 0.0 {
+            return None;
+        }
+        let e = m.cross(&axis);
+        let curves = [e, -e].iter().map(|e| {
+            let base = location + e * radius;
+            let pts = vec![base + axis * (lo - pad), base + axis * (hi + pad)];
+            CutCurve::new(pts, false, m, *e)
+        }).collect();
+        Some(Self { origin: location, normal: m, curves })
+    }
+
+    /// Cuts a torus along two minor circles, half a turn apart
+    fn torus(location: DVec3, axis: DVec3, major_radius: f64,
+             minor_radius: f64, m: DVec3) -> Self
+    {
+        let e = m.cross(&axis);
+        let curves = [e, -e].iter().map(|e| {
+            let pts = circle(location + e * major_radius,
+                             e * minor_radius, axis * minor_radius);
+            CutCurve::new(pts, true, m, *e)
+        }).collect();
+        Self { origin: location, normal: m, curves }
+    }
+
+    /// Clips the face's loops to one side of the cut, closing them up along
+    /// the cut curves.  `forward` indicates whether the cut curves should
+    /// be walked in their original direction, which depends on both the side
+    /// and the face's sense.
+    fn clip(&self, loops: &[Vec<DVec3>], forward: bool, positive: bool)
+        -> Option<Vec<Vec<DVec3>>>
+    {
+        let normal = if positive { self.normal } else { -self.normal };
+        let dist = |p: &DVec3| (p - self.origin).dot(&normal);
+        let crossing = |a: DVec3, b: DVec3| {
+            let (da, db) = (dist(&a), dist(&b));
+            a + (b - a) * (da / (da - db))
+        };
+        let mut curves: Vec<&CutCurve> = self.curves.iter().collect();
+        let reversed: Vec<CutCurve>;
+        if !forward {
+            reversed = self.curves.iter().map(|c| {
+                let mut c = CutCurve { pts: c.pts.clone(), t: c.t.clone(),
+                                       closed: c.closed };
+                c.reverse();
+                c
+            }).collect();
+            curves = reversed.iter().collect();
+        }
+        let locate = |p: DVec3| -> Option<(usize, f64)> {
+            curves.iter().enumerate()
+                .map(|(i, c)| (c.locate(p), i, c.len()))
+                .filter(|((d, _), _, len)| *d < len / CUT_SAMPLES as f64)
+                .min_by(|a, b| (a.0).0.partial_cmp(&(b.0).0).unwrap())
+                .map(|((_, t), i, _)| (i, t))
+        };
+
+        let mut out = Vec::new();
+        let mut chains = Vec::new();
+        for l in loops {
+            // Single-point loops are kept on whichever side they fall
+            if l.len() == 1 {
+                if dist(&l[0]) > 0.0 {
+                    out.push(l.clone());
+                }
+                continue;
+            }
+            let pts = &l[..l.len() - 1];
+            let inside: Vec<bool> = pts.iter().map(|p| dist(p) > 0.0).collect();
+            if inside.iter().all(|i| *i) {
+                out.push(l.clone());
+                continue;
+            }
+
+            // Start walking from an outside point, so that every chain is
+            // built up in one piece
+            let n = pts.len();
+            let start = inside.iter().position(|i| !*i)?;
+            let mut chain = Vec::new();
+            for k in 0..n {
+                let (i, j) = ((start + k) % n, (start + k + 1) % n);
+                if inside[i] {
+                    chain.push(pts[i]);
+                }
+                match (inside[i], inside[j]) {
+                    (false, true) => chain = vec![crossing(pts[i], pts[j])],
+                    (true, false) => {
+                        chain.push(crossing(pts[i], pts[j]));
+                        let entry = locate(chain[0])?;
+                        let exit = locate(chain[chain.len() - 1])?;
+                        chains.push(Chain {
+                            pts: std::mem::take(&mut chain),
+                            entry, exit,
+                        });
+                    },
+                    _ => (),
+                }
+            }
+        }
+
+        // Closed cut curves which don't touch the boundary are entirely
+        // inside the face, since we only cut faces which straddle the plane
+        for (i, c) in curves.iter().enumerate() {
+            if c.closed && !chains.iter().any(|ch| ch.entry.0 == i) {
+                let mut pts = c.pts.clone();
+                pts.push(pts[0]);
+                out.push(pts);
+            }
+        }
+
+        // Link chains together by walking along the cut curves, from each
+        // exit to the next entry
+        let mut used = vec![false; chains.len()];
+        for first in 0..chains.len() {
+            if used[first] {
+                continue;
+            }
+            let mut pts = Vec::new();
+            let mut k = first;
+            loop {
+                used[k] = true;
+                pts.extend_from_slice(&chains[k].pts);
+                let (c, t) = chains[k].exit;
+                let curve = &curves[c];
+                let next = chains.iter().enumerate()
+                    .filter(|(_, ch)| ch.entry.0 == c)
+                    .filter_map(|(i, ch)| curve.forward(t, ch.entry.1)
+                                               .map(|d| (d, i)))
+                    .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())?.1;
+                pts.extend(curve.between(t, chains[next].entry.1));
+                if next == first {
+                    break;
+                } else if used[next] {
+                    return None;
+                }
+                k = next;
+            }
+            pts.push(pts[0]);
+            out.push(pts);
+        }
+        Some(out)
+    }
+}
+
+/// Returns a pair of unit vectors which are perpendicular to each other and
+/// to `v` (which must be normalized), forming a right-handed basis
+fn perpendicular(v: DVec3) -> (DVec3, DVec3) {
+    let u = v.cross(&if v.x.abs() < 0.9 { DVec3::x() } else { DVec3::y() })
+        .normalize();
+    (u, v.cross(&u))
+}
+
+/// Samples a circle with the given center and in-plane radius vectors
+fn circle(center: DVec3, u: DVec3, v: DVec3) -> Vec<DVec3> {
+    (0..CUT_SAMPLES).map(|i| {
+        let t = i as f64 / CUT_SAMPLES as f64 * 2.0 * PI;
+        center + u * t.cos() + v * t.sin()
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples a closed loop around the Z axis, in the same format as
+    /// `face_bound` (with a duplicate point at the end)
+    fn ring(r: f64, z: f64, ccw: bool) -> Vec<DVec3> {
+        let mut pts: Vec<DVec3> = (0..=48).map(|i| {
+            let t = i as f64 / 48.0 * 2.0 * PI;
+            DVec3::new(r * t.cos(), r * t.sin(), z)
+        }).collect();
+        if !ccw {
+            pts.reverse();
+        }
+        pts
+    }
+
+    #[test]
+    fn test_torus_split() {
+        // Upper half of a torus, bounded by its inner and outer equators
+        let mut surf = Surface::new_torus(DVec3::zeros(), DVec3::z(), 2.0, 0.5);
+        let loops = vec![ring(2.5, 0.0, true), ring(1.5, 0.0, false)];
+        let parts = split_face(&mut surf, &loops, true).unwrap();
+        assert_eq!(parts.len(), 2);
+        for (_, loops) in &parts {
+            // Each half is a single loop, which stays on the upper half of
+            // the torus when walking along the cut
+            assert_eq!(loops.len(), 1);
+            assert!(loops[0].iter().all(|p| p.z > -1e-9));
+            assert!(loops[0].iter().any(|p| p.z > 0.4));
+        }
+    }
+
+    #[test]
+    fn test_cylinder_split() {
+        let mut surf = Surface::new_cylinder(
+            DVec3::z(), DVec3::x(), DVec3::zeros(), 1.0);
+        let loops = vec![ring(1.0, 0.0, true), ring(1.0, 2.0, false)];
+        let parts = split_face(&mut surf, &loops, true).unwrap();
+        assert_eq!(parts.len(), 2);
+        for (_, loops) in &parts {
+            assert_eq!(loops.len(), 1);
+            let lo = loops[0].iter().map(|p| p.z).fold(f64::INFINITY, f64::min);
+            let hi = loops[0].iter().map(|p| p.z).fold(f64::NEG_INFINITY, f64::max);
+            assert_eq!((lo, hi), (0.0, 2.0));
+        }
+
+        // Faces which only span a quarter-turn aren't split
+        let arc: Vec<DVec3> = ring(1.0, 0.0, true)[..13].to_vec();
+        assert!(split_face(&mut surf, &[arc], true).is_none());
+    }
+}
@@ -1,9 +1,23 @@
+use crate::{
+    diagnostic::Diagnostic,
+    metrics::{self, Metrics},
+    params::{RefineCounts, UvFallbackCounts},
+};
+
 #[derive(Default)]
 pub struct Stats {
     pub num_shells: usize,
     pub num_faces: usize,
     pub num_errors: usize,
     pub num_panics: usize,
+    /// Boundary points which were only projected onto a spline surface by a
+    /// fallback (see [`UvFallback`](crate::params::UvFallback))
+    pub uv_fallbacks: UvFallbackCounts,
+    /// See [`Refinement`](crate::params::Refinement)
+    pub refinement: RefineCounts,
+    /// Every entity which was skipped or failed to triangulate, in the order
+    /// in which they were found
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl Stats {
@@ -14,6 +28,59 @@ impl Stats {
         a.num_faces += b.num_faces;
         a.num_errors += b.num_errors;
         a.num_panics += b.num_panics;
+        a.uv_fallbacks += b.uv_fallbacks;
+        a.refinement += b.refinement;
+        a.diagnostics.extend(b.diagnostics);
         a
     }
+
+    /// Formats the counts and diagnostics as a JSON object, e.g. to save as
+    /// a report beside a converted file
+    pub fn to_json(&self) -> String {
+        let diagnostics: Vec<String> = self.diagnostics.iter()
+            .map(|d| format!(r#"{{"id":{},"severity":"{}","message":{}}}"#,
+                             d.id, d.severity, json_string(&d.message)))
+            .collect();
+        format!(concat!(
+            r#"{{"shells":{},"faces":{},"errors":{},"panics":{},"#,
+            r#""uv_reseeded":{},"uv_grid_searched":{},"refined_points":{},"#,
+            r#""refine_over_budget":{},"max_deviation":{},"diagnostics":[{}]}}"#),
+            self.num_shells, self.num_faces, self.num_errors,
+            self.num_panics, self.uv_fallbacks.reseeded,
+            self.uv_fallbacks.grid_searched, self.refinement.points,
+            self.refinement.over_budget, self.refinement.max_deviation,
+            diagnostics.join(","))
+    }
+
+    /// Reports each count to a [`Metrics`] sink
+    pub fn report(&self, m: &mut dyn Metrics) {
+        m.counter(metrics::SHELLS, self.num_shells as u64);
+        m.counter(metrics::FACES, self.num_faces as u64);
+        m.counter(metrics::ERRORS, self.num_errors as u64);
+        m.counter(metrics::PANICS, self.num_panics as u64);
+        m.counter(metrics::UV_RESEEDED, self.uv_fallbacks.reseeded as u64);
+        m.counter(metrics::UV_GRID_SEARCHED,
+                  self.uv_fallbacks.grid_searched as u64);
+        m.counter(metrics::REFINED_POINTS, self.refinement.points as u64);
+        m.counter(metrics::REFINE_OVER_BUDGET,
+                  self.refinement.over_budget as u64);
+    }
+}
+
+/// Quotes and escapes a string for JSON
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 =>
+                out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
 }
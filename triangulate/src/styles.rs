@@ -0,0 +1,169 @@
+//! Colors assigned to geometric items by presentation styles.
+//!
+//! A `STYLED_ITEM` gives an item (usually a solid, a shell, or a face) a
+//! list of `PRESENTATION_STYLE_ASSIGNMENT`s.  The color is found by
+//! following a surface style through its `SURFACE_STYLE_FILL_AREA` to a
+//! `COLOUR_RGB`, and the opacity from the surface's rendering properties.
+use std::collections::BTreeMap;
+
+use nalgebra_glm::{DVec3, DVec4};
+
+use step::{ap214::*, step_file::StepFile};
+
+/// Color of an item which isn't styled, or whose style has no color
+pub const DEFAULT_COLOR: DVec3 = DVec3::new(0.5, 0.5, 0.5);
+
+/// Finds the presentation styles in a `PRESENTATION_STYLE_ASSIGNMENT` or
+/// `PRESENTATION_STYLE_BY_CONTEXT`, by entity ID
+fn assigned_styles<'a>(s: &'a StepFile, p: usize)
+    -> Option<&'a [PresentationStyleSelect<'a>]>
+{
+    let e = s.entities().get(p)?;
+    match e.downcast::<PresentationStyleAssignment_>() {
+        Some(p) => Some(&p.styles),
+        None => e.downcast::<PresentationStyleByContext_>()
+            .map(|p| &p.styles[..]),
+    }
+}
+
+/// Finds the surface style which applies to the outside of a styled item
+fn presentation_side_style<'a>(s: &'a StepFile, p: usize)
+    -> Option<&'a SurfaceSideStyle_<'a>>
+{
+    // AAAAAHHHHH
+    assigned_styles(s, p)
+        .and_then(|styles| styles.iter().find_map(|y| {
+                // This is an ambiguous parse, so we hard-code the first
+                // Entity item in the enum
+                use PresentationStyleSelect::PreDefinedPresentationStyle;
+                if let PreDefinedPresentationStyle(u) = y {
+                    s.entity(u.cast::<SurfaceStyleUsage_>())
+                } else {
+                    None
+                }
+            }))
+        .and_then(|surf: &SurfaceStyleUsage_|
+            s.entity(surf.style.cast::<SurfaceSideStyle_>()))
+}
+
+fn presentation_style_color(s: &StepFile, p: usize) -> Option<DVec3> {
+    // The fill area may be accompanied by other styles (e.g. rendering
+    // properties), so search for it rather than requiring it to be alone
+    presentation_side_style(s, p)
+        .and_then(|surf: &SurfaceSideStyle_| surf.styles.iter()
+            .find_map(|t| s.entity(t.cast::<SurfaceStyleFillArea_>())))
+        .map(|surf: &SurfaceStyleFillArea_|
+            s.entity(surf.fill_area).expect("Could not get fill_area"))
+        .and_then(|fill: &FillAreaStyle_| if fill.fill_styles.len() != 1 {
+                None
+            } else {
+                s.entity(fill.fill_styles[0].cast::<FillAreaStyleColour_>())
+            })
+        .and_then(|f: &FillAreaStyleColour_|
+            s.entity(f.fill_colour.cast::<ColourRgb_>()))
+        .map(|c| DVec3::new(c.red, c.green, c.blue))
+}
+
+/// Reads the opacity (1 - transparency) from a surface's rendering
+/// properties, returning `None` if it isn't specified
+fn presentation_style_opacity(s: &StepFile, p: usize) -> Option<f64> {
+    presentation_side_style(s, p)
+        .and_then(|surf: &SurfaceSideStyle_| surf.styles.iter()
+            .find_map(|t| s.entity(
+                t.cast::<SurfaceStyleRenderingWithProperties_>())))
+        .and_then(|r: &SurfaceStyleRenderingWithProperties_| r.properties
+            .iter()
+            .find_map(|p| s.entity(p.cast::<SurfaceStyleTransparent_>())))
+        .map(|t| (1.0 - t.transparency).clamp(0.0, 1.0))
+}
+
+/// Finds the color of every styled item, by the entity ID of the item, as
+/// RGBA.  Styles which don't give a color use [`DEFAULT_COLOR`], and those
+/// which don't give a transparency are opaque; items whose styles give
+/// neither are left out.
+///
+/// If an item is styled more than once, an `OVER_RIDING_STYLED_ITEM` takes
+/// precedence over a plain `STYLED_ITEM`, and otherwise the last one in the
+/// file wins.
+pub fn styles(s: &StepFile) -> BTreeMap<usize, DVec4> {
+    let plain = s.iter::<StyledItem>()
+        .map(|(_, i)| (i.item, &i.styles));
+    let over_riding = s.iter::<OverRidingStyledItem>()
+        .map(|(_, i)| (i.item, &i.styles));
+
+    let mut out = BTreeMap::new();
+    for (item, styles) in plain.chain(over_riding) {
+        let color = styles.iter()
+            .find_map(|p| presentation_style_color(s, p.0));
+        let opacity = styles.iter()
+            .find_map(|p| presentation_style_opacity(s, p.0));
+        if color.is_some() || opacity.is_some() {
+            let c = color.unwrap_or(DEFAULT_COLOR);
+            out.insert(item.0, DVec4::new(c.x, c.y, c.z, opacity.unwrap_or(1.0)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A red face (#10) which is half transparent, overridden in green, and
+    /// a shell (#11) with a transparency but no color
+    const DATA: &[u8] = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=COLOUR_RGB('',1.,0.,0.);
+#2=FILL_AREA_STYLE_COLOUR('',#1);
+#3=FILL_AREA_STYLE('',(#2));
+#4=SURFACE_STYLE_FILL_AREA(#3);
+#5=SURFACE_STYLE_TRANSPARENT(0.5);
+#6=SURFACE_STYLE_RENDERING_WITH_PROPERTIES(.NORMAL_SHADING.,#1,(#5));
+#7=SURFACE_SIDE_STYLE('',(#4,#6));
+#8=SURFACE_STYLE_USAGE(.BOTH.,#7);
+#9=PRESENTATION_STYLE_ASSIGNMENT((#8));
+#10=ADVANCED_FACE('',(),#99,.T.);
+#11=OPEN_SHELL('',(#10));
+#20=STYLED_ITEM('',(#9),#10);
+#30=COLOUR_RGB('',0.,1.,0.);
+#31=FILL_AREA_STYLE_COLOUR('',#30);
+#32=FILL_AREA_STYLE('',(#31));
+#33=SURFACE_STYLE_FILL_AREA(#32);
+#34=SURFACE_SIDE_STYLE('',(#33));
+#35=SURFACE_STYLE_USAGE(.BOTH.,#34);
+#36=PRESENTATION_STYLE_BY_CONTEXT((#35),#11);
+#40=OVER_RIDING_STYLED_ITEM('',(#36),#10,#20);
+#50=SURFACE_SIDE_STYLE('',(#6));
+#51=SURFACE_STYLE_USAGE(.BOTH.,#50);
+#52=PRESENTATION_STYLE_ASSIGNMENT((#51));
+#53=STYLED_ITEM('',(#52),#11);
+ENDSEC;
+END-ISO-10303-21;";
+
+    #[test]
+    fn test_styles() {
+        let flat = StepFile::strip_flatten(DATA);
+        let s = StepFile::parse(&flat);
+        assert_eq!(presentation_style_color(&s, 9), Some(DVec3::new(1.0, 0.0, 0.0)));
+        assert_eq!(presentation_style_opacity(&s, 9), Some(0.5));
+
+        let styles = styles(&s);
+        assert_eq!(styles.len(), 2);
+        assert_eq!(styles[&10], DVec4::new(0.0, 1.0, 0.0, 1.0));
+        assert_eq!(styles[&11], DVec4::new(0.5, 0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_example() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/abstract_pca.step");
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        let s = StepFile::parse(&flat);
+        let styles = styles(&s);
+        assert_eq!(styles.keys().copied().collect::<Vec<_>>(), [47, 256]);
+        assert!((styles[&47] - DVec4::new(0.141176477075, 0.470588237047,
+                                          0.196078434587, 1.0)).norm() < 1e-12);
+    }
+}
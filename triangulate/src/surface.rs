@@ -1,10 +1,16 @@
+use std::collections::HashSet;
 use std::f64::{EPSILON, consts::PI};
 
 use nalgebra_glm as glm;
-use glm::{DVec2, DVec3, DVec4, DMat4};
+use glm::{DVec2, DVec3, DVec4, DMat2, DMat4};
 
 use nurbs::{AbstractSurface, NDBSplineSurface, SampledSurface};
-use crate::{Error, mesh::Vertex};
+use crate::{
+    Error,
+    mesh::Vertex,
+    params::{UvFallback, UvFallbackCounts},
+    split::largest_gap,
+};
 
 // Represents a surface in 3D space, with a function to project a 3D point
 // on the surface down to a 2D space.
@@ -26,6 +32,7 @@ pub enum Surface {
     Cone {
         mat: DMat4,
         mat_i: DMat4,
+        radius: f64,
         angle: f64,
     },
     BSpline(SampledSurface<3>),
@@ -35,6 +42,9 @@ pub enum Surface {
         mat: DMat4,     // uv to world
         mat_i: DMat4,   // world to uv
         radius: f64,
+        // Unit vector from the location to the center of the projection,
+        // chosen by `orient_sphere`.  If unset, `prepare` picks one.
+        center: Option<DVec3>,
     },
     Torus {
         axis: DVec3,
@@ -43,6 +53,9 @@ pub enum Surface {
         mat_i: DMat4,
         major_radius: f64,
         minor_radius: f64,
+        // If set, the face doesn't wrap around the minor axis, so we can use
+        // a (major, minor) rectangle with its seam at this minor angle
+        minor_seam: Option<f64>,
     },
 }
 
@@ -53,6 +66,7 @@ impl Surface {
             mat: DMat4::identity(),
             mat_i: DMat4::identity(),
             location, radius,
+            center: None,
         }
     }
     pub fn new_cylinder(axis: DVec3, ref_direction: DVec3, location: DVec3, radius: f64) -> Self {
@@ -73,7 +87,8 @@ impl Surface {
             // mat and mat_i are built in prepare()
             mat: DMat4::identity(),
             mat_i: DMat4::identity(),
-            location, axis, major_radius, minor_radius
+            location, axis, major_radius, minor_radius,
+            minor_seam: None,
         }
     }
 
@@ -86,12 +101,13 @@ impl Surface {
         }
     }
 
-    pub fn new_cone(axis: DVec3, ref_direction: DVec3, location: DVec3, angle: f64) -> Self {
+    pub fn new_cone(axis: DVec3, ref_direction: DVec3, location: DVec3,
+                    radius: f64, angle: f64) -> Self {
         let mat = Self::make_rigid_transform(axis, ref_direction, location);
         Surface::Cone {
             mat,
             mat_i: mat.try_inverse().expect("Could not invert"),
-            angle,
+            radius, angle,
         }
     }
 
@@ -105,6 +121,18 @@ impl Surface {
         mat
     }
 
+    /// Builds a frame for a sphere's projection, where `center` (a unit
+    /// vector) is the X axis and the other axes are arbitrary
+    fn sphere_frame(location: DVec3, center: DVec3) -> DMat4 {
+        let other = if center.x.abs() < 0.9 {
+            DVec3::x()
+        } else {
+            DVec3::y()
+        };
+        Self::make_rigid_transform(
+            center.cross(&other).normalize(), center, location)
+    }
+
     fn make_rigid_transform(z_world: DVec3, x_world: DVec3, origin_world: DVec3) -> DMat4 {
         let mut mat = DMat4::identity();
         mat.set_column(0, &glm::vec3_to_vec4(&x_world));
@@ -121,6 +149,37 @@ impl Surface {
         surf.uv_from_point(p).ok_or(Error::CouldNotLower)
     }
 
+    /// Returns the major and minor angles of a point on a torus, given the
+    /// torus's world-to-local transform
+    fn torus_angles(mat_i: &DMat4, major_radius: f64, p: DVec3) -> (f64, f64) {
+        let p = mat_i * DVec4::new(p.x, p.y, p.z, 1.0);
+        /*
+                 ^ Y
+                 |
+            /---------\
+           /     |     \
+           |   -----   |
+           |   | O |- -|- - >Z
+           |   -----   |
+           \           /
+            \---------/
+
+            (X axis points into the screen)
+        */
+        let major_angle = p.y.atan2(p.z);
+
+        // Rotate the point so that it's got Y = 0, so we can calculate
+        // the minor angle
+        let z = DVec3::new(0.0, major_angle.sin(), major_angle.cos());
+        let new_mat = Self::make_rigid_transform(
+            z, DVec3::new(1.0, 0.0, 0.0), z * major_radius);
+        let new_mat_i = new_mat.try_inverse()
+            .expect("Could not invert");
+        let new_p = new_mat_i * DVec4::new(p.x, p.y, p.z, 1.0);
+
+        (major_angle, new_p.x.atan2(new_p.z))
+    }
+
     /// Lowers a 3D point on a specific surface into a 2D space defined by
     /// the surface type.  This should only be called from `lower_verts`,
     /// to ensure that `prepare` is called first.
@@ -147,33 +206,14 @@ impl Surface {
                 let scale = 1.0 / (1.0 + z);
                 Ok(DVec2::new(p.x * scale, p.y * scale))
             },
-            Surface::Torus { mat_i, major_radius, minor_radius, .. } => {
-                let p = mat_i * p_;
-                /*
-                         ^ Y
-                         |
-                    /---------\
-                   /     |     \
-                   |   -----   |
-                   |   | O |- -|- - >Z
-                   |   -----   |
-                   \           /
-                    \---------/
-
-                    (X axis points into the screen)
-                */
-                let major_angle = p.y.atan2(p.z);
-
-                // Rotate the point so that it's got Y = 0, so we can calculate
-                // the minor angle
-                let z = DVec3::new(0.0, major_angle.sin(), major_angle.cos());
-                let new_mat = Self::make_rigid_transform(
-                    z, DVec3::new(1.0, 0.0, 0.0), z * *major_radius);
-                let new_mat_i = new_mat.try_inverse()
-                    .expect("Could not invert");
-                let new_p = new_mat_i * DVec4::new(p.x, p.y, p.z, 1.0);
-
-                let minor_angle = new_p.x.atan2(new_p.z);
+            Surface::Torus { mat_i, major_radius, minor_radius, minor_seam, .. } => {
+                let (major_angle, minor_angle) =
+                    Self::torus_angles(mat_i, *major_radius, p);
+                if let Some(seam) = minor_seam {
+                    return Ok(DVec2::new(
+                        -major_angle * *major_radius,
+                        (minor_angle - seam).rem_euclid(2.0 * PI) * *minor_radius));
+                }
 
                 // Construct nested circles with a scale based on the ratio
                 // of radiuses (to make an _attempt_ to match 3D distance)
@@ -221,13 +261,15 @@ impl Surface {
                     }
                 }
             },
-            Surface::Sphere { mat, mat_i, location, .. } => {
-                let ref_direction = (verts[0].pos - *location).normalize();
-                let d1 = (verts.last().unwrap().pos - *location).normalize();
-                let axis = ref_direction.cross(&d1).normalize();
-
-                *mat = Self::make_rigid_transform(
-                        axis, ref_direction, *location);
+            Surface::Sphere { mat, mat_i, location, center, .. } => {
+                *mat = if let Some(c) = center {
+                    Self::sphere_frame(*location, *c)
+                } else {
+                    let ref_direction = (verts[0].pos - *location).normalize();
+                    let d1 = (verts.last().unwrap().pos - *location).normalize();
+                    let axis = ref_direction.cross(&d1).normalize();
+                    Self::make_rigid_transform(axis, ref_direction, *location)
+                };
                 *mat_i = mat
                     .try_inverse()
                     .expect("Could not invert");
@@ -248,14 +290,178 @@ impl Surface {
         }
     }
 
-    pub fn lower_verts(&mut self, verts: &mut [Vertex])
+    /// Returns alternative parameterizations for a face bounded by `verts`,
+    /// beyond the default one chosen by `prepare`
+    fn alternatives(&self, verts: &[Vertex]) -> Vec<Self> {
+        match self {
+            // If the projection center wasn't picked from the boundary's
+            // orientation, then try centering it on the mean direction of
+            // the boundary, which keeps small faces far from the antipode
+            Surface::Sphere { location, radius, center: None, .. } => {
+                let mean_dir = verts.iter()
+                    .map(|v| (v.pos - *location).normalize())
+                    .sum::<DVec3>();
+                if mean_dir.norm() < f64::EPSILON {
+                    return vec![];
+                }
+                let mat = Self::sphere_frame(*location, mean_dir.normalize());
+                vec![Surface::Sphere {
+                    mat,
+                    mat_i: mat.try_inverse().expect("Could not invert"),
+                    location: *location,
+                    radius: *radius,
+                    center: None,
+                }]
+            },
+            // If the face doesn't wrap around the minor axis, then a simple
+            // (major, minor) rectangle is much closer to isometric than the
+            // default nested circles
+            Surface::Torus { mat_i, major_radius, minor_seam: None, .. } => {
+                let mut angles: Vec<f64> = verts.iter()
+                    .map(|v| Self::torus_angles(mat_i, *major_radius, v.pos).1)
+                    .collect();
+                if angles.is_empty() {
+                    return vec![];
+                }
+                let (gap, middle) = largest_gap(&mut angles, 2.0 * PI);
+                // Only use the rectangle if the gap is clearly wider than
+                // the spacing of sampled curves
+                if gap < PI / 8.0 {
+                    return vec![];
+                }
+                let mut out = self.clone();
+                if let Surface::Torus { minor_seam, .. } = &mut out {
+                    *minor_seam = Some(middle);
+                }
+                vec![out]
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Picks a sphere's projection center from the face's boundary loops,
+    /// returning the angular extent of the boundary around that center
+    /// (i.e. the largest angle between the center and a boundary point).
+    ///
+    /// Loops are oriented with the face on their left, when seen from the
+    /// side which the face normal points to.  Their summed vector area then
+    /// points into the face, which keeps the projection's singularity (at
+    /// the antipode of the center) away from it.
+    ///
+    /// Returns `None` for other surfaces, or if the boundary doesn't pick
+    /// out a direction (e.g. a band which is symmetric about the equator).
+    pub fn orient_sphere(&mut self, loops: &[Vec<DVec3>], same_sense: bool)
+        -> Option<f64>
+    {
+        let (location, center) = match self {
+            Surface::Sphere { location, center, .. } => (*location, center),
+            _ => return None,
+        };
+        let mut area = DVec3::zeros();
+        for l in loops {
+            for (a, b) in l.iter().zip(l.iter().cycle().skip(1)) {
+                area += (a - location).cross(&(b - location));
+            }
+        }
+        if !same_sense {
+            area = -area;
+        }
+        let scale = loops.iter().flatten()
+            .map(|p| (p - location).norm_squared())
+            .fold(0.0, f64::max);
+        if area.norm() <= scale * 1e-9 {
+            return None;
+        }
+        let c = area.normalize();
+        *center = Some(c);
+
+        Some(loops.iter().flatten()
+            .map(|p| glm::angle(&(p - location), &c))
+            .fold(0.0, f64::max))
+    }
+
+    /// Estimates how badly lowering distorts the region around `verts`, as
+    /// the worst condition number of the lowering's Jacobian (restricted to
+    /// the tangent plane) at each vertex.  A perfectly conformal
+    /// parameterization scores 1; the score grows without bound near a
+    /// projection singularity, where triangles in 2D become slivers in 3D.
+    ///
+    /// This must only be called after `prepare`, and only samples the
+    /// boundary, since the face's interior isn't known before triangulation.
+    pub fn distortion(&self, verts: &[Vertex]) -> f64 {
+        // Finite difference step, scaled to the size of the face
+        let (lo, hi) = verts.iter().fold(
+            (DVec3::repeat(f64::INFINITY), DVec3::repeat(f64::NEG_INFINITY)),
+            |(lo, hi), v| (lo.inf(&v.pos), hi.sup(&v.pos)));
+        let h = (hi - lo).norm() * 1e-4;
+        if !h.is_finite() || h == 0.0 {
+            return 1.0;
+        }
+
+        let mut worst: f64 = 1.0;
+        for v in verts {
+            let uv = match self.lower(v.pos) {
+                Ok(uv) => uv,
+                Err(_) => return f64::INFINITY,
+            };
+            let n = self.normal(v.pos, uv);
+            if n.norm() < f64::EPSILON || n.iter().any(|c| !c.is_finite()) {
+                continue;
+            }
+            let t1 = n.cross(&if n.x.abs() < 0.9 { DVec3::x() } else { DVec3::y() })
+                .normalize();
+            let t2 = n.cross(&t1);
+
+            let mut jac = DMat2::zeros();
+            for (i, t) in [t1, t2].iter().enumerate() {
+                match (self.lower(v.pos + t * h), self.lower(v.pos - t * h)) {
+                    (Ok(a), Ok(b)) => jac.set_column(i, &((a - b) / (2.0 * h))),
+                    _ => return f64::INFINITY,
+                }
+            }
+            let sv = jac.singular_values();
+            let cond = sv.max() / sv.min();
+            worst = if cond.is_nan() { f64::INFINITY } else { worst.max(cond) };
+        }
+        worst
+    }
+
+    /// Lowers every vertex onto the surface, updating its normal.  Points
+    /// which can't be projected onto a spline surface are retried using
+    /// `fallback`, with recoveries added to `counts`.
+    pub fn lower_verts(&mut self, verts: &mut [Vertex], fallback: UvFallback,
+                       counts: &mut UvFallbackCounts)
         -> Result<Vec<(f64, f64)>, Error>
     {
         self.prepare(verts);
+
+        // If there are other ways to parameterize this face, then pick the
+        // one with the least distortion
+        let alts = self.alternatives(verts);
+        if !alts.is_empty() {
+            let mut best = self.distortion(verts);
+            for a in alts {
+                let d = a.distortion(verts);
+                if d < best {
+                    best = d;
+                    *self = a;
+                }
+            }
+        }
+        // Project to the 2D subspace for triangulation
+        let mut projs: Vec<_> = verts.iter().map(|v| self.lower(v.pos)).collect();
+        if projs.iter().any(Result::is_err) {
+            match self {
+                Surface::BSpline(surf) =>
+                    Self::recover(surf, verts, &mut projs, fallback, counts),
+                Surface::NURBS(surf) =>
+                    Self::recover(surf, verts, &mut projs, fallback, counts),
+                _ => (),
+            }
+        }
         let mut pts = Vec::with_capacity(verts.len());
-        for v in verts {
-            // Project to the 2D subspace for triangulation
-            let proj = self.lower(v.pos)?;
+        for (v, proj) in verts.iter_mut().zip(projs) {
+            let proj = proj?;
             // Update the surface normal
             v.norm = self.normal(v.pos, proj);
             pts.push((proj.x, proj.y));
@@ -277,6 +483,44 @@ impl Surface {
         Ok(pts)
     }
 
+    /// Retries points which couldn't be lowered onto a spline surface,
+    /// trying each stage of `fallback` in turn
+    fn recover<const N: usize>(surf: &SampledSurface<N>, verts: &[Vertex],
+                               projs: &mut [Result<DVec2, Error>],
+                               fallback: UvFallback,
+                               counts: &mut UvFallbackCounts)
+        where NDBSplineSurface<N>: AbstractSurface
+    {
+        if fallback.reseed {
+            // Sweep forwards then backwards, so that a run of failures can
+            // be seeded from either end
+            let n = projs.len();
+            for i in (0..n).chain((0..n).rev()) {
+                if projs[i].is_ok() {
+                    continue;
+                }
+                let uv = [i.wrapping_sub(1), i + 1].iter()
+                    .filter_map(|j| projs.get(*j)?.as_ref().ok().copied())
+                    .find_map(|seed|
+                        surf.uv_from_point_newtons_method(verts[i].pos, seed));
+                if let Some(uv) = uv {
+                    projs[i] = Ok(uv);
+                    counts.reseeded += 1;
+                }
+            }
+        }
+        if fallback.grid > 0 {
+            for (v, proj) in verts.iter().zip(projs.iter_mut()) {
+                if proj.is_err() {
+                    if let Some(uv) = surf.uv_from_point_grid(v.pos, fallback.grid) {
+                        *proj = Ok(uv);
+                        counts.grid_searched += 1;
+                    }
+                }
+            }
+        }
+    }
+
     pub fn raise(&self, uv: DVec2) -> Option<DVec3> {
         match self {
             Surface::Sphere { mat, radius, .. } => {
@@ -298,16 +542,47 @@ impl Surface {
                     .xyz();
                 Some(pos)
             },
+            Surface::Cylinder { mat, radius, z_min, z_max, .. } => {
+                // Invert the scaling in `lower`, which maps the bottom of
+                // the cylinder to the full radius and the top to half of it
+                let r = uv.norm();
+                if r < f64::EPSILON {
+                    return None;
+                }
+                let z = if z_max > z_min {
+                    z_min + (radius / r - 1.0) * (z_max - z_min)
+                } else {
+                    *z_min
+                };
+                let xy = uv * (radius / r);
+                Some((mat * DVec4::new(xy.x, xy.y, z, 1.0)).xyz())
+            },
+            Surface::Cone { mat, radius, angle, .. } => {
+                // `lower` projects along the axis, so the height comes from
+                // the distance to the axis
+                let t = angle.tan();
+                if t.abs() < f64::EPSILON {
+                    return None;
+                }
+                let z = (uv.norm() - radius) / t;
+                Some((mat * DVec4::new(-uv.x, uv.y, z, 1.0)).xyz())
+            },
             Surface::BSpline(s) => Some(s.surf.point(uv)),
             Surface::NURBS(s) => Some(s.surf.point(uv)),
-            Surface::Torus { mat, minor_radius, major_radius, .. } => {
-                let mut uv = uv;
-                if *major_radius > 0.0 {
-                    uv.x *= -1.0;
-                }
-                let minor_angle = uv.y.atan2(uv.x);
-                let major_angle = (uv.norm() - 1.0) /
-                                  (major_radius / minor_radius) * 2.0 * PI - PI;
+            Surface::Torus { mat, minor_radius, major_radius, minor_seam, .. } => {
+                let (major_angle, minor_angle) = if let Some(seam) = minor_seam {
+                    (-uv.x / major_radius, uv.y / minor_radius + seam)
+                } else {
+                    let mut uv = uv;
+                    if *major_radius > 0.0 {
+                        uv.x *= -1.0;
+                    }
+                    (
+                        (uv.norm() - 1.0) / (major_radius / minor_radius)
+                            * 2.0 * PI - PI,
+                        uv.y.atan2(uv.x),
+                    )
+                };
                 let new_p = DVec3::new(minor_angle.sin(), 0.0, minor_angle.cos()) * *minor_radius;
 
                 let z = DVec3::new(0.0, major_angle.sin(), major_angle.cos());
@@ -317,7 +592,7 @@ impl Surface {
 
                 Some((mat * p).xyz())
             },
-            _ => unimplemented!(),
+            Surface::Plane { .. } => unimplemented!(),
         }
     }
 
@@ -333,6 +608,65 @@ impl Surface {
         (xmin, xmax, ymin, ymax)
     }
 
+    /// Measures how far each triangle (given by indices into `pts` and
+    /// `verts`, which are parallel) strays from the surface, as the distance
+    /// along the surface normal between its centroid and the surface point
+    /// at its UV centroid.  Only the normal component is used because the
+    /// UV mapping isn't isometric, so even a triangle which lies on the
+    /// surface may be offset from that point along the surface.
+    ///
+    /// Returns that distance for each triangle which can be measured, along
+    /// with the UV and surface position of the point which should be
+    /// inserted to refine it: the midpoint of its longest edge which isn't
+    /// in `fixed`, since splitting edges (rather than inserting centroids)
+    /// doesn't leave slivers behind.  Points are only inserted inside a
+    /// face, so a triangle with an edge in `fixed` which strays at least as
+    /// far from the surface can't be brought any closer; these have no
+    /// point.  Planes are flat, so they always return nothing.
+    pub fn deviations<I>(&self, pts: &[(f64, f64)], verts: &[Vertex], tris: I,
+                         fixed: &HashSet<(usize, usize)>)
+        -> Vec<(f64, Option<(DVec2, DVec3)>)>
+        where I: Iterator<Item=(usize, usize, usize)>
+    {
+        if let Surface::Plane { .. } = self {
+            return Vec::new();
+        }
+        let uv = |i: usize| DVec2::new(pts[i].0, pts[i].1);
+        let deviation = |uv: DVec2, p: DVec3| {
+            let pos = self.raise(uv)?;
+            Some(((pos - p).dot(&self.normal(pos, uv)).abs(), pos))
+        };
+        tris.filter_map(|(a, b, c)| {
+                let (d, _) = deviation((uv(a) + uv(b) + uv(c)) / 3.0,
+                    (verts[a].pos + verts[b].pos + verts[c].pos) / 3.0)?;
+                let mut free = Vec::new();
+                for (i, j) in [(a, b), (b, c), (c, a)] {
+                    if !fixed.contains(&(i, j)) && !fixed.contains(&(j, i)) {
+                        free.push((i, j));
+                        continue;
+                    }
+                    let mid = deviation((uv(i) + uv(j)) / 2.0,
+                                        (verts[i].pos + verts[j].pos) / 2.0);
+                    if mid.is_some_and(|(e, _)| e >= d) {
+                        return Some((d, None));
+                    }
+                }
+                let split = free.into_iter()
+                    .max_by(|x, y| {
+                        let len = |(i, j): &(usize, usize)|
+                            (verts[*i].pos - verts[*j].pos).norm();
+                        len(x).partial_cmp(&len(y))
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .and_then(|(i, j)| {
+                        let uv = (uv(i) + uv(j)) / 2.0;
+                        Some((uv, self.raise(uv)?))
+                    });
+                Some((d, split))
+            })
+            .collect()
+    }
+
     pub fn add_steiner_points(&self, pts: &mut Vec<(f64, f64)>,
                                      verts: &mut Vec<Vertex>)
     {
@@ -367,9 +701,43 @@ impl Surface {
         where NDBSplineSurface<N>: AbstractSurface
     {
         // Calculate first order derivs, then cross them to get normal
-        let derivs = surf.surf.derivs::<1>(uv);
-        let n = derivs[1][0].cross(&derivs[0][1]);
-        n.normalize()
+        let n = Self::surf_cross(uv, &surf.surf);
+        if let Some(n) = n {
+            return n;
+        }
+
+        // Coincident control points (e.g. a row collapsed into a pole, or a
+        // degenerate edge) make one derivative vanish, so the normal is only
+        // defined as a limit.  Approach it by stepping towards the middle of
+        // the domain, which stays on the surface's side of the pole.
+        let s = &surf.surf;
+        let lo = DVec2::new(s.min_u(), s.min_v());
+        let hi = DVec2::new(s.max_u(), s.max_v());
+        let dir = ((lo + hi) / 2.0 - uv).zip_map(&(hi - lo), |d, r|
+            if d == 0.0 { 0.0 } else { d.signum() * r });
+        for frac in &[1e-7, 1e-5, 1e-3, 1e-2] {
+            if let Some(n) = Self::surf_cross(uv + dir * *frac, s) {
+                return n;
+            }
+        }
+        DVec3::zeros()
+    }
+
+    /// Returns the unit normal at a point on a spline surface, or `None` if
+    /// the partial derivatives are (nearly) parallel or vanish there
+    fn surf_cross<const N: usize>(uv: DVec2, surf: &NDBSplineSurface<N>)
+        -> Option<DVec3>
+        where NDBSplineSurface<N>: AbstractSurface
+    {
+        let derivs = surf.derivs::<1>(uv);
+        let (du, dv) = (derivs[1][0], derivs[0][1]);
+        let n = du.cross(&dv);
+        let scale = du.norm() * dv.norm();
+        if n.norm() > scale * 1e-9 {
+            Some(n.normalize())
+        } else {
+            None
+        }
     }
 
     // Calculate the surface normal, using either the 3D or 2D position
@@ -413,3 +781,140 @@ impl Surface {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_distortion() {
+        // A large cap around the +Z axis, with its boundary 80° from the pole
+        let theta = 80f64.to_radians();
+        let mut verts: Vec<Vertex> = (0..64).map(|i| {
+            let phi = i as f64 / 64.0 * 2.0 * PI;
+            Vertex {
+                pos: DVec3::new(theta.sin() * phi.cos(),
+                                theta.sin() * phi.sin(),
+                                theta.cos()),
+                norm: DVec3::zeros(),
+                color: DVec3::zeros(),
+            }
+        }).collect();
+
+        // Centering the projection on the first vertex puts the far side of
+        // the boundary 160° away, which is badly stretched
+        let mut surf = Surface::new_sphere(DVec3::zeros(), 1.0);
+        surf.prepare(&verts);
+        assert!(surf.distortion(&verts) > 5.0);
+
+        // Lowering should pick the projection centered on the cap instead
+        surf.lower_verts(&mut verts, UvFallback::NONE,
+                         &mut UvFallbackCounts::default()).unwrap();
+        assert!(surf.distortion(&verts) < 1.5);
+        match surf {
+            Surface::Sphere { mat, .. } => {
+                let center = mat.column(0).xyz();
+                assert!((center - DVec3::z()).norm() < 1e-9);
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_collapsed_bspline_normal() {
+        use nurbs::{BSplineSurface, KnotVector};
+        // A bilinear patch whose u = 0 edge is collapsed to a single point,
+        // making a flat triangle in the plane x + y + z = 1
+        let knots = || KnotVector::from_multiplicities(1, &[0.0, 1.0], &[2, 2]);
+        let apex = DVec3::new(0.0, 0.0, 1.0);
+        let surf = BSplineSurface::new(false, false, knots(), knots(), vec![
+            vec![apex, apex],
+            vec![DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0)]]);
+        let surf = Surface::BSpline(SampledSurface::new(surf));
+
+        let expected = DVec3::new(1.0, 1.0, 1.0).normalize();
+        for uv in &[DVec2::new(0.5, 0.5), DVec2::new(0.0, 0.5),
+                    DVec2::new(0.0, 0.0), DVec2::new(0.0, 1.0)]
+        {
+            let n = surf.normal(DVec3::zeros(), *uv);
+            assert!((n - expected).norm() < 1e-6, "{:?} at {:?}", n, uv);
+        }
+    }
+
+    #[test]
+    fn test_uv_fallback() {
+        use nurbs::{BSplineSurface, KnotVector};
+        // A saddle, with points along its diagonal
+        let knots = || KnotVector::from_multiplicities(1, &[0.0, 1.0], &[2, 2]);
+        let surf = SampledSurface::new(BSplineSurface::new(
+            false, false, knots(), knots(), vec![
+                vec![DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 1.0)],
+                vec![DVec3::new(1.0, 0.0, 1.0), DVec3::new(1.0, 1.0, 0.0)]]));
+        let uvs = [0.2, 0.5, 0.8].iter()
+            .map(|t| DVec2::new(*t, *t))
+            .collect::<Vec<_>>();
+        let verts: Vec<Vertex> = uvs.iter().map(|uv| Vertex {
+            pos: surf.surf.point(*uv),
+            norm: DVec3::zeros(),
+            color: DVec3::zeros(),
+        }).collect();
+
+        // Pretend that the middle point failed on the first attempt
+        let run = |fallback| {
+            let mut projs = vec![Ok(uvs[0]), Err(Error::CouldNotLower),
+                                 Ok(uvs[2])];
+            let mut counts = UvFallbackCounts::default();
+            Surface::recover(&surf, &verts, &mut projs, fallback, &mut counts);
+            (projs.remove(1), counts)
+        };
+
+        let (uv, counts) = run(UvFallback::NONE);
+        assert_eq!(uv, Err(Error::CouldNotLower));
+        assert_eq!(counts, UvFallbackCounts::default());
+
+        let (uv, counts) = run(UvFallback::default());
+        assert!((uv.unwrap() - uvs[1]).norm() < 0.05);
+        assert_eq!(counts, UvFallbackCounts { reseeded: 1, grid_searched: 0 });
+
+        let (uv, counts) = run(UvFallback { reseed: false, grid: 8 });
+        assert!((uv.unwrap() - uvs[1]).norm() < 0.05);
+        assert_eq!(counts, UvFallbackCounts { reseeded: 0, grid_searched: 1 });
+    }
+
+    #[test]
+    fn test_cylinder_deviations() {
+        // A triangle spanning 60° of a unit cylinder around the Z axis
+        let mut verts: Vec<Vertex> = [(0.0, 0.0), (60.0, 0.0), (30.0, 1.0)]
+            .iter()
+            .map(|(a, z): &(f64, f64)| Vertex {
+                pos: DVec3::new(a.to_radians().cos(), a.to_radians().sin(), *z),
+                norm: DVec3::zeros(),
+                color: DVec3::zeros(),
+            })
+            .collect();
+        let mut surf = Surface::new_cylinder(DVec3::z(), DVec3::x(),
+                                             DVec3::zeros(), 1.0);
+        let pts = surf.lower_verts(&mut verts, UvFallback::NONE,
+                                   &mut UvFallbackCounts::default()).unwrap();
+        for (p, v) in pts.iter().zip(&verts) {
+            let pos = surf.raise(DVec2::new(p.0, p.1)).unwrap();
+            assert!((pos - v.pos).norm() < 1e-9);
+        }
+
+        // The flat triangle cuts inside the cylinder, and should be split at
+        // a point on the surface
+        let tris = || std::iter::once((0, 1, 2));
+        let devs = surf.deviations(&pts, &verts, tris(), &HashSet::new());
+        assert_eq!(devs.len(), 1);
+        let (d, split) = devs[0];
+        assert!(d > 0.05 && d < 0.15, "{}", d);
+        let (_, pos) = split.unwrap();
+        assert!((pos.xy().norm() - 1.0).abs() < 1e-9);
+
+        // If the chord between the two bottom points is part of the boundary,
+        // splitting the triangle can't get it any closer
+        let fixed = std::iter::once((1, 0)).collect();
+        let devs = surf.deviations(&pts, &verts, tris(), &fixed);
+        assert!(devs[0].1.is_none());
+    }
+}
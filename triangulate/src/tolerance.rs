@@ -0,0 +1,94 @@
+//! Geometric tolerance, as declared by the system which wrote the model.
+//!
+//! Representation contexts usually carry a `GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT`
+//! which gives the distance below which the authoring system treats two
+//! points as the same (e.g. `UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(
+//! 1.E-07),...)`).  Triangulation uses it to drop degenerate boundary edges
+//! and to weld vertices, so that gaps which the authoring system considered
+//! closed are closed in the mesh too.
+use nalgebra_glm::DVec3;
+use step::{ap214::{Entity, MeasureValue}, step_file::StepFile};
+
+/// Returns the smallest positive length uncertainty declared in the file, in
+/// the model's length units, or `None` if there isn't one.  If several
+/// contexts declare different tolerances, the tightest one is used, so that
+/// features which are meaningful in any context survive.
+pub fn model_tolerance(s: &StepFile) -> Option<f64> {
+    s.entities().iter()
+        .filter_map(|e| match e {
+            Entity::UncertaintyMeasureWithUnit(u) => match &u.value_component {
+                MeasureValue::LengthMeasure(m) => Some(m.0),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|d| d.is_finite() && *d > 0.0)
+        .fold(None, |a: Option<f64>, d| Some(a.map_or(d, |a| a.min(d))))
+}
+
+/// Removes points from a closed boundary loop (whose last point repeats the
+/// first) which are within `eps` of the previous point, so that edges which
+/// are shorter than the model's tolerance don't reach the triangulator.  The
+/// loop is closed exactly on its first point afterwards.
+///
+/// Loops which would collapse to fewer than three points are left as they
+/// are, so that they fail (and are reported) in the usual way.  Returns the
+/// number of points removed.
+pub(crate) fn remove_short_edges(pts: &mut Vec<DVec3>, eps: f64) -> usize {
+    if pts.len() < 4 {
+        return 0;
+    }
+    let n = pts.len() - 1;
+    let mut out: Vec<DVec3> = Vec::with_capacity(pts.len());
+    for p in &pts[..n] {
+        if !matches!(out.last(), Some(q) if (p - q).norm() <= eps) {
+            out.push(*p);
+        }
+    }
+    // The loop's final edge runs back to its first point
+    while out.len() > 1 && (out[out.len() - 1] - out[0]).norm() <= eps {
+        out.pop();
+    }
+    if out.len() < 3 {
+        return 0;
+    }
+    let removed = n - out.len();
+    out.push(out[0]);
+    *pts = out;
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_tolerance() {
+        let data = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"),
+                                         "/../examples/cuboid.step"))
+            .unwrap();
+        let flat = StepFile::strip_flatten(&data);
+        assert_eq!(model_tolerance(&StepFile::parse(&flat)), Some(5e-6));
+
+        let flat = StepFile::strip_flatten(b"ISO-10303-21;HEADER;ENDSEC;DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));ENDSEC;END-ISO-10303-21;");
+        assert_eq!(model_tolerance(&StepFile::parse(&flat)), None);
+    }
+
+    #[test]
+    fn test_remove_short_edges() {
+        let v = |x, y| DVec3::new(x, y, 0.0);
+        let mut pts = vec![v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1e-9),
+                           v(1.0, 1.0), v(0.0, 1.0), v(1e-9, 1e-9),
+                           v(0.0, 0.0)];
+        assert_eq!(remove_short_edges(&mut pts, 1e-7), 2);
+        assert_eq!(pts, [v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1.0), v(0.0, 1.0),
+                         v(0.0, 0.0)]);
+
+        // A sliver which would collapse entirely is left alone
+        let mut pts = vec![v(0.0, 0.0), v(1e-9, 0.0), v(0.0, 1e-9),
+                           v(0.0, 0.0)];
+        assert_eq!(remove_short_edges(&mut pts, 1e-7), 0);
+        assert_eq!(pts.len(), 4);
+    }
+}
@@ -1,5 +1,6 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use nalgebra_glm as glm;
 use glm::{DVec3, DVec4, DMat4, U32Vec3};
@@ -7,143 +8,43 @@ use log::{info, warn, error};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
+#[cfg(feature = "rayon")]
+use crate::estimate::estimate_solid;
 
 use step::{
-    ap214, ap214::*, step_file::{FromEntity, StepFile}, id::Id, ap214::Entity,
+    ap214, ap214::*, step_file::StepFile, id::Id, ap214::Entity,
 };
 use crate::{
     Error,
+    assembly::Assembly,
     curve::Curve,
+    diagnostic::{Diagnostic, Severity, panic_message},
     mesh, mesh::{Mesh, Triangle},
+    metrics::{self, Metrics},
+    params::MeshParams,
+    split::split_face,
     stats::Stats,
-    surface::Surface
+    styles::{styles, DEFAULT_COLOR},
+    surface::Surface,
+    tolerance::{model_tolerance, remove_short_edges},
+    units::item_length_units,
+    wireframe::Wireframe,
 };
 use nurbs::{BSplineSurface, SampledCurve, SampledSurface, NURBSSurface, KnotVector};
 
 const SAVE_DEBUG_SVGS: bool = false;
 const SAVE_PANIC_SVGS: bool = false;
 
-/// `TransformStack` is a mapping of representations to transformed children.
-type TransformStack<'a> =
-    HashMap<Representation<'a>, Vec<(Representation<'a>, DMat4)>>;
-fn build_transform_stack<'a>(s: &'a StepFile, flip: bool) -> TransformStack<'a> {
-    // Store a map of parent -> (child, transform)
-    let mut transform_stack: HashMap<_, Vec<_>> = HashMap::new();
-    for r in s.0.iter()
-        .filter_map(|e|
-            RepresentationRelationshipWithTransformation_::try_from_entity(e))
-    {
-        let (a, b) = if flip {
-            (r.rep_2, r.rep_1)
-        } else {
-            (r.rep_1, r.rep_2)
-        };
-        let mut mat = item_defined_transformation(s, r.transformation_operator.cast());
-        if flip {
-            mat = mat.try_inverse().expect("Could not invert transform matrix");
-        }
-
-        transform_stack.entry(b)
-            .or_default()
-            .push((a, mat));
-    }
-    transform_stack
-}
-
-fn transform_stack_roots<'a>(transform_stack: &TransformStack<'a>) -> Vec<Representation<'a>> {
-    let children: HashSet<_> = transform_stack
-        .values()
-        .flat_map(|v| v.iter())
-        .map(|v| v.0)
-        .collect();
-    transform_stack
-        .keys()
-        .filter(|k| !children.contains(k))
-        .copied()
-        .collect()
-}
-
-pub fn triangulate(s: &StepFile) -> (Mesh, Stats) {
-    let styled_items: Vec<_> = s.0.iter()
-        .filter_map(|e| MechanicalDesignGeometricPresentationRepresentation_::try_from_entity(e))
-        .flat_map(|m| m.items.iter())
-        .filter_map(|item| s.entity(item.cast::<StyledItem_>()))
-        .collect();
-    let brep_colors: HashMap<_, DVec3> = styled_items.iter()
-        .filter_map(|styled|
-            if styled.styles.len() != 1 {
-                None
-            } else {
-                presentation_style_color(s, styled.styles[0])
-                    .map(|c| (styled.item, c))
-            })
-        .collect();
-
-    // Store a map of parent -> (child, transform)
-    let mut transform_stack = build_transform_stack(s, false);
-    let mut roots = transform_stack_roots(&transform_stack);
-    // The transformation graph isn't directional (because STEP is a Good File
-    // Format), so if it's got more than one root, assume it's backwards.  We
-    // are assuming that directions in the graph are consistent within the file,
-    // until we find a counterexample.
-    if roots.len() > 1 {
-        info!("Flipping transform stack");
-        transform_stack = build_transform_stack(s, true);
-        roots = transform_stack_roots(&transform_stack);
-    }
-    let mut todo: Vec<_> = roots.into_iter()
-        .map(|v| (v, DMat4::identity()))
-        .collect();
-    if todo.len() > 1 {
-        warn!("Transformation stack has more than one root!");
-    }
-
-    // Store a map of ShapeRepresentationRelationships, which some models
-    // use to map from axes to specific instances
-    let mut shape_rep_relationship: HashMap<Id<_>, Vec<Id<_>>> = HashMap::new();
-    for (r1, r2) in s.0.iter()
-        .filter_map(|e| ShapeRepresentationRelationship_::try_from_entity(e))
-        .map(|e| (e.rep_1, e.rep_2))
-    {
-        shape_rep_relationship.entry(r1).or_default().push(r2);
-    }
-
-    let mut to_mesh: HashMap<Id<_>, Vec<_>> = HashMap::new();
-    while let Some((id, mat)) = todo.pop() {
-        for child in shape_rep_relationship.get(&id).unwrap_or(&vec![]) {
-            todo.push((*child, mat));
-        }
-        if let Some(children) = transform_stack.get(&id) {
-            for (child, next_mat) in children {
-                todo.push((*child, mat * next_mat));
-            }
-        } else {
-            // Bind this transform to the RepresentationItem, which is
-            // either a ManifoldSolidBrep or a ShellBasedSurfaceModel
-            let items = match &s[id] {
-                Entity::AdvancedBrepShapeRepresentation(b) => &b.items,
-                Entity::ShapeRepresentation(b) => &b.items,
-                Entity::ManifoldSurfaceShapeRepresentation(b) => &b.items,
-                e => panic!("Could not get shape from {:?}", e),
-            };
-
-            for m in items.iter() {
-                match &s[*m] {
-                    Entity::ManifoldSolidBrep(_)
-                    | Entity::BrepWithVoids(_)
-                    | Entity::ShellBasedSurfaceModel(_) =>
-                        to_mesh.entry(*m).or_default().push(mat),
-                    Entity::Axis2Placement3d(_) => (),
-                    e => warn!("Skipping {:?}", e),
-                }
-            }
-        }
-    }
+/// Walks the assembly tree, finding every solid (or surface model) to draw
+/// along with the transforms which place its instances in the model.
+pub(crate) fn solid_transforms<'a>(s: &'a StepFile)
+    -> BTreeMap<RepresentationItem<'a>, Vec<DMat4>>
+{
+    let mut to_mesh = Assembly::new(s).solids(s);
     // If there are items in breps that aren't attached to a transformation
     // chain, then draw them individually (with an identity matrix)
     if to_mesh.is_empty() {
-        s.0.iter()
-            .enumerate()
+        s.iter_by_id()
             .filter(|(_i, e)|
                 match e {
                     Entity::ManifoldSolidBrep(_)
@@ -155,154 +56,407 @@ pub fn triangulate(s: &StepFile) -> (Mesh, Stats) {
             .map(|(i, _e)| Id::new(i))
             .for_each(|i| to_mesh.entry(i).or_default().push(DMat4::identity()));
     }
+    to_mesh
+}
+
+pub fn triangulate(s: &StepFile) -> (Mesh, Stats) {
+    triangulate_with_metrics(s, &mut ())
+}
+
+/// Triangulates every solid in the file, like [`triangulate`], reporting
+/// counts and timing to the given [`Metrics`] sink
+pub fn triangulate_with_metrics(s: &StepFile, m: &mut dyn Metrics)
+    -> (Mesh, Stats)
+{
+    triangulate_timed(s, solid_transforms(s), &MeshParams::default(), m)
+}
+
+/// Triangulates every solid in the file (or only those listed in `solids`,
+/// like [`triangulate_solids`]), using `tolerance` instead of the model's
+/// own uncertainty (see [`model_tolerance`]) when dropping short edges and
+/// welding vertices.
+pub fn triangulate_with_tolerance(s: &StepFile, solids: Option<&[usize]>,
+                                  tolerance: f64, m: &mut dyn Metrics)
+    -> (Mesh, Stats)
+{
+    let params = MeshParams { tolerance: Some(tolerance), ..Default::default() };
+    triangulate_with_params(s, solids, &params, m)
+}
+
+/// Triangulates every solid in the file (or only those listed in `solids`,
+/// like [`triangulate_solids`]), with the given options
+pub fn triangulate_with_params(s: &StepFile, solids: Option<&[usize]>,
+                               params: &MeshParams, m: &mut dyn Metrics)
+    -> (Mesh, Stats)
+{
+    let to_mesh = match solids {
+        Some(solids) => placed_solids(s, solids),
+        None => solid_transforms(s),
+    };
+    triangulate_timed(s, to_mesh, params, m)
+}
+
+/// Triangulates a subset of the file's solids (or surface models), given by
+/// entity ID.  Each solid is placed at every instance found in the file, so
+/// the output matches the corresponding part of [`triangulate`]'s mesh, but
+/// solids which aren't listed are skipped without any surface evaluation.
+///
+/// IDs which don't refer to a placed solid are skipped with a warning.
+pub fn triangulate_solids(s: &StepFile, solids: &[usize]) -> (Mesh, Stats) {
+    triangulate_solids_with_metrics(s, solids, &mut ())
+}
+
+/// Triangulates a subset of the file's solids, like [`triangulate_solids`],
+/// reporting counts and timing to the given [`Metrics`] sink
+pub fn triangulate_solids_with_metrics(s: &StepFile, solids: &[usize],
+                                       m: &mut dyn Metrics) -> (Mesh, Stats)
+{
+    triangulate_timed(s, placed_solids(s, solids), &MeshParams::default(), m)
+}
+
+/// Picks out the listed solids (by entity ID) from [`solid_transforms`]
+fn placed_solids<'a>(s: &'a StepFile, solids: &[usize])
+    -> BTreeMap<RepresentationItem<'a>, Vec<DMat4>>
+{
+    let keep: BTreeSet<usize> = solids.iter().copied().collect();
+    let mut to_mesh = solid_transforms(s);
+    to_mesh.retain(|id, _| keep.contains(&id.0));
+    for id in keep.iter().filter(|i| !to_mesh.contains_key(&Id::new(**i))) {
+        warn!("Skipping #{} (not a placed solid)", id);
+    }
+    to_mesh
+}
+
+/// Triangulates the solids drawn by an [`Assembly`], which may have been
+/// re-rooted, baked, or otherwise edited after being built from this file
+pub fn triangulate_assembly(s: &StepFile, a: &Assembly) -> (Mesh, Stats) {
+    triangulate_timed(s, a.solids(s), &MeshParams::default(), &mut ())
+}
+
+fn triangulate_timed(s: &StepFile,
+                     to_mesh: BTreeMap<RepresentationItem, Vec<DMat4>>,
+                     params: &MeshParams,
+                     m: &mut dyn Metrics) -> (Mesh, Stats)
+{
+    let (mesh, stats) = metrics::timed(
+        m, metrics::TRIANGULATE_TIME,
+        || triangulate_inner(s, to_mesh, params));
+    m.counter(metrics::ENTITIES, s.iter_by_id().count() as u64);
+    m.counter(metrics::TRIANGLES, mesh.triangles.len() as u64);
+    stats.report(m);
+    (mesh, stats)
+}
+
+fn triangulate_inner(s: &StepFile,
+                     to_mesh: BTreeMap<RepresentationItem, Vec<DMat4>>,
+                     params: &MeshParams)
+    -> (Mesh, Stats)
+{
+    let (mesh, stats, _) = triangulate_reusing(s, &to_mesh, params,
+                                               |_| None);
+    (mesh, stats)
+}
+
+/// A solid's triangulation in its own coordinates, before it's placed and
+/// colored
+pub(crate) type LocalSolid = Arc<(Mesh, Stats)>;
+
+/// Triangulates and places each solid in `to_mesh`.  Solids for which
+/// `known` returns a triangulation are placed without being triangulated
+/// again.  Returns the local triangulation of every solid (in ID order), so
+/// that they can be reused later.
+///
+/// `params.tolerance` overrides the model's own uncertainty; if neither is
+/// given, only exactly coincident points are treated as the same.
+pub(crate) fn triangulate_reusing<'a, F>(
+    s: &'a StepFile, to_mesh: &BTreeMap<RepresentationItem<'a>, Vec<DMat4>>,
+    params: &MeshParams, known: F)
+    -> (Mesh, Stats, Vec<(RepresentationItem<'a>, LocalSolid)>)
+    where F: Fn(RepresentationItem) -> Option<LocalSolid> + Sync
+{
+    profile_scope!("triangulate");
+    let eps = params.tolerance.or_else(|| model_tolerance(s)).unwrap_or(0.0);
+    info!("tolerance: {:e}", eps);
+
+    let styles = styles(s);
+
+    // Scale factors into the requested length unit, by solid
+    let scales = match params.length_unit {
+        Some(target) => item_length_units(s).into_iter()
+            .map(|(id, f)| (id, f / target))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    let local = |id: &RepresentationItem|
+        known(*id).unwrap_or_else(|| Arc::new(
+            triangulate_solid(s, *id, eps, params)));
+    let locals: Vec<(RepresentationItem, LocalSolid)> = {
+        #[cfg(feature = "rayon")]
+        {
+            // Hand out solids longest-first, so that a single giant solid
+            // doesn't start last and keep one core busy after the rest of
+            // the pool has finished.  `par_bridge` pulls from the iterator
+            // in order as threads become free.
+            let mut order: Vec<_> = to_mesh.iter()
+                .map(|(id, mats)| (estimate_solid(s, *id, mats.len()).cost,
+                                   id))
+                .collect();
+            order.sort_by(|a, b| b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.1.cmp(b.1)));
+            let mut locals: Vec<_> = order.into_iter()
+                .par_bridge()
+                .map(|(_, id)| (*id, local(id)))
+                .collect();
+            // Place in ID order, so the output doesn't depend on scheduling
+            locals.sort_by_key(|(id, _)| *id);
+            locals
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            to_mesh.keys().map(|id| (*id, local(id))).collect()
+        }
+    };
+
+    let mut mesh = Mesh::default();
+    let mut stats = Stats::default();
+    for ((id, local), mats) in locals.iter().zip(to_mesh.values()) {
+        let (solid, solid_stats) = local.as_ref();
+        stats.num_shells += solid_stats.num_shells;
+        stats.num_faces += solid_stats.num_faces;
+        stats.num_errors += solid_stats.num_errors;
+        stats.num_panics += solid_stats.num_panics;
+        stats.uv_fallbacks += solid_stats.uv_fallbacks;
+        stats.refinement += solid_stats.refinement;
+        if !is_solid(&s[*id]) {
+            stats.diagnostics.extend(solid_stats.diagnostics.iter().cloned());
+            continue;
+        }
+
+        let scale = scales.get(&id.0).copied();
+        let place = |mat: &DMat4| match scale {
+            Some(k) => glm::scaling(&DVec3::new(k, k, k)) * mat,
+            None => *mat,
+        };
+
+        // Problems are reported at the solid's first instance
+        for d in &solid_stats.diagnostics {
+            let mut d = d.clone();
+            d.transform(&place(&mats[0]));
+            stats.diagnostics.push(d);
+        }
+
+        // Pick out a color from the color map and apply it to each
+        // placed vertex
+        let style = styles.get(&id.0);
+        let color = style.map_or(DEFAULT_COLOR, |c| c.xyz());
+
+        // Each instance is its own part, since they may be far apart
+        let opacity = style.map_or(1.0, |c| c.w);
+        for mat in mats {
+            let v_start = mesh.verts.len();
+            let t_start = mesh.triangles.len();
+            for v in &solid.verts {
+                let p = v.pos;
+                let p_h = DVec4::new(p.x, p.y, p.z, 1.0);
+                let pos = (mat * p_h).xyz();
+                let pos = scale.map_or(pos, |k| pos * k);
+                let norm = (mat * glm::vec3_to_vec4(&v.norm)).xyz();
+                mesh.verts.push(mesh::Vertex { pos, norm, color });
+            }
+            for t in &solid.triangles {
+                let mut tri = *t;
+                tri.verts.add_scalar_mut(v_start as u32);
+                mesh.triangles.push(tri);
+            }
+            let triangles = t_start..mesh.triangles.len();
+            mesh.parts.push(mesh::Part {
+                id: id.0, bounds: mesh.bounds(triangles.clone()), triangles,
+                opacity, metadata: BTreeMap::new(),
+            });
+        }
+    }
+
+    info!("num_shells: {}", stats.num_shells);
+    info!("num_faces: {}", stats.num_faces);
+    info!("num_errors: {}", stats.num_errors);
+    info!("num_panics: {}", stats.num_panics);
+    (mesh, stats, locals)
+}
+
+fn is_solid(e: &Entity) -> bool {
+    matches!(e, Entity::ManifoldSolidBrep(_)
+              | Entity::BrepWithVoids(_)
+              | Entity::ShellBasedSurfaceModel(_))
+}
+
+/// Triangulates a single solid (or surface model), in its own coordinates.
+/// Boundary edges shorter than `eps` are dropped, and vertices within `eps`
+/// of each other are welded together.
+fn triangulate_solid(s: &StepFile, id: RepresentationItem, eps: f64,
+                     params: &MeshParams) -> (Mesh, Stats)
+{
+    profile_scope!("triangulate_solid", format!("#{}", id.0).as_str());
+    let mut mesh = Mesh::default();
+    let mut stats = Stats::default();
+    match &s[id] {
+        Entity::ManifoldSolidBrep(b) =>
+            closed_shell(s, b.outer, eps, params, &mut mesh, &mut stats),
+        Entity::ShellBasedSurfaceModel(b) =>
+            for v in &b.sbsm_boundary {
+                shell(s, *v, eps, params, &mut mesh, &mut stats);
+            },
+        Entity::BrepWithVoids(b) =>
+            // TODO: handle voids
+            closed_shell(s, b.outer, eps, params, &mut mesh, &mut stats),
+        _ => {
+            warn!("Skipping {:?} (not a known solid)", s[id]);
+            stats.diagnostics.push(Diagnostic::new(Severity::Skipped,
+                id.0, "not a known solid".to_owned()));
+        },
+    };
+    let removed = mesh.weld(eps);
+    if removed > 0 {
+        info!("Removed {} collapsed triangles from #{}", removed, id.0);
+    }
+    (mesh, stats)
+}
+
+/// Tessellates every edge curve in the model's solids, without triangulating
+/// any faces.  This is much cheaper than [`triangulate`], so it's useful for
+/// quickly inspecting very large models.
+///
+/// Edges which can't be found or tessellated (including those which panic)
+/// are skipped, and counted in the returned [`Stats`] along with a
+/// diagnostic for each.
+pub fn wireframe(s: &StepFile) -> (Wireframe, Stats) {
+    let to_mesh = solid_transforms(s);
 
     let (to_mesh_iter, empty) = {
         #[cfg(feature = "rayon")]
-        { (to_mesh.par_iter(), || (Mesh::default(), Stats::default())) }
+        { (to_mesh.par_iter(), || (Wireframe::default(), Stats::default())) }
         #[cfg(not(feature = "rayon"))]
-        { (to_mesh.iter(), (Mesh::default(), Stats::default())) }
+        { (to_mesh.iter(), (Wireframe::default(), Stats::default())) }
     };
-    let mesh_fold = to_mesh_iter
+    let wire_fold = to_mesh_iter
         .fold(
-            // Empty constructor
             empty,
-
-            // Fold operation
-            |(mut mesh, mut stats), (id, mats)| {
-                let v_start = mesh.verts.len();
-                let t_start = mesh.triangles.len();
-                match &s[*id] {
-                    Entity::ManifoldSolidBrep(b) =>
-                        closed_shell(s, b.outer, &mut mesh, &mut stats),
-                    Entity::ShellBasedSurfaceModel(b) =>
-                        for v in &b.sbsm_boundary {
-                            shell(s, *v, &mut mesh, &mut stats);
-                        },
-                    Entity::BrepWithVoids(b) =>
-                        // TODO: handle voids
-                        closed_shell(s, b.outer, &mut mesh, &mut stats),
+            |(mut wire, mut stats), (id, mats)| {
+                let shells = match &s[*id] {
+                    Entity::ManifoldSolidBrep(b) => vec![b.outer.cast()],
+                    Entity::BrepWithVoids(b) => vec![b.outer.cast()],
+                    Entity::ShellBasedSurfaceModel(b) => b.sbsm_boundary.clone(),
                     _ => {
                         warn!("Skipping {:?} (not a known solid)", s[*id]);
-                        return (mesh, stats);
+                        return (wire, stats);
                     },
                 };
 
-                // Pick out a color from the color map and apply it to each
-                // newly-created vertex
-                let color = brep_colors.get(id)
-                    .map(|c| *c)
-                    .unwrap_or(DVec3::new(0.5, 0.5, 0.5));
-
-                // Build copies of the mesh by copying and applying transforms
-                let v_end = mesh.verts.len();
-                let t_end = mesh.triangles.len();
-                for mat in &mats[1..] {
-                    for v in v_start..v_end {
-                        let p = mesh.verts[v].pos;
-                        let p_h = DVec4::new(p.x, p.y, p.z, 1.0);
-                        let pos = (mat * p_h).xyz();
-
-                        let n = mesh.verts[v].norm;
-                        let norm = (mat * glm::vec3_to_vec4(&n)).xyz();
-
-                        mesh.verts.push(mesh::Vertex { pos, norm, color });
-                    }
-                    let offset = mesh.verts.len() - v_end;
-                    for t in t_start..t_end {
-                        let mut tri = mesh.triangles[t];
-                        tri.verts.add_scalar_mut(offset as u32);
-                        mesh.triangles.push(tri);
+                // Each edge is shared by (at least) two faces, so collect
+                // them into a set before tessellating.
+                let mut edges = BTreeSet::new();
+                for shell in shells {
+                    let faces = match &s[shell] {
+                        Entity::ClosedShell(c) => &c.cfs_faces,
+                        Entity::OpenShell(c) => &c.cfs_faces,
+                        h => {
+                            warn!("Skipping {:?} (unknown Shell type)", h);
+                            continue;
+                        },
+                    };
+                    stats.num_shells += 1;
+                    for f in faces {
+                        stats.num_faces += 1;
+                        let face = match s.entity(f.cast::<AdvancedFace_>()) {
+                            Some(face) => face,
+                            None => {
+                                skip_edges(&mut stats, f.0,
+                                           format!("not a face: {:?}", s[*f]));
+                                continue;
+                            },
+                        };
+                        for b in &face.bounds {
+                            let bound = match &s[*b] {
+                                Entity::FaceBound(b) => b.bound,
+                                Entity::FaceOuterBound(b) => b.bound,
+                                e => {
+                                    skip_edges(&mut stats, b.0,
+                                               format!("not a bound: {:?}", e));
+                                    continue;
+                                },
+                            };
+                            // Vertex loops (e.g. the tips of cones) have no
+                            // edges, so they're skipped here.
+                            let e = match &s[bound] {
+                                Entity::EdgeLoop(e) => e,
+                                _ => continue,
+                            };
+                            for o in &e.edge_list {
+                                let edge = s.entity(*o)
+                                    .map(|o| o.edge_element.cast::<EdgeCurve_>())
+                                    .filter(|e| s.entity(*e).is_some());
+                                match edge {
+                                    Some(edge) => { edges.insert(edge); },
+                                    None => skip_edges(&mut stats, o.0,
+                                        "not an oriented edge of an edge curve"
+                                            .to_owned()),
+                                }
+                            }
+                        }
                     }
                 }
 
-                // Now that we've built all of the other copies of the mesh,
-                // re-use the original mesh and apply the first transform
-                let mat = mats[0];
-                for v in v_start..v_end {
-                    let p = mesh.verts[v].pos;
-                    let p_h = DVec4::new(p.x, p.y, p.z, 1.0);
-                    mesh.verts[v].pos = (mat * p_h).xyz();
-
-                    let n = mesh.verts[v].norm;
-                    mesh.verts[v].norm = (mat * glm::vec3_to_vec4(&n)).xyz();
-
-                    mesh.verts[v].color = color;
+                for e in edges {
+                    let pts = match std::panic::catch_unwind(
+                        std::panic::AssertUnwindSafe(|| edge_curve(s, e, true)))
+                    {
+                        Ok(Ok(pts)) => pts,
+                        Ok(Err(err)) => {
+                            skip_edges(&mut stats, e.0,
+                                       format!("failed to tessellate: {}", err));
+                            continue;
+                        },
+                        Err(p) => {
+                            error!("Got panic while tessellating #{}", e.0);
+                            stats.num_panics += 1;
+                            stats.diagnostics.push(Diagnostic::new(
+                                Severity::Panic, e.0, panic_message(&*p)));
+                            continue;
+                        },
+                    };
+                    for mat in mats {
+                        let pts: Vec<DVec3> = pts.iter()
+                            .map(|p| (mat * DVec4::new(p.x, p.y, p.z, 1.0)).xyz())
+                            .collect();
+                        wire.push_polyline(&pts);
+                    }
                 }
-                (mesh, stats)
+                (wire, stats)
             });
 
-    let (mesh, stats) = {
+    let (wire, stats) = {
         #[cfg(feature = "rayon")]
-        { mesh_fold.reduce(empty,
-                |a, b| (Mesh::combine(a.0, b.0), Stats::combine(a.1, b.1))) }
+        { wire_fold.reduce(empty,
+                |a, b| (Wireframe::combine(a.0, b.0), Stats::combine(a.1, b.1))) }
         #[cfg(not(feature = "rayon"))]
         {
-            mesh_fold
+            wire_fold
         }
     };
 
     info!("num_shells: {}", stats.num_shells);
     info!("num_faces: {}", stats.num_faces);
     info!("num_errors: {}", stats.num_errors);
-    info!("num_panics: {}", stats.num_panics);
-    (mesh, stats)
+    (wire, stats)
 }
 
-fn item_defined_transformation(s: &StepFile, t: Id<ItemDefinedTransformation_>) -> DMat4 {
-    let i = s.entity(t).expect("Could not get ItemDefinedTransform");
-
-    let (location, axis, ref_direction) = axis2_placement_3d(s,
-        i.transform_item_1.cast());
-    let t1 = Surface::make_affine_transform(axis,
-        ref_direction,
-        axis.cross(&ref_direction),
-        location);
-
-    let (location, axis, ref_direction) = axis2_placement_3d(s,
-        i.transform_item_2.cast());
-    let t2 = Surface::make_affine_transform(axis,
-        ref_direction,
-        axis.cross(&ref_direction),
-        location);
-
-    t2 * t1.try_inverse().expect("Could not invert transform matrix")
-}
-
-fn presentation_style_color(s: &StepFile, p: PresentationStyleAssignment)
-    -> Option<DVec3>
-{
-    // AAAAAHHHHH
-    s.entity(p)
-        .and_then(|p: &PresentationStyleAssignment_| {
-                let mut surf = p.styles.iter().filter_map(|y| {
-                    // This is an ambiguous parse, so we hard-code the first
-                    // Entity item in the enum
-                    use PresentationStyleSelect::PreDefinedPresentationStyle;
-                    if let PreDefinedPresentationStyle(u) = y {
-                        s.entity(u.cast::<SurfaceStyleUsage_>())
-                    } else {
-                        None
-                    }});
-                let out = surf.next();
-                out
-            })
-        .and_then(|surf: &SurfaceStyleUsage_|
-            s.entity(surf.style.cast::<SurfaceSideStyle_>()))
-        .and_then(|surf: &SurfaceSideStyle_| if surf.styles.len() != 1 {
-                None
-            } else {
-                s.entity(surf.styles[0].cast::<SurfaceStyleFillArea_>())
-            })
-        .map(|surf: &SurfaceStyleFillArea_|
-            s.entity(surf.fill_area).expect("Could not get fill_area"))
-        .and_then(|fill: &FillAreaStyle_| if fill.fill_styles.len() != 1 {
-                None
-            } else {
-                s.entity(fill.fill_styles[0].cast::<FillAreaStyleColour_>())
-            })
-        .and_then(|f: &FillAreaStyleColour_|
-            s.entity(f.fill_colour.cast::<ColourRgb_>()))
-        .map(|c| DVec3::new(c.red, c.green, c.blue))
+/// Records an entity which was skipped while collecting or tessellating
+/// edges for [`wireframe`], so that one bad edge doesn't stop the export
+fn skip_edges(stats: &mut Stats, id: usize, message: String) {
+    error!("Skipping #{}: {}", id, message);
+    stats.num_errors += 1;
+    stats.diagnostics.push(Diagnostic::new(Severity::Error, id, message));
 }
 
 fn cartesian_point(s: &StepFile, a: Id<CartesianPoint_>) -> DVec3 {
@@ -317,55 +471,123 @@ fn direction(s: &StepFile, a: Direction) -> DVec3 {
                p.direction_ratios[2])
 }
 
-fn axis2_placement_3d(s: &StepFile, t: Id<Axis2Placement3d_>) -> (DVec3, DVec3, DVec3) {
+pub(crate) fn axis2_placement_3d(s: &StepFile, t: Id<Axis2Placement3d_>) -> (DVec3, DVec3, DVec3) {
     let a = s.entity(t).expect("Could not get Axis2Placement3d");
     let location = cartesian_point(s, a.location);
     // TODO: this doesn't necessarily match the behavior of `build_axes`
-    let axis = direction(s, a.axis.expect("Missing axis"));
-    let ref_direction = match a.ref_direction {
+    let axis = match a.axis.get() {
+        None => DVec3::new(0.0, 0.0, 1.0),
+        Some(r) => direction(s, *r),
+    };
+    let ref_direction = match a.ref_direction.get() {
         None => DVec3::new(1.0, 0.0, 0.0),
-        Some(r) => direction(s, r),
+        Some(r) => direction(s, *r),
     };
     (location, axis, ref_direction)
 }
 
-fn shell(s: &StepFile, c: Shell, mesh: &mut Mesh, stats: &mut Stats) {
+fn shell(s: &StepFile, c: Shell, eps: f64, params: &MeshParams,
+         mesh: &mut Mesh, stats: &mut Stats)
+{
     match &s[c] {
-        Entity::ClosedShell(_) => closed_shell(s, c.cast(), mesh, stats),
-        Entity::OpenShell(_) => open_shell(s, c.cast(), mesh, stats),
-        h => warn!("Skipping {:?} (unknown Shell type)", h),
+        Entity::ClosedShell(_) => closed_shell(s, c.cast(), eps, params, mesh, stats),
+        Entity::OpenShell(_) => open_shell(s, c.cast(), eps, params, mesh, stats),
+        h => {
+            warn!("Skipping {:?} (unknown Shell type)", h);
+            stats.diagnostics.push(Diagnostic::new(Severity::Skipped, c.0,
+                "unknown shell type".to_owned()));
+        },
     }
 }
 
-fn open_shell(s: &StepFile, c: OpenShell, mesh: &mut Mesh, stats: &mut Stats) {
+fn open_shell(s: &StepFile, c: OpenShell, eps: f64, params: &MeshParams,
+              mesh: &mut Mesh, stats: &mut Stats)
+{
     let cs = s.entity(c).expect("Could not get OpenShell");
     for face in &cs.cfs_faces {
-        if let Err(err) = advanced_face(s, face.cast(), mesh, stats) {
+        if let Err(err) = advanced_face(s, face.cast(), eps, params, mesh, stats) {
             error!("Failed to triangulate {:?}: {}", s[*face], err);
+            stats.diagnostics.push(face_error(s, face.cast(), &err));
         }
     }
     stats.num_shells += 1;
 }
 
-fn closed_shell(s: &StepFile, c: ClosedShell, mesh: &mut Mesh, stats: &mut Stats) {
+fn closed_shell(s: &StepFile, c: ClosedShell, eps: f64,
+                params: &MeshParams, mesh: &mut Mesh, stats: &mut Stats)
+{
     let cs = s.entity(c).expect("Could not get ClosedShell");
     for face in &cs.cfs_faces {
-        if let Err(err) = advanced_face(s, face.cast(), mesh, stats) {
+        if let Err(err) = advanced_face(s, face.cast(), eps, params, mesh, stats) {
             error!("Failed to triangulate {:?}: {}", s[*face], err);
+            stats.diagnostics.push(face_error(s, face.cast(), &err));
         }
     }
     stats.num_shells += 1;
 }
 
-fn advanced_face(s: &StepFile, f: AdvancedFace, mesh: &mut Mesh,
-                 stats: &mut Stats) -> Result<(), Error>
+/// Builds a diagnostic for a face which couldn't be triangulated, located
+/// at the vertices of its boundary
+fn face_error(s: &StepFile, f: AdvancedFace, err: &Error) -> Diagnostic {
+    let mut pts = Vec::new();
+    for b in s.entity(f).iter().flat_map(|f| f.bounds.iter()) {
+        let bound = match &s[*b] {
+            Entity::FaceBound(b) => b.bound,
+            Entity::FaceOuterBound(b) => b.bound,
+            _ => continue,
+        };
+        let edges = match &s[bound] {
+            Entity::EdgeLoop(e) => &e.edge_list,
+            _ => continue,
+        };
+        // This is an error path, so it must not panic on malformed entities
+        pts.extend(edges.iter()
+            .filter_map(|e| s.entity(*e))
+            .filter_map(|e| s.entity(e.edge_element.cast::<EdgeCurve_>()))
+            .filter_map(|e| s.entity(e.edge_start.cast::<VertexPoint_>()))
+            .filter_map(|v| s.entity(v.vertex_geometry.cast::<CartesianPoint_>()))
+            .map(|p| DVec3::new(p.coordinates[0].0, p.coordinates[1].0,
+                                p.coordinates[2].0)));
+    }
+    Diagnostic::new(Severity::Error, f.0, err.to_string()).with_points(&pts)
+}
+
+fn advanced_face(s: &StepFile, f: AdvancedFace, eps: f64,
+                 params: &MeshParams, mesh: &mut Mesh, stats: &mut Stats)
+    -> Result<(), Error>
 {
+    profile_scope!("advanced_face", format!("#{}", f.0).as_str());
     let face = s.entity(f).expect("Could not get AdvancedFace");
     stats.num_faces += 1;
 
     // Grab the surface, returning early if it's unimplemented
     let mut surf = get_surface(s, face.face_geometry)?;
 
+    let mut loops = Vec::new();
+    for b in &face.bounds {
+        let mut pts = face_bound(s, *b)?;
+        remove_short_edges(&mut pts, eps);
+        loops.push(pts);
+    }
+
+    // Faces which wrap too far around their surface are cut in half, which
+    // avoids singularities and seams when lowering
+    if let Some(parts) = split_face(&mut surf, &loops, face.same_sense) {
+        for (surf, loops) in parts {
+            triangulate_loops(f, face, surf, &loops, params, mesh, stats)?;
+        }
+        return Ok(());
+    }
+    triangulate_loops(f, face, surf, &loops, params, mesh, stats)
+}
+
+/// Lowers a set of boundary loops (from `face_bound`) onto the surface, then
+/// triangulates the region that they enclose
+fn triangulate_loops(f: AdvancedFace, face: &AdvancedFace_, mut surf: Surface,
+                     loops: &[Vec<DVec3>], params: &MeshParams,
+                     mesh: &mut Mesh, stats: &mut Stats)
+    -> Result<(), Error>
+{
     // This is the starting point at which we insert new vertices
     let offset = mesh.verts.len();
 
@@ -374,9 +596,7 @@ fn advanced_face(s: &StepFile, f: AdvancedFace, mesh: &mut Mesh,
     let mut edges = Vec::new();
     let v_start = mesh.verts.len();
     let mut num_pts = 0;
-    for b in &face.bounds {
-        let bound_contours = face_bound(s, *b)?;
-
+    for bound_contours in loops {
         match bound_contours.len() {
             // We should always have non-zero items in the contour
             0 => panic!("Got empty contours for {:?}", face),
@@ -397,7 +617,7 @@ fn advanced_face(s: &StepFile, f: AdvancedFace, mesh: &mut Mesh,
             _ => {
                 // Record the initial point to close the loop
                 let start = num_pts;
-                for pt in bound_contours {
+                for pt in bound_contours.iter().cloned() {
                     // The contour marches forward!
                     edges.push((num_pts, num_pts + 1));
 
@@ -427,51 +647,86 @@ fn advanced_face(s: &StepFile, f: AdvancedFace, mesh: &mut Mesh,
     // _fail_ due to these points, so if that happens, we nuke the point (by
     // assigning it to the first point in the list, which causes it to get
     // deduplicated), then retry.
-    let mut pts = surf.lower_verts(&mut mesh.verts[v_start..])?;
+    let mut pts = {
+        profile_scope!("lower_verts");
+        surf.lower_verts(&mut mesh.verts[v_start..], params.uv_fallback,
+                         &mut stats.uv_fallbacks)?
+    };
     let bonus_points = pts.len();
-    surf.add_steiner_points(&mut pts, &mut mesh.verts);
-    let result = std::panic::catch_unwind(|| {
-        // TODO: this is only needed because we use pts below to save a debug
-        // SVG if this panics.  Once we're confident in never panicking, we
-        // can remove this.
-        let mut pts = pts.clone();
-        loop {
-            let mut t = match cdt::Triangulation::new_with_edges(&pts, &edges) {
-                Err(e) => break Err(e),
-                Ok(t) => t,
-            };
-            match t.run() {
-                Ok(()) => break Ok(t),
-                // If triangulation failed due to a Steiner point on a fixed
-                // edge, then reassign that point to pts[0] (so it will be
-                // ignored as a duplicate)
-                Err(cdt::Error::PointOnFixedEdge(p)) if p >= bonus_points => {
-                    pts[p] = pts[0];
-                    continue;
-                },
-                Err(e) => {
-                    if SAVE_DEBUG_SVGS {
-                        let filename = format!("err{}.svg", face.face_geometry.0);
-                        t.save_debug_svg(&filename)
-                            .expect("Could not save debug SVG");
-                    }
-                    break Err(e)
-                },
+    if params.refine.is_none() {
+        surf.add_steiner_points(&mut pts, &mut mesh.verts);
+    }
+    let mut result = run_cdt(&mut pts, &mut mesh.verts[v_start..], &edges,
+                             bonus_points, face);
+
+    // If refinement is enabled, insert points where triangles stray from
+    // the surface, then re-triangulate, until every triangle is within
+    // tolerance or the budget runs out.
+    if let Some(r) = params.refine {
+        profile_scope!("refine");
+        let fixed: HashSet<_> = edges.iter().copied().collect();
+        let (mut passes, mut added) = (0, 0);
+        while let Ok(Ok(t)) = &result {
+            let mut devs = surf.deviations(&pts, &mesh.verts[v_start..],
+                                           t.triangles(), &fixed);
+            let max = devs.iter().map(|d| d.0).fold(0.0, f64::max);
+            let done = devs.iter().all(|d| d.0 <= r.tolerance || d.1.is_none());
+            if done || passes == r.max_passes || added == r.max_points {
+                stats.refinement.over_budget += !done as usize;
+                stats.refinement.max_deviation =
+                    stats.refinement.max_deviation.max(max);
+                break;
+            }
+
+            // Fix the worst triangles first, in case the budget runs out.
+            // Neighbouring triangles may split the same edge, so points
+            // are deduplicated.
+            devs.retain(|d| d.0 > r.tolerance);
+            devs.sort_by(|a, b| b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal));
+            let mut seen = HashSet::new();
+            let new: Vec<_> = devs.into_iter()
+                .filter_map(|d| d.1)
+                .filter(|(uv, _)| seen.insert((uv.x.to_bits(), uv.y.to_bits())))
+                .take(r.max_points - added)
+                .collect();
+            for (uv, pos) in &new {
+                pts.push((uv.x, uv.y));
+                mesh.verts.push(mesh::Vertex {
+                    pos: *pos,
+                    norm: surf.normal(*pos, *uv),
+                    color: DVec3::new(0.0, 0.0, 0.0),
+                });
+            }
+            passes += 1;
+            added += new.len();
+            stats.refinement.points += new.len();
+
+            // If the new points break triangulation, keep the previous pass
+            let next = run_cdt(&mut pts, &mut mesh.verts[v_start..], &edges,
+                               bonus_points, face);
+            if !matches!(next, Ok(Ok(_))) {
+                stats.refinement.over_budget += 1;
+                stats.refinement.max_deviation =
+                    stats.refinement.max_deviation.max(max);
+                break;
             }
+            result = next;
         }
-    });
+    }
     match result {
         Ok(Ok(t)) => {
             for (a, b, c) in t.triangles() {
                 let a = (a + offset) as u32;
                 let b = (b + offset) as u32;
                 let c = (c + offset) as u32;
-                mesh.triangles.push(Triangle { verts:
-                    if face.same_sense {
+                mesh.triangles.push(Triangle {
+                    verts: if face.same_sense {
                         U32Vec3::new(a, b, c)
                     } else {
                         U32Vec3::new(a, c, b)
-                    }
+                    },
+                    face: f.0,
                 });
             }
         },
@@ -479,10 +734,16 @@ fn advanced_face(s: &StepFile, f: AdvancedFace, mesh: &mut Mesh,
             error!("Got error while triangulating {}: {:?}",
                    face.face_geometry.0, e);
             stats.num_errors += 1;
+            stats.diagnostics.push(Diagnostic::new(Severity::Error, f.0,
+                    format!("triangulation failed: {:?}", e))
+                .with_points(loops.iter().flatten()));
         },
         Err(e) => {
             error!("Got panic while triangulating {}: {:?}",
                    face.face_geometry.0, e);
+            stats.diagnostics.push(Diagnostic::new(Severity::Panic, f.0,
+                    panic_message(&*e))
+                .with_points(loops.iter().flatten()));
             if SAVE_PANIC_SVGS {
                 let filename = format!("panic{}.svg", face.face_geometry.0);
                 cdt::save_debug_panic(&pts, &edges, &filename)
@@ -500,6 +761,62 @@ fn advanced_face(s: &StepFile, f: AdvancedFace, mesh: &mut Mesh,
     Ok(())
 }
 
+type CdtResult = std::thread::Result<Result<cdt::Triangulation, cdt::Error>>;
+
+/// Triangulates a set of points and fixed edges, catching panics.
+///
+/// If a Steiner point (at or after `bonus_points`) lands on a fixed edge,
+/// it's moved onto the first point and the triangulation is retried.  The
+/// triangulation may then use either index for that position, so on
+/// success, the point is also moved in `pts` and `verts` (which are
+/// parallel).
+fn run_cdt(pts: &mut [(f64, f64)], verts: &mut [mesh::Vertex],
+           edges: &[(usize, usize)], bonus_points: usize, face: &AdvancedFace_)
+    -> CdtResult
+{
+    let input: &[(f64, f64)] = pts;
+    let result = std::panic::catch_unwind(|| {
+        profile_scope!("cdt");
+        // TODO: this is only needed because we use pts below to save a debug
+        // SVG if this panics.  Once we're confident in never panicking, we
+        // can remove this.
+        let mut pts = input.to_vec();
+        let mut dropped = Vec::new();
+        loop {
+            let mut t = match cdt::Triangulation::new_with_edges(&pts, edges) {
+                Err(e) => break Err(e),
+                Ok(t) => t,
+            };
+            match t.run() {
+                Ok(()) => break Ok((t, dropped)),
+                // If triangulation failed due to a Steiner point on a fixed
+                // edge, then reassign that point to pts[0] (so it will be
+                // ignored as a duplicate)
+                Err(cdt::Error::PointOnFixedEdge(p)) if p >= bonus_points => {
+                    pts[p] = pts[0];
+                    dropped.push(p);
+                    continue;
+                },
+                Err(e) => {
+                    if SAVE_DEBUG_SVGS {
+                        let filename = format!("err{}.svg", face.face_geometry.0);
+                        t.save_debug_svg(&filename)
+                            .expect("Could not save debug SVG");
+                    }
+                    break Err(e)
+                },
+            }
+        }
+    });
+    result.map(|r| r.map(|(t, dropped)| {
+        for p in dropped {
+            pts[p] = pts[0];
+            verts[p] = verts[0];
+        }
+        t
+    }))
+}
+
 fn get_surface(s: &StepFile, surf: ap214::Surface) -> Result<Surface, Error> {
     match &s[surf] {
         Entity::CylindricalSurface(c) => {
@@ -519,7 +836,8 @@ fn get_surface(s: &StepFile, surf: ap214::Surface) -> Result<Surface, Error> {
         // We treat cones like planes, since that's a valid mapping into 2D
         Entity::ConicalSurface(c) => {
             let (location, axis, ref_direction) = axis2_placement_3d(s, c.position);
-            Ok(Surface::new_cone(axis, ref_direction, location, c.semi_angle.0))
+            Ok(Surface::new_cone(axis, ref_direction, location, c.radius.0,
+                                 c.semi_angle.0))
         },
         Entity::SphericalSurface(c) => {
             // We'll ignore axis and ref_direction in favor of building an
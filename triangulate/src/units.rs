@@ -0,0 +1,259 @@
+//! Units of measure, as assigned by representation contexts.
+//!
+//! Each shape representation's context lists its units in a
+//! `GLOBAL_UNIT_ASSIGNED_CONTEXT`, either as SI units (e.g.
+//! `SI_UNIT(.MILLI.,.METRE.)`) or as conversion-based units defined by a
+//! measure in another unit (e.g. an `INCH` of `LENGTH_MEASURE(25.4)`
+//! millimetres).  The parser drops empty parts of complex instances, so a
+//! unit's kind is worked out from its SI name or its conversion factor
+//! rather than from its `LENGTH_UNIT()` or `PLANE_ANGLE_UNIT()` part.
+use std::collections::HashMap;
+
+use step::{ap214::*, ap214::Entity, step_file::StepFile};
+
+/// Size of a millimetre in metres, for use as a target length unit
+pub const MILLIMETRE: f64 = 0.001;
+/// Size of a metre in metres, for use as a target length unit
+pub const METRE: f64 = 1.0;
+
+/// Conversion-based units which are defined in terms of each other more
+/// deeply than this are treated as unknown, which also breaks cycles
+const MAX_DEPTH: usize = 8;
+
+/// The quantity measured by a unit
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Quantity {
+    Length,
+    PlaneAngle,
+}
+
+/// Units of a representation context, each given as its size in SI units
+/// (metres or radians).  A unit is `None` if the context doesn't assign
+/// one, or if it couldn't be resolved.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Units {
+    pub length: Option<f64>,
+    pub plane_angle: Option<f64>,
+}
+
+impl Units {
+    /// Returns the factor which converts lengths from these units into the
+    /// given length unit (as its size in metres)
+    pub fn length_scale(&self, target: f64) -> Option<f64> {
+        self.length.map(|f| f / target)
+    }
+}
+
+fn prefix_factor(p: &SiPrefix) -> Option<f64> {
+    use SiPrefix::*;
+    Some(match p {
+        Exa => 1e18,
+        Peta => 1e15,
+        Tera => 1e12,
+        Giga => 1e9,
+        Mega => 1e6,
+        Kilo => 1e3,
+        Hecto => 1e2,
+        Deca => 1e1,
+        Deci => 1e-1,
+        Centi => 1e-2,
+        Milli => 1e-3,
+        Micro => 1e-6,
+        Nano => 1e-9,
+        Pico => 1e-12,
+        Femto => 1e-15,
+        Atto => 1e-18,
+        Unknown(_) => return None,
+    })
+}
+
+fn measure_value(v: &MeasureValue) -> Option<f64> {
+    match v {
+        MeasureValue::LengthMeasure(m) => Some(m.0),
+        MeasureValue::PositiveLengthMeasure(m) => Some(m.0.0.0),
+        MeasureValue::NonNegativeLengthMeasure(m) => Some(m.0.0),
+        MeasureValue::PlaneAngleMeasure(m) => Some(m.0),
+        MeasureValue::PositivePlaneAngleMeasure(m) => Some(m.0.0),
+        MeasureValue::RatioMeasure(m) => Some(m.0),
+        MeasureValue::PositiveRatioMeasure(m) => Some(m.0.0),
+        MeasureValue::ParameterValue(m) => Some(m.0),
+        _ => None,
+    }
+}
+
+/// Resolves a unit (by entity ID) into the quantity which it measures and
+/// its size in SI units, or `None` if it isn't a length or plane angle unit
+/// which can be resolved
+pub fn unit_factor(s: &StepFile, unit: usize) -> Option<(Quantity, f64)> {
+    unit_factor_inner(s, unit, 0)
+}
+
+fn unit_factor_inner(s: &StepFile, unit: usize, depth: usize)
+    -> Option<(Quantity, f64)>
+{
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let e = s.entities().get(unit)?;
+    if let Some(u) = e.downcast::<SiUnit_>() {
+        let quantity = match u.name {
+            SiUnitName::Metre | SiUnitName::Unknown("METER") => Quantity::Length,
+            SiUnitName::Radian => Quantity::PlaneAngle,
+            _ => return None,
+        };
+        let scale = match u.prefix.get() {
+            Some(p) => prefix_factor(p)?,
+            None => 1.0,
+        };
+        return Some((quantity, scale));
+    }
+
+    // A conversion-based unit is a multiple of another unit
+    let u = e.downcast::<ConversionBasedUnit_>()?;
+    let m = s.entities().get(u.conversion_factor.0)?;
+    let (value, base) = if let Some(m) = m.downcast::<LengthMeasureWithUnit_>() {
+        (&m.value_component, m.unit_component)
+    } else if let Some(m) = m.downcast::<PlaneAngleMeasureWithUnit_>() {
+        (&m.value_component, m.unit_component)
+    } else {
+        let m = m.downcast::<MeasureWithUnit_>()?;
+        (&m.value_component, m.unit_component)
+    };
+    let (quantity, scale) = unit_factor_inner(s, base.0, depth + 1)?;
+    Some((quantity, measure_value(value)? * scale))
+        .filter(|(_, f)| f.is_finite() && *f > 0.0)
+}
+
+/// Finds the units assigned by a representation context (by entity ID).
+/// If a context assigns several units of the same kind, the first one
+/// which can be resolved is used.
+pub fn context_units(s: &StepFile, context: usize) -> Units {
+    let mut out = Units::default();
+    let ctx = match s.entities().get(context)
+        .and_then(|e| e.downcast::<GlobalUnitAssignedContext_>())
+    {
+        Some(c) => c,
+        None => return out,
+    };
+    for u in &ctx.units {
+        match unit_factor(s, u.0) {
+            Some((Quantity::Length, f)) =>
+                { out.length.get_or_insert(f); },
+            Some((Quantity::PlaneAngle, f)) =>
+                { out.plane_angle.get_or_insert(f); },
+            None => (),
+        }
+    }
+    out
+}
+
+/// Returns the items and context of a shape representation
+pub(crate) fn representation<'a>(e: &'a Entity<'a>)
+    -> Option<(&'a [RepresentationItem<'a>], usize)>
+{
+    if let Some(r) = e.downcast::<AdvancedBrepShapeRepresentation_>() {
+        Some((&r.items[..], r.context_of_items.0))
+    } else if let Some(r) = e.downcast::<ManifoldSurfaceShapeRepresentation_>() {
+        Some((&r.items[..], r.context_of_items.0))
+    } else if let Some(r) = e.downcast::<FacetedBrepShapeRepresentation_>() {
+        Some((&r.items[..], r.context_of_items.0))
+    } else {
+        e.downcast::<ShapeRepresentation_>()
+            .map(|r| (&r.items[..], r.context_of_items.0))
+    }
+}
+
+/// Finds the units of a shape representation (by entity ID), from its
+/// context.  Returns the default (no units) if it isn't a shape
+/// representation.
+pub fn representation_units(s: &StepFile, rep: usize) -> Units {
+    s.entities().get(rep)
+        .and_then(representation)
+        .map(|(_, ctx)| context_units(s, ctx))
+        .unwrap_or_default()
+}
+
+/// Returns the length unit (in metres) of each representation item, by
+/// entity ID, for items in a shape representation whose context has one
+pub(crate) fn item_length_units(s: &StepFile) -> HashMap<usize, f64> {
+    let mut contexts = HashMap::new();
+    let mut out = HashMap::new();
+    for e in s.entities() {
+        if let Some((items, ctx)) = representation(e) {
+            let length = *contexts.entry(ctx)
+                .or_insert_with(|| context_units(s, ctx).length);
+            if let Some(f) = length {
+                for i in items {
+                    out.entry(i.0).or_insert(f);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        params::MeshParams,
+        triangulate::{triangulate, triangulate_with_params},
+    };
+
+    /// A context in inches (defined in millimetres) and degrees
+    const DATA: &[u8] = b"ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+#2=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4),#1);
+#3=(CONVERSION_BASED_UNIT('INCH',#2)LENGTH_UNIT()NAMED_UNIT(*));
+#4=(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.));
+#5=PLANE_ANGLE_MEASURE_WITH_UNIT(PLANE_ANGLE_MEASURE(0.0174532925),#4);
+#6=(CONVERSION_BASED_UNIT('DEGREE',#5)NAMED_UNIT(*)PLANE_ANGLE_UNIT());
+#7=(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT());
+#10=(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNIT_ASSIGNED_CONTEXT((#7,#6,#3))REPRESENTATION_CONTEXT('',''));
+#11=CARTESIAN_POINT('',(0.,0.,0.));
+#12=SHAPE_REPRESENTATION('',(#11),#10);
+ENDSEC;
+END-ISO-10303-21;";
+
+    #[test]
+    fn test_units() {
+        let flat = StepFile::strip_flatten(DATA);
+        let s = StepFile::parse(&flat);
+        assert_eq!(unit_factor(&s, 1), Some((Quantity::Length, 0.001)));
+        let (q, inch) = unit_factor(&s, 3).unwrap();
+        assert_eq!(q, Quantity::Length);
+        assert!((inch - 0.0254).abs() < 1e-12);
+        assert_eq!(unit_factor(&s, 4), Some((Quantity::PlaneAngle, 1.0)));
+        assert_eq!(unit_factor(&s, 7), None);
+        assert_eq!(unit_factor(&s, 11), None);
+
+        let units = representation_units(&s, 12);
+        assert_eq!(units, context_units(&s, 10));
+        assert!((units.plane_angle.unwrap() - 0.0174532925).abs() < 1e-12);
+        assert!((units.length_scale(MILLIMETRE).unwrap() - 25.4).abs() < 1e-9);
+        assert_eq!(representation_units(&s, 11), Units::default());
+    }
+
+    #[test]
+    fn test_length_unit() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"),
+                           "/../examples/cube_hole.step");
+        let flat = StepFile::strip_flatten(&std::fs::read(path).unwrap());
+        let s = StepFile::parse(&flat);
+        let (mesh, _) = triangulate(&s);
+
+        // The model is in metres
+        let params = MeshParams {
+            length_unit: Some(MILLIMETRE), ..Default::default()
+        };
+        let (scaled, _) = triangulate_with_params(&s, None, &params, &mut ());
+        assert_eq!(mesh.verts.len(), scaled.verts.len());
+        for (a, b) in mesh.verts.iter().zip(&scaled.verts) {
+            assert!((a.pos * 1000.0 - b.pos).norm() < 1e-9);
+            assert_eq!(a.norm, b.norm);
+        }
+    }
+}
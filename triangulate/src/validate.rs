@@ -0,0 +1,203 @@
+use log::warn;
+use nalgebra_glm::DVec3;
+
+use step::validation::{ValidationProperty, ValidationValue};
+use crate::mesh::Mesh;
+
+/// A validation property compared against the same value measured from the
+/// triangulated mesh
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    /// ID of the property's `PROPERTY_DEFINITION`
+    pub id: usize,
+    pub product: Option<String>,
+    pub expected: ValidationValue,
+    pub measured: ValidationValue,
+    /// Relative error for volumes and areas; for centroids, the distance
+    /// between them as a fraction of the bounding box diagonal
+    pub deviation: f64,
+}
+
+/// Measures each validation property on the mesh and compares it with the
+/// stored value.
+///
+/// A property which names its solids is measured on the first instance of
+/// each of them; otherwise, it's measured on the whole mesh.  Centroids
+/// depend on placement, so they're only compared when the measurement covers
+/// the whole mesh.  Properties whose solids weren't triangulated are skipped.
+///
+/// Units aren't converted, so this assumes that the properties use the same
+/// length unit as the geometry (which is the usual practice).
+pub fn compare_properties(mesh: &Mesh, props: &[ValidationProperty])
+    -> Vec<Comparison>
+{
+    let mut out = Vec::new();
+    for p in props {
+        let tris: Vec<usize> = if p.solids.is_empty() {
+            (0..mesh.triangles.len()).collect()
+        } else {
+            let mut tris = Vec::new();
+            for s in &p.solids {
+                match mesh.parts.iter().find(|part| part.id == *s) {
+                    Some(part) => tris.extend(part.triangles.clone()),
+                    None => {
+                        tris.clear();
+                        break;
+                    },
+                }
+            }
+            tris
+        };
+        if tris.is_empty() {
+            warn!("Skipping validation property #{}: its solids weren't \
+                   triangulated", p.id);
+            continue;
+        }
+        let whole = tris.len() == mesh.triangles.len();
+
+        let m = measure(mesh, &tris);
+        let (measured, deviation) = match p.value {
+            ValidationValue::Volume(v) =>
+                (ValidationValue::Volume(m.volume), relative(m.volume, v)),
+            ValidationValue::Area(a) =>
+                (ValidationValue::Area(m.area), relative(m.area, a)),
+            ValidationValue::Centroid(c) if whole => {
+                let d = (m.centroid - DVec3::new(c[0], c[1], c[2])).norm();
+                let c = [m.centroid.x, m.centroid.y, m.centroid.z];
+                (ValidationValue::Centroid(c), d / m.diagonal.max(f64::EPSILON))
+            },
+            ValidationValue::Centroid(_) => continue,
+        };
+        out.push(Comparison {
+            id: p.id,
+            product: p.product.clone(),
+            expected: p.value,
+            measured,
+            deviation,
+        });
+    }
+    out
+}
+
+fn relative(measured: f64, expected: f64) -> f64 {
+    let d = (measured - expected).abs();
+    if expected == 0.0 { d } else { d / expected.abs() }
+}
+
+struct Measurement {
+    volume: f64,
+    area: f64,
+    centroid: DVec3,
+    diagonal: f64,
+}
+
+/// Measures a set of triangles, in model coordinates.  The volume is only
+/// meaningful if they form closed shells; if they don't enclose any volume,
+/// the centroid is weighted by area instead.
+fn measure(mesh: &Mesh, tris: &[usize]) -> Measurement {
+    let mut volume = 0.0;
+    let mut area = 0.0;
+    let mut vc = DVec3::zeros();
+    let mut ac = DVec3::zeros();
+    let mut min = DVec3::repeat(f64::INFINITY);
+    let mut max = DVec3::repeat(f64::NEG_INFINITY);
+    for t in tris {
+        let t = &mesh.triangles[*t];
+        let p = |j: usize| mesh.verts[t.verts[j] as usize].pos + mesh.offset;
+        let (a, b, c) = (p(0), p(1), p(2));
+        for v in &[a, b, c] {
+            min = min.inf(v);
+            max = max.sup(v);
+        }
+
+        // Signed volume of the tetrahedron to the origin
+        let v = a.dot(&b.cross(&c)) / 6.0;
+        volume += v;
+        vc += (a + b + c) * v / 4.0;
+
+        let s = (b - a).cross(&(c - a)).norm() / 2.0;
+        area += s;
+        ac += (a + b + c) * s / 3.0;
+    }
+    let centroid = if volume.abs() > f64::EPSILON {
+        vc / volume
+    } else if area > 0.0 {
+        ac / area
+    } else {
+        DVec3::zeros()
+    };
+    Measurement {
+        volume: volume.abs(), area, centroid, diagonal: (max - min).norm(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Part, Triangle, Vertex};
+    use nalgebra_glm::U32Vec3;
+
+    /// A unit cube with a corner at `origin`, wound outwards
+    fn cube(mesh: &mut Mesh, id: usize, origin: DVec3) {
+        let v0 = mesh.verts.len() as u32;
+        for i in 0..8 {
+            let p = DVec3::new((i & 1) as f64, ((i >> 1) & 1) as f64,
+                               ((i >> 2) & 1) as f64);
+            mesh.verts.push(Vertex {
+                pos: p + origin, norm: DVec3::zeros(), color: DVec3::zeros(),
+            });
+        }
+        let start = mesh.triangles.len();
+        let quads = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4],
+                     [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]];
+        for q in &quads {
+            for t in &[[q[0], q[1], q[2]], [q[0], q[2], q[3]]] {
+                mesh.triangles.push(Triangle {
+                    verts: U32Vec3::new(v0 + t[0], v0 + t[1], v0 + t[2]),
+                    face: 0,
+                });
+            }
+        }
+        let triangles = start..mesh.triangles.len();
+        mesh.parts.push(Part {
+            id, bounds: mesh.bounds(triangles.clone()), triangles,
+            opacity: 1.0, metadata: Default::default(),
+        });
+    }
+
+    fn prop(solids: &[usize], value: ValidationValue) -> ValidationProperty {
+        ValidationProperty {
+            id: 1, product: None, solids: solids.to_vec(), value,
+        }
+    }
+
+    #[test]
+    fn test_compare_properties() {
+        let mut mesh = Mesh::default();
+        cube(&mut mesh, 10, DVec3::zeros());
+        cube(&mut mesh, 20, DVec3::new(3.0, 0.0, 0.0));
+        mesh.recenter();
+
+        let c = compare_properties(&mesh, &[
+            prop(&[], ValidationValue::Volume(2.0)),
+            prop(&[10], ValidationValue::Area(5.0)),
+            prop(&[], ValidationValue::Centroid([2.5, 0.5, 0.5])),
+            prop(&[20], ValidationValue::Centroid([3.5, 0.5, 0.5])),
+            prop(&[30], ValidationValue::Volume(1.0)),
+        ]);
+        assert_eq!(c.len(), 3);
+        assert_eq!(c[0].measured, ValidationValue::Volume(2.0));
+        assert!(c[0].deviation < 1e-12);
+        assert_eq!(c[1].measured, ValidationValue::Area(6.0));
+        assert!((c[1].deviation - 0.2).abs() < 1e-12);
+        match c[2].measured {
+            ValidationValue::Centroid(p) => {
+                assert!((p[0] - 2.0).abs() < 1e-12);
+                assert!((p[1] - 0.5).abs() < 1e-12);
+            },
+            v => panic!("Unexpected value {:?}", v),
+        }
+        // 0.5 off, with a bounding box diagonal of sqrt(18)
+        assert!((c[2].deviation - 0.5 / 18f64.sqrt()).abs() < 1e-12);
+    }
+}
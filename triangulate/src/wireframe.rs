@@ -0,0 +1,117 @@
+use std::convert::TryInto;
+use std::io::Write;
+use nalgebra_glm::{DVec3, U32Vec2};
+
+use crate::{export::ExportOptions, mesh::glb};
+
+/// A single line segment, indexing into [`Wireframe::verts`]
+#[derive(Copy, Clone, Debug)]
+pub struct Segment {
+    pub verts: U32Vec2,
+}
+
+/// Tessellated edge curves, without any faces
+#[derive(Default)]
+pub struct Wireframe {
+    pub verts: Vec<DVec3>,
+    pub segments: Vec<Segment>,
+}
+
+impl Wireframe {
+    // Combine two wireframes with an associative binary operator, same as
+    // `Mesh::combine`
+    pub fn combine(mut a: Self, b: Self) -> Self {
+        let dv = a.verts.len().try_into().expect("too many vertices");
+        a.verts.extend(b.verts);
+        a.segments.extend(b.segments.into_iter()
+            .map(|s| Segment { verts: s.verts.add_scalar(dv) }));
+        a
+    }
+
+    /// Appends a polyline, as a chain of segments
+    pub fn push_polyline(&mut self, pts: &[DVec3]) {
+        let start: u32 = self.verts.len().try_into().expect("too many vertices");
+        self.verts.extend_from_slice(pts);
+        for i in 1..pts.len() as u32 {
+            self.segments.push(Segment {
+                verts: U32Vec2::new(start + i - 1, start + i)
+            });
+        }
+    }
+
+    /// Encodes the wireframe as binary glTF (`.glb`), with every segment in
+    /// a single `LINES` primitive
+    pub fn to_glb(&self) -> Vec<u8> {
+        if self.segments.is_empty() {
+            let json = concat!(
+                r#"{"asset":{"version":"2.0","generator":"foxtrot"},"#,
+                r#""scene":0,"scenes":[{"nodes":[]}]}"#);
+            return glb(json.to_owned(), &[]);
+        }
+        let mut bin: Vec<u8> = Vec::new();
+        let mut lo = [f32::INFINITY; 3];
+        let mut hi = [f32::NEG_INFINITY; 3];
+        for v in &self.verts {
+            for (j, c) in v.iter().enumerate() {
+                // Accessor bounds must match the stored (f32) values
+                let c = *c as f32;
+                lo[j] = lo[j].min(c);
+                hi[j] = hi[j].max(c);
+                bin.extend(&c.to_le_bytes());
+            }
+        }
+        let positions = bin.len();
+        for s in &self.segments {
+            for v in s.verts.iter() {
+                bin.extend(&v.to_le_bytes());
+            }
+        }
+
+        let json = format!(concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"foxtrot"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+            r#""meshes":[{{"primitives":[{{"attributes":{{"POSITION":0}},"#,
+            r#""indices":1,"mode":1}}]}}],"#,
+            r#""buffers":[{{"byteLength":{}}}],"#,
+            r#""bufferViews":[{{"buffer":0,"byteOffset":0,"byteLength":{},"#,
+            r#""target":34962}},{{"buffer":0,"byteOffset":{},"#,
+            r#""byteLength":{},"target":34963}}],"#,
+            r#""accessors":[{{"bufferView":0,"componentType":5126,"#,
+            r#""count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}},"#,
+            r#"{{"bufferView":1,"componentType":5125,"count":{},"#,
+            r#""type":"SCALAR"}}]}}"#),
+            bin.len(), positions, positions, bin.len() - positions,
+            self.verts.len(), lo[0], lo[1], lo[2], hi[0], hi[1], hi[2],
+            self.segments.len() * 2);
+        glb(json, &bin)
+    }
+
+    /// Writes the wireframe as binary glTF.  Returns the number of bytes
+    /// written.
+    pub fn save_glb(&self, filename: &str) -> std::io::Result<usize> {
+        let out = self.to_glb();
+        std::fs::write(filename, &out)?;
+        Ok(out.len())
+    }
+
+    /// Writes the wireframe as an OBJ file made of `l` (line) elements
+    pub fn save_obj(&self, filename: &str) -> std::io::Result<()> {
+        self.save_obj_with(filename, &ExportOptions::default())
+    }
+
+    /// Writes the wireframe as an OBJ file with the given index base.  Lines
+    /// have no winding, so that option is ignored.
+    pub fn save_obj_with(&self, filename: &str, options: &ExportOptions)
+        -> std::io::Result<()>
+    {
+        let mut out = std::io::BufWriter::new(std::fs::File::create(filename)?);
+        for v in &self.verts {
+            writeln!(out, "v {} {} {}", v.x, v.y, v.z)?;
+        }
+        for s in &self.segments {
+            writeln!(out, "l {} {}", options.index(s.verts.x),
+                     options.index(s.verts.y))?;
+        }
+        out.flush()
+    }
+}
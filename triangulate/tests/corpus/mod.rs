@@ -0,0 +1,556 @@
+//! Generator for small synthetic STEP files, which lets us test the whole
+//! pipeline (parsing, transform stack, and triangulation) without checking
+//! third-party models into the repository.
+//!
+//! Every function in this module returns the complete text of a STEP file.
+use std::collections::HashMap;
+
+use step::writer::{Param, StepWriter};
+
+type P = [f64; 3];
+
+const X: P = [1.0, 0.0, 0.0];
+const Z: P = [0.0, 0.0, 1.0];
+const NEG_Z: P = [0.0, 0.0, -1.0];
+
+fn sub(a: P, b: P) -> P {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+fn cross(a: P, b: P) -> P {
+    [a[1] * b[2] - a[2] * b[1],
+     a[2] * b[0] - a[0] * b[2],
+     a[0] * b[1] - a[1] * b[0]]
+}
+fn normalize(a: P) -> P {
+    let n = (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt();
+    [a[0] / n, a[1] / n, a[2] / n]
+}
+
+/// A part which has been added to a file, with its top-level
+/// `SHAPE_REPRESENTATION` and `PRODUCT_DEFINITION`
+#[derive(Copy, Clone)]
+pub struct Part {
+    pub shape_rep: usize,
+    pub product_def: usize,
+}
+
+/// Wrapper around a [`StepWriter`] which knows how to build B-rep topology,
+/// deduplicating vertices and edges so that each edge is shared by the two
+/// faces on either side of it.
+pub struct Builder {
+    w: StepWriter,
+    vertices: HashMap<[u64; 3], usize>,
+    /// Map from (start vertex, end vertex, is_arc) to `EDGE_CURVE`, where
+    /// the vertex pair is sorted and the original start is stored alongside
+    edges: HashMap<(usize, usize, bool), (usize, usize)>,
+    context: usize,
+    app_context: usize,
+    origin: usize,
+}
+
+impl Builder {
+    pub fn new(name: &str) -> Self {
+        let mut w = StepWriter::new(name);
+        let app_context = w.add("APPLICATION_CONTEXT", &[
+            Param::str("core data for automotive mechanical design processes")]);
+        w.add("APPLICATION_PROTOCOL_DEFINITION", &[
+            Param::str("international standard"),
+            Param::str("automotive_design"),
+            Param::Integer(2010),
+            Param::Ref(app_context)]);
+
+        let metre = w.add_complex(&[
+            ("LENGTH_UNIT", &[]),
+            ("NAMED_UNIT", &[Param::Derived]),
+            ("SI_UNIT", &[Param::Unset, Param::enum_("METRE")])]);
+        let radian = w.add_complex(&[
+            ("NAMED_UNIT", &[Param::Derived]),
+            ("PLANE_ANGLE_UNIT", &[]),
+            ("SI_UNIT", &[Param::Unset, Param::enum_("RADIAN")])]);
+        let steradian = w.add_complex(&[
+            ("NAMED_UNIT", &[Param::Derived]),
+            ("SI_UNIT", &[Param::Unset, Param::enum_("STERADIAN")]),
+            ("SOLID_ANGLE_UNIT", &[])]);
+        let uncertainty = w.add("UNCERTAINTY_MEASURE_WITH_UNIT", &[
+            Param::typed("LENGTH_MEASURE", Param::Real(1e-6)),
+            Param::Ref(metre),
+            Param::str("DISTANCE_ACCURACY_VALUE"),
+            Param::str("")]);
+        let context = w.add_complex(&[
+            ("GEOMETRIC_REPRESENTATION_CONTEXT", &[Param::Integer(3)]),
+            ("GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT",
+                &[Param::refs(&[uncertainty])]),
+            ("GLOBAL_UNIT_ASSIGNED_CONTEXT",
+                &[Param::refs(&[metre, radian, steradian])]),
+            ("REPRESENTATION_CONTEXT", &[Param::str(""), Param::str("3D")])]);
+
+        let mut out = Self {
+            w,
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+            context,
+            app_context,
+            origin: 0,
+        };
+        out.origin = out.axis([0.0; 3], Z, X);
+        out
+    }
+
+    /// Adds a raw entity to the underlying writer
+    pub fn add(&mut self, name: &str, params: &[Param]) -> usize {
+        self.w.add(name, params)
+    }
+
+    pub fn point(&mut self, p: P) -> usize {
+        self.w.add("CARTESIAN_POINT", &[Param::str(""), Param::reals(&p)])
+    }
+
+    pub fn direction(&mut self, d: P) -> usize {
+        self.w.add("DIRECTION", &[Param::str(""), Param::reals(&d)])
+    }
+
+    pub fn axis(&mut self, location: P, axis: P, ref_direction: P) -> usize {
+        let p = self.point(location);
+        let a = self.direction(axis);
+        let r = self.direction(ref_direction);
+        self.w.add("AXIS2_PLACEMENT_3D", &[
+            Param::str(""), Param::Ref(p), Param::Ref(a), Param::Ref(r)])
+    }
+
+    /// Returns a `VERTEX_POINT` at the given position, reusing an existing
+    /// vertex if one has already been created there
+    pub fn vertex(&mut self, p: P) -> usize {
+        let key = [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()];
+        if let Some(v) = self.vertices.get(&key) {
+            return *v;
+        }
+        let pt = self.point(p);
+        let v = self.w.add("VERTEX_POINT", &[Param::str(""), Param::Ref(pt)]);
+        self.vertices.insert(key, v);
+        v
+    }
+
+    fn oriented_edge(&mut self, edge: usize, orientation: bool) -> usize {
+        self.w.add("ORIENTED_EDGE", &[
+            Param::str(""), Param::Derived, Param::Derived,
+            Param::Ref(edge), Param::bool(orientation)])
+    }
+
+    /// Looks up or builds an edge between two vertices, returning an
+    /// `ORIENTED_EDGE` which runs from `a` to `b`.
+    fn edge<F>(&mut self, a: P, b: P, is_arc: bool, curve: F) -> usize
+        where F: FnOnce(&mut Self) -> usize
+    {
+        let (va, vb) = (self.vertex(a), self.vertex(b));
+        let key = (va.min(vb), va.max(vb), is_arc);
+        let (edge, start) = match self.edges.get(&key) {
+            Some(e) => *e,
+            None => {
+                let c = curve(self);
+                let e = self.w.add("EDGE_CURVE", &[
+                    Param::str(""), Param::Ref(va), Param::Ref(vb),
+                    Param::Ref(c), Param::bool(true)]);
+                self.edges.insert(key, (e, va));
+                (e, va)
+            },
+        };
+        self.oriented_edge(edge, start == va)
+    }
+
+    /// Straight edge from `a` to `b`
+    pub fn line(&mut self, a: P, b: P) -> usize {
+        self.edge(a, b, false, |s| {
+            let p = s.point(a);
+            let d = sub(b, a);
+            let mag = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+            let dir = s.direction(normalize(d));
+            let v = s.w.add("VECTOR", &[
+                Param::str(""), Param::Ref(dir), Param::Real(mag)]);
+            s.w.add("LINE", &[Param::str(""), Param::Ref(p), Param::Ref(v)])
+        })
+    }
+
+    /// Circular arc from `a` to `b`, running counter-clockwise around `axis`
+    pub fn arc(&mut self, center: P, axis: P, a: P, b: P) -> usize {
+        self.edge(a, b, true, |s| {
+            let d = sub(a, center);
+            let r = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+            let placement = s.axis(center, axis, normalize(d));
+            s.w.add("CIRCLE", &[
+                Param::str(""), Param::Ref(placement), Param::Real(r)])
+        })
+    }
+
+    /// Closed circle of radius `r` around `axis`, with a single vertex on
+    /// its `ref_direction`.  Returns the `EDGE_CURVE`, which the caller must
+    /// orient with [`Builder::use_edge`].
+    pub fn circle(&mut self, center: P, axis: P, ref_direction: P, r: f64)
+        -> usize
+    {
+        let placement = self.axis(center, axis, ref_direction);
+        let c = self.w.add("CIRCLE", &[
+            Param::str(""), Param::Ref(placement), Param::Real(r)]);
+        let v = self.vertex([center[0] + ref_direction[0] * r,
+                             center[1] + ref_direction[1] * r,
+                             center[2] + ref_direction[2] * r]);
+        self.w.add("EDGE_CURVE", &[
+            Param::str(""), Param::Ref(v), Param::Ref(v),
+            Param::Ref(c), Param::bool(true)])
+    }
+
+    /// Uses a (closed) `EDGE_CURVE` with the given orientation
+    pub fn use_edge(&mut self, edge: usize, orientation: bool) -> usize {
+        self.oriented_edge(edge, orientation)
+    }
+
+    /// Builds an `ADVANCED_FACE` from a surface and a set of loops, each of
+    /// which is a list of `ORIENTED_EDGE`.  The first loop is the outer bound.
+    pub fn face(&mut self, surface: usize, loops: &[Vec<usize>],
+                same_sense: bool) -> usize
+    {
+        let mut bounds = Vec::new();
+        for (i, edges) in loops.iter().enumerate() {
+            let l = self.w.add("EDGE_LOOP", &[
+                Param::str(""), Param::refs(edges)]);
+            bounds.push(self.w.add(
+                if i == 0 { "FACE_OUTER_BOUND" } else { "FACE_BOUND" },
+                &[Param::str(""), Param::Ref(l), Param::bool(true)]));
+        }
+        self.w.add("ADVANCED_FACE", &[
+            Param::str(""), Param::refs(&bounds),
+            Param::Ref(surface), Param::bool(same_sense)])
+    }
+
+    /// Builds a `PLANE` at `origin`, facing along `normal`
+    pub fn plane(&mut self, origin: P, normal: P, ref_direction: P) -> usize {
+        let a = self.axis(origin, normal, ref_direction);
+        self.w.add("PLANE", &[Param::str(""), Param::Ref(a)])
+    }
+
+    /// Builds a planar polygonal face, with vertices listed counter-clockwise
+    /// when seen from outside the solid
+    pub fn polygon(&mut self, pts: &[P]) -> usize {
+        let normal = normalize(cross(sub(pts[1], pts[0]), sub(pts[2], pts[1])));
+        let surf = self.plane(pts[0], normal, normalize(sub(pts[1], pts[0])));
+        let edges: Vec<usize> = (0..pts.len())
+            .map(|i| self.line(pts[i], pts[(i + 1) % pts.len()]))
+            .collect();
+        self.face(surf, &[edges], true)
+    }
+
+    /// Wraps a set of faces into a `MANIFOLD_SOLID_BREP`
+    pub fn solid(&mut self, name: &str, faces: &[usize]) -> usize {
+        let shell = self.w.add("CLOSED_SHELL", &[
+            Param::str(""), Param::refs(faces)]);
+        self.w.add("MANIFOLD_SOLID_BREP", &[
+            Param::str(name), Param::Ref(shell)])
+    }
+
+    /// Builds the product structure and shape representations for a set of
+    /// representation items (e.g. solids).  Passing no items builds an
+    /// assembly, which is populated with [`Builder::place`].
+    pub fn part(&mut self, name: &str, items: &[usize]) -> Part {
+        let product_context = self.w.add("PRODUCT_CONTEXT", &[
+            Param::str(""), Param::Ref(self.app_context),
+            Param::str("mechanical")]);
+        let product = self.w.add("PRODUCT", &[
+            Param::str(name), Param::str(name), Param::str(""),
+            Param::refs(&[product_context])]);
+        let formation = self.w.add("PRODUCT_DEFINITION_FORMATION", &[
+            Param::str(""), Param::str(""), Param::Ref(product)]);
+        let def_context = self.w.add("PRODUCT_DEFINITION_CONTEXT", &[
+            Param::str("part definition"), Param::Ref(self.app_context),
+            Param::str("design")]);
+        let product_def = self.w.add("PRODUCT_DEFINITION", &[
+            Param::str("design"), Param::str(""),
+            Param::Ref(formation), Param::Ref(def_context)]);
+        let def_shape = self.w.add("PRODUCT_DEFINITION_SHAPE", &[
+            Param::str(""), Param::str(""), Param::Ref(product_def)]);
+
+        let shape_rep = self.w.add("SHAPE_REPRESENTATION", &[
+            Param::str(name), Param::refs(&[self.origin]),
+            Param::Ref(self.context)]);
+        self.w.add("SHAPE_DEFINITION_REPRESENTATION", &[
+            Param::Ref(def_shape), Param::Ref(shape_rep)]);
+
+        if !items.is_empty() {
+            let mut brep_items = items.to_vec();
+            brep_items.push(self.origin);
+            let brep = self.w.add("ADVANCED_BREP_SHAPE_REPRESENTATION", &[
+                Param::str(name), Param::refs(&brep_items),
+                Param::Ref(self.context)]);
+            self.w.add("SHAPE_REPRESENTATION_RELATIONSHIP", &[
+                Param::str(""), Param::str(""),
+                Param::Ref(shape_rep), Param::Ref(brep)]);
+        }
+        Part { shape_rep, product_def }
+    }
+
+    /// Places `child` within the assembly `parent`, translated by `offset`
+    pub fn place(&mut self, parent: Part, child: Part, offset: P) {
+        let target = self.axis(offset, Z, X);
+        let transform = self.w.add("ITEM_DEFINED_TRANSFORMATION", &[
+            Param::str(""), Param::str(""),
+            Param::Ref(self.origin), Param::Ref(target)]);
+        let rel = self.w.add_complex(&[
+            ("REPRESENTATION_RELATIONSHIP", &[
+                Param::str(""), Param::str(""),
+                Param::Ref(child.shape_rep), Param::Ref(parent.shape_rep)]),
+            ("REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION", &[
+                Param::Ref(transform)]),
+            ("SHAPE_REPRESENTATION_RELATIONSHIP", &[])]);
+        let usage = self.w.add("NEXT_ASSEMBLY_USAGE_OCCURRENCE", &[
+            Param::str(""), Param::str(""), Param::str(""),
+            Param::Ref(parent.product_def), Param::Ref(child.product_def),
+            Param::Unset]);
+        let def_shape = self.w.add("PRODUCT_DEFINITION_SHAPE", &[
+            Param::str("Placement"), Param::str(""), Param::Ref(usage)]);
+        self.w.add("CONTEXT_DEPENDENT_SHAPE_REPRESENTATION", &[
+            Param::Ref(rel), Param::Ref(def_shape)]);
+    }
+
+    /// Colors a representation item, optionally with a `transparency`
+    /// (from 0 for opaque to 1 for invisible)
+    pub fn style(&mut self, item: usize, rgb: P, transparency: Option<f64>) {
+        let colour = self.w.add("COLOUR_RGB", &[
+            Param::str(""), Param::Real(rgb[0]), Param::Real(rgb[1]),
+            Param::Real(rgb[2])]);
+        let fill_colour = self.w.add("FILL_AREA_STYLE_COLOUR", &[
+            Param::str(""), Param::Ref(colour)]);
+        let fill = self.w.add("FILL_AREA_STYLE", &[
+            Param::str(""), Param::refs(&[fill_colour])]);
+        let mut styles = vec![
+            self.w.add("SURFACE_STYLE_FILL_AREA", &[Param::Ref(fill)])];
+        if let Some(t) = transparency {
+            let t = self.w.add("SURFACE_STYLE_TRANSPARENT", &[Param::Real(t)]);
+            styles.push(self.w.add("SURFACE_STYLE_RENDERING_WITH_PROPERTIES", &[
+                Param::enum_("NORMAL_SHADING"), Param::Ref(colour),
+                Param::refs(&[t])]));
+        }
+        let side = self.w.add("SURFACE_SIDE_STYLE", &[
+            Param::str(""), Param::refs(&styles)]);
+        let usage = self.w.add("SURFACE_STYLE_USAGE", &[
+            Param::enum_("BOTH"), Param::Ref(side)]);
+        let assignment = self.w.add("PRESENTATION_STYLE_ASSIGNMENT", &[
+            Param::refs(&[usage])]);
+        let styled = self.w.add("STYLED_ITEM", &[
+            Param::str(""), Param::refs(&[assignment]), Param::Ref(item)]);
+        self.w.add("MECHANICAL_DESIGN_GEOMETRIC_PRESENTATION_REPRESENTATION", &[
+            Param::str(""), Param::refs(&[styled]), Param::Ref(self.context)]);
+    }
+
+    pub fn finish(&self) -> String {
+        self.w.to_string()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Axis-aligned box from the origin to `size`
+fn box_solid(b: &mut Builder, size: P) -> usize {
+    let [x, y, z] = size;
+    let faces = [
+        b.polygon(&[[0.0, 0.0, 0.0], [0.0, y, 0.0], [x, y, 0.0], [x, 0.0, 0.0]]),
+        b.polygon(&[[0.0, 0.0, z], [x, 0.0, z], [x, y, z], [0.0, y, z]]),
+        b.polygon(&[[0.0, 0.0, 0.0], [x, 0.0, 0.0], [x, 0.0, z], [0.0, 0.0, z]]),
+        b.polygon(&[[x, 0.0, 0.0], [x, y, 0.0], [x, y, z], [x, 0.0, z]]),
+        b.polygon(&[[x, y, 0.0], [0.0, y, 0.0], [0.0, y, z], [x, y, z]]),
+        b.polygon(&[[0.0, y, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, z], [0.0, y, z]]),
+    ];
+    b.solid("box", &faces)
+}
+
+/// Cylinder along +Z, with its base centered at the origin
+fn cylinder_solid(b: &mut Builder, r: f64, h: f64) -> usize {
+    let bottom = b.circle([0.0; 3], Z, X, r);
+    let top = b.circle([0.0, 0.0, h], Z, X, r);
+
+    let surf = b.plane([0.0; 3], NEG_Z, X);
+    let e = b.use_edge(bottom, false);
+    let f0 = b.face(surf, &[vec![e]], true);
+
+    let surf = b.plane([0.0, 0.0, h], Z, X);
+    let e = b.use_edge(top, true);
+    let f1 = b.face(surf, &[vec![e]], true);
+
+    let axis = b.axis([0.0; 3], Z, X);
+    let surf = b.add("CYLINDRICAL_SURFACE", &[
+        Param::str(""), Param::Ref(axis), Param::Real(r)]);
+    let lower = b.use_edge(bottom, true);
+    let upper = b.use_edge(top, false);
+    let f2 = b.face(surf, &[vec![lower], vec![upper]], true);
+
+    b.solid("cylinder", &[f0, f1, f2])
+}
+
+pub fn cube() -> String {
+    let mut b = Builder::new("box");
+    let solid = box_solid(&mut b, [1.0, 2.0, 3.0]);
+    b.part("box", &[solid]);
+    b.finish()
+}
+
+pub fn cylinder() -> String {
+    let mut b = Builder::new("cylinder");
+    let solid = cylinder_solid(&mut b, 1.0, 2.0);
+    b.part("cylinder", &[solid]);
+    b.finish()
+}
+
+/// Sphere built from two hemispheres, which meet at an equatorial circle
+pub fn sphere() -> String {
+    let mut b = Builder::new("sphere");
+    let r = 1.0;
+    let equator = b.circle([0.0; 3], Z, X, r);
+    let axis = b.axis([0.0; 3], Z, X);
+    let surf = b.add("SPHERICAL_SURFACE", &[
+        Param::str(""), Param::Ref(axis), Param::Real(r)]);
+
+    let e = b.use_edge(equator, true);
+    let north = b.face(surf, &[vec![e]], true);
+    let e = b.use_edge(equator, false);
+    let south = b.face(surf, &[vec![e]], true);
+
+    let solid = b.solid("sphere", &[north, south]);
+    b.part("sphere", &[solid]);
+    b.finish()
+}
+
+/// Unit sphere with its top sliced off at Z = 0.5, so the spherical face
+/// covers more than a hemisphere
+pub fn truncated_sphere() -> String {
+    let mut b = Builder::new("truncated_sphere");
+    let (r, h) = (1.0, 0.5);
+    let rim = b.circle([0.0, 0.0, h], Z, X, (r * r - h * h).sqrt());
+
+    let surf = b.plane([0.0, 0.0, h], Z, X);
+    let e = b.use_edge(rim, true);
+    let top = b.face(surf, &[vec![e]], true);
+
+    let axis = b.axis([0.0; 3], Z, X);
+    let surf = b.add("SPHERICAL_SURFACE", &[
+        Param::str(""), Param::Ref(axis), Param::Real(r)]);
+    let e = b.use_edge(rim, false);
+    let body = b.face(surf, &[vec![e]], true);
+
+    let solid = b.solid("truncated_sphere", &[top, body]);
+    b.part("truncated_sphere", &[solid]);
+    b.finish()
+}
+
+/// Torus built from upper and lower halves, which meet at the inner and
+/// outer equatorial circles
+pub fn torus() -> String {
+    let mut b = Builder::new("torus");
+    let (major, minor) = (2.0, 0.5);
+    let outer = b.circle([0.0; 3], Z, X, major + minor);
+    let inner = b.circle([0.0; 3], Z, X, major - minor);
+    let axis = b.axis([0.0; 3], Z, X);
+    let surf = b.add("TOROIDAL_SURFACE", &[
+        Param::str(""), Param::Ref(axis),
+        Param::Real(major), Param::Real(minor)]);
+
+    let (o, i) = (b.use_edge(outer, true), b.use_edge(inner, false));
+    let upper = b.face(surf, &[vec![o], vec![i]], true);
+    let (o, i) = (b.use_edge(outer, false), b.use_edge(inner, true));
+    let lower = b.face(surf, &[vec![o], vec![i]], true);
+
+    let solid = b.solid("torus", &[upper, lower]);
+    b.part("torus", &[solid]);
+    b.finish()
+}
+
+/// 2 x 2 x 1 block with one vertical edge rounded by a quarter-cylinder
+pub fn filleted_block() -> String {
+    let mut b = Builder::new("filleted_block");
+    let r = 0.5;
+    let (w, h) = (2.0, 1.0);
+    let c = w - r;
+    let at = |x: f64, y: f64, z: f64| [x, y, z];
+
+    // Bottom and top faces, which include the fillet's arcs
+    let mut faces = vec![];
+    for &(z, up) in &[(0.0, false), (h, true)] {
+        let mut pts = [
+            at(0.0, 0.0, z), at(w, 0.0, z), at(w, c, z),
+            at(c, w, z), at(0.0, w, z)];
+        if !up {
+            pts.reverse();
+        }
+        let center = at(c, c, z);
+        let edges: Vec<usize> = (0..pts.len())
+            .map(|i| {
+                let (a, p) = (pts[i], pts[(i + 1) % pts.len()]);
+                if a[0] == w && p[0] == c {
+                    b.arc(center, Z, a, p)
+                } else if a[0] == c && p[0] == w {
+                    b.arc(center, NEG_Z, a, p)
+                } else {
+                    b.line(a, p)
+                }
+            })
+            .collect();
+        let surf = b.plane(at(0.0, 0.0, z), if up { Z } else { NEG_Z }, X);
+        faces.push(b.face(surf, &[edges], true));
+    }
+
+    // Flat sides
+    faces.push(b.polygon(&[
+        at(0.0, 0.0, 0.0), at(w, 0.0, 0.0), at(w, 0.0, h), at(0.0, 0.0, h)]));
+    faces.push(b.polygon(&[
+        at(w, 0.0, 0.0), at(w, c, 0.0), at(w, c, h), at(w, 0.0, h)]));
+    faces.push(b.polygon(&[
+        at(c, w, 0.0), at(0.0, w, 0.0), at(0.0, w, h), at(c, w, h)]));
+    faces.push(b.polygon(&[
+        at(0.0, w, 0.0), at(0.0, 0.0, 0.0), at(0.0, 0.0, h), at(0.0, w, h)]));
+
+    // Fillet
+    let axis = b.axis(at(c, c, 0.0), Z, X);
+    let surf = b.add("CYLINDRICAL_SURFACE", &[
+        Param::str(""), Param::Ref(axis), Param::Real(r)]);
+    let edges = vec![
+        b.arc(at(c, c, 0.0), Z, at(w, c, 0.0), at(c, w, 0.0)),
+        b.line(at(c, w, 0.0), at(c, w, h)),
+        b.arc(at(c, c, h), NEG_Z, at(c, w, h), at(w, c, h)),
+        b.line(at(w, c, h), at(w, c, 0.0)),
+    ];
+    faces.push(b.face(surf, &[edges], true));
+
+    let solid = b.solid("filleted_block", &faces);
+    b.part("filleted_block", &[solid]);
+    b.finish()
+}
+
+/// Assembly of two parts (a box and a cylinder standing beside it), which
+/// are positioned with `ITEM_DEFINED_TRANSFORMATION`s
+pub fn assembly() -> String {
+    let mut b = Builder::new("assembly");
+    let root = b.part("assembly", &[]);
+
+    let solid = box_solid(&mut b, [1.0, 1.0, 1.0]);
+    let part = b.part("box", &[solid]);
+    b.place(root, part, [-2.0, 0.0, 0.0]);
+
+    let solid = cylinder_solid(&mut b, 0.5, 2.0);
+    let part = b.part("cylinder", &[solid]);
+    b.place(root, part, [2.0, 0.0, 0.0]);
+    b.finish()
+}
+
+/// The same assembly as [`assembly`], with a red box and a half-transparent
+/// blue cylinder
+pub fn styled_assembly() -> String {
+    let mut b = Builder::new("styled_assembly");
+    let root = b.part("assembly", &[]);
+
+    let solid = box_solid(&mut b, [1.0, 1.0, 1.0]);
+    b.style(solid, [1.0, 0.0, 0.0], None);
+    let part = b.part("box", &[solid]);
+    b.place(root, part, [-2.0, 0.0, 0.0]);
+
+    let solid = cylinder_solid(&mut b, 0.5, 2.0);
+    b.style(solid, [0.0, 0.0, 1.0], Some(0.5));
+    let part = b.part("cylinder", &[solid]);
+    b.place(root, part, [2.0, 0.0, 0.0]);
+    b.finish()
+}
@@ -0,0 +1,322 @@
+mod corpus;
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::time::Duration;
+
+use step::{ap214::Entity, step_file::StepFile};
+use triangulate::{
+    assembly::Assembly, audit::audit_normals, diagnostic::Severity, estimate::estimate, mesh::Mesh, metrics, metrics::Metrics, stats::Stats,
+    triangulate::{triangulate, triangulate_solids, triangulate_with_metrics,
+                  wireframe},
+};
+
+/// Runs a generated file through the full pipeline, checking that every
+/// entity parses and every face triangulates cleanly (with triangles wound to
+/// match their vertex normals)
+fn run(text: &str, num_faces: usize) -> Mesh {
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let step = StepFile::parse(&flat);
+    for (i, e) in step.iter_by_id() {
+        assert!(!matches!(e, Entity::_FailedToParse),
+                "Failed to parse entity #{}", i);
+    }
+    let (mesh, stats) = triangulate(&step);
+    let Stats { num_faces: f, num_errors, num_panics, .. } = stats;
+    assert_eq!(f, num_faces);
+    assert_eq!(num_errors, 0);
+    assert_eq!(num_panics, 0);
+    assert!(!mesh.triangles.is_empty());
+    let issues = audit_normals(&mesh, std::f64::consts::FRAC_PI_2);
+    assert!(issues.is_empty(), "Inconsistent normals: {:?}", issues);
+    mesh
+}
+
+/// Returns the (min, max) corners of the mesh's bounding box.  Only vertices
+/// which are used by a triangle are counted, since Steiner points outside of
+/// a face are left in the vertex list.
+fn bounds(mesh: &Mesh) -> ([f64; 3], [f64; 3]) {
+    let mut lo = [f64::INFINITY; 3];
+    let mut hi = [-f64::INFINITY; 3];
+    for v in mesh.triangles.iter().flat_map(|t| t.verts.iter()) {
+        let v = &mesh.verts[*v as usize];
+        for i in 0..3 {
+            lo[i] = lo[i].min(v.pos[i]);
+            hi[i] = hi[i].max(v.pos[i]);
+        }
+    }
+    (lo, hi)
+}
+
+/// Checks that two points are within `eps` of each other.  Curved edges are
+/// sampled as polylines, so their bounds are only approximate.
+fn assert_close(a: [f64; 3], b: [f64; 3], eps: f64) {
+    for i in 0..3 {
+        assert!((a[i] - b[i]).abs() < eps, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn smoke_box() {
+    let mesh = run(&corpus::cube(), 6);
+    assert_eq!(mesh.triangles.len(), 12);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [0.0, 0.0, 0.0], 1e-9);
+    assert_close(hi, [1.0, 2.0, 3.0], 1e-9);
+}
+
+#[test]
+fn smoke_cylinder() {
+    let mesh = run(&corpus::cylinder(), 3);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [-1.0, -1.0, 0.0], 1e-2);
+    assert_close(hi, [1.0, 1.0, 2.0], 1e-2);
+}
+
+#[test]
+fn smoke_filleted_block() {
+    let mesh = run(&corpus::filleted_block(), 7);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [0.0, 0.0, 0.0], 1e-2);
+    assert_close(hi, [2.0, 2.0, 1.0], 1e-2);
+}
+
+#[test]
+fn smoke_assembly() {
+    // The box is moved to [-2, -1] on the X axis, and the cylinder (with
+    // radius 0.5) is centered at X = 2
+    let mesh = run(&corpus::assembly(), 9);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [-2.0, -0.5, 0.0], 1e-2);
+    assert_close(hi, [2.5, 1.0, 2.0], 1e-2);
+}
+
+#[test]
+fn smoke_assembly_to_step() {
+    // Baking the tree and writing it back out keeps every part in place
+    let text = corpus::assembly();
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let mut a = Assembly::new(&StepFile::parse(&flat));
+    a.bake();
+    let mesh = run(&a.to_step(&flat, "baked"), 9);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [-2.0, -0.5, 0.0], 1e-2);
+    assert_close(hi, [2.5, 1.0, 2.0], 1e-2);
+}
+
+#[test]
+fn smoke_styles() {
+    let mesh = run(&corpus::styled_assembly(), 9);
+    assert_eq!(mesh.parts.len(), 2);
+    assert_eq!(mesh.parts[0].triangles.start, 0);
+    assert_eq!(mesh.parts[0].triangles.end, mesh.parts[1].triangles.start);
+    assert_eq!(mesh.parts[1].triangles.end, mesh.triangles.len());
+
+    // Each part is colored from its style, and the cylinder is translucent
+    let color = |p: &triangulate::mesh::Part|
+        mesh.verts[mesh.triangles[p.triangles.start].verts[0] as usize].color;
+    let (cube, cylinder) = if mesh.parts[0].opacity == 1.0 {
+        (&mesh.parts[0], &mesh.parts[1])
+    } else {
+        (&mesh.parts[1], &mesh.parts[0])
+    };
+    assert_eq!(cube.opacity, 1.0);
+    assert_close(color(cube).into(), [1.0, 0.0, 0.0], 1e-9);
+    assert!((cylinder.opacity - 0.5).abs() < 1e-9);
+    assert_close(color(cylinder).into(), [0.0, 0.0, 1.0], 1e-9);
+}
+
+#[test]
+fn smoke_solids_subset() {
+    let text = corpus::assembly();
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let step = StepFile::parse(&flat);
+    let solids: Vec<usize> = estimate(&step).solids.iter()
+        .map(|s| s.id)
+        .collect();
+    assert_eq!(solids.len(), 2);
+
+    // Meshing each solid on its own gives the same faces as meshing both,
+    // and each solid is still moved into place
+    let (full, _) = triangulate(&step);
+    let (a, stats_a) = triangulate_solids(&step, &solids[..1]);
+    let (b, stats_b) = triangulate_solids(&step, &solids[1..]);
+    assert_eq!(stats_a.num_faces + stats_b.num_faces, 9);
+    assert_eq!(a.triangles.len() + b.triangles.len(), full.triangles.len());
+    assert_eq!(bounds(&a).0[0].min(bounds(&b).0[0]), bounds(&full).0[0]);
+
+    // Unknown IDs are skipped
+    let (empty, stats) = triangulate_solids(&step, &[usize::MAX - 2]);
+    assert!(empty.triangles.is_empty());
+    assert_eq!(stats.num_faces, 0);
+}
+
+#[test]
+fn smoke_wireframe() {
+    // Each of the box's 12 edges is a single line segment
+    let text = corpus::cube();
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let (wire, stats) = wireframe(&StepFile::parse(&flat));
+    assert_eq!(stats.num_faces, 6);
+    assert_eq!(stats.num_errors, 0);
+    assert_eq!(wire.segments.len(), 12);
+
+    // Saved as glTF, the segments are a single LINES primitive, with 3 floats
+    // per vertex and 2 indices per segment in the binary chunk
+    let glb = wire.to_glb();
+    let word = |i: usize| u32::from_le_bytes(glb[i..i + 4].try_into().unwrap());
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(word(8) as usize, glb.len());
+    let json_len = word(12) as usize;
+    let json = std::str::from_utf8(&glb[20..20 + json_len]).unwrap();
+    assert!(json.contains(r#""indices":1,"mode":1"#), "{}", json);
+    assert!(json.contains(r#""min":[0,0,0],"max":[1,2,3]"#), "{}", json);
+    assert_eq!(word(20 + json_len) as usize,
+               wire.verts.len() * 12 + wire.segments.len() * 8);
+
+    // Breaking one face's reference to an edge skips it there, but the
+    // edge is still drawn for the neighbouring face
+    let broken = text.replacen("ORIENTED_EDGE('',*,*,#",
+                               "ORIENTED_EDGE('',*,*,#99999", 1);
+    let flat = StepFile::strip_flatten(broken.as_bytes());
+    let (wire, stats) = wireframe(&StepFile::parse(&flat));
+    assert_eq!((stats.num_errors, stats.num_panics), (1, 0));
+    assert_eq!(stats.diagnostics.len(), 1);
+    assert_eq!(wire.segments.len(), 12);
+
+    // In the assembly, the cylinder's circles are tessellated into many
+    // segments, and each part is moved into place
+    let text = corpus::assembly();
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let (wire, stats) = wireframe(&StepFile::parse(&flat));
+    assert_eq!(stats.num_errors, 0);
+    assert!(wire.segments.len() > 12 + 2);
+    let min_x = wire.verts.iter().map(|v| v.x).fold(f64::INFINITY, f64::min);
+    assert_eq!(min_x, -2.0);
+}
+
+#[test]
+fn smoke_sphere() {
+    // Interior points come from a coarse grid of Steiner points, which
+    // misses the poles, so the bounds are very approximate
+    let mesh = run(&corpus::sphere(), 2);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [-1.0, -1.0, -1.0], 0.1);
+    assert_close(hi, [1.0, 1.0, 1.0], 0.1);
+}
+
+#[test]
+fn smoke_truncated_sphere() {
+    // The spherical face covers more than a hemisphere, so it's split
+    let mesh = run(&corpus::truncated_sphere(), 2);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [-1.0, -1.0, -1.0], 0.1);
+    assert_close(hi, [1.0, 1.0, 0.5], 1e-2);
+}
+
+#[test]
+fn smoke_torus() {
+    // Each face wraps all the way around the axis, so it's split in half
+    let mesh = run(&corpus::torus(), 2);
+    let (lo, hi) = bounds(&mesh);
+    assert_close(lo, [-2.5, -2.5, -0.5], 0.1);
+    assert_close(hi, [2.5, 2.5, 0.5], 0.1);
+}
+
+#[test]
+fn smoke_diagnostics() {
+    // Break one of the box's planes, so that its face can't be triangulated
+    let text = corpus::assembly().replacen("=PLANE(", "=NOT_A_SURFACE(", 1);
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let (_mesh, stats) = triangulate(&StepFile::parse(&flat));
+    assert_eq!(stats.diagnostics.len(), 1);
+    let d = &stats.diagnostics[0];
+    assert_eq!(d.severity, Severity::Error);
+
+    // The diagnostic is located on the box, which is moved to X = [-2, -1]
+    let (lo, hi) = d.bounds.expect("Missing bounds");
+    assert!(lo.x >= -2.0 - 1e-9 && hi.x <= -1.0 + 1e-9, "{:?}", d.bounds);
+
+    let (_mesh, stats) = triangulate(&StepFile::parse(
+        &StepFile::strip_flatten(corpus::assembly().as_bytes())));
+    assert!(stats.diagnostics.is_empty());
+}
+
+#[derive(Default)]
+struct Recorder {
+    counters: HashMap<&'static str, u64>,
+    timers: Vec<&'static str>,
+}
+
+impl Metrics for Recorder {
+    fn counter(&mut self, name: &'static str, value: u64) {
+        *self.counters.entry(name).or_default() += value;
+    }
+    fn timer(&mut self, name: &'static str, _elapsed: Duration) {
+        self.timers.push(name);
+    }
+}
+
+#[test]
+fn smoke_metrics() {
+    let text = corpus::cube();
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let step = StepFile::parse(&flat);
+    let mut m = Recorder::default();
+    let (mesh, _stats) = triangulate_with_metrics(&step, &mut m);
+    // Every instance in the file, without the unused IDs around them
+    let instances = step::raw::records(&flat).len() as u64;
+    assert!(instances < step.entities().len() as u64);
+    assert_eq!(m.counters[metrics::ENTITIES], instances);
+    assert_eq!(m.counters[metrics::FACES], 6);
+    assert_eq!(m.counters[metrics::ERRORS], 0);
+    assert_eq!(m.counters[metrics::TRIANGLES], mesh.triangles.len() as u64);
+    assert_eq!(m.timers, vec![metrics::TRIANGULATE_TIME]);
+}
+
+#[test]
+fn smoke_estimate() {
+    // The estimate is exact for planar faces with straight edges, and within
+    // 25% for the curved models
+    for (text, exact) in [(corpus::cube(), true),
+                          (corpus::cylinder(), false),
+                          (corpus::filleted_block(), false),
+                          (corpus::assembly(), false),
+                          (corpus::sphere(), false),
+                          (corpus::torus(), false)].iter()
+    {
+        let flat = StepFile::strip_flatten(text.as_bytes());
+        let step = StepFile::parse(&flat);
+        let est = estimate(&step);
+        let (mesh, stats) = triangulate(&step);
+        let faces: usize = est.solids.iter().map(|s| s.faces).sum();
+        assert_eq!(faces, stats.num_faces);
+        let (est, actual) = (est.triangles(), mesh.triangles.len());
+        if *exact {
+            assert_eq!(est, actual);
+        } else {
+            assert!((est as f64 - actual as f64).abs() < 0.25 * actual as f64,
+                    "Estimated {} triangles, got {}", est, actual);
+        }
+    }
+
+    // A surface curve which refers back to itself falls back to the default
+    // estimate instead of looping forever
+    let text = corpus::cube();
+    let line = text.find("=LINE(").unwrap();
+    let start = text[..line].rfind('#').unwrap();
+    let end = line + text[line..].find(';').unwrap();
+    let plane = text.find("=PLANE(").unwrap();
+    let plane = &text[text[..plane].rfind('#').unwrap()..plane];
+    let text = format!("{}{}=SURFACE_CURVE('',{},({}),.CURVE_3D.){}",
+                       &text[..start], &text[start..line], &text[start..line],
+                       plane, &text[end..]);
+    let flat = StepFile::strip_flatten(text.as_bytes());
+    let step = StepFile::parse(&flat);
+    let est = estimate(&step);
+    assert_eq!(est.solids.iter().map(|s| s.faces).sum::<usize>(), 6);
+    assert!(step.iter_by_id()
+        .any(|(_, e)| matches!(e, Entity::SurfaceCurve(_))));
+    assert!(est.triangles() > 12);
+}
+